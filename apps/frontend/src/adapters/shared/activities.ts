@@ -1,6 +1,8 @@
 // Activity Commands
 import type {
   Activity,
+  ActivityBulkEditRequest,
+  ActivityBulkEditResult,
   ActivityBulkMutationRequest,
   ActivityBulkMutationResult,
   ActivityCreate,
@@ -8,8 +10,12 @@ import type {
   ActivitySearchResponse,
   ActivityUpdate,
   ActivityImport,
+  BrokerParserInfo,
   ImportActivitiesResult,
   ImportMappingData,
+  ImportRunChange,
+  ImportRunRevertResult,
+  ProbableDuplicate,
 } from "@/lib/types";
 
 import { invoke, logger } from "./platform";
@@ -122,6 +128,20 @@ export const saveActivities = async (
   }
 };
 
+/**
+ * Edit every activity matching a query. With `dryRun` the edit is only previewed.
+ */
+export const bulkEditActivities = async (
+  request: ActivityBulkEditRequest,
+): Promise<ActivityBulkEditResult> => {
+  try {
+    return await invoke<ActivityBulkEditResult>("bulk_edit_activities", { request });
+  } catch (err) {
+    logger.error("Error bulk editing activities.");
+    throw err;
+  }
+};
+
 export const deleteActivity = async (activityId: string): Promise<Activity> => {
   try {
     return await invoke<Activity>("delete_activity", { activityId });
@@ -221,3 +241,96 @@ export const checkExistingDuplicates = async (
     throw err;
   }
 };
+
+/**
+ * List the broker statement formats the statement importer recognises.
+ */
+export const listBrokerParsers = async (): Promise<BrokerParserInfo[]> => {
+  try {
+    return await invoke<BrokerParserInfo[]>("list_broker_parsers");
+  } catch (err) {
+    logger.error("Error listing broker statement formats.");
+    throw err;
+  }
+};
+
+/**
+ * List the activities an import run inserted or updated.
+ */
+export const getImportRunChanges = async (importRunId: string): Promise<ImportRunChange[]> => {
+  try {
+    return await invoke<ImportRunChange[]>("get_import_run_changes", { importRunId });
+  } catch (err) {
+    logger.error("Error fetching import run changes.");
+    throw err;
+  }
+};
+
+export const approveImportRunChanges = async (
+  importRunId: string,
+  changeIds: string[],
+): Promise<number> => {
+  try {
+    return await invoke<number>("approve_import_run_changes", { importRunId, changeIds });
+  } catch (err) {
+    logger.error("Error approving import run changes.");
+    throw err;
+  }
+};
+
+/**
+ * Revert individual changes of an import run.
+ */
+export const rejectImportRunChanges = async (
+  importRunId: string,
+  changeIds: string[],
+): Promise<ImportRunRevertResult> => {
+  try {
+    return await invoke<ImportRunRevertResult>("reject_import_run_changes", {
+      importRunId,
+      changeIds,
+    });
+  } catch (err) {
+    logger.error("Error rejecting import run changes.");
+    throw err;
+  }
+};
+
+/**
+ * Undo everything an import run changed.
+ */
+export const rollbackImportRun = async (importRunId: string): Promise<ImportRunRevertResult> => {
+  try {
+    return await invoke<ImportRunRevertResult>("rollback_import_run", { importRunId });
+  } catch (err) {
+    logger.error("Error rolling back import run.");
+    throw err;
+  }
+};
+
+/**
+ * Scan existing activities for probable duplicates imported from different sources.
+ */
+export const findProbableDuplicates = async (accountId?: string): Promise<ProbableDuplicate[]> => {
+  try {
+    return await invoke<ProbableDuplicate[]>("find_probable_duplicates", { accountId });
+  } catch (err) {
+    logger.error("Error finding probable duplicate activities.");
+    throw err;
+  }
+};
+
+/**
+ * Merge a probable duplicate into the activity kept, deleting the duplicate.
+ */
+export const mergeDuplicateActivities = async (
+  keepId: string,
+  duplicateId: string,
+): Promise<Activity> => {
+  try {
+    return await invoke<Activity>("merge_duplicate_activities", { keepId, duplicateId });
+  } catch (err) {
+    logger.error("Error merging duplicate activities.");
+    throw err;
+  }
+};
@@ -6,6 +6,12 @@ import type {
   UpdateValuationRequest,
   UpdateValuationResponse,
   LinkLiabilityRequest,
+  LoanTerms,
+  AmortizationSchedule,
+  ValuationModel,
+  PropertyCashFlow,
+  NewPropertyCashFlow,
+  RentalPerformance,
   NetWorthResponse,
   NetWorthHistoryPoint,
 } from "@/lib/types";
@@ -65,6 +71,71 @@ export const unlinkLiability = async (liabilityId: string): Promise<void> => {
   return invoke<void>("unlink_liability", { liabilityId });
 };
 
+/**
+ * Attach loan terms to a liability so its balance follows an amortization schedule.
+ * Pass null to clear the terms and fall back to manual valuations.
+ */
+export const setLoanTerms = async (
+  liabilityId: string,
+  terms: LoanTerms | null,
+): Promise<AmortizationSchedule | null> => {
+  return invoke<AmortizationSchedule | null>("set_loan_terms", { liabilityId, terms });
+};
+
+/**
+ * Get the amortization schedule of a liability, or null if it has no loan terms
+ */
+export const getLoanSchedule = async (
+  liabilityId: string,
+): Promise<AmortizationSchedule | null> => {
+  return invoke<AmortizationSchedule | null>("get_loan_schedule", { liabilityId });
+};
+
+/**
+ * Set how an alternative asset's value is projected between appraisals.
+ * Pass null to clear the model and use manual valuations only.
+ */
+export const setValuationModel = async (
+  assetId: string,
+  model: ValuationModel | null,
+): Promise<void> => {
+  return invoke<void>("set_valuation_model", { assetId, model });
+};
+
+/**
+ * Record rental income, an operating expense or a mortgage payment on a property
+ */
+export const addPropertyCashFlow = async (
+  newFlow: NewPropertyCashFlow,
+): Promise<PropertyCashFlow> => {
+  return invoke<PropertyCashFlow>("add_property_cash_flow", { newFlow });
+};
+
+/**
+ * Delete a property cash-flow entry
+ */
+export const deletePropertyCashFlow = async (cashFlowId: string): Promise<void> => {
+  return invoke<void>("delete_property_cash_flow", { cashFlowId });
+};
+
+/**
+ * List the cash-flow entries of a property, oldest first
+ */
+export const getPropertyCashFlows = async (assetId: string): Promise<PropertyCashFlow[]> => {
+  return invoke<PropertyCashFlow[]>("get_property_cash_flows", { assetId });
+};
+
+/**
+ * Get NOI, cap rate, cash-on-cash return and equity of a property
+ * for the 12 months ending at asOf (ISO format: YYYY-MM-DD). Defaults to today.
+ */
+export const getRentalPerformance = async (
+  assetId: string,
+  asOf?: string,
+): Promise<RentalPerformance> => {
+  return invoke<RentalPerformance>("get_rental_performance", { assetId, asOf });
+};
+
 /**
  * Get the net worth calculation
  * @param date Optional date for as-of calculation (ISO format: YYYY-MM-DD). Defaults to today.
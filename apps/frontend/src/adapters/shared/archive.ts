@@ -0,0 +1,38 @@
+// Portfolio Archive Commands
+import type {
+  ArchiveExport,
+  ArchiveRestoreMode,
+  ArchiveRestoreSummary,
+  PortfolioArchive,
+} from "@/lib/types";
+
+import { invoke, logger } from "./platform";
+
+/**
+ * Single-file archive of the portfolio, encrypted when a passphrase is given
+ */
+export const createPortfolioArchive = async (passphrase?: string): Promise<ArchiveExport> => {
+  try {
+    return await invoke<ArchiveExport>("create_portfolio_archive", { passphrase });
+  } catch (error) {
+    logger.error("Error creating portfolio archive.");
+    throw error;
+  }
+};
+
+export const restorePortfolioArchive = async (
+  archive: PortfolioArchive,
+  mode: ArchiveRestoreMode,
+  passphrase?: string,
+): Promise<ArchiveRestoreSummary> => {
+  try {
+    return await invoke<ArchiveRestoreSummary>("restore_portfolio_archive", {
+      archive,
+      passphrase,
+      mode,
+    });
+  } catch (error) {
+    logger.error("Error restoring portfolio archive.");
+    throw error;
+  }
+};
@@ -0,0 +1,63 @@
+// Audit Log Commands
+import type { AuditEntityType, AuditLogEntry } from "@/lib/types";
+
+import { invoke, logger } from "./platform";
+
+/**
+ * Recorded changes of one activity, account or asset, newest first
+ */
+export const getEntityHistory = async (
+  entityType: AuditEntityType,
+  entityId: string,
+): Promise<AuditLogEntry[]> => {
+  try {
+    return await invoke<AuditLogEntry[]>("get_entity_history", { entityType, entityId });
+  } catch (error) {
+    logger.error("Error fetching entity history.");
+    throw error;
+  }
+};
+
+export const getRecentChanges = async (
+  entityType?: AuditEntityType,
+  limit?: number,
+): Promise<AuditLogEntry[]> => {
+  try {
+    return await invoke<AuditLogEntry[]>("get_recent_changes", { entityType, limit });
+  } catch (error) {
+    logger.error("Error fetching recent changes.");
+    throw error;
+  }
+};
+
+/**
+ * Stored version of an entity at a point in time, null if it did not exist then
+ */
+export const getEntityVersionAt = async (
+  entityType: AuditEntityType,
+  entityId: string,
+  at: string,
+): Promise<Record<string, unknown> | null> => {
+  try {
+    return await invoke<Record<string, unknown> | null>("get_entity_version_at", {
+      entityType,
+      entityId,
+      at,
+    });
+  } catch (error) {
+    logger.error("Error fetching entity version.");
+    throw error;
+  }
+};
+
+/**
+ * Restore the version left by an audit entry (for deletes, the version it removed)
+ */
+export const restoreAuditVersion = async (entryId: string): Promise<AuditLogEntry> => {
+  try {
+    return await invoke<AuditLogEntry>("restore_audit_version", { entryId });
+  } catch (error) {
+    logger.error("Error restoring version.");
+    throw error;
+  }
+};
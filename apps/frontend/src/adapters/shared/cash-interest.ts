@@ -0,0 +1,75 @@
+// Cash Interest Commands
+import type {
+  Account,
+  CashInterestAccrual,
+  CashInterestSettings,
+  CashYieldSummary,
+} from "@/lib/types";
+
+import { invoke, logger } from "./platform";
+
+export const getCashInterestSettings = async (
+  accountId: string,
+): Promise<CashInterestSettings | null> => {
+  try {
+    return await invoke<CashInterestSettings | null>("get_cash_interest_settings", { accountId });
+  } catch (error) {
+    logger.error("Error fetching cash interest settings.");
+    throw error;
+  }
+};
+
+/**
+ * Save an account's cash interest settings (pass null to remove them)
+ */
+export const setCashInterestSettings = async (
+  accountId: string,
+  settings: CashInterestSettings | null,
+): Promise<Account> => {
+  try {
+    return await invoke<Account>("set_cash_interest_settings", { accountId, settings });
+  } catch (error) {
+    logger.error("Error saving cash interest settings.");
+    throw error;
+  }
+};
+
+/**
+ * Interest accrued in the current payment period
+ * @param asOf - Optional date in YYYY-MM-DD format (defaults to today)
+ */
+export const getCashInterestAccrual = async (
+  accountId: string,
+  asOf?: string,
+): Promise<CashInterestAccrual> => {
+  try {
+    return await invoke<CashInterestAccrual>("get_cash_interest_accrual", { accountId, asOf });
+  } catch (error) {
+    logger.error("Error fetching cash interest accrual.");
+    throw error;
+  }
+};
+
+/**
+ * Configured and realized cash yields of all accounts, for comparing savings accounts
+ */
+export const getCashYieldComparison = async (asOf?: string): Promise<CashYieldSummary[]> => {
+  try {
+    return await invoke<CashYieldSummary[]>("get_cash_yield_comparison", { asOf });
+  } catch (error) {
+    logger.error("Error fetching cash yield comparison.");
+    throw error;
+  }
+};
+
+/**
+ * Create INTEREST drafts for payment dates that have passed
+ */
+export const generateCashInterestDrafts = async (asOf?: string): Promise<number> => {
+  try {
+    return await invoke<number>("generate_cash_interest_drafts", { asOf });
+  } catch (error) {
+    logger.error("Error generating cash interest drafts.");
+    throw error;
+  }
+};
@@ -0,0 +1,81 @@
+// Equity Grant Commands
+import type { EquityGrant, EquityGrantSummary, NewEquityGrant, VestEvent } from "@/lib/types";
+
+import { invoke, logger } from "./platform";
+
+export const getEquityGrants = async (): Promise<EquityGrant[]> => {
+  try {
+    return await invoke<EquityGrant[]>("get_equity_grants");
+  } catch (error) {
+    logger.error("Error fetching equity grants.");
+    throw error;
+  }
+};
+
+/**
+ * Get every grant with its vested/unvested position
+ * @param asOf Optional date (ISO format: YYYY-MM-DD). Defaults to today.
+ */
+export const getEquityGrantSummaries = async (asOf?: string): Promise<EquityGrantSummary[]> => {
+  try {
+    return await invoke<EquityGrantSummary[]>("get_equity_grant_summaries", { asOf });
+  } catch (error) {
+    logger.error("Error fetching equity grant summaries.");
+    throw error;
+  }
+};
+
+export const getVestingSchedule = async (grantId: string): Promise<VestEvent[]> => {
+  try {
+    return await invoke<VestEvent[]>("get_vesting_schedule", { grantId });
+  } catch (error) {
+    logger.error("Error fetching vesting schedule.");
+    throw error;
+  }
+};
+
+/**
+ * Create a grant. Vests that already happened are recorded as activities.
+ */
+export const createEquityGrant = async (grant: NewEquityGrant): Promise<EquityGrant> => {
+  try {
+    return await invoke<EquityGrant>("create_equity_grant", { grant });
+  } catch (error) {
+    logger.error("Error creating equity grant.");
+    throw error;
+  }
+};
+
+export const updateEquityGrant = async (grant: EquityGrant): Promise<EquityGrant> => {
+  try {
+    return await invoke<EquityGrant>("update_equity_grant", { grant });
+  } catch (error) {
+    logger.error("Error updating equity grant.");
+    throw error;
+  }
+};
+
+/**
+ * Delete a grant and the activities booked for its vests.
+ */
+export const deleteEquityGrant = async (grantId: string): Promise<void> => {
+  try {
+    await invoke<void>("delete_equity_grant", { grantId });
+  } catch (error) {
+    logger.error("Error deleting equity grant.");
+    throw error;
+  }
+};
+
+/**
+ * Record activities for vests that are due but not recorded yet
+ * @returns Number of activities created
+ */
+export const syncVestActivities = async (asOf?: string): Promise<number> => {
+  try {
+    return await invoke<number>("sync_vest_activities", { asOf });
+  } catch (error) {
+    logger.error("Error syncing vest activities.");
+    throw error;
+  }
+};
@@ -8,6 +8,9 @@ import type {
   MarketDataProviderInfo,
   ExchangeInfo,
   ResolvedQuote,
+  QuoteProvenance,
+  ConsensusConfig,
+  QuoteQualityConfig,
 } from "@/lib/types";
 import type { QuoteImport } from "@/lib/types/quote-import";
 import type { MarketDataProviderSetting } from "../types";
@@ -128,6 +131,59 @@ export const getQuoteHistory = async (symbol: string): Promise<Quote[]> => {
   }
 };
 
+export const getQuoteProvenance = async (
+  assetId: string,
+  startDate: string,
+  endDate: string,
+): Promise<QuoteProvenance[]> => {
+  try {
+    return await invoke<QuoteProvenance[]>("get_quote_provenance", { assetId, startDate, endDate });
+  } catch (error) {
+    logger.error(`Error fetching quote provenance for ${assetId}.`);
+    throw error;
+  }
+};
+
+export const getQuoteConsensusSettings = async (): Promise<ConsensusConfig> => {
+  try {
+    return await invoke<ConsensusConfig>("get_quote_consensus_settings");
+  } catch (error) {
+    logger.error("Error fetching quote consensus settings.");
+    throw error;
+  }
+};
+
+export const updateQuoteConsensusSettings = async (
+  config: ConsensusConfig,
+): Promise<ConsensusConfig> => {
+  try {
+    return await invoke<ConsensusConfig>("update_quote_consensus_settings", { config });
+  } catch (error) {
+    logger.error("Error updating quote consensus settings.");
+    throw error;
+  }
+};
+
+export const getQuoteQualitySettings = async (): Promise<QuoteQualityConfig> => {
+  try {
+    return await invoke<QuoteQualityConfig>("get_quote_quality_settings");
+  } catch (error) {
+    logger.error("Error fetching quote quality settings.");
+    throw error;
+  }
+};
+
+export const updateQuoteQualitySettings = async (
+  config: QuoteQualityConfig,
+): Promise<QuoteQualityConfig> => {
+  try {
+    return await invoke<QuoteQualityConfig>("update_quote_quality_settings", { config });
+  } catch (error) {
+    logger.error("Error updating quote quality settings.");
+    throw error;
+  }
+};
+
 export const getMarketDataProviders = async (): Promise<MarketDataProviderInfo[]> => {
   try {
     return await invoke<MarketDataProviderInfo[]>("get_market_data_providers");
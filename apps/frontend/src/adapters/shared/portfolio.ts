@@ -3,6 +3,7 @@ import type {
   Holding,
   AllocationHoldings,
   IncomeSummary,
+  RealizedFxGain,
   AccountValuation,
   PerformanceMetrics,
   PortfolioAllocations,
@@ -31,6 +32,23 @@ export const getIncomeSummary = async (): Promise<IncomeSummary[]> => {
   return invoke<IncomeSummary[]>("get_income_summary");
 };
 
+export const getRealizedFxGains = async (
+  accountId?: string,
+  startDate?: string,
+  endDate?: string,
+): Promise<RealizedFxGain[]> => {
+  try {
+    return await invoke<RealizedFxGain[]>("get_realized_fx_gains", {
+      accountId,
+      startDate,
+      endDate,
+    });
+  } catch (error) {
+    logger.error("Error fetching realized FX gains.");
+    throw error;
+  }
+};
+
 export const getHistoricalValuations = async (
   accountId?: string,
   startDate?: string,
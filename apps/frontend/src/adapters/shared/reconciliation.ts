@@ -0,0 +1,39 @@
+// Holdings Reconciliation Commands
+import type { ReconciliationReport } from "@/lib/types";
+
+import { invoke, logger } from "./platform";
+
+/**
+ * Compare broker-reported holdings with positions computed from activities
+ */
+export const getReconciliationReports = async (): Promise<ReconciliationReport[]> => {
+  try {
+    return await invoke<ReconciliationReport[]>("get_reconciliation_reports");
+  } catch (error) {
+    logger.error("Error fetching reconciliation reports.");
+    throw error;
+  }
+};
+
+export const getAccountReconciliation = async (
+  accountId: string,
+): Promise<ReconciliationReport | null> => {
+  try {
+    return await invoke<ReconciliationReport | null>("get_account_reconciliation", { accountId });
+  } catch (error) {
+    logger.error("Error fetching account reconciliation.");
+    throw error;
+  }
+};
+
+/**
+ * Create the draft activities proposed for an account's discrepancies
+ */
+export const createReconciliationDrafts = async (accountId: string): Promise<number> => {
+  try {
+    return await invoke<number>("create_reconciliation_drafts", { accountId });
+  } catch (error) {
+    logger.error("Error creating reconciliation drafts.");
+    throw error;
+  }
+};
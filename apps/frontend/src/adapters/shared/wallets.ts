@@ -0,0 +1,49 @@
+// Watch-only Wallet Commands
+import type { Account, WalletConfig, WalletSyncResult } from "@/lib/types";
+
+import { invoke, logger } from "./platform";
+
+export const getWalletConfig = async (accountId: string): Promise<WalletConfig | null> => {
+  try {
+    return await invoke<WalletConfig | null>("get_wallet_config", { accountId });
+  } catch (error) {
+    logger.error("Error fetching wallet config.");
+    throw error;
+  }
+};
+
+/**
+ * Link a watch-only wallet to a crypto account
+ */
+export const setWalletConfig = async (
+  accountId: string,
+  config: WalletConfig,
+): Promise<Account> => {
+  try {
+    return await invoke<Account>("set_wallet_config", { accountId, config });
+  } catch (error) {
+    logger.error("Error saving wallet config.");
+    throw error;
+  }
+};
+
+/**
+ * Pull new on-chain transfers of the account's wallet as activities
+ */
+export const syncWallet = async (accountId: string): Promise<WalletSyncResult> => {
+  try {
+    return await invoke<WalletSyncResult>("sync_wallet", { accountId });
+  } catch (error) {
+    logger.error("Error syncing wallet.");
+    throw error;
+  }
+};
+
+export const syncAllWallets = async (): Promise<WalletSyncResult[]> => {
+  try {
+    return await invoke<WalletSyncResult[]>("sync_all_wallets");
+  } catch (error) {
+    logger.error("Error syncing wallets.");
+    throw error;
+  }
+};
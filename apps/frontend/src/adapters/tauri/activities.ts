@@ -1,5 +1,5 @@
 // Tauri-specific activity commands
-import type { ParseConfig, ParsedCsvResult } from "@/lib/types";
+import type { BrokerStatement, ParseConfig, ParsedCsvResult } from "@/lib/types";
 import { invoke, logger } from "./core";
 
 /**
@@ -16,3 +16,22 @@ export const parseCsv = async (file: File, config: ParseConfig): Promise<ParsedC
     throw err;
   }
 };
+
+/**
+ * Detect the broker format of a statement (IBKR Flex XML, a supported CSV export or a
+ * PDF contract note) and parse it into import rows.
+ * Tauri implementation: reads file as ArrayBuffer and invokes parse_broker_statement command.
+ */
+export const parseBrokerStatement = async (
+  file: File,
+  accountId: string,
+): Promise<BrokerStatement> => {
+  try {
+    const buffer = await file.arrayBuffer();
+    const content = Array.from(new Uint8Array(buffer));
+    return await invoke<BrokerStatement>("parse_broker_statement", { accountId, content });
+  } catch (err) {
+    logger.error("Error parsing broker statement:", err);
+    throw err;
+  }
+};
@@ -71,17 +71,34 @@ export * from "../shared/accounts";
 
 // Activity Commands
 export * from "../shared/activities";
-export { parseCsv } from "./activities";
+export { parseCsv, parseBrokerStatement } from "./activities";
 
 // Portfolio Commands
 export * from "../shared/portfolio";
 
 // Market Data Commands
 export * from "../shared/market-data";
+export { bulkImportQuotes, exportMarketData } from "./market-data";
 
 // Goal Commands
 export * from "../shared/goals";
 
+// Equity Grant Commands
+export * from "../shared/equity-grants";
+export * from "../shared/wallets";
+
+// Cash Interest Commands
+export * from "../shared/cash-interest";
+
+// Holdings Reconciliation Commands
+export * from "../shared/reconciliation";
+
+// Audit Log Commands
+export * from "../shared/audit";
+
+// Portfolio Archive Commands
+export * from "../shared/archive";
+
 // Taxonomy Commands
 export * from "../shared/taxonomies";
 
@@ -0,0 +1,37 @@
+// Tauri-specific market data commands
+import type { BulkImportOptions, BulkImportSummary, QuoteExportRequest } from "@/lib/types";
+import { invoke, logger } from "./core";
+
+/**
+ * Bulk import quote history from a dump file, directory or zip archive.
+ * Tauri implementation: the backend reads the file directly from the given path,
+ * so large dumps never pass through the webview.
+ */
+export const bulkImportQuotes = async (
+  source: File | string,
+  options?: BulkImportOptions,
+): Promise<BulkImportSummary> => {
+  try {
+    if (typeof source !== "string") {
+      throw new Error("Bulk quote import on desktop expects a file or folder path");
+    }
+    return await invoke<BulkImportSummary>("bulk_import_quotes", { path: source, options });
+  } catch (err) {
+    logger.error("Error bulk importing quotes:", err);
+    throw err;
+  }
+};
+
+/**
+ * Export stored quotes and FX rates as CSV, JSON Lines or Parquet.
+ * Tauri implementation: the command returns the encoded file as a byte array.
+ */
+export const exportMarketData = async (request: QuoteExportRequest): Promise<Uint8Array> => {
+  try {
+    const bytes = await invoke<number[]>("export_market_data", { request });
+    return new Uint8Array(bytes);
+  } catch (err) {
+    logger.error("Error exporting market data:", err);
+    throw err;
+  }
+};
@@ -1,6 +1,6 @@
 // Web-specific activity commands
 import { getAuthToken } from "@/lib/auth-token";
-import type { ParseConfig, ParsedCsvResult } from "@/lib/types";
+import type { BrokerStatement, ParseConfig, ParsedCsvResult } from "@/lib/types";
 import { API_PREFIX, logger } from "./core";
 
 async function extractErrorMessage(response: Response): Promise<string | null> {
@@ -68,3 +68,42 @@ export const parseCsv = async (file: File, config: ParseConfig): Promise<ParsedC
     throw err;
   }
 };
+
+/**
+ * Detect the broker format of a statement (IBKR Flex XML, a supported CSV export or a
+ * PDF contract note) and parse it into import rows.
+ * Web implementation: POSTs multipart form data to /api/v1/activities/import/broker-statement.
+ */
+export const parseBrokerStatement = async (
+  file: File,
+  accountId: string,
+): Promise<BrokerStatement> => {
+  try {
+    const formData = new FormData();
+    formData.append("file", file);
+    formData.append("accountId", accountId);
+
+    const headers: HeadersInit = {};
+    const token = getAuthToken();
+    if (token) {
+      headers.Authorization = `Bearer ${token}`;
+    }
+
+    const response = await fetch(`${API_PREFIX}/activities/import/broker-statement`, {
+      method: "POST",
+      headers,
+      body: formData,
+    });
+
+    if (!response.ok) {
+      const details = await extractErrorMessage(response);
+      const fallback = `Request failed (${response.status})`;
+      throw new Error(`Failed to parse broker statement: ${details ?? fallback}`);
+    }
+
+    return (await response.json()) as BrokerStatement;
+  } catch (err) {
+    logger.error("Error parsing broker statement:", err);
+    throw err;
+  }
+};
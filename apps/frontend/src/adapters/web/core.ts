@@ -29,6 +29,8 @@ export const COMMANDS: CommandMap = {
   backup_database: { method: "POST", path: "/utilities/database/backup" },
   backup_database_to_path: { method: "POST", path: "/utilities/database/backup-to-path" },
   restore_database: { method: "POST", path: "/utilities/database/restore" },
+  create_portfolio_archive: { method: "POST", path: "/utilities/database/archive" },
+  restore_portfolio_archive: { method: "POST", path: "/utilities/database/archive/restore" },
   get_holdings: { method: "GET", path: "/holdings" },
   get_holding: { method: "GET", path: "/holdings/item" },
   get_asset_holdings: { method: "GET", path: "/holdings/by-asset" },
@@ -49,6 +51,7 @@ export const COMMANDS: CommandMap = {
   calculate_performance_history: { method: "POST", path: "/performance/history" },
   calculate_performance_summary: { method: "POST", path: "/performance/summary" },
   get_income_summary: { method: "GET", path: "/income/summary" },
+  get_realized_fx_gains: { method: "GET", path: "/fx-gains/realized" },
   // Goals
   get_goals: { method: "GET", path: "/goals" },
   create_goal: { method: "POST", path: "/goals" },
@@ -56,6 +59,31 @@ export const COMMANDS: CommandMap = {
   delete_goal: { method: "DELETE", path: "/goals" },
   update_goal_allocations: { method: "POST", path: "/goals/allocations" },
   load_goals_allocations: { method: "GET", path: "/goals/allocations" },
+  // Equity grants
+  get_equity_grants: { method: "GET", path: "/equity-grants" },
+  get_equity_grant_summaries: { method: "GET", path: "/equity-grants/summary" },
+  get_vesting_schedule: { method: "GET", path: "/equity-grants" },
+  create_equity_grant: { method: "POST", path: "/equity-grants" },
+  update_equity_grant: { method: "PUT", path: "/equity-grants" },
+  delete_equity_grant: { method: "DELETE", path: "/equity-grants" },
+  sync_vest_activities: { method: "POST", path: "/equity-grants/sync-vests" },
+  // Cash interest
+  get_cash_interest_settings: { method: "GET", path: "/cash-interest" },
+  set_cash_interest_settings: { method: "PUT", path: "/cash-interest" },
+  get_cash_interest_accrual: { method: "GET", path: "/cash-interest" },
+  get_cash_yield_comparison: { method: "GET", path: "/cash-interest/yields" },
+  generate_cash_interest_drafts: { method: "POST", path: "/cash-interest/generate-drafts" },
+  get_reconciliation_reports: { method: "GET", path: "/reconciliation" },
+  get_account_reconciliation: { method: "GET", path: "/reconciliation" },
+  create_reconciliation_drafts: { method: "POST", path: "/reconciliation" },
+  get_entity_history: { method: "GET", path: "/audit/entities" },
+  get_recent_changes: { method: "GET", path: "/audit/recent" },
+  get_entity_version_at: { method: "GET", path: "/audit/entities" },
+  restore_audit_version: { method: "POST", path: "/audit/entries" },
+  get_wallet_config: { method: "GET", path: "/wallets" },
+  set_wallet_config: { method: "PUT", path: "/wallets" },
+  sync_wallet: { method: "POST", path: "/wallets" },
+  sync_all_wallets: { method: "POST", path: "/wallets/sync" },
   // FX
   get_latest_exchange_rates: { method: "GET", path: "/exchange-rates/latest" },
   update_exchange_rate: { method: "PUT", path: "/exchange-rates" },
@@ -66,17 +94,29 @@ export const COMMANDS: CommandMap = {
   create_activity: { method: "POST", path: "/activities" },
   update_activity: { method: "PUT", path: "/activities" },
   save_activities: { method: "POST", path: "/activities/bulk" },
+  bulk_edit_activities: { method: "POST", path: "/activities/bulk-edit" },
   delete_activity: { method: "DELETE", path: "/activities" },
   // Activity import
   check_activities_import: { method: "POST", path: "/activities/import/check" },
   import_activities: { method: "POST", path: "/activities/import" },
   get_account_import_mapping: { method: "GET", path: "/activities/import/mapping" },
   save_account_import_mapping: { method: "POST", path: "/activities/import/mapping" },
+  list_broker_parsers: { method: "GET", path: "/activities/import/brokers" },
+  get_import_run_changes: { method: "GET", path: "/activities/import/runs" },
+  approve_import_run_changes: { method: "POST", path: "/activities/import/runs" },
+  reject_import_run_changes: { method: "POST", path: "/activities/import/runs" },
+  rollback_import_run: { method: "POST", path: "/activities/import/runs" },
+  find_probable_duplicates: { method: "GET", path: "/activities/duplicates" },
+  merge_duplicate_activities: { method: "POST", path: "/activities/duplicates/merge" },
   // Market data providers
   get_exchanges: { method: "GET", path: "/exchanges" },
   get_market_data_providers: { method: "GET", path: "/providers" },
   get_market_data_providers_settings: { method: "GET", path: "/providers/settings" },
   update_market_data_provider_settings: { method: "PUT", path: "/providers/settings" },
+  get_quote_consensus_settings: { method: "GET", path: "/providers/consensus" },
+  update_quote_consensus_settings: { method: "PUT", path: "/providers/consensus" },
+  get_quote_quality_settings: { method: "GET", path: "/providers/quality" },
+  update_quote_quality_settings: { method: "PUT", path: "/providers/quality" },
   // Contribution limits
   get_contribution_limits: { method: "GET", path: "/limits" },
   create_contribution_limit: { method: "POST", path: "/limits" },
@@ -93,6 +133,7 @@ export const COMMANDS: CommandMap = {
   search_symbol: { method: "GET", path: "/market-data/search" },
   resolve_symbol_quote: { method: "GET", path: "/market-data/resolve-currency" },
   get_quote_history: { method: "GET", path: "/market-data/quotes/history" },
+  get_quote_provenance: { method: "GET", path: "/market-data/quotes/provenance" },
   get_latest_quotes: { method: "POST", path: "/market-data/quotes/latest" },
   update_quote: { method: "PUT", path: "/market-data/quotes" },
   delete_quote: { method: "DELETE", path: "/market-data/quotes/id" },
@@ -252,6 +293,13 @@ export const COMMANDS: CommandMap = {
   link_liability: { method: "POST", path: "/alternative-assets" },
   unlink_liability: { method: "DELETE", path: "/alternative-assets" },
   update_alternative_asset_metadata: { method: "PUT", path: "/alternative-assets" },
+  set_loan_terms: { method: "PUT", path: "/alternative-assets" },
+  get_loan_schedule: { method: "GET", path: "/alternative-assets" },
+  set_valuation_model: { method: "PUT", path: "/alternative-assets" },
+  add_property_cash_flow: { method: "POST", path: "/alternative-assets" },
+  delete_property_cash_flow: { method: "DELETE", path: "/alternative-assets/cash-flows" },
+  get_property_cash_flows: { method: "GET", path: "/alternative-assets" },
+  get_rental_performance: { method: "GET", path: "/alternative-assets" },
   get_alternative_holdings: { method: "GET", path: "/alternative-holdings" },
   // Budget
   get_budget_categories: { method: "GET", path: "/budget/categories" },
@@ -353,6 +401,20 @@ export const invoke = async <T>(command: string, payload?: Record<string, unknow
       body = JSON.stringify({ backupFilePath });
       break;
     }
+    case "create_portfolio_archive": {
+      const { passphrase } = payload as { passphrase?: string };
+      body = JSON.stringify({ passphrase });
+      break;
+    }
+    case "restore_portfolio_archive": {
+      const { archive, passphrase, mode } = payload as {
+        archive: Record<string, unknown>;
+        passphrase?: string;
+        mode: string;
+      };
+      body = JSON.stringify({ archive, passphrase, mode });
+      break;
+    }
     case "update_settings": {
       const data = payload as { settingsUpdate: Record<string, unknown> };
       body = JSON.stringify(data.settingsUpdate);
@@ -502,6 +564,16 @@ export const invoke = async <T>(command: string, payload?: Record<string, unknow
     }
     case "get_income_summary":
       break;
+    case "get_realized_fx_gains": {
+      const p = payload as { accountId?: string; startDate?: string; endDate?: string };
+      const params = new URLSearchParams();
+      if (p?.accountId) params.set("accountId", p.accountId);
+      if (p?.startDate) params.set("startDate", p.startDate);
+      if (p?.endDate) params.set("endDate", p.endDate);
+      const qs = params.toString();
+      if (qs) url += `?${qs}`;
+      break;
+    }
     case "delete_goal": {
       const { goalId } = payload as { goalId: string };
       url += `/${encodeURIComponent(goalId)}`;
@@ -517,6 +589,165 @@ export const invoke = async <T>(command: string, payload?: Record<string, unknow
       body = JSON.stringify(goal);
       break;
     }
+    case "get_equity_grants":
+      break;
+    case "get_equity_grant_summaries":
+    case "sync_vest_activities": {
+      const { asOf } = (payload ?? {}) as { asOf?: string };
+      if (asOf) {
+        const params = new URLSearchParams();
+        params.set("asOf", asOf);
+        url += `?${params.toString()}`;
+      }
+      break;
+    }
+    case "get_vesting_schedule": {
+      const { grantId } = payload as { grantId: string };
+      url += `/${encodeURIComponent(grantId)}/schedule`;
+      break;
+    }
+    case "create_equity_grant": {
+      const { grant } = payload as { grant: Record<string, unknown> };
+      body = JSON.stringify(grant);
+      break;
+    }
+    case "update_equity_grant": {
+      const { grant } = payload as { grant: { id: string } };
+      url += `/${encodeURIComponent(grant.id)}`;
+      body = JSON.stringify(grant);
+      break;
+    }
+    case "delete_equity_grant": {
+      const { grantId } = payload as { grantId: string };
+      url += `/${encodeURIComponent(grantId)}`;
+      break;
+    }
+    case "get_cash_interest_settings": {
+      const { accountId } = payload as { accountId: string };
+      url += `/${encodeURIComponent(accountId)}/settings`;
+      break;
+    }
+    case "set_cash_interest_settings": {
+      const { accountId, settings } = payload as {
+        accountId: string;
+        settings: Record<string, unknown> | null;
+      };
+      url += `/${encodeURIComponent(accountId)}/settings`;
+      body = JSON.stringify(settings ?? null);
+      break;
+    }
+    case "get_cash_interest_accrual": {
+      const { accountId, asOf } = payload as { accountId: string; asOf?: string };
+      url += `/${encodeURIComponent(accountId)}/accrual`;
+      if (asOf) {
+        const params = new URLSearchParams();
+        params.set("asOf", asOf);
+        url += `?${params.toString()}`;
+      }
+      break;
+    }
+    case "get_cash_yield_comparison":
+    case "generate_cash_interest_drafts": {
+      const { asOf } = (payload ?? {}) as { asOf?: string };
+      if (asOf) {
+        const params = new URLSearchParams();
+        params.set("asOf", asOf);
+        url += `?${params.toString()}`;
+      }
+      break;
+    }
+    case "get_import_run_changes": {
+      const { importRunId } = payload as { importRunId: string };
+      url += `/${encodeURIComponent(importRunId)}/changes`;
+      break;
+    }
+    case "approve_import_run_changes":
+    case "reject_import_run_changes": {
+      const { importRunId, changeIds } = payload as { importRunId: string; changeIds: string[] };
+      const action = command === "approve_import_run_changes" ? "approve" : "reject";
+      url += `/${encodeURIComponent(importRunId)}/changes/${action}`;
+      body = JSON.stringify({ changeIds });
+      break;
+    }
+    case "rollback_import_run": {
+      const { importRunId } = payload as { importRunId: string };
+      url += `/${encodeURIComponent(importRunId)}/rollback`;
+      break;
+    }
+    case "find_probable_duplicates": {
+      const { accountId } = (payload ?? {}) as { accountId?: string };
+      if (accountId) {
+        const params = new URLSearchParams();
+        params.set("accountId", accountId);
+        url += `?${params.toString()}`;
+      }
+      break;
+    }
+    case "merge_duplicate_activities":
+      body = JSON.stringify(payload);
+      break;
+    case "get_account_reconciliation": {
+      const { accountId } = payload as { accountId: string };
+      url += `/${encodeURIComponent(accountId)}`;
+      break;
+    }
+    case "create_reconciliation_drafts": {
+      const { accountId } = payload as { accountId: string };
+      url += `/${encodeURIComponent(accountId)}/drafts`;
+      break;
+    }
+    case "get_entity_history": {
+      const { entityType, entityId } = payload as { entityType: string; entityId: string };
+      url += `/${encodeURIComponent(entityType)}/${encodeURIComponent(entityId)}`;
+      break;
+    }
+    case "get_recent_changes": {
+      const { entityType, limit } = (payload ?? {}) as { entityType?: string; limit?: number };
+      const params = new URLSearchParams();
+      if (entityType) params.set("entityType", entityType);
+      if (limit !== undefined) params.set("limit", String(limit));
+      const qs = params.toString();
+      if (qs) url += `?${qs}`;
+      break;
+    }
+    case "get_entity_version_at": {
+      const { entityType, entityId, at } = payload as {
+        entityType: string;
+        entityId: string;
+        at: string;
+      };
+      const params = new URLSearchParams();
+      params.set("at", at);
+      url += `/${encodeURIComponent(entityType)}/${encodeURIComponent(entityId)}/at`;
+      url += `?${params.toString()}`;
+      break;
+    }
+    case "restore_audit_version": {
+      const { entryId } = payload as { entryId: string };
+      url += `/${encodeURIComponent(entryId)}/restore`;
+      break;
+    }
+    case "get_wallet_config": {
+      const { accountId } = payload as { accountId: string };
+      url += `/${encodeURIComponent(accountId)}/config`;
+      break;
+    }
+    case "set_wallet_config": {
+      const { accountId, config } = payload as {
+        accountId: string;
+        config: Record<string, unknown>;
+      };
+      url += `/${encodeURIComponent(accountId)}/config`;
+      body = JSON.stringify(config);
+      break;
+    }
+    case "sync_wallet": {
+      const { accountId } = payload as { accountId: string };
+      url += `/${encodeURIComponent(accountId)}/sync`;
+      break;
+    }
+    case "sync_all_wallets":
+      break;
     case "update_goal_allocations": {
       const { allocations } = payload as { allocations: Record<string, unknown> };
       body = JSON.stringify(allocations);
@@ -538,6 +769,8 @@ export const invoke = async <T>(command: string, payload?: Record<string, unknow
       break;
     }
     case "get_exchanges":
+    case "get_reconciliation_reports":
+    case "list_broker_parsers":
     case "synch_quotes":
       break;
     case "search_activities": {
@@ -554,7 +787,8 @@ export const invoke = async <T>(command: string, payload?: Record<string, unknow
       body = JSON.stringify(activity);
       break;
     }
-    case "save_activities": {
+    case "save_activities":
+    case "bulk_edit_activities": {
       const { request } = payload as { request: Record<string, unknown> };
       body = JSON.stringify(request);
       break;
@@ -585,6 +819,12 @@ export const invoke = async <T>(command: string, payload?: Record<string, unknow
       body = JSON.stringify(payload);
       break;
     }
+    case "update_quote_consensus_settings":
+    case "update_quote_quality_settings": {
+      const { config } = payload as { config: Record<string, unknown> };
+      body = JSON.stringify(config);
+      break;
+    }
     case "create_contribution_limit": {
       const { newLimit } = payload as { newLimit: Record<string, unknown> };
       body = JSON.stringify(newLimit);
@@ -660,6 +900,19 @@ export const invoke = async <T>(command: string, payload?: Record<string, unknow
       url += `?${params.toString()}`;
       break;
     }
+    case "get_quote_provenance": {
+      const { assetId, startDate, endDate } = payload as {
+        assetId: string;
+        startDate: string;
+        endDate: string;
+      };
+      const params = new URLSearchParams();
+      params.set("assetId", assetId);
+      params.set("startDate", startDate);
+      params.set("endDate", endDate);
+      url += `?${params.toString()}`;
+      break;
+    }
     case "get_latest_quotes": {
       const { assetIds } = payload as { assetIds: string[] };
       body = JSON.stringify({ assetIds });
@@ -1135,6 +1388,55 @@ export const invoke = async <T>(command: string, payload?: Record<string, unknow
       body = JSON.stringify(metadata);
       break;
     }
+    case "set_loan_terms": {
+      const { liabilityId, terms } = payload as {
+        liabilityId: string;
+        terms: Record<string, unknown> | null;
+      };
+      url += `/${encodeURIComponent(liabilityId)}/loan`;
+      body = JSON.stringify(terms);
+      break;
+    }
+    case "get_loan_schedule": {
+      const { liabilityId } = payload as { liabilityId: string };
+      url += `/${encodeURIComponent(liabilityId)}/loan/schedule`;
+      break;
+    }
+    case "set_valuation_model": {
+      const { assetId, model } = payload as {
+        assetId: string;
+        model: Record<string, unknown> | null;
+      };
+      url += `/${encodeURIComponent(assetId)}/valuation-model`;
+      body = JSON.stringify(model);
+      break;
+    }
+    case "add_property_cash_flow": {
+      const { newFlow } = payload as { newFlow: { assetId: string } };
+      url += `/${encodeURIComponent(newFlow.assetId)}/cash-flows`;
+      body = JSON.stringify(newFlow);
+      break;
+    }
+    case "delete_property_cash_flow": {
+      const { cashFlowId } = payload as { cashFlowId: string };
+      url += `/${encodeURIComponent(cashFlowId)}`;
+      break;
+    }
+    case "get_property_cash_flows": {
+      const { assetId } = payload as { assetId: string };
+      url += `/${encodeURIComponent(assetId)}/cash-flows`;
+      break;
+    }
+    case "get_rental_performance": {
+      const { assetId, asOf } = payload as { assetId: string; asOf?: string };
+      url += `/${encodeURIComponent(assetId)}/rental-performance`;
+      if (asOf) {
+        const params = new URLSearchParams();
+        params.set("asOf", asOf);
+        url += `?${params.toString()}`;
+      }
+      break;
+    }
     case "get_alternative_holdings":
       break;
     // AI Providers
@@ -85,8 +85,16 @@ export {
   getAccountImportMapping,
   saveAccountImportMapping,
   checkExistingDuplicates,
+  listBrokerParsers,
+  getImportRunChanges,
+  approveImportRunChanges,
+  rejectImportRunChanges,
+  rollbackImportRun,
+  findProbableDuplicates,
+  mergeDuplicateActivities,
+  bulkEditActivities,
 } from "../shared/activities";
-export { parseCsv } from "./activities";
+export { parseCsv, parseBrokerStatement } from "./activities";
 
 // Goal Commands
 export {
@@ -98,6 +106,47 @@ export {
   getGoalsAllocation,
 } from "../shared/goals";
 
+// Equity Grant Commands
+export {
+  getEquityGrants,
+  getEquityGrantSummaries,
+  getVestingSchedule,
+  createEquityGrant,
+  updateEquityGrant,
+  deleteEquityGrant,
+  syncVestActivities,
+} from "../shared/equity-grants";
+
+// Cash Interest Commands
+export {
+  getCashInterestSettings,
+  setCashInterestSettings,
+  getCashInterestAccrual,
+  getCashYieldComparison,
+  generateCashInterestDrafts,
+} from "../shared/cash-interest";
+
+// Holdings Reconciliation Commands
+export {
+  getReconciliationReports,
+  getAccountReconciliation,
+  createReconciliationDrafts,
+} from "../shared/reconciliation";
+
+// Audit Log Commands
+export {
+  getEntityHistory,
+  getRecentChanges,
+  getEntityVersionAt,
+  restoreAuditVersion,
+} from "../shared/audit";
+
+// Portfolio Archive Commands
+export { createPortfolioArchive, restorePortfolioArchive } from "../shared/archive";
+
+// Wallet Commands
+export { getWalletConfig, setWalletConfig, syncWallet, syncAllWallets } from "../shared/wallets";
+
 // Secrets Commands
 export { setSecret, getSecret, deleteSecret } from "../shared/secrets";
 
@@ -127,6 +176,7 @@ export {
   recalculatePortfolio,
   getHoldings,
   getIncomeSummary,
+  getRealizedFxGains,
   getHistoricalValuations,
   getLatestValuations,
   calculatePerformanceHistory,
@@ -158,6 +208,11 @@ export {
   syncMarketData,
   deleteQuote,
   getQuoteHistory,
+  getQuoteProvenance,
+  getQuoteConsensusSettings,
+  updateQuoteConsensusSettings,
+  getQuoteQualitySettings,
+  updateQuoteQualitySettings,
   getMarketDataProviders,
   getMarketDataProviderSettings,
   updateMarketDataProviderSettings,
@@ -166,6 +221,7 @@ export {
   getExchanges,
   resolveSymbolQuote,
 } from "../shared/market-data";
+export { bulkImportQuotes, exportMarketData } from "./market-data";
 
 // Contribution Limits Commands
 export {
@@ -191,6 +247,13 @@ export {
   deleteAlternativeAsset,
   linkLiability,
   unlinkLiability,
+  setLoanTerms,
+  getLoanSchedule,
+  setValuationModel,
+  addPropertyCashFlow,
+  deletePropertyCashFlow,
+  getPropertyCashFlows,
+  getRentalPerformance,
   getNetWorth,
   updateAlternativeAssetMetadata,
   getAlternativeHoldings,
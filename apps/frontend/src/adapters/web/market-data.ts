@@ -0,0 +1,80 @@
+// Web-specific market data commands
+import { getAuthToken } from "@/lib/auth-token";
+import type { BulkImportOptions, BulkImportSummary, QuoteExportRequest } from "@/lib/types";
+import { API_PREFIX, logger } from "./core";
+
+const authHeaders = (): Record<string, string> => {
+  const headers: Record<string, string> = {};
+  const token = getAuthToken();
+  if (token) {
+    headers.Authorization = `Bearer ${token}`;
+  }
+  return headers;
+};
+
+/**
+ * Bulk import quote history from a dump file or zip archive.
+ * Web implementation: streams the file as multipart form data to
+ * /api/v1/market-data/quotes/bulk-import.
+ */
+export const bulkImportQuotes = async (
+  source: File | string,
+  options?: BulkImportOptions,
+): Promise<BulkImportSummary> => {
+  try {
+    if (typeof source === "string") {
+      throw new Error("Bulk quote import in the browser expects an uploaded file");
+    }
+
+    const formData = new FormData();
+    formData.append("file", source);
+    if (options) {
+      formData.append("options", JSON.stringify(options));
+    }
+
+    const response = await fetch(`${API_PREFIX}/market-data/quotes/bulk-import`, {
+      method: "POST",
+      headers: authHeaders(),
+      body: formData,
+    });
+
+    if (!response.ok) {
+      const details = (await response.text()).trim();
+      throw new Error(
+        `Failed to import quotes: ${details || `Request failed (${response.status})`}`,
+      );
+    }
+
+    return (await response.json()) as BulkImportSummary;
+  } catch (err) {
+    logger.error("Error bulk importing quotes:", err);
+    throw err;
+  }
+};
+
+/**
+ * Export stored quotes and FX rates as CSV, JSON Lines or Parquet.
+ * Web implementation: POSTs the request to /api/v1/market-data/export and
+ * returns the response body as bytes.
+ */
+export const exportMarketData = async (request: QuoteExportRequest): Promise<Uint8Array> => {
+  try {
+    const response = await fetch(`${API_PREFIX}/market-data/export`, {
+      method: "POST",
+      headers: { ...authHeaders(), "Content-Type": "application/json" },
+      body: JSON.stringify(request),
+    });
+
+    if (!response.ok) {
+      const details = (await response.text()).trim();
+      throw new Error(
+        `Failed to export market data: ${details || `Request failed (${response.status})`}`,
+      );
+    }
+
+    return new Uint8Array(await response.arrayBuffer());
+  } catch (err) {
+    logger.error("Error exporting market data:", err);
+    throw err;
+  }
+};
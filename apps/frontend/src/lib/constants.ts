@@ -282,8 +282,25 @@ export const ACTIVITY_SUBTYPES = {
   // DIVIDEND_IN_KIND: dividend paid in asset (not cash), e.g., spinoff shares
   DIVIDEND_IN_KIND: "DIVIDEND_IN_KIND",
 
-  // INTEREST subtypes - STAKING_REWARD expands to INTEREST + BUY
+  // INTEREST subtypes - STAKING_REWARD and AIRDROP expand to INTEREST + BUY
   STAKING_REWARD: "STAKING_REWARD",
+  AIRDROP: "AIRDROP",
+
+  // SELL subtypes
+  // SWAP: crypto-to-crypto trade → SELL + BUY of the received coin at FMV
+  SWAP: "SWAP",
+
+  // FEE subtypes
+  // NETWORK_FEE: fee paid in kind (gas) → SELL of the coin + FEE of the same value
+  NETWORK_FEE: "NETWORK_FEE",
+
+  // ADJUSTMENT subtypes
+  // HARD_FORK: coins from a chain split, takes a share of the parent's cost basis
+  HARD_FORK: "HARD_FORK",
+
+  // TRANSFER_OUT subtypes
+  // FX_CONVERSION: cash exchanged between currencies inside the account (internal flow)
+  FX_CONVERSION: "FX_CONVERSION",
 
   // CREDIT subtypes
   // BONUS: external flow (new capital, affects TWR/net_contribution)
@@ -301,6 +318,11 @@ export const SUBTYPE_DISPLAY_NAMES: Record<string, string> = {
   DRIP: "Dividend Reinvested (DRIP)",
   DIVIDEND_IN_KIND: "Dividend in Kind",
   STAKING_REWARD: "Staking Reward",
+  AIRDROP: "Airdrop",
+  SWAP: "Crypto Swap",
+  NETWORK_FEE: "Network Fee (in kind)",
+  HARD_FORK: "Hard Fork",
+  FX_CONVERSION: "FX Conversion",
   BONUS: "Bonus",
   REBATE: "Trading Rebate",
   REFUND: "Fee Refund",
@@ -309,7 +331,11 @@ export const SUBTYPE_DISPLAY_NAMES: Record<string, string> = {
 // Suggested subtypes per activity type
 export const SUBTYPES_BY_ACTIVITY_TYPE: Record<string, string[]> = {
   [ActivityType.DIVIDEND]: [ACTIVITY_SUBTYPES.DRIP, ACTIVITY_SUBTYPES.DIVIDEND_IN_KIND],
-  [ActivityType.INTEREST]: [ACTIVITY_SUBTYPES.STAKING_REWARD],
+  [ActivityType.INTEREST]: [ACTIVITY_SUBTYPES.STAKING_REWARD, ACTIVITY_SUBTYPES.AIRDROP],
+  [ActivityType.SELL]: [ACTIVITY_SUBTYPES.SWAP],
+  [ActivityType.FEE]: [ACTIVITY_SUBTYPES.NETWORK_FEE],
+  [ActivityType.ADJUSTMENT]: [ACTIVITY_SUBTYPES.HARD_FORK],
+  [ActivityType.TRANSFER_OUT]: [ACTIVITY_SUBTYPES.FX_CONVERSION],
   [ActivityType.CREDIT]: [
     ACTIVITY_SUBTYPES.BONUS,
     ACTIVITY_SUBTYPES.REBATE,
@@ -169,6 +169,13 @@ export const importActivitySchema = z
     comment: z.string().optional(),
     fxRate: decimalLikeSchema.nullable().optional(),
     subtype: z.string().optional(),
+    /** JSON metadata blob (e.g., FX conversion target, closed lots) */
+    metadata: z.string().optional(),
+    /** Originating system for native broker imports (e.g., IBKR); defaults to CSV */
+    sourceSystem: z.string().optional(),
+    sourceRecordId: z.string().optional(),
+    /** Links related rows, e.g. a dividend and its withholding tax */
+    sourceGroupId: z.string().optional(),
   })
   .refine(
     (data) => {
@@ -286,6 +286,54 @@ export interface ActivityBulkMutationResult {
   createdMappings: ActivityBulkIdentifierMapping[];
   errors: ActivityBulkMutationError[];
 }
+
+/** Selects activities for a bulk edit; criteria are combined with AND. */
+export interface ActivityQuery {
+  accountIds?: string[];
+  assetIds?: string[];
+  activityTypes?: string[];
+  dateFrom?: string; // YYYY-MM-DD format
+  dateTo?: string; // YYYY-MM-DD format
+  notesContains?: string;
+}
+/** Changes applied to every selected activity; unset fields are left as they are. */
+export interface ActivityBulkEdit {
+  accountId?: string;
+  activityType?: string;
+  /** `null` clears the subtype. */
+  subtype?: string | null;
+  /** Currency of the whole activity; unit price, amount and fee keep their values. */
+  activityCurrency?: string;
+  isExternalFlow?: boolean;
+}
+export interface ActivityBulkEditRequest {
+  query: ActivityQuery;
+  edit: ActivityBulkEdit;
+  dryRun?: boolean;
+}
+export interface ActivityBulkEditChange {
+  activityId: string;
+  /** Includes unitPrice, amount and fee when the currency change re-denominates them. */
+  changedFields: string[];
+  before: Activity;
+  after: Activity;
+}
+/** How much a position (assetId) or cash balance (currency) moves because of the edit. */
+export interface ActivityBulkEditHoldingChange {
+  accountId: string;
+  assetId?: string | null;
+  currency?: string | null;
+  before: number;
+  after: number;
+  change: number;
+}
+export interface ActivityBulkEditResult {
+  matched: number;
+  changes: ActivityBulkEditChange[];
+  holdingChanges: ActivityBulkEditHoldingChange[];
+  /** False for a dry run. */
+  applied: boolean;
+}
 export type ActivityImport = z.infer<typeof importActivitySchema>;
 export type ImportMappingData = z.infer<typeof importMappingSchema>;
 export type ParseConfig = z.infer<typeof parseConfigSchema>;
@@ -335,6 +383,23 @@ export interface ParsedCsvResult {
   rowCount: number;
 }
 
+/**
+ * A broker statement format recognised by the statement importer.
+ */
+export interface BrokerParserInfo {
+  id: string;
+  name: string;
+}
+
+/**
+ * Import rows parsed from a broker statement, with the detected format.
+ */
+export interface BrokerStatement {
+  brokerId: string;
+  brokerName: string;
+  activities: ActivityImport[];
+}
+
 export interface SymbolSearchResult {
   exchange: string;
   /** Canonical exchange MIC code (e.g., "XNAS", "XTSE") */
@@ -514,6 +579,197 @@ export interface Holding {
   prevCloseValue?: MonetaryValue | null;
   weight: number;
   asOfDate: string;
+  /** Vested and unvested equity grants in this holding's asset */
+  equityCompensation?: EquityCompensation | null;
+}
+
+/**
+ * Employee equity granted in a holding's asset.
+ * Unvested value is not part of the holding's market value.
+ */
+export interface EquityCompensation {
+  vestedShares: number;
+  unvestedShares: number;
+  /** Intrinsic value for options, market value for shares */
+  vestedValue: MonetaryValue;
+  unvestedValue: MonetaryValue;
+}
+
+export type GrantType = "RSU" | "ISO" | "NSO" | "ESPP";
+
+/**
+ * Employee equity grant (RSU, stock options or ESPP) with its vesting terms
+ */
+export interface EquityGrant {
+  id: string;
+  /** Account receiving the vested shares */
+  accountId: string;
+  /** Employer stock */
+  assetId: string;
+  grantType: GrantType;
+  grantDate: string;
+  totalShares: number;
+  /** Exercise price for options, purchase price for ESPP */
+  strikePrice?: number | null;
+  /** Fair market value per share at grant, used when no quote exists at a vest date */
+  grantPrice?: number | null;
+  currency: string;
+  vestingStartDate: string;
+  /** Total vesting period in months; 0 vests everything on the start date */
+  vestingMonths: number;
+  cliffMonths: number;
+  /** Months between vests after the cliff (1 = monthly, 3 = quarterly) */
+  vestingIntervalMonths: number;
+  notes?: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewEquityGrant {
+  accountId: string;
+  assetId: string;
+  grantType: GrantType;
+  grantDate: string;
+  totalShares: number;
+  strikePrice?: number | null;
+  grantPrice?: number | null;
+  /** Defaults to the asset's currency */
+  currency?: string | null;
+  /** Defaults to the grant date */
+  vestingStartDate?: string | null;
+  vestingMonths: number;
+  cliffMonths?: number;
+  vestingIntervalMonths?: number;
+  notes?: string | null;
+}
+
+export interface VestEvent {
+  date: string;
+  shares: number;
+  cumulativeShares: number;
+}
+
+/**
+ * A grant with its vested/unvested position as of a date, in the grant currency
+ */
+export interface EquityGrantSummary {
+  grant: EquityGrant;
+  asOf: string;
+  price?: number | null;
+  vestedShares: number;
+  unvestedShares: number;
+  vestedValue: number;
+  unvestedValue: number;
+  nextVest?: VestEvent | null;
+}
+
+export type WalletChain = "BITCOIN" | "ETHEREUM";
+
+export type WalletEndpointKind = "ESPLORA" | "BLOCKBOOK" | "ETHEREUM_RPC";
+
+/**
+ * Watch-only wallet linked to a crypto account, stored in the account metadata
+ */
+export interface WalletConfig {
+  chain: WalletChain;
+  endpointKind: WalletEndpointKind;
+  endpointUrl: string;
+  /** Addresses, or extended public keys for Blockbook endpoints */
+  addresses: string[];
+  /** Ethereum only: first block to scan */
+  startBlock?: number | null;
+}
+
+export interface WalletSyncResult {
+  accountId: string;
+  importRunId?: string | null;
+  transfersFetched: number;
+  activitiesUpserted: number;
+  blockHeight: number;
+  warnings: string[];
+}
+
+export type CashInterestCompounding = "DAILY" | "MONTHLY";
+
+export type CashInterestTierMode = "WHOLE" | "MARGINAL";
+
+/** APY paid from `minBalance` upwards; `apy` is a fraction (0.045 = 4.5%). */
+export interface ApyTier {
+  minBalance: number;
+  apy: number;
+}
+
+/** Per-account cash interest settings, stored in the account metadata. */
+export interface CashInterestSettings {
+  currency?: string | null;
+  tiers: ApyTier[];
+  tierMode?: CashInterestTierMode;
+  compounding?: CashInterestCompounding;
+  /** Day of month interest is paid (1-31, clamped to shorter months). */
+  paymentDay: number;
+  startDate?: string | null;
+  /** Last payment date drafts were generated for; kept by the server. */
+  draftedThrough?: string | null;
+}
+
+export interface DailyAccrual {
+  date: string;
+  balance: number;
+  interest: number;
+}
+
+export interface CashInterestAccrual {
+  accountId: string;
+  currency: string;
+  asOf: string;
+  balance: number;
+  apy: number;
+  periodStart: string;
+  nextPaymentDate: string;
+  accruedInterest: number;
+  days: DailyAccrual[];
+}
+
+export interface CashYieldSummary {
+  accountId: string;
+  accountName: string;
+  currency: string;
+  balance: number;
+  configuredApy?: number | null;
+  projectedAnnualInterest: number;
+  trailingInterest: number;
+  averageBalance: number;
+  realizedYield?: number | null;
+}
+
+export type DiscrepancyCause =
+  | "MISSING_SPLIT"
+  | "MISSING_DIVIDEND_REINVESTMENT"
+  | "UNKNOWN_TRANSFER";
+
+/** A position or cash balance that differs between the broker and the activities. */
+export interface HoldingDiscrepancy {
+  accountId: string;
+  assetId?: string | null;
+  /** Reported symbol, or the currency for cash. */
+  symbol: string;
+  currency: string;
+  isCash: boolean;
+  reportedQuantity: number;
+  computedQuantity: number;
+  difference: number;
+  cause: DiscrepancyCause;
+  explanation: string;
+  /** Draft activities that would close the discrepancy. */
+  proposedActivities: ActivityCreate[];
+}
+
+export interface ReconciliationReport {
+  accountId: string;
+  asOf: string;
+  computedAsOf?: string | null;
+  matchedCount: number;
+  discrepancies: HoldingDiscrepancy[];
 }
 
 /**
@@ -603,6 +859,97 @@ export interface Quote {
   notes?: string | null;
 }
 
+export type ConsensusStatus = "AGREED" | "UNIT_CORRECTED" | "DISPUTED" | "SINGLE_SOURCE";
+
+export interface QuoteCandidate {
+  source: string;
+  open: string;
+  high: string;
+  low: string;
+  close: string;
+  adjclose: string;
+  volume: string;
+  currency: string;
+}
+
+export interface QuoteProvenance {
+  quoteId: string;
+  assetId: string;
+  day: string; // YYYY-MM-DD
+  status: ConsensusStatus;
+  fieldSources: Record<string, string>;
+  candidates: QuoteCandidate[];
+  deviation?: string | null;
+  createdAt: string;
+}
+
+export interface ConsensusConfig {
+  enabled: boolean;
+  tolerance: number;
+}
+
+/** Thresholds of the post-sync quote gap and spike detection. */
+export interface QuoteQualityConfig {
+  spikeZScore: number;
+  /** Fraction, 0.15 = 15% */
+  minSpikeMove: number;
+  minGapTradingDays: number;
+  refetchFromFallback: boolean;
+  /** Days of history inspected by the health check */
+  lookbackDays: number;
+}
+
+export interface QuoteGap {
+  start: string;
+  end: string;
+  missingDays: number;
+  filledDays: number;
+}
+
+export interface QuoteSpike {
+  day: string;
+  quoteId: string;
+  source: string;
+  close: number;
+  expected: number;
+  zScore: number;
+  resolved: boolean;
+}
+
+/** Quote quality findings for one asset, reported with the market sync result. */
+export interface QuoteQualityReport {
+  assetId: string;
+  gaps: QuoteGap[];
+  spikes: QuoteSpike[];
+}
+
+export interface BulkImportOptions {
+  batchSize?: number;
+  overwrite?: boolean;
+  defaultExchangeMic?: string;
+}
+
+export type QuoteExportFormat = "csv" | "jsonl" | "parquet";
+
+export interface QuoteExportRequest {
+  assetIds: string[];
+  startDate?: string; // YYYY-MM-DD
+  endDate?: string; // YYYY-MM-DD
+  format: QuoteExportFormat;
+  includeFx?: boolean;
+}
+
+export interface BulkImportSummary {
+  filesRead: number;
+  rowsRead: number;
+  imported: number;
+  skippedDuplicates: number;
+  invalid: number;
+  unmatched: number;
+  unmatchedSymbols: string[];
+  errors: string[];
+}
+
 export interface LatestQuoteSnapshot {
   quote: Quote;
   isStale: boolean;
@@ -685,6 +1032,27 @@ export interface IncomeSummary {
   yoyGrowth: number | null; // Changed from optional to nullable
 }
 
+export interface RealizedFxGain {
+  activityId: string;
+  accountId: string;
+  date: string;
+  soldCurrency: string;
+  soldAmount: number;
+  boughtCurrency: string;
+  boughtAmount: number;
+  currency: string;
+  proceeds: number;
+  costBasis: number;
+  gain: number;
+  /** Rates that were unavailable; cash without a rate is left out of the gain. */
+  missingRates?: MissingFxRate[];
+}
+
+export interface MissingFxRate {
+  currency: string;
+  date: string;
+}
+
 // Define custom DateRange type matching react-day-picker's
 export interface DateRange {
   from: Date | undefined;
@@ -789,6 +1157,27 @@ export interface ReturnData {
 }
 
 // Renamed from PerformanceData to match Rust struct
+export interface HoldingFxDecomposition {
+  assetId: string;
+  currency: string;
+  localReturnAmount: number;
+  currencyReturnAmount: number;
+  unrealizedLocalGain: number;
+  unrealizedFxGain: number;
+  realizedFxGain: number;
+}
+
+export interface FxReturnDecomposition {
+  localReturn: number;
+  currencyReturn: number;
+  localReturnAmount: number;
+  /** Includes the revaluation of cash held in foreign currencies */
+  currencyReturnAmount: number;
+  realizedFxGain: number;
+  unrealizedFxGain: number;
+  holdings: HoldingFxDecomposition[];
+}
+
 export interface PerformanceMetrics {
   id: string;
   returns: ReturnData[];
@@ -815,6 +1204,8 @@ export interface PerformanceMetrics {
   maxDrawdown: number;
   /** Indicates if this is a HOLDINGS mode account (no cash flow tracking) */
   isHoldingsMode?: boolean;
+  /** Split of the return into local price and currency effects (account performance only) */
+  fxDecomposition?: FxReturnDecomposition;
 }
 
 export interface UpdateAssetProfile {
@@ -915,6 +1306,122 @@ export interface ImportRun {
   updatedAt: string;
 }
 
+export type ImportRunChangeType = "INSERT" | "UPDATE";
+
+export type ImportRunChangeStatus = "PENDING" | "APPROVED" | "REJECTED" | "ROLLED_BACK";
+
+/** An activity inserted or updated by an import run. */
+export interface ImportRunChange {
+  id: string;
+  importRunId: string;
+  activityId: string;
+  changeType: ImportRunChangeType;
+  /** Activity overwritten by the run (updates only). */
+  before?: Activity | null;
+  /** Activity as the run wrote it. */
+  after?: Activity | null;
+  status: ImportRunChangeStatus;
+  reviewedAt?: string | null;
+  createdAt: string;
+}
+
+export interface ImportRunRevertResult {
+  removed: number;
+  restored: number;
+  /** Activities kept because they were edited after the import. */
+  skipped: number;
+}
+
+/** An activity that probably duplicates one imported from another source. */
+export interface ProbableDuplicate {
+  /** The later of the two, suspected to be the duplicate. */
+  activityId: string;
+  duplicateOfId: string;
+  accountId: string;
+  assetId?: string | null;
+  activityType: string;
+  activitySource: string;
+  duplicateOfSource: string;
+  dayDifference: number;
+  quantityDifference: number;
+  priceDifference: number;
+}
+
+// ============================================================================
+// Audit Log Types
+// ============================================================================
+
+export type AuditEntityType = "ACTIVITY" | "ACCOUNT" | "ASSET";
+
+export type AuditOperation = "CREATE" | "UPDATE" | "DELETE" | "RESTORE";
+
+export type AuditSource = "USER" | "IMPORT" | "BROKER_SYNC" | "DEVICE_SYNC" | "SYSTEM";
+
+/** One recorded change to an activity, account or asset. */
+export interface AuditLogEntry {
+  id: string;
+  entityType: AuditEntityType;
+  entityId: string;
+  operation: AuditOperation;
+  source: AuditSource;
+  deviceId?: string | null;
+  importRunId?: string | null;
+  /** Entry whose version this change restored. */
+  restoredFrom?: string | null;
+  /** Fields that differ from `before`; empty when there is nothing to compare to. */
+  changedFields: string[];
+  /** Stored row before the change, snake_case as persisted. */
+  before?: Record<string, unknown> | null;
+  /** Stored row after the change; null for deletes. */
+  after?: Record<string, unknown> | null;
+  createdAt: string;
+}
+
+// ============================================================================
+// Portfolio Archive Types
+// ============================================================================
+
+export interface ArchiveManifest {
+  format: string;
+  formatVersion: number;
+  appVersion: string;
+  /** Latest migration applied to the archived database. */
+  schemaVersion: string;
+  createdAt: string;
+  /** Rows archived per section (accounts, activities, quotes, ...). */
+  sections: Record<string, number>;
+  checksum: string;
+  encryption?: {
+    algorithm: string;
+    keyDerivation: string;
+    salt: string;
+  } | null;
+}
+
+/** Contents of a portable archive file. */
+export interface PortfolioArchive {
+  manifest: ArchiveManifest;
+  /** Base64 database image, encrypted when the manifest says so. */
+  payload: string;
+}
+
+export interface ArchiveExport {
+  filename: string;
+  archive: PortfolioArchive;
+}
+
+/**
+ * `empty` restores into a database without accounts or activities;
+ * `merge` adds archived rows, keeping rows whose id already exists.
+ */
+export type ArchiveRestoreMode = "empty" | "merge";
+
+export interface ArchiveRestoreSummary {
+  mode: ArchiveRestoreMode;
+  manifest: ArchiveManifest;
+  sections: Record<string, { archived: number; restored: number }>;
+}
+
 // ============================================================================
 // Sync State Types
 // ============================================================================
@@ -1018,6 +1525,124 @@ export interface LinkLiabilityRequest {
   targetAssetId: string;
 }
 
+export type LoanRateType = "FIXED" | "VARIABLE";
+
+export type LoanPaymentFrequency = "WEEKLY" | "BIWEEKLY" | "MONTHLY" | "QUARTERLY" | "ANNUALLY";
+
+/**
+ * Loan terms attached to a liability. Rates are fractions (0.045 = 4.5%).
+ */
+export interface LoanTerms {
+  principal: number;
+  /** Disbursement date (YYYY-MM-DD) */
+  startDate: string;
+  annualRate: number;
+  rateType?: LoanRateType;
+  /** Rate changes for variable-rate loans */
+  rateChanges?: { effectiveDate: string; annualRate: number }[];
+  termMonths: number;
+  paymentFrequency?: LoanPaymentFrequency;
+  /** Defaults to one period after startDate */
+  firstPaymentDate?: string | null;
+  /** Additional principal paid with every regular payment */
+  recurringExtraPayment?: number | null;
+  /** One-off principal prepayments */
+  extraPayments?: { date: string; amount: number }[];
+}
+
+/**
+ * One payment in an amortization schedule
+ */
+export interface AmortizationEntry {
+  period: number;
+  date: string;
+  annualRate: number;
+  payment: number;
+  interest: number;
+  principal: number;
+  extraPrincipal: number;
+  /** Outstanding balance after this payment */
+  balance: number;
+}
+
+export interface AmortizationSchedule {
+  terms: LoanTerms;
+  entries: AmortizationEntry[];
+  totalInterest: number;
+  totalPaid: number;
+  payoffDate: string | null;
+}
+
+/**
+ * How an alternative asset's value evolves between manual appraisals.
+ * Rates are fractions (0.03 = 3% per year).
+ */
+export type ValuationModel =
+  | { type: "INDEX_LINKED"; indexAssetId: string }
+  | { type: "APPRECIATION"; annualRate: number }
+  | {
+      type: "STRAIGHT_LINE";
+      usefulLifeYears: number;
+      salvageValue?: number;
+      /** Start of the useful life (YYYY-MM-DD); defaults to the purchase date */
+      inServiceDate?: string | null;
+    }
+  | { type: "DECLINING_BALANCE"; annualRate: number; salvageValue?: number };
+
+export type PropertyCashFlowKind = "INCOME" | "EXPENSE" | "MORTGAGE_PAYMENT";
+
+/**
+ * Rental income, operating expense or mortgage payment recorded on a property
+ */
+export interface PropertyCashFlow {
+  id: string;
+  assetId: string;
+  kind: PropertyCashFlowKind;
+  category?: string | null;
+  amount: string;
+  currency: string;
+  date: string;
+  notes?: string | null;
+  createdAt: string;
+}
+
+export interface NewPropertyCashFlow {
+  assetId: string;
+  kind: PropertyCashFlowKind;
+  category?: string | null;
+  amount: string;
+  /** Defaults to the property's currency */
+  currency?: string | null;
+  date: string;
+  notes?: string | null;
+}
+
+/**
+ * Rental metrics of a property over a trailing 12-month period.
+ * Amounts are decimal strings in the property's currency.
+ */
+export interface RentalPerformance {
+  assetId: string;
+  periodStart: string;
+  periodEnd: string;
+  currency: string;
+  grossIncome: string;
+  operatingExpenses: string;
+  netOperatingIncome: string;
+  debtService: string;
+  /** Net operating income minus debt service */
+  cashFlow: string;
+  propertyValue: string;
+  linkedLiabilities: string;
+  /** Property value minus linked liabilities */
+  equity: string;
+  cashInvested?: string | null;
+  /** Annualized NOI / property value */
+  capRate?: string | null;
+  /** Annualized cash flow / cash invested */
+  cashOnCashReturn?: string | null;
+}
+
 /**
  * Information about a stale asset valuation
  */
@@ -1032,6 +1657,22 @@ export interface StaleAssetInfo {
   daysStale: number;
 }
 
+/**
+ * Equity held in a property (value minus linked liabilities)
+ */
+export interface PropertyEquity {
+  /** Property asset ID */
+  assetId: string;
+  /** Property name (if available) */
+  name?: string;
+  /** Property value in base currency as decimal string */
+  value: string;
+  /** Balance of linked liabilities in base currency as decimal string */
+  linkedLiabilities: string;
+  /** Value minus linked liabilities as decimal string */
+  equity: string;
+}
+
 /**
  * Individual item in the assets or liabilities breakdown
  */
@@ -1084,6 +1725,8 @@ export interface NetWorthResponse {
   oldestValuationDate?: string;
   /** Assets with valuations older than 90 days */
   staleAssets: StaleAssetInfo[];
+  /** Equity per property (value minus linked liabilities) */
+  propertyEquity: PropertyEquity[];
 }
 
 /**
@@ -351,17 +351,18 @@ function validateDraft(draft: Partial<DraftActivity>): {
 
   // INTEREST validation
   if (activityType === ActivityType.INTEREST) {
-    // STAKING_REWARD - needs quantity (tokens received) and may have unit price
-    if (subtype === ACTIVITY_SUBTYPES.STAKING_REWARD) {
+    // STAKING_REWARD / AIRDROP - needs quantity (tokens received) and may have unit price
+    if (subtype === ACTIVITY_SUBTYPES.STAKING_REWARD || subtype === ACTIVITY_SUBTYPES.AIRDROP) {
+      const label = subtype === ACTIVITY_SUBTYPES.AIRDROP ? "airdrops" : "staking rewards";
       if (!draft.symbol) {
-        errors.symbol = ["Symbol is required for staking rewards"];
+        errors.symbol = [`Symbol is required for ${label}`];
       }
       if (!hasPositiveValue(draft.quantity)) {
-        errors.quantity = ["Quantity is required for staking rewards (tokens received)"];
+        errors.quantity = [`Quantity is required for ${label} (tokens received)`];
       }
-      // Amount is optional for staking - can be calculated from quantity * price
+      // Amount is optional - can be calculated from quantity * price
       if (!hasNonZeroValue(draft.amount) && !hasPositiveValue(draft.unitPrice)) {
-        warnings.amount = ["Either amount or unit price is recommended for staking rewards"];
+        warnings.amount = [`Either amount or unit price is recommended for ${label}`];
       }
     } else {
       // Regular interest - amount is required
@@ -14,6 +14,7 @@ import { useQueryClient } from "@tanstack/react-query";
 import { useEffect, useRef } from "react";
 import { useNavigate } from "react-router-dom";
 import { toast } from "sonner";
+import type { QuoteQualityReport } from "@/lib/types";
 import {
   isDesktop,
   listenBrokerSyncComplete,
@@ -66,8 +67,10 @@ const useGlobalEventListener = () => {
       }
     };
 
-    const handleMarketSyncComplete = (event: { payload: { failed_syncs: [string, string][] } }) => {
-      const { failed_syncs } = event.payload || { failed_syncs: [] };
+    const handleMarketSyncComplete = (event: {
+      payload: { failed_syncs: [string, string][]; quality_reports?: QuoteQualityReport[] };
+    }) => {
+      const { failed_syncs, quality_reports } = event.payload || { failed_syncs: [] };
 
       if (isMobileViewportRef.current && syncContextRef.current) {
         syncContextRef.current.setIdle();
@@ -95,6 +98,27 @@ const useGlobalEventListener = () => {
           },
         });
       }
+
+      // Gaps and spikes a fallback provider could not fix
+      const suspicious = (quality_reports ?? []).filter(
+        (report) =>
+          report.spikes.some((spike) => !spike.resolved) ||
+          report.gaps.some((gap) => gap.filledDays < gap.missingDays),
+      );
+      if (suspicious.length > 0) {
+        const assets = suspicious.map((report) => report.assetId).join(", ");
+        toast.warning("Market Data Needs Review", {
+          id: `market-sync-quality-${assets}`,
+          description: `Missing days or price spikes were found for: ${assets}.`,
+          duration: 15000,
+          action: {
+            label: "View Data Status",
+            onClick: () => {
+              navigateRef.current("/health");
+            },
+          },
+        });
+      }
     };
 
     const handleMarketSyncError = (event: { payload: string }) => {
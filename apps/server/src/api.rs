@@ -23,7 +23,9 @@ mod ai_chat;
 mod ai_providers;
 mod alternative_assets;
 mod assets;
+mod audit;
 mod budget;
+mod cash_interest;
 mod fire;
 #[cfg(any(feature = "connect-sync", feature = "device-sync"))]
 pub mod connect;
@@ -31,6 +33,7 @@ pub mod connect;
 mod device_sync;
 #[cfg(feature = "device-sync")]
 pub(crate) mod device_sync_engine;
+mod equity_grants;
 mod exchange_rates;
 mod goals;
 mod health;
@@ -40,12 +43,14 @@ mod market_data;
 mod net_worth;
 mod performance;
 mod portfolio;
+mod reconciliation;
 mod secrets;
 mod settings;
 pub mod shared;
 #[cfg(feature = "device-sync")]
 mod sync_crypto;
 mod taxonomies;
+mod wallets;
 
 #[utoipa::path(get, path = "/api/v1/healthz", responses((status = 200, description = "Health")))]
 pub async fn healthz() -> &'static str {
@@ -99,6 +104,11 @@ pub fn app_router(state: Arc<AppState>, config: &Config) -> Router {
         .merge(taxonomies::router())
         .merge(net_worth::router())
         .merge(alternative_assets::router())
+        .merge(equity_grants::router())
+        .merge(cash_interest::router())
+        .merge(reconciliation::router())
+        .merge(audit::router())
+        .merge(wallets::router())
         .merge(ai_providers::router())
         .merge(ai_chat::router())
         .merge(fire::router())
@@ -8,9 +8,10 @@ use axum::{
     Json, Router,
 };
 use wealthfolio_core::activities::{
-    Activity, ActivityBulkMutationRequest, ActivityBulkMutationResult, ActivityImport,
-    ActivitySearchResponse, ActivityUpdate, ImportActivitiesResult, ImportMappingData, NewActivity,
-    ParseConfig, ParsedCsvResult,
+    Activity, ActivityBulkEditRequest, ActivityBulkEditResult, ActivityBulkMutationRequest,
+    ActivityBulkMutationResult, ActivityImport, ActivitySearchResponse, ActivityUpdate,
+    BrokerParserInfo, BrokerStatement, ImportActivitiesResult, ImportMappingData, ImportRunChange,
+    ImportRunRevertResult, NewActivity, ParseConfig, ParsedCsvResult, ProbableDuplicate,
 };
 
 use super::shared::parse_date_optional;
@@ -119,6 +120,14 @@ async fn save_activities(
     Ok(Json(result))
 }
 
+async fn bulk_edit_activities(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ActivityBulkEditRequest>,
+) -> ApiResult<Json<ActivityBulkEditResult>> {
+    let result = state.activity_service.bulk_edit_activities(request).await?;
+    Ok(Json(result))
+}
+
 async fn delete_activity(
     Path(id): Path<String>,
     State(state): State<Arc<AppState>>,
@@ -262,15 +271,180 @@ async fn parse_csv_endpoint(
     Ok(Json(result))
 }
 
+/// Reads the `file` and `accountId` fields of a statement upload.
+async fn read_statement_upload(mut multipart: Multipart) -> ApiResult<(String, Vec<u8>)> {
+    let mut file_content: Option<Vec<u8>> = None;
+    let mut account_id: Option<String> = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        crate::error::ApiError::BadRequest(format!("Failed to read multipart field: {}", e))
+    })? {
+        let name = field.name().unwrap_or("").to_string();
+        match name.as_str() {
+            "file" => {
+                file_content = Some(
+                    field
+                        .bytes()
+                        .await
+                        .map_err(|e| {
+                            crate::error::ApiError::BadRequest(format!(
+                                "Failed to read file content: {}",
+                                e
+                            ))
+                        })?
+                        .to_vec(),
+                );
+            }
+            "accountId" => {
+                account_id = Some(field.text().await.map_err(|e| {
+                    crate::error::ApiError::BadRequest(format!("Failed to read accountId: {}", e))
+                })?);
+            }
+            _ => {}
+        }
+    }
+
+    let content = file_content.ok_or_else(|| {
+        crate::error::ApiError::BadRequest("Missing file in multipart request".to_string())
+    })?;
+    let account_id = account_id.ok_or_else(|| {
+        crate::error::ApiError::BadRequest("Missing accountId in multipart request".to_string())
+    })?;
+    Ok((account_id, content))
+}
+
+async fn parse_ibkr_flex_endpoint(
+    State(state): State<Arc<AppState>>,
+    multipart: Multipart,
+) -> ApiResult<Json<Vec<ActivityImport>>> {
+    let (account_id, content) = read_statement_upload(multipart).await?;
+    let rows = state
+        .activity_service
+        .parse_ibkr_flex(&account_id, &content)?;
+    Ok(Json(rows))
+}
+
+async fn parse_broker_statement_endpoint(
+    State(state): State<Arc<AppState>>,
+    multipart: Multipart,
+) -> ApiResult<Json<BrokerStatement>> {
+    let (account_id, content) = read_statement_upload(multipart).await?;
+    let statement = state
+        .activity_service
+        .parse_broker_statement(&account_id, &content)?;
+    Ok(Json(statement))
+}
+
+async fn list_broker_parsers(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Vec<BrokerParserInfo>>> {
+    Ok(Json(state.activity_service.list_broker_parsers()))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportRunChangeIdsBody {
+    change_ids: Vec<String>,
+}
+
+async fn get_import_run_changes(
+    Path(import_run_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Vec<ImportRunChange>>> {
+    let changes = state
+        .import_review_service
+        .get_import_run_changes(&import_run_id)?;
+    Ok(Json(changes))
+}
+
+async fn approve_import_run_changes(
+    Path(import_run_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<ImportRunChangeIdsBody>,
+) -> ApiResult<Json<usize>> {
+    let approved = state
+        .import_review_service
+        .approve_import_run_changes(&import_run_id, body.change_ids)
+        .await?;
+    Ok(Json(approved))
+}
+
+async fn reject_import_run_changes(
+    Path(import_run_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<ImportRunChangeIdsBody>,
+) -> ApiResult<Json<ImportRunRevertResult>> {
+    let result = state
+        .import_review_service
+        .reject_import_run_changes(&import_run_id, body.change_ids)
+        .await?;
+    Ok(Json(result))
+}
+
+async fn rollback_import_run(
+    Path(import_run_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<ImportRunRevertResult>> {
+    let result = state
+        .import_review_service
+        .rollback_import_run(&import_run_id)
+        .await?;
+    Ok(Json(result))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProbableDuplicatesQuery {
+    account_id: Option<String>,
+}
+
+async fn find_probable_duplicates(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<ProbableDuplicatesQuery>,
+) -> ApiResult<Json<Vec<ProbableDuplicate>>> {
+    let duplicates = state
+        .activity_service
+        .find_probable_duplicates(q.account_id)?;
+    Ok(Json(duplicates))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeDuplicateBody {
+    keep_id: String,
+    duplicate_id: String,
+}
+
+async fn merge_duplicate_activities(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<MergeDuplicateBody>,
+) -> ApiResult<Json<Activity>> {
+    let activity = state
+        .activity_service
+        .merge_duplicate_activities(&body.keep_id, &body.duplicate_id)
+        .await?;
+    Ok(Json(activity))
+}
+
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/activities/search", post(search_activities))
         .route("/activities", post(create_activity).put(update_activity))
         .route("/activities/bulk", post(save_activities))
+        .route("/activities/bulk-edit", post(bulk_edit_activities))
         .route("/activities/{id}", delete(delete_activity))
         .route("/activities/import/check", post(check_activities_import))
         .route("/activities/import", post(import_activities))
         .route("/activities/import/parse", post(parse_csv_endpoint))
+        .route(
+            "/activities/import/ibkr-flex",
+            post(parse_ibkr_flex_endpoint),
+        )
+        .route(
+            "/activities/import/broker-statement",
+            post(parse_broker_statement_endpoint),
+        )
+        .route("/activities/import/brokers", get(list_broker_parsers))
         .route(
             "/activities/import/mapping",
             get(get_account_import_mapping).post(save_account_import_mapping),
@@ -279,4 +453,25 @@ pub fn router() -> Router<Arc<AppState>> {
             "/activities/import/check-duplicates",
             post(check_existing_duplicates),
         )
+        .route(
+            "/activities/import/runs/{id}/changes",
+            get(get_import_run_changes),
+        )
+        .route(
+            "/activities/import/runs/{id}/changes/approve",
+            post(approve_import_run_changes),
+        )
+        .route(
+            "/activities/import/runs/{id}/changes/reject",
+            post(reject_import_run_changes),
+        )
+        .route(
+            "/activities/import/runs/{id}/rollback",
+            post(rollback_import_run),
+        )
+        .route("/activities/duplicates", get(find_probable_duplicates))
+        .route(
+            "/activities/duplicates/merge",
+            post(merge_duplicate_activities),
+        )
 }
@@ -9,26 +9,26 @@
 use std::sync::Arc;
 
 use crate::{
-    api::shared::{enqueue_portfolio_job, PortfolioJobConfig},
+    api::shared::{enqueue_portfolio_job, parse_date_optional, PortfolioJobConfig},
     error::ApiResult,
     main_lib::AppState,
 };
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     routing::{delete, get, post, put},
     Json, Router,
 };
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use wealthfolio_core::{
     assets::{
-        AssetKind, CreateAlternativeAssetRequest as CoreCreateRequest,
-        LinkLiabilityRequest as CoreLinkRequest,
-        UpdateAssetDetailsRequest as CoreUpdateDetailsRequest,
-        UpdateValuationRequest as CoreValuationRequest,
+        AmortizationSchedule, AssetKind, CreateAlternativeAssetRequest as CoreCreateRequest,
+        LinkLiabilityRequest as CoreLinkRequest, LoanTerms, NewPropertyCashFlow, PropertyCashFlow,
+        RentalPerformance, UpdateAssetDetailsRequest as CoreUpdateDetailsRequest,
+        UpdateValuationRequest as CoreValuationRequest, ValuationModel,
     },
     quotes::MarketSyncMode,
 };
@@ -332,6 +332,107 @@ async fn unlink_liability(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// PUT /alternative-assets/:id/loan - Attaches loan terms to a liability (null clears them)
+async fn set_loan_terms(
+    Path(liability_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Json(terms): Json<Option<LoanTerms>>,
+) -> ApiResult<Json<Option<AmortizationSchedule>>> {
+    let schedule = state
+        .alternative_asset_service
+        .set_loan_terms(&liability_id, terms)
+        .await?;
+
+    Ok(Json(schedule))
+}
+
+/// GET /alternative-assets/:id/loan/schedule - Gets the amortization schedule of a liability
+async fn get_loan_schedule(
+    Path(liability_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Option<AmortizationSchedule>>> {
+    let schedule = state
+        .alternative_asset_service
+        .get_loan_schedule(&liability_id)?;
+
+    Ok(Json(schedule))
+}
+
+/// PUT /alternative-assets/:id/valuation-model - Sets an asset's valuation model (null clears it)
+async fn set_valuation_model(
+    Path(asset_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Json(model): Json<Option<ValuationModel>>,
+) -> ApiResult<StatusCode> {
+    state
+        .alternative_asset_service
+        .set_valuation_model(&asset_id, model)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /alternative-assets/:id/cash-flows - Records rental income, an expense or a mortgage payment
+async fn add_property_cash_flow(
+    Path(asset_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Json(mut new_flow): Json<NewPropertyCashFlow>,
+) -> ApiResult<Json<PropertyCashFlow>> {
+    new_flow.asset_id = asset_id;
+    let flow = state
+        .alternative_asset_service
+        .add_property_cash_flow(new_flow)
+        .await?;
+
+    Ok(Json(flow))
+}
+
+/// GET /alternative-assets/:id/cash-flows - Lists the cash-flow entries of a property
+async fn get_property_cash_flows(
+    Path(asset_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Vec<PropertyCashFlow>>> {
+    let flows = state
+        .alternative_asset_service
+        .get_property_cash_flows(&asset_id)?;
+
+    Ok(Json(flows))
+}
+
+/// DELETE /alternative-assets/cash-flows/:id - Deletes a property cash-flow entry
+async fn delete_property_cash_flow(
+    Path(cash_flow_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<StatusCode> {
+    state
+        .alternative_asset_service
+        .delete_property_cash_flow(&cash_flow_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RentalPerformanceQuery {
+    /// End of the trailing 12-month period (YYYY-MM-DD). Defaults to today.
+    as_of: Option<String>,
+}
+
+/// GET /alternative-assets/:id/rental-performance - Gets NOI, cap rate, cash-on-cash and equity
+async fn get_rental_performance(
+    Path(asset_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<RentalPerformanceQuery>,
+) -> ApiResult<Json<RentalPerformance>> {
+    let as_of = parse_date_optional(q.as_of, "asOf")?.unwrap_or_else(|| Utc::now().date_naive());
+    let performance = state
+        .alternative_asset_service
+        .get_rental_performance(&asset_id, as_of)?;
+
+    Ok(Json(performance))
+}
+
 /// GET /alternative-holdings - Gets all alternative holdings (assets with their latest valuations)
 async fn get_alternative_holdings(
     State(state): State<Arc<AppState>>,
@@ -398,5 +499,26 @@ pub fn router() -> Router<Arc<AppState>> {
             "/alternative-assets/{id}/metadata",
             put(update_alternative_asset_metadata),
         )
+        .route("/alternative-assets/{id}/loan", put(set_loan_terms))
+        .route(
+            "/alternative-assets/{id}/loan/schedule",
+            get(get_loan_schedule),
+        )
+        .route(
+            "/alternative-assets/{id}/valuation-model",
+            put(set_valuation_model),
+        )
+        .route(
+            "/alternative-assets/{id}/cash-flows",
+            get(get_property_cash_flows).post(add_property_cash_flow),
+        )
+        .route(
+            "/alternative-assets/cash-flows/{id}",
+            delete(delete_property_cash_flow),
+        )
+        .route(
+            "/alternative-assets/{id}/rental-performance",
+            get(get_rental_performance),
+        )
         .route("/alternative-holdings", get(get_alternative_holdings))
 }
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use crate::{error::ApiResult, main_lib::AppState};
+use axum::{
+    extract::{Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use wealthfolio_core::audit::{AuditEntityType, AuditLogEntry};
+
+async fn get_entity_history(
+    Path((entity_type, entity_id)): Path<(AuditEntityType, String)>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Vec<AuditLogEntry>>> {
+    let history = state
+        .audit_service
+        .get_entity_history(entity_type, &entity_id)?;
+    Ok(Json(history))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecentChangesQuery {
+    entity_type: Option<AuditEntityType>,
+    limit: Option<i64>,
+}
+
+async fn get_recent_changes(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<RecentChangesQuery>,
+) -> ApiResult<Json<Vec<AuditLogEntry>>> {
+    let entries = state
+        .audit_service
+        .get_recent_changes(q.entity_type, q.limit)?;
+    Ok(Json(entries))
+}
+
+#[derive(serde::Deserialize)]
+struct VersionAtQuery {
+    at: DateTime<Utc>,
+}
+
+async fn get_entity_version_at(
+    Path((entity_type, entity_id)): Path<(AuditEntityType, String)>,
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<VersionAtQuery>,
+) -> ApiResult<Json<Option<Value>>> {
+    let version = state
+        .audit_service
+        .get_entity_version_at(entity_type, &entity_id, q.at)?;
+    Ok(Json(version))
+}
+
+async fn restore_audit_version(
+    Path(entry_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<AuditLogEntry>> {
+    let entry = state.audit_service.restore_version(&entry_id).await?;
+    Ok(Json(entry))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/audit/recent", get(get_recent_changes))
+        .route(
+            "/audit/entities/{entity_type}/{entity_id}",
+            get(get_entity_history),
+        )
+        .route(
+            "/audit/entities/{entity_type}/{entity_id}/at",
+            get(get_entity_version_at),
+        )
+        .route(
+            "/audit/entries/{entry_id}/restore",
+            post(restore_audit_version),
+        )
+}
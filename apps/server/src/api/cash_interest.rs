@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use crate::{error::ApiResult, main_lib::AppState};
+use axum::{
+    extract::{Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use wealthfolio_core::accounts::Account;
+use wealthfolio_core::cash_interest::{
+    CashInterestAccrual, CashInterestSettings, CashYieldSummary,
+};
+
+use super::shared::parse_date_optional;
+
+#[derive(serde::Deserialize)]
+struct AsOfQuery {
+    /// Optional date in ISO format (YYYY-MM-DD). Defaults to today.
+    #[serde(rename = "asOf")]
+    as_of: Option<String>,
+}
+
+async fn get_cash_interest_settings(
+    Path(account_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Option<CashInterestSettings>>> {
+    let settings = state.cash_interest_service.get_settings(&account_id)?;
+    Ok(Json(settings))
+}
+
+async fn set_cash_interest_settings(
+    Path(account_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Json(settings): Json<Option<CashInterestSettings>>,
+) -> ApiResult<Json<Account>> {
+    let account = state
+        .cash_interest_service
+        .set_settings(&account_id, settings)
+        .await?;
+    Ok(Json(account))
+}
+
+async fn get_cash_interest_accrual(
+    Path(account_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AsOfQuery>,
+) -> ApiResult<Json<CashInterestAccrual>> {
+    let as_of = parse_date_optional(q.as_of, "asOf")?.unwrap_or_else(|| Utc::now().date_naive());
+    let accrual = state
+        .cash_interest_service
+        .get_accrual(&account_id, as_of)?;
+    Ok(Json(accrual))
+}
+
+async fn get_cash_yield_comparison(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AsOfQuery>,
+) -> ApiResult<Json<Vec<CashYieldSummary>>> {
+    let as_of = parse_date_optional(q.as_of, "asOf")?.unwrap_or_else(|| Utc::now().date_naive());
+    let yields = state.cash_interest_service.get_yield_comparison(as_of)?;
+    Ok(Json(yields))
+}
+
+async fn generate_cash_interest_drafts(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AsOfQuery>,
+) -> ApiResult<Json<usize>> {
+    let as_of = parse_date_optional(q.as_of, "asOf")?.unwrap_or_else(|| Utc::now().date_naive());
+    let created = state
+        .cash_interest_service
+        .generate_interest_drafts(as_of)
+        .await?;
+    Ok(Json(created))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/cash-interest/yields", get(get_cash_yield_comparison))
+        .route(
+            "/cash-interest/generate-drafts",
+            post(generate_cash_interest_drafts),
+        )
+        .route(
+            "/cash-interest/{account_id}/settings",
+            get(get_cash_interest_settings).put(set_cash_interest_settings),
+        )
+        .route(
+            "/cash-interest/{account_id}/accrual",
+            get(get_cash_interest_accrual),
+        )
+}
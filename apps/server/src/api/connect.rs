@@ -7,7 +7,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use axum::{
-    extract::{Query, State},
+    extract::{Multipart, Query, State},
     routing::{delete, get, post},
     Json, Router,
 };
@@ -26,10 +26,11 @@ use wealthfolio_connect::{
         BrokerApiClient, PlansResponse, SyncAccountsResponse, SyncActivitiesResponse,
         SyncConnectionsResponse, UserInfo,
     },
-    fetch_subscription_plans_public, ConnectApiClient, SyncConfig, SyncOrchestrator,
-    SyncProgressPayload, SyncProgressReporter, SyncResult,
+    fetch_subscription_plans_public, ConnectApiClient, LocalFileBrokerClient, SyncConfig,
+    SyncOrchestrator, SyncProgressPayload, SyncProgressReporter, SyncResult,
 };
 use wealthfolio_core::accounts::TrackingMode;
+use wealthfolio_core::audit::{with_audit_context, AuditContext};
 use wealthfolio_device_sync::{EnableSyncResult, SyncState, SyncStateResult};
 
 // Storage keys (without prefix - the SecretStore adds "wealthfolio_" prefix)
@@ -486,11 +487,12 @@ async fn sync_broker_connections(
     );
 
     // Sync to local database
-    let result = state
-        .connect_sync_service
-        .sync_connections(connections)
-        .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let result = with_audit_context(
+        AuditContext::broker_sync(None),
+        state.connect_sync_service.sync_connections(connections),
+    )
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))?;
 
     info!(
         "[Connect] Synced connections: {} platforms created, {} updated",
@@ -517,11 +519,12 @@ async fn sync_broker_accounts(
     info!("[Connect] Fetched {} accounts from cloud", accounts.len());
 
     // Sync to local database
-    let result = state
-        .connect_sync_service
-        .sync_accounts(accounts)
-        .await
-        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let result = with_audit_context(
+        AuditContext::broker_sync(None),
+        state.connect_sync_service.sync_accounts(accounts),
+    )
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))?;
 
     info!(
         "[Connect] Synced accounts: {} created, {} updated, {} skipped",
@@ -594,13 +597,122 @@ pub async fn perform_broker_sync(state: &AppState) -> Result<SyncResult, String>
 
     // Run the sync via the centralized orchestrator
     // Note: Asset enrichment is handled automatically via domain events (AssetsCreated)
-    orchestrator.sync_all(&client).await
+    with_audit_context(
+        AuditContext::broker_sync(None),
+        orchestrator.sync_all(&client),
+    )
+    .await
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Local file-drop sync (no Connect account needed)
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn local_broker_client(state: &AppState) -> ApiResult<LocalFileBrokerClient> {
+    state
+        .broker_drop_dir
+        .as_deref()
+        .map(LocalFileBrokerClient::new)
+        .ok_or_else(|| {
+            ApiError::NotImplemented(
+                "Local broker sync is disabled. Set WF_BROKER_DROP_DIR to enable it.".to_string(),
+            )
+        })
+}
+
+/// Syncs the broker exports in the drop folder through the same orchestrator
+/// as Connect, so accounts, import runs and sync states behave the same way.
+/// Also used by the drop folder watcher.
+pub async fn perform_local_broker_sync(state: &AppState) -> Result<SyncResult, String> {
+    let client = local_broker_client(state).map_err(|e| e.to_string())?;
+    let reporter = Arc::new(EventBusProgressReporter::new(state.event_bus.clone()));
+    let orchestrator = SyncOrchestrator::new(
+        state.connect_sync_service.clone(),
+        reporter,
+        SyncConfig::default(),
+    );
+    with_audit_context(
+        AuditContext::broker_sync(None),
+        orchestrator.sync_all(&client),
+    )
+    .await
+}
+
+/// Trigger a sync of the local drop folder.
+/// Returns immediately with 202 Accepted. Sync runs in background and emits SSE events.
+async fn sync_local_broker_files(State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    local_broker_client(&state)?;
+    info!("[Connect] Starting local broker file sync (non-blocking)...");
+    tokio::spawn(async move {
+        if let Err(err) = perform_local_broker_sync(&state).await {
+            error!("[Connect] Local broker file sync failed: {}", err);
+        }
+    });
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Store an uploaded broker export in an account folder of the drop folder
+/// (multipart fields `account` and `file`), then sync in the background.
+async fn upload_local_broker_file(
+    State(state): State<Arc<AppState>>,
+    mut multipart: Multipart,
+) -> ApiResult<StatusCode> {
+    let client = local_broker_client(&state)?;
+    let mut account: Option<String> = None;
+    let mut file: Option<(String, Vec<u8>)> = None;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read multipart field: {}", e)))?
+    {
+        match field.name().unwrap_or("") {
+            "account" => {
+                account =
+                    Some(field.text().await.map_err(|e| {
+                        ApiError::BadRequest(format!("Failed to read account: {}", e))
+                    })?);
+            }
+            "file" => {
+                let file_name = field.file_name().unwrap_or("statement").to_string();
+                let content = field.bytes().await.map_err(|e| {
+                    ApiError::BadRequest(format!("Failed to read file content: {}", e))
+                })?;
+                file = Some((file_name, content.to_vec()));
+            }
+            _ => {}
+        }
+    }
+
+    let account = account
+        .ok_or_else(|| ApiError::BadRequest("Missing account in multipart request".to_string()))?;
+    let (file_name, content) =
+        file.ok_or_else(|| ApiError::BadRequest("Missing file in multipart request".to_string()))?;
+    let path = client.save_upload(&account, &file_name, &content)?;
+    info!("[Connect] Stored broker export at {}", path.display());
+
+    tokio::spawn(async move {
+        if let Err(err) = perform_local_broker_sync(&state).await {
+            error!("[Connect] Local broker file sync failed: {}", err);
+        }
+    });
+    Ok(StatusCode::ACCEPTED)
 }
 
 /// Sync only brokerage activities for existing TRANSACTIONS accounts.
 /// This preserves legacy /connect/sync/activities behavior (no connections/accounts/holdings sync).
 async fn perform_broker_activities_only_sync(
     state: &AppState,
+) -> Result<SyncActivitiesResponse, String> {
+    with_audit_context(
+        AuditContext::broker_sync(None),
+        run_broker_activities_only_sync(state),
+    )
+    .await
+}
+
+async fn run_broker_activities_only_sync(
+    state: &AppState,
 ) -> Result<SyncActivitiesResponse, String> {
     ensure_connect_sync_enabled().map_err(|e| e.to_string())?;
     let client = create_connect_client(state)
@@ -1217,6 +1329,9 @@ pub fn router() -> Router<Arc<AppState>> {
         .route("/connect/sync/connections", post(sync_broker_connections))
         .route("/connect/sync/accounts", post(sync_broker_accounts))
         .route("/connect/sync/activities", post(sync_broker_activities))
+        // Local file-drop sync
+        .route("/connect/local/sync", post(sync_local_broker_files))
+        .route("/connect/local/upload", post(upload_local_broker_file))
         // Local data queries (from local database)
         .route("/connect/synced-accounts", get(get_synced_accounts))
         .route("/connect/platforms", get(get_platforms))
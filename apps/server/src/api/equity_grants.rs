@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use crate::{error::ApiResult, main_lib::AppState};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post, put},
+    Json, Router,
+};
+use chrono::Utc;
+use wealthfolio_core::equity_grants::{EquityGrant, EquityGrantSummary, NewEquityGrant, VestEvent};
+
+use super::shared::parse_date_optional;
+
+#[derive(serde::Deserialize)]
+struct AsOfQuery {
+    /// Optional date in ISO format (YYYY-MM-DD). Defaults to today.
+    #[serde(rename = "asOf")]
+    as_of: Option<String>,
+}
+
+async fn get_equity_grants(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Vec<EquityGrant>>> {
+    let grants = state.equity_grant_service.get_equity_grants()?;
+    Ok(Json(grants))
+}
+
+async fn get_equity_grant_summaries(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AsOfQuery>,
+) -> ApiResult<Json<Vec<EquityGrantSummary>>> {
+    let as_of = parse_date_optional(q.as_of, "asOf")?.unwrap_or_else(|| Utc::now().date_naive());
+    let summaries = state
+        .equity_grant_service
+        .get_equity_grant_summaries(as_of)?;
+    Ok(Json(summaries))
+}
+
+async fn get_vesting_schedule(
+    Path(id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Vec<VestEvent>>> {
+    let events = state.equity_grant_service.get_vesting_schedule(&id)?;
+    Ok(Json(events))
+}
+
+async fn create_equity_grant(
+    State(state): State<Arc<AppState>>,
+    Json(grant): Json<NewEquityGrant>,
+) -> ApiResult<Json<EquityGrant>> {
+    let created = state
+        .equity_grant_service
+        .create_equity_grant(grant)
+        .await?;
+    Ok(Json(created))
+}
+
+async fn update_equity_grant(
+    Path(id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Json(grant): Json<EquityGrant>,
+) -> ApiResult<Json<EquityGrant>> {
+    let updated = state
+        .equity_grant_service
+        .update_equity_grant(EquityGrant { id, ..grant })
+        .await?;
+    Ok(Json(updated))
+}
+
+async fn delete_equity_grant(
+    Path(id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<StatusCode> {
+    state.equity_grant_service.delete_equity_grant(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn sync_vest_activities(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AsOfQuery>,
+) -> ApiResult<Json<usize>> {
+    let as_of = parse_date_optional(q.as_of, "asOf")?.unwrap_or_else(|| Utc::now().date_naive());
+    let created = state
+        .equity_grant_service
+        .sync_vest_activities(as_of)
+        .await?;
+    Ok(Json(created))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/equity-grants",
+            get(get_equity_grants).post(create_equity_grant),
+        )
+        .route("/equity-grants/summary", get(get_equity_grant_summaries))
+        .route("/equity-grants/sync-vests", post(sync_vest_activities))
+        .route(
+            "/equity-grants/{id}",
+            put(update_equity_grant).delete(delete_equity_grant),
+        )
+        .route("/equity-grants/{id}/schedule", get(get_vesting_schedule))
+}
@@ -44,6 +44,8 @@ async fn run_health_checks_internal(
             state.quote_service.clone(),
             state.asset_service.clone(),
             state.taxonomy_service.clone(),
+            state.fx_service.clone(),
+            state.reconciliation_service.clone(),
         )
         .await
         .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -124,6 +126,22 @@ async fn execute_health_fix(
         return Ok(());
     }
 
+    // Handle create_reconciliation_drafts by creating the proposed activities
+    if action.id == "create_reconciliation_drafts" {
+        let account_ids: Vec<String> = serde_json::from_value(action.payload.clone())
+            .map_err(|e| anyhow::anyhow!("Invalid payload for {}: {}", action.id, e))?;
+
+        for account_id in &account_ids {
+            state
+                .reconciliation_service
+                .create_proposed_drafts(account_id)
+                .await?;
+        }
+
+        state.health_service.clear_cache().await;
+        return Ok(());
+    }
+
     state.health_service.execute_fix(&action).await?;
     Ok(())
 }
@@ -2,18 +2,25 @@ use std::sync::Arc;
 
 use crate::{
     api::shared::{enqueue_portfolio_job, PortfolioJobConfig},
-    error::ApiResult,
+    error::{ApiError, ApiResult},
     main_lib::AppState,
 };
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    http::{header, StatusCode},
+    response::Response,
     routing::{delete, get, post, put},
     Json, Router,
 };
+use chrono::NaiveDate;
 use wealthfolio_core::quotes::{
-    LatestQuoteSnapshot, MarketSyncMode, ProviderInfo, Quote, QuoteImport, SymbolSearchResult,
+    BulkImportOptions, BulkImportSummary, ConsensusConfig, LatestQuoteSnapshot, MarketSyncMode,
+    ProviderInfo, Quote, QuoteExportFormat, QuoteExportRequest, QuoteImport, QuoteProvenance,
+    QuoteQualityConfig, SymbolSearchResult, QUOTE_CONSENSUS_SETTINGS_KEY,
+    QUOTE_QUALITY_SETTINGS_KEY,
 };
+use wealthfolio_core::settings::SettingsServiceTrait;
 use wealthfolio_market_data::ExchangeInfo;
 
 async fn get_market_data_providers(
@@ -49,6 +56,57 @@ async fn update_market_data_provider_settings(
     Ok(StatusCode::NO_CONTENT)
 }
 
+async fn get_quote_consensus_settings(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<ConsensusConfig>> {
+    Ok(Json(state.quote_service.get_consensus_config()))
+}
+
+async fn update_quote_consensus_settings(
+    State(state): State<Arc<AppState>>,
+    Json(config): Json<ConsensusConfig>,
+) -> ApiResult<Json<ConsensusConfig>> {
+    if !(0.0..1.0).contains(&config.tolerance) {
+        return Err(ApiError::BadRequest(
+            "Tolerance must be between 0 and 1".to_string(),
+        ));
+    }
+    let json = serde_json::to_string(&config).map_err(|e| anyhow::anyhow!(e))?;
+    state
+        .settings_service
+        .set_setting_value(QUOTE_CONSENSUS_SETTINGS_KEY, &json)
+        .await?;
+    state
+        .quote_service
+        .update_consensus_config(config.clone())
+        .await?;
+    Ok(Json(config))
+}
+
+async fn get_quote_quality_settings(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<QuoteQualityConfig>> {
+    Ok(Json(state.quote_service.get_quality_config()))
+}
+
+async fn update_quote_quality_settings(
+    State(state): State<Arc<AppState>>,
+    Json(config): Json<QuoteQualityConfig>,
+) -> ApiResult<Json<QuoteQualityConfig>> {
+    config.validate()?;
+    let json = serde_json::to_string(&config).map_err(|e| anyhow::anyhow!(e))?;
+    state
+        .settings_service
+        .set_setting_value(QUOTE_QUALITY_SETTINGS_KEY, &json)
+        .await?;
+    state
+        .quote_service
+        .update_quality_config(config.clone())
+        .await?;
+    state.health_service.clear_cache().await;
+    Ok(Json(config))
+}
+
 #[derive(serde::Deserialize)]
 struct SearchQuery {
     query: String,
@@ -75,6 +133,30 @@ async fn get_quote_history(
     Ok(Json(res))
 }
 
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuoteProvenanceQuery {
+    asset_id: String,
+    start_date: String,
+    end_date: String,
+}
+
+async fn get_quote_provenance(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<QuoteProvenanceQuery>,
+) -> ApiResult<Json<Vec<QuoteProvenance>>> {
+    let parse = |s: &str| {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map_err(|e| ApiError::BadRequest(format!("Invalid date '{}': {}", s, e)))
+    };
+    let start = parse(&q.start_date)?;
+    let end = parse(&q.end_date)?;
+    let res = state
+        .quote_service
+        .get_quote_provenance(&q.asset_id, start, end)?;
+    Ok(Json(res))
+}
+
 async fn update_quote(
     Path(symbol): Path<String>,
     State(state): State<Arc<AppState>>,
@@ -170,6 +252,166 @@ async fn import_quotes_csv(
     Ok(Json(result))
 }
 
+/// Bulk import of a quote dump uploaded as multipart form data.
+///
+/// The `file` part is streamed to a temporary directory (keeping its original
+/// name, which may carry the ticker) and an optional `options` part holds
+/// JSON-encoded `BulkImportOptions`.
+async fn bulk_import_quotes(
+    State(state): State<Arc<AppState>>,
+    mut multipart: Multipart,
+) -> ApiResult<Json<BulkImportSummary>> {
+    let upload_dir = std::env::temp_dir().join(format!("wf_quote_import_{}", uuid::Uuid::new_v4()));
+    let result = receive_and_import(&state, &mut multipart, &upload_dir).await;
+    let _ = tokio::fs::remove_dir_all(&upload_dir).await;
+    let summary = result?;
+
+    if summary.imported > 0 {
+        enqueue_portfolio_job(
+            state,
+            PortfolioJobConfig {
+                account_ids: None,
+                market_sync_mode: MarketSyncMode::None,
+                force_full_recalculation: true,
+            },
+        );
+    }
+
+    Ok(Json(summary))
+}
+
+async fn receive_and_import(
+    state: &Arc<AppState>,
+    multipart: &mut Multipart,
+    upload_dir: &std::path::Path,
+) -> ApiResult<BulkImportSummary> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut file_path: Option<std::path::PathBuf> = None;
+    let mut options = BulkImportOptions::default();
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read multipart field: {}", e)))?
+    {
+        match field.name().unwrap_or("") {
+            "file" => {
+                let file_name = field
+                    .file_name()
+                    .and_then(|n| std::path::Path::new(n).file_name())
+                    .map(|n| n.to_string_lossy().to_string())
+                    .ok_or_else(|| ApiError::BadRequest("Missing file name".to_string()))?;
+                tokio::fs::create_dir_all(upload_dir).await.map_err(|e| {
+                    ApiError::Internal(format!("Failed to create upload dir: {}", e))
+                })?;
+                let path = upload_dir.join(file_name);
+                let mut file = tokio::fs::File::create(&path).await.map_err(|e| {
+                    ApiError::Internal(format!("Failed to create upload file: {}", e))
+                })?;
+                while let Some(chunk) = field.chunk().await.map_err(|e| {
+                    ApiError::BadRequest(format!("Failed to read file content: {}", e))
+                })? {
+                    file.write_all(&chunk).await.map_err(|e| {
+                        ApiError::Internal(format!("Failed to write upload: {}", e))
+                    })?;
+                }
+                file.flush()
+                    .await
+                    .map_err(|e| ApiError::Internal(format!("Failed to write upload: {}", e)))?;
+                file_path = Some(path);
+            }
+            "options" => {
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|e| ApiError::BadRequest(format!("Failed to read options: {}", e)))?;
+                options = serde_json::from_slice(&bytes)
+                    .map_err(|e| ApiError::BadRequest(format!("Invalid options JSON: {}", e)))?;
+            }
+            _ => {}
+        }
+    }
+
+    let path = file_path
+        .ok_or_else(|| ApiError::BadRequest("Missing file in multipart request".to_string()))?;
+    let summary = state
+        .quote_service
+        .bulk_import_quotes(&path, options)
+        .await?;
+    Ok(summary)
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarketDataExportQuery {
+    /// Comma-separated asset IDs.
+    asset_ids: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    format: Option<String>,
+    include_fx: Option<bool>,
+}
+
+/// Export via query string, e.g.
+/// `GET /market-data/export?assetIds=SEC:AAPL:XNAS&startDate=2020-01-01&format=parquet`.
+async fn export_market_data_query(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<MarketDataExportQuery>,
+) -> ApiResult<Response> {
+    let parse = |s: &str| {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map_err(|e| ApiError::BadRequest(format!("Invalid date '{}': {}", s, e)))
+    };
+    let request = QuoteExportRequest {
+        asset_ids: q
+            .asset_ids
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect(),
+        start_date: q.start_date.as_deref().map(parse).transpose()?,
+        end_date: q.end_date.as_deref().map(parse).transpose()?,
+        format: match q.format.as_deref() {
+            Some(format) => format.parse::<QuoteExportFormat>()?,
+            None => QuoteExportFormat::default(),
+        },
+        include_fx: q.include_fx.unwrap_or(false),
+    };
+    export_market_data_response(&state, &request)
+}
+
+async fn export_market_data(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<QuoteExportRequest>,
+) -> ApiResult<Response> {
+    export_market_data_response(&state, &request)
+}
+
+fn export_market_data_response(
+    state: &Arc<AppState>,
+    request: &QuoteExportRequest,
+) -> ApiResult<Response> {
+    let bytes = state.quote_service.export_market_data(request)?;
+    let file_name = format!(
+        "market-data-{}.{}",
+        chrono::Utc::now().format("%Y%m%d"),
+        request.format.extension()
+    );
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, request.format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file_name),
+        )
+        .body(Body::from(bytes))
+        .map_err(|e| ApiError::Internal(format!("Failed to build export response: {}", e)))
+}
+
 #[derive(serde::Deserialize)]
 struct SyncBody {
     #[serde(rename = "assetIds")]
@@ -263,14 +505,31 @@ pub fn router() -> Router<Arc<AppState>> {
             "/providers/settings",
             get(get_market_data_provider_settings).put(update_market_data_provider_settings),
         )
+        .route(
+            "/providers/consensus",
+            get(get_quote_consensus_settings).put(update_quote_consensus_settings),
+        )
+        .route(
+            "/providers/quality",
+            get(get_quote_quality_settings).put(update_quote_quality_settings),
+        )
         .route("/market-data/search", get(search_symbol))
         .route("/market-data/resolve-currency", get(resolve_symbol_quote))
         .route("/market-data/quotes/history", get(get_quote_history))
         .route("/market-data/quotes/latest", post(get_latest_quotes))
+        .route("/market-data/quotes/provenance", get(get_quote_provenance))
         .route("/market-data/quotes/{symbol}", put(update_quote))
         .route("/market-data/quotes/id/{id}", delete(delete_quote))
         .route("/market-data/quotes/check", post(check_quotes_import))
         .route("/market-data/quotes/import", post(import_quotes_csv))
+        .route(
+            "/market-data/quotes/bulk-import",
+            post(bulk_import_quotes).layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/market-data/export",
+            get(export_market_data_query).post(export_market_data),
+        )
         .route("/market-data/sync/history", post(sync_history_quotes))
         .route("/market-data/sync", post(sync_market_data))
 }
@@ -1,10 +1,15 @@
 use std::sync::Arc;
 
 use crate::{error::ApiResult, main_lib::AppState};
-use axum::{extract::State, routing::post, Json, Router};
+use axum::{
+    extract::{Query, State},
+    routing::post,
+    Json, Router,
+};
 use wealthfolio_core::{
     accounts::{AccountServiceTrait, TrackingMode},
     portfolio::{
+        fx_gains::RealizedFxGain,
         income::IncomeSummary,
         performance::{PerformanceMetrics, SimplePerformanceMetrics},
     },
@@ -98,6 +103,29 @@ async fn get_income_summary(
     Ok(Json(items))
 }
 
+#[derive(serde::Deserialize)]
+struct FxGainsQuery {
+    #[serde(rename = "accountId")]
+    account_id: Option<String>,
+    #[serde(rename = "startDate")]
+    start_date: Option<String>,
+    #[serde(rename = "endDate")]
+    end_date: Option<String>,
+}
+
+async fn get_realized_fx_gains(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<FxGainsQuery>,
+) -> ApiResult<Json<Vec<RealizedFxGain>>> {
+    let start = parse_date_optional(q.start_date, "startDate")?;
+    let end = parse_date_optional(q.end_date, "endDate")?;
+    let gains =
+        state
+            .fx_gains_service
+            .get_realized_fx_gains(q.account_id.as_deref(), start, end)?;
+    Ok(Json(gains))
+}
+
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .route(
@@ -107,4 +135,8 @@ pub fn router() -> Router<Arc<AppState>> {
         .route("/performance/history", post(calculate_performance_history))
         .route("/performance/summary", post(calculate_performance_summary))
         .route("/income/summary", axum::routing::get(get_income_summary))
+        .route(
+            "/fx-gains/realized",
+            axum::routing::get(get_realized_fx_gains),
+        )
 }
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::{error::ApiResult, main_lib::AppState};
+use axum::{
+    extract::{Path, State},
+    routing::{get, post},
+    Json, Router,
+};
+use wealthfolio_core::reconciliation::ReconciliationReport;
+
+async fn get_reconciliation_reports(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Vec<ReconciliationReport>>> {
+    let reports = state.reconciliation_service.reconcile_all().await?;
+    Ok(Json(reports))
+}
+
+async fn get_account_reconciliation(
+    Path(account_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Option<ReconciliationReport>>> {
+    let report = state
+        .reconciliation_service
+        .reconcile_account(&account_id)
+        .await?;
+    Ok(Json(report))
+}
+
+async fn create_reconciliation_drafts(
+    Path(account_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<usize>> {
+    let created = state
+        .reconciliation_service
+        .create_proposed_drafts(&account_id)
+        .await?;
+    state.health_service.clear_cache().await;
+    Ok(Json(created))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/reconciliation", get(get_reconciliation_reports))
+        .route(
+            "/reconciliation/{account_id}",
+            get(get_account_reconciliation),
+        )
+        .route(
+            "/reconciliation/{account_id}/drafts",
+            post(create_reconciliation_drafts),
+        )
+}
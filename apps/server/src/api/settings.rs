@@ -1,13 +1,15 @@
 use std::{collections::HashMap, path::Path as StdPath, sync::Arc};
 
 use crate::{
-    api::shared::{normalize_file_path, process_portfolio_job, PortfolioJobConfig},
+    api::shared::{
+        enqueue_portfolio_job, normalize_file_path, process_portfolio_job, PortfolioJobConfig,
+    },
     error::ApiResult,
     main_lib::AppState,
 };
 use anyhow::Context;
 use axum::{
-    extract::State,
+    extract::{DefaultBodyLimit, State},
     http::StatusCode,
     routing::{get, post},
     Json, Router,
@@ -21,7 +23,10 @@ use wealthfolio_core::{
     quotes::MarketSyncMode,
     settings::{Settings, SettingsServiceTrait, SettingsUpdate},
 };
-use wealthfolio_storage_sqlite::db;
+use wealthfolio_storage_sqlite::db::{
+    self,
+    archive::{self, ArchiveExport, ArchiveRestoreMode, ArchiveRestoreSummary, PortfolioArchive},
+};
 
 async fn get_settings(State(state): State<Arc<AppState>>) -> ApiResult<Json<Settings>> {
     let s = state.settings_service.get_settings()?;
@@ -309,6 +314,70 @@ async fn restore_database_route(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateArchiveBody {
+    passphrase: Option<String>,
+}
+
+async fn create_archive_route(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<CreateArchiveBody>,
+) -> ApiResult<Json<ArchiveExport>> {
+    let data_root = state.data_root.clone();
+    let archive = task::spawn_blocking(move || {
+        archive::create_archive(&data_root, body.passphrase.as_deref())
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to execute archive task: {}", e))??;
+
+    Ok(Json(ArchiveExport {
+        filename: archive.filename(),
+        archive,
+    }))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RestoreArchiveBody {
+    archive: PortfolioArchive,
+    passphrase: Option<String>,
+    #[serde(default)]
+    mode: ArchiveRestoreMode,
+}
+
+async fn restore_archive_route(
+    State(state): State<Arc<AppState>>,
+    Json(body): Json<RestoreArchiveBody>,
+) -> ApiResult<Json<ArchiveRestoreSummary>> {
+    let data_root = state.data_root.clone();
+    let summary = task::spawn_blocking(move || {
+        archive::restore_archive(
+            &data_root,
+            &body.archive,
+            body.passphrase.as_deref(),
+            body.mode,
+        )
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to execute archive restore task: {}", e))??;
+
+    // The archive may bring its own base currency along with the accounts
+    let settings = state.settings_service.get_settings()?;
+    *state.base_currency.write().unwrap() = settings.base_currency;
+
+    enqueue_portfolio_job(
+        state.clone(),
+        PortfolioJobConfig {
+            account_ids: None,
+            market_sync_mode: MarketSyncMode::Incremental { asset_ids: None },
+            force_full_recalculation: true,
+        },
+    );
+
+    Ok(Json(summary))
+}
+
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/settings", get(get_settings).put(update_settings))
@@ -324,4 +393,9 @@ pub fn router() -> Router<Arc<AppState>> {
             post(backup_database_to_path_route),
         )
         .route("/utilities/database/restore", post(restore_database_route))
+        .route("/utilities/database/archive", post(create_archive_route))
+        .route(
+            "/utilities/database/archive/restore",
+            post(restore_archive_route).layer(DefaultBodyLimit::disable()),
+        )
 }
@@ -124,7 +124,10 @@ pub async fn process_portfolio_job(
             Ok(result) => {
                 event_bus.publish(ServerEvent::with_payload(
                     MARKET_SYNC_COMPLETE,
-                    json!({ "failed_syncs": result.failed }),
+                    json!({
+                        "failed_syncs": result.failed,
+                        "quality_reports": result.quality_reports,
+                    }),
                 ));
                 tracing::info!("Market data sync completed in {:?}", sync_start.elapsed());
                 state.health_service.clear_cache().await;
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use crate::{error::ApiResult, main_lib::AppState};
+use axum::{
+    extract::{Path, State},
+    routing::{get, post},
+    Json, Router,
+};
+use wealthfolio_connect::{WalletConfig, WalletSyncResult};
+use wealthfolio_core::accounts::Account;
+
+async fn get_wallet_config(
+    Path(account_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Option<WalletConfig>>> {
+    let config = state.wallet_sync_service.get_wallet_config(&account_id)?;
+    Ok(Json(config))
+}
+
+async fn set_wallet_config(
+    Path(account_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Json(config): Json<WalletConfig>,
+) -> ApiResult<Json<Account>> {
+    let account = state
+        .wallet_sync_service
+        .set_wallet_config(&account_id, config)
+        .await?;
+    Ok(Json(account))
+}
+
+async fn sync_wallet(
+    Path(account_id): Path<String>,
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<WalletSyncResult>> {
+    let result = state.wallet_sync_service.sync_wallet(&account_id).await?;
+    Ok(Json(result))
+}
+
+async fn sync_all_wallets(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<Vec<WalletSyncResult>>> {
+    let results = state.wallet_sync_service.sync_all_wallets().await?;
+    Ok(Json(results))
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/wallets/sync", post(sync_all_wallets))
+        .route(
+            "/wallets/{account_id}/config",
+            get(get_wallet_config).put(set_wallet_config),
+        )
+        .route("/wallets/{account_id}/sync", post(sync_wallet))
+}
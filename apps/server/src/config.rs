@@ -11,6 +11,8 @@ pub struct Config {
     pub addons_root: String,
     pub secret_key: String,
     pub auth: Option<AuthConfig>,
+    /// Folder watched for broker exports (local file-drop sync); disabled when unset.
+    pub broker_drop_dir: Option<String>,
 }
 
 impl Config {
@@ -64,6 +66,10 @@ impl Config {
                     access_token_ttl: Duration::from_secs(ttl_minutes.saturating_mul(60)),
                 }
             });
+        let broker_drop_dir = std::env::var("WF_BROKER_DROP_DIR")
+            .ok()
+            .map(|dir| dir.trim().to_string())
+            .filter(|dir| !dir.is_empty());
         Self {
             listen_addr,
             db_path,
@@ -73,6 +79,7 @@ impl Config {
             addons_root,
             secret_key,
             auth,
+            broker_drop_dir,
         }
     }
 }
@@ -10,6 +10,7 @@ use std::time::Duration;
 
 use tokio::sync::mpsc;
 use wealthfolio_connect::BrokerSyncServiceTrait;
+use wealthfolio_core::audit::{with_audit_context, AuditContext, AuditSource};
 use wealthfolio_core::{assets::AssetServiceTrait, events::DomainEvent, secrets::SecretStore};
 
 use super::planner::{plan_asset_enrichment, plan_broker_sync, plan_portfolio_job};
@@ -142,7 +143,8 @@ async fn process_event_batch(events: &[DomainEvent], deps: Arc<QueueWorkerDeps>)
 
         let asset_service = deps.asset_service.clone();
         tokio::spawn(async move {
-            match asset_service.enrich_assets(enrichment_assets).await {
+            let enrichment = asset_service.enrich_assets(enrichment_assets);
+            match with_audit_context(AuditContext::new(AuditSource::System), enrichment).await {
                 Ok((enriched, skipped, failed)) => {
                     tracing::info!(
                         "Asset enrichment complete: {} enriched, {} skipped, {} failed",
@@ -230,7 +232,10 @@ async fn run_portfolio_job(
             Ok(result) => {
                 event_bus.publish(ServerEvent::with_payload(
                     MARKET_SYNC_COMPLETE,
-                    json!({ "failed_syncs": result.failed }),
+                    json!({
+                        "failed_syncs": result.failed,
+                        "quality_reports": result.quality_reports,
+                    }),
                 ));
                 tracing::info!("Market data sync completed in {:?}", sync_start.elapsed());
                 deps.health_service.clear_cache().await;
@@ -530,5 +535,9 @@ async fn perform_broker_sync(
 
     // Run the sync via the centralized orchestrator
     // Note: Asset enrichment is handled automatically via domain events (AssetsCreated)
-    orchestrator.sync_all(&client).await
+    with_audit_context(
+        AuditContext::broker_sync(None),
+        orchestrator.sync_all(&client),
+    )
+    .await
 }
@@ -50,6 +50,15 @@ async fn main() -> anyhow::Result<()> {
     // Start background broker sync scheduler (4-hour interval)
     scheduler::start_broker_sync_scheduler(state.clone());
 
+    // Sync broker exports dropped into WF_BROKER_DROP_DIR
+    scheduler::start_local_broker_sync_scheduler(state.clone());
+
+    // Record equity grant vests that are due (daily)
+    scheduler::start_vest_sync_scheduler(state.clone());
+
+    // Draft accrued cash interest on payment dates (daily)
+    scheduler::start_cash_interest_scheduler(state.clone());
+
     let static_dir = std::path::PathBuf::from(&config.static_dir);
     let index_file = static_dir.join("index.html");
     let static_service = ServeDir::new(static_dir).fallback(ServeFile::new(index_file));
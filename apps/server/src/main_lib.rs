@@ -11,22 +11,29 @@ use tracing_subscriber::{fmt, EnvFilter};
 use wealthfolio_ai::{AiProviderService, AiProviderServiceTrait, ChatConfig, ChatService};
 use wealthfolio_connect::{
     BrokerSyncService, BrokerSyncServiceTrait, CoreImportRunRepositoryAdapter,
-    ImportRunRepositoryTrait,
+    ImportRunRepositoryTrait, WalletSyncService, WalletSyncServiceTrait,
 };
 use wealthfolio_core::addons::{AddonService, AddonServiceTrait};
 use wealthfolio_core::{
     accounts::AccountService,
-    activities::{ActivityService as CoreActivityService, ActivityServiceTrait},
+    activities::{
+        ActivityService as CoreActivityService, ActivityServiceTrait, ImportReviewService,
+        ImportReviewServiceTrait,
+    },
     assets::{
         AlternativeAssetRepositoryTrait, AlternativeAssetService, AlternativeAssetServiceTrait,
         AssetClassificationService, AssetService, AssetServiceTrait,
     },
+    audit::{AuditService, AuditServiceTrait},
+    cash_interest::{CashInterestService, CashInterestServiceTrait},
+    equity_grants::{EquityGrantService, EquityGrantServiceTrait},
     events::DomainEventSink,
     fx::{FxService, FxServiceTrait},
     goals::{GoalService, GoalServiceTrait},
     health::{HealthService, HealthServiceTrait},
     limits::{ContributionLimitService, ContributionLimitServiceTrait},
     portfolio::allocation::{AllocationService, AllocationServiceTrait},
+    portfolio::fx_gains::{FxGainsService, FxGainsServiceTrait},
     portfolio::income::{IncomeService, IncomeServiceTrait},
     portfolio::{
         holdings::{
@@ -34,10 +41,14 @@ use wealthfolio_core::{
             HoldingsServiceTrait,
         },
         net_worth::{NetWorthService, NetWorthServiceTrait},
-        snapshot::{SnapshotService, SnapshotServiceTrait},
+        snapshot::{HoldingsCalculator, SnapshotService, SnapshotServiceTrait},
         valuation::{ValuationService, ValuationServiceTrait},
     },
-    quotes::{QuoteService, QuoteServiceTrait},
+    quotes::{
+        ConsensusConfig, QuoteQualityConfig, QuoteService, QuoteServiceTrait,
+        QUOTE_CONSENSUS_SETTINGS_KEY, QUOTE_QUALITY_SETTINGS_KEY,
+    },
+    reconciliation::{HoldingsReconciliationService, HoldingsReconciliationServiceTrait},
     secrets::SecretStore,
     settings::{SettingsRepositoryTrait, SettingsService, SettingsServiceTrait},
     taxonomies::{TaxonomyService, TaxonomyServiceTrait},
@@ -48,7 +59,9 @@ use wealthfolio_storage_sqlite::{
     activities::ActivityRepository,
     ai_chat::AiChatRepository,
     assets::{AlternativeAssetRepository, AssetRepository},
+    audit::AuditLogRepository,
     db::{self, write_actor},
+    equity_grants::EquityGrantRepository,
     fx::FxRepository,
     goals::GoalRepository,
     health::HealthDismissalRepository,
@@ -85,20 +98,28 @@ pub struct AppState {
     pub performance_service:
         Arc<dyn wealthfolio_core::portfolio::performance::PerformanceServiceTrait + Send + Sync>,
     pub income_service: Arc<dyn IncomeServiceTrait + Send + Sync>,
+    pub fx_gains_service: Arc<dyn FxGainsServiceTrait + Send + Sync>,
     pub goal_service: Arc<dyn GoalServiceTrait + Send + Sync>,
     pub limits_service: Arc<dyn ContributionLimitServiceTrait + Send + Sync>,
     pub fx_service: Arc<dyn FxServiceTrait + Send + Sync>,
     pub activity_service: Arc<dyn ActivityServiceTrait + Send + Sync>,
+    pub import_review_service: Arc<dyn ImportReviewServiceTrait + Send + Sync>,
     pub asset_service: Arc<dyn AssetServiceTrait + Send + Sync>,
     pub taxonomy_service: Arc<dyn TaxonomyServiceTrait + Send + Sync>,
     pub net_worth_service: Arc<dyn NetWorthServiceTrait + Send + Sync>,
     pub alternative_asset_service: Arc<dyn AlternativeAssetServiceTrait + Send + Sync>,
+    pub equity_grant_service: Arc<dyn EquityGrantServiceTrait + Send + Sync>,
+    pub cash_interest_service: Arc<dyn CashInterestServiceTrait + Send + Sync>,
+    pub reconciliation_service: Arc<dyn HoldingsReconciliationServiceTrait + Send + Sync>,
+    pub audit_service: Arc<dyn AuditServiceTrait + Send + Sync>,
     pub addon_service: Arc<dyn AddonServiceTrait + Send + Sync>,
     pub connect_sync_service: Arc<dyn BrokerSyncServiceTrait + Send + Sync>,
+    pub wallet_sync_service: Arc<dyn WalletSyncServiceTrait + Send + Sync>,
     pub ai_provider_service: Arc<dyn AiProviderServiceTrait + Send + Sync>,
     pub ai_chat_service: Arc<ChatService<ServerAiEnvironment>>,
     pub data_root: String,
     pub db_path: String,
+    pub broker_drop_dir: Option<String>,
     pub instance_id: String,
     pub secret_store: Arc<dyn SecretStore>,
     pub event_bus: EventBus,
@@ -203,6 +224,28 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
         .await?,
     );
 
+    // Apply persisted dual-provider consensus settings (disabled by default)
+    if let Some(json) = settings_service.get_setting_value(QUOTE_CONSENSUS_SETTINGS_KEY)? {
+        match serde_json::from_str::<ConsensusConfig>(&json) {
+            Ok(config) => quote_service.update_consensus_config(config).await?,
+            Err(e) => tracing::warn!("Ignoring invalid quote consensus settings: {}", e),
+        }
+    }
+
+    // Apply persisted quote quality thresholds
+    if let Some(json) = settings_service.get_setting_value(QUOTE_QUALITY_SETTINGS_KEY)? {
+        let applied = match serde_json::from_str::<QuoteQualityConfig>(&json) {
+            Ok(config) => quote_service
+                .update_quality_config(config)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = applied {
+            tracing::warn!("Ignoring invalid quote quality settings: {}", e);
+        }
+    }
+
     // Create taxonomy service for auto-classification
     let taxonomy_repository = Arc::new(TaxonomyRepository::new(pool.clone(), writer.clone()));
     let taxonomy_service = Arc::new(TaxonomyService::new(taxonomy_repository));
@@ -247,10 +290,12 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
             fx_service.clone(),
         ));
 
-    let holdings_valuation_service = Arc::new(HoldingsValuationService::new(
-        fx_service.clone(),
-        quote_service.clone(),
-    ));
+    let equity_grant_repository =
+        Arc::new(EquityGrantRepository::new(pool.clone(), writer.clone()));
+    let holdings_valuation_service = Arc::new(
+        HoldingsValuationService::new(fx_service.clone(), quote_service.clone())
+            .with_equity_grants(equity_grant_repository.clone()),
+    );
     let classification_service =
         Arc::new(AssetClassificationService::new(taxonomy_service.clone()));
     let holdings_service = Arc::new(HoldingsService::new(
@@ -268,6 +313,8 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
         wealthfolio_core::portfolio::performance::PerformanceService::new(
             valuation_service.clone(),
             quote_service.clone(),
+            snapshot_service.clone(),
+            fx_service.clone(),
         ),
     );
 
@@ -307,9 +354,18 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
             asset_service.clone(),
             fx_service.clone(),
             quote_service.clone(),
-            core_import_run_repository,
+            core_import_run_repository.clone(),
         )
-        .with_event_sink(domain_event_sink.clone()),
+        .with_event_sink(domain_event_sink.clone())
+        .with_holdings_calculator(HoldingsCalculator::new(
+            fx_service.clone(),
+            base_currency.clone(),
+            asset_repository.clone(),
+        )),
+    );
+    let import_review_service: Arc<dyn ImportReviewServiceTrait + Send + Sync> = Arc::new(
+        ImportReviewService::new(activity_repository.clone(), core_import_run_repository)
+            .with_event_sink(domain_event_sink.clone()),
     );
 
     // Alternative asset repository for alternative assets operations
@@ -329,6 +385,58 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
         .with_event_sink(domain_event_sink.clone()),
     );
 
+    // Equity grant service (vest events are recorded as activities)
+    let equity_grant_service: Arc<dyn EquityGrantServiceTrait + Send + Sync> =
+        Arc::new(EquityGrantService::new(
+            equity_grant_repository,
+            asset_repository.clone(),
+            quote_service.clone(),
+            activity_service.clone(),
+        ));
+
+    // Realized FX gains of currency conversions (average cost per currency)
+    let fx_gains_service: Arc<dyn FxGainsServiceTrait + Send + Sync> =
+        Arc::new(FxGainsService::new(
+            account_service.clone(),
+            activity_service.clone(),
+            snapshot_service.clone(),
+            fx_service.clone(),
+            base_currency.clone(),
+        ));
+
+    // Cash interest accrual (settings live in the account metadata)
+    let cash_interest_service: Arc<dyn CashInterestServiceTrait + Send + Sync> =
+        Arc::new(CashInterestService::new(
+            account_service.clone(),
+            activity_service.clone(),
+            snapshot_service.clone(),
+        ));
+
+    // Watch-only crypto wallet sync (same import-run bookkeeping as broker sync)
+    let wallet_sync_service: Arc<dyn WalletSyncServiceTrait + Send + Sync> =
+        Arc::new(WalletSyncService::new(
+            account_service.clone(),
+            activity_service.clone(),
+            import_run_repository.clone(),
+            broker_sync_state_repository.clone(),
+        ));
+
+    // Broker-reported holdings against positions computed from activities
+    let reconciliation_service: Arc<dyn HoldingsReconciliationServiceTrait + Send + Sync> =
+        Arc::new(HoldingsReconciliationService::new(
+            account_service.clone(),
+            activity_service.clone(),
+            snapshot_service.clone(),
+            asset_service.clone(),
+            settings_service.clone(),
+        ));
+
+    // Change history of activities, accounts and assets
+    let audit_repository = Arc::new(AuditLogRepository::new(pool.clone(), writer.clone()));
+    let audit_service: Arc<dyn AuditServiceTrait + Send + Sync> = Arc::new(
+        AuditService::new(audit_repository).with_event_sink(domain_event_sink.clone()),
+    );
+
     // Connect sync service for broker data synchronization
     let platform_repository = Arc::new(PlatformRepository::new(pool.clone(), writer.clone()));
     let connect_sync_service: Arc<dyn BrokerSyncServiceTrait + Send + Sync> = Arc::new(
@@ -343,7 +451,8 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
             snapshot_repository.clone(),
         )
         .with_event_sink(domain_event_sink.clone())
-        .with_snapshot_service(snapshot_service.clone()),
+        .with_snapshot_service(snapshot_service.clone())
+        .with_reconciliation_service(reconciliation_service.clone()),
     );
 
     // Determine data root directory (parent of DB path)
@@ -439,20 +548,28 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
         snapshot_repository,
         performance_service,
         income_service,
+        fx_gains_service,
         goal_service,
         limits_service,
         fx_service: fx_service.clone(),
         activity_service,
+        import_review_service,
         asset_service,
         taxonomy_service,
         net_worth_service,
         alternative_asset_service,
+        equity_grant_service,
+        cash_interest_service,
+        reconciliation_service,
+        audit_service,
         addon_service,
         connect_sync_service,
+        wallet_sync_service,
         ai_provider_service,
         ai_chat_service,
         data_root,
         db_path,
+        broker_drop_dir: config.broker_drop_dir.clone(),
         instance_id: settings.instance_id,
         secret_store,
         event_bus,
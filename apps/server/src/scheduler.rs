@@ -1,6 +1,7 @@
-//! Background scheduler for periodic broker sync.
+//! Background schedulers for periodic broker sync and equity grant vests.
 //!
-//! Runs a fixed 4-hour interval sync for the Docker/Web server.
+//! Runs a fixed 4-hour interval sync for the Docker/Web server, and watches
+//! the local broker drop folder when one is configured.
 
 use std::sync::Arc;
 
@@ -95,3 +96,93 @@ async fn run_scheduled_sync(state: &Arc<AppState>) {
         }
     }
 }
+
+/// Interval between checks of the local broker drop folder: 5 minutes.
+#[cfg(any(feature = "connect-sync", feature = "device-sync"))]
+const LOCAL_DROP_POLL_SECS: u64 = 5 * 60;
+
+/// Watches the local broker drop folder (`WF_BROKER_DROP_DIR`) and syncs it
+/// whenever a file was added or changed since the last check. Polling keeps
+/// this working on network shares and bind mounts where file events are not
+/// delivered.
+#[cfg(any(feature = "connect-sync", feature = "device-sync"))]
+pub fn start_local_broker_sync_scheduler(state: Arc<AppState>) {
+    let Some(drop_dir) = state.broker_drop_dir.clone() else {
+        return;
+    };
+    tokio::spawn(async move {
+        tracing::info!("Watching broker drop folder {}", drop_dir);
+        let root = std::path::PathBuf::from(&drop_dir);
+        let mut last_seen = None;
+        let mut poll_interval =
+            tokio::time::interval(std::time::Duration::from_secs(LOCAL_DROP_POLL_SECS));
+        loop {
+            poll_interval.tick().await;
+            let latest = wealthfolio_connect::broker::local_files::latest_modification(&root);
+            if latest.is_none() || latest == last_seen {
+                continue;
+            }
+            match crate::api::connect::perform_local_broker_sync(&state).await {
+                Ok(result) => {
+                    last_seen = latest;
+                    tracing::info!("Local broker sync completed: {}", result.message);
+                }
+                Err(e) => tracing::warn!("Local broker sync failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Watches the local broker drop folder (`WF_BROKER_DROP_DIR`).
+#[cfg(not(any(feature = "connect-sync", feature = "device-sync")))]
+pub fn start_local_broker_sync_scheduler(_state: Arc<AppState>) {
+    tracing::info!("Local broker sync disabled: sync API is not compiled");
+}
+
+/// Interval between equity grant vest syncs: daily.
+const VEST_SYNC_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// Starts the background job recording vested equity grant shares as activities.
+///
+/// The first run happens at startup so vests that occurred while the server was
+/// down are picked up.
+pub fn start_vest_sync_scheduler(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut sync_interval =
+            tokio::time::interval(std::time::Duration::from_secs(VEST_SYNC_INTERVAL_SECS));
+        loop {
+            sync_interval.tick().await;
+            let today = chrono::Utc::now().date_naive();
+            match state.equity_grant_service.sync_vest_activities(today).await {
+                Ok(0) => {}
+                Ok(created) => tracing::info!("Recorded {} equity grant vests", created),
+                Err(e) => tracing::warn!("Equity grant vest sync failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Interval between cash interest draft runs: daily.
+const CASH_INTEREST_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// Starts the background job creating `INTEREST` drafts for accounts with cash
+/// interest settings once a payment date has passed.
+pub fn start_cash_interest_scheduler(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(CASH_INTEREST_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            let today = chrono::Utc::now().date_naive();
+            match state
+                .cash_interest_service
+                .generate_interest_drafts(today)
+                .await
+            {
+                Ok(0) => {}
+                Ok(created) => tracing::info!("Created {} cash interest drafts", created),
+                Err(e) => tracing::warn!("Cash interest draft run failed: {}", e),
+            }
+        }
+    });
+}
@@ -5,9 +5,10 @@ use crate::context::ServiceContext;
 use log::debug;
 use tauri::State;
 use wealthfolio_core::activities::{
-    Activity, ActivityBulkMutationRequest, ActivityBulkMutationResult, ActivityImport,
-    ActivitySearchResponse, ActivityUpdate, ImportActivitiesResult, ImportMappingData, NewActivity,
-    ParseConfig, ParsedCsvResult, Sort,
+    Activity, ActivityBulkEditRequest, ActivityBulkEditResult, ActivityBulkMutationRequest,
+    ActivityBulkMutationResult, ActivityImport, ActivitySearchResponse, ActivityUpdate,
+    BrokerParserInfo, BrokerStatement, ImportActivitiesResult, ImportMappingData, ImportRunChange,
+    ImportRunRevertResult, NewActivity, ParseConfig, ParsedCsvResult, ProbableDuplicate, Sort,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -112,6 +113,20 @@ pub async fn save_activities(
         .map_err(|e| e.to_string())
 }
 
+/// Edits every activity matching a query, or previews the edit when `dry_run` is set.
+#[tauri::command]
+pub async fn bulk_edit_activities(
+    request: ActivityBulkEditRequest,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<ActivityBulkEditResult, String> {
+    debug!("Bulk activity edit request (dry run: {})", request.dry_run);
+    state
+        .activity_service()
+        .bulk_edit_activities(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_account_import_mapping(
     account_id: String,
@@ -197,3 +212,131 @@ pub async fn parse_csv(
             e.to_string()
         })
 }
+
+#[tauri::command]
+pub async fn parse_ibkr_flex(
+    account_id: String,
+    content: Vec<u8>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ActivityImport>, String> {
+    debug!(
+        "Parsing IBKR Flex statement with {} bytes for account: {}",
+        content.len(),
+        account_id
+    );
+    state
+        .activity_service()
+        .parse_ibkr_flex(&account_id, &content)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn parse_broker_statement(
+    account_id: String,
+    content: Vec<u8>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<BrokerStatement, String> {
+    debug!(
+        "Parsing broker statement with {} bytes for account: {}",
+        content.len(),
+        account_id
+    );
+    state
+        .activity_service()
+        .parse_broker_statement(&account_id, &content)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_broker_parsers(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<BrokerParserInfo>, String> {
+    Ok(state.activity_service().list_broker_parsers())
+}
+
+#[tauri::command]
+pub async fn get_import_run_changes(
+    import_run_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ImportRunChange>, String> {
+    debug!("Fetching changes of import run {}", import_run_id);
+    state
+        .import_review_service()
+        .get_import_run_changes(&import_run_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn approve_import_run_changes(
+    import_run_id: String,
+    change_ids: Vec<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<usize, String> {
+    debug!(
+        "Approving {} changes of import run {}",
+        change_ids.len(),
+        import_run_id
+    );
+    state
+        .import_review_service()
+        .approve_import_run_changes(&import_run_id, change_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn reject_import_run_changes(
+    import_run_id: String,
+    change_ids: Vec<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<ImportRunRevertResult, String> {
+    debug!(
+        "Rejecting {} changes of import run {}",
+        change_ids.len(),
+        import_run_id
+    );
+    state
+        .import_review_service()
+        .reject_import_run_changes(&import_run_id, change_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn rollback_import_run(
+    import_run_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<ImportRunRevertResult, String> {
+    debug!("Rolling back import run {}", import_run_id);
+    state
+        .import_review_service()
+        .rollback_import_run(&import_run_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn find_probable_duplicates(
+    account_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ProbableDuplicate>, String> {
+    debug!("Scanning for probable duplicate activities...");
+    state
+        .activity_service()
+        .find_probable_duplicates(account_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn merge_duplicate_activities(
+    keep_id: String,
+    duplicate_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Activity, String> {
+    debug!("Merging activity {} into {}", duplicate_id, keep_id);
+    state
+        .activity_service()
+        .merge_duplicate_activities(&keep_id, &duplicate_id)
+        .await
+        .map_err(|e| e.to_string())
+}
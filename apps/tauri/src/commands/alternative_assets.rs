@@ -17,9 +17,10 @@ use tauri::State;
 use crate::context::ServiceContext;
 
 use wealthfolio_core::assets::{
-    AssetKind, CreateAlternativeAssetRequest as CoreCreateRequest,
-    LinkLiabilityRequest as CoreLinkRequest, UpdateAssetDetailsRequest as CoreUpdateDetailsRequest,
-    UpdateValuationRequest as CoreValuationRequest,
+    AmortizationSchedule, AssetKind, CreateAlternativeAssetRequest as CoreCreateRequest,
+    LinkLiabilityRequest as CoreLinkRequest, LoanTerms, NewPropertyCashFlow, PropertyCashFlow,
+    RentalPerformance, UpdateAssetDetailsRequest as CoreUpdateDetailsRequest,
+    UpdateValuationRequest as CoreValuationRequest, ValuationModel,
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -150,6 +151,16 @@ pub struct StaleAssetInfo {
     pub days_stale: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyEquity {
+    pub asset_id: String,
+    pub name: Option<String>,
+    pub value: String,
+    pub linked_liabilities: String,
+    pub equity: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NetWorthResponse {
@@ -160,6 +171,7 @@ pub struct NetWorthResponse {
     pub currency: String,
     pub oldest_valuation_date: Option<String>,
     pub stale_assets: Vec<StaleAssetInfo>,
+    pub property_equity: Vec<PropertyEquity>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -376,6 +388,116 @@ pub async fn unlink_liability(
     Ok(())
 }
 
+/// Attaches loan terms to a liability, or clears them when `terms` is null.
+#[tauri::command]
+pub async fn set_loan_terms(
+    liability_id: String,
+    terms: Option<LoanTerms>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Option<AmortizationSchedule>, String> {
+    state
+        .alternative_asset_service()
+        .set_loan_terms(&liability_id, terms)
+        .await
+        .map_err(|e| {
+            error!("Failed to set loan terms: {}", e);
+            format!("Failed to set loan terms: {}", e)
+        })
+}
+
+/// Gets the amortization schedule of a liability with loan terms.
+#[tauri::command]
+pub async fn get_loan_schedule(
+    liability_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Option<AmortizationSchedule>, String> {
+    state
+        .alternative_asset_service()
+        .get_loan_schedule(&liability_id)
+        .map_err(|e| format!("Failed to get loan schedule: {}", e))
+}
+
+/// Sets the valuation model of an alternative asset, or clears it when `model` is null.
+#[tauri::command]
+pub async fn set_valuation_model(
+    asset_id: String,
+    model: Option<ValuationModel>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    state
+        .alternative_asset_service()
+        .set_valuation_model(&asset_id, model)
+        .await
+        .map_err(|e| {
+            error!("Failed to set valuation model: {}", e);
+            format!("Failed to set valuation model: {}", e)
+        })
+}
+
+/// Records rental income, an operating expense or a mortgage payment on a property.
+#[tauri::command]
+pub async fn add_property_cash_flow(
+    new_flow: NewPropertyCashFlow,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<PropertyCashFlow, String> {
+    state
+        .alternative_asset_service()
+        .add_property_cash_flow(new_flow)
+        .await
+        .map_err(|e| {
+            error!("Failed to add property cash flow: {}", e);
+            format!("Failed to add property cash flow: {}", e)
+        })
+}
+
+/// Deletes a property cash-flow entry.
+#[tauri::command]
+pub async fn delete_property_cash_flow(
+    cash_flow_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    state
+        .alternative_asset_service()
+        .delete_property_cash_flow(&cash_flow_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete property cash flow: {}", e);
+            format!("Failed to delete property cash flow: {}", e)
+        })
+}
+
+/// Lists the cash-flow entries of a property.
+#[tauri::command]
+pub fn get_property_cash_flows(
+    asset_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<PropertyCashFlow>, String> {
+    state
+        .alternative_asset_service()
+        .get_property_cash_flows(&asset_id)
+        .map_err(|e| format!("Failed to get property cash flows: {}", e))
+}
+
+/// Gets rental metrics of a property for the 12 months ending `as_of` (defaults to today).
+#[tauri::command]
+pub fn get_rental_performance(
+    asset_id: String,
+    as_of: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<RentalPerformance, String> {
+    let as_of = match as_of {
+        Some(d) => {
+            NaiveDate::parse_from_str(&d, "%Y-%m-%d").map_err(|e| format!("Invalid date: {}", e))?
+        }
+        None => Utc::now().date_naive(),
+    };
+
+    state
+        .alternative_asset_service()
+        .get_rental_performance(&asset_id, as_of)
+        .map_err(|e| format!("Failed to get rental performance: {}", e))
+}
+
 /// Gets all alternative holdings (assets with their latest valuations).
 #[tauri::command]
 pub async fn get_alternative_holdings(
@@ -484,6 +606,17 @@ pub async fn get_net_worth(
                 days_stale: s.days_stale,
             })
             .collect(),
+        property_equity: core_response
+            .property_equity
+            .into_iter()
+            .map(|p| PropertyEquity {
+                asset_id: p.asset_id,
+                name: p.name,
+                value: p.value.to_string(),
+                linked_liabilities: p.linked_liabilities.to_string(),
+                equity: p.equity.to_string(),
+            })
+            .collect(),
     })
 }
 
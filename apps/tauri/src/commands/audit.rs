@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use chrono::{DateTime, Utc};
+use log::debug;
+use serde_json::Value;
+use tauri::State;
+use wealthfolio_core::audit::{AuditEntityType, AuditLogEntry};
+
+/// Recorded changes of one activity, account or asset, newest first.
+#[tauri::command]
+pub async fn get_entity_history(
+    entity_type: AuditEntityType,
+    entity_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<AuditLogEntry>, String> {
+    state
+        .audit_service()
+        .get_entity_history(entity_type, &entity_id)
+        .map_err(|e| format!("Failed to load history: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_recent_changes(
+    entity_type: Option<AuditEntityType>,
+    limit: Option<i64>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<AuditLogEntry>, String> {
+    state
+        .audit_service()
+        .get_recent_changes(entity_type, limit)
+        .map_err(|e| format!("Failed to load recent changes: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_entity_version_at(
+    entity_type: AuditEntityType,
+    entity_id: String,
+    at: DateTime<Utc>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Option<Value>, String> {
+    state
+        .audit_service()
+        .get_entity_version_at(entity_type, &entity_id, at)
+        .map_err(|e| format!("Failed to load version: {}", e))
+}
+
+/// Restores the version left by an audit entry. Returns the entry recording the restore.
+#[tauri::command]
+pub async fn restore_audit_version(
+    entry_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AuditLogEntry, String> {
+    debug!("Restoring version of audit entry {}", entry_id);
+    state
+        .audit_service()
+        .restore_version(&entry_id)
+        .await
+        .map_err(|e| format!("Failed to restore version: {}", e))
+}
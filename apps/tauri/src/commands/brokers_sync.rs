@@ -11,6 +11,7 @@ use wealthfolio_connect::{
     PlansResponse, Platform, SyncConfig, SyncOrchestrator, SyncProgressPayload,
     SyncProgressReporter, SyncResult, UserInfo,
 };
+use wealthfolio_core::audit::{with_audit_context, AuditContext};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Tauri Progress Reporter
@@ -131,12 +132,20 @@ pub async fn perform_broker_sync(
         let reporter = Arc::new(TauriProgressReporter::new(app_handle.clone()));
         let orchestrator =
             SyncOrchestrator::new(context.sync_service(), reporter, SyncConfig::default());
-        orchestrator.sync_all(&client).await
+        with_audit_context(
+            AuditContext::broker_sync(None),
+            orchestrator.sync_all(&client),
+        )
+        .await
     } else {
         let reporter = Arc::new(wealthfolio_connect::NoOpProgressReporter);
         let orchestrator =
             SyncOrchestrator::new(context.sync_service(), reporter, SyncConfig::default());
-        orchestrator.sync_all(&client).await
+        with_audit_context(
+            AuditContext::broker_sync(None),
+            orchestrator.sync_all(&client),
+        )
+        .await
     }
 }
 
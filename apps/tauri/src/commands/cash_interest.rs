@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use chrono::{NaiveDate, Utc};
+use log::debug;
+use tauri::State;
+use wealthfolio_core::accounts::Account;
+use wealthfolio_core::cash_interest::{
+    CashInterestAccrual, CashInterestSettings, CashYieldSummary,
+};
+
+fn parse_as_of(as_of: Option<String>) -> Result<NaiveDate, String> {
+    match as_of {
+        Some(d) => {
+            NaiveDate::parse_from_str(&d, "%Y-%m-%d").map_err(|e| format!("Invalid date: {}", e))
+        }
+        None => Ok(Utc::now().date_naive()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_cash_interest_settings(
+    account_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Option<CashInterestSettings>, String> {
+    state
+        .cash_interest_service()
+        .get_settings(&account_id)
+        .map_err(|e| format!("Failed to load cash interest settings: {}", e))
+}
+
+/// Saves the account's cash interest settings; `null` removes them.
+#[tauri::command]
+pub async fn set_cash_interest_settings(
+    account_id: String,
+    settings: Option<CashInterestSettings>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Account, String> {
+    debug!("Setting cash interest settings for account {}", account_id);
+    state
+        .cash_interest_service()
+        .set_settings(&account_id, settings)
+        .await
+        .map_err(|e| format!("Failed to save cash interest settings: {}", e))
+}
+
+/// Gets the interest accrued in the current payment period (defaults to today).
+#[tauri::command]
+pub async fn get_cash_interest_accrual(
+    account_id: String,
+    as_of: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<CashInterestAccrual, String> {
+    let as_of = parse_as_of(as_of)?;
+    state
+        .cash_interest_service()
+        .get_accrual(&account_id, as_of)
+        .map_err(|e| format!("Failed to get cash interest accrual: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_cash_yield_comparison(
+    as_of: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<CashYieldSummary>, String> {
+    let as_of = parse_as_of(as_of)?;
+    state
+        .cash_interest_service()
+        .get_yield_comparison(as_of)
+        .map_err(|e| format!("Failed to compare cash yields: {}", e))
+}
+
+/// Creates `INTEREST` drafts for payment dates up to `as_of` (defaults to today).
+#[tauri::command]
+pub async fn generate_cash_interest_drafts(
+    as_of: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<usize, String> {
+    let as_of = parse_as_of(as_of)?;
+    state
+        .cash_interest_service()
+        .generate_interest_drafts(as_of)
+        .await
+        .map_err(|e| format!("Failed to generate cash interest drafts: {}", e))
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use chrono::{NaiveDate, Utc};
+use log::debug;
+use tauri::State;
+use wealthfolio_core::equity_grants::{EquityGrant, EquityGrantSummary, NewEquityGrant, VestEvent};
+
+fn parse_as_of(as_of: Option<String>) -> Result<NaiveDate, String> {
+    match as_of {
+        Some(d) => {
+            NaiveDate::parse_from_str(&d, "%Y-%m-%d").map_err(|e| format!("Invalid date: {}", e))
+        }
+        None => Ok(Utc::now().date_naive()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_equity_grants(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<EquityGrant>, String> {
+    debug!("Fetching equity grants...");
+    state
+        .equity_grant_service()
+        .get_equity_grants()
+        .map_err(|e| format!("Failed to load equity grants: {}", e))
+}
+
+/// Gets every grant with its vested/unvested position as of a date (defaults to today).
+#[tauri::command]
+pub async fn get_equity_grant_summaries(
+    as_of: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<EquityGrantSummary>, String> {
+    let as_of = parse_as_of(as_of)?;
+    state
+        .equity_grant_service()
+        .get_equity_grant_summaries(as_of)
+        .map_err(|e| format!("Failed to load equity grant summaries: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_vesting_schedule(
+    grant_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<VestEvent>, String> {
+    state
+        .equity_grant_service()
+        .get_vesting_schedule(&grant_id)
+        .map_err(|e| format!("Failed to get vesting schedule: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_equity_grant(
+    grant: NewEquityGrant,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<EquityGrant, String> {
+    debug!("Creating equity grant...");
+    state
+        .equity_grant_service()
+        .create_equity_grant(grant)
+        .await
+        .map_err(|e| format!("Failed to create equity grant: {}", e))
+}
+
+#[tauri::command]
+pub async fn update_equity_grant(
+    grant: EquityGrant,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<EquityGrant, String> {
+    debug!("Updating equity grant {}...", grant.id);
+    state
+        .equity_grant_service()
+        .update_equity_grant(grant)
+        .await
+        .map_err(|e| format!("Failed to update equity grant: {}", e))
+}
+
+#[tauri::command]
+pub async fn delete_equity_grant(
+    grant_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    debug!("Deleting equity grant {}...", grant_id);
+    state
+        .equity_grant_service()
+        .delete_equity_grant(&grant_id)
+        .await
+        .map_err(|e| format!("Failed to delete equity grant: {}", e))
+}
+
+/// Records activities for vests up to `as_of` (defaults to today) that are not recorded yet.
+#[tauri::command]
+pub async fn sync_vest_activities(
+    as_of: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<usize, String> {
+    let as_of = parse_as_of(as_of)?;
+    state
+        .equity_grant_service()
+        .sync_vest_activities(as_of)
+        .await
+        .map_err(|e| format!("Failed to sync vest activities: {}", e))
+}
@@ -51,6 +51,8 @@ async fn run_health_checks_internal(
             state.quote_service(),
             state.asset_service(),
             state.taxonomy_service(),
+            state.fx_service(),
+            state.reconciliation_service(),
         )
         .await
         .map_err(|e| e.to_string())
@@ -141,6 +143,27 @@ pub async fn execute_health_fix(
         return Ok(());
     }
 
+    // Handle create_reconciliation_drafts - needs the reconciliation service
+    if action.id == "create_reconciliation_drafts" {
+        let account_ids: Vec<String> = serde_json::from_value(action.payload.clone())
+            .map_err(|e| format!("Failed to parse account IDs: {}", e))?;
+
+        let reconciliation_service = state.reconciliation_service();
+        for account_id in &account_ids {
+            let created = reconciliation_service
+                .create_proposed_drafts(account_id)
+                .await
+                .map_err(|e| format!("Failed to create reconciliation drafts: {}", e))?;
+            info!(
+                "Created {} reconciliation drafts for account {}",
+                created, account_id
+            );
+        }
+
+        state.health_service().clear_cache().await;
+        return Ok(());
+    }
+
     state
         .health_service()
         .execute_fix(&action)
@@ -8,11 +8,12 @@ use crate::{
     },
 };
 
+use chrono::NaiveDate;
 use log::{debug, error};
 use tauri::{AppHandle, State};
 use wealthfolio_core::quotes::{
-    service::ProviderInfo, LatestQuoteSnapshot, MarketSyncMode, Quote, QuoteImport,
-    SymbolSearchResult,
+    service::ProviderInfo, BulkImportOptions, BulkImportSummary, LatestQuoteSnapshot,
+    MarketSyncMode, Quote, QuoteExportRequest, QuoteImport, QuoteProvenance, SymbolSearchResult,
 };
 use wealthfolio_market_data::ExchangeInfo;
 
@@ -120,6 +121,23 @@ pub async fn get_quote_history(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn get_quote_provenance(
+    asset_id: String,
+    start_date: String,
+    end_date: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<QuoteProvenance>, String> {
+    let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid start date: {}", e))?;
+    let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid end date: {}", e))?;
+    state
+        .quote_service()
+        .get_quote_provenance(&asset_id, start, end)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_latest_quotes(
     asset_ids: Vec<String>,
@@ -202,6 +220,57 @@ pub async fn import_quotes_csv(
     Ok(result)
 }
 
+#[tauri::command]
+pub async fn bulk_import_quotes(
+    path: String,
+    options: Option<BulkImportOptions>,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<BulkImportSummary, String> {
+    debug!("Bulk importing quotes from {}", path);
+    let summary = state
+        .quote_service()
+        .bulk_import_quotes(std::path::Path::new(&path), options.unwrap_or_default())
+        .await
+        .map_err(|e| {
+            error!("TAURI COMMAND: bulk_import_quotes failed: {}", e);
+            format!("Failed to bulk import quotes: {}", e)
+        })?;
+
+    if summary.imported > 0 {
+        let handle = handle.clone();
+        tauri::async_runtime::spawn(async move {
+            debug!("Triggering portfolio recalculation after bulk quote import");
+            let payload = PortfolioRequestPayload::builder()
+                .account_ids(None)
+                .market_sync_mode(MarketSyncMode::None)
+                .build();
+            emit_portfolio_trigger_recalculate(&handle, payload);
+        });
+    }
+
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn export_market_data(
+    request: QuoteExportRequest,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<u8>, String> {
+    debug!(
+        "Exporting market data for {} assets as {:?}",
+        request.asset_ids.len(),
+        request.format
+    );
+    state
+        .quote_service()
+        .export_market_data(&request)
+        .map_err(|e| {
+            error!("TAURI COMMAND: export_market_data failed: {}", e);
+            format!("Failed to export market data: {}", e)
+        })
+}
+
 #[tauri::command]
 pub async fn resolve_symbol_quote(
     symbol: String,
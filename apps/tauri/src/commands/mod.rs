@@ -5,12 +5,15 @@ pub mod ai_chat;
 pub mod ai_providers;
 pub mod alternative_assets;
 pub mod asset;
+pub mod audit;
 #[cfg(feature = "connect-sync")]
 pub mod brokers_sync;
 pub mod budget;
+pub mod cash_interest;
 pub mod device_enroll_service;
 #[cfg(feature = "device-sync")]
 pub mod device_sync;
+pub mod equity_grants;
 pub mod error;
 pub mod fire;
 pub mod goal;
@@ -20,11 +23,13 @@ pub mod market_data;
 pub mod platform;
 pub mod portfolio;
 pub mod providers_settings;
+pub mod reconciliation;
 pub mod secrets;
 pub mod settings;
 #[cfg(feature = "device-sync")]
 pub mod sync_crypto;
 pub mod taxonomy;
 pub mod utilities;
+pub mod wallets;
 #[cfg(any(feature = "connect-sync", feature = "device-sync"))]
 pub mod wealthfolio_connect;
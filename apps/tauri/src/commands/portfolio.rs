@@ -16,6 +16,7 @@ use tauri::{AppHandle, State};
 use wealthfolio_core::{
     accounts::TrackingMode,
     allocation::{AllocationHoldings, PortfolioAllocations},
+    fx_gains::RealizedFxGain,
     holdings::Holding,
     income::IncomeSummary,
     performance::{PerformanceMetrics, SimplePerformanceMetrics},
@@ -232,6 +233,35 @@ pub async fn get_income_summary(
         .map_err(|e| e.to_string())
 }
 
+/// Gets gains realized by FX conversions, for one account or all active accounts.
+#[tauri::command]
+pub async fn get_realized_fx_gains(
+    state: State<'_, Arc<ServiceContext>>,
+    account_id: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<Vec<RealizedFxGain>, String> {
+    debug!("Fetching realized FX gains...");
+    let start_date_opt: Option<chrono::NaiveDate> = start_date
+        .map(|date_str| {
+            chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid start date: {}", e))
+        })
+        .transpose()?;
+
+    let end_date_opt: Option<chrono::NaiveDate> = end_date
+        .map(|date_str| {
+            chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid end date: {}", e))
+        })
+        .transpose()?;
+
+    state
+        .fx_gains_service()
+        .get_realized_fx_gains(account_id.as_deref(), start_date_opt, end_date_opt)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn calculate_accounts_simple_performance(
     state: State<'_, Arc<ServiceContext>>,
@@ -1048,6 +1078,7 @@ pub async fn get_snapshot_by_date(
             weight: Decimal::ZERO,
             as_of_date: target_date,
             metadata: asset.metadata.clone(),
+            equity_compensation: None,
         };
         holdings.push(holding);
     }
@@ -1092,6 +1123,7 @@ pub async fn get_snapshot_by_date(
             weight: Decimal::ZERO,
             as_of_date: target_date,
             metadata: None,
+            equity_compensation: None,
         };
         holdings.push(holding);
     }
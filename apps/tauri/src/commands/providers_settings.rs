@@ -1,5 +1,8 @@
 use tauri::State;
 use wealthfolio_core::quotes::service::ProviderInfo;
+use wealthfolio_core::quotes::{
+    ConsensusConfig, QuoteQualityConfig, QUOTE_CONSENSUS_SETTINGS_KEY, QUOTE_QUALITY_SETTINGS_KEY,
+};
 
 use crate::context::ServiceContext;
 use std::sync::Arc;
@@ -26,3 +29,53 @@ pub async fn update_market_data_provider_settings(
         .await?;
     Ok(())
 }
+
+#[tauri::command]
+pub async fn get_quote_consensus_settings(
+    context: State<'_, Arc<ServiceContext>>,
+) -> CommandResult<ConsensusConfig> {
+    Ok(context.quote_service.get_consensus_config())
+}
+
+#[tauri::command]
+pub async fn update_quote_consensus_settings(
+    context: State<'_, Arc<ServiceContext>>,
+    config: ConsensusConfig,
+) -> CommandResult<ConsensusConfig> {
+    let json = serde_json::to_string(&config)?;
+    context
+        .settings_service
+        .set_setting_value(QUOTE_CONSENSUS_SETTINGS_KEY, &json)
+        .await?;
+    context
+        .quote_service
+        .update_consensus_config(config.clone())
+        .await?;
+    Ok(config)
+}
+
+#[tauri::command]
+pub async fn get_quote_quality_settings(
+    context: State<'_, Arc<ServiceContext>>,
+) -> CommandResult<QuoteQualityConfig> {
+    Ok(context.quote_service.get_quality_config())
+}
+
+#[tauri::command]
+pub async fn update_quote_quality_settings(
+    context: State<'_, Arc<ServiceContext>>,
+    config: QuoteQualityConfig,
+) -> CommandResult<QuoteQualityConfig> {
+    config.validate()?;
+    let json = serde_json::to_string(&config)?;
+    context
+        .settings_service
+        .set_setting_value(QUOTE_QUALITY_SETTINGS_KEY, &json)
+        .await?;
+    context
+        .quote_service
+        .update_quality_config(config.clone())
+        .await?;
+    context.health_service().clear_cache().await;
+    Ok(config)
+}
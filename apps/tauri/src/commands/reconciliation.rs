@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use log::debug;
+use tauri::State;
+use wealthfolio_core::reconciliation::ReconciliationReport;
+
+/// Reconciles every account with broker-reported holdings.
+#[tauri::command]
+pub async fn get_reconciliation_reports(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ReconciliationReport>, String> {
+    debug!("Reconciling broker-reported holdings...");
+    state
+        .reconciliation_service()
+        .reconcile_all()
+        .await
+        .map_err(|e| format!("Failed to reconcile holdings: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_account_reconciliation(
+    account_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Option<ReconciliationReport>, String> {
+    state
+        .reconciliation_service()
+        .reconcile_account(&account_id)
+        .await
+        .map_err(|e| format!("Failed to reconcile holdings: {}", e))
+}
+
+/// Creates the draft activities proposed for an account. Returns the number created.
+#[tauri::command]
+pub async fn create_reconciliation_drafts(
+    account_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<usize, String> {
+    debug!("Creating reconciliation drafts for account {}", account_id);
+    let created = state
+        .reconciliation_service()
+        .create_proposed_drafts(&account_id)
+        .await
+        .map_err(|e| format!("Failed to create reconciliation drafts: {}", e))?;
+    state.health_service().clear_cache().await;
+    Ok(created)
+}
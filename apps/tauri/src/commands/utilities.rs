@@ -4,10 +4,15 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 use tauri::Manager;
-use tauri::{AppHandle, Emitter};
-use wealthfolio_storage_sqlite::db;
+use tauri::{AppHandle, Emitter, State};
+use wealthfolio_core::quotes::MarketSyncMode;
+use wealthfolio_storage_sqlite::db::{
+    self,
+    archive::{self, ArchiveExport, ArchiveRestoreMode, ArchiveRestoreSummary, PortfolioArchive},
+};
 
 use crate::context::ServiceContext;
+use crate::events::{emit_portfolio_trigger_recalculate, PortfolioRequestPayload};
 #[cfg(desktop)]
 use crate::updater::{check_for_update, install_update};
 
@@ -202,3 +207,65 @@ pub async fn restore_database(
 
     Ok(())
 }
+
+/// Creates a portable archive of the portfolio, encrypted when a passphrase is given.
+#[tauri::command]
+pub async fn create_portfolio_archive(
+    app_handle: AppHandle,
+    passphrase: Option<String>,
+) -> Result<ArchiveExport, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .to_string_lossy()
+        .to_string();
+
+    let archive = archive::create_archive(&app_data_dir, passphrase.as_deref())
+        .map_err(|e| format!("Failed to create archive: {}", e))?;
+
+    Ok(ArchiveExport {
+        filename: archive.filename(),
+        archive,
+    })
+}
+
+/// Restores a portable archive into the database, then recalculates the portfolio.
+#[tauri::command]
+pub async fn restore_portfolio_archive(
+    app_handle: AppHandle,
+    archive: PortfolioArchive,
+    passphrase: Option<String>,
+    mode: Option<ArchiveRestoreMode>,
+    state: State<'_, std::sync::Arc<ServiceContext>>,
+) -> Result<ArchiveRestoreSummary, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .to_string_lossy()
+        .to_string();
+
+    let summary = archive::restore_archive(
+        &app_data_dir,
+        &archive,
+        passphrase.as_deref(),
+        mode.unwrap_or_default(),
+    )
+    .map_err(|e| format!("Failed to restore archive: {}", e))?;
+
+    // The archive may bring its own base currency along with the accounts
+    let settings = state
+        .settings_service()
+        .get_settings()
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+    state.update_base_currency(settings.base_currency);
+
+    let payload = PortfolioRequestPayload::builder()
+        .account_ids(None)
+        .market_sync_mode(MarketSyncMode::Incremental { asset_ids: None })
+        .build();
+    emit_portfolio_trigger_recalculate(&app_handle, payload);
+
+    Ok(summary)
+}
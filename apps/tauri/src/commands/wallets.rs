@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use log::debug;
+use tauri::State;
+use wealthfolio_connect::{WalletConfig, WalletSyncResult};
+use wealthfolio_core::accounts::Account;
+
+#[tauri::command]
+pub async fn get_wallet_config(
+    account_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Option<WalletConfig>, String> {
+    state
+        .wallet_sync_service()
+        .get_wallet_config(&account_id)
+        .map_err(|e| format!("Failed to load wallet config: {}", e))
+}
+
+#[tauri::command]
+pub async fn set_wallet_config(
+    account_id: String,
+    config: WalletConfig,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Account, String> {
+    debug!("Setting wallet config for account {}", account_id);
+    state
+        .wallet_sync_service()
+        .set_wallet_config(&account_id, config)
+        .await
+        .map_err(|e| format!("Failed to save wallet config: {}", e))
+}
+
+/// Pulls new on-chain transfers of the account's watch-only wallet.
+#[tauri::command]
+pub async fn sync_wallet(
+    account_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<WalletSyncResult, String> {
+    debug!("Syncing wallet for account {}", account_id);
+    state
+        .wallet_sync_service()
+        .sync_wallet(&account_id)
+        .await
+        .map_err(|e| format!("Failed to sync wallet: {}", e))
+}
+
+#[tauri::command]
+pub async fn sync_all_wallets(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<WalletSyncResult>, String> {
+    state
+        .wallet_sync_service()
+        .sync_all_wallets()
+        .await
+        .map_err(|e| format!("Failed to sync wallets: {}", e))
+}
@@ -7,12 +7,15 @@ use std::sync::{Arc, RwLock};
 use tokio::sync::mpsc;
 use wealthfolio_ai::{AiProviderService, ChatConfig, ChatService};
 use wealthfolio_connect::{
-    BrokerSyncService, CoreImportRunRepositoryAdapter, ImportRunRepositoryTrait,
+    BrokerSyncService, CoreImportRunRepositoryAdapter, ImportRunRepositoryTrait, WalletSyncService,
 };
 use wealthfolio_core::{
     accounts::AccountService,
-    activities::ActivityService,
+    activities::{ActivityService, ImportReviewService},
     assets::{AlternativeAssetService, AssetClassificationService, AssetService},
+    audit::AuditService,
+    cash_interest::CashInterestService,
+    equity_grants::EquityGrantService,
     events::DomainEvent,
     fx::{FxService, FxServiceTrait},
     goals::GoalService,
@@ -20,14 +23,19 @@ use wealthfolio_core::{
     limits::ContributionLimitService,
     portfolio::{
         allocation::AllocationService,
+        fx_gains::FxGainsService,
         holdings::{HoldingsService, HoldingsValuationService},
         income::IncomeService,
         net_worth::NetWorthService,
         performance::PerformanceService,
-        snapshot::SnapshotService,
+        snapshot::{HoldingsCalculator, SnapshotService},
         valuation::ValuationService,
     },
-    quotes::{QuoteService, QuoteServiceTrait},
+    quotes::{
+        ConsensusConfig, QuoteQualityConfig, QuoteService, QuoteServiceTrait,
+        QUOTE_CONSENSUS_SETTINGS_KEY, QUOTE_QUALITY_SETTINGS_KEY,
+    },
+    reconciliation::HoldingsReconciliationService,
     settings::{SettingsRepositoryTrait, SettingsService, SettingsServiceTrait},
     taxonomies::TaxonomyService,
 };
@@ -37,7 +45,9 @@ use wealthfolio_storage_sqlite::{
     activities::ActivityRepository,
     ai_chat::AiChatRepository,
     assets::{AlternativeAssetRepository, AssetRepository},
+    audit::AuditLogRepository,
     db::{self, write_actor},
+    equity_grants::EquityGrantRepository,
     fx::FxRepository,
     goals::GoalRepository,
     health::HealthDismissalRepository,
@@ -122,6 +132,28 @@ pub async fn initialize_context(
         .await?,
     );
 
+    // Apply persisted dual-provider consensus settings (disabled by default)
+    if let Some(json) = settings_service.get_setting_value(QUOTE_CONSENSUS_SETTINGS_KEY)? {
+        match serde_json::from_str::<ConsensusConfig>(&json) {
+            Ok(config) => quote_service.update_consensus_config(config).await?,
+            Err(e) => log::warn!("Ignoring invalid quote consensus settings: {}", e),
+        }
+    }
+
+    // Apply persisted quote quality thresholds
+    if let Some(json) = settings_service.get_setting_value(QUOTE_QUALITY_SETTINGS_KEY)? {
+        let applied = match serde_json::from_str::<QuoteQualityConfig>(&json) {
+            Ok(config) => quote_service
+                .update_quality_config(config)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = applied {
+            log::warn!("Ignoring invalid quote quality settings: {}", e);
+        }
+    }
+
     // Create taxonomy service before asset service (needed for auto-classification)
     let taxonomy_repository = Arc::new(TaxonomyRepository::new(pool.clone(), writer.clone()));
     let taxonomy_service = Arc::new(TaxonomyService::new(taxonomy_repository));
@@ -158,9 +190,18 @@ pub async fn initialize_context(
             asset_service.clone(),
             fx_service.clone(),
             quote_service.clone(),
-            core_import_run_repository,
+            core_import_run_repository.clone(),
         )
-        .with_event_sink(domain_event_sink.clone()),
+        .with_event_sink(domain_event_sink.clone())
+        .with_holdings_calculator(HoldingsCalculator::new(
+            fx_service.clone(),
+            base_currency.clone(),
+            asset_repository.clone(),
+        )),
+    );
+    let import_review_service = Arc::new(
+        ImportReviewService::new(activity_repository.clone(), core_import_run_repository)
+            .with_event_sink(domain_event_sink.clone()),
     );
     let goal_service = Arc::new(GoalService::new(goal_repo.clone()));
     let limits_service = Arc::new(ContributionLimitService::new(
@@ -187,10 +228,12 @@ pub async fn initialize_context(
         .with_event_sink(domain_event_sink.clone()),
     );
 
-    let holdings_valuation_service = Arc::new(HoldingsValuationService::new(
-        fx_service.clone(),
-        quote_service.clone(),
-    ));
+    let equity_grant_repository =
+        Arc::new(EquityGrantRepository::new(pool.clone(), writer.clone()));
+    let holdings_valuation_service = Arc::new(
+        HoldingsValuationService::new(fx_service.clone(), quote_service.clone())
+            .with_equity_grants(equity_grant_repository.clone()),
+    );
 
     let valuation_service = Arc::new(ValuationService::new(
         base_currency.clone(),
@@ -203,6 +246,8 @@ pub async fn initialize_context(
     let performance_service = Arc::new(PerformanceService::new(
         valuation_service.clone(),
         quote_service.clone(),
+        snapshot_service.clone(),
+        fx_service.clone(),
     ));
 
     let classification_service =
@@ -243,6 +288,43 @@ pub async fn initialize_context(
         .with_event_sink(domain_event_sink.clone()),
     );
 
+    let equity_grant_service = Arc::new(EquityGrantService::new(
+        equity_grant_repository,
+        asset_repository.clone(),
+        quote_service.clone(),
+        activity_service.clone(),
+    ));
+
+    let cash_interest_service = Arc::new(CashInterestService::new(
+        account_service.clone(),
+        activity_service.clone(),
+        snapshot_service.clone(),
+    ));
+
+    let fx_gains_service = Arc::new(FxGainsService::new(
+        account_service.clone(),
+        activity_service.clone(),
+        snapshot_service.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+
+    let reconciliation_service = Arc::new(HoldingsReconciliationService::new(
+        account_service.clone(),
+        activity_service.clone(),
+        snapshot_service.clone(),
+        asset_service.clone(),
+        settings_service.clone(),
+    ));
+
+    let audit_service = Arc::new(
+        AuditService::new(Arc::new(AuditLogRepository::new(
+            pool.clone(),
+            writer.clone(),
+        )))
+        .with_event_sink(domain_event_sink.clone()),
+    );
+
     let sync_service = Arc::new(
         BrokerSyncService::new(
             account_service.clone(),
@@ -255,9 +337,17 @@ pub async fn initialize_context(
             snapshot_repository.clone(),
         )
         .with_event_sink(domain_event_sink.clone())
-        .with_snapshot_service(snapshot_service.clone()),
+        .with_snapshot_service(snapshot_service.clone())
+        .with_reconciliation_service(reconciliation_service.clone()),
     );
 
+    let wallet_sync_service = Arc::new(WalletSyncService::new(
+        account_service.clone(),
+        activity_service.clone(),
+        import_run_repository.clone(),
+        broker_sync_state_repository.clone(),
+    ));
+
     let connect_service = Arc::new(ConnectService::new());
 
     // AI provider service - catalog is embedded at compile time
@@ -315,6 +405,7 @@ pub async fn initialize_context(
             settings_service,
             account_service,
             activity_service,
+            import_review_service,
             asset_service,
             goal_service,
             quote_service,
@@ -322,6 +413,7 @@ pub async fn initialize_context(
             fx_service,
             performance_service,
             income_service,
+            fx_gains_service,
             snapshot_service,
             snapshot_repository,
             app_sync_repository,
@@ -330,7 +422,12 @@ pub async fn initialize_context(
             valuation_service,
             net_worth_service,
             sync_service,
+            wallet_sync_service,
             alternative_asset_service,
+            equity_grant_service,
+            cash_interest_service,
+            reconciliation_service,
+            audit_service,
             taxonomy_service,
             connect_service,
             ai_provider_service,
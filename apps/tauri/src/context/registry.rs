@@ -2,12 +2,17 @@ use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::SqliteConnection;
 use std::sync::{Arc, RwLock};
 use wealthfolio_ai::{AiProviderServiceTrait, ChatService};
-use wealthfolio_connect::BrokerSyncServiceTrait;
+use wealthfolio_connect::{BrokerSyncServiceTrait, WalletSyncServiceTrait};
 use wealthfolio_core::{
     self, accounts, activities,
     assets::{self, AlternativeAssetServiceTrait},
+    audit::AuditServiceTrait,
+    cash_interest::CashInterestServiceTrait,
+    equity_grants::EquityGrantServiceTrait,
     events::DomainEventSink,
-    fx, goals, health, limits, portfolio, quotes, settings, taxonomies,
+    fx, goals, health, limits, portfolio, quotes,
+    reconciliation::HoldingsReconciliationServiceTrait,
+    settings, taxonomies,
 };
 use wealthfolio_device_sync::{engine::DeviceSyncRuntimeState, DeviceEnrollService};
 use wealthfolio_storage_sqlite::{
@@ -35,6 +40,7 @@ pub struct ServiceContext {
     // Services
     pub settings_service: Arc<dyn settings::SettingsServiceTrait>,
     pub activity_service: Arc<dyn activities::ActivityServiceTrait>,
+    pub import_review_service: Arc<dyn activities::ImportReviewServiceTrait>,
     pub account_service: Arc<dyn accounts::AccountServiceTrait>,
     pub goal_service: Arc<dyn goals::GoalServiceTrait>,
     pub asset_service: Arc<dyn assets::AssetServiceTrait>,
@@ -43,6 +49,7 @@ pub struct ServiceContext {
     pub fx_service: Arc<dyn fx::FxServiceTrait>,
     pub performance_service: Arc<dyn portfolio::performance::PerformanceServiceTrait>,
     pub income_service: Arc<dyn portfolio::income::IncomeServiceTrait>,
+    pub fx_gains_service: Arc<dyn portfolio::fx_gains::FxGainsServiceTrait>,
     pub snapshot_service: Arc<dyn portfolio::snapshot::SnapshotServiceTrait>,
     pub snapshot_repository: Arc<SnapshotRepository>,
     pub app_sync_repository: Arc<AppSyncRepository>,
@@ -51,7 +58,12 @@ pub struct ServiceContext {
     pub valuation_service: Arc<dyn portfolio::valuation::ValuationServiceTrait>,
     pub net_worth_service: Arc<dyn portfolio::net_worth::NetWorthServiceTrait>,
     pub sync_service: Arc<dyn BrokerSyncServiceTrait>,
+    pub wallet_sync_service: Arc<dyn WalletSyncServiceTrait>,
     pub alternative_asset_service: Arc<dyn AlternativeAssetServiceTrait>,
+    pub equity_grant_service: Arc<dyn EquityGrantServiceTrait>,
+    pub cash_interest_service: Arc<dyn CashInterestServiceTrait>,
+    pub reconciliation_service: Arc<dyn HoldingsReconciliationServiceTrait>,
+    pub audit_service: Arc<dyn AuditServiceTrait>,
     pub taxonomy_service: Arc<dyn taxonomies::TaxonomyServiceTrait>,
     pub connect_service: Arc<ConnectService>,
     pub ai_provider_service: Arc<dyn AiProviderServiceTrait>,
@@ -87,6 +99,10 @@ impl ServiceContext {
         Arc::clone(&self.activity_service)
     }
 
+    pub fn import_review_service(&self) -> Arc<dyn activities::ImportReviewServiceTrait> {
+        Arc::clone(&self.import_review_service)
+    }
+
     pub fn asset_service(&self) -> Arc<dyn assets::AssetServiceTrait> {
         Arc::clone(&self.asset_service)
     }
@@ -115,6 +131,10 @@ impl ServiceContext {
         Arc::clone(&self.income_service)
     }
 
+    pub fn fx_gains_service(&self) -> Arc<dyn portfolio::fx_gains::FxGainsServiceTrait> {
+        Arc::clone(&self.fx_gains_service)
+    }
+
     pub fn snapshot_service(&self) -> Arc<dyn portfolio::snapshot::SnapshotServiceTrait> {
         Arc::clone(&self.snapshot_service)
     }
@@ -143,6 +163,10 @@ impl ServiceContext {
         Arc::clone(&self.sync_service)
     }
 
+    pub fn wallet_sync_service(&self) -> Arc<dyn WalletSyncServiceTrait> {
+        Arc::clone(&self.wallet_sync_service)
+    }
+
     pub fn net_worth_service(&self) -> Arc<dyn portfolio::net_worth::NetWorthServiceTrait> {
         Arc::clone(&self.net_worth_service)
     }
@@ -151,6 +175,22 @@ impl ServiceContext {
         Arc::clone(&self.alternative_asset_service)
     }
 
+    pub fn equity_grant_service(&self) -> Arc<dyn EquityGrantServiceTrait> {
+        Arc::clone(&self.equity_grant_service)
+    }
+
+    pub fn cash_interest_service(&self) -> Arc<dyn CashInterestServiceTrait> {
+        Arc::clone(&self.cash_interest_service)
+    }
+
+    pub fn reconciliation_service(&self) -> Arc<dyn HoldingsReconciliationServiceTrait> {
+        Arc::clone(&self.reconciliation_service)
+    }
+
+    pub fn audit_service(&self) -> Arc<dyn AuditServiceTrait> {
+        Arc::clone(&self.audit_service)
+    }
+
     pub fn taxonomy_service(&self) -> Arc<dyn taxonomies::TaxonomyServiceTrait> {
         Arc::clone(&self.taxonomy_service)
     }
@@ -10,6 +10,7 @@ use std::time::Duration;
 use log::{debug, error, info, warn};
 use tauri::{AppHandle, Emitter};
 use tokio::sync::mpsc;
+use wealthfolio_core::audit::{with_audit_context, AuditContext, AuditSource};
 use wealthfolio_core::constants::PORTFOLIO_TOTAL_ACCOUNT_ID;
 use wealthfolio_core::events::DomainEvent;
 use wealthfolio_core::health::HealthServiceTrait;
@@ -125,7 +126,8 @@ async fn process_event_batch(
         );
         let asset_service = context.asset_service();
         tokio::spawn(async move {
-            match asset_service.enrich_assets(enrichment_asset_ids).await {
+            let enrichment = asset_service.enrich_assets(enrichment_asset_ids);
+            match with_audit_context(AuditContext::new(AuditSource::System), enrichment).await {
                 Ok((enriched, skipped, failed)) => {
                     info!(
                         "Asset enrichment complete: {} enriched, {} skipped, {} failed",
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use tauri::Emitter;
-use wealthfolio_core::quotes::MarketSyncMode;
+use wealthfolio_core::quotes::{MarketSyncMode, QuoteQualityReport};
 
 pub const PORTFOLIO_TOTAL_ACCOUNT_ID: &str = "TOTAL";
 
@@ -33,6 +33,8 @@ pub const MARKET_SYNC_COMPLETE: &str = "market:sync-complete";
 pub struct MarketSyncResult {
     /// List of (asset_id, error_message) tuples for failed syncs.
     pub failed_syncs: Vec<(String, String)>,
+    /// Quote gaps and spikes found in the synced assets.
+    pub quality_reports: Vec<QuoteQualityReport>,
 }
 
 /// Event emitted when the market data sync process encounters an error.
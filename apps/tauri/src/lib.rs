@@ -98,6 +98,18 @@ mod desktop {
             scheduler::run_startup_sync(&startup_handle, &startup_context).await;
         });
 
+        // Record equity grant vests that happened since the last launch
+        let vest_context = Arc::clone(&context);
+        tauri::async_runtime::spawn(async move {
+            scheduler::run_vest_sync(&vest_context).await;
+        });
+
+        // Draft cash interest for payment dates passed since the last launch
+        let cash_interest_context = Arc::clone(&context);
+        tauri::async_runtime::spawn(async move {
+            scheduler::run_cash_interest_drafts(&cash_interest_context).await;
+        });
+
         // Start background device sync engine (self-skips when device is not READY).
         #[cfg(feature = "device-sync")]
         {
@@ -262,6 +274,16 @@ pub fn run() {
             commands::activity::save_account_import_mapping,
             commands::activity::check_existing_duplicates,
             commands::activity::parse_csv,
+            commands::activity::parse_ibkr_flex,
+            commands::activity::parse_broker_statement,
+            commands::activity::list_broker_parsers,
+            commands::activity::get_import_run_changes,
+            commands::activity::approve_import_run_changes,
+            commands::activity::reject_import_run_changes,
+            commands::activity::rollback_import_run,
+            commands::activity::find_probable_duplicates,
+            commands::activity::merge_duplicate_activities,
+            commands::activity::bulk_edit_activities,
             // Settings commands
             commands::settings::get_settings,
             commands::settings::is_auto_update_check_enabled,
@@ -295,6 +317,7 @@ pub fn run() {
             commands::portfolio::get_portfolio_allocations,
             commands::portfolio::get_holdings_by_allocation,
             commands::portfolio::get_income_summary,
+            commands::portfolio::get_realized_fx_gains,
             commands::portfolio::get_historical_valuations,
             commands::portfolio::get_latest_valuations,
             commands::portfolio::calculate_accounts_simple_performance,
@@ -321,6 +344,8 @@ pub fn run() {
             commands::utilities::backup_database,
             commands::utilities::backup_database_to_path,
             commands::utilities::restore_database,
+            commands::utilities::create_portfolio_archive,
+            commands::utilities::restore_portfolio_archive,
             // Asset commands
             commands::asset::get_asset_profile,
             commands::asset::get_assets,
@@ -334,9 +359,39 @@ pub fn run() {
             commands::alternative_assets::delete_alternative_asset,
             commands::alternative_assets::link_liability,
             commands::alternative_assets::unlink_liability,
+            commands::alternative_assets::set_loan_terms,
+            commands::alternative_assets::get_loan_schedule,
+            commands::alternative_assets::set_valuation_model,
+            commands::alternative_assets::add_property_cash_flow,
+            commands::alternative_assets::delete_property_cash_flow,
+            commands::alternative_assets::get_property_cash_flows,
+            commands::alternative_assets::get_rental_performance,
             commands::alternative_assets::get_net_worth,
             commands::alternative_assets::get_net_worth_history,
             commands::alternative_assets::get_alternative_holdings,
+            commands::equity_grants::get_equity_grants,
+            commands::equity_grants::get_equity_grant_summaries,
+            commands::equity_grants::get_vesting_schedule,
+            commands::equity_grants::create_equity_grant,
+            commands::equity_grants::update_equity_grant,
+            commands::equity_grants::delete_equity_grant,
+            commands::equity_grants::sync_vest_activities,
+            commands::cash_interest::get_cash_interest_settings,
+            commands::cash_interest::set_cash_interest_settings,
+            commands::cash_interest::get_cash_interest_accrual,
+            commands::cash_interest::get_cash_yield_comparison,
+            commands::cash_interest::generate_cash_interest_drafts,
+            commands::reconciliation::get_reconciliation_reports,
+            commands::reconciliation::get_account_reconciliation,
+            commands::reconciliation::create_reconciliation_drafts,
+            commands::audit::get_entity_history,
+            commands::audit::get_recent_changes,
+            commands::audit::get_entity_version_at,
+            commands::audit::restore_audit_version,
+            commands::wallets::get_wallet_config,
+            commands::wallets::set_wallet_config,
+            commands::wallets::sync_wallet,
+            commands::wallets::sync_all_wallets,
             // Market data commands
             commands::market_data::search_symbol,
             commands::market_data::resolve_symbol_quote,
@@ -344,10 +399,13 @@ pub fn run() {
             commands::market_data::update_quote,
             commands::market_data::delete_quote,
             commands::market_data::get_quote_history,
+            commands::market_data::get_quote_provenance,
             commands::market_data::get_latest_quotes,
             commands::market_data::get_market_data_providers,
             commands::market_data::check_quotes_import,
             commands::market_data::import_quotes_csv,
+            commands::market_data::bulk_import_quotes,
+            commands::market_data::export_market_data,
             commands::market_data::get_exchanges,
             // Taxonomy commands
             commands::taxonomy::get_taxonomies,
@@ -378,6 +436,10 @@ pub fn run() {
             // Provider settings commands
             commands::providers_settings::get_market_data_providers_settings,
             commands::providers_settings::update_market_data_provider_settings,
+            commands::providers_settings::get_quote_consensus_settings,
+            commands::providers_settings::update_quote_consensus_settings,
+            commands::providers_settings::get_quote_quality_settings,
+            commands::providers_settings::update_quote_quality_settings,
             // AI provider commands
             commands::ai_providers::get_ai_providers,
             commands::ai_providers::update_ai_provider_settings,
@@ -87,7 +87,10 @@ fn handle_portfolio_request(handle: AppHandle, payload_str: &str, force_recalc:
                                     health_clone.clear_cache().await;
                                 });
 
-                                let result_payload = MarketSyncResult { failed_syncs };
+                                let result_payload = MarketSyncResult {
+                                    failed_syncs,
+                                    quality_reports: result.quality_reports,
+                                };
                                 if let Err(e) =
                                     handle_clone.emit(MARKET_SYNC_COMPLETE, &result_payload)
                                 {
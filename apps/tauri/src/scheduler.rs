@@ -1,4 +1,4 @@
-//! Startup sync for broker data.
+//! Startup sync for broker data and equity grant vests.
 //!
 //! Syncs broker data once on app startup. After that, user manually triggers sync.
 
@@ -106,3 +106,34 @@ pub async fn run_startup_sync(handle: &AppHandle, context: &Arc<ServiceContext>)
 
 #[cfg(not(feature = "connect-sync"))]
 pub async fn run_startup_sync(_handle: &AppHandle, _context: &std::sync::Arc<ServiceContext>) {}
+
+/// Records equity grant vests that are due but have no activity yet.
+///
+/// Activity creation emits domain events, so the portfolio is recalculated
+/// automatically when vests are recorded.
+pub async fn run_vest_sync(context: &std::sync::Arc<ServiceContext>) {
+    let today = chrono::Utc::now().date_naive();
+    match context
+        .equity_grant_service()
+        .sync_vest_activities(today)
+        .await
+    {
+        Ok(0) => {}
+        Ok(created) => log::info!("Recorded {} equity grant vests on startup", created),
+        Err(e) => log::warn!("Equity grant vest sync failed: {}", e),
+    }
+}
+
+/// Creates `INTEREST` drafts for cash interest payment dates that have passed.
+pub async fn run_cash_interest_drafts(context: &std::sync::Arc<ServiceContext>) {
+    let today = chrono::Utc::now().date_naive();
+    match context
+        .cash_interest_service()
+        .generate_interest_drafts(today)
+        .await
+    {
+        Ok(0) => {}
+        Ok(created) => log::info!("Created {} cash interest drafts on startup", created),
+        Err(e) => log::warn!("Cash interest draft run failed: {}", e),
+    }
+}
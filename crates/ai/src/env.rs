@@ -94,9 +94,10 @@ pub mod test_env {
         portfolio::income::{IncomeServiceTrait, IncomeSummary},
         portfolio::performance::{PerformanceMetrics, PerformanceServiceTrait},
         quotes::{
-            LatestQuotePair, LatestQuoteSnapshot, ProviderInfo, Quote, QuoteImport,
-            QuoteServiceTrait, QuoteSyncState, SymbolSearchResult, SymbolSyncPlan, SyncMode,
-            SyncResult,
+            BulkImportOptions, BulkImportSummary, ConsensusConfig, LatestQuotePair,
+            LatestQuoteSnapshot, ProviderInfo, Quote, QuoteExportRequest, QuoteImport,
+            QuoteProvenance, QuoteQualityConfig, QuoteServiceTrait, QuoteSyncState,
+            SymbolSearchResult, SymbolSyncPlan, SyncMode, SyncResult,
         },
         secrets::SecretStore,
         settings::{Settings, SettingsServiceTrait, SettingsUpdate},
@@ -351,6 +352,26 @@ pub mod test_env {
             wealthfolio_core::activities::parse_csv(content, config)
         }
 
+        fn parse_ibkr_flex(
+            &self,
+            _account_id: &str,
+            _content: &[u8],
+        ) -> CoreResult<Vec<ActivityImport>> {
+            Ok(vec![])
+        }
+
+        fn parse_broker_statement(
+            &self,
+            _account_id: &str,
+            _content: &[u8],
+        ) -> CoreResult<wealthfolio_core::activities::BrokerStatement> {
+            unimplemented!("MockActivityService::parse_broker_statement")
+        }
+
+        fn list_broker_parsers(&self) -> Vec<wealthfolio_core::activities::BrokerParserInfo> {
+            vec![]
+        }
+
         async fn prepare_activities(
             &self,
             _activities: Vec<NewActivity>,
@@ -365,6 +386,28 @@ pub mod test_env {
         ) -> CoreResult<wealthfolio_core::activities::BulkUpsertResult> {
             unimplemented!("MockActivityService::upsert_activities_bulk")
         }
+
+        fn find_probable_duplicates(
+            &self,
+            _account_id: Option<String>,
+        ) -> CoreResult<Vec<wealthfolio_core::activities::ProbableDuplicate>> {
+            unimplemented!("MockActivityService::find_probable_duplicates")
+        }
+
+        async fn merge_duplicate_activities(
+            &self,
+            _keep_id: &str,
+            _duplicate_id: &str,
+        ) -> CoreResult<Activity> {
+            unimplemented!("MockActivityService::merge_duplicate_activities")
+        }
+
+        async fn bulk_edit_activities(
+            &self,
+            _request: wealthfolio_core::activities::ActivityBulkEditRequest,
+        ) -> CoreResult<wealthfolio_core::activities::ActivityBulkEditResult> {
+            unimplemented!("MockActivityService::bulk_edit_activities")
+        }
     }
 
     /// Mock holdings service for testing.
@@ -772,6 +815,15 @@ pub mod test_env {
             Ok(HashMap::new())
         }
 
+        fn get_quote_provenance(
+            &self,
+            _asset_id: &str,
+            _start: NaiveDate,
+            _end: NaiveDate,
+        ) -> CoreResult<Vec<QuoteProvenance>> {
+            Ok(Vec::new())
+        }
+
         async fn add_quote(&self, quote: &Quote) -> CoreResult<Quote> {
             Ok(quote.clone())
         }
@@ -899,6 +951,22 @@ pub mod test_env {
             Ok(())
         }
 
+        fn get_consensus_config(&self) -> ConsensusConfig {
+            ConsensusConfig::default()
+        }
+
+        async fn update_consensus_config(&self, _config: ConsensusConfig) -> CoreResult<()> {
+            Ok(())
+        }
+
+        fn get_quality_config(&self) -> QuoteQualityConfig {
+            QuoteQualityConfig::default()
+        }
+
+        async fn update_quality_config(&self, _config: QuoteQualityConfig) -> CoreResult<()> {
+            Ok(())
+        }
+
         async fn check_quotes_import(
             &self,
             _content: &[u8],
@@ -914,6 +982,18 @@ pub mod test_env {
         ) -> CoreResult<Vec<QuoteImport>> {
             Ok(quotes)
         }
+
+        async fn bulk_import_quotes(
+            &self,
+            _path: &std::path::Path,
+            _options: BulkImportOptions,
+        ) -> CoreResult<BulkImportSummary> {
+            Ok(BulkImportSummary::default())
+        }
+
+        fn export_market_data(&self, _request: &QuoteExportRequest) -> CoreResult<Vec<u8>> {
+            Ok(Vec::new())
+        }
     }
 
     /// Mock allocation service for testing.
@@ -996,6 +1076,7 @@ pub mod test_env {
                 volatility: rust_decimal::Decimal::ZERO,
                 max_drawdown: rust_decimal::Decimal::ZERO,
                 is_holdings_mode: false,
+                fx_decomposition: None,
             })
         }
 
@@ -1025,6 +1106,7 @@ pub mod test_env {
                 volatility: rust_decimal::Decimal::ZERO,
                 max_drawdown: rust_decimal::Decimal::ZERO,
                 is_holdings_mode: false,
+                fx_decomposition: None,
             })
         }
 
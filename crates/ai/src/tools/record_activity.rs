@@ -44,7 +44,7 @@ pub struct RecordActivityArgs {
     /// Account name or ID. If ambiguous/missing, tool returns available accounts.
     pub account: Option<String>,
 
-    /// Activity subtype: DRIP, DIVIDEND_IN_KIND, STAKING_REWARD, BONUS.
+    /// Activity subtype: DRIP, DIVIDEND_IN_KIND, STAKING_REWARD, AIRDROP, BONUS.
     pub subtype: Option<String>,
 
     /// Optional notes.
@@ -209,11 +209,17 @@ fn get_subtypes_for_activity_type(activity_type: &str) -> Vec<SubtypeOption> {
                 label: "Dividend in Kind".to_string(),
             },
         ],
-        // STAKING_REWARD expands to INTEREST + BUY
-        "INTEREST" => vec![SubtypeOption {
-            value: "STAKING_REWARD".to_string(),
-            label: "Staking Reward".to_string(),
-        }],
+        // STAKING_REWARD and AIRDROP expand to INTEREST + BUY
+        "INTEREST" => vec![
+            SubtypeOption {
+                value: "STAKING_REWARD".to_string(),
+                label: "Staking Reward".to_string(),
+            },
+            SubtypeOption {
+                value: "AIRDROP".to_string(),
+                label: "Airdrop".to_string(),
+            },
+        ],
         // BONUS is external flow (affects TWR)
         "CREDIT" => vec![SubtypeOption {
             value: "BONUS".to_string(),
@@ -459,7 +465,7 @@ impl<E: AiEnvironment + 'static> Tool for RecordActivityTool<E> {
                     },
                     "subtype": {
                         "type": "string",
-                        "description": "Activity subtype for semantic variations: DRIP (dividend reinvested), DIVIDEND_IN_KIND (dividend paid in asset), STAKING_REWARD (crypto staking), BONUS (promotional credit)"
+                        "description": "Activity subtype for semantic variations: DRIP (dividend reinvested), DIVIDEND_IN_KIND (dividend paid in asset), STAKING_REWARD (crypto staking), AIRDROP (free tokens received), BONUS (promotional credit)"
                     },
                     "notes": {
                         "type": "string",
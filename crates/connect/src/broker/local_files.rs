@@ -0,0 +1,519 @@
+//! Local file-drop broker client.
+//!
+//! Serves broker exports dropped into a folder through the same
+//! [`BrokerApiClient`] contract as the Connect cloud API, so the
+//! [`SyncOrchestrator`](super::SyncOrchestrator) can sync them without a
+//! subscription and without the data leaving the machine.
+//!
+//! Layout: every sub-folder of the drop folder is one broker account, holding
+//! that account's statements (any format the core broker statement parsers
+//! recognise: CSV exports, IBKR Flex XML, PDF contract notes).
+//!
+//! ```text
+//! drop/
+//!   degiro/     Account.csv, Transactions.csv
+//!   ibkr-main/  flex-2024.xml
+//! ```
+//!
+//! Incremental sync reuses the orchestrator's checkpoint: the query window
+//! starts one day before the account's last successful sync, and only files
+//! modified inside the window are read. Activity ids are derived from the
+//! statement's record ids, so re-reading a file upserts the same activities.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::{debug, warn};
+use rust_decimal::prelude::ToPrimitive;
+use sha2::{Digest, Sha256};
+
+use super::models::{
+    AccountUniversalActivity, AccountUniversalActivityCurrency, AccountUniversalActivityExchange,
+    AccountUniversalActivitySymbol, AccountUniversalActivitySymbolType, BrokerAccount,
+    BrokerBrokerage, BrokerConnection, BrokerConnectionBrokerage, BrokerHoldingsResponse,
+    MappingMetadata, PaginatedUniversalActivity, PaginationDetails,
+};
+use super::traits::BrokerApiClient;
+use wealthfolio_core::activities::{ActivityImport, BrokerParserRegistry};
+use wealthfolio_core::errors::{Error, Result, ValidationError};
+
+/// Provider name recorded on accounts and activities synced from local files.
+pub const LOCAL_FILES_PROVIDER: &str = "LOCAL_FILES";
+
+/// Prefix of the provider account id of a drop folder account.
+const ACCOUNT_ID_PREFIX: &str = "local:";
+
+/// Connection and brokerage id for the drop folder.
+const CONNECTION_ID: &str = "local-files";
+
+/// [`BrokerApiClient`] backed by a folder of broker exports.
+pub struct LocalFileBrokerClient {
+    root: PathBuf,
+    parsers: Arc<BrokerParserRegistry>,
+    /// Activities of the last window read, reused for later pages.
+    cache: Mutex<Option<(String, Vec<AccountUniversalActivity>)>>,
+}
+
+impl LocalFileBrokerClient {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            parsers: Arc::new(BrokerParserRegistry::default()),
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Replaces the statement parsers (defaults to the built-in profiles).
+    pub fn with_parsers(mut self, parsers: Arc<BrokerParserRegistry>) -> Self {
+        self.parsers = parsers;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Stores an uploaded statement in an account folder, creating the folder
+    /// when needed. Returns the path written.
+    pub fn save_upload(
+        &self,
+        account_folder: &str,
+        file_name: &str,
+        content: &[u8],
+    ) -> Result<PathBuf> {
+        let folder = sanitize_name(account_folder).ok_or_else(|| {
+            Error::Validation(ValidationError::InvalidInput(format!(
+                "Invalid account folder name '{}'",
+                account_folder
+            )))
+        })?;
+        let file = sanitize_name(file_name).ok_or_else(|| {
+            Error::Validation(ValidationError::InvalidInput(format!(
+                "Invalid file name '{}'",
+                file_name
+            )))
+        })?;
+        let dir = self.root.join(folder);
+        std::fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+        let path = dir.join(file);
+        std::fs::write(&path, content).map_err(|e| io_error(&path, e))?;
+        Ok(path)
+    }
+
+    /// Account folders, sorted by name.
+    fn account_folders(&self) -> Result<Vec<String>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = std::fs::read_dir(&self.root).map_err(|e| io_error(&self.root, e))?;
+        let mut folders: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .filter(|name| !name.starts_with('.'))
+            .collect();
+        folders.sort();
+        Ok(folders)
+    }
+
+    fn folder_for(&self, account_id: &str) -> Result<PathBuf> {
+        account_id
+            .strip_prefix(ACCOUNT_ID_PREFIX)
+            .and_then(sanitize_name)
+            .map(|folder| self.root.join(folder))
+            .ok_or_else(|| {
+                Error::Validation(ValidationError::InvalidInput(format!(
+                    "'{}' is not a local file account",
+                    account_id
+                )))
+            })
+    }
+
+    /// Reads every statement in the account folder modified on or after
+    /// `since`, oldest file first.
+    fn read_activities(
+        &self,
+        account_id: &str,
+        since: Option<NaiveDate>,
+    ) -> Result<Vec<AccountUniversalActivity>> {
+        let folder = self.folder_for(account_id)?;
+        if !folder.is_dir() {
+            return Ok(Vec::new());
+        }
+        let entries = std::fs::read_dir(&folder).map_err(|e| io_error(&folder, e))?;
+        let mut files: Vec<(DateTime<Utc>, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .filter(|path| {
+                !path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with('.'))
+            })
+            .filter_map(|path| {
+                let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                Some((DateTime::<Utc>::from(modified), path))
+            })
+            .filter(|(modified, _)| since.is_none_or(|since| modified.date_naive() >= since))
+            .collect();
+        files.sort();
+
+        let mut activities = Vec::new();
+        for (_, path) in files {
+            let content = std::fs::read(&path).map_err(|e| io_error(&path, e))?;
+            let file_name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default()
+                .to_string();
+            // One unreadable file must not block the rest of the folder
+            let statement = match self.parsers.parse(&content) {
+                Ok(statement) => statement,
+                Err(e) => {
+                    warn!(
+                        "Skipping '{}' in local broker folder: {}",
+                        path.display(),
+                        e
+                    );
+                    continue;
+                }
+            };
+            debug!(
+                "Read {} rows from '{}' ({})",
+                statement.activities.len(),
+                file_name,
+                statement.broker_name
+            );
+            for row in &statement.activities {
+                if !row.is_valid {
+                    warn!(
+                        "Skipping invalid row {} in '{}': {:?}",
+                        row.line_number.unwrap_or_default(),
+                        file_name,
+                        row.errors
+                    );
+                    continue;
+                }
+                activities.push(to_universal_activity(
+                    account_id,
+                    &file_name,
+                    &statement.broker_name,
+                    row,
+                ));
+            }
+        }
+        Ok(activities)
+    }
+}
+
+#[async_trait]
+impl BrokerApiClient for LocalFileBrokerClient {
+    async fn list_connections(&self) -> Result<Vec<BrokerConnection>> {
+        Ok(vec![BrokerConnection {
+            id: CONNECTION_ID.to_string(),
+            brokerage: Some(BrokerConnectionBrokerage {
+                id: Some(CONNECTION_ID.to_string()),
+                slug: Some(LOCAL_FILES_PROVIDER.to_string()),
+                name: Some("Local files".to_string()),
+                display_name: Some("Local files".to_string()),
+                aws_s3_logo_url: None,
+                aws_s3_square_logo_url: None,
+            }),
+            connection_type: Some("read".to_string()),
+            status: Some("connected".to_string()),
+            disabled: false,
+            disabled_date: None,
+            updated_at: None,
+            name: Some(self.root.display().to_string()),
+        }])
+    }
+
+    async fn list_accounts(
+        &self,
+        _authorization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<BrokerAccount>> {
+        Ok(self
+            .account_folders()?
+            .into_iter()
+            .map(|folder| BrokerAccount {
+                id: Some(format!("{}{}", ACCOUNT_ID_PREFIX, folder)),
+                name: Some(folder),
+                brokerage_authorization: Some(CONNECTION_ID.to_string()),
+                institution_name: Some(LOCAL_FILES_PROVIDER.to_string()),
+                provider: Some(LOCAL_FILES_PROVIDER.to_string()),
+                sync_enabled: true,
+                ..Default::default()
+            })
+            .collect())
+    }
+
+    async fn list_brokerages(&self) -> Result<Vec<BrokerBrokerage>> {
+        Ok(vec![BrokerBrokerage {
+            id: Some(CONNECTION_ID.to_string()),
+            slug: Some(LOCAL_FILES_PROVIDER.to_string()),
+            name: Some("Local files".to_string()),
+            display_name: Some("Local files".to_string()),
+            url: None,
+            enabled: true,
+        }])
+    }
+
+    async fn get_account_activities(
+        &self,
+        account_id: &str,
+        start_date: Option<&str>,
+        _end_date: Option<&str>,
+        offset: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<PaginatedUniversalActivity> {
+        let offset = offset.unwrap_or(0).max(0) as usize;
+        let since = start_date.and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+        let key = format!("{}|{}", account_id, start_date.unwrap_or(""));
+
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        // The first page always re-reads the folder
+        let cached = cache
+            .as_ref()
+            .filter(|(cached_key, _)| offset > 0 && *cached_key == key)
+            .is_some();
+        if !cached {
+            *cache = Some((key, self.read_activities(account_id, since)?));
+        }
+        let activities = cache
+            .as_ref()
+            .map(|(_, a)| a.as_slice())
+            .unwrap_or_default();
+
+        let total = activities.len();
+        let end = match limit {
+            Some(limit) if limit > 0 => (offset + limit as usize).min(total),
+            _ => total,
+        };
+        let data = activities.get(offset..end).unwrap_or_default().to_vec();
+        Ok(PaginatedUniversalActivity {
+            data,
+            pagination: Some(PaginationDetails {
+                offset: Some(offset as i64),
+                limit,
+                total: Some(total as i64),
+                has_more: Some(end < total),
+            }),
+        })
+    }
+
+    async fn get_account_holdings(&self, _account_id: &str) -> Result<BrokerHoldingsResponse> {
+        // An empty snapshot would wipe the account's positions
+        Err(Error::Validation(ValidationError::InvalidInput(
+            "Local file sync only supports transaction tracking".to_string(),
+        )))
+    }
+}
+
+/// Maps a parsed statement row to the shape the sync service upserts.
+fn to_universal_activity(
+    account_id: &str,
+    file_name: &str,
+    broker_name: &str,
+    row: &ActivityImport,
+) -> AccountUniversalActivity {
+    let record_id = row
+        .source_record_id
+        .clone()
+        .unwrap_or_else(|| format!("{}:{}", file_name, row.line_number.unwrap_or_default()));
+    let source_system = row
+        .source_system
+        .clone()
+        .unwrap_or_else(|| LOCAL_FILES_PROVIDER.to_string());
+    let symbol = Some(row.symbol.trim())
+        .filter(|s| !s.is_empty())
+        .map(|symbol| AccountUniversalActivitySymbol {
+            symbol: Some(symbol.to_string()),
+            raw_symbol: Some(symbol.to_string()),
+            description: row.symbol_name.clone(),
+            symbol_type: row.instrument_type.as_ref().map(|code| {
+                AccountUniversalActivitySymbolType {
+                    code: Some(code.clone()),
+                    ..Default::default()
+                }
+            }),
+            exchange: row
+                .exchange_mic
+                .as_ref()
+                .map(|mic| AccountUniversalActivityExchange {
+                    mic_code: Some(mic.clone()),
+                    ..Default::default()
+                }),
+            ..Default::default()
+        });
+    let reasons: Vec<String> = row
+        .warnings
+        .iter()
+        .flat_map(|warnings| warnings.values().flatten().cloned())
+        .collect();
+
+    AccountUniversalActivity {
+        id: Some(stable_activity_id(account_id, &source_system, &record_id)),
+        symbol,
+        price: row.unit_price.and_then(|d| d.to_f64()),
+        units: row.quantity.and_then(|d| d.to_f64()),
+        amount: row.amount.and_then(|d| d.to_f64()),
+        fee: row.fee.and_then(|d| d.to_f64()),
+        fx_rate: row.fx_rate.and_then(|d| d.to_f64()),
+        currency: Some(AccountUniversalActivityCurrency {
+            code: Some(row.currency.clone()),
+            ..Default::default()
+        }),
+        activity_type: Some(row.activity_type.clone()),
+        subtype: row.subtype.clone(),
+        description: row.comment.clone(),
+        // RFC 3339 keeps the idempotency key stable across re-reads
+        trade_date: Some(format!("{}T00:00:00Z", row.date)),
+        institution: Some(broker_name.to_string()),
+        provider_type: Some(LOCAL_FILES_PROVIDER.to_string()),
+        source_system: Some(source_system),
+        source_record_id: Some(record_id),
+        source_group_id: row.source_group_id.clone(),
+        mapping_metadata: Some(MappingMetadata {
+            reasons,
+            ..Default::default()
+        }),
+        needs_review: row.is_draft || row.warnings.as_ref().is_some_and(|w| !w.is_empty()),
+        ..Default::default()
+    }
+}
+
+/// Deterministic UUID-shaped id so re-reading a statement updates the same
+/// activities instead of duplicating them.
+fn stable_activity_id(account_id: &str, source_system: &str, record_id: &str) -> String {
+    let digest = format!(
+        "{:x}",
+        Sha256::digest(format!("{}|{}|{}", account_id, source_system, record_id).as_bytes())
+    );
+    format!(
+        "{}-{}-{}-{}-{}",
+        &digest[0..8],
+        &digest[8..12],
+        &digest[12..16],
+        &digest[16..20],
+        &digest[20..32]
+    )
+}
+
+/// A single path component without separators or leading dots.
+fn sanitize_name(name: &str) -> Option<&str> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && !name.contains(['/', '\\'])
+        && !name.contains('\0');
+    valid.then_some(name)
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> Error {
+    Error::Unexpected(format!("{}: {}", path.display(), e))
+}
+
+/// Latest modification time in the drop folder, used to skip scheduled syncs
+/// when nothing changed.
+pub fn latest_modification(root: &Path) -> Option<DateTime<Utc>> {
+    let mut latest: Option<DateTime<Utc>> = None;
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                let modified = DateTime::<Utc>::from(modified);
+                latest = Some(latest.map_or(modified, |l| l.max(modified)));
+            }
+            if path.is_dir() && dir == root {
+                dirs.push(path);
+            }
+        }
+    }
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::broker::mapping::map_broker_activity;
+
+    const REVOLUT: &str =
+        "Date,Ticker,Type,Quantity,Price per share,Total Amount,Currency,FX Rate\n\
+2024-01-15T14:30:05.123Z,AAPL,BUY - MARKET,10,USD 185.50,\"USD 1,856.49\",USD,1.0000\n";
+
+    fn client_with_account() -> (tempfile::TempDir, LocalFileBrokerClient) {
+        let dir = tempfile::tempdir().unwrap();
+        let client = LocalFileBrokerClient::new(dir.path());
+        client
+            .save_upload("revolut", "statement.csv", REVOLUT.as_bytes())
+            .unwrap();
+        (dir, client)
+    }
+
+    #[tokio::test]
+    async fn test_lists_one_account_per_folder() {
+        let (_dir, client) = client_with_account();
+        let accounts = client.list_accounts(None).await.unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].id.as_deref(), Some("local:revolut"));
+        assert_eq!(accounts[0].provider.as_deref(), Some(LOCAL_FILES_PROVIDER));
+        assert!(accounts[0].sync_enabled);
+    }
+
+    #[tokio::test]
+    async fn test_serves_statement_rows_with_stable_ids() {
+        let (_dir, client) = client_with_account();
+        let page = client
+            .get_account_activities("local:revolut", None, None, Some(0), Some(1000))
+            .await
+            .unwrap();
+        assert_eq!(page.data.len(), 1);
+        assert_eq!(page.pagination.as_ref().unwrap().has_more, Some(false));
+
+        let activity = &page.data[0];
+        assert_eq!(activity.activity_type.as_deref(), Some("BUY"));
+        assert_eq!(activity.trade_date.as_deref(), Some("2024-01-15T00:00:00Z"));
+        assert_eq!(activity.source_system.as_deref(), Some("REVOLUT"));
+
+        let again = client
+            .get_account_activities("local:revolut", None, None, Some(0), Some(1000))
+            .await
+            .unwrap();
+        assert_eq!(again.data[0].id, activity.id);
+
+        let mapped = map_broker_activity(activity, "acc-1", Some("EUR"), Some("EUR")).unwrap();
+        assert_eq!(mapped.symbol.unwrap().symbol.as_deref(), Some("AAPL"));
+        assert_eq!(mapped.currency, "USD");
+        assert_eq!(mapped.fee, Some(rust_decimal_macros::dec!(1.49)));
+    }
+
+    #[tokio::test]
+    async fn test_incremental_window_skips_old_files() {
+        let (_dir, client) = client_with_account();
+        let tomorrow = (Utc::now().date_naive() + chrono::Days::new(1))
+            .format("%Y-%m-%d")
+            .to_string();
+        let page = client
+            .get_account_activities("local:revolut", Some(&tomorrow), None, Some(0), Some(1000))
+            .await
+            .unwrap();
+        assert!(page.data.is_empty());
+    }
+
+    #[test]
+    fn test_rejects_path_traversal() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = LocalFileBrokerClient::new(dir.path());
+        assert!(client.save_upload("../etc", "x.csv", b"").is_err());
+        assert!(client.save_upload("degiro", "../x.csv", b"").is_err());
+        assert!(client.folder_for("local:..").is_err());
+        assert!(client.folder_for("snaptrade-id").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod local_files;
 pub mod mapping;
 mod models;
 pub mod orchestrator;
@@ -5,6 +6,7 @@ pub mod progress;
 mod service;
 mod traits;
 
+pub use local_files::{LocalFileBrokerClient, LOCAL_FILES_PROVIDER};
 pub use models::*;
 pub use orchestrator::{SyncConfig, SyncOrchestrator};
 pub use progress::{NoOpProgressReporter, SyncProgressPayload, SyncProgressReporter, SyncStatus};
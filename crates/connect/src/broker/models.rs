@@ -118,6 +118,10 @@ pub struct BrokerAccount {
     /// Whether this account is shared with the household
     #[serde(default)]
     pub shared_with_household: bool,
+
+    /// Provider serving the account; the Connect API (SnapTrade) when unset
+    #[serde(default)]
+    pub provider: Option<String>,
 }
 
 fn default_sync_enabled() -> bool {
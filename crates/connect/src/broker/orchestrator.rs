@@ -611,6 +611,14 @@ impl<P: SyncProgressReporter> SyncOrchestrator<P> {
                     activities_summary.activities_upserted += inserted as usize;
                     activities_summary.assets_inserted += assets_created as usize;
                     activities_summary.new_asset_ids.extend(new_asset_ids);
+
+                    self.record_reported_holdings(
+                        api_client,
+                        &account_id,
+                        &account_name,
+                        &broker_account_id,
+                    )
+                    .await;
                 }
                 Err(err) => {
                     error!("Failed to sync activities for '{}': {}", account_name, err);
@@ -653,6 +661,41 @@ impl<P: SyncProgressReporter> SyncOrchestrator<P> {
         Ok((activities_summary, holdings_summary))
     }
 
+    /// Records the broker's holdings of a TRANSACTIONS mode account for
+    /// reconciliation. Best effort: not every broker reports holdings.
+    async fn record_reported_holdings(
+        &self,
+        api_client: &dyn BrokerApiClient,
+        account_id: &str,
+        account_name: &str,
+        broker_account_id: &str,
+    ) {
+        let holdings = match api_client.get_account_holdings(broker_account_id).await {
+            Ok(holdings) => holdings,
+            Err(e) => {
+                debug!(
+                    "No broker holdings to reconcile for '{}': {}",
+                    account_name, e
+                );
+                return;
+            }
+        };
+        if let Err(e) = self
+            .sync_service
+            .record_reported_holdings(
+                account_id.to_string(),
+                holdings.balances.unwrap_or_default(),
+                holdings.positions.unwrap_or_default(),
+            )
+            .await
+        {
+            warn!(
+                "Failed to record broker holdings of '{}' for reconciliation: {}",
+                account_name, e
+            );
+        }
+    }
+
     /// Sync holdings for a single account (HOLDINGS tracking mode).
     ///
     /// Fetches current holdings from the broker API and saves as a snapshot.
@@ -33,6 +33,9 @@ use wealthfolio_core::events::{DomainEvent, DomainEventSink, NoOpDomainEventSink
 use wealthfolio_core::portfolio::snapshot::{
     AccountStateSnapshot, Position, SnapshotRepositoryTrait, SnapshotServiceTrait, SnapshotSource,
 };
+use wealthfolio_core::reconciliation::{
+    HoldingsReconciliationServiceTrait, ReportedHoldings, ReportedPosition,
+};
 use wealthfolio_core::utils::time_utils::valuation_date_today;
 
 const DEFAULT_BROKERAGE_PROVIDER: &str = "snaptrade";
@@ -51,6 +54,7 @@ pub struct BrokerSyncService {
     import_run_repository: Arc<dyn ImportRunRepositoryTrait>,
     snapshot_repository: Arc<dyn SnapshotRepositoryTrait>,
     snapshot_service: Option<Arc<dyn SnapshotServiceTrait>>,
+    reconciliation_service: Option<Arc<dyn HoldingsReconciliationServiceTrait>>,
     event_sink: Arc<dyn DomainEventSink>,
 }
 
@@ -76,6 +80,7 @@ impl BrokerSyncService {
             import_run_repository,
             snapshot_repository,
             snapshot_service: None,
+            reconciliation_service: None,
             event_sink: Arc::new(NoOpDomainEventSink),
         }
     }
@@ -89,6 +94,16 @@ impl BrokerSyncService {
         self
     }
 
+    /// Sets the reconciliation service that receives broker-reported holdings
+    /// of accounts tracked through transactions.
+    pub fn with_reconciliation_service(
+        mut self,
+        reconciliation_service: Arc<dyn HoldingsReconciliationServiceTrait>,
+    ) -> Self {
+        self.reconciliation_service = Some(reconciliation_service);
+        self
+    }
+
     /// Sets the domain event sink for emitting events during broker sync.
     pub fn with_event_sink(mut self, event_sink: Arc<dyn DomainEventSink>) -> Self {
         self.event_sink = event_sink;
@@ -222,7 +237,12 @@ impl BrokerSyncServiceTrait for BrokerSyncService {
                 platform_id,
                 account_number: broker_account.account_number.clone(),
                 meta: broker_account.to_meta_json(),
-                provider: Some("SNAPTRADE".to_string()),
+                provider: Some(
+                    broker_account
+                        .provider
+                        .clone()
+                        .unwrap_or_else(|| "SNAPTRADE".to_string()),
+                ),
                 provider_account_id: Some(provider_account_id.clone()),
                 is_archived: false,
                 tracking_mode: TrackingMode::NotSet,
@@ -524,6 +544,70 @@ impl BrokerSyncServiceTrait for BrokerSyncService {
         Ok(())
     }
 
+    async fn record_reported_holdings(
+        &self,
+        account_id: String,
+        balances: Vec<HoldingsBalance>,
+        positions: Vec<HoldingsPosition>,
+    ) -> Result<()> {
+        let Some(reconciliation_service) = self.reconciliation_service.as_ref() else {
+            return Ok(());
+        };
+        let account = self.account_service.get_account(&account_id)?;
+
+        let mut cash_balances: HashMap<String, Decimal> = HashMap::new();
+        for balance in &balances {
+            if let (Some(currency), Some(cash)) = (
+                balance.currency.as_ref().and_then(|c| c.code.clone()),
+                balance.cash,
+            ) {
+                *cash_balances.entry(currency).or_insert(Decimal::ZERO) +=
+                    Decimal::from_f64(cash).unwrap_or(Decimal::ZERO);
+            }
+        }
+
+        let reported_positions = positions
+            .iter()
+            .filter_map(|pos| {
+                let symbol_info = pos.symbol.as_ref().and_then(|s| s.symbol.as_ref());
+                let is_crypto = mapping::is_broker_crypto(
+                    symbol_info
+                        .and_then(|s| s.symbol_type.as_ref())
+                        .and_then(|t| t.code.as_deref()),
+                );
+                let (symbol, exchange_mic) = Self::normalize_holdings_symbol(
+                    symbol_info.and_then(|s| s.raw_symbol.as_deref()),
+                    symbol_info.and_then(|s| s.symbol.as_deref()),
+                    is_crypto,
+                )?;
+                let quantity = Decimal::from_f64(pos.units?)?.round_dp(HOLDINGS_DECIMAL_PRECISION);
+                Some(ReportedPosition {
+                    symbol,
+                    exchange_mic,
+                    quantity,
+                    price: pos
+                        .price
+                        .and_then(Decimal::from_f64)
+                        .map(|p| p.round_dp(HOLDINGS_DECIMAL_PRECISION)),
+                    currency: pos
+                        .currency
+                        .as_ref()
+                        .and_then(|c| c.code.clone())
+                        .unwrap_or_else(|| account.currency.clone()),
+                })
+            })
+            .collect();
+
+        reconciliation_service
+            .record_reported_holdings(ReportedHoldings {
+                account_id,
+                as_of: valuation_date_today(),
+                positions: reported_positions,
+                cash_balances,
+            })
+            .await
+    }
+
     async fn save_broker_holdings(
         &self,
         account_id: String,
@@ -744,6 +828,7 @@ impl BrokerSyncServiceTrait for BrokerSyncService {
             net_contribution_base: Decimal::ZERO,
             cash_total_account_currency: cash_total,
             cash_total_base_currency: Decimal::ZERO,
+            cash_interest_accrued: Decimal::ZERO,
             calculated_at: now.naive_utc(),
             source: SnapshotSource::BrokerImported,
         };
@@ -961,6 +1046,7 @@ impl BrokerSyncService {
             net_contribution_base: earliest.net_contribution_base,
             cash_total_account_currency: earliest.cash_total_account_currency,
             cash_total_base_currency: earliest.cash_total_base_currency,
+            cash_interest_accrued: Decimal::ZERO,
         };
 
         self.snapshot_repository
@@ -141,6 +141,15 @@ pub trait BrokerSyncServiceTrait: Send + Sync {
         error: Option<String>,
     ) -> Result<()>;
 
+    /// Record broker holdings of an account tracked through transactions, for
+    /// reconciliation against the positions computed from its activities.
+    async fn record_reported_holdings(
+        &self,
+        account_id: String,
+        balances: Vec<HoldingsBalance>,
+        positions: Vec<HoldingsPosition>,
+    ) -> Result<()>;
+
     /// Save broker holdings as a snapshot with source=BROKER_IMPORTED.
     /// Returns (position_diff, assets_created, new_asset_ids).
     async fn save_broker_holdings(
@@ -8,16 +8,17 @@ pub mod broker;
 pub mod broker_ingest;
 pub mod client;
 pub mod platform;
+pub mod wallet;
 
 // Re-export commonly used types
 #[cfg(feature = "broker")]
 pub use broker::{
     AccountUniversalActivity, BrokerAccount, BrokerApiClient, BrokerBrokerage, BrokerConnection,
-    BrokerSyncService, BrokerSyncServiceTrait, NoOpProgressReporter, PaginatedUniversalActivity,
-    PlanLimitValue, PlanLimits, PlanPricing, PlansResponse, PlatformRepositoryTrait,
-    SubscriptionPlan, SyncAccountsResponse, SyncActivitiesResponse, SyncConfig,
-    SyncConnectionsResponse, SyncOrchestrator, SyncProgressPayload, SyncProgressReporter,
-    SyncResult, SyncStatus, UserInfo, UserTeam,
+    BrokerSyncService, BrokerSyncServiceTrait, LocalFileBrokerClient, NoOpProgressReporter,
+    PaginatedUniversalActivity, PlanLimitValue, PlanLimits, PlanPricing, PlansResponse,
+    PlatformRepositoryTrait, SubscriptionPlan, SyncAccountsResponse, SyncActivitiesResponse,
+    SyncConfig, SyncConnectionsResponse, SyncOrchestrator, SyncProgressPayload,
+    SyncProgressReporter, SyncResult, SyncStatus, UserInfo, UserTeam,
 };
 
 // Re-export the HTTP client and public functions
@@ -29,3 +30,4 @@ pub use broker_ingest::{
     ReviewMode,
 };
 pub use platform::Platform;
+pub use wallet::{WalletConfig, WalletSyncResult, WalletSyncService, WalletSyncServiceTrait};
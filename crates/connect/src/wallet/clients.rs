@@ -0,0 +1,865 @@
+//! Chain clients for watch-only wallets.
+//!
+//! Each client turns the history of a set of addresses into [`WalletTransfer`]s:
+//! - [`EsploraClient`]: Esplora REST API (mempool.space, Blockstream, local `electrs`).
+//! - [`BlockbookClient`]: Blockbook v2 REST API, which also resolves xpubs server-side.
+//! - [`EthereumRpcClient`]: plain Ethereum JSON-RPC. Nodes have no per-address index,
+//!   so blocks are scanned forward from the checkpoint.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::debug;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
+use wealthfolio_core::errors::{Error, Result};
+
+use super::models::{
+    is_extended_public_key, WalletChain, WalletCheckpoint, WalletConfig, WalletEndpointKind,
+    WalletFetch, WalletTransfer,
+};
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Recent blocks re-read on every sync so shallow reorgs are picked up.
+/// Activities are upserted by transaction, so re-reading is harmless.
+const BITCOIN_REORG_MARGIN: u64 = 6;
+const ETHEREUM_REORG_MARGIN: u64 = 12;
+
+/// Maximum blocks scanned per Ethereum sync; the next sync continues from the checkpoint.
+const ETHEREUM_MAX_BLOCKS_PER_SYNC: u64 = 5_000;
+
+/// Fetches the confirmed history of a watched wallet.
+#[async_trait]
+pub trait WalletChainClient: Send + Sync {
+    /// Confirmed transfers after `since` (all history when `None`), with the new checkpoint.
+    async fn fetch(
+        &self,
+        sources: &[String],
+        since: Option<&WalletCheckpoint>,
+    ) -> Result<WalletFetch>;
+}
+
+/// Builds the client for a wallet configuration.
+pub fn build_client(config: &WalletConfig) -> Result<Box<dyn WalletChainClient>> {
+    config.validate()?;
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| Error::Unexpected(format!("Failed to initialize HTTP client: {}", e)))?;
+    let base_url = config.endpoint_url.trim().trim_end_matches('/').to_string();
+
+    Ok(match config.endpoint_kind {
+        WalletEndpointKind::Esplora => Box::new(EsploraClient {
+            http,
+            base_url,
+            chain: config.chain,
+        }),
+        WalletEndpointKind::Blockbook => Box::new(BlockbookClient {
+            http,
+            base_url,
+            chain: config.chain,
+        }),
+        WalletEndpointKind::EthereumRpc => Box::new(EthereumRpcClient {
+            http,
+            url: base_url,
+            start_block: config.start_block,
+        }),
+    })
+}
+
+/// Converts an integer amount of the smallest unit (satoshi, wei) to coins.
+fn to_coins(units: u128, chain: WalletChain) -> Result<Decimal> {
+    i128::try_from(units)
+        .ok()
+        .and_then(|u| Decimal::try_from_i128_with_scale(u, chain.decimals()).ok())
+        .map(|d| d.normalize())
+        .ok_or_else(|| Error::Unexpected(format!("Amount out of range: {}", units)))
+}
+
+/// Net effect of a transaction given the wallet's inputs and outputs, in smallest units.
+///
+/// The fee is only attributed to the wallet when it funded the transaction.
+fn transfer_from_units(
+    txid: String,
+    timestamp: DateTime<Utc>,
+    block_height: u64,
+    own_inputs: u128,
+    own_outputs: u128,
+    fee: u128,
+    chain: WalletChain,
+) -> Result<Option<WalletTransfer>> {
+    let received = to_coins(own_outputs, chain)?;
+    let spent = to_coins(own_inputs, chain)?;
+    let net_amount = received - spent;
+    if net_amount.is_zero() {
+        return Ok(None);
+    }
+    let fee = if own_inputs > 0 {
+        to_coins(fee, chain)?
+    } else {
+        Decimal::ZERO
+    };
+    Ok(Some(WalletTransfer {
+        txid,
+        timestamp,
+        block_height,
+        net_amount,
+        fee,
+    }))
+}
+
+fn timestamp_from_secs(secs: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(secs, 0).unwrap_or_else(Utc::now)
+}
+
+/// First block to read for a sync starting after `since`.
+fn first_block_after(since: Option<&WalletCheckpoint>, reorg_margin: u64) -> u64 {
+    since
+        .map(|c| (c.block_height + 1).saturating_sub(reorg_margin))
+        .unwrap_or(0)
+}
+
+async fn get_json<T: DeserializeOwned>(http: &reqwest::Client, url: &str) -> Result<T> {
+    let response = http
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| Error::Unexpected(format!("Request to {} failed: {}", url, e)))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| Error::Unexpected(format!("Failed to read response: {}", e)))?;
+    if !status.is_success() {
+        return Err(Error::Unexpected(format!(
+            "Wallet endpoint error {}: {}",
+            status,
+            body.chars().take(200).collect::<String>()
+        )));
+    }
+    serde_json::from_str(&body)
+        .map_err(|e| Error::Unexpected(format!("Failed to parse response from {}: {}", url, e)))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Esplora
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct EsploraTx {
+    txid: String,
+    #[serde(default)]
+    vin: Vec<EsploraVin>,
+    #[serde(default)]
+    vout: Vec<EsploraOutput>,
+    #[serde(default)]
+    fee: u64,
+    status: EsploraStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraVin {
+    /// `None` for coinbase inputs.
+    prevout: Option<EsploraOutput>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraOutput {
+    scriptpubkey_address: Option<String>,
+    value: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraStatus {
+    confirmed: bool,
+    block_height: Option<u64>,
+    block_time: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraAddress {
+    chain_stats: EsploraStats,
+}
+
+#[derive(Debug, Deserialize)]
+struct EsploraStats {
+    funded_txo_sum: u64,
+    spent_txo_sum: u64,
+}
+
+/// Client for the Esplora REST API.
+pub struct EsploraClient {
+    http: reqwest::Client,
+    base_url: String,
+    chain: WalletChain,
+}
+
+impl EsploraClient {
+    /// Confirmed transactions of `address` at or above `min_height`, newest first.
+    async fn address_txs(&self, address: &str, min_height: u64) -> Result<Vec<EsploraTx>> {
+        let mut txs: Vec<EsploraTx> = Vec::new();
+        let mut url = format!("{}/address/{}/txs", self.base_url, address);
+        loop {
+            let page: Vec<EsploraTx> = get_json(&self.http, &url).await?;
+            let last_confirmed = page
+                .iter()
+                .rev()
+                .find(|tx| tx.status.confirmed)
+                .map(|tx| (tx.txid.clone(), tx.status.block_height.unwrap_or(0)));
+            txs.extend(page.into_iter().filter(|tx| {
+                tx.status.confirmed && tx.status.block_height.unwrap_or(0) >= min_height
+            }));
+            match last_confirmed {
+                // Pages are newest first: stop once below the checkpoint
+                Some((txid, height)) if height > min_height => {
+                    url = format!("{}/address/{}/txs/chain/{}", self.base_url, address, txid);
+                }
+                _ => break,
+            }
+        }
+        Ok(txs)
+    }
+}
+
+#[async_trait]
+impl WalletChainClient for EsploraClient {
+    async fn fetch(
+        &self,
+        sources: &[String],
+        since: Option<&WalletCheckpoint>,
+    ) -> Result<WalletFetch> {
+        let tip: u64 =
+            get_json(&self.http, &format!("{}/blocks/tip/height", self.base_url)).await?;
+        let min_height = first_block_after(since, BITCOIN_REORG_MARGIN);
+        let own: HashSet<&str> = sources.iter().map(String::as_str).collect();
+
+        let mut by_txid: BTreeMap<String, EsploraTx> = BTreeMap::new();
+        let mut balance_units: u128 = 0;
+        for address in sources {
+            for tx in self.address_txs(address, min_height).await? {
+                by_txid.entry(tx.txid.clone()).or_insert(tx);
+            }
+            let info: EsploraAddress = get_json(
+                &self.http,
+                &format!("{}/address/{}", self.base_url, address),
+            )
+            .await?;
+            balance_units += u128::from(
+                info.chain_stats
+                    .funded_txo_sum
+                    .saturating_sub(info.chain_stats.spent_txo_sum),
+            );
+        }
+
+        let mut transfers = Vec::new();
+        for (txid, tx) in by_txid {
+            let is_own = |o: &EsploraOutput| {
+                o.scriptpubkey_address
+                    .as_deref()
+                    .is_some_and(|a| own.contains(a))
+            };
+            let own_inputs: u128 = tx
+                .vin
+                .iter()
+                .filter_map(|i| i.prevout.as_ref())
+                .filter(|o| is_own(o))
+                .map(|o| u128::from(o.value))
+                .sum();
+            let own_outputs: u128 = tx
+                .vout
+                .iter()
+                .filter(|o| is_own(o))
+                .map(|o| u128::from(o.value))
+                .sum();
+            if let Some(transfer) = transfer_from_units(
+                txid,
+                timestamp_from_secs(tx.status.block_time.unwrap_or_default()),
+                tx.status.block_height.unwrap_or_default(),
+                own_inputs,
+                own_outputs,
+                u128::from(tx.fee),
+                self.chain,
+            )? {
+                transfers.push(transfer);
+            }
+        }
+
+        Ok(WalletFetch {
+            transfers,
+            checkpoint: WalletCheckpoint { block_height: tip },
+            balance: to_coins(balance_units, self.chain)?,
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Blockbook
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockbookPage {
+    #[serde(default)]
+    total_pages: Option<u32>,
+    balance: String,
+    #[serde(default)]
+    transactions: Vec<BlockbookTx>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockbookTx {
+    txid: String,
+    block_height: i64,
+    block_time: i64,
+    #[serde(default)]
+    fees: Option<String>,
+    #[serde(default)]
+    vin: Vec<BlockbookIo>,
+    #[serde(default)]
+    vout: Vec<BlockbookIo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockbookIo {
+    #[serde(default)]
+    value: Option<String>,
+    #[serde(default)]
+    is_own: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockbookStatus {
+    blockbook: BlockbookInfo,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockbookInfo {
+    best_height: u64,
+}
+
+fn parse_units(value: Option<&str>) -> u128 {
+    value.and_then(|v| v.parse().ok()).unwrap_or(0)
+}
+
+/// Client for the Blockbook v2 REST API. Inputs and outputs are flagged `isOwn`
+/// by the server, including for addresses derived from an xpub.
+pub struct BlockbookClient {
+    http: reqwest::Client,
+    base_url: String,
+    chain: WalletChain,
+}
+
+#[async_trait]
+impl WalletChainClient for BlockbookClient {
+    async fn fetch(
+        &self,
+        sources: &[String],
+        since: Option<&WalletCheckpoint>,
+    ) -> Result<WalletFetch> {
+        let status: BlockbookStatus =
+            get_json(&self.http, &format!("{}/api/v2", self.base_url)).await?;
+        let min_height = first_block_after(since, BITCOIN_REORG_MARGIN);
+
+        let mut by_txid: BTreeMap<String, BlockbookTx> = BTreeMap::new();
+        let mut balance_units: u128 = 0;
+        for source in sources {
+            let kind = if is_extended_public_key(source) {
+                "xpub"
+            } else {
+                "address"
+            };
+            let mut page = 1;
+            loop {
+                let url = format!(
+                    "{}/api/v2/{}/{}?details=txs&page={}&pageSize=100&from={}",
+                    self.base_url, kind, source, page, min_height
+                );
+                let result: BlockbookPage = get_json(&self.http, &url).await?;
+                if page == 1 {
+                    balance_units += parse_units(Some(&result.balance));
+                }
+                for tx in result.transactions {
+                    if tx.block_height > 0 {
+                        by_txid.entry(tx.txid.clone()).or_insert(tx);
+                    }
+                }
+                if page >= result.total_pages.unwrap_or(1) {
+                    break;
+                }
+                page += 1;
+            }
+        }
+
+        let mut transfers = Vec::new();
+        for (txid, tx) in by_txid {
+            let own_sum = |ios: &[BlockbookIo]| -> u128 {
+                ios.iter()
+                    .filter(|io| io.is_own.unwrap_or(false))
+                    .map(|io| parse_units(io.value.as_deref()))
+                    .sum()
+            };
+            if let Some(transfer) = transfer_from_units(
+                txid,
+                timestamp_from_secs(tx.block_time),
+                tx.block_height as u64,
+                own_sum(&tx.vin),
+                own_sum(&tx.vout),
+                parse_units(tx.fees.as_deref()),
+                self.chain,
+            )? {
+                transfers.push(transfer);
+            }
+        }
+
+        Ok(WalletFetch {
+            transfers,
+            checkpoint: WalletCheckpoint {
+                block_height: status.blockbook.best_height,
+            },
+            balance: to_coins(balance_units, self.chain)?,
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Ethereum JSON-RPC
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct EthBlock {
+    timestamp: String,
+    #[serde(default)]
+    transactions: Vec<EthTransaction>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EthTransaction {
+    hash: String,
+    from: String,
+    to: Option<String>,
+    value: String,
+    #[serde(default)]
+    gas_price: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EthReceipt {
+    gas_used: String,
+    #[serde(default)]
+    effective_gas_price: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+}
+
+fn parse_hex(value: &str) -> Result<u128> {
+    let digits = value.trim_start_matches("0x");
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    u128::from_str_radix(digits, 16)
+        .map_err(|e| Error::Unexpected(format!("Invalid hex quantity {}: {}", value, e)))
+}
+
+/// Client for a plain Ethereum JSON-RPC endpoint. Tracks native ETH only.
+pub struct EthereumRpcClient {
+    http: reqwest::Client,
+    url: String,
+    start_block: Option<u64>,
+}
+
+impl EthereumRpcClient {
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| Error::Unexpected(format!("RPC {} failed: {}", method, e)))?;
+        let parsed: RpcResponse<T> = response
+            .json()
+            .await
+            .map_err(|e| Error::Unexpected(format!("Failed to parse RPC {}: {}", method, e)))?;
+        if let Some(err) = parsed.error {
+            return Err(Error::Unexpected(format!(
+                "RPC {} error: {}",
+                method, err.message
+            )));
+        }
+        parsed
+            .result
+            .ok_or_else(|| Error::Unexpected(format!("RPC {} returned no result", method)))
+    }
+
+    async fn block(&self, number: u64) -> Result<EthBlock> {
+        self.call(
+            "eth_getBlockByNumber",
+            json!([format!("0x{:x}", number), true]),
+        )
+        .await
+    }
+
+    async fn balance(&self, address: &str, block: u64) -> Result<u128> {
+        let hex: String = self
+            .call("eth_getBalance", json!([address, format!("0x{:x}", block)]))
+            .await?;
+        parse_hex(&hex)
+    }
+}
+
+#[async_trait]
+impl WalletChainClient for EthereumRpcClient {
+    async fn fetch(
+        &self,
+        sources: &[String],
+        since: Option<&WalletCheckpoint>,
+    ) -> Result<WalletFetch> {
+        let chain = WalletChain::Ethereum;
+        let latest = parse_hex(&self.call::<String>("eth_blockNumber", json!([])).await?)? as u64;
+        let own: HashSet<String> = sources.iter().map(|a| a.to_lowercase()).collect();
+
+        let mut balance_units: u128 = 0;
+        for address in sources {
+            balance_units += self.balance(address, latest).await?;
+        }
+        let balance = to_coins(balance_units, chain)?;
+
+        let first = match (since, self.start_block) {
+            (Some(checkpoint), _) => first_block_after(Some(checkpoint), ETHEREUM_REORG_MARGIN),
+            (None, Some(start)) => start,
+            (None, None) => {
+                // No index to read history from: start with the current balance
+                let block = self.block(latest).await?;
+                let timestamp = timestamp_from_secs(parse_hex(&block.timestamp)? as i64);
+                let transfers = if balance.is_zero() {
+                    Vec::new()
+                } else {
+                    vec![WalletTransfer {
+                        txid: format!("opening-balance-{}", latest),
+                        timestamp,
+                        block_height: latest,
+                        net_amount: balance,
+                        fee: Decimal::ZERO,
+                    }]
+                };
+                return Ok(WalletFetch {
+                    transfers,
+                    checkpoint: WalletCheckpoint {
+                        block_height: latest,
+                    },
+                    balance,
+                });
+            }
+        };
+        let last = latest.min(first.saturating_add(ETHEREUM_MAX_BLOCKS_PER_SYNC - 1));
+        debug!("Scanning Ethereum blocks {}..={}", first, last);
+
+        let mut transfers = Vec::new();
+        for number in first..=last {
+            let block = self.block(number).await?;
+            let timestamp = timestamp_from_secs(parse_hex(&block.timestamp)? as i64);
+            for tx in block.transactions {
+                let is_sender = own.contains(&tx.from.to_lowercase());
+                let is_recipient = tx
+                    .to
+                    .as_deref()
+                    .is_some_and(|to| own.contains(&to.to_lowercase()));
+                if !is_sender && !is_recipient {
+                    continue;
+                }
+
+                let receipt: EthReceipt = self
+                    .call("eth_getTransactionReceipt", json!([tx.hash]))
+                    .await?;
+                // Reverted transactions move no value but still pay gas
+                let succeeded = receipt.status.as_deref() != Some("0x0");
+                let value = if succeeded { parse_hex(&tx.value)? } else { 0 };
+                let gas_price = receipt
+                    .effective_gas_price
+                    .as_deref()
+                    .or(tx.gas_price.as_deref())
+                    .map(parse_hex)
+                    .transpose()?
+                    .unwrap_or(0);
+                let fee = parse_hex(&receipt.gas_used)?.saturating_mul(gas_price);
+
+                let own_inputs = if is_sender { value + fee } else { 0 };
+                let own_outputs = if is_recipient { value } else { 0 };
+                if let Some(transfer) = transfer_from_units(
+                    tx.hash,
+                    timestamp,
+                    number,
+                    own_inputs,
+                    own_outputs,
+                    fee,
+                    chain,
+                )? {
+                    transfers.push(transfer);
+                }
+            }
+        }
+
+        Ok(WalletFetch {
+            transfers,
+            checkpoint: WalletCheckpoint { block_height: last },
+            balance,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// Serves canned JSON responses, picked by the first route whose key is
+    /// contained in the request line or body.
+    fn mock_server(routes: Vec<(&'static str, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(v) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = v.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let request = format!("{}{}", request_line, String::from_utf8_lossy(&body));
+                let response = routes
+                    .iter()
+                    .find(|(key, _)| request.contains(key))
+                    .map(|(_, r)| r.clone())
+                    .unwrap_or_else(|| "null".to_string());
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                );
+            }
+        });
+        url
+    }
+
+    fn config(kind: WalletEndpointKind, url: String, addresses: &[&str]) -> WalletConfig {
+        WalletConfig {
+            chain: if kind == WalletEndpointKind::EthereumRpc {
+                WalletChain::Ethereum
+            } else {
+                WalletChain::Bitcoin
+            },
+            endpoint_kind: kind,
+            endpoint_url: url,
+            addresses: addresses.iter().map(|a| a.to_string()).collect(),
+            start_block: None,
+        }
+    }
+
+    #[test]
+    fn test_transfer_from_units_attributes_fee_only_when_funding() {
+        let ts = Utc::now();
+        let incoming = transfer_from_units(
+            "a".into(),
+            ts,
+            1,
+            0,
+            150_000_000,
+            1_000,
+            WalletChain::Bitcoin,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(incoming.net_amount, dec!(1.5));
+        assert_eq!(incoming.fee, Decimal::ZERO);
+
+        // Spent 1 BTC input, 0.4 BTC change back, 0.0001 BTC fee
+        let outgoing = transfer_from_units(
+            "b".into(),
+            ts,
+            2,
+            100_000_000,
+            40_000_000,
+            10_000,
+            WalletChain::Bitcoin,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(outgoing.net_amount, dec!(-0.6));
+        assert_eq!(outgoing.fee, dec!(0.0001));
+
+        let wei = to_coins(1_500_000_000_000_000_000, WalletChain::Ethereum).unwrap();
+        assert_eq!(wei, dec!(1.5));
+    }
+
+    #[tokio::test]
+    async fn test_esplora_client_nets_inputs_and_outputs_per_transaction() {
+        let ours = "bcrt1qours";
+        let txs = json!([
+            {
+                "txid": "tx2",
+                "vin": [{ "prevout": { "scriptpubkey_address": ours, "value": 50_000_000 } }],
+                "vout": [
+                    { "scriptpubkey_address": "bcrt1qother", "value": 29_990_000 },
+                    { "scriptpubkey_address": ours, "value": 20_000_000 }
+                ],
+                "fee": 10_000,
+                "status": { "confirmed": true, "block_height": 105, "block_time": 1_700_000_600 }
+            },
+            {
+                "txid": "tx1",
+                "vin": [{ "prevout": { "scriptpubkey_address": "bcrt1qother", "value": 60_000_000 } }],
+                "vout": [{ "scriptpubkey_address": ours, "value": 50_000_000 }],
+                "fee": 2_000,
+                "status": { "confirmed": true, "block_height": 101, "block_time": 1_700_000_000 }
+            }
+        ]);
+        let url = mock_server(vec![
+            ("/blocks/tip/height", "110".to_string()),
+            ("/txs/chain/tx1", "[]".to_string()),
+            ("/txs", txs.to_string()),
+            (
+                "/address/bcrt1qours",
+                json!({ "chain_stats": { "funded_txo_sum": 70_000_000, "spent_txo_sum": 50_000_000 } })
+                    .to_string(),
+            ),
+        ]);
+
+        let client = build_client(&config(WalletEndpointKind::Esplora, url, &[ours])).unwrap();
+        let fetch = client.fetch(&[ours.to_string()], None).await.unwrap();
+
+        assert_eq!(fetch.checkpoint.block_height, 110);
+        assert_eq!(fetch.balance, dec!(0.2));
+        assert_eq!(fetch.transfers.len(), 2);
+        let tx1 = fetch.transfers.iter().find(|t| t.txid == "tx1").unwrap();
+        assert_eq!(tx1.net_amount, dec!(0.5));
+        assert_eq!(tx1.fee, Decimal::ZERO);
+        let tx2 = fetch.transfers.iter().find(|t| t.txid == "tx2").unwrap();
+        assert_eq!(tx2.net_amount, dec!(-0.3));
+        assert_eq!(tx2.fee, dec!(0.0001));
+        assert_eq!(tx2.block_height, 105);
+    }
+
+    #[tokio::test]
+    async fn test_ethereum_client_scans_blocks_for_watched_address() {
+        let ours = "0xAbC0000000000000000000000000000000000001";
+        let block = |number: &str, txs: Value| {
+            json!({ "jsonrpc": "2.0", "id": 1, "result": { "number": number, "timestamp": "0x6553f100", "transactions": txs } })
+                .to_string()
+        };
+        let url = mock_server(vec![
+            (
+                "eth_blockNumber",
+                json!({ "jsonrpc": "2.0", "id": 1, "result": "0x2" }).to_string(),
+            ),
+            (
+                "eth_getBalance",
+                json!({ "jsonrpc": "2.0", "id": 1, "result": "0xde0b6b3a7640000" }).to_string(),
+            ),
+            (
+                "\"0x1\"",
+                block(
+                    "0x1",
+                    json!([{
+                        "hash": "0xin", "from": "0xfeed", "to": ours.to_lowercase(),
+                        "value": "0x1bc16d674ec80000", "gasPrice": "0x1"
+                    }]),
+                ),
+            ),
+            (
+                "\"0x2\"",
+                block(
+                    "0x2",
+                    json!([{
+                        "hash": "0xout", "from": ours, "to": "0xfeed",
+                        "value": "0xde0b6b3a7640000", "gasPrice": "0x1"
+                    }]),
+                ),
+            ),
+            (
+                "\"0xout\"",
+                json!({ "jsonrpc": "2.0", "id": 1, "result": {
+                    "gasUsed": "0x5208", "effectiveGasPrice": "0x3b9aca00", "status": "0x1"
+                } })
+                .to_string(),
+            ),
+            (
+                "\"0xin\"",
+                json!({ "jsonrpc": "2.0", "id": 1, "result": {
+                    "gasUsed": "0x5208", "effectiveGasPrice": "0x3b9aca00", "status": "0x1"
+                } })
+                .to_string(),
+            ),
+        ]);
+        let mut cfg = config(WalletEndpointKind::EthereumRpc, url, &[ours]);
+        cfg.start_block = Some(1);
+
+        let client = build_client(&cfg).unwrap();
+        let fetch = client.fetch(&[ours.to_string()], None).await.unwrap();
+
+        assert_eq!(fetch.checkpoint.block_height, 2);
+        assert_eq!(fetch.balance, dec!(1));
+        assert_eq!(fetch.transfers.len(), 2);
+        assert_eq!(fetch.transfers[0].txid, "0xin");
+        assert_eq!(fetch.transfers[0].net_amount, dec!(2));
+        // 21000 gas at 1 gwei
+        assert_eq!(fetch.transfers[1].fee, dec!(0.000021));
+        assert_eq!(fetch.transfers[1].net_amount, dec!(-1.000021));
+    }
+
+    #[tokio::test]
+    async fn test_ethereum_client_without_start_block_records_opening_balance() {
+        let ours = "0xabc0000000000000000000000000000000000001";
+        let url = mock_server(vec![
+            ("eth_blockNumber", json!({ "jsonrpc": "2.0", "id": 1, "result": "0x64" }).to_string()),
+            ("eth_getBalance", json!({ "jsonrpc": "2.0", "id": 1, "result": "0x29a2241af62c0000" }).to_string()),
+            (
+                "eth_getBlockByNumber",
+                json!({ "jsonrpc": "2.0", "id": 1, "result": { "timestamp": "0x6553f100", "transactions": [] } })
+                    .to_string(),
+            ),
+        ]);
+
+        let client = build_client(&config(WalletEndpointKind::EthereumRpc, url, &[ours])).unwrap();
+        let fetch = client.fetch(&[ours.to_string()], None).await.unwrap();
+
+        assert_eq!(fetch.checkpoint.block_height, 100);
+        assert_eq!(fetch.transfers.len(), 1);
+        assert_eq!(fetch.transfers[0].txid, "opening-balance-100");
+        assert_eq!(fetch.transfers[0].net_amount, dec!(3));
+    }
+}
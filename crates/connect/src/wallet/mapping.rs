@@ -0,0 +1,147 @@
+//! Mapping of on-chain wallet transfers to activities.
+
+use serde_json::json;
+use wealthfolio_core::activities::{
+    ActivityStatus, NewActivity, SymbolInput, ACTIVITY_TYPE_TRANSFER_IN, ACTIVITY_TYPE_TRANSFER_OUT,
+};
+
+use super::models::{WalletChain, WalletTransfer, WALLET_PROVIDER};
+
+/// Stable activity ID of a transfer, so re-syncing a transaction updates it in place.
+pub fn wallet_activity_id(account_id: &str, chain: WalletChain, txid: &str) -> String {
+    format!(
+        "wallet-{}-{}-{}",
+        chain.symbol().to_lowercase(),
+        account_id,
+        txid
+    )
+}
+
+/// Maps a transfer to a `TRANSFER_IN` / `TRANSFER_OUT` of the chain's native coin.
+///
+/// The quantity is the wallet's net change, so spent network fees are part of a
+/// `TRANSFER_OUT` and the tracked quantity matches the on-chain balance. The fee
+/// is also kept in the metadata. No unit price is set: the cost basis of coins
+/// moved between a user's own wallets is not known on-chain.
+pub fn map_wallet_transfer(
+    transfer: &WalletTransfer,
+    account_id: &str,
+    chain: WalletChain,
+    currency: &str,
+) -> Option<NewActivity> {
+    if transfer.net_amount.is_zero() {
+        return None;
+    }
+    let activity_type = if transfer.net_amount.is_sign_positive() {
+        ACTIVITY_TYPE_TRANSFER_IN
+    } else {
+        ACTIVITY_TYPE_TRANSFER_OUT
+    };
+
+    let metadata = json!({
+        "wallet": {
+            "chain": chain.as_str(),
+            "txid": transfer.txid,
+            "block_height": transfer.block_height,
+            "network_fee": transfer.fee.to_string(),
+        }
+    });
+
+    Some(NewActivity {
+        id: Some(wallet_activity_id(account_id, chain, &transfer.txid)),
+        account_id: account_id.to_string(),
+        symbol: Some(SymbolInput {
+            symbol: Some(chain.symbol().to_string()),
+            kind: Some("CRYPTO".to_string()),
+            quote_ccy: Some(currency.to_string()),
+            instrument_type: Some("CRYPTO".to_string()),
+            ..Default::default()
+        }),
+        activity_type: activity_type.to_string(),
+        subtype: None,
+        activity_date: transfer.timestamp.to_rfc3339(),
+        quantity: Some(transfer.net_amount.abs()),
+        unit_price: None,
+        currency: currency.to_string(),
+        fee: None,
+        amount: None,
+        status: Some(ActivityStatus::Posted),
+        notes: None,
+        fx_rate: None,
+        metadata: Some(metadata.to_string()),
+        needs_review: Some(false),
+        source_system: Some(WALLET_PROVIDER.to_string()),
+        source_record_id: Some(transfer.txid.clone()),
+        source_group_id: None,
+        idempotency_key: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn transfer(net_amount: Decimal, fee: Decimal) -> WalletTransfer {
+        WalletTransfer {
+            txid: "abc123".to_string(),
+            timestamp: Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+            block_height: 830_000,
+            net_amount,
+            fee,
+        }
+    }
+
+    #[test]
+    fn test_incoming_transfer_maps_to_transfer_in() {
+        let activity = map_wallet_transfer(
+            &transfer(dec!(0.5), Decimal::ZERO),
+            "acc-1",
+            WalletChain::Bitcoin,
+            "USD",
+        )
+        .unwrap();
+
+        assert_eq!(activity.activity_type, ACTIVITY_TYPE_TRANSFER_IN);
+        assert_eq!(activity.quantity, Some(dec!(0.5)));
+        assert_eq!(activity.id.as_deref(), Some("wallet-btc-acc-1-abc123"));
+        assert_eq!(activity.activity_date, "2024-03-01T12:00:00+00:00");
+        let symbol = activity.symbol.unwrap();
+        assert_eq!(symbol.symbol.as_deref(), Some("BTC"));
+        assert_eq!(symbol.kind.as_deref(), Some("CRYPTO"));
+        assert_eq!(activity.source_system.as_deref(), Some(WALLET_PROVIDER));
+        assert_eq!(activity.source_record_id.as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn test_outgoing_transfer_includes_network_fee_in_quantity() {
+        let activity = map_wallet_transfer(
+            &transfer(dec!(-1.000021), dec!(0.000021)),
+            "acc-1",
+            WalletChain::Ethereum,
+            "EUR",
+        )
+        .unwrap();
+
+        assert_eq!(activity.activity_type, ACTIVITY_TYPE_TRANSFER_OUT);
+        assert_eq!(activity.quantity, Some(dec!(1.000021)));
+        assert_eq!(activity.currency, "EUR");
+        let metadata: serde_json::Value =
+            serde_json::from_str(activity.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["wallet"]["network_fee"], "0.000021");
+        assert_eq!(metadata["wallet"]["chain"], "ETHEREUM");
+    }
+
+    #[test]
+    fn test_zero_net_transfer_is_skipped() {
+        assert!(map_wallet_transfer(
+            &transfer(Decimal::ZERO, Decimal::ZERO),
+            "acc-1",
+            WalletChain::Bitcoin,
+            "USD"
+        )
+        .is_none());
+    }
+}
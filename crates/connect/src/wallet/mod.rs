@@ -0,0 +1,19 @@
+//! Watch-only crypto wallet sync.
+//!
+//! Pulls the history of watched addresses (or xpubs through Blockbook) from an
+//! Esplora, Blockbook or Ethereum JSON-RPC endpoint and records it as
+//! `TRANSFER_IN` / `TRANSFER_OUT` activities of `CRYPTOCURRENCY` accounts, using
+//! the same import-run and sync-state bookkeeping as broker sync.
+
+mod clients;
+mod mapping;
+mod models;
+mod service;
+
+pub use clients::{build_client, WalletChainClient};
+pub use mapping::{map_wallet_transfer, wallet_activity_id};
+pub use models::{
+    is_extended_public_key, WalletChain, WalletCheckpoint, WalletConfig, WalletEndpointKind,
+    WalletFetch, WalletSyncResult, WalletTransfer, WALLET_META_KEY, WALLET_PROVIDER,
+};
+pub use service::{WalletSyncService, WalletSyncServiceTrait};
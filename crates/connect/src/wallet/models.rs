@@ -0,0 +1,184 @@
+//! Models for watch-only crypto wallet sync.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use wealthfolio_core::errors::{Error, Result, ValidationError};
+
+/// Provider name used for sync state, import runs and `source_system` of wallet activities.
+pub const WALLET_PROVIDER: &str = "WALLET";
+
+/// Key of the wallet configuration in the account `meta` JSON.
+pub const WALLET_META_KEY: &str = "wallet";
+
+/// Blockchain of a watched wallet. Only the native coin is tracked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WalletChain {
+    Bitcoin,
+    Ethereum,
+}
+
+impl WalletChain {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WalletChain::Bitcoin => "BITCOIN",
+            WalletChain::Ethereum => "ETHEREUM",
+        }
+    }
+
+    /// Symbol of the native coin.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            WalletChain::Bitcoin => "BTC",
+            WalletChain::Ethereum => "ETH",
+        }
+    }
+
+    /// Decimals of the smallest unit (satoshi, wei).
+    pub fn decimals(&self) -> u32 {
+        match self {
+            WalletChain::Bitcoin => 8,
+            WalletChain::Ethereum => 18,
+        }
+    }
+}
+
+/// API spoken by the configured endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WalletEndpointKind {
+    /// Esplora REST API (Blockstream/mempool.space, or a local `electrs`).
+    Esplora,
+    /// Blockbook v2 REST API. Required for xpubs, which it derives server-side.
+    Blockbook,
+    /// Ethereum JSON-RPC (local node, regtest/devnet or hosted RPC).
+    EthereumRpc,
+}
+
+/// Watch-only wallet configuration, stored under `wallet` in the account `meta`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletConfig {
+    pub chain: WalletChain,
+    pub endpoint_kind: WalletEndpointKind,
+    /// Base URL of the endpoint, e.g. `http://127.0.0.1:3002` or `https://mempool.space/api`.
+    pub endpoint_url: String,
+    /// Watched addresses, or extended public keys for Blockbook endpoints.
+    pub addresses: Vec<String>,
+    /// Ethereum only: first block to scan. Without it the first sync records the
+    /// current balance as an opening transfer instead of scanning the whole chain.
+    #[serde(default)]
+    pub start_block: Option<u64>,
+}
+
+/// Whether `source` looks like a BIP32 extended public key (xpub/ypub/zpub and testnet variants).
+pub fn is_extended_public_key(source: &str) -> bool {
+    ["xpub", "ypub", "zpub", "tpub", "upub", "vpub"]
+        .iter()
+        .any(|prefix| source.starts_with(prefix))
+}
+
+impl WalletConfig {
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: String| -> Result<()> {
+            Err(Error::Validation(ValidationError::InvalidInput(msg)))
+        };
+
+        if self.endpoint_url.trim().is_empty() {
+            return invalid("Wallet endpoint URL is required".to_string());
+        }
+        if self.addresses.iter().all(|a| a.trim().is_empty()) {
+            return invalid("At least one wallet address is required".to_string());
+        }
+
+        let endpoint_matches_chain = match self.chain {
+            WalletChain::Bitcoin => self.endpoint_kind != WalletEndpointKind::EthereumRpc,
+            WalletChain::Ethereum => self.endpoint_kind == WalletEndpointKind::EthereumRpc,
+        };
+        if !endpoint_matches_chain {
+            return invalid(format!(
+                "{:?} endpoints cannot be used for {} wallets",
+                self.endpoint_kind,
+                self.chain.as_str()
+            ));
+        }
+
+        if self.endpoint_kind != WalletEndpointKind::Blockbook {
+            if let Some(xpub) = self.addresses.iter().find(|a| is_extended_public_key(a)) {
+                return invalid(format!(
+                    "Extended public key {}... requires a Blockbook endpoint",
+                    xpub.chars().take(8).collect::<String>()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Watched addresses with blanks removed.
+    pub fn sources(&self) -> Vec<String> {
+        self.addresses
+            .iter()
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect()
+    }
+
+    /// Reads the wallet configuration from an account `meta` JSON string.
+    pub fn from_account_meta(meta: Option<&str>) -> Option<Self> {
+        let meta: serde_json::Value = serde_json::from_str(meta?).ok()?;
+        serde_json::from_value(meta.get(WALLET_META_KEY)?.clone()).ok()
+    }
+
+    /// Writes the configuration into an account `meta` JSON string, keeping other keys.
+    pub fn merge_into_account_meta(&self, meta: Option<&str>) -> Result<String> {
+        let mut value = meta
+            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+            .filter(|v| v.is_object())
+            .unwrap_or_else(|| serde_json::json!({}));
+        value[WALLET_META_KEY] = serde_json::to_value(self)
+            .map_err(|e| Error::Unexpected(format!("Failed to serialize wallet config: {}", e)))?;
+        Ok(value.to_string())
+    }
+}
+
+/// Net effect of one confirmed transaction on the watched wallet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletTransfer {
+    pub txid: String,
+    pub timestamp: DateTime<Utc>,
+    pub block_height: u64,
+    /// Coins received (positive) or spent (negative), network fee included.
+    pub net_amount: Decimal,
+    /// Network fee paid by the wallet (zero for incoming transfers).
+    pub fee: Decimal,
+}
+
+/// Sync cursor persisted on the import run (`checkpoint_out`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletCheckpoint {
+    /// Highest block fully processed.
+    pub block_height: u64,
+}
+
+/// Result of fetching a wallet's history since a checkpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletFetch {
+    pub transfers: Vec<WalletTransfer>,
+    pub checkpoint: WalletCheckpoint,
+    /// Current confirmed balance, used to warn about gaps in the history.
+    pub balance: Decimal,
+}
+
+/// Outcome of syncing one wallet account.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletSyncResult {
+    pub account_id: String,
+    pub import_run_id: Option<String>,
+    pub transfers_fetched: usize,
+    pub activities_upserted: usize,
+    pub block_height: u64,
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,347 @@
+//! Service syncing watch-only crypto wallets into their accounts.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+use super::clients::{build_client, WalletChainClient};
+use super::mapping::map_wallet_transfer;
+use super::models::{WalletCheckpoint, WalletConfig, WalletSyncResult, WALLET_PROVIDER};
+use crate::broker_ingest::{
+    BrokerSyncStateRepositoryTrait, ImportRun, ImportRunMode, ImportRunRepositoryTrait,
+    ImportRunStatus, ImportRunSummary, ImportRunType, ReviewMode,
+};
+use wealthfolio_core::accounts::{account_types, Account, AccountServiceTrait, AccountUpdate};
+use wealthfolio_core::activities::{
+    compute_idempotency_key, ActivityServiceTrait, ActivityUpsert, NewActivity,
+    ACTIVITY_TYPE_TRANSFER_OUT,
+};
+use wealthfolio_core::errors::{Error, Result, ValidationError};
+
+/// Number of recent import runs searched for the last checkpoint.
+const CHECKPOINT_LOOKBACK_RUNS: i64 = 20;
+
+#[async_trait]
+pub trait WalletSyncServiceTrait: Send + Sync {
+    /// Wallet configuration of an account, if any.
+    fn get_wallet_config(&self, account_id: &str) -> Result<Option<WalletConfig>>;
+
+    /// Validates and stores the wallet configuration in the account metadata.
+    async fn set_wallet_config(&self, account_id: &str, config: WalletConfig) -> Result<Account>;
+
+    /// Pulls new transfers of an account's wallet and upserts them as activities.
+    async fn sync_wallet(&self, account_id: &str) -> Result<WalletSyncResult>;
+
+    /// Syncs every active crypto account with a wallet configuration.
+    /// Failures are recorded on the account's sync state and skipped.
+    async fn sync_all_wallets(&self) -> Result<Vec<WalletSyncResult>>;
+}
+
+pub struct WalletSyncService {
+    account_service: Arc<dyn AccountServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    import_run_repository: Arc<dyn ImportRunRepositoryTrait>,
+    sync_state_repository: Arc<dyn BrokerSyncStateRepositoryTrait>,
+}
+
+impl WalletSyncService {
+    pub fn new(
+        account_service: Arc<dyn AccountServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        import_run_repository: Arc<dyn ImportRunRepositoryTrait>,
+        sync_state_repository: Arc<dyn BrokerSyncStateRepositoryTrait>,
+    ) -> Self {
+        Self {
+            account_service,
+            activity_service,
+            import_run_repository,
+            sync_state_repository,
+        }
+    }
+
+    /// Checkpoint of the last applied wallet import run of the account.
+    fn last_checkpoint(&self, account_id: &str) -> Result<Option<WalletCheckpoint>> {
+        let runs = self
+            .import_run_repository
+            .get_recent_for_account(account_id, CHECKPOINT_LOOKBACK_RUNS)?;
+        Ok(runs
+            .into_iter()
+            .filter(|run| {
+                run.source_system == WALLET_PROVIDER && run.status == ImportRunStatus::Applied
+            })
+            .max_by_key(|run| run.started_at)
+            .and_then(|run| run.checkpoint_out)
+            .and_then(|value| serde_json::from_value(value).ok()))
+    }
+
+    /// Net quantity of the chain's coin recorded by previous wallet syncs.
+    fn tracked_balance(&self, account_id: &str) -> Result<Decimal> {
+        Ok(self
+            .activity_service
+            .get_activities_by_account_id(account_id)?
+            .iter()
+            .filter(|a| a.source_system.as_deref() == Some(WALLET_PROVIDER))
+            .map(|a| {
+                if a.activity_type == ACTIVITY_TYPE_TRANSFER_OUT {
+                    -a.qty()
+                } else {
+                    a.qty()
+                }
+            })
+            .sum())
+    }
+
+    /// Fetches, maps and upserts the wallet history; updates `run` with the outcome.
+    async fn ingest(
+        &self,
+        account: &Account,
+        config: &WalletConfig,
+        client: &dyn WalletChainClient,
+        checkpoint: Option<&WalletCheckpoint>,
+        run: &mut ImportRun,
+    ) -> Result<WalletSyncResult> {
+        let fetch = client.fetch(&config.sources(), checkpoint).await?;
+        let currency = if account.currency.is_empty() {
+            self.account_service
+                .get_base_currency()
+                .unwrap_or_else(|| "USD".to_string())
+        } else {
+            account.currency.clone()
+        };
+
+        let new_activities: Vec<NewActivity> = fetch
+            .transfers
+            .iter()
+            .filter_map(|t| map_wallet_transfer(t, &account.id, config.chain, &currency))
+            .collect();
+
+        let mut upserted = 0;
+        let mut assets_created = 0;
+        if !new_activities.is_empty() {
+            let prepared = self
+                .activity_service
+                .prepare_activities(new_activities, account)
+                .await?;
+            assets_created = prepared.assets_created;
+
+            let upserts: Vec<ActivityUpsert> = prepared
+                .prepared
+                .into_iter()
+                .map(|p| {
+                    let act = p.activity;
+                    let activity_datetime: DateTime<Utc> =
+                        DateTime::parse_from_rfc3339(&act.activity_date)
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .unwrap_or_else(|_| Utc::now());
+                    let idempotency_key = compute_idempotency_key(
+                        &act.account_id,
+                        &act.activity_type,
+                        &activity_datetime,
+                        p.resolved_asset_id.as_deref(),
+                        act.quantity,
+                        act.unit_price,
+                        act.amount,
+                        &act.currency,
+                        act.source_record_id.as_deref(),
+                        act.notes.as_deref(),
+                    );
+                    ActivityUpsert {
+                        id: act.id.unwrap_or_default(),
+                        account_id: act.account_id,
+                        asset_id: p.resolved_asset_id,
+                        activity_type: act.activity_type,
+                        subtype: act.subtype,
+                        activity_date: act.activity_date,
+                        quantity: act.quantity,
+                        unit_price: act.unit_price,
+                        currency: act.currency,
+                        fee: act.fee,
+                        amount: act.amount,
+                        status: act.status,
+                        notes: act.notes,
+                        fx_rate: act.fx_rate,
+                        metadata: act.metadata,
+                        needs_review: act.needs_review,
+                        source_system: act.source_system,
+                        source_record_id: act.source_record_id,
+                        source_group_id: act.source_group_id,
+                        idempotency_key: Some(idempotency_key),
+                        import_run_id: Some(run.id.clone()),
+                    }
+                })
+                .collect();
+            upserted = self
+                .activity_service
+                .upsert_activities_bulk(upserts)
+                .await?
+                .upserted;
+        }
+
+        // The Ethereum client pages through blocks, so the balance only matches
+        // once the scan has caught up with the chain tip
+        let mut warnings = Vec::new();
+        let tracked = self.tracked_balance(&account.id)?;
+        if tracked != fetch.balance {
+            warnings.push(format!(
+                "Synced transfers add up to {} {} but the on-chain balance is {} {}",
+                tracked.normalize(),
+                config.chain.symbol(),
+                fetch.balance.normalize(),
+                config.chain.symbol()
+            ));
+        }
+
+        let summary = ImportRunSummary {
+            fetched: fetch.transfers.len() as u32,
+            inserted: upserted as u32,
+            skipped: fetch.transfers.len().saturating_sub(upserted) as u32,
+            warnings: warnings.len() as u32,
+            assets_created,
+            ..Default::default()
+        };
+        run.summary = Some(summary);
+        run.checkpoint_out = serde_json::to_value(&fetch.checkpoint).ok();
+        if !warnings.is_empty() {
+            run.warnings = Some(warnings.clone());
+        }
+
+        Ok(WalletSyncResult {
+            account_id: account.id.clone(),
+            import_run_id: Some(run.id.clone()),
+            transfers_fetched: fetch.transfers.len(),
+            activities_upserted: upserted,
+            block_height: fetch.checkpoint.block_height,
+            warnings,
+        })
+    }
+}
+
+#[async_trait]
+impl WalletSyncServiceTrait for WalletSyncService {
+    fn get_wallet_config(&self, account_id: &str) -> Result<Option<WalletConfig>> {
+        let account = self.account_service.get_account(account_id)?;
+        Ok(WalletConfig::from_account_meta(account.meta.as_deref()))
+    }
+
+    async fn set_wallet_config(&self, account_id: &str, config: WalletConfig) -> Result<Account> {
+        config.validate()?;
+        let account = self.account_service.get_account(account_id)?;
+        if account.account_type != account_types::CRYPTOCURRENCY {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Wallets can only be linked to {} accounts",
+                account_types::CRYPTOCURRENCY
+            ))));
+        }
+
+        let meta = config.merge_into_account_meta(account.meta.as_deref())?;
+        self.account_service
+            .update_account(AccountUpdate {
+                id: Some(account.id),
+                name: account.name,
+                account_type: account.account_type,
+                group: account.group,
+                is_default: account.is_default,
+                is_active: account.is_active,
+                platform_id: account.platform_id,
+                account_number: account.account_number,
+                meta: Some(meta),
+                provider: account.provider,
+                provider_account_id: account.provider_account_id,
+                is_archived: Some(account.is_archived),
+                tracking_mode: Some(account.tracking_mode),
+            })
+            .await
+    }
+
+    async fn sync_wallet(&self, account_id: &str) -> Result<WalletSyncResult> {
+        let account = self.account_service.get_account(account_id)?;
+        let config = WalletConfig::from_account_meta(account.meta.as_deref()).ok_or_else(|| {
+            Error::Validation(ValidationError::InvalidInput(format!(
+                "Account {} has no wallet configured",
+                account_id
+            )))
+        })?;
+        let client = build_client(&config)?;
+        let checkpoint = self.last_checkpoint(account_id)?;
+
+        self.sync_state_repository
+            .upsert_attempt(account_id.to_string(), WALLET_PROVIDER.to_string())
+            .await?;
+        let mut run = ImportRun::new(
+            account_id.to_string(),
+            WALLET_PROVIDER.to_string(),
+            ImportRunType::Sync,
+            if checkpoint.is_some() {
+                ImportRunMode::Incremental
+            } else {
+                ImportRunMode::Initial
+            },
+            ReviewMode::Never,
+        );
+        run.checkpoint_in = checkpoint
+            .as_ref()
+            .and_then(|c| serde_json::to_value(c).ok());
+        let mut run = self.import_run_repository.create(run).await?;
+
+        match self
+            .ingest(
+                &account,
+                &config,
+                client.as_ref(),
+                checkpoint.as_ref(),
+                &mut run,
+            )
+            .await
+        {
+            Ok(result) => {
+                run.complete();
+                self.import_run_repository.update(run).await?;
+                self.sync_state_repository
+                    .upsert_success(
+                        account_id.to_string(),
+                        WALLET_PROVIDER.to_string(),
+                        Utc::now().date_naive().to_string(),
+                        result.import_run_id.clone(),
+                    )
+                    .await?;
+                info!(
+                    "Wallet sync for account {}: {} transfers, {} activities upserted",
+                    account_id, result.transfers_fetched, result.activities_upserted
+                );
+                Ok(result)
+            }
+            Err(e) => {
+                let run_id = run.id.clone();
+                run.fail(e.to_string());
+                self.import_run_repository.update(run).await?;
+                self.sync_state_repository
+                    .upsert_failure(
+                        account_id.to_string(),
+                        WALLET_PROVIDER.to_string(),
+                        e.to_string(),
+                        Some(run_id),
+                    )
+                    .await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn sync_all_wallets(&self) -> Result<Vec<WalletSyncResult>> {
+        let mut results = Vec::new();
+        for account in self.account_service.get_active_non_archived_accounts()? {
+            if account.account_type != account_types::CRYPTOCURRENCY
+                || WalletConfig::from_account_meta(account.meta.as_deref()).is_none()
+            {
+                continue;
+            }
+            match self.sync_wallet(&account.id).await {
+                Ok(result) => results.push(result),
+                Err(e) => warn!("Wallet sync failed for account {}: {}", account.id, e),
+            }
+        }
+        Ok(results)
+    }
+}
@@ -223,6 +223,49 @@ pub const ACTIVITY_SUBTYPE_REBATE: &str = "REBATE";
 /// Examples: erroneous fee refund, service credit.
 pub const ACTIVITY_SUBTYPE_REFUND: &str = "REFUND";
 
+/// Airdrop: Tokens received for free, recognized as income at FMV.
+/// Expands to: INTEREST + BUY
+pub const ACTIVITY_SUBTYPE_AIRDROP: &str = "AIRDROP";
+
+/// Hard Fork: Coins of a new chain received from a chain split.
+/// Stored as ADJUSTMENT on the parent coin with metadata.received_asset_id and
+/// metadata.cost_basis_ratio (share of the parent's cost basis moved to the new coin, default 0).
+/// Expands to: ADJUSTMENT posting that the calculator applies as a cost basis split
+pub const ACTIVITY_SUBTYPE_HARD_FORK: &str = "HARD_FORK";
+
+/// Swap: Crypto-to-crypto trade recorded as one event.
+/// Stored as SELL of the coin given up with metadata.received_asset_id and
+/// metadata.received_quantity.
+/// Expands to: SELL + BUY (received coin valued at the FMV of the coin given up)
+pub const ACTIVITY_SUBTYPE_SWAP: &str = "SWAP";
+
+/// Network Fee: Fee paid in kind (e.g., gas), reducing the quantity held.
+/// Stored as FEE with asset_id, quantity spent and unit_price at FMV.
+/// Expands to: SELL (disposal at FMV) + FEE (charge of the same value)
+pub const ACTIVITY_SUBTYPE_NETWORK_FEE: &str = "NETWORK_FEE";
+
+/// FX Conversion: Cash exchanged between two currencies inside the account.
+/// Stored as TRANSFER_OUT of the sold currency (currency, amount, fee) with
+/// metadata.to_currency and metadata.to_amount (or metadata.rate, bought per sold).
+/// Expands to: TRANSFER_OUT posting that the calculator books as two cash legs
+/// without touching net contribution
+pub const ACTIVITY_SUBTYPE_FX_CONVERSION: &str = "FX_CONVERSION";
+
+// Label-only subtypes: the compiler passes these through unchanged.
+
+/// Withholding: TAX withheld at source on a dividend or interest payment.
+/// Broker imports link it to the income activity through source_group_id.
+pub const ACTIVITY_SUBTYPE_WITHHOLDING: &str = "WITHHOLDING";
+
+/// Tax Refund: CREDIT returning previously withheld tax (internal flow).
+pub const ACTIVITY_SUBTYPE_TAX_REFUND: &str = "TAX_REFUND";
+
+/// Fee Refund: CREDIT reversing a previously charged fee (internal flow).
+pub const ACTIVITY_SUBTYPE_FEE_REFUND: &str = "FEE_REFUND";
+
+/// Interest Charge: FEE for margin or debit interest paid to the broker.
+pub const ACTIVITY_SUBTYPE_INTEREST_CHARGE: &str = "INTEREST_CHARGE";
+
 #[cfg(test)]
 mod tests {
     use super::*;
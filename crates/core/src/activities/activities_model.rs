@@ -1,6 +1,10 @@
 //! Activity domain models.
 
+use crate::activities::activities_constants::{
+    ACTIVITY_SUBTYPE_FX_CONVERSION, ACTIVITY_TYPE_TRANSFER_OUT,
+};
 use crate::activities::activities_errors::ActivityError;
+use crate::activities::compiler::fx_conversion_target;
 use crate::activities::csv_parser::ParseConfig;
 use crate::Result;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
@@ -161,6 +165,41 @@ impl Activity {
             .and_then(|v| v.get(key))
             .and_then(|v| serde_json::from_value(v.clone()).ok())
     }
+
+    /// Get a decimal metadata value stored either as a JSON number or a string
+    pub fn get_meta_decimal(&self, key: &str) -> Option<Decimal> {
+        decimal_from_json(self.metadata.as_ref()?.get(key)?)
+    }
+}
+
+/// Parse a decimal stored either as a JSON number or a string
+pub(crate) fn decimal_from_json(value: &Value) -> Option<Decimal> {
+    let raw = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.trim().to_string(),
+        _ => return None,
+    };
+    Decimal::from_str(&raw)
+        .or_else(|_| Decimal::from_scientific(&raw))
+        .ok()
+}
+
+/// Whether a stored `FX_CONVERSION` does not say what was bought. Only the
+/// amount sold can be booked, so the activity needs review.
+fn is_incomplete_fx_conversion(
+    activity_type: &str,
+    subtype: Option<&str>,
+    currency: &str,
+    amount: Option<Decimal>,
+    metadata: Option<&str>,
+) -> bool {
+    if activity_type != ACTIVITY_TYPE_TRANSFER_OUT
+        || subtype != Some(ACTIVITY_SUBTYPE_FX_CONVERSION)
+    {
+        return false;
+    }
+    let metadata: Option<Value> = metadata.and_then(|m| serde_json::from_str(m).ok());
+    fx_conversion_target(currency, amount.unwrap_or(Decimal::ZERO), metadata.as_ref()).is_none()
 }
 
 /// Input for asset identification when creating/updating activities.
@@ -239,6 +278,19 @@ pub struct NewActivity {
 }
 
 impl NewActivity {
+    /// Flags an `FX_CONVERSION` that does not say what was bought for review.
+    pub fn flag_incomplete_fx_conversion(&mut self) {
+        if is_incomplete_fx_conversion(
+            &self.activity_type,
+            self.subtype.as_deref(),
+            &self.currency,
+            self.amount,
+            self.metadata.as_deref(),
+        ) {
+            self.needs_review = Some(true);
+        }
+    }
+
     /// Validates the new activity data
     pub fn validate(&self) -> std::result::Result<(), ActivityError> {
         if self.account_id.trim().is_empty() {
@@ -546,7 +598,7 @@ pub struct ActivitySearchResponse {
 }
 
 /// Model for importing activities
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ActivityImport {
     pub id: Option<String>,
@@ -603,6 +655,22 @@ pub struct ActivityImport {
     )]
     pub fx_rate: Option<Decimal>,
     pub subtype: Option<String>,
+    /// JSON metadata blob (e.g., FX conversion target, closed lots)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
+    /// Originating system for native broker imports (defaults to CSV)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_system: Option<String>,
+    /// Broker's record ID, used to tell apart otherwise identical rows
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_record_id: Option<String>,
+    /// Grouping key linking related rows (e.g., dividend and its withholding tax)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_group_id: Option<String>,
 }
 
 /// Model for sorting activities
@@ -1055,6 +1123,21 @@ pub struct ActivityUpsert {
     pub import_run_id: Option<String>,
 }
 
+impl ActivityUpsert {
+    /// Flags an `FX_CONVERSION` that does not say what was bought for review.
+    pub fn flag_incomplete_fx_conversion(&mut self) {
+        if is_incomplete_fx_conversion(
+            &self.activity_type,
+            self.subtype.as_deref(),
+            &self.currency,
+            self.amount,
+            self.metadata.as_deref(),
+        ) {
+            self.needs_review = Some(true);
+        }
+    }
+}
+
 /// Result of a bulk upsert operation
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -1067,6 +1150,9 @@ pub struct BulkUpsertResult {
     pub updated: usize,
     /// Number of activities skipped (e.g., user-modified)
     pub skipped: usize,
+    /// Number of new activities flagged as probable duplicates from another source
+    #[serde(default)]
+    pub probable_duplicates: usize,
 }
 
 /// Activity ready for persistence
@@ -1124,11 +1210,11 @@ impl From<ActivityImport> for NewActivity {
             status,
             notes: import.comment,
             fx_rate: import.fx_rate,
-            metadata: None,
+            metadata: import.metadata,
             needs_review: None,
-            source_system: Some("CSV".to_string()),
-            source_record_id: None,
-            source_group_id: None,
+            source_system: import.source_system.or_else(|| Some("CSV".to_string())),
+            source_record_id: import.source_record_id,
+            source_group_id: import.source_group_id,
             idempotency_key: None,
         }
     }
@@ -683,6 +683,10 @@ mod tests {
             line_number: Some(1),
             fx_rate: None,
             subtype: None,
+            metadata: None,
+            source_system: None,
+            source_record_id: None,
+            source_group_id: None,
         };
 
         let converted = NewActivity::from(import);
@@ -691,4 +695,29 @@ mod tests {
         assert_eq!(symbol.instrument_type.as_deref(), Some("EQUITY"));
         assert_eq!(symbol.exchange_mic.as_deref(), Some("XLON"));
     }
+
+    #[test]
+    fn test_activity_import_to_new_activity_keeps_source_fields() {
+        let import = ActivityImport {
+            date: "2024-02-15".to_string(),
+            activity_type: "TAX".to_string(),
+            currency: "USD".to_string(),
+            amount: Some(dec!(0.36)),
+            account_id: Some("acc-1".to_string()),
+            subtype: Some("WITHHOLDING".to_string()),
+            source_system: Some("IBKR".to_string()),
+            source_record_id: Some("2002".to_string()),
+            source_group_id: Some("ibkr:U1:555".to_string()),
+            is_valid: true,
+            ..Default::default()
+        };
+
+        let converted = NewActivity::from(import);
+        assert_eq!(converted.source_system.as_deref(), Some("IBKR"));
+        assert_eq!(converted.source_record_id.as_deref(), Some("2002"));
+        assert_eq!(converted.source_group_id.as_deref(), Some("ibkr:U1:555"));
+
+        let csv = NewActivity::from(ActivityImport::default());
+        assert_eq!(csv.source_system.as_deref(), Some("CSV"));
+    }
 }
@@ -1,7 +1,8 @@
 use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use log::debug;
 use rust_decimal::Decimal;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::accounts::{Account, AccountServiceTrait};
@@ -11,7 +12,18 @@ use crate::activities::activities_constants::{
 };
 use crate::activities::activities_errors::ActivityError;
 use crate::activities::activities_model::*;
+use crate::activities::broker_parsers::{
+    self, BrokerParserInfo, BrokerParserRegistry, BrokerStatement,
+};
+use crate::activities::bulk_edit::{
+    edited_fields, holding_changes, ActivityBulkEditChange, ActivityBulkEditHoldingChange,
+    ActivityBulkEditRequest, ActivityBulkEditResult,
+};
 use crate::activities::csv_parser::{self, ParseConfig, ParsedCsvResult};
+use crate::activities::fuzzy_duplicates::{
+    drop_merged_duplicates, find_probable_duplicates, flag_probable_duplicates, FuzzyMatchConfig,
+    ProbableDuplicate,
+};
 use crate::activities::idempotency::compute_idempotency_key;
 use crate::activities::{ActivityRepositoryTrait, ActivityServiceTrait};
 use crate::activities::{
@@ -22,9 +34,11 @@ use crate::assets::{
     resolve_quote_ccy_precedence, AssetKind, AssetServiceTrait, InstrumentType,
     QuoteCcyResolutionSource, QuoteMode,
 };
+use crate::audit::{with_audit_context, AuditContext};
 use crate::events::{DomainEvent, DomainEventSink, NoOpDomainEventSink};
 use crate::fx::currency::{get_normalization_rule, normalize_amount, resolve_currency};
 use crate::fx::FxServiceTrait;
+use crate::portfolio::snapshot::HoldingsCalculator;
 use crate::quotes::{DataSource, Quote, QuoteServiceTrait};
 use crate::Result;
 use log::warn;
@@ -40,6 +54,8 @@ pub struct ActivityService {
     quote_service: Arc<dyn QuoteServiceTrait>,
     import_run_repository: Option<Arc<dyn ImportRunRepositoryTrait>>,
     event_sink: Arc<dyn DomainEventSink>,
+    broker_parsers: Arc<BrokerParserRegistry>,
+    holdings_calculator: Option<HoldingsCalculator>,
 }
 
 impl ActivityService {
@@ -149,6 +165,8 @@ impl ActivityService {
             quote_service,
             import_run_repository: None,
             event_sink: Arc::new(NoOpDomainEventSink),
+            broker_parsers: Arc::new(BrokerParserRegistry::default()),
+            holdings_calculator: None,
         }
     }
 
@@ -169,6 +187,8 @@ impl ActivityService {
             quote_service,
             import_run_repository: Some(import_run_repository),
             event_sink: Arc::new(NoOpDomainEventSink),
+            broker_parsers: Arc::new(BrokerParserRegistry::default()),
+            holdings_calculator: None,
         }
     }
 
@@ -180,6 +200,98 @@ impl ActivityService {
         self
     }
 
+    /// Replaces the broker statement parsers (defaults to the built-in profiles).
+    pub fn with_broker_parsers(mut self, broker_parsers: Arc<BrokerParserRegistry>) -> Self {
+        self.broker_parsers = broker_parsers;
+        self
+    }
+
+    /// Sets the calculator used to preview the holdings change of bulk edits.
+    /// Without it, bulk edit results carry no holding changes.
+    pub fn with_holdings_calculator(mut self, holdings_calculator: HoldingsCalculator) -> Self {
+        self.holdings_calculator = Some(holdings_calculator);
+        self
+    }
+
+    /// Holdings change of a bulk edit, from replaying the history of every
+    /// account it touches with and without the edited activities.
+    fn preview_bulk_edit_holdings(
+        &self,
+        changes: &[ActivityBulkEditChange],
+    ) -> Result<Vec<ActivityBulkEditHoldingChange>> {
+        let Some(calculator) = &self.holdings_calculator else {
+            return Ok(Vec::new());
+        };
+        let account_ids: Vec<String> = changes
+            .iter()
+            .flat_map(|c| [c.before.account_id.clone(), c.after.account_id.clone()])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let history = self
+            .activity_repository
+            .get_activities_by_account_ids(&account_ids)?;
+        let edited: HashMap<&str, &Activity> = changes
+            .iter()
+            .map(|c| (c.activity_id.as_str(), &c.after))
+            .collect();
+        let edited_history: Vec<Activity> = history
+            .iter()
+            .map(|a| edited.get(a.id.as_str()).map_or(a, |e| *e).clone())
+            .collect();
+
+        let mut before = Vec::with_capacity(account_ids.len());
+        let mut after = Vec::with_capacity(account_ids.len());
+        for account_id in &account_ids {
+            let account = self.account_service.get_account(account_id)?;
+            let replay = |activities: &[Activity]| -> Result<_> {
+                let in_account: Vec<Activity> = activities
+                    .iter()
+                    .filter(|a| &a.account_id == account_id)
+                    .cloned()
+                    .collect();
+                Ok(calculator
+                    .replay(account_id, &account.currency, &in_account)?
+                    .snapshot)
+            };
+            before.push(replay(&history)?);
+            after.push(replay(&edited_history)?);
+        }
+        Ok(holding_changes(&before, &after))
+    }
+
+    /// Runs an edited activity through the checks of a regular update, so a
+    /// bulk edit cannot store what the activity form would reject.
+    async fn validate_bulk_edited(&self, activity: &Activity) -> Result<()> {
+        let update = ActivityUpdate {
+            id: activity.id.clone(),
+            account_id: activity.account_id.clone(),
+            symbol: activity.asset_id.clone().map(|id| SymbolInput {
+                id: Some(id),
+                ..Default::default()
+            }),
+            activity_type: activity.effective_type().to_string(),
+            subtype: activity.subtype.clone(),
+            activity_date: activity.activity_date.to_rfc3339(),
+            quantity: Some(activity.quantity),
+            unit_price: Some(activity.unit_price),
+            currency: activity.currency.clone(),
+            fee: Some(activity.fee),
+            amount: Some(activity.amount),
+            status: Some(activity.status.clone()),
+            notes: activity.notes.clone(),
+            fx_rate: Some(activity.fx_rate),
+            metadata: activity.metadata.as_ref().map(|m| m.to_string()),
+        };
+        update.validate()?;
+        self.prepare_update_activity(update)
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                ActivityError::InvalidData(format!("Activity {}: {}", activity.id, e)).into()
+            })
+    }
+
     fn get_base_currency_or_usd(&self) -> String {
         resolve_currency(&[self
             .account_service
@@ -244,7 +356,7 @@ impl ActivityService {
             activity.unit_price,
             activity.amount,
             currency,
-            None,
+            activity.source_record_id.as_deref(),
             activity.comment.as_deref(),
         ))
     }
@@ -865,6 +977,7 @@ impl ActivityService {
             );
             activity.idempotency_key = Some(key);
         }
+        activity.flag_incomplete_fx_conversion();
 
         Ok(activity)
     }
@@ -2061,10 +2174,21 @@ impl ActivityServiceTrait for ActivityService {
         let account = self.account_service.get_account(&account_id)?;
         let total_count = activities.len() as u32;
 
+        // Native broker imports tag their rows; everything else is a CSV import
+        let source_system = activities
+            .first()
+            .and_then(|a| a.source_system.clone())
+            .filter(|source| {
+                activities
+                    .iter()
+                    .all(|a| a.source_system.as_deref() == Some(source.as_str()))
+            })
+            .unwrap_or_else(|| "CSV".to_string());
+
         // Create import run at the start
         let import_run = ImportRun::new(
             account_id.clone(),
-            "CSV".to_string(),
+            source_system,
             ImportRunType::Import,
             ImportRunMode::Initial,
             ReviewMode::Never,
@@ -2072,10 +2196,12 @@ impl ActivityServiceTrait for ActivityService {
         let import_run_id = import_run.id.clone();
 
         // Try to persist the import run if repository is available
+        let mut run_recorded = false;
         if let Some(ref repo) = self.import_run_repository {
-            if let Err(e) = repo.create(import_run.clone()).await {
-                warn!("Failed to create import run record: {}", e);
+            match repo.create(import_run.clone()).await {
+                Ok(_) => run_recorded = true,
                 // Continue with import even if tracking fails
+                Err(e) => warn!("Failed to create import run record: {}", e),
             }
         }
 
@@ -2229,7 +2355,7 @@ impl ActivityServiceTrait for ActivityService {
                         activity.unit_price,
                         activity.amount,
                         &activity.currency,
-                        None,
+                        activity.source_record_id.as_deref(),
                         activity.notes.as_deref(),
                     );
                     activity.idempotency_key = Some(key.clone());
@@ -2350,10 +2476,19 @@ impl ActivityServiceTrait for ActivityService {
             .into_iter()
             .collect();
 
-        let count = self
-            .activity_repository
-            .create_activities(activities_to_insert)
-            .await?;
+        // Link the activities to the run so it can be reviewed and rolled back
+        let count = with_audit_context(AuditContext::import(import_run_id.clone()), async {
+            if run_recorded {
+                self.activity_repository
+                    .create_import_run_activities(&import_run_id, activities_to_insert)
+                    .await
+            } else {
+                self.activity_repository
+                    .create_activities(activities_to_insert)
+                    .await
+            }
+        })
+        .await?;
         debug!("Successfully imported {} activities", count);
 
         // Emit domain event after successful import
@@ -2452,12 +2587,32 @@ impl ActivityServiceTrait for ActivityService {
         csv_parser::parse_csv(content, config)
     }
 
+    fn parse_ibkr_flex(&self, account_id: &str, content: &[u8]) -> Result<Vec<ActivityImport>> {
+        let mut rows = broker_parsers::parse_ibkr_flex(content)?;
+        for row in &mut rows {
+            row.account_id = Some(account_id.to_string());
+        }
+        Ok(rows)
+    }
+
+    fn parse_broker_statement(&self, account_id: &str, content: &[u8]) -> Result<BrokerStatement> {
+        let mut statement = self.broker_parsers.parse(content)?;
+        for row in &mut statement.activities {
+            row.account_id = Some(account_id.to_string());
+        }
+        Ok(statement)
+    }
+
+    fn list_broker_parsers(&self) -> Vec<BrokerParserInfo> {
+        self.broker_parsers.parsers()
+    }
+
     /// Upserts multiple activities (insert or update on conflict).
     /// Used by broker sync to efficiently sync activities.
     /// Emits a single aggregated ActivitiesChanged event for all upserted activities.
     async fn upsert_activities_bulk(
         &self,
-        activities: Vec<ActivityUpsert>,
+        mut activities: Vec<ActivityUpsert>,
     ) -> Result<BulkUpsertResult> {
         if activities.is_empty() {
             return Ok(BulkUpsertResult::default());
@@ -2484,8 +2639,40 @@ impl ActivityServiceTrait for ActivityService {
             .into_iter()
             .collect();
 
-        // Perform the upsert via repository
-        let result = self.activity_repository.bulk_upsert(activities).await?;
+        // The same trade may already exist from another source with slightly
+        // different rounding or date; flag it for review instead of counting it twice.
+        let existing = self
+            .activity_repository
+            .get_activities_by_account_ids(&account_ids)?;
+        // Duplicates the user merged away stay merged
+        let merged = drop_merged_duplicates(&mut activities, &existing);
+        if activities.is_empty() {
+            return Ok(BulkUpsertResult {
+                skipped: merged,
+                ..Default::default()
+            });
+        }
+        for activity in &mut activities {
+            activity.flag_incomplete_fx_conversion();
+        }
+        let probable_duplicates =
+            flag_probable_duplicates(&mut activities, &existing, &FuzzyMatchConfig::default());
+        if probable_duplicates > 0 {
+            debug!(
+                "Flagged {} upserted activities as probable duplicates",
+                probable_duplicates
+            );
+        }
+
+        // Perform the upsert via repository, attributed to the sync's import run
+        let import_run_id = activities.iter().find_map(|a| a.import_run_id.clone());
+        let mut result = with_audit_context(
+            AuditContext::broker_sync(import_run_id),
+            self.activity_repository.bulk_upsert(activities),
+        )
+        .await?;
+        result.probable_duplicates = probable_duplicates;
+        result.skipped += merged;
 
         // Emit single aggregated event if any activities were affected
         if result.upserted > 0 {
@@ -2499,6 +2686,187 @@ impl ActivityServiceTrait for ActivityService {
         Ok(result)
     }
 
+    fn find_probable_duplicates(
+        &self,
+        account_id: Option<String>,
+    ) -> Result<Vec<ProbableDuplicate>> {
+        let activities = match account_id {
+            Some(account_id) => self
+                .activity_repository
+                .get_activities_by_account_id(&account_id)?,
+            None => self.activity_repository.get_activities()?,
+        };
+        Ok(find_probable_duplicates(
+            &activities,
+            &FuzzyMatchConfig::default(),
+        ))
+    }
+
+    async fn merge_duplicate_activities(
+        &self,
+        keep_id: &str,
+        duplicate_id: &str,
+    ) -> Result<Activity> {
+        if keep_id == duplicate_id {
+            return Err(ActivityError::InvalidData(
+                "Cannot merge an activity into itself".to_string(),
+            )
+            .into());
+        }
+        let kept = self.activity_repository.get_activity(keep_id)?;
+        let duplicate = self.activity_repository.get_activity(duplicate_id)?;
+        if kept.account_id != duplicate.account_id {
+            return Err(ActivityError::InvalidData(
+                "Only activities of the same account can be merged".to_string(),
+            )
+            .into());
+        }
+
+        let merged = self
+            .activity_repository
+            .merge_activities(keep_id, duplicate_id)
+            .await?;
+
+        let mut asset_ids: Vec<String> = [&kept.asset_id, &duplicate.asset_id]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect();
+        asset_ids.dedup();
+        let mut currencies = vec![kept.currency.clone(), duplicate.currency.clone()];
+        currencies.dedup();
+        self.event_sink.emit(DomainEvent::activities_changed(
+            vec![merged.account_id.clone()],
+            asset_ids,
+            currencies,
+        ));
+
+        Ok(merged)
+    }
+
+    async fn bulk_edit_activities(
+        &self,
+        request: ActivityBulkEditRequest,
+    ) -> Result<ActivityBulkEditResult> {
+        let ActivityBulkEditRequest {
+            query,
+            mut edit,
+            dry_run,
+        } = request;
+        if query.is_empty() {
+            return Err(ActivityError::InvalidData(
+                "A bulk edit needs at least one filter".to_string(),
+            )
+            .into());
+        }
+        if edit.is_empty() {
+            return Err(ActivityError::InvalidData("Nothing to change".to_string()).into());
+        }
+
+        // Normalize and validate the edit once rather than per row
+        if let Some(account_id) = &edit.account_id {
+            self.account_service.get_account(account_id)?;
+        }
+        if let Some(activity_type) = edit.activity_type.take() {
+            let parsed = ActivityType::from_str(activity_type.trim().to_uppercase().as_str())
+                .map_err(|_| {
+                    ActivityError::InvalidData(format!("Unknown activity type '{}'", activity_type))
+                })?;
+            edit.activity_type = Some(parsed.as_str().to_string());
+        }
+        if let Some(Some(subtype)) = &edit.subtype {
+            let subtype = subtype.trim().to_uppercase();
+            edit.subtype = Some((!subtype.is_empty()).then_some(subtype));
+        }
+        if let Some(currency) = edit.activity_currency.take() {
+            let currency = currency.trim().to_string();
+            if currency.is_empty() {
+                return Err(
+                    ActivityError::InvalidData("Currency cannot be empty".to_string()).into(),
+                );
+            }
+            edit.activity_currency = Some(currency);
+        }
+
+        let candidates = match query.account_ids.as_ref().filter(|ids| !ids.is_empty()) {
+            Some(account_ids) => self
+                .activity_repository
+                .get_activities_by_account_ids(account_ids)?,
+            None => self.activity_repository.get_activities()?,
+        };
+        let matched: Vec<Activity> = candidates
+            .into_iter()
+            .filter(|activity| query.matches(activity))
+            .collect();
+
+        let mut changes: Vec<ActivityBulkEditChange> = matched
+            .iter()
+            .filter_map(|before| {
+                let after = edit.apply(before);
+                let changed_fields = edited_fields(before, &after);
+                (!changed_fields.is_empty()).then(|| ActivityBulkEditChange {
+                    activity_id: before.id.clone(),
+                    changed_fields,
+                    before: before.clone(),
+                    after,
+                })
+            })
+            .collect();
+        let holding_changes = self.preview_bulk_edit_holdings(&changes)?;
+
+        if dry_run || changes.is_empty() {
+            return Ok(ActivityBulkEditResult {
+                matched: matched.len(),
+                changes,
+                holding_changes,
+                applied: !dry_run,
+            });
+        }
+
+        for change in &changes {
+            self.validate_bulk_edited(&change.after).await?;
+        }
+        let afters: Vec<Activity> = changes.iter().map(|c| c.after.clone()).collect();
+        let persisted = self
+            .activity_repository
+            .bulk_edit_activities(afters)
+            .await?;
+        debug!(
+            "Bulk edit changed {} of {} matched activities",
+            persisted.len(),
+            matched.len()
+        );
+        let mut persisted: HashMap<String, Activity> =
+            persisted.into_iter().map(|a| (a.id.clone(), a)).collect();
+        for change in &mut changes {
+            if let Some(activity) = persisted.remove(&change.activity_id) {
+                change.after = activity;
+            }
+        }
+
+        // Recalculate both the old and the new location of every edited activity
+        let mut account_ids: HashSet<String> = HashSet::new();
+        let mut asset_ids: HashSet<String> = HashSet::new();
+        let mut currencies: HashSet<String> = HashSet::new();
+        for activity in changes.iter().flat_map(|c| [&c.before, &c.after]) {
+            account_ids.insert(activity.account_id.clone());
+            asset_ids.extend(activity.asset_id.clone());
+            currencies.insert(activity.currency.clone());
+        }
+        self.event_sink.emit(DomainEvent::activities_changed(
+            account_ids.into_iter().collect(),
+            asset_ids.into_iter().collect(),
+            currencies.into_iter().collect(),
+        ));
+
+        Ok(ActivityBulkEditResult {
+            matched: matched.len(),
+            changes,
+            holding_changes,
+            applied: true,
+        })
+    }
+
     async fn prepare_activities(
         &self,
         activities: Vec<NewActivity>,
@@ -2751,6 +3119,7 @@ impl ActivityServiceTrait for ActivityService {
                 }
             }
 
+            activity.flag_incomplete_fx_conversion();
             result.prepared.push(PreparedActivity {
                 activity,
                 resolved_asset_id,
@@ -2,7 +2,10 @@
 mod tests {
     use crate::accounts::{Account, AccountServiceTrait, AccountUpdate, NewAccount};
     use crate::activities::activities_model::*;
-    use crate::activities::{ActivityRepositoryTrait, ActivityService, ActivityServiceTrait};
+    use crate::activities::{
+        ActivityBulkEdit, ActivityBulkEditRequest, ActivityQuery, ActivityRepositoryTrait,
+        ActivityService, ActivityServiceTrait,
+    };
     use crate::assets::{
         Asset, AssetKind, AssetServiceTrait, InstrumentType, ProviderProfile, UpdateAssetProfile,
     };
@@ -10,8 +13,10 @@ mod tests {
     use crate::fx::{ExchangeRate, FxServiceTrait, NewExchangeRate};
     use crate::quotes::service::ProviderInfo;
     use crate::quotes::{
-        LatestQuotePair, LatestQuoteSnapshot, Quote, QuoteImport, QuoteServiceTrait,
-        QuoteSyncState, SymbolSearchResult, SymbolSyncPlan, SyncMode, SyncResult,
+        BulkImportOptions, BulkImportSummary, ConsensusConfig, LatestQuotePair,
+        LatestQuoteSnapshot, Quote, QuoteExportRequest, QuoteImport, QuoteProvenance,
+        QuoteQualityConfig, QuoteServiceTrait, QuoteSyncState, SymbolSearchResult, SymbolSyncPlan,
+        SyncMode, SyncResult,
     };
     use async_trait::async_trait;
     use chrono::{DateTime, NaiveDate, Utc};
@@ -401,6 +406,15 @@ mod tests {
             unimplemented!()
         }
 
+        fn get_quote_provenance(
+            &self,
+            _asset_id: &str,
+            _start: NaiveDate,
+            _end: NaiveDate,
+        ) -> Result<Vec<QuoteProvenance>> {
+            Ok(Vec::new())
+        }
+
         async fn add_quote(&self, _quote: &Quote) -> Result<Quote> {
             unimplemented!()
         }
@@ -528,6 +542,22 @@ mod tests {
             Ok(())
         }
 
+        fn get_consensus_config(&self) -> ConsensusConfig {
+            ConsensusConfig::default()
+        }
+
+        async fn update_consensus_config(&self, _config: ConsensusConfig) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_quality_config(&self) -> QuoteQualityConfig {
+            QuoteQualityConfig::default()
+        }
+
+        async fn update_quality_config(&self, _config: QuoteQualityConfig) -> Result<()> {
+            Ok(())
+        }
+
         async fn check_quotes_import(
             &self,
             _content: &[u8],
@@ -543,6 +573,18 @@ mod tests {
         ) -> Result<Vec<QuoteImport>> {
             Ok(quotes)
         }
+
+        async fn bulk_import_quotes(
+            &self,
+            _path: &std::path::Path,
+            _options: BulkImportOptions,
+        ) -> Result<BulkImportSummary> {
+            Ok(BulkImportSummary::default())
+        }
+
+        fn export_market_data(&self, _request: &QuoteExportRequest) -> Result<Vec<u8>> {
+            Ok(Vec::new())
+        }
     }
 
     // --- Mock ActivityRepository ---
@@ -678,6 +720,14 @@ mod tests {
             unimplemented!()
         }
 
+        async fn create_import_run_activities(
+            &self,
+            _import_run_id: &str,
+            _activities: Vec<NewActivity>,
+        ) -> Result<usize> {
+            unimplemented!()
+        }
+
         fn get_first_activity_date(
             &self,
             _account_ids: Option<&[String]>,
@@ -741,6 +791,14 @@ mod tests {
         ) -> Result<(Vec<String>, Vec<String>)> {
             Ok((Vec::new(), Vec::new()))
         }
+
+        async fn merge_activities(&self, _keep_id: &str, _duplicate_id: &str) -> Result<Activity> {
+            unimplemented!()
+        }
+
+        async fn bulk_edit_activities(&self, _edited: Vec<Activity>) -> Result<Vec<Activity>> {
+            unimplemented!()
+        }
     }
 
     // Helper to create a test account
@@ -1381,6 +1439,66 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_bulk_edit_rejects_buy_without_asset() {
+        let account_service = Arc::new(MockAccountService::new());
+        account_service.add_account(create_test_account("acc-1", "USD"));
+        let activity_repository = Arc::new(MockActivityRepository::new());
+        activity_repository
+            .create_activity(NewActivity {
+                id: Some("deposit-1".to_string()),
+                account_id: "acc-1".to_string(),
+                symbol: None,
+                activity_type: "DEPOSIT".to_string(),
+                subtype: None,
+                activity_date: "2024-01-15".to_string(),
+                quantity: None,
+                unit_price: None,
+                currency: "USD".to_string(),
+                fee: None,
+                amount: Some(dec!(1000)),
+                status: None,
+                notes: None,
+                fx_rate: None,
+                metadata: None,
+                needs_review: None,
+                source_system: None,
+                source_record_id: None,
+                source_group_id: None,
+                idempotency_key: None,
+            })
+            .await
+            .unwrap();
+
+        let activity_service = ActivityService::new(
+            activity_repository,
+            account_service,
+            Arc::new(MockAssetService::new()),
+            Arc::new(MockFxService::new()),
+            Arc::new(MockQuoteService),
+        );
+        let request = |dry_run: bool| ActivityBulkEditRequest {
+            query: ActivityQuery {
+                activity_types: Some(vec!["DEPOSIT".to_string()]),
+                ..Default::default()
+            },
+            edit: ActivityBulkEdit {
+                activity_type: Some("BUY".to_string()),
+                ..Default::default()
+            },
+            dry_run,
+        };
+
+        let preview = activity_service
+            .bulk_edit_activities(request(true))
+            .await
+            .unwrap();
+        assert_eq!(preview.changes.len(), 1);
+
+        let result = activity_service.bulk_edit_activities(request(false)).await;
+        assert!(result.is_err(), "BUY without an asset must not be stored");
+    }
+
     /// Test: Crypto symbol (BTC) without exchange infers CRYPTO kind
     #[tokio::test]
     async fn test_infer_asset_kind_common_crypto_symbol() {
@@ -1854,6 +1972,10 @@ mod tests {
             line_number: Some(1),
             fx_rate: None,
             subtype: None,
+            metadata: None,
+            source_system: None,
+            source_record_id: None,
+            source_group_id: None,
         };
 
         let result = activity_service
@@ -1914,6 +2036,10 @@ mod tests {
             line_number: Some(1),
             fx_rate: None,
             subtype: None,
+            metadata: None,
+            source_system: None,
+            source_record_id: None,
+            source_group_id: None,
         };
 
         let result = activity_service
@@ -1981,6 +2107,10 @@ mod tests {
             line_number: Some(1),
             fx_rate: None,
             subtype: None,
+            metadata: None,
+            source_system: None,
+            source_record_id: None,
+            source_group_id: None,
         };
 
         let result = activity_service
@@ -2041,6 +2171,10 @@ mod tests {
             line_number: Some(1),
             fx_rate: None,
             subtype: None,
+            metadata: None,
+            source_system: None,
+            source_record_id: None,
+            source_group_id: None,
         };
 
         let result = activity_service
@@ -1,4 +1,5 @@
 use super::activities_model::*;
+use super::import_run_model::{ImportRunChange, ImportRunRevertResult};
 use crate::limits::ContributionActivity;
 use crate::Result;
 use async_trait::async_trait;
@@ -49,6 +50,13 @@ pub trait ActivityRepositoryTrait: Send + Sync {
         delete_ids: Vec<String>,
     ) -> Result<ActivityBulkMutationResult>;
     async fn create_activities(&self, activities: Vec<NewActivity>) -> Result<usize>;
+    /// Creates activities on behalf of an import run, linking them to the run
+    /// and recording each insert so the run can be reviewed or rolled back.
+    async fn create_import_run_activities(
+        &self,
+        import_run_id: &str,
+        activities: Vec<NewActivity>,
+    ) -> Result<usize>;
     fn get_first_activity_date(
         &self,
         account_ids: Option<&[String]>,
@@ -103,6 +111,18 @@ pub trait ActivityRepositoryTrait: Send + Sync {
         &self,
         asset_id: &str,
     ) -> Result<(Vec<String>, Vec<String>)>;
+
+    /// Merges a duplicate activity into the one kept.
+    ///
+    /// Deletes the duplicate and, when the kept activity has no source record,
+    /// takes over the duplicate's source identifiers so future syncs keep matching
+    /// it. Clears any probable-duplicate flag on the kept activity.
+    async fn merge_activities(&self, keep_id: &str, duplicate_id: &str) -> Result<Activity>;
+
+    /// Writes the account, type, subtype, currency and metadata of activities
+    /// already edited by a bulk edit, in one transaction, and marks them as
+    /// user-modified so broker sync does not revert them.
+    async fn bulk_edit_activities(&self, edited: Vec<Activity>) -> Result<Vec<Activity>>;
 }
 
 /// Trait defining the contract for Activity service operations.
@@ -169,6 +189,23 @@ pub trait ActivityServiceTrait: Send + Sync {
         config: &super::csv_parser::ParseConfig,
     ) -> Result<super::csv_parser::ParsedCsvResult>;
 
+    /// Parses an Interactive Brokers Flex Query XML statement into import rows
+    /// for the given account. Rows still need `check_activities_import`.
+    /// Kept for existing clients; `parse_broker_statement` also detects Flex XML.
+    fn parse_ibkr_flex(&self, account_id: &str, content: &[u8]) -> Result<Vec<ActivityImport>>;
+
+    /// Detects the broker format of a statement (IBKR Flex XML, a known CSV
+    /// export or a PDF contract note) and parses it into import rows for the
+    /// given account. Rows still need `check_activities_import`.
+    fn parse_broker_statement(
+        &self,
+        account_id: &str,
+        content: &[u8],
+    ) -> Result<super::broker_parsers::BrokerStatement>;
+
+    /// Lists the broker statement formats `parse_broker_statement` recognises.
+    fn list_broker_parsers(&self) -> Vec<super::broker_parsers::BrokerParserInfo>;
+
     /// Upserts multiple activities (insert or update on conflict).
     /// Used by broker sync to efficiently sync activities.
     /// Emits a single aggregated ActivitiesChanged event for all upserted activities.
@@ -177,6 +214,27 @@ pub trait ActivityServiceTrait: Send + Sync {
         activities: Vec<super::ActivityUpsert>,
     ) -> Result<super::BulkUpsertResult>;
 
+    /// Scans existing activities, optionally of a single account, for probable
+    /// duplicates imported from different sources.
+    fn find_probable_duplicates(
+        &self,
+        account_id: Option<String>,
+    ) -> Result<Vec<super::ProbableDuplicate>>;
+
+    /// Merges a probable duplicate into the activity kept, deleting the duplicate.
+    async fn merge_duplicate_activities(
+        &self,
+        keep_id: &str,
+        duplicate_id: &str,
+    ) -> Result<Activity>;
+
+    /// Edits every activity selected by a query, or only previews the rows
+    /// and holdings it would change when `dry_run` is set.
+    async fn bulk_edit_activities(
+        &self,
+        request: super::ActivityBulkEditRequest,
+    ) -> Result<super::ActivityBulkEditResult>;
+
     /// Prepares activities for persistence.
     /// This is the unified entry point for all activity preparation logic.
     ///
@@ -194,3 +252,27 @@ pub trait ActivityServiceTrait: Send + Sync {
         account: &crate::accounts::Account,
     ) -> Result<PrepareActivitiesResult>;
 }
+
+/// Trait for reviewing and undoing the changes made by import runs.
+#[async_trait]
+pub trait ImportReviewServiceTrait: Send + Sync {
+    /// Activities inserted or updated by the run, with before/after for updates.
+    fn get_import_run_changes(&self, import_run_id: &str) -> Result<Vec<ImportRunChange>>;
+
+    /// Keeps the given changes. Returns the number of changes approved.
+    async fn approve_import_run_changes(
+        &self,
+        import_run_id: &str,
+        change_ids: Vec<String>,
+    ) -> Result<usize>;
+
+    /// Reverts the given changes.
+    async fn reject_import_run_changes(
+        &self,
+        import_run_id: &str,
+        change_ids: Vec<String>,
+    ) -> Result<ImportRunRevertResult>;
+
+    /// Reverts every change still in effect and cancels the run.
+    async fn rollback_import_run(&self, import_run_id: &str) -> Result<ImportRunRevertResult>;
+}
@@ -0,0 +1,287 @@
+//! Helpers shared by the broker statement parsers.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate};
+use rust_decimal::Decimal;
+use serde_json::json;
+
+use crate::activities::ActivityImport;
+
+/// Header name → column index lookup for one statement layout.
+pub(crate) struct Columns {
+    index: HashMap<String, usize>,
+}
+
+impl Columns {
+    pub(crate) fn new(headers: &[String]) -> Self {
+        let mut index = HashMap::new();
+        for (idx, header) in headers.iter().enumerate() {
+            // Keep the first occurrence; some exports repeat blank headers
+            index.entry(normalize_header(header)).or_insert(idx);
+        }
+        Self { index }
+    }
+
+    pub(crate) fn index(&self, name: &str) -> Option<usize> {
+        self.index.get(&normalize_header(name)).copied()
+    }
+
+    /// Index of the first column whose header starts with `prefix`.
+    pub(crate) fn index_starting_with(&self, prefix: &str) -> Option<usize> {
+        let prefix = normalize_header(prefix);
+        self.index
+            .iter()
+            .filter(|(name, _)| name.starts_with(&prefix))
+            .map(|(_, idx)| *idx)
+            .min()
+    }
+
+    /// Trimmed cell value, or "" when the column or cell is missing.
+    pub(crate) fn get<'a>(&self, row: &'a [String], name: &str) -> &'a str {
+        self.index(name).map(|idx| cell(row, idx)).unwrap_or("")
+    }
+
+    /// Cell right after `name`; Degiro puts currencies in unnamed columns.
+    pub(crate) fn next_to<'a>(&self, row: &'a [String], name: &str) -> &'a str {
+        self.index(name).map(|idx| cell(row, idx + 1)).unwrap_or("")
+    }
+}
+
+pub(crate) fn cell(row: &[String], idx: usize) -> &str {
+    row.get(idx).map(|v| v.trim()).unwrap_or("")
+}
+
+pub(crate) fn normalize_header(header: &str) -> String {
+    header
+        .trim()
+        .trim_start_matches('\u{feff}')
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// True when every name in `required` is present in `headers`.
+pub(crate) fn has_columns(headers: &[String], required: &[&str]) -> bool {
+    let columns = Columns::new(headers);
+    required.iter().all(|name| columns.index(name).is_some())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NumberFormat {
+    /// `1,234.56`
+    Dot,
+    /// `1.234,56`
+    Comma,
+    /// Whichever of `.` and `,` comes last is the decimal separator, for
+    /// exports that follow the user's locale without thousands separators.
+    Auto,
+}
+
+/// Parses broker-formatted amounts such as `-$1,855.00`, `(12.50)`,
+/// `USD 185.50` or `1.234,56`. Returns None for blank or unparsable cells.
+pub(crate) fn parse_number(value: &str, format: NumberFormat) -> Option<Decimal> {
+    let value = value.trim();
+    let negative = value
+        .chars()
+        .take_while(|c| !c.is_ascii_digit())
+        .any(|c| c == '-')
+        || (value.starts_with('(') && value.ends_with(')'));
+    let digits: String = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .collect();
+    if !digits.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let normalized = match format {
+        NumberFormat::Dot => digits.replace(',', ""),
+        NumberFormat::Comma => digits.replace('.', "").replace(',', "."),
+        NumberFormat::Auto => match (digits.rfind('.'), digits.rfind(',')) {
+            (Some(dot), Some(comma)) if dot > comma => digits.replace(',', ""),
+            (_, Some(_)) => digits.replace('.', "").replace(',', "."),
+            _ => digits,
+        },
+    };
+    let number = Decimal::from_str(&normalized).ok()?;
+    Some(if negative { -number } else { number })
+}
+
+/// Parses a date cell into `YYYY-MM-DD`. RFC 3339 timestamps are accepted as
+/// is; otherwise the first token (before a space or `T`) is tried against
+/// `formats` in order.
+pub(crate) fn parse_date(value: &str, formats: &[&str]) -> Option<String> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.date_naive().format("%Y-%m-%d").to_string());
+    }
+    let token = value.split([' ', 'T']).next()?.trim();
+    formats
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(token, format).ok())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+/// Skeleton row for one statement line.
+pub(crate) fn new_row(
+    source_system: &str,
+    activity_type: &str,
+    date: &str,
+    currency: &str,
+) -> ActivityImport {
+    ActivityImport {
+        activity_type: activity_type.to_string(),
+        date: date.to_string(),
+        currency: currency.trim().to_uppercase(),
+        source_system: Some(source_system.to_string()),
+        is_valid: true,
+        ..Default::default()
+    }
+}
+
+/// Converts a fee charged in another currency into the row currency using a
+/// broker-reported rate expressed as row currency per fee currency.
+pub(crate) fn convert_fee(
+    fee: Decimal,
+    fee_currency: &str,
+    row_currency: &str,
+    rate: Option<Decimal>,
+) -> Option<Decimal> {
+    if fee_currency.is_empty() || fee_currency.eq_ignore_ascii_case(row_currency) {
+        return Some(fee);
+    }
+    rate.filter(|r| !r.is_zero()).map(|r| fee * r)
+}
+
+/// For brokers that identify products by ISIN only: the ISIN becomes the
+/// symbol (provider search resolves ISINs) and the review asks for a check.
+pub(crate) fn set_isin_symbol(activity: &mut ActivityImport, isin: &str, name: &str) {
+    activity.symbol = isin.trim().to_uppercase();
+    activity.symbol_name = non_empty(name);
+    activity.metadata = Some(json!({ "isin": activity.symbol }).to_string());
+    add_warning(
+        activity,
+        "symbol",
+        "Only the ISIN is reported; confirm the ticker before importing",
+    );
+}
+
+pub(crate) fn non_empty(value: &str) -> Option<String> {
+    Some(value.trim())
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+pub(crate) fn add_error(row: &mut ActivityImport, field: &str, message: &str) {
+    row.is_valid = false;
+    row.errors
+        .get_or_insert_with(HashMap::new)
+        .entry(field.to_string())
+        .or_default()
+        .push(message.to_string());
+}
+
+pub(crate) fn add_warning(row: &mut ActivityImport, field: &str, message: &str) {
+    row.warnings
+        .get_or_insert_with(HashMap::new)
+        .entry(field.to_string())
+        .or_default()
+        .push(message.to_string());
+}
+
+/// Sorts rows by date, makes repeated source record ids unique (partial fills
+/// of one order share an id) and assigns line numbers.
+pub(crate) fn finalize(rows: &mut [ActivityImport]) {
+    rows.sort_by(|a, b| a.date.cmp(&b.date));
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (idx, row) in rows.iter_mut().enumerate() {
+        if let Some(id) = row.source_record_id.as_mut() {
+            let count = seen.entry(id.clone()).or_insert(0);
+            *count += 1;
+            if *count > 1 {
+                *id = format!("{}:{}", id, count);
+            }
+        }
+        row.line_number = Some(idx as i32 + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_number_formats() {
+        assert_eq!(
+            parse_number("-$1,855.00", NumberFormat::Dot),
+            Some(dec!(-1855.00))
+        );
+        assert_eq!(
+            parse_number("($12.50)", NumberFormat::Dot),
+            Some(dec!(-12.50))
+        );
+        assert_eq!(
+            parse_number("USD 185.50", NumberFormat::Dot),
+            Some(dec!(185.50))
+        );
+        assert_eq!(
+            parse_number("EUR -3.20", NumberFormat::Dot),
+            Some(dec!(-3.20))
+        );
+        assert_eq!(
+            parse_number("1.234,56", NumberFormat::Comma),
+            Some(dec!(1234.56))
+        );
+        assert_eq!(
+            parse_number("-185,5", NumberFormat::Auto),
+            Some(dec!(-185.5))
+        );
+        assert_eq!(
+            parse_number("1,234.5", NumberFormat::Auto),
+            Some(dec!(1234.5))
+        );
+        assert_eq!(parse_number("", NumberFormat::Dot), None);
+        assert_eq!(parse_number("--", NumberFormat::Dot), None);
+    }
+
+    #[test]
+    fn test_parse_date_formats() {
+        assert_eq!(
+            parse_date("2024-03-15T14:30:00.123Z", &[]),
+            Some("2024-03-15".to_string())
+        );
+        assert_eq!(
+            parse_date("03/15/2024 as of 03/14/2024", &["%m/%d/%Y"]),
+            Some("2024-03-15".to_string())
+        );
+        assert_eq!(
+            parse_date("15-03-2024", &["%Y-%m-%d", "%d-%m-%Y"]),
+            Some("2024-03-15".to_string())
+        );
+        assert_eq!(parse_date("Total", &["%m/%d/%Y"]), None);
+    }
+
+    #[test]
+    fn test_finalize_disambiguates_record_ids() {
+        let mut rows = vec![
+            ActivityImport {
+                date: "2024-02-01".to_string(),
+                source_record_id: Some("order-1".to_string()),
+                ..Default::default()
+            },
+            ActivityImport {
+                date: "2024-01-01".to_string(),
+                source_record_id: Some("order-1".to_string()),
+                ..Default::default()
+            },
+        ];
+        finalize(&mut rows);
+        assert_eq!(rows[0].date, "2024-01-01");
+        assert_eq!(rows[0].source_record_id.as_deref(), Some("order-1"));
+        assert_eq!(rows[1].source_record_id.as_deref(), Some("order-1:2"));
+        assert_eq!(rows[1].line_number, Some(2));
+    }
+}
@@ -0,0 +1,573 @@
+//! PDF contract notes (trade confirmations).
+//!
+//! Many European brokers only send trade confirmations as PDFs. The text layer
+//! is extracted and matched against per-broker [`ContractNoteTemplate`]s: a
+//! template recognises its broker by marker phrases and locates each field
+//! with a regular expression whose first capture group holds the value.
+//!
+//! Every note becomes one BUY or SELL draft. Commissions and transaction
+//! taxes (e.g. the Italian Tobin tax or the French FTT) are added to the fee,
+//! with the breakdown kept in the row metadata for the import review.
+
+use regex::{Regex, RegexBuilder};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::common::*;
+use crate::activities::activities_constants::*;
+use crate::activities::ActivityImport;
+use crate::errors::{Error, ValidationError};
+use crate::Result;
+
+/// A charge line on a contract note (commission, stamp duty, Tobin tax...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChargePattern {
+    /// Label used in the fee breakdown, e.g. `Tobin tax`.
+    pub label: String,
+    /// Regex whose first capture group is the amount, in the trade currency.
+    pub pattern: String,
+}
+
+/// How to read one broker's contract notes.
+///
+/// Patterns are case-insensitive and multi-line (`^`/`$` match at line
+/// breaks); the first capture group is the value, or the whole match when the
+/// pattern has no group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractNoteTemplate {
+    /// Stable identifier, e.g. `fineco-pdf`.
+    pub id: String,
+    /// Display name shown in the import wizard.
+    pub name: String,
+    /// Written to `source_system` on every row.
+    pub source_system: String,
+    /// Phrases that must all appear in the text for the template to apply.
+    pub markers: Vec<String>,
+    /// Splits documents holding several notes; each match starts a note.
+    #[serde(default)]
+    pub note_separator: Option<String>,
+    /// True for `1.234,56` numbers, false for `1,234.56`.
+    #[serde(default)]
+    pub decimal_comma: bool,
+    /// chrono formats tried in order for the trade date.
+    pub date_formats: Vec<String>,
+    pub trade_date: String,
+    /// Captures the side word, which is matched against `buy_words` and
+    /// `sell_words`.
+    pub side: String,
+    pub buy_words: Vec<String>,
+    pub sell_words: Vec<String>,
+    pub isin: String,
+    #[serde(default)]
+    pub security_name: Option<String>,
+    pub quantity: String,
+    pub price: String,
+    /// Gross consideration; derived by the import check when missing.
+    #[serde(default)]
+    pub amount: Option<String>,
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// Used when `currency` is not set or does not match.
+    pub default_currency: String,
+    /// Order or contract number, used as `source_record_id`.
+    #[serde(default)]
+    pub reference: Option<String>,
+    #[serde(default)]
+    pub fees: Vec<ChargePattern>,
+    #[serde(default)]
+    pub taxes: Vec<ChargePattern>,
+}
+
+/// Starts every PDF file.
+const PDF_MAGIC: &[u8] = b"%PDF";
+
+pub(crate) fn is_pdf(content: &[u8]) -> bool {
+    content.starts_with(PDF_MAGIC)
+}
+
+/// Extracts the text layer of a PDF.
+///
+/// The PDF parser panics on some malformed files; since notes come from
+/// untrusted sources (uploads, the drop folder), a panic is reported as an
+/// unreadable PDF.
+#[cfg(feature = "pdf-extract")]
+pub(crate) fn extract_pdf_text(content: &[u8]) -> Result<String> {
+    let unreadable = |reason: String| {
+        Error::Validation(ValidationError::InvalidInput(format!(
+            "Could not read the PDF: {}",
+            reason
+        )))
+    };
+    let text = std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(content))
+        .map_err(|_| unreadable("the file is malformed".to_string()))?
+        .map_err(|e| unreadable(e.to_string()))?;
+    if text.trim().is_empty() {
+        return Err(Error::Validation(ValidationError::InvalidInput(
+            "The PDF has no text layer. Scanned documents are not supported.".to_string(),
+        )));
+    }
+    Ok(text)
+}
+
+#[cfg(not(feature = "pdf-extract"))]
+pub(crate) fn extract_pdf_text(_content: &[u8]) -> Result<String> {
+    Err(ValidationError::InvalidInput(
+        "PDF contract notes are not supported in this build".to_string(),
+    )
+    .into())
+}
+
+/// Compiled form of a template.
+pub(crate) struct ContractNoteParser {
+    template: ContractNoteTemplate,
+    note_separator: Option<Regex>,
+    trade_date: Regex,
+    side: Regex,
+    isin: Regex,
+    security_name: Option<Regex>,
+    quantity: Regex,
+    price: Regex,
+    amount: Option<Regex>,
+    currency: Option<Regex>,
+    reference: Option<Regex>,
+    fees: Vec<(String, Regex)>,
+    taxes: Vec<(String, Regex)>,
+}
+
+impl ContractNoteParser {
+    pub(crate) fn new(template: ContractNoteTemplate) -> Result<Self> {
+        let compile = |pattern: &str| -> Result<Regex> {
+            RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .multi_line(true)
+                .build()
+                .map_err(|e| {
+                    Error::Validation(ValidationError::InvalidInput(format!(
+                        "Invalid pattern in contract note template '{}': {}",
+                        template.id, e
+                    )))
+                })
+        };
+        let compile_opt = |pattern: &Option<String>| -> Result<Option<Regex>> {
+            pattern.as_deref().map(compile).transpose()
+        };
+        let compile_charges = |charges: &[ChargePattern]| -> Result<Vec<(String, Regex)>> {
+            charges
+                .iter()
+                .map(|c| Ok((c.label.clone(), compile(&c.pattern)?)))
+                .collect()
+        };
+
+        Ok(Self {
+            note_separator: compile_opt(&template.note_separator)?,
+            trade_date: compile(&template.trade_date)?,
+            side: compile(&template.side)?,
+            isin: compile(&template.isin)?,
+            security_name: compile_opt(&template.security_name)?,
+            quantity: compile(&template.quantity)?,
+            price: compile(&template.price)?,
+            amount: compile_opt(&template.amount)?,
+            currency: compile_opt(&template.currency)?,
+            reference: compile_opt(&template.reference)?,
+            fees: compile_charges(&template.fees)?,
+            taxes: compile_charges(&template.taxes)?,
+            template,
+        })
+    }
+
+    pub(crate) fn id(&self) -> &str {
+        &self.template.id
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.template.name
+    }
+
+    pub(crate) fn detect(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        !self.template.markers.is_empty()
+            && self
+                .template
+                .markers
+                .iter()
+                .all(|marker| text.contains(&marker.to_lowercase()))
+    }
+
+    /// Maps each note in `text` to an import row.
+    pub(crate) fn parse(&self, text: &str) -> Vec<ActivityImport> {
+        self.split_notes(text)
+            .into_iter()
+            .map(|note| self.map_note(note))
+            .collect()
+    }
+
+    fn split_notes<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let Some(separator) = &self.note_separator else {
+            return vec![text];
+        };
+        let starts: Vec<usize> = separator.find_iter(text).map(|m| m.start()).collect();
+        if starts.is_empty() {
+            return vec![text];
+        }
+        starts
+            .iter()
+            .enumerate()
+            .map(|(idx, start)| &text[*start..starts.get(idx + 1).copied().unwrap_or(text.len())])
+            .collect()
+    }
+
+    fn number(&self, value: &str) -> Option<Decimal> {
+        let format = if self.template.decimal_comma {
+            NumberFormat::Comma
+        } else {
+            NumberFormat::Dot
+        };
+        parse_number(value, format)
+    }
+
+    fn map_note(&self, note: &str) -> ActivityImport {
+        let template = &self.template;
+        let side = capture(&self.side, note).unwrap_or("");
+        let is_side = |words: &[String]| words.iter().any(|w| w.eq_ignore_ascii_case(side));
+        let activity_type = if is_side(&template.buy_words) {
+            ACTIVITY_TYPE_BUY
+        } else if is_side(&template.sell_words) {
+            ACTIVITY_TYPE_SELL
+        } else {
+            ACTIVITY_TYPE_UNKNOWN
+        };
+        let currency = self
+            .currency
+            .as_ref()
+            .and_then(|re| capture(re, note))
+            .filter(|c| c.len() == 3 && c.chars().all(|ch| ch.is_ascii_alphabetic()))
+            .unwrap_or(&template.default_currency);
+
+        let mut activity = new_row(&template.source_system, activity_type, "", currency);
+        activity.is_draft = true;
+
+        let formats: Vec<&str> = template.date_formats.iter().map(String::as_str).collect();
+        match capture(&self.trade_date, note).and_then(|d| parse_date(d, &formats)) {
+            Some(date) => activity.date = date,
+            None => add_error(&mut activity, "date", "Trade date not found on the note"),
+        }
+
+        let name = self
+            .security_name
+            .as_ref()
+            .and_then(|re| capture(re, note))
+            .unwrap_or("");
+        match capture(&self.isin, note) {
+            Some(isin) => set_isin_symbol(&mut activity, isin, name),
+            None => add_error(&mut activity, "symbol", "ISIN not found on the note"),
+        }
+
+        activity.quantity = capture(&self.quantity, note)
+            .and_then(|q| self.number(q))
+            .map(|q| q.abs());
+        if activity.quantity.is_none() {
+            add_error(&mut activity, "quantity", "Quantity not found on the note");
+        }
+        activity.unit_price = capture(&self.price, note).and_then(|p| self.number(p));
+        if activity.unit_price.is_none() {
+            add_error(&mut activity, "unitPrice", "Price not found on the note");
+        }
+        activity.amount = self
+            .amount
+            .as_ref()
+            .and_then(|re| capture(re, note))
+            .and_then(|a| self.number(a))
+            .map(|a| a.abs());
+
+        let fees = self.charges(&self.fees, note);
+        let taxes = self.charges(&self.taxes, note);
+        activity.fee = Some(fees.iter().chain(&taxes).map(|(_, amount)| *amount).sum());
+        let breakdown = |charges: &[(String, Decimal)]| {
+            charges
+                .iter()
+                .map(|(label, amount)| json!({ "label": label, "amount": amount.to_string() }))
+                .collect::<Vec<_>>()
+        };
+        activity.metadata = Some(
+            json!({
+                "isin": activity.symbol,
+                "fees": breakdown(&fees),
+                "taxes": breakdown(&taxes),
+            })
+            .to_string(),
+        );
+
+        let reference = self.reference.as_ref().and_then(|re| capture(re, note));
+        activity.source_record_id = Some(match reference {
+            Some(reference) => format!("{}:{}", template.id, reference),
+            None => format!(
+                "{}:{}:{}:{}:{}",
+                template.id,
+                activity.date,
+                activity.symbol,
+                side.to_lowercase(),
+                activity.quantity.unwrap_or_default()
+            ),
+        });
+
+        if activity_type == ACTIVITY_TYPE_UNKNOWN {
+            add_error(
+                &mut activity,
+                "activityType",
+                &format!("Unrecognized side '{}' on the note", side),
+            );
+        }
+        activity
+    }
+
+    /// Sums each charge pattern over all its matches; zero charges are left out.
+    fn charges(&self, patterns: &[(String, Regex)], note: &str) -> Vec<(String, Decimal)> {
+        patterns
+            .iter()
+            .filter_map(|(label, re)| {
+                let total: Decimal = re
+                    .captures_iter(note)
+                    .filter_map(|c| c.get(1).or_else(|| c.get(0)))
+                    .filter_map(|m| self.number(m.as_str()))
+                    .map(|amount| amount.abs())
+                    .sum();
+                (!total.is_zero()).then(|| (label.clone(), total))
+            })
+            .collect()
+    }
+}
+
+/// First capture group of the first match (or the whole match), trimmed.
+fn capture<'a>(re: &Regex, text: &'a str) -> Option<&'a str> {
+    let captures = re.captures(text)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(0))
+        .map(|m| m.as_str().trim())
+        .filter(|v| !v.is_empty())
+}
+
+fn words(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+fn charge(label: &str, pattern: &str) -> ChargePattern {
+    ChargePattern {
+        label: label.to_string(),
+        pattern: pattern.to_string(),
+    }
+}
+
+/// Templates for the brokers supported out of the box.
+pub(crate) fn builtin_templates() -> Vec<ContractNoteTemplate> {
+    vec![
+        ContractNoteTemplate {
+            id: "fineco-pdf".to_string(),
+            name: "Fineco (PDF contract note)".to_string(),
+            source_system: "FINECO".to_string(),
+            markers: words(&["FinecoBank", "Codice ISIN"]),
+            note_separator: Some(r"^\s*Nota informativa".to_string()),
+            decimal_comma: true,
+            date_formats: words(&["%d/%m/%Y"]),
+            trade_date: r"Data operazione:?\s*(\d{2}/\d{2}/\d{4})".to_string(),
+            side: r"Operazione:?\s*(Acquisto|Vendita)".to_string(),
+            buy_words: words(&["Acquisto"]),
+            sell_words: words(&["Vendita"]),
+            isin: r"Codice ISIN:?\s*([A-Z]{2}[A-Z0-9]{9}\d)".to_string(),
+            security_name: Some(r"^\s*Titolo:?\s*(.+?)\s*$".to_string()),
+            quantity: r"Quantit[àa']:?\s*([\d.,]+)".to_string(),
+            price: r"Prezzo:?\s*([\d.,]+)".to_string(),
+            amount: Some(r"Controvalore:?\s*([\d.,]+)".to_string()),
+            currency: Some(r"Divisa:?\s*([A-Z]{3})".to_string()),
+            default_currency: "EUR".to_string(),
+            reference: Some(r"Numero ordine:?\s*(\S+)".to_string()),
+            fees: vec![
+                charge("Commissioni", r"Commissioni:?\s*([\d.,]+)"),
+                charge("Spese", r"Spese:?\s*([\d.,]+)"),
+            ],
+            taxes: vec![charge(
+                "Tobin tax",
+                r"(?:Tobin tax|Tassa sulle transazioni finanziarie):?\s*([\d.,]+)",
+            )],
+        },
+        ContractNoteTemplate {
+            id: "directa-pdf".to_string(),
+            name: "Directa (PDF contract note)".to_string(),
+            source_system: "DIRECTA".to_string(),
+            markers: words(&["Directa SIM", "Conferma di eseguito"]),
+            note_separator: Some(r"^\s*Conferma di eseguito".to_string()),
+            decimal_comma: true,
+            date_formats: words(&["%d-%m-%Y", "%d/%m/%Y"]),
+            trade_date: r"Data esecuzione:?\s*(\d{2}[-/]\d{2}[-/]\d{4})".to_string(),
+            side: r"Segno:?\s*(Acquisto|Vendita)".to_string(),
+            buy_words: words(&["Acquisto"]),
+            sell_words: words(&["Vendita"]),
+            isin: r"ISIN:?\s*([A-Z]{2}[A-Z0-9]{9}\d)".to_string(),
+            security_name: Some(r"^\s*Strumento:?\s*(.+?)\s*$".to_string()),
+            quantity: r"Quantit[àa']:?\s*([\d.,]+)".to_string(),
+            price: r"Prezzo eseguito:?\s*([\d.,]+)".to_string(),
+            amount: Some(r"Controvalore:?\s*([\d.,]+)".to_string()),
+            currency: Some(r"Valuta:?\s*([A-Z]{3})".to_string()),
+            default_currency: "EUR".to_string(),
+            reference: Some(r"Riferimento ordine:?\s*(\S+)".to_string()),
+            fees: vec![charge("Commissioni", r"Commissioni:?\s*([\d.,]+)")],
+            taxes: vec![
+                charge("Tobin tax", r"Tobin tax:?\s*([\d.,]+)"),
+                charge("FTT", r"Imposta transazioni finanziarie FR:?\s*([\d.,]+)"),
+            ],
+        },
+        ContractNoteTemplate {
+            id: "trade-republic-pdf".to_string(),
+            name: "Trade Republic (PDF contract note)".to_string(),
+            source_system: "TRADE_REPUBLIC".to_string(),
+            markers: words(&["Trade Republic", "Wertpapierabrechnung"]),
+            note_separator: None,
+            decimal_comma: true,
+            date_formats: words(&["%d.%m.%Y"]),
+            trade_date: r"(?:Kauf|Verkauf) am (\d{2}\.\d{2}\.\d{4})".to_string(),
+            side: r"-Order (Kauf|Verkauf)".to_string(),
+            buy_words: words(&["Kauf"]),
+            sell_words: words(&["Verkauf"]),
+            isin: r"ISIN:?\s*([A-Z]{2}[A-Z0-9]{9}\d)".to_string(),
+            security_name: Some(r"^\s*(.+?)\s+[\d.,]+ Stk\.".to_string()),
+            quantity: r"([\d.,]+) Stk\.".to_string(),
+            price: r"Stk\.\s+([\d.,]+)\s+[A-Z]{3}".to_string(),
+            amount: Some(r"Stk\.\s+[\d.,]+\s+[A-Z]{3}\s+([\d.,]+)\s+[A-Z]{3}".to_string()),
+            currency: Some(r"Stk\.\s+[\d.,]+\s+([A-Z]{3})".to_string()),
+            default_currency: "EUR".to_string(),
+            reference: Some(r"^\s*ORDER\s+(\S+)".to_string()),
+            fees: vec![charge(
+                "Fremdkostenzuschlag",
+                r"Fremdkostenzuschlag\s+-?([\d.,]+)",
+            )],
+            taxes: vec![
+                charge(
+                    "Finanztransaktionssteuer",
+                    r"Finanztransaktionssteuer\s+-?([\d.,]+)",
+                ),
+                charge("Stempelsteuer", r"Stempelsteuer\s+-?([\d.,]+)"),
+            ],
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn parser(id: &str) -> ContractNoteParser {
+        let template = builtin_templates()
+            .into_iter()
+            .find(|t| t.id == id)
+            .unwrap();
+        ContractNoteParser::new(template).unwrap()
+    }
+
+    #[test]
+    fn test_builtin_templates_compile() {
+        for template in builtin_templates() {
+            assert!(ContractNoteParser::new(template).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_fineco_notes_with_tobin_tax() {
+        let text = include_str!("fixtures/fineco_contract_notes.txt");
+        let fineco = parser("fineco-pdf");
+        assert!(fineco.detect(text));
+        assert!(!parser("trade-republic-pdf").detect(text));
+
+        let rows = fineco.parse(text);
+        assert_eq!(rows.len(), 2);
+
+        let buy = &rows[0];
+        assert_eq!(buy.activity_type, ACTIVITY_TYPE_BUY);
+        assert!(buy.is_draft);
+        assert!(buy.is_valid);
+        assert_eq!(buy.date, "2024-02-05");
+        assert_eq!(buy.symbol, "IT0003128367");
+        assert_eq!(buy.symbol_name.as_deref(), Some("ENEL SPA"));
+        assert_eq!(buy.quantity, Some(dec!(500)));
+        assert_eq!(buy.unit_price, Some(dec!(6.123)));
+        assert_eq!(buy.amount, Some(dec!(3061.50)));
+        // Commissioni 2,95 + Tobin tax 3,06
+        assert_eq!(buy.fee, Some(dec!(6.01)));
+        assert_eq!(
+            buy.source_record_id.as_deref(),
+            Some("fineco-pdf:1234567890")
+        );
+        let metadata: serde_json::Value =
+            serde_json::from_str(buy.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["isin"], "IT0003128367");
+        assert_eq!(metadata["taxes"][0]["label"], "Tobin tax");
+        assert_eq!(metadata["taxes"][0]["amount"], "3.06");
+
+        let sell = &rows[1];
+        assert_eq!(sell.activity_type, ACTIVITY_TYPE_SELL);
+        assert_eq!(sell.currency, "USD");
+        assert_eq!(sell.quantity, Some(dec!(10)));
+        assert_eq!(sell.unit_price, Some(dec!(1234.5)));
+        assert_eq!(sell.fee, Some(dec!(12.95)));
+    }
+
+    #[test]
+    fn test_trade_republic_note() {
+        let text = include_str!("fixtures/trade_republic_contract_note.txt");
+        let trade_republic = parser("trade-republic-pdf");
+        assert!(trade_republic.detect(text));
+
+        let rows = trade_republic.parse(text);
+        assert_eq!(rows.len(), 1);
+        let buy = &rows[0];
+        assert_eq!(buy.activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(buy.date, "2024-01-15");
+        assert_eq!(buy.symbol, "FR0000120321");
+        assert_eq!(buy.symbol_name.as_deref(), Some("L'Oreal S.A."));
+        assert_eq!(buy.quantity, Some(dec!(5)));
+        assert_eq!(buy.unit_price, Some(dec!(432.10)));
+        assert_eq!(buy.currency, "EUR");
+        // Fremdkostenzuschlag 1,00 + Finanztransaktionssteuer 6,48
+        assert_eq!(buy.fee, Some(dec!(7.48)));
+        assert_eq!(
+            buy.source_record_id.as_deref(),
+            Some("trade-republic-pdf:a1b2-c3d4")
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_are_reported() {
+        let rows = parser("fineco-pdf").parse(
+            "FinecoBank S.p.A.\nNota informativa\nOperazione: Acquisto\nCodice ISIN: IT0003128367\n",
+        );
+        assert_eq!(rows.len(), 1);
+        let errors = rows[0].errors.as_ref().unwrap();
+        assert!(!rows[0].is_valid);
+        assert!(errors.contains_key("date"));
+        assert!(errors.contains_key("quantity"));
+        assert!(errors.contains_key("unitPrice"));
+    }
+
+    #[test]
+    fn test_invalid_template_pattern_is_rejected() {
+        let mut template = builtin_templates().remove(0);
+        template.price = "(".to_string();
+        assert!(ContractNoteParser::new(template).is_err());
+    }
+
+    #[test]
+    fn test_malformed_pdf_is_an_error() {
+        let content =
+            b"%PDF-1.4\n1 0 obj << /Type /Catalog /Pages 2 0 R >>\ntrailer << /Root 1 0 R";
+
+        let err = extract_pdf_text(content).unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::Validation(ValidationError::InvalidInput(_))
+        ));
+    }
+}
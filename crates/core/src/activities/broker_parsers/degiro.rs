@@ -0,0 +1,318 @@
+//! Degiro exports: `Transactions.csv` (trades) and `Account.csv` (cash
+//! movements: dividends, dividend tax, deposits, fees).
+//!
+//! Degiro identifies products by ISIN only, so the ISIN is used as the symbol
+//! and the product name as the symbol name; the import review resolves it.
+//! Numbers follow the user's locale, so both `185.50` and `185,50` occur.
+
+use rust_decimal::Decimal;
+
+use super::common::*;
+use super::BrokerStatementParser;
+use crate::activities::activities_constants::*;
+use crate::activities::ActivityImport;
+
+const SOURCE_SYSTEM: &str = "DEGIRO";
+const DATE_FORMATS: &[&str] = &["%d-%m-%Y", "%d/%m/%Y", "%Y-%m-%d"];
+
+pub(crate) struct DegiroTransactionsParser;
+
+impl BrokerStatementParser for DegiroTransactionsParser {
+    fn id(&self) -> &'static str {
+        "degiro-transactions"
+    }
+
+    fn name(&self) -> &'static str {
+        "Degiro (Transactions)"
+    }
+
+    fn detect(&self, headers: &[String]) -> bool {
+        has_columns(
+            headers,
+            &[
+                "Date",
+                "Product",
+                "ISIN",
+                "Quantity",
+                "Price",
+                "Local value",
+            ],
+        )
+    }
+
+    fn parse(&self, headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport> {
+        let columns = Columns::new(headers);
+        rows.iter()
+            .filter_map(|row| map_transaction(headers, &columns, row))
+            .collect()
+    }
+}
+
+pub(crate) struct DegiroAccountParser;
+
+impl BrokerStatementParser for DegiroAccountParser {
+    fn id(&self) -> &'static str {
+        "degiro-account"
+    }
+
+    fn name(&self) -> &'static str {
+        "Degiro (Account statement)"
+    }
+
+    fn detect(&self, headers: &[String]) -> bool {
+        has_columns(
+            headers,
+            &[
+                "Date",
+                "Value date",
+                "Product",
+                "ISIN",
+                "Description",
+                "Change",
+            ],
+        )
+    }
+
+    fn parse(&self, headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport> {
+        let columns = Columns::new(headers);
+        rows.iter()
+            .filter_map(|row| map_account_movement(&columns, row))
+            .collect()
+    }
+}
+
+fn map_transaction(
+    headers: &[String],
+    columns: &Columns,
+    row: &[String],
+) -> Option<ActivityImport> {
+    let quantity = parse_number(columns.get(row, "Quantity"), NumberFormat::Auto)?;
+    let activity_type = if quantity < Decimal::ZERO {
+        ACTIVITY_TYPE_SELL
+    } else {
+        ACTIVITY_TYPE_BUY
+    };
+    let currency = columns.next_to(row, "Price");
+    let mut activity = new_row(SOURCE_SYSTEM, activity_type, "", currency);
+    set_date(&mut activity, columns.get(row, "Date"));
+    set_isin_symbol(
+        &mut activity,
+        columns.get(row, "ISIN"),
+        columns.get(row, "Product"),
+    );
+    activity.quantity = Some(quantity.abs());
+    activity.unit_price =
+        parse_number(columns.get(row, "Price"), NumberFormat::Auto).map(|p| p.abs());
+    activity.amount =
+        parse_number(columns.get(row, "Local value"), NumberFormat::Auto).map(|v| v.abs());
+    activity.exchange_mic =
+        reference_exchange_mic(columns.get(row, "Reference exchange")).map(str::to_string);
+    activity.source_record_id = non_empty(columns.get(row, "Order ID"));
+
+    // Fees are charged in the account currency; `Exchange rate` is local
+    // currency per account currency.
+    let rate = parse_number(columns.get(row, "Exchange rate"), NumberFormat::Auto);
+    let mut fee = Decimal::ZERO;
+    for idx in [
+        columns.index_starting_with("Transaction"),
+        columns.index("AutoFX Fee"),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let Some(amount) = parse_number(cell(row, idx), NumberFormat::Auto) else {
+            continue;
+        };
+        let fee_currency = header_currency(&headers[idx]).unwrap_or(cell(row, idx + 1));
+        match convert_fee(amount.abs(), fee_currency, &activity.currency, rate) {
+            Some(converted) => fee += converted,
+            None => add_warning(
+                &mut activity,
+                "fee",
+                &format!(
+                    "Fee in {} could not be converted and was left out",
+                    fee_currency
+                ),
+            ),
+        }
+    }
+    activity.fee = Some(fee.round_dp(4));
+    Some(activity)
+}
+
+fn map_account_movement(columns: &Columns, row: &[String]) -> Option<ActivityImport> {
+    let description = columns.get(row, "Description");
+    let lower = description.to_lowercase();
+    let amount = parse_number(columns.next_to(row, "Change"), NumberFormat::Auto)?;
+    if amount.is_zero() || is_internal_movement(&lower) {
+        return None;
+    }
+
+    let (activity_type, subtype) =
+        if lower.contains("dividend tax") || lower.contains("withholding") {
+            if amount < Decimal::ZERO {
+                (ACTIVITY_TYPE_TAX, Some(ACTIVITY_SUBTYPE_WITHHOLDING))
+            } else {
+                (ACTIVITY_TYPE_CREDIT, Some(ACTIVITY_SUBTYPE_TAX_REFUND))
+            }
+        } else if lower.starts_with("dividend") || lower.contains("capital return") {
+            (ACTIVITY_TYPE_DIVIDEND, None)
+        } else if lower.contains("deposit") {
+            (ACTIVITY_TYPE_DEPOSIT, None)
+        } else if lower.contains("withdrawal") {
+            (ACTIVITY_TYPE_WITHDRAWAL, None)
+        } else if lower.contains("interest") {
+            if amount < Decimal::ZERO {
+                (ACTIVITY_TYPE_FEE, Some(ACTIVITY_SUBTYPE_INTEREST_CHARGE))
+            } else {
+                (ACTIVITY_TYPE_INTEREST, None)
+            }
+        } else if lower.contains("fee") || lower.contains("costs") {
+            if amount < Decimal::ZERO {
+                (ACTIVITY_TYPE_FEE, None)
+            } else {
+                (ACTIVITY_TYPE_CREDIT, Some(ACTIVITY_SUBTYPE_FEE_REFUND))
+            }
+        } else {
+            (ACTIVITY_TYPE_UNKNOWN, None)
+        };
+
+    let currency = columns.get(row, "Change");
+    let mut activity = new_row(SOURCE_SYSTEM, activity_type, "", currency);
+    set_date(&mut activity, columns.get(row, "Date"));
+    activity.subtype = subtype.map(str::to_string);
+    activity.amount = Some(amount.abs());
+    activity.comment = non_empty(description);
+    if !columns.get(row, "ISIN").is_empty() {
+        set_isin_symbol(
+            &mut activity,
+            columns.get(row, "ISIN"),
+            columns.get(row, "Product"),
+        );
+        if matches!(activity_type, ACTIVITY_TYPE_DIVIDEND | ACTIVITY_TYPE_TAX) {
+            activity.source_group_id = Some(format!(
+                "degiro:{}:{}",
+                columns.get(row, "ISIN"),
+                activity.date
+            ));
+        }
+    }
+    if let Some(order_id) = non_empty(columns.get(row, "Order Id")) {
+        activity.source_record_id = Some(format!("{}:{}", order_id, activity_type));
+    }
+    if activity_type == ACTIVITY_TYPE_UNKNOWN {
+        activity.is_draft = true;
+        add_warning(
+            &mut activity,
+            "activityType",
+            &format!("Unrecognized Degiro movement '{}'", description),
+        );
+    }
+    Some(activity)
+}
+
+/// Trade settlements, currency legs and money market sweeps duplicate what
+/// `Transactions.csv` already covers.
+fn is_internal_movement(description: &str) -> bool {
+    description.starts_with("buy ")
+        || description.starts_with("sell ")
+        || description.starts_with("fx ")
+        || description.contains("cash sweep")
+        || description.contains("money market")
+        || description.contains("degiro transaction")
+}
+
+fn set_date(activity: &mut ActivityImport, value: &str) {
+    match parse_date(value, DATE_FORMATS) {
+        Some(date) => activity.date = date,
+        None => add_error(activity, "date", &format!("Invalid date '{}'", value)),
+    }
+}
+
+/// Newer exports put the currency in the header (`... fees EUR`).
+fn header_currency(header: &str) -> Option<&str> {
+    let last = header.split_whitespace().last()?;
+    (last.len() == 3 && last.chars().all(|c| c.is_ascii_uppercase())).then_some(last)
+}
+
+fn reference_exchange_mic(exchange: &str) -> Option<&'static str> {
+    match exchange {
+        "NDQ" => Some("XNAS"),
+        "NSY" => Some("XNYS"),
+        "EAM" => Some("XAMS"),
+        "XET" => Some("XETR"),
+        "EPA" => Some("XPAR"),
+        "EBR" => Some("XBRU"),
+        "MIL" => Some("XMIL"),
+        "LSE" => Some("XLON"),
+        "SWX" => Some("XSWX"),
+        "TSV" | "TOR" => Some("XTSE"),
+        "ASX" => Some("XASX"),
+        "HKS" => Some("XHKG"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::BrokerParserRegistry;
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn parse(content: &str) -> Vec<ActivityImport> {
+        BrokerParserRegistry::default()
+            .parse(content.as_bytes())
+            .unwrap()
+            .activities
+    }
+
+    #[test]
+    fn test_transactions_map_trades_and_convert_fees() {
+        let rows = parse(include_str!("fixtures/degiro_transactions.csv"));
+        assert_eq!(rows.len(), 3);
+
+        let buy = &rows[0];
+        assert_eq!(buy.activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(buy.date, "2024-01-15");
+        assert_eq!(buy.symbol, "US0378331005");
+        assert_eq!(buy.symbol_name.as_deref(), Some("APPLE INC. - COMMON ST"));
+        assert_eq!(buy.exchange_mic.as_deref(), Some("XNAS"));
+        assert_eq!(buy.currency, "USD");
+        assert_eq!(buy.quantity, Some(dec!(10)));
+        assert_eq!(buy.unit_price, Some(dec!(185.50)));
+        // EUR 2.00 fee at 1.0850 USD per EUR
+        assert_eq!(buy.fee, Some(dec!(2.17)));
+        assert_eq!(buy.source_system.as_deref(), Some("DEGIRO"));
+
+        let etf = &rows[1];
+        assert_eq!(etf.currency, "EUR");
+        assert_eq!(etf.unit_price, Some(dec!(110.20)));
+        assert_eq!(etf.fee, Some(dec!(1.00)));
+
+        let sell = &rows[2];
+        assert_eq!(sell.activity_type, ACTIVITY_TYPE_SELL);
+        assert_eq!(sell.quantity, Some(dec!(4)));
+    }
+
+    #[test]
+    fn test_account_statement_links_dividend_tax_and_skips_trades() {
+        let rows = parse(include_str!("fixtures/degiro_account.csv"));
+        let of_type = |t: &str| rows.iter().filter(|r| r.activity_type == t).count();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(of_type(ACTIVITY_TYPE_DEPOSIT), 1);
+        assert_eq!(of_type(ACTIVITY_TYPE_FEE), 1);
+
+        let find = |t: &str| rows.iter().find(|r| r.activity_type == t).unwrap();
+        let dividend = find(ACTIVITY_TYPE_DIVIDEND);
+        let tax = find(ACTIVITY_TYPE_TAX);
+        assert_eq!(dividend.amount, Some(dec!(2.40)));
+        assert_eq!(dividend.currency, "USD");
+        assert_eq!(tax.amount, Some(dec!(0.36)));
+        assert_eq!(tax.subtype.as_deref(), Some(ACTIVITY_SUBTYPE_WITHHOLDING));
+        assert_eq!(dividend.source_group_id, tax.source_group_id);
+        assert_eq!(
+            dividend.source_group_id.as_deref(),
+            Some("degiro:US0378331005:2024-02-16")
+        );
+    }
+}
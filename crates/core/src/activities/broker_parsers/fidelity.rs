@@ -0,0 +1,188 @@
+//! Fidelity "Accounts History" export.
+//!
+//! The transaction table sits between an account preamble and a disclaimer
+//! footer. Actions are free text (`YOU BOUGHT APPLE INC (AAPL) (Cash)`), so
+//! they are classified by prefix.
+
+use rust_decimal::Decimal;
+
+use super::common::*;
+use super::BrokerStatementParser;
+use crate::activities::activities_constants::*;
+use crate::activities::ActivityImport;
+
+const SOURCE_SYSTEM: &str = "FIDELITY";
+const DATE_FORMATS: &[&str] = &["%m/%d/%Y", "%Y-%m-%d"];
+const CURRENCY: &str = "USD";
+
+pub(crate) struct FidelityParser;
+
+impl BrokerStatementParser for FidelityParser {
+    fn id(&self) -> &'static str {
+        "fidelity"
+    }
+
+    fn name(&self) -> &'static str {
+        "Fidelity"
+    }
+
+    fn detect(&self, headers: &[String]) -> bool {
+        has_columns(headers, &["Run Date", "Action", "Symbol", "Amount ($)"])
+    }
+
+    fn parse(&self, headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport> {
+        let columns = Columns::new(headers);
+        rows.iter()
+            .filter(|row| !columns.get(row, "Action").is_empty())
+            .map(|row| map_row(&columns, row))
+            .collect()
+    }
+}
+
+fn map_row(columns: &Columns, row: &[String]) -> ActivityImport {
+    let action = columns.get(row, "Action");
+    let amount = parse_number(columns.get(row, "Amount ($)"), NumberFormat::Dot);
+    let negative = amount.is_some_and(|a| a < Decimal::ZERO);
+    let (activity_type, subtype) = classify(action, negative);
+
+    let mut activity = new_row(SOURCE_SYSTEM, activity_type, "", CURRENCY);
+    let date = columns.get(row, "Run Date");
+    match parse_date(date, DATE_FORMATS) {
+        Some(parsed) => activity.date = parsed,
+        None => add_error(&mut activity, "date", &format!("Invalid date '{}'", date)),
+    }
+    activity.subtype = subtype.map(str::to_string);
+    activity.symbol = columns.get(row, "Symbol").to_string();
+    activity.symbol_name =
+        non_empty(columns.get(row, "Description")).filter(|d| d != "No Description");
+    activity.account_name = non_empty(columns.get(row, "Account"))
+        .or_else(|| non_empty(columns.get(row, "Account Number")));
+    activity.comment = non_empty(action);
+    activity.amount = amount.map(|a| a.abs());
+
+    match activity_type {
+        ACTIVITY_TYPE_BUY | ACTIVITY_TYPE_SELL => {
+            activity.quantity =
+                parse_number(columns.get(row, "Quantity"), NumberFormat::Dot).map(|q| q.abs());
+            activity.unit_price = parse_number(columns.get(row, "Price ($)"), NumberFormat::Dot);
+            let fee: Decimal = ["Commission ($)", "Fees ($)"]
+                .iter()
+                .filter_map(|name| parse_number(columns.get(row, name), NumberFormat::Dot))
+                .map(|f| f.abs())
+                .sum();
+            activity.fee = Some(fee);
+        }
+        ACTIVITY_TYPE_DIVIDEND | ACTIVITY_TYPE_TAX if !activity.symbol.is_empty() => {
+            activity.source_group_id =
+                Some(format!("fidelity:{}:{}", activity.symbol, activity.date));
+        }
+        ACTIVITY_TYPE_UNKNOWN => {
+            activity.is_draft = true;
+            add_warning(
+                &mut activity,
+                "activityType",
+                &format!("Unrecognized Fidelity action '{}'", action),
+            );
+        }
+        _ => {}
+    }
+    activity
+}
+
+fn classify(action: &str, negative: bool) -> (&'static str, Option<&'static str>) {
+    let action = action.to_uppercase();
+    let starts = |prefixes: &[&str]| prefixes.iter().any(|p| action.starts_with(p));
+
+    if starts(&["YOU BOUGHT", "REINVESTMENT", "BUY "]) {
+        (ACTIVITY_TYPE_BUY, None)
+    } else if starts(&["YOU SOLD", "SELL "]) {
+        (ACTIVITY_TYPE_SELL, None)
+    } else if starts(&[
+        "DIVIDEND RECEIVED",
+        "LONG-TERM CAP GAIN",
+        "SHORT-TERM CAP GAIN",
+    ]) {
+        (ACTIVITY_TYPE_DIVIDEND, None)
+    } else if starts(&["FOREIGN TAX PAID", "NON-RESIDENT TAX"]) {
+        if negative {
+            (ACTIVITY_TYPE_TAX, Some(ACTIVITY_SUBTYPE_WITHHOLDING))
+        } else {
+            (ACTIVITY_TYPE_CREDIT, Some(ACTIVITY_SUBTYPE_TAX_REFUND))
+        }
+    } else if starts(&["MARGIN INTEREST"]) {
+        (ACTIVITY_TYPE_FEE, Some(ACTIVITY_SUBTYPE_INTEREST_CHARGE))
+    } else if starts(&["INTEREST EARNED"]) {
+        (ACTIVITY_TYPE_INTEREST, None)
+    } else if starts(&["FEE CHARGED", "ADVISORY FEE"]) {
+        (ACTIVITY_TYPE_FEE, None)
+    } else if starts(&[
+        "ELECTRONIC FUNDS TRANSFER",
+        "CASH CONTRIBUTION",
+        "PARTIC CONTR",
+        "TRANSFERRED",
+        "DIRECT DEPOSIT",
+        "DIRECT DEBIT",
+        "CHECK RECEIVED",
+    ]) {
+        if negative {
+            (ACTIVITY_TYPE_WITHDRAWAL, None)
+        } else {
+            (ACTIVITY_TYPE_DEPOSIT, None)
+        }
+    } else {
+        (ACTIVITY_TYPE_UNKNOWN, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::BrokerParserRegistry;
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_skips_preamble_and_footer() {
+        let statement = BrokerParserRegistry::default()
+            .parse(include_bytes!("fixtures/fidelity.csv"))
+            .unwrap();
+        assert_eq!(statement.broker_id, "fidelity");
+        let rows = statement.activities;
+        assert_eq!(rows.len(), 7);
+        assert!(rows.iter().all(|r| r.is_valid));
+
+        let buy = &rows[1];
+        assert_eq!(buy.activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(buy.symbol, "AAPL");
+        assert_eq!(buy.quantity, Some(dec!(10)));
+        assert_eq!(buy.unit_price, Some(dec!(185.5)));
+
+        let sell = &rows[6];
+        assert_eq!(sell.activity_type, ACTIVITY_TYPE_SELL);
+        assert_eq!(sell.quantity, Some(dec!(4)));
+        assert_eq!(sell.fee, Some(dec!(0.02)));
+        assert_eq!(sell.account_name.as_deref(), Some("Z12345678"));
+    }
+
+    #[test]
+    fn test_classifies_income_and_transfers() {
+        let rows = BrokerParserRegistry::default()
+            .parse(include_bytes!("fixtures/fidelity.csv"))
+            .unwrap()
+            .activities;
+        let types: Vec<&str> = rows.iter().map(|r| r.activity_type.as_str()).collect();
+        assert_eq!(
+            types,
+            vec![
+                ACTIVITY_TYPE_DEPOSIT,
+                ACTIVITY_TYPE_BUY,
+                ACTIVITY_TYPE_INTEREST,
+                ACTIVITY_TYPE_BUY,
+                ACTIVITY_TYPE_DIVIDEND,
+                ACTIVITY_TYPE_TAX,
+                ACTIVITY_TYPE_SELL,
+            ]
+        );
+        assert_eq!(rows[4].source_group_id, rows[5].source_group_id);
+        assert_eq!(rows[5].amount, Some(dec!(0.38)));
+    }
+}
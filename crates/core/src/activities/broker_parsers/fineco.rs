@@ -0,0 +1,148 @@
+//! Fineco "Movimenti titoli" export (Italian headers, `;` separated, comma
+//! decimals). `Segno` is `A` (acquisto) for buys and `V` (vendita) for sells;
+//! commissions are charged in EUR and `Cambio` is instrument currency per EUR.
+
+use rust_decimal::Decimal;
+
+use super::common::*;
+use super::BrokerStatementParser;
+use crate::activities::activities_constants::*;
+use crate::activities::ActivityImport;
+
+const SOURCE_SYSTEM: &str = "FINECO";
+const DATE_FORMATS: &[&str] = &["%d/%m/%Y", "%d-%m-%Y", "%Y-%m-%d"];
+const FEE_CURRENCY: &str = "EUR";
+
+pub(crate) struct FinecoParser;
+
+impl BrokerStatementParser for FinecoParser {
+    fn id(&self) -> &'static str {
+        "fineco"
+    }
+
+    fn name(&self) -> &'static str {
+        "Fineco"
+    }
+
+    fn detect(&self, headers: &[String]) -> bool {
+        has_columns(
+            headers,
+            &[
+                "Operazione",
+                "Titolo",
+                "Isin",
+                "Segno",
+                "Quantita",
+                "Prezzo",
+            ],
+        )
+    }
+
+    fn parse(&self, headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport> {
+        let columns = Columns::new(headers);
+        let fee_columns: Vec<usize> = headers
+            .iter()
+            .enumerate()
+            .filter(|(_, header)| {
+                let name = normalize_header(header);
+                name.starts_with("commissioni") || name.starts_with("spese")
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        rows.iter()
+            .map(|row| map_row(&columns, &fee_columns, row))
+            .collect()
+    }
+}
+
+fn map_row(columns: &Columns, fee_columns: &[usize], row: &[String]) -> ActivityImport {
+    let activity_type = match columns.get(row, "Segno").to_uppercase().as_str() {
+        "A" => ACTIVITY_TYPE_BUY,
+        "V" => ACTIVITY_TYPE_SELL,
+        _ => ACTIVITY_TYPE_UNKNOWN,
+    };
+    let mut activity = new_row(SOURCE_SYSTEM, activity_type, "", columns.get(row, "Divisa"));
+    let date = columns.get(row, "Operazione");
+    match parse_date(date, DATE_FORMATS) {
+        Some(parsed) => activity.date = parsed,
+        None => add_error(&mut activity, "date", &format!("Invalid date '{}'", date)),
+    }
+    set_isin_symbol(
+        &mut activity,
+        columns.get(row, "Isin"),
+        columns.get(row, "Titolo"),
+    );
+    activity.quantity =
+        parse_number(columns.get(row, "Quantita"), NumberFormat::Comma).map(|q| q.abs());
+    activity.unit_price = parse_number(columns.get(row, "Prezzo"), NumberFormat::Comma);
+    activity.comment = non_empty(columns.get(row, "Descrizione"));
+
+    let rate = parse_number(columns.get(row, "Cambio"), NumberFormat::Comma);
+    let fee: Decimal = fee_columns
+        .iter()
+        .filter_map(|idx| parse_number(cell(row, *idx), NumberFormat::Comma))
+        .map(|fee| fee.abs())
+        .sum();
+    if !fee.is_zero() {
+        match convert_fee(fee, FEE_CURRENCY, &activity.currency, rate) {
+            Some(converted) => activity.fee = Some(converted.round_dp(4)),
+            None => add_warning(
+                &mut activity,
+                "fee",
+                "Commissions in EUR could not be converted and were left out",
+            ),
+        }
+    } else {
+        activity.fee = Some(Decimal::ZERO);
+    }
+
+    if activity_type == ACTIVITY_TYPE_UNKNOWN {
+        activity.is_draft = true;
+        add_warning(
+            &mut activity,
+            "activityType",
+            &format!(
+                "Unrecognized Fineco movement '{}'",
+                columns.get(row, "Descrizione")
+            ),
+        );
+    }
+    activity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::BrokerParserRegistry;
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parses_italian_statement() {
+        let statement = BrokerParserRegistry::default()
+            .parse(include_bytes!("fixtures/fineco.csv"))
+            .unwrap();
+        assert_eq!(statement.broker_id, "fineco");
+        let rows = statement.activities;
+        assert_eq!(rows.len(), 3);
+
+        let etf = &rows[0];
+        assert_eq!(etf.activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(etf.date, "2024-02-05");
+        assert_eq!(etf.symbol, "IE00B4L5Y983");
+        assert_eq!(etf.symbol_name.as_deref(), Some("ISHARES CORE MSCI WORLD"));
+        assert_eq!(etf.quantity, Some(dec!(25)));
+        assert_eq!(etf.unit_price, Some(dec!(85.432)));
+        assert_eq!(etf.currency, "EUR");
+        assert_eq!(etf.fee, Some(dec!(2.95)));
+
+        let usd = &rows[1];
+        assert_eq!(usd.currency, "USD");
+        assert_eq!(usd.unit_price, Some(dec!(1234.5)));
+        // EUR 12.95 at 1.0800 USD per EUR
+        assert_eq!(usd.fee, Some(dec!(13.986)));
+
+        let sell = &rows[2];
+        assert_eq!(sell.activity_type, ACTIVITY_TYPE_SELL);
+        assert_eq!(sell.quantity, Some(dec!(10)));
+    }
+}
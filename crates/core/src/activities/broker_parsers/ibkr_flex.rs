@@ -0,0 +1,735 @@
+//! Interactive Brokers Flex Query XML importer.
+//!
+//! Converts the Trades, CashTransactions and CorporateActions sections of a
+//! Flex statement into `ActivityImport` rows that go through the regular
+//! import check and import flow:
+//! - Stock/ETF/option trades become BUY/SELL, with closed lots in metadata
+//! - Cash trades (e.g. `EUR.USD`) become FX_CONVERSION transfers
+//! - Withholding tax is linked to its dividend through `source_group_id`
+//! - Splits become SPLIT; other corporate actions become draft transfers
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use rust_decimal::Decimal;
+use serde_json::json;
+
+use super::common::{add_error, add_warning, finalize};
+use crate::activities::activities_constants::*;
+use crate::activities::ActivityImport;
+use crate::errors::{Error, ValidationError};
+use crate::Result;
+
+/// Source system recorded on activities and import runs from Flex statements.
+pub const IBKR_FLEX_SOURCE_SYSTEM: &str = "IBKR";
+
+type Attrs = HashMap<String, String>;
+
+/// A trade execution with the closed lots reported after it.
+struct FlexTrade {
+    attrs: Attrs,
+    lots: Vec<Attrs>,
+}
+
+/// Returns true when the content looks like a Flex Query XML statement.
+pub fn is_ibkr_flex(content: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&content[..content.len().min(2048)]);
+    head.contains("<FlexQueryResponse") || head.contains("<FlexStatements")
+}
+
+/// Parses a Flex Query XML statement into import rows.
+///
+/// Rows carry the IBKR account number in `account_name`; the caller assigns
+/// `account_id`. Rows that could not be mapped are returned with errors so the
+/// user sees them in the import review instead of losing them silently.
+pub fn parse_ibkr_flex(content: &[u8]) -> Result<Vec<ActivityImport>> {
+    if !is_ibkr_flex(content) {
+        return Err(Error::Validation(ValidationError::InvalidInput(
+            "Not an Interactive Brokers Flex Query XML statement".to_string(),
+        )));
+    }
+    let text = String::from_utf8_lossy(content);
+    let mut reader = Reader::from_str(&text);
+    reader.config_mut().trim_text(true);
+
+    let mut statement_account: Option<String> = None;
+    let mut trades: Vec<FlexTrade> = Vec::new();
+    let mut cash_transactions: Vec<Attrs> = Vec::new();
+    let mut corporate_actions: Vec<Attrs> = Vec::new();
+
+    loop {
+        let event = reader.read_event().map_err(|e| {
+            Error::Validation(ValidationError::InvalidInput(format!(
+                "Invalid Flex XML at position {}: {}",
+                reader.error_position(),
+                e
+            )))
+        })?;
+        let element = match event {
+            Event::Start(e) | Event::Empty(e) => e,
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        let name = element.name();
+        match name.as_ref() {
+            b"FlexStatement" => {
+                statement_account = read_attrs(&reader, &element)?.remove("accountId");
+            }
+            b"Trade" | b"Lot" => {
+                let mut attrs = read_attrs(&reader, &element)?;
+                fill_account(&mut attrs, &statement_account);
+                let is_lot = name.as_ref() == b"Lot"
+                    || attr(&attrs, "levelOfDetail").eq_ignore_ascii_case("CLOSED_LOT");
+                if is_lot {
+                    // Closed lots follow the closing execution they belong to
+                    if let Some(trade) = trades
+                        .last_mut()
+                        .filter(|t| same_instrument(&t.attrs, &attrs))
+                    {
+                        trade.lots.push(attrs);
+                    }
+                } else if is_execution(&attrs) {
+                    trades.push(FlexTrade {
+                        attrs,
+                        lots: Vec::new(),
+                    });
+                }
+            }
+            b"CashTransaction" => {
+                let mut attrs = read_attrs(&reader, &element)?;
+                fill_account(&mut attrs, &statement_account);
+                if attr(&attrs, "levelOfDetail").is_empty()
+                    || attr(&attrs, "levelOfDetail").eq_ignore_ascii_case("DETAIL")
+                {
+                    cash_transactions.push(attrs);
+                }
+            }
+            b"CorporateAction" => {
+                let mut attrs = read_attrs(&reader, &element)?;
+                fill_account(&mut attrs, &statement_account);
+                corporate_actions.push(attrs);
+            }
+            _ => {}
+        }
+    }
+
+    let mut rows: Vec<ActivityImport> = Vec::new();
+    for trade in &trades {
+        rows.push(map_trade(trade));
+    }
+    for transaction in &cash_transactions {
+        rows.push(map_cash_transaction(transaction));
+    }
+    let mut seen_splits: HashSet<String> = HashSet::new();
+    for action in &corporate_actions {
+        if let Some(row) = map_corporate_action(action, &mut seen_splits) {
+            rows.push(row);
+        }
+    }
+
+    finalize(&mut rows);
+    Ok(rows)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Trades
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn map_trade(trade: &FlexTrade) -> ActivityImport {
+    let attrs = &trade.attrs;
+    if attr(attrs, "assetCategory").eq_ignore_ascii_case("CASH") {
+        return map_fx_conversion(attrs);
+    }
+
+    let side = attr(attrs, "buySell").to_uppercase();
+    let is_cancellation = side.contains("(CA");
+    let is_buy = side.starts_with("BUY") != is_cancellation;
+    let activity_type = if is_buy {
+        ACTIVITY_TYPE_BUY
+    } else {
+        ACTIVITY_TYPE_SELL
+    };
+
+    let mut row = base_row(attrs, activity_type, &["tradeDate", "dateTime"]);
+    let quantity = dec(attrs, "quantity").map(|q| q.abs());
+    row.quantity = quantity;
+    row.unit_price = dec(attrs, "tradePrice");
+    row.amount = dec(attrs, "proceeds").map(|p| p.abs());
+    row.symbol_name = opt(attrs, "description");
+    row.exchange_mic = listing_exchange_mic(attr(attrs, "listingExchange")).map(str::to_string);
+    row.instrument_type = instrument_type(attr(attrs, "assetCategory")).map(str::to_string);
+    row.symbol = flex_symbol(attrs);
+    row.fee = trade_fee(attrs, &mut row);
+
+    if quantity.is_none_or(|q| q.is_zero()) {
+        add_error(&mut row, "quantity", "Trade has no quantity");
+    }
+    if is_cancellation {
+        add_warning(
+            &mut row,
+            "buySell",
+            "Trade cancellation, imported as the opposite side",
+        );
+    }
+
+    let mut metadata = serde_json::Map::new();
+    if let Some(isin) = opt(attrs, "isin") {
+        metadata.insert("isin".to_string(), json!(isin));
+    }
+    if let Some(multiplier) = dec(attrs, "multiplier").filter(|m| *m != Decimal::ONE) {
+        metadata.insert("multiplier".to_string(), json!(multiplier));
+    }
+    if let Some(pnl) = dec(attrs, "fifoPnlRealized").filter(|p| !p.is_zero()) {
+        metadata.insert("realized_pnl".to_string(), json!(pnl));
+    }
+    if !trade.lots.is_empty() {
+        let lots: Vec<serde_json::Value> = trade
+            .lots
+            .iter()
+            .map(|lot| {
+                let open_date = ["openDateTime", "tradeDate"]
+                    .iter()
+                    .find_map(|key| flex_date(attr(lot, key)));
+                json!({
+                    "open_date": open_date,
+                    "quantity": dec(lot, "quantity").map(|q| q.abs()),
+                    "cost_basis": dec(lot, "cost").or_else(|| dec(lot, "costBasis")),
+                    "realized_pnl": dec(lot, "fifoPnlRealized"),
+                })
+            })
+            .collect();
+        metadata.insert("closed_lots".to_string(), json!(lots));
+    }
+    if !metadata.is_empty() {
+        row.metadata = Some(serde_json::Value::Object(metadata).to_string());
+    }
+
+    row
+}
+
+/// Cash trades are currency exchanges. For `EUR.USD`, a BUY of 1000 at 1.08
+/// buys 1000 EUR and sells 1080 USD; the trade currency is the quote (USD).
+fn map_fx_conversion(attrs: &Attrs) -> ActivityImport {
+    let mut row = base_row(
+        attrs,
+        ACTIVITY_TYPE_TRANSFER_OUT,
+        &["tradeDate", "dateTime"],
+    );
+    row.subtype = Some(ACTIVITY_SUBTYPE_FX_CONVERSION.to_string());
+
+    let symbol = attr(attrs, "symbol").to_uppercase();
+    let Some((base, quote)) = symbol.split_once('.') else {
+        add_error(
+            &mut row,
+            "symbol",
+            &format!("Unrecognized currency pair '{}'", symbol),
+        );
+        return row;
+    };
+    let base_amount = dec(attrs, "quantity").map(|q| q.abs());
+    let quote_amount = dec(attrs, "proceeds").map(|p| p.abs());
+    let is_buy = attr(attrs, "buySell").to_uppercase().starts_with("BUY");
+
+    let (sold, sold_amount, bought, bought_amount) = if is_buy {
+        (quote, quote_amount, base, base_amount)
+    } else {
+        (base, base_amount, quote, quote_amount)
+    };
+    row.currency = sold.to_string();
+    row.amount = sold_amount;
+    row.fee = trade_fee(attrs, &mut row);
+    row.comment = Some(format!("FX {} {} → {}", symbol, sold, bought));
+    row.metadata = Some(
+        json!({
+            "to_currency": bought,
+            "to_amount": bought_amount,
+        })
+        .to_string(),
+    );
+    if sold_amount.is_none_or(|a| a.is_zero()) || bought_amount.is_none_or(|a| a.is_zero()) {
+        add_error(
+            &mut row,
+            "amount",
+            "FX trade is missing quantity or proceeds",
+        );
+    }
+    row
+}
+
+/// Commission plus transaction taxes (stamp duty, FTT) in the row currency.
+fn trade_fee(attrs: &Attrs, row: &mut ActivityImport) -> Option<Decimal> {
+    let commission = dec(attrs, "ibCommission").map(|c| c.abs());
+    let commission_currency = attr(attrs, "ibCommissionCurrency");
+    let commission = match commission {
+        Some(c) if !commission_currency.is_empty() && commission_currency != row.currency => {
+            add_warning(
+                row,
+                "fee",
+                &format!(
+                    "Commission of {} {} is not in the row currency and was not included",
+                    c, commission_currency
+                ),
+            );
+            None
+        }
+        other => other,
+    };
+    let taxes = dec(attrs, "taxes").map(|t| t.abs());
+    match (commission, taxes) {
+        (None, None) => None,
+        (c, t) => Some(c.unwrap_or_default() + t.unwrap_or_default()),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Cash transactions
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn map_cash_transaction(attrs: &Attrs) -> ActivityImport {
+    let amount = dec(attrs, "amount").unwrap_or_default();
+    let kind = attr(attrs, "type").to_lowercase();
+    let date_keys = ["dateTime", "settleDate", "reportDate"];
+
+    let (activity_type, subtype) = match kind.as_str() {
+        "dividends" | "payment in lieu of dividends" => (ACTIVITY_TYPE_DIVIDEND, None),
+        "withholding tax" if amount.is_sign_negative() => {
+            (ACTIVITY_TYPE_TAX, Some(ACTIVITY_SUBTYPE_WITHHOLDING))
+        }
+        "withholding tax" => (ACTIVITY_TYPE_CREDIT, Some(ACTIVITY_SUBTYPE_TAX_REFUND)),
+        "deposits/withdrawals" | "deposits & withdrawals" if amount.is_sign_negative() => {
+            (ACTIVITY_TYPE_WITHDRAWAL, None)
+        }
+        "deposits/withdrawals" | "deposits & withdrawals" => (ACTIVITY_TYPE_DEPOSIT, None),
+        "broker interest received" | "bond interest received" => (ACTIVITY_TYPE_INTEREST, None),
+        "broker interest paid" | "bond interest paid" => {
+            (ACTIVITY_TYPE_FEE, Some(ACTIVITY_SUBTYPE_INTEREST_CHARGE))
+        }
+        "other fees" | "commission adjustments" | "advisor fees" if amount.is_sign_negative() => {
+            (ACTIVITY_TYPE_FEE, None)
+        }
+        "other fees" | "commission adjustments" | "advisor fees" => {
+            (ACTIVITY_TYPE_CREDIT, Some(ACTIVITY_SUBTYPE_FEE_REFUND))
+        }
+        _ => (ACTIVITY_TYPE_UNKNOWN, None),
+    };
+
+    let mut row = base_row(attrs, activity_type, &date_keys);
+    row.subtype = subtype.map(str::to_string);
+    row.amount = Some(amount.abs());
+    row.comment = opt(attrs, "description");
+
+    let is_income_related = matches!(
+        activity_type,
+        ACTIVITY_TYPE_DIVIDEND | ACTIVITY_TYPE_INTEREST | ACTIVITY_TYPE_TAX
+    ) || subtype == Some(ACTIVITY_SUBTYPE_TAX_REFUND);
+    if is_income_related {
+        row.symbol = flex_symbol(attrs);
+        row.exchange_mic = listing_exchange_mic(attr(attrs, "listingExchange")).map(str::to_string);
+        row.source_group_id = income_group_id(attrs, &date_keys);
+    }
+
+    if activity_type == ACTIVITY_TYPE_UNKNOWN {
+        row.is_draft = true;
+        add_warning(
+            &mut row,
+            "activityType",
+            &format!(
+                "Unsupported cash transaction type '{}'",
+                attr(attrs, "type")
+            ),
+        );
+    } else if activity_type == ACTIVITY_TYPE_DIVIDEND && amount.is_sign_negative() {
+        row.is_draft = true;
+        add_warning(
+            &mut row,
+            "amount",
+            "Dividend reversal: remove or correct the original dividend instead",
+        );
+    }
+    row
+}
+
+/// Links a dividend with its withholding tax. IBKR reports both with the same
+/// `actionID`; older statements only share the instrument and pay date.
+fn income_group_id(attrs: &Attrs, date_keys: &[&str]) -> Option<String> {
+    let account = attr(attrs, "accountId");
+    if let Some(action_id) = opt(attrs, "actionID").filter(|id| id != "0") {
+        return Some(format!("ibkr:{}:{}", account, action_id));
+    }
+    let instrument = opt(attrs, "conid")
+        .or_else(|| opt(attrs, "isin"))
+        .or_else(|| opt(attrs, "symbol"))?;
+    let date = date_keys
+        .iter()
+        .find_map(|key| flex_date(attr(attrs, key)))?;
+    Some(format!("ibkr:{}:{}:{}", account, instrument, date))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Corporate actions
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn map_corporate_action(
+    attrs: &Attrs,
+    seen_splits: &mut HashSet<String>,
+) -> Option<ActivityImport> {
+    let action_type = attr(attrs, "type").to_uppercase();
+    let description = opt(attrs, "actionDescription").or_else(|| opt(attrs, "description"));
+    let date_keys = ["dateTime", "reportDate"];
+    let group_id =
+        opt(attrs, "actionID").map(|id| format!("ibkr:{}:{}", attr(attrs, "accountId"), id));
+
+    if action_type == "FS" || action_type == "RS" {
+        // IBKR may report a split as several legs; keep one SPLIT per action
+        let key = group_id
+            .clone()
+            .unwrap_or_else(|| format!("{}:{}", attr(attrs, "symbol"), attr(attrs, "dateTime")));
+        if !seen_splits.insert(key) {
+            return None;
+        }
+        let mut row = base_row(attrs, ACTIVITY_TYPE_SPLIT, &date_keys);
+        row.symbol = flex_symbol(attrs);
+        row.exchange_mic = listing_exchange_mic(attr(attrs, "listingExchange")).map(str::to_string);
+        row.source_group_id = group_id;
+        row.comment = description.clone();
+        row.amount = description.as_deref().and_then(split_ratio);
+        if row.amount.is_none() {
+            add_error(
+                &mut row,
+                "amount",
+                "Could not read the split ratio from the description",
+            );
+        }
+        return Some(row);
+    }
+
+    let quantity = dec(attrs, "quantity").unwrap_or_default();
+    if quantity.is_zero() {
+        return None;
+    }
+    let activity_type = if quantity.is_sign_positive() {
+        ACTIVITY_TYPE_TRANSFER_IN
+    } else {
+        ACTIVITY_TYPE_TRANSFER_OUT
+    };
+    let mut row = base_row(attrs, activity_type, &date_keys);
+    row.symbol = flex_symbol(attrs);
+    row.symbol_name = opt(attrs, "description");
+    row.exchange_mic = listing_exchange_mic(attr(attrs, "listingExchange")).map(str::to_string);
+    row.quantity = Some(quantity.abs());
+    row.unit_price = dec(attrs, "value")
+        .filter(|v| !v.is_zero())
+        .map(|v| v.abs() / quantity.abs());
+    row.source_group_id = group_id;
+    row.comment = description;
+    row.is_draft = true;
+    add_warning(
+        &mut row,
+        "activityType",
+        &format!(
+            "Corporate action ({}): review quantity and cost basis",
+            action_type
+        ),
+    );
+    Some(row)
+}
+
+/// Reads "SPLIT 4 FOR 1" as a ratio of 4 (new shares per old share).
+fn split_ratio(description: &str) -> Option<Decimal> {
+    let upper = description.to_uppercase();
+    let rest = &upper[upper.find("SPLIT ")? + "SPLIT ".len()..];
+    let mut tokens = rest.split_whitespace();
+    let new = Decimal::from_str(tokens.next()?).ok()?;
+    if tokens.next()? != "FOR" {
+        return None;
+    }
+    let old = Decimal::from_str(
+        tokens
+            .next()?
+            .trim_end_matches(|c: char| !c.is_ascii_digit()),
+    )
+    .ok()?;
+    if new.is_zero() || old.is_zero() {
+        return None;
+    }
+    Some(new / old)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn base_row(attrs: &Attrs, activity_type: &str, date_keys: &[&str]) -> ActivityImport {
+    let mut row = ActivityImport {
+        activity_type: activity_type.to_string(),
+        currency: attr(attrs, "currency").to_uppercase(),
+        account_name: opt(attrs, "accountId"),
+        source_system: Some(IBKR_FLEX_SOURCE_SYSTEM.to_string()),
+        source_record_id: opt(attrs, "transactionID").or_else(|| opt(attrs, "tradeID")),
+        is_valid: true,
+        ..Default::default()
+    };
+    match date_keys.iter().find_map(|key| flex_date(attr(attrs, key))) {
+        Some(date) => row.date = date,
+        None => add_error(
+            &mut row,
+            "date",
+            "Unrecognized date; set the Flex query date format to yyyyMMdd",
+        ),
+    }
+    row
+}
+
+fn read_attrs(reader: &Reader<&[u8]>, element: &BytesStart) -> Result<Attrs> {
+    let invalid = |e: String| {
+        Error::Validation(ValidationError::InvalidInput(format!(
+            "Invalid Flex XML attribute: {}",
+            e
+        )))
+    };
+    let mut attrs = HashMap::new();
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(|e| invalid(e.to_string()))?;
+        let key = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+        let value = attribute
+            .decode_and_unescape_value(reader.decoder())
+            .map_err(|e| invalid(e.to_string()))?;
+        attrs.insert(key, value.trim().to_string());
+    }
+    Ok(attrs)
+}
+
+fn fill_account(attrs: &mut Attrs, statement_account: &Option<String>) {
+    if attr(attrs, "accountId").is_empty() {
+        if let Some(account) = statement_account {
+            attrs.insert("accountId".to_string(), account.clone());
+        }
+    }
+}
+
+fn attr<'a>(attrs: &'a Attrs, key: &str) -> &'a str {
+    attrs.get(key).map(String::as_str).unwrap_or("")
+}
+
+fn opt(attrs: &Attrs, key: &str) -> Option<String> {
+    Some(attr(attrs, key))
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn dec(attrs: &Attrs, key: &str) -> Option<Decimal> {
+    let value = attr(attrs, key).replace(',', "");
+    if value.is_empty() {
+        return None;
+    }
+    Decimal::from_str(&value)
+        .or_else(|_| Decimal::from_scientific(&value))
+        .ok()
+}
+
+/// Parses Flex dates (`20240115`, `2024-01-15`, optionally followed by a time).
+fn flex_date(value: &str) -> Option<String> {
+    let date = value.split([';', ',', ' ', 'T']).next()?.trim();
+    NaiveDate::parse_from_str(date, "%Y%m%d")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+        .ok()
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+/// Only executions are imported; order and summary rows would double count.
+fn is_execution(attrs: &Attrs) -> bool {
+    let level = attr(attrs, "levelOfDetail");
+    level.is_empty() || level.eq_ignore_ascii_case("EXECUTION")
+}
+
+fn same_instrument(a: &Attrs, b: &Attrs) -> bool {
+    match (opt(a, "conid"), opt(b, "conid")) {
+        (Some(x), Some(y)) => x == y,
+        _ => attr(a, "symbol") == attr(b, "symbol"),
+    }
+}
+
+/// IBKR uses spaces in share classes (`BRK B`) and OCC option symbols.
+fn flex_symbol(attrs: &Attrs) -> String {
+    let symbol = attr(attrs, "symbol");
+    if attr(attrs, "assetCategory").eq_ignore_ascii_case("OPT") {
+        symbol.split_whitespace().collect()
+    } else {
+        symbol.split_whitespace().collect::<Vec<_>>().join("-")
+    }
+}
+
+fn instrument_type(asset_category: &str) -> Option<&'static str> {
+    match asset_category.to_uppercase().as_str() {
+        "STK" | "FUND" => Some("EQUITY"),
+        "OPT" => Some("OPTION"),
+        "CRYPTO" => Some("CRYPTO"),
+        _ => None,
+    }
+}
+
+/// Maps IBKR listing exchange codes to MICs for the most common venues.
+fn listing_exchange_mic(exchange: &str) -> Option<&'static str> {
+    match exchange.to_uppercase().as_str() {
+        "NASDAQ" => Some("XNAS"),
+        "NYSE" => Some("XNYS"),
+        "ARCA" | "NYSEARCA" => Some("ARCX"),
+        "AMEX" => Some("XASE"),
+        "BATS" => Some("BATS"),
+        "TSE" => Some("XTSE"),
+        "VENTURE" => Some("XTSX"),
+        "LSE" | "LSEETF" => Some("XLON"),
+        "IBIS" | "IBIS2" => Some("XETR"),
+        "FWB" | "FWB2" => Some("XFRA"),
+        "SBF" => Some("XPAR"),
+        "AEB" => Some("XAMS"),
+        "EBS" | "SWX" => Some("XSWX"),
+        "BVME" | "BVME.ETF" => Some("XMIL"),
+        "BM" => Some("XMAD"),
+        "SFB" => Some("XSTO"),
+        "ASX" => Some("XASX"),
+        "SEHK" => Some("XHKG"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const STATEMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<FlexQueryResponse queryName="Activity" type="AF">
+  <FlexStatements count="1">
+    <FlexStatement accountId="U1234567" fromDate="20240101" toDate="20241231">
+      <Trades>
+        <Trade accountId="U1234567" currency="USD" assetCategory="STK" symbol="AAPL" description="APPLE INC" isin="US0378331005" listingExchange="NASDAQ" conid="265598" transactionID="1001" tradeDate="20240115" quantity="10" tradePrice="185.5" proceeds="-1855" ibCommission="-1" ibCommissionCurrency="USD" buySell="BUY" levelOfDetail="EXECUTION"/>
+        <Trade accountId="U1234567" currency="USD" assetCategory="STK" symbol="AAPL" conid="265598" transactionID="1000" tradeDate="20240115" quantity="10" tradePrice="185.5" buySell="BUY" levelOfDetail="ORDER"/>
+        <Trade accountId="U1234567" currency="USD" assetCategory="STK" symbol="AAPL" listingExchange="NASDAQ" conid="265598" transactionID="1002" tradeDate="20240610" quantity="-4" tradePrice="195" proceeds="780" ibCommission="-1" ibCommissionCurrency="USD" fifoPnlRealized="36" buySell="SELL" levelOfDetail="EXECUTION"/>
+        <Lot accountId="U1234567" currency="USD" assetCategory="STK" symbol="AAPL" conid="265598" quantity="-4" cost="742" openDateTime="20240115;093000" levelOfDetail="CLOSED_LOT"/>
+        <Trade accountId="U1234567" currency="USD" assetCategory="CASH" symbol="EUR.USD" transactionID="1003" tradeDate="20240201" quantity="1000" tradePrice="1.08" proceeds="-1080" ibCommission="-2" ibCommissionCurrency="USD" buySell="BUY" levelOfDetail="EXECUTION"/>
+      </Trades>
+      <CashTransactions>
+        <CashTransaction accountId="U1234567" currency="USD" symbol="AAPL" conid="265598" dateTime="20240215" amount="2.4" type="Dividends" actionID="555" transactionID="2001" description="AAPL(US0378331005) CASH DIVIDEND USD 0.24 PER SHARE"/>
+        <CashTransaction accountId="U1234567" currency="USD" symbol="AAPL" conid="265598" dateTime="20240215" amount="-0.36" type="Withholding Tax" actionID="555" transactionID="2002" description="AAPL(US0378331005) CASH DIVIDEND - US TAX"/>
+        <CashTransaction accountId="U1234567" currency="USD" dateTime="20240105" amount="5000" type="Deposits/Withdrawals" transactionID="2003" description="CASH RECEIPTS"/>
+      </CashTransactions>
+      <CorporateActions>
+        <CorporateAction accountId="U1234567" currency="USD" symbol="NVDA" conid="4815747" dateTime="20240610;202500" quantity="90" type="FS" actionID="777" actionDescription="NVDA(US67066G1040) SPLIT 10 FOR 1 (NVDA, NVIDIA CORP, US67066G1040)"/>
+      </CorporateActions>
+    </FlexStatement>
+  </FlexStatements>
+</FlexQueryResponse>"#;
+
+    fn parse() -> Vec<ActivityImport> {
+        parse_ibkr_flex(STATEMENT.as_bytes()).expect("statement should parse")
+    }
+
+    fn find<'a>(rows: &'a [ActivityImport], record_id: &str) -> &'a ActivityImport {
+        rows.iter()
+            .find(|r| r.source_record_id.as_deref() == Some(record_id))
+            .expect("row should exist")
+    }
+
+    #[test]
+    fn test_rejects_non_flex_content() {
+        assert!(parse_ibkr_flex(b"Date,Symbol,Quantity\n").is_err());
+    }
+
+    #[test]
+    fn test_imports_executions_only() {
+        let rows = parse();
+        // 3 executions + 3 cash transactions + 1 split; the ORDER row is skipped
+        assert_eq!(rows.len(), 7);
+        assert!(rows
+            .iter()
+            .all(|r| r.source_system.as_deref() == Some("IBKR")));
+
+        let buy = find(&rows, "1001");
+        assert_eq!(buy.activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(buy.date, "2024-01-15");
+        assert_eq!(buy.quantity, Some(dec!(10)));
+        assert_eq!(buy.unit_price, Some(dec!(185.5)));
+        assert_eq!(buy.fee, Some(dec!(1)));
+        assert_eq!(buy.exchange_mic.as_deref(), Some("XNAS"));
+        assert_eq!(buy.account_name.as_deref(), Some("U1234567"));
+    }
+
+    #[test]
+    fn test_sell_records_closed_lots() {
+        let rows = parse();
+        let sell = find(&rows, "1002");
+        assert_eq!(sell.activity_type, ACTIVITY_TYPE_SELL);
+        assert_eq!(sell.quantity, Some(dec!(4)));
+
+        let metadata: serde_json::Value =
+            serde_json::from_str(sell.metadata.as_deref().unwrap()).unwrap();
+        let lots = metadata["closed_lots"].as_array().unwrap();
+        assert_eq!(lots.len(), 1);
+        assert_eq!(lots[0]["open_date"], "2024-01-15");
+    }
+
+    #[test]
+    fn test_cash_trade_becomes_fx_conversion() {
+        let rows = parse();
+        let fx = find(&rows, "1003");
+        assert_eq!(fx.activity_type, ACTIVITY_TYPE_TRANSFER_OUT);
+        assert_eq!(fx.subtype.as_deref(), Some(ACTIVITY_SUBTYPE_FX_CONVERSION));
+        assert_eq!(fx.currency, "USD");
+        assert_eq!(fx.amount, Some(dec!(1080)));
+        assert_eq!(fx.fee, Some(dec!(2)));
+
+        let metadata: serde_json::Value =
+            serde_json::from_str(fx.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["to_currency"], "EUR");
+    }
+
+    #[test]
+    fn test_withholding_tax_is_linked_to_dividend() {
+        let rows = parse();
+        let dividend = find(&rows, "2001");
+        let tax = find(&rows, "2002");
+        assert_eq!(dividend.activity_type, ACTIVITY_TYPE_DIVIDEND);
+        assert_eq!(tax.activity_type, ACTIVITY_TYPE_TAX);
+        assert_eq!(tax.subtype.as_deref(), Some(ACTIVITY_SUBTYPE_WITHHOLDING));
+        assert_eq!(tax.amount, Some(dec!(0.36)));
+        assert!(dividend.source_group_id.is_some());
+        assert_eq!(dividend.source_group_id, tax.source_group_id);
+
+        let deposit = find(&rows, "2003");
+        assert_eq!(deposit.activity_type, ACTIVITY_TYPE_DEPOSIT);
+        assert!(deposit.source_group_id.is_none());
+    }
+
+    #[test]
+    fn test_forward_split_ratio() {
+        let rows = parse();
+        let split = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_SPLIT)
+            .unwrap();
+        assert_eq!(split.symbol, "NVDA");
+        assert_eq!(split.date, "2024-06-10");
+        assert_eq!(split.amount, Some(dec!(10)));
+        assert!(split.is_valid);
+    }
+
+    #[test]
+    fn test_rows_are_sorted_and_numbered() {
+        let rows = parse();
+        assert_eq!(rows[0].activity_type, ACTIVITY_TYPE_DEPOSIT);
+        let lines: Vec<i32> = rows.iter().filter_map(|r| r.line_number).collect();
+        assert_eq!(lines, (1..=7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_split_ratio_parsing() {
+        assert_eq!(split_ratio("SPLIT 4 FOR 1"), Some(dec!(4)));
+        assert_eq!(split_ratio("X(US0) SPLIT 1 FOR 10 (X)"), Some(dec!(0.1)));
+        assert_eq!(split_ratio("SPINOFF 1 FOR 5"), None);
+    }
+}
@@ -0,0 +1,370 @@
+//! Kraken ledger export (`ledgers.csv`).
+//!
+//! Every balance change is one ledger row; the two legs of a trade share a
+//! `refid`. Fiat/crypto pairs become BUY/SELL priced from the fiat leg,
+//! fiat/fiat pairs become FX conversions, and crypto/crypto pairs are
+//! returned as drafts because the ledger has no value in a fiat currency.
+//! Kraken asset codes (`XXBT`, `ZEUR`, `DOT.S`) are normalized to tickers.
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use serde_json::json;
+
+use super::common::*;
+use super::BrokerStatementParser;
+use crate::activities::activities_constants::*;
+use crate::activities::ActivityImport;
+
+const SOURCE_SYSTEM: &str = "KRAKEN";
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d"];
+const FIAT: &[&str] = &["USD", "EUR", "GBP", "CAD", "JPY", "AUD", "CHF"];
+const INSTRUMENT_TYPE_CRYPTO: &str = "CRYPTO";
+
+pub(crate) struct KrakenLedgerParser;
+
+/// One ledger row.
+struct Leg {
+    txid: String,
+    refid: String,
+    time: String,
+    kind: String,
+    subtype: String,
+    asset: String,
+    amount: Decimal,
+    fee: Decimal,
+}
+
+impl Leg {
+    fn is_fiat(&self) -> bool {
+        FIAT.contains(&self.asset.as_str())
+    }
+}
+
+impl BrokerStatementParser for KrakenLedgerParser {
+    fn id(&self) -> &'static str {
+        "kraken-ledger"
+    }
+
+    fn name(&self) -> &'static str {
+        "Kraken (Ledgers)"
+    }
+
+    fn detect(&self, headers: &[String]) -> bool {
+        has_columns(
+            headers,
+            &[
+                "txid", "refid", "time", "type", "asset", "amount", "fee", "balance",
+            ],
+        )
+    }
+
+    fn parse(&self, headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport> {
+        let columns = Columns::new(headers);
+        let legs: Vec<Leg> = rows
+            .iter()
+            // Pending deposits and withdrawals are repeated without a txid
+            .filter(|row| !columns.get(row, "txid").is_empty())
+            .map(|row| Leg {
+                txid: columns.get(row, "txid").to_string(),
+                refid: columns.get(row, "refid").to_string(),
+                time: columns.get(row, "time").to_string(),
+                kind: columns.get(row, "type").to_lowercase(),
+                subtype: columns.get(row, "subtype").to_lowercase(),
+                asset: normalize_asset(columns.get(row, "asset")),
+                amount: parse_number(columns.get(row, "amount"), NumberFormat::Dot)
+                    .unwrap_or_default(),
+                fee: parse_number(columns.get(row, "fee"), NumberFormat::Dot)
+                    .unwrap_or_default()
+                    .abs(),
+            })
+            .collect();
+
+        let mut activities = Vec::new();
+        let mut trades: Vec<(String, Vec<&Leg>)> = Vec::new();
+        let mut trade_index: HashMap<&str, usize> = HashMap::new();
+        for leg in &legs {
+            match leg.kind.as_str() {
+                "trade" | "spend" | "receive" => {
+                    let idx = *trade_index.entry(leg.refid.as_str()).or_insert_with(|| {
+                        trades.push((leg.refid.clone(), Vec::new()));
+                        trades.len() - 1
+                    });
+                    trades[idx].1.push(leg);
+                }
+                "deposit" | "withdrawal" => activities.push(map_funding(leg)),
+                "staking" | "earn" => activities.extend(map_reward(leg)),
+                // Moves between spot and staking wallets
+                "transfer" => {}
+                _ => activities.push(unknown(leg)),
+            }
+        }
+        for (refid, group) in &trades {
+            activities.extend(map_trade(refid, group));
+        }
+        activities
+    }
+}
+
+fn map_trade(refid: &str, legs: &[&Leg]) -> Vec<ActivityImport> {
+    let sent = legs.iter().find(|l| l.amount < Decimal::ZERO);
+    let received = legs.iter().find(|l| l.amount > Decimal::ZERO);
+    let (Some(sent), Some(received), 2) = (sent, received, legs.len()) else {
+        return legs.iter().copied().map(unknown).collect();
+    };
+
+    match (sent.is_fiat(), received.is_fiat()) {
+        (true, false) => vec![priced_trade(ACTIVITY_TYPE_BUY, refid, received, sent)],
+        (false, true) => vec![priced_trade(ACTIVITY_TYPE_SELL, refid, sent, received)],
+        (true, true) => {
+            let mut activity = base_row(sent, ACTIVITY_TYPE_TRANSFER_OUT, &sent.asset);
+            activity.subtype = Some(ACTIVITY_SUBTYPE_FX_CONVERSION.to_string());
+            activity.amount = Some(sent.amount.abs());
+            activity.fee = Some(sent.fee);
+            activity.metadata = Some(
+                json!({
+                    "to_currency": received.asset,
+                    "to_amount": received.amount - received.fee,
+                })
+                .to_string(),
+            );
+            activity.source_record_id = Some(refid.to_string());
+            vec![activity]
+        }
+        (false, false) => {
+            let mut sell = crypto_row(sent, ACTIVITY_TYPE_SELL);
+            sell.quantity = Some(sent.amount.abs() + sent.fee);
+            let mut buy = crypto_row(received, ACTIVITY_TYPE_BUY);
+            buy.quantity = Some(received.amount - received.fee);
+            for activity in [&mut sell, &mut buy] {
+                activity.is_draft = true;
+                activity.source_group_id = Some(refid.to_string());
+                add_warning(
+                    activity,
+                    "unitPrice",
+                    &format!(
+                        "Crypto-to-crypto trade {} → {}: set the price in your currency",
+                        sent.asset, received.asset
+                    ),
+                );
+            }
+            vec![sell, buy]
+        }
+    }
+}
+
+/// BUY or SELL of `crypto` priced from the `fiat` leg. Fees charged in the
+/// coin are valued at the trade price.
+fn priced_trade(activity_type: &str, refid: &str, crypto: &Leg, fiat: &Leg) -> ActivityImport {
+    let mut activity = crypto_row(crypto, activity_type);
+    activity.currency = fiat.asset.clone();
+    let quantity = crypto.amount.abs();
+    let value = fiat.amount.abs();
+    activity.quantity = Some(quantity);
+    activity.amount = Some(value);
+    if !quantity.is_zero() {
+        let unit_price = value / quantity;
+        activity.unit_price = Some(unit_price.round_dp(8));
+        activity.fee = Some((fiat.fee + crypto.fee * unit_price).round_dp(4));
+    }
+    activity.source_record_id = Some(refid.to_string());
+    activity
+}
+
+fn map_funding(leg: &Leg) -> ActivityImport {
+    let deposit = leg.kind == "deposit";
+    if leg.is_fiat() {
+        let activity_type = if deposit {
+            ACTIVITY_TYPE_DEPOSIT
+        } else {
+            ACTIVITY_TYPE_WITHDRAWAL
+        };
+        let mut activity = base_row(leg, activity_type, &leg.asset);
+        activity.amount = Some(leg.amount.abs());
+        activity.fee = Some(leg.fee);
+        return activity;
+    }
+
+    let activity_type = if deposit {
+        ACTIVITY_TYPE_TRANSFER_IN
+    } else {
+        ACTIVITY_TYPE_TRANSFER_OUT
+    };
+    let mut activity = crypto_row(leg, activity_type);
+    if deposit {
+        activity.quantity = Some(leg.amount.abs() - leg.fee);
+        activity.is_draft = true;
+        add_warning(
+            &mut activity,
+            "unitPrice",
+            "Kraken does not report the cost basis of deposited coins",
+        );
+    } else {
+        // The network fee leaves the account in the same coin
+        activity.quantity = Some(leg.amount.abs() + leg.fee);
+        if !leg.fee.is_zero() {
+            activity.comment = Some(format!("Includes {} {} network fee", leg.fee, leg.asset));
+        }
+    }
+    activity
+}
+
+fn map_reward(leg: &Leg) -> Option<ActivityImport> {
+    let is_reward = leg.kind == "staking" || leg.subtype == "reward";
+    if !is_reward || leg.amount <= Decimal::ZERO {
+        return None;
+    }
+    let mut activity = crypto_row(leg, ACTIVITY_TYPE_INTEREST);
+    activity.subtype = Some(ACTIVITY_SUBTYPE_STAKING_REWARD.to_string());
+    activity.quantity = Some(leg.amount - leg.fee);
+    activity.is_draft = true;
+    add_warning(
+        &mut activity,
+        "unitPrice",
+        "Set the market value of the reward on the day it was received",
+    );
+    Some(activity)
+}
+
+fn unknown(leg: &Leg) -> ActivityImport {
+    let currency = if leg.is_fiat() {
+        leg.asset.as_str()
+    } else {
+        ""
+    };
+    let mut activity = base_row(leg, ACTIVITY_TYPE_UNKNOWN, currency);
+    if !leg.is_fiat() {
+        activity.symbol = leg.asset.clone();
+        activity.quantity = Some(leg.amount);
+    } else {
+        activity.amount = Some(leg.amount);
+    }
+    activity.is_draft = true;
+    add_warning(
+        &mut activity,
+        "activityType",
+        &format!("Unrecognized Kraken ledger entry '{}'", leg.kind),
+    );
+    activity
+}
+
+fn crypto_row(leg: &Leg, activity_type: &str) -> ActivityImport {
+    let mut activity = base_row(leg, activity_type, "");
+    activity.symbol = leg.asset.clone();
+    activity.instrument_type = Some(INSTRUMENT_TYPE_CRYPTO.to_string());
+    activity
+}
+
+fn base_row(leg: &Leg, activity_type: &str, currency: &str) -> ActivityImport {
+    let mut activity = new_row(SOURCE_SYSTEM, activity_type, "", currency);
+    match parse_date(&leg.time, DATE_FORMATS) {
+        Some(date) => activity.date = date,
+        None => add_error(
+            &mut activity,
+            "date",
+            &format!("Invalid date '{}'", leg.time),
+        ),
+    }
+    activity.source_record_id = Some(leg.txid.clone());
+    activity
+}
+
+/// Maps Kraken asset codes to tickers: `XXBT` → `BTC`, `ZEUR` → `EUR`,
+/// `DOT.S` → `DOT`, `ETH2.S` → `ETH`.
+fn normalize_asset(asset: &str) -> String {
+    let upper = asset.trim().to_uppercase();
+    let code = upper.split('.').next().unwrap_or_default();
+    match code {
+        "XXBT" | "XBT" => "BTC",
+        "XXDG" | "XDG" => "DOGE",
+        "ETH2" => "ETH",
+        // Legacy codes: X prefix for crypto, Z prefix for fiat
+        "XETC" | "XETH" | "XLTC" | "XMLN" | "XREP" | "XXLM" | "XXMR" | "XXRP" | "XZEC" | "ZUSD"
+        | "ZEUR" | "ZGBP" | "ZCAD" | "ZJPY" | "ZAUD" => &code[1..],
+        other => other,
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::BrokerParserRegistry;
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn fixture() -> Vec<ActivityImport> {
+        BrokerParserRegistry::default()
+            .parse(include_bytes!("fixtures/kraken_ledgers.csv"))
+            .unwrap()
+            .activities
+    }
+
+    #[test]
+    fn test_normalize_asset() {
+        assert_eq!(normalize_asset("XXBT"), "BTC");
+        assert_eq!(normalize_asset("XBT.M"), "BTC");
+        assert_eq!(normalize_asset("XETH"), "ETH");
+        assert_eq!(normalize_asset("ZEUR"), "EUR");
+        assert_eq!(normalize_asset("DOT.S"), "DOT");
+        assert_eq!(normalize_asset("ETH2.S"), "ETH");
+        assert_eq!(normalize_asset("SOL"), "SOL");
+        assert_eq!(normalize_asset("XTZ"), "XTZ");
+    }
+
+    #[test]
+    fn test_fiat_crypto_trade_becomes_buy() {
+        let rows = fixture();
+        let buy = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_BUY && r.symbol == "BTC")
+            .unwrap();
+        assert_eq!(buy.currency, "EUR");
+        assert_eq!(buy.quantity, Some(dec!(0.04)));
+        assert_eq!(buy.unit_price, Some(dec!(37500)));
+        assert_eq!(buy.fee, Some(dec!(2.40)));
+        assert_eq!(buy.instrument_type.as_deref(), Some("CRYPTO"));
+        assert_eq!(buy.source_record_id.as_deref(), Some("TJKLXX-A2B3C-1"));
+        assert!(!buy.is_draft);
+
+        let spend = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_BUY && r.symbol == "SOL")
+            .unwrap();
+        assert_eq!(spend.unit_price, Some(dec!(125)));
+    }
+
+    #[test]
+    fn test_funding_staking_and_crypto_swaps() {
+        let rows = fixture();
+        // The pending duplicate without a txid is skipped
+        let deposits: Vec<_> = rows
+            .iter()
+            .filter(|r| r.activity_type == ACTIVITY_TYPE_DEPOSIT)
+            .collect();
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].amount, Some(dec!(2000)));
+
+        let reward = rows
+            .iter()
+            .find(|r| r.subtype.as_deref() == Some(ACTIVITY_SUBTYPE_STAKING_REWARD))
+            .unwrap();
+        assert_eq!(reward.activity_type, ACTIVITY_TYPE_INTEREST);
+        assert_eq!(reward.symbol, "ETH");
+        assert_eq!(reward.quantity, Some(dec!(0.0012)));
+        assert!(reward.is_draft);
+
+        let swap: Vec<_> = rows
+            .iter()
+            .filter(|r| r.source_group_id.as_deref() == Some("TSWAP1-XYZ"))
+            .collect();
+        assert_eq!(swap.len(), 2);
+        assert!(swap.iter().all(|r| r.is_draft));
+
+        let withdrawal = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_TRANSFER_OUT)
+            .unwrap();
+        assert_eq!(withdrawal.symbol, "BTC");
+        assert_eq!(withdrawal.quantity, Some(dec!(0.01015)));
+    }
+}
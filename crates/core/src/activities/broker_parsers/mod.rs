@@ -0,0 +1,296 @@
+//! Broker statement parsers.
+//!
+//! Each parser recognises one broker export by its header row and maps it to
+//! `ActivityImport` rows with `source_system`, `source_record_id` and, where
+//! the broker reports them, linked income/tax rows through `source_group_id`.
+//! The rows then go through the regular import check and review flow, so
+//! anything a parser cannot map is returned with errors or as a draft rather
+//! than dropped.
+//!
+//! New brokers are added by implementing [`BrokerStatementParser`] and
+//! registering it in [`BrokerParserRegistry::default`]. PDF contract notes are
+//! read through [`ContractNoteTemplate`]s instead (see `contract_notes`).
+
+mod common;
+mod contract_notes;
+mod degiro;
+mod fidelity;
+mod fineco;
+mod ibkr_flex;
+mod kraken;
+mod revolut;
+mod schwab;
+mod trading212;
+mod vanguard;
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::activities_model::ActivityImport;
+use super::csv_parser::{parse_csv, ParseConfig};
+use crate::errors::{Error, ValidationError};
+use crate::Result;
+use contract_notes::ContractNoteParser;
+
+pub use contract_notes::{ChargePattern, ContractNoteTemplate};
+pub use ibkr_flex::{is_ibkr_flex, parse_ibkr_flex, IBKR_FLEX_SOURCE_SYSTEM};
+
+/// How many leading rows are searched for a known header row. Fidelity and
+/// Vanguard put account summaries and holdings above their transactions.
+const HEADER_SEARCH_ROWS: usize = 60;
+
+/// A parser for one broker's CSV export.
+pub trait BrokerStatementParser: Send + Sync {
+    /// Stable identifier, e.g. `degiro-transactions`.
+    fn id(&self) -> &'static str;
+
+    /// Display name shown in the import wizard.
+    fn name(&self) -> &'static str;
+
+    /// Returns true when `headers` is this export's header row.
+    fn detect(&self, headers: &[String]) -> bool;
+
+    /// Maps the data rows that follow the header row.
+    fn parse(&self, headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport>;
+}
+
+/// A registered parser, as listed to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerParserInfo {
+    pub id: String,
+    pub name: String,
+}
+
+/// Result of parsing a broker statement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrokerStatement {
+    pub broker_id: String,
+    pub broker_name: String,
+    pub activities: Vec<ActivityImport>,
+}
+
+/// Ordered set of broker parsers; the first parser that recognises a header
+/// row (or, for PDFs, a contract note template) wins.
+pub struct BrokerParserRegistry {
+    parsers: Vec<Arc<dyn BrokerStatementParser>>,
+    contract_notes: Vec<ContractNoteParser>,
+}
+
+impl BrokerParserRegistry {
+    /// Creates a registry without any parsers.
+    pub fn new() -> Self {
+        Self {
+            parsers: Vec::new(),
+            contract_notes: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, parser: Arc<dyn BrokerStatementParser>) {
+        self.parsers.push(parser);
+    }
+
+    /// Adds a PDF contract note template. Fails when a pattern does not compile.
+    pub fn register_contract_note(&mut self, template: ContractNoteTemplate) -> Result<()> {
+        self.contract_notes.push(ContractNoteParser::new(template)?);
+        Ok(())
+    }
+
+    /// Lists the supported formats, including the IBKR Flex XML importer.
+    pub fn parsers(&self) -> Vec<BrokerParserInfo> {
+        let mut infos = vec![BrokerParserInfo {
+            id: "ibkr-flex".to_string(),
+            name: "Interactive Brokers (Flex Query XML)".to_string(),
+        }];
+        infos.extend(self.parsers.iter().map(|parser| BrokerParserInfo {
+            id: parser.id().to_string(),
+            name: parser.name().to_string(),
+        }));
+        infos.extend(self.contract_notes.iter().map(|parser| BrokerParserInfo {
+            id: parser.id().to_string(),
+            name: parser.name().to_string(),
+        }));
+        infos
+    }
+
+    /// Detects the statement format and parses it into import rows.
+    ///
+    /// Rows carry the broker's account reference in `account_name` when the
+    /// export has one; the caller assigns `account_id`.
+    pub fn parse(&self, content: &[u8]) -> Result<BrokerStatement> {
+        if is_ibkr_flex(content) {
+            return Ok(BrokerStatement {
+                broker_id: "ibkr-flex".to_string(),
+                broker_name: "Interactive Brokers (Flex Query XML)".to_string(),
+                activities: parse_ibkr_flex(content)?,
+            });
+        }
+        if contract_notes::is_pdf(content) {
+            return self.parse_pdf(content);
+        }
+
+        // Auto-detection looks at the first lines only, which may be a
+        // preamble, so fall back to each common delimiter explicitly.
+        for delimiter in [None, Some(","), Some(";"), Some("\t")] {
+            let config = ParseConfig {
+                has_header_row: Some(false),
+                delimiter: delimiter.map(str::to_string),
+                ..Default::default()
+            };
+            let Ok(parsed) = parse_csv(content, &config) else {
+                continue;
+            };
+            if let Some(statement) = self.parse_rows(&parsed.rows) {
+                return Ok(statement);
+            }
+        }
+
+        Err(Error::Validation(ValidationError::InvalidInput(
+            "Unrecognized broker statement. Use the CSV import with a column mapping instead."
+                .to_string(),
+        )))
+    }
+
+    fn parse_pdf(&self, content: &[u8]) -> Result<BrokerStatement> {
+        let text = contract_notes::extract_pdf_text(content)?;
+        let parser = self
+            .contract_notes
+            .iter()
+            .find(|parser| parser.detect(&text))
+            .ok_or_else(|| {
+                Error::Validation(ValidationError::InvalidInput(
+                    "Unrecognized contract note. No template matches this broker's PDF."
+                        .to_string(),
+                ))
+            })?;
+        let mut activities = parser.parse(&text);
+        common::finalize(&mut activities);
+        Ok(BrokerStatement {
+            broker_id: parser.id().to_string(),
+            broker_name: parser.name().to_string(),
+            activities,
+        })
+    }
+
+    fn parse_rows(&self, rows: &[Vec<String>]) -> Option<BrokerStatement> {
+        for (idx, headers) in rows.iter().take(HEADER_SEARCH_ROWS).enumerate() {
+            let Some(parser) = self.parsers.iter().find(|p| p.detect(headers)) else {
+                continue;
+            };
+            // Section titles, totals and disclaimers have at most one cell
+            let data: Vec<Vec<String>> = rows[idx + 1..]
+                .iter()
+                .filter(|row| row.iter().filter(|c| !c.trim().is_empty()).count() > 1)
+                .cloned()
+                .collect();
+            let mut activities = parser.parse(headers, &data);
+            common::finalize(&mut activities);
+            return Some(BrokerStatement {
+                broker_id: parser.id().to_string(),
+                broker_name: parser.name().to_string(),
+                activities,
+            });
+        }
+        None
+    }
+}
+
+impl Default for BrokerParserRegistry {
+    /// Registry with the built-in broker profiles.
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(degiro::DegiroTransactionsParser));
+        registry.register(Arc::new(degiro::DegiroAccountParser));
+        registry.register(Arc::new(trading212::Trading212Parser));
+        registry.register(Arc::new(fineco::FinecoParser));
+        registry.register(Arc::new(schwab::SchwabParser));
+        registry.register(Arc::new(fidelity::FidelityParser));
+        registry.register(Arc::new(vanguard::VanguardParser));
+        registry.register(Arc::new(revolut::RevolutParser));
+        registry.register(Arc::new(kraken::KrakenLedgerParser));
+        for template in contract_notes::builtin_templates() {
+            registry
+                .register_contract_note(template)
+                .expect("built-in contract note templates compile");
+        }
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_each_builtin_format() {
+        let registry = BrokerParserRegistry::default();
+        let fixtures: [(&str, &str); 9] = [
+            (
+                "degiro-transactions",
+                include_str!("fixtures/degiro_transactions.csv"),
+            ),
+            (
+                "degiro-account",
+                include_str!("fixtures/degiro_account.csv"),
+            ),
+            ("trading212", include_str!("fixtures/trading212.csv")),
+            ("fineco", include_str!("fixtures/fineco.csv")),
+            ("schwab", include_str!("fixtures/schwab.csv")),
+            ("fidelity", include_str!("fixtures/fidelity.csv")),
+            ("vanguard", include_str!("fixtures/vanguard.csv")),
+            ("revolut", include_str!("fixtures/revolut.csv")),
+            ("kraken-ledger", include_str!("fixtures/kraken_ledgers.csv")),
+        ];
+        for (expected, content) in fixtures {
+            let statement = registry.parse(content.as_bytes()).unwrap();
+            assert_eq!(statement.broker_id, expected);
+            assert!(
+                !statement.activities.is_empty(),
+                "{} parsed no rows",
+                expected
+            );
+            assert!(statement
+                .activities
+                .windows(2)
+                .all(|pair| pair[0].date <= pair[1].date));
+        }
+    }
+
+    #[test]
+    fn test_unknown_format_is_rejected() {
+        let registry = BrokerParserRegistry::default();
+        let result = registry.parse(b"foo,bar,baz\n1,2,3\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_custom_parser_can_be_registered() {
+        struct Custom;
+        impl BrokerStatementParser for Custom {
+            fn id(&self) -> &'static str {
+                "custom"
+            }
+            fn name(&self) -> &'static str {
+                "Custom"
+            }
+            fn detect(&self, headers: &[String]) -> bool {
+                common::has_columns(headers, &["foo", "bar"])
+            }
+            fn parse(&self, _headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport> {
+                rows.iter()
+                    .map(|row| common::new_row("CUSTOM", "DEPOSIT", &row[0], "USD"))
+                    .collect()
+            }
+        }
+
+        let mut registry = BrokerParserRegistry::new();
+        registry.register(Arc::new(Custom));
+        let statement = registry.parse(b"foo,bar\n2024-01-02,1\n").unwrap();
+        assert_eq!(statement.broker_id, "custom");
+        assert_eq!(statement.activities.len(), 1);
+        assert_eq!(statement.activities[0].line_number, Some(1));
+        assert!(registry.parsers().iter().any(|p| p.id == "custom"));
+    }
+}
@@ -0,0 +1,181 @@
+//! Revolut stocks account statement.
+//!
+//! Amounts carry their currency (`USD 185.50`). Commissions are not reported
+//! separately, so the fee is the gap between the total and quantity × price.
+
+use rust_decimal::Decimal;
+
+use super::common::*;
+use super::BrokerStatementParser;
+use crate::activities::activities_constants::*;
+use crate::activities::ActivityImport;
+
+const SOURCE_SYSTEM: &str = "REVOLUT";
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%d/%m/%Y"];
+
+pub(crate) struct RevolutParser;
+
+impl BrokerStatementParser for RevolutParser {
+    fn id(&self) -> &'static str {
+        "revolut"
+    }
+
+    fn name(&self) -> &'static str {
+        "Revolut"
+    }
+
+    fn detect(&self, headers: &[String]) -> bool {
+        has_columns(
+            headers,
+            &[
+                "Date",
+                "Ticker",
+                "Type",
+                "Quantity",
+                "Price per share",
+                "Total Amount",
+            ],
+        )
+    }
+
+    fn parse(&self, headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport> {
+        let columns = Columns::new(headers);
+        rows.iter()
+            .filter(|row| !columns.get(row, "Type").is_empty())
+            .map(|row| map_row(&columns, row))
+            .collect()
+    }
+}
+
+fn map_row(columns: &Columns, row: &[String]) -> ActivityImport {
+    let kind = columns.get(row, "Type").to_uppercase();
+    let total = parse_number(columns.get(row, "Total Amount"), NumberFormat::Dot);
+    let (activity_type, subtype) = if kind.starts_with("BUY") {
+        (ACTIVITY_TYPE_BUY, None)
+    } else if kind.starts_with("SELL") {
+        (ACTIVITY_TYPE_SELL, None)
+    } else if kind.starts_with("DIVIDEND TAX") {
+        (ACTIVITY_TYPE_TAX, Some(ACTIVITY_SUBTYPE_WITHHOLDING))
+    } else if kind.starts_with("DIVIDEND") {
+        (ACTIVITY_TYPE_DIVIDEND, None)
+    } else if kind == "CASH TOP-UP" {
+        (ACTIVITY_TYPE_DEPOSIT, None)
+    } else if kind == "CASH WITHDRAWAL" {
+        (ACTIVITY_TYPE_WITHDRAWAL, None)
+    } else if kind.ends_with("FEE") {
+        (ACTIVITY_TYPE_FEE, None)
+    } else if kind == "STOCK SPLIT" {
+        (ACTIVITY_TYPE_SPLIT, None)
+    } else {
+        (ACTIVITY_TYPE_UNKNOWN, None)
+    };
+
+    let mut activity = new_row(
+        SOURCE_SYSTEM,
+        activity_type,
+        "",
+        columns.get(row, "Currency"),
+    );
+    let date = columns.get(row, "Date");
+    match parse_date(date, DATE_FORMATS) {
+        Some(parsed) => activity.date = parsed,
+        None => add_error(&mut activity, "date", &format!("Invalid date '{}'", date)),
+    }
+    activity.subtype = subtype.map(str::to_string);
+    activity.symbol = columns.get(row, "Ticker").to_string();
+    activity.amount = total.map(|t| t.abs());
+
+    let quantity = parse_number(columns.get(row, "Quantity"), NumberFormat::Dot).map(|q| q.abs());
+    let price = parse_number(columns.get(row, "Price per share"), NumberFormat::Dot);
+    match activity_type {
+        ACTIVITY_TYPE_BUY | ACTIVITY_TYPE_SELL => {
+            activity.quantity = quantity;
+            activity.unit_price = price;
+            let fee = match (quantity, price, total) {
+                (Some(q), Some(p), Some(t)) if activity_type == ACTIVITY_TYPE_BUY => {
+                    t.abs() - q * p
+                }
+                (Some(q), Some(p), Some(t)) => q * p - t.abs(),
+                _ => Decimal::ZERO,
+            };
+            activity.fee = Some(fee.round_dp(2).max(Decimal::ZERO));
+        }
+        ACTIVITY_TYPE_SPLIT => {
+            activity.amount = None;
+            activity.comment = quantity.map(|q| format!("Received {} shares", q));
+            add_error(
+                &mut activity,
+                "amount",
+                "Revolut does not report the split ratio; enter it before importing",
+            );
+        }
+        ACTIVITY_TYPE_DIVIDEND | ACTIVITY_TYPE_TAX => {
+            activity.source_group_id =
+                Some(format!("revolut:{}:{}", activity.symbol, activity.date));
+        }
+        ACTIVITY_TYPE_UNKNOWN => {
+            activity.is_draft = true;
+            add_warning(
+                &mut activity,
+                "activityType",
+                &format!("Unrecognized Revolut type '{}'", columns.get(row, "Type")),
+            );
+        }
+        _ => {}
+    }
+    activity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::BrokerParserRegistry;
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn fixture() -> Vec<ActivityImport> {
+        BrokerParserRegistry::default()
+            .parse(include_bytes!("fixtures/revolut.csv"))
+            .unwrap()
+            .activities
+    }
+
+    #[test]
+    fn test_derives_fees_from_totals() {
+        let rows = fixture();
+        let buy = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_BUY)
+            .unwrap();
+        assert_eq!(buy.date, "2024-01-15");
+        assert_eq!(buy.currency, "USD");
+        assert_eq!(buy.unit_price, Some(dec!(185.50)));
+        assert_eq!(buy.fee, Some(dec!(1.49)));
+
+        let sell = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_SELL)
+            .unwrap();
+        assert_eq!(sell.quantity, Some(dec!(4)));
+        assert_eq!(sell.fee, Some(dec!(1.00)));
+    }
+
+    #[test]
+    fn test_cash_rows_and_split_without_ratio() {
+        let rows = fixture();
+        let fee = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_FEE)
+            .unwrap();
+        assert_eq!(fee.amount, Some(dec!(1.20)));
+        assert!(rows
+            .iter()
+            .any(|r| r.activity_type == ACTIVITY_TYPE_DEPOSIT && r.amount == Some(dec!(2000))));
+
+        let split = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_SPLIT)
+            .unwrap();
+        assert!(!split.is_valid);
+        assert_eq!(split.symbol, "NVDA");
+    }
+}
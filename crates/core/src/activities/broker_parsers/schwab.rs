@@ -0,0 +1,216 @@
+//! Charles Schwab brokerage transaction history export.
+//!
+//! Amounts are `$`-formatted and signed from the account's point of view;
+//! dates may carry an `as of` suffix, which is ignored.
+
+use rust_decimal::Decimal;
+
+use super::common::*;
+use super::BrokerStatementParser;
+use crate::activities::activities_constants::*;
+use crate::activities::ActivityImport;
+
+const SOURCE_SYSTEM: &str = "SCHWAB";
+const DATE_FORMATS: &[&str] = &["%m/%d/%Y", "%Y-%m-%d"];
+const CURRENCY: &str = "USD";
+
+pub(crate) struct SchwabParser;
+
+impl BrokerStatementParser for SchwabParser {
+    fn id(&self) -> &'static str {
+        "schwab"
+    }
+
+    fn name(&self) -> &'static str {
+        "Charles Schwab"
+    }
+
+    fn detect(&self, headers: &[String]) -> bool {
+        has_columns(
+            headers,
+            &[
+                "Date",
+                "Action",
+                "Symbol",
+                "Description",
+                "Fees & Comm",
+                "Amount",
+            ],
+        )
+    }
+
+    fn parse(&self, headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport> {
+        let columns = Columns::new(headers);
+        rows.iter()
+            // The trailing "Transactions Total" row has no action
+            .filter(|row| !columns.get(row, "Action").is_empty())
+            .map(|row| map_row(&columns, row))
+            .collect()
+    }
+}
+
+fn map_row(columns: &Columns, row: &[String]) -> ActivityImport {
+    let action = columns.get(row, "Action");
+    let amount = parse_number(columns.get(row, "Amount"), NumberFormat::Dot);
+    let negative = amount.is_some_and(|a| a < Decimal::ZERO);
+    let (activity_type, subtype) = classify(action, negative);
+
+    let mut activity = new_row(SOURCE_SYSTEM, activity_type, "", CURRENCY);
+    let date = columns.get(row, "Date");
+    match parse_date(date, DATE_FORMATS) {
+        Some(parsed) => activity.date = parsed,
+        None => add_error(&mut activity, "date", &format!("Invalid date '{}'", date)),
+    }
+    activity.subtype = subtype.map(str::to_string);
+    activity.symbol = columns.get(row, "Symbol").to_string();
+    activity.symbol_name = non_empty(columns.get(row, "Description"));
+    activity.comment = non_empty(action);
+    activity.amount = amount.map(|a| a.abs());
+
+    match activity_type {
+        ACTIVITY_TYPE_BUY | ACTIVITY_TYPE_SELL => {
+            activity.quantity =
+                parse_number(columns.get(row, "Quantity"), NumberFormat::Dot).map(|q| q.abs());
+            activity.unit_price = parse_number(columns.get(row, "Price"), NumberFormat::Dot);
+            activity.fee = Some(
+                parse_number(columns.get(row, "Fees & Comm"), NumberFormat::Dot)
+                    .map(|f| f.abs())
+                    .unwrap_or_default(),
+            );
+        }
+        ACTIVITY_TYPE_DIVIDEND | ACTIVITY_TYPE_TAX if !activity.symbol.is_empty() => {
+            activity.source_group_id =
+                Some(format!("schwab:{}:{}", activity.symbol, activity.date));
+        }
+        ACTIVITY_TYPE_SPLIT => {
+            // Schwab reports the shares received, not the ratio
+            activity.quantity = None;
+            activity.amount = None;
+            add_error(
+                &mut activity,
+                "amount",
+                "Schwab does not report the split ratio; enter it before importing",
+            );
+        }
+        ACTIVITY_TYPE_UNKNOWN => {
+            activity.is_draft = true;
+            add_warning(
+                &mut activity,
+                "activityType",
+                &format!("Unrecognized Schwab action '{}'", action),
+            );
+        }
+        _ => {}
+    }
+    activity
+}
+
+fn classify(action: &str, negative: bool) -> (&'static str, Option<&'static str>) {
+    match action.to_lowercase().as_str() {
+        "buy" | "reinvest shares" | "buy to open" | "buy to close" => (ACTIVITY_TYPE_BUY, None),
+        "sell" | "sell short" | "sell to open" | "sell to close" => (ACTIVITY_TYPE_SELL, None),
+        "cash dividend"
+        | "qualified dividend"
+        | "non-qualified div"
+        | "qual div reinvest"
+        | "reinvest dividend"
+        | "pr yr div reinvest"
+        | "pr yr cash div"
+        | "special dividend"
+        | "special qual div"
+        | "long term cap gain"
+        | "short term cap gain"
+        | "cash in lieu" => (ACTIVITY_TYPE_DIVIDEND, None),
+        "nra tax adj" | "nra withholding" | "foreign tax paid" | "pr yr nra tax" => {
+            if negative {
+                (ACTIVITY_TYPE_TAX, Some(ACTIVITY_SUBTYPE_WITHHOLDING))
+            } else {
+                (ACTIVITY_TYPE_CREDIT, Some(ACTIVITY_SUBTYPE_TAX_REFUND))
+            }
+        }
+        "bank interest" | "credit interest" | "bond interest" => (ACTIVITY_TYPE_INTEREST, None),
+        "margin interest" => (ACTIVITY_TYPE_FEE, Some(ACTIVITY_SUBTYPE_INTEREST_CHARGE)),
+        "service fee" | "adr mgmt fee" | "wire funds adj" | "misc cash entry" if negative => {
+            (ACTIVITY_TYPE_FEE, None)
+        }
+        "moneylink transfer" | "moneylink deposit" | "wire funds" | "wire received"
+        | "funds received" | "journal" | "journaled shares" => {
+            if negative {
+                (ACTIVITY_TYPE_WITHDRAWAL, None)
+            } else {
+                (ACTIVITY_TYPE_DEPOSIT, None)
+            }
+        }
+        "stock split" => (ACTIVITY_TYPE_SPLIT, None),
+        _ => (ACTIVITY_TYPE_UNKNOWN, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::BrokerParserRegistry;
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn fixture() -> Vec<ActivityImport> {
+        BrokerParserRegistry::default()
+            .parse(include_bytes!("fixtures/schwab.csv"))
+            .unwrap()
+            .activities
+    }
+
+    #[test]
+    fn test_maps_trades_with_dollar_amounts() {
+        let rows = fixture();
+        assert_eq!(rows.len(), 7);
+        assert!(rows.iter().all(|r| r.currency == "USD"));
+
+        let buy = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_BUY && r.symbol == "AAPL")
+            .unwrap();
+        assert_eq!(buy.date, "2024-01-15");
+        assert_eq!(buy.quantity, Some(dec!(10)));
+        assert_eq!(buy.unit_price, Some(dec!(185.50)));
+        assert_eq!(buy.fee, Some(dec!(1.00)));
+        assert_eq!(buy.amount, Some(dec!(1856.00)));
+
+        let as_of = rows.iter().find(|r| r.symbol == "MSFT").unwrap();
+        assert_eq!(as_of.date, "2024-03-15");
+        assert_eq!(as_of.fee, Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_links_nra_withholding_to_dividend() {
+        let rows = fixture();
+        let dividend = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_DIVIDEND)
+            .unwrap();
+        let tax = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_TAX)
+            .unwrap();
+        assert_eq!(dividend.amount, Some(dec!(2.50)));
+        assert_eq!(tax.amount, Some(dec!(0.38)));
+        assert_eq!(tax.subtype.as_deref(), Some(ACTIVITY_SUBTYPE_WITHHOLDING));
+        assert_eq!(
+            dividend.source_group_id.as_deref(),
+            Some("schwab:AAPL:2024-05-16")
+        );
+        assert_eq!(dividend.source_group_id, tax.source_group_id);
+    }
+
+    #[test]
+    fn test_cash_movements() {
+        let rows = fixture();
+        let deposit = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_DEPOSIT)
+            .unwrap();
+        assert_eq!(deposit.amount, Some(dec!(5000)));
+        assert!(rows
+            .iter()
+            .any(|r| r.activity_type == ACTIVITY_TYPE_INTEREST && r.amount == Some(dec!(1.23))));
+    }
+}
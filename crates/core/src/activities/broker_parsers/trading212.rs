@@ -0,0 +1,314 @@
+//! Trading 212 history export.
+//!
+//! Trades are priced in the instrument currency while totals, conversion fees
+//! and stamp duty are in the account currency; `Exchange rate` is instrument
+//! currency per account currency. Dividends are reported net, so the gross
+//! amount is rebuilt from the withholding tax, which becomes a linked TAX row.
+
+use rust_decimal::Decimal;
+use serde_json::json;
+
+use super::common::*;
+use super::BrokerStatementParser;
+use crate::activities::activities_constants::*;
+use crate::activities::ActivityImport;
+
+const SOURCE_SYSTEM: &str = "TRADING212";
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%d/%m/%Y"];
+
+pub(crate) struct Trading212Parser;
+
+impl BrokerStatementParser for Trading212Parser {
+    fn id(&self) -> &'static str {
+        "trading212"
+    }
+
+    fn name(&self) -> &'static str {
+        "Trading 212"
+    }
+
+    fn detect(&self, headers: &[String]) -> bool {
+        has_columns(
+            headers,
+            &["Action", "Time", "Ticker", "No. of shares", "Price / share"],
+        )
+    }
+
+    fn parse(&self, headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport> {
+        let columns = Columns::new(headers);
+        let fee_columns = fee_columns(headers, &columns);
+        let mut activities = Vec::new();
+        for row in rows {
+            let action = columns.get(row, "Action").to_lowercase();
+            if action.is_empty() {
+                continue;
+            }
+            if action.starts_with("dividend") {
+                activities.extend(map_dividend(&columns, row));
+            } else if action.ends_with("buy") || action.ends_with("sell") {
+                activities.push(map_trade(&columns, &fee_columns, row, &action));
+            } else if let Some(conversion) = map_conversion(&columns, row, &action) {
+                activities.push(conversion);
+            } else {
+                activities.push(map_cash(&columns, row, &action));
+            }
+        }
+        activities
+    }
+}
+
+/// Fee and transaction tax columns paired with their currency columns.
+fn fee_columns(headers: &[String], columns: &Columns) -> Vec<(usize, Option<usize>)> {
+    headers
+        .iter()
+        .enumerate()
+        .filter(|(_, header)| {
+            let name = normalize_header(header);
+            !name.starts_with("currency (")
+                && !name.contains("withholding")
+                && ["fee", "tax", "stamp duty", "levy"]
+                    .iter()
+                    .any(|word| name.contains(word))
+        })
+        .map(|(idx, header)| (idx, columns.index(&format!("Currency ({})", header.trim()))))
+        .collect()
+}
+
+fn map_trade(
+    columns: &Columns,
+    fee_columns: &[(usize, Option<usize>)],
+    row: &[String],
+    action: &str,
+) -> ActivityImport {
+    let activity_type = if action.ends_with("sell") {
+        ACTIVITY_TYPE_SELL
+    } else {
+        ACTIVITY_TYPE_BUY
+    };
+    let currency = columns.get(row, "Currency (Price / share)");
+    let mut activity = base_row(columns, row, activity_type, currency);
+    activity.quantity = parse_number(columns.get(row, "No. of shares"), NumberFormat::Dot);
+    activity.unit_price = parse_number(columns.get(row, "Price / share"), NumberFormat::Dot);
+
+    let rate = parse_number(columns.get(row, "Exchange rate"), NumberFormat::Dot);
+    let mut fee = Decimal::ZERO;
+    for (idx, currency_idx) in fee_columns {
+        let Some(amount) = parse_number(cell(row, *idx), NumberFormat::Dot) else {
+            continue;
+        };
+        let fee_currency = currency_idx.map(|i| cell(row, i)).unwrap_or("");
+        match convert_fee(amount.abs(), fee_currency, &activity.currency, rate) {
+            Some(converted) => fee += converted,
+            None => add_warning(
+                &mut activity,
+                "fee",
+                &format!(
+                    "Fee in {} could not be converted and was left out",
+                    fee_currency
+                ),
+            ),
+        }
+    }
+    activity.fee = Some(fee.round_dp(4));
+    activity
+}
+
+fn map_dividend(columns: &Columns, row: &[String]) -> Vec<ActivityImport> {
+    let currency = columns.get(row, "Currency (Total)");
+    let mut dividend = base_row(columns, row, ACTIVITY_TYPE_DIVIDEND, currency);
+    let net = parse_number(columns.get(row, "Total"), NumberFormat::Dot).unwrap_or_default();
+    dividend.source_group_id = Some(format!("t212:{}:{}", dividend.symbol, dividend.date));
+    dividend.comment = non_empty(columns.get(row, "Action"));
+
+    let withholding = parse_number(columns.get(row, "Withholding tax"), NumberFormat::Dot)
+        .map(|v| v.abs())
+        .filter(|v| !v.is_zero());
+    let Some(withholding) = withholding else {
+        dividend.amount = Some(net);
+        return vec![dividend];
+    };
+
+    // Withholding is in the instrument currency; bring it to the total's
+    let withholding_currency = columns.get(row, "Currency (Withholding tax)");
+    let rate =
+        parse_number(columns.get(row, "Exchange rate"), NumberFormat::Dot).filter(|r| !r.is_zero());
+    let withholding = if withholding_currency.is_empty()
+        || withholding_currency.eq_ignore_ascii_case(&dividend.currency)
+    {
+        Some(withholding)
+    } else {
+        rate.map(|r| (withholding / r).round_dp(4))
+    };
+    let Some(withholding) = withholding else {
+        dividend.amount = Some(net);
+        add_warning(
+            &mut dividend,
+            "amount",
+            "Withholding tax could not be converted; amount is net of tax",
+        );
+        return vec![dividend];
+    };
+
+    dividend.amount = Some(net + withholding);
+    let mut tax = base_row(columns, row, ACTIVITY_TYPE_TAX, currency);
+    tax.subtype = Some(ACTIVITY_SUBTYPE_WITHHOLDING.to_string());
+    tax.amount = Some(withholding);
+    tax.source_group_id = dividend.source_group_id.clone();
+    tax.source_record_id = tax.source_record_id.map(|id| format!("{}:tax", id));
+    vec![dividend, tax]
+}
+
+/// Currency conversions only describe both legs in `Notes`
+/// (`100.00 GBP -> 117.20 EUR`).
+fn map_conversion(columns: &Columns, row: &[String], action: &str) -> Option<ActivityImport> {
+    if action != "currency conversion" {
+        return None;
+    }
+    let notes = columns.get(row, "Notes");
+    let (from, to) = notes.split_once("->")?;
+    let leg = |text: &str| -> Option<(Decimal, String)> {
+        let (amount, currency) = text.trim().split_once(' ')?;
+        Some((
+            parse_number(amount, NumberFormat::Dot)?.abs(),
+            currency.trim().to_uppercase(),
+        ))
+    };
+    let (from_amount, from_currency) = leg(from)?;
+    let (to_amount, to_currency) = leg(to)?;
+
+    let mut activity = base_row(columns, row, ACTIVITY_TYPE_TRANSFER_OUT, &from_currency);
+    activity.subtype = Some(ACTIVITY_SUBTYPE_FX_CONVERSION.to_string());
+    activity.amount = Some(from_amount);
+    activity.metadata = Some(
+        json!({
+            "to_currency": to_currency,
+            "to_amount": to_amount,
+        })
+        .to_string(),
+    );
+    activity.comment = Some(notes.to_string());
+    Some(activity)
+}
+
+fn map_cash(columns: &Columns, row: &[String], action: &str) -> ActivityImport {
+    let (activity_type, subtype) = match action {
+        "deposit" => (ACTIVITY_TYPE_DEPOSIT, None),
+        "withdrawal" => (ACTIVITY_TYPE_WITHDRAWAL, None),
+        "interest on cash" | "lending interest" => (ACTIVITY_TYPE_INTEREST, None),
+        "spending cashback" => (ACTIVITY_TYPE_CREDIT, Some(ACTIVITY_SUBTYPE_BONUS)),
+        _ => (ACTIVITY_TYPE_UNKNOWN, None),
+    };
+    let currency = columns.get(row, "Currency (Total)");
+    let mut activity = base_row(columns, row, activity_type, currency);
+    activity.subtype = subtype.map(str::to_string);
+    activity.amount = parse_number(columns.get(row, "Total"), NumberFormat::Dot).map(|v| v.abs());
+    activity.comment = non_empty(columns.get(row, "Notes"));
+    if activity_type == ACTIVITY_TYPE_UNKNOWN {
+        activity.is_draft = true;
+        add_warning(
+            &mut activity,
+            "activityType",
+            &format!(
+                "Unrecognized Trading 212 action '{}'",
+                columns.get(row, "Action")
+            ),
+        );
+    }
+    activity
+}
+
+fn base_row(
+    columns: &Columns,
+    row: &[String],
+    activity_type: &str,
+    currency: &str,
+) -> ActivityImport {
+    let mut activity = new_row(SOURCE_SYSTEM, activity_type, "", currency);
+    let time = columns.get(row, "Time");
+    match parse_date(time, DATE_FORMATS) {
+        Some(date) => activity.date = date,
+        None => add_error(&mut activity, "date", &format!("Invalid date '{}'", time)),
+    }
+    activity.symbol = columns.get(row, "Ticker").to_string();
+    activity.symbol_name = non_empty(columns.get(row, "Name"));
+    activity.source_record_id = non_empty(columns.get(row, "ID"));
+    if let Some(isin) = non_empty(columns.get(row, "ISIN")) {
+        activity.metadata = Some(json!({ "isin": isin }).to_string());
+    }
+    activity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::BrokerParserRegistry;
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn fixture() -> Vec<ActivityImport> {
+        BrokerParserRegistry::default()
+            .parse(include_bytes!("fixtures/trading212.csv"))
+            .unwrap()
+            .activities
+    }
+
+    #[test]
+    fn test_trades_convert_account_currency_fees() {
+        let rows = fixture();
+        let buy = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_BUY)
+            .unwrap();
+        assert_eq!(buy.symbol, "AAPL");
+        assert_eq!(buy.currency, "USD");
+        assert_eq!(buy.quantity, Some(dec!(10)));
+        assert_eq!(buy.unit_price, Some(dec!(185.50)));
+        // GBP 2.19 conversion fee at 1.27 USD per GBP
+        assert_eq!(buy.fee, Some(dec!(2.7813)));
+        assert_eq!(buy.source_record_id.as_deref(), Some("EOF1234567"));
+
+        let sell = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_SELL)
+            .unwrap();
+        assert_eq!(sell.quantity, Some(dec!(4)));
+    }
+
+    #[test]
+    fn test_dividend_is_grossed_up_with_linked_withholding() {
+        let rows = fixture();
+        let dividend = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_DIVIDEND)
+            .unwrap();
+        let tax = rows
+            .iter()
+            .find(|r| r.activity_type == ACTIVITY_TYPE_TAX)
+            .unwrap();
+        assert_eq!(dividend.currency, "GBP");
+        // USD 0.36 withheld at 1.26 USD per GBP
+        assert_eq!(tax.amount, Some(dec!(0.2857)));
+        assert_eq!(dividend.amount, Some(dec!(1.9057)));
+        assert_eq!(dividend.source_group_id, tax.source_group_id);
+    }
+
+    #[test]
+    fn test_cash_actions_and_currency_conversion() {
+        let rows = fixture();
+        assert!(rows
+            .iter()
+            .any(|r| r.activity_type == ACTIVITY_TYPE_DEPOSIT && r.amount == Some(dec!(2000))));
+        assert!(rows
+            .iter()
+            .any(|r| r.activity_type == ACTIVITY_TYPE_INTEREST && r.amount == Some(dec!(1.85))));
+        let conversion = rows
+            .iter()
+            .find(|r| r.subtype.as_deref() == Some(ACTIVITY_SUBTYPE_FX_CONVERSION))
+            .unwrap();
+        assert_eq!(conversion.activity_type, ACTIVITY_TYPE_TRANSFER_OUT);
+        assert_eq!(conversion.currency, "GBP");
+        assert_eq!(conversion.amount, Some(dec!(100)));
+        let metadata: serde_json::Value =
+            serde_json::from_str(conversion.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["to_currency"], "EUR");
+    }
+}
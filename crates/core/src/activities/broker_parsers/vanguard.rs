@@ -0,0 +1,181 @@
+//! Vanguard brokerage download (`OfxDownload.csv`).
+//!
+//! The file starts with a holdings table; the transactions table follows it
+//! with its own header row, which is the one detected here. Settlement fund
+//! sweeps only move cash in and out of the money market fund and are skipped.
+
+use rust_decimal::Decimal;
+
+use super::common::*;
+use super::BrokerStatementParser;
+use crate::activities::activities_constants::*;
+use crate::activities::ActivityImport;
+
+const SOURCE_SYSTEM: &str = "VANGUARD";
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%m/%d/%Y"];
+const CURRENCY: &str = "USD";
+
+pub(crate) struct VanguardParser;
+
+impl BrokerStatementParser for VanguardParser {
+    fn id(&self) -> &'static str {
+        "vanguard"
+    }
+
+    fn name(&self) -> &'static str {
+        "Vanguard"
+    }
+
+    fn detect(&self, headers: &[String]) -> bool {
+        has_columns(
+            headers,
+            &[
+                "Trade Date",
+                "Transaction Type",
+                "Investment Name",
+                "Shares",
+                "Share Price",
+                "Net Amount",
+            ],
+        )
+    }
+
+    fn parse(&self, headers: &[String], rows: &[Vec<String>]) -> Vec<ActivityImport> {
+        let columns = Columns::new(headers);
+        rows.iter()
+            .filter_map(|row| map_row(&columns, row))
+            .collect()
+    }
+}
+
+fn map_row(columns: &Columns, row: &[String]) -> Option<ActivityImport> {
+    let transaction_type = columns.get(row, "Transaction Type");
+    let lower = transaction_type.to_lowercase();
+    if lower.is_empty() || lower.starts_with("sweep") {
+        return None;
+    }
+    let net = parse_number(columns.get(row, "Net Amount"), NumberFormat::Dot);
+    let negative = net.is_some_and(|a| a < Decimal::ZERO);
+    let (activity_type, subtype) = match lower.as_str() {
+        "buy" | "reinvestment" => (ACTIVITY_TYPE_BUY, None),
+        "sell" => (ACTIVITY_TYPE_SELL, None),
+        "dividend" | "capital gain (lt)" | "capital gain (st)" => (ACTIVITY_TYPE_DIVIDEND, None),
+        "interest" => (ACTIVITY_TYPE_INTEREST, None),
+        "fee" => (ACTIVITY_TYPE_FEE, None),
+        "withholding" | "tax withholding" | "foreign tax" => {
+            (ACTIVITY_TYPE_TAX, Some(ACTIVITY_SUBTYPE_WITHHOLDING))
+        }
+        "funds received" | "withdrawal" | "distribution" | "contribution" => {
+            if negative {
+                (ACTIVITY_TYPE_WITHDRAWAL, None)
+            } else {
+                (ACTIVITY_TYPE_DEPOSIT, None)
+            }
+        }
+        _ if lower.ends_with("(incoming)") => (ACTIVITY_TYPE_TRANSFER_IN, None),
+        _ if lower.ends_with("(outgoing)") => (ACTIVITY_TYPE_TRANSFER_OUT, None),
+        _ => (ACTIVITY_TYPE_UNKNOWN, None),
+    };
+
+    let mut activity = new_row(SOURCE_SYSTEM, activity_type, "", CURRENCY);
+    let date = columns.get(row, "Trade Date");
+    match parse_date(date, DATE_FORMATS) {
+        Some(parsed) => activity.date = parsed,
+        None => add_error(&mut activity, "date", &format!("Invalid date '{}'", date)),
+    }
+    activity.subtype = subtype.map(str::to_string);
+    activity.symbol = columns.get(row, "Symbol").to_string();
+    activity.symbol_name = non_empty(columns.get(row, "Investment Name"));
+    activity.account_name = non_empty(columns.get(row, "Account Number"));
+    activity.comment = non_empty(columns.get(row, "Transaction Description"));
+    activity.amount = net.map(|a| a.abs());
+
+    let shares = parse_number(columns.get(row, "Shares"), NumberFormat::Dot)
+        .map(|q| q.abs())
+        .filter(|q| !q.is_zero());
+    let price =
+        parse_number(columns.get(row, "Share Price"), NumberFormat::Dot).filter(|p| !p.is_zero());
+    match activity_type {
+        ACTIVITY_TYPE_BUY | ACTIVITY_TYPE_SELL => {
+            activity.quantity = shares;
+            activity.unit_price = price;
+            activity.fee = Some(
+                parse_number(columns.get(row, "Commissions and Fees"), NumberFormat::Dot)
+                    .map(|f| f.abs())
+                    .unwrap_or_default(),
+            );
+        }
+        ACTIVITY_TYPE_TRANSFER_IN | ACTIVITY_TYPE_TRANSFER_OUT if shares.is_some() => {
+            activity.quantity = shares;
+            activity.unit_price = price;
+            if price.is_none() {
+                add_warning(
+                    &mut activity,
+                    "unitPrice",
+                    "Vanguard does not report the cost basis of transferred shares",
+                );
+            }
+        }
+        ACTIVITY_TYPE_DIVIDEND | ACTIVITY_TYPE_TAX if !activity.symbol.is_empty() => {
+            activity.source_group_id =
+                Some(format!("vanguard:{}:{}", activity.symbol, activity.date));
+        }
+        ACTIVITY_TYPE_DEPOSIT | ACTIVITY_TYPE_WITHDRAWAL => {
+            // Reported against the settlement fund, but they are cash flows
+            activity.symbol = String::new();
+            activity.symbol_name = None;
+        }
+        ACTIVITY_TYPE_UNKNOWN => {
+            activity.is_draft = true;
+            add_warning(
+                &mut activity,
+                "activityType",
+                &format!(
+                    "Unrecognized Vanguard transaction type '{}'",
+                    transaction_type
+                ),
+            );
+        }
+        _ => {}
+    }
+    Some(activity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::BrokerParserRegistry;
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_reads_transactions_after_holdings_section() {
+        let statement = BrokerParserRegistry::default()
+            .parse(include_bytes!("fixtures/vanguard.csv"))
+            .unwrap();
+        assert_eq!(statement.broker_id, "vanguard");
+        let rows = statement.activities;
+        let types: Vec<&str> = rows.iter().map(|r| r.activity_type.as_str()).collect();
+        assert_eq!(
+            types,
+            vec![
+                ACTIVITY_TYPE_DEPOSIT,
+                ACTIVITY_TYPE_BUY,
+                ACTIVITY_TYPE_DIVIDEND,
+                ACTIVITY_TYPE_BUY,
+                ACTIVITY_TYPE_SELL,
+            ]
+        );
+
+        let buy = &rows[1];
+        assert_eq!(buy.date, "2024-01-16");
+        assert_eq!(buy.symbol, "VTI");
+        assert_eq!(buy.quantity, Some(dec!(5)));
+        assert_eq!(buy.unit_price, Some(dec!(235.00)));
+        assert_eq!(buy.account_name.as_deref(), Some("12345678"));
+
+        let reinvest = &rows[3];
+        assert_eq!(reinvest.quantity, Some(dec!(0.0185)));
+        assert_eq!(rows[2].amount, Some(dec!(4.50)));
+        assert_eq!(rows[0].symbol, "");
+    }
+}
@@ -0,0 +1,308 @@
+//! Query-driven bulk editing of activities.
+//!
+//! `bulk_mutate_activities` needs every id spelled out. Fixing a misconfigured
+//! import means editing thousands of rows that share an account, a date range
+//! or a note, so edits here select activities with a query instead and can be
+//! previewed, with the holdings change they would cause, before being applied.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+use super::activities_model::Activity;
+use crate::portfolio::snapshot::AccountStateSnapshot;
+
+/// Selects the activities a bulk edit applies to. Criteria are combined with AND;
+/// list criteria match any of their values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityQuery {
+    pub account_ids: Option<Vec<String>>,
+    pub asset_ids: Option<Vec<String>>,
+    /// Matched against the effective type, respecting user overrides
+    pub activity_types: Option<Vec<String>>,
+    pub date_from: Option<NaiveDate>,
+    pub date_to: Option<NaiveDate>,
+    /// Case-insensitive substring of the notes
+    pub notes_contains: Option<String>,
+}
+
+impl ActivityQuery {
+    /// True when no criterion is set, i.e. the query would select every activity.
+    pub fn is_empty(&self) -> bool {
+        let is_blank = |values: &Option<Vec<String>>| values.as_ref().is_none_or(Vec::is_empty);
+        is_blank(&self.account_ids)
+            && is_blank(&self.asset_ids)
+            && is_blank(&self.activity_types)
+            && self.date_from.is_none()
+            && self.date_to.is_none()
+            && self
+                .notes_contains
+                .as_deref()
+                .is_none_or(|n| n.trim().is_empty())
+    }
+
+    pub fn matches(&self, activity: &Activity) -> bool {
+        let in_list = |values: &Option<Vec<String>>, value: Option<&str>| match values {
+            Some(values) if !values.is_empty() => {
+                value.is_some_and(|v| values.iter().any(|candidate| candidate == v))
+            }
+            _ => true,
+        };
+        let date = activity.effective_date();
+
+        in_list(&self.account_ids, Some(&activity.account_id))
+            && in_list(&self.asset_ids, activity.asset_id.as_deref())
+            && in_list(&self.activity_types, Some(activity.effective_type()))
+            && self.date_from.is_none_or(|from| date >= from)
+            && self.date_to.is_none_or(|to| date <= to)
+            && match self.notes_contains.as_deref().map(str::trim) {
+                Some(needle) if !needle.is_empty() => activity
+                    .notes
+                    .as_deref()
+                    .is_some_and(|notes| notes.to_lowercase().contains(&needle.to_lowercase())),
+                _ => true,
+            }
+    }
+}
+
+/// Changes applied to every selected activity. Unset fields are left as they are.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityBulkEdit {
+    /// Moves the activities to another account
+    pub account_id: Option<String>,
+    /// New type; replaces any user override
+    pub activity_type: Option<String>,
+    /// `null` clears the subtype
+    #[serde(
+        default,
+        with = "serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub subtype: Option<Option<String>>,
+    /// Currency of the whole activity. Activities carry a single currency, so
+    /// this re-denominates the unit price, amount and fee together: their values
+    /// are kept and read in the new currency.
+    pub activity_currency: Option<String>,
+    /// Sets `metadata.flow.is_external`, marking transfers and credits as
+    /// flows in or out of the tracked portfolio
+    pub is_external_flow: Option<bool>,
+}
+
+impl ActivityBulkEdit {
+    pub fn is_empty(&self) -> bool {
+        self.account_id.is_none()
+            && self.activity_type.is_none()
+            && self.subtype.is_none()
+            && self.activity_currency.is_none()
+            && self.is_external_flow.is_none()
+    }
+
+    /// The activity as it would be after the edit.
+    pub fn apply(&self, activity: &Activity) -> Activity {
+        let mut edited = activity.clone();
+        if let Some(account_id) = &self.account_id {
+            edited.account_id = account_id.clone();
+        }
+        if let Some(activity_type) = &self.activity_type {
+            edited.activity_type = activity_type.clone();
+            edited.activity_type_override = None;
+        }
+        if let Some(subtype) = &self.subtype {
+            edited.subtype = subtype.clone();
+        }
+        if let Some(currency) = &self.activity_currency {
+            edited.currency = currency.clone();
+        }
+        if let Some(is_external) = self.is_external_flow {
+            edited.metadata = Some(set_external_flow(edited.metadata.take(), is_external));
+        }
+        edited
+    }
+}
+
+/// Sets `flow.is_external` in an activity's metadata, keeping everything else.
+fn set_external_flow(metadata: Option<Value>, is_external: bool) -> Value {
+    let mut metadata = match metadata {
+        Some(Value::Object(map)) => Value::Object(map),
+        _ => json!({}),
+    };
+    let flow = metadata
+        .as_object_mut()
+        .map(|m| m.entry("flow").or_insert_with(|| json!({})));
+    match flow {
+        Some(Value::Object(flow)) => {
+            flow.insert("is_external".to_string(), Value::Bool(is_external));
+        }
+        Some(flow) => *flow = json!({ "is_external": is_external }),
+        None => {}
+    }
+    metadata
+}
+
+/// A query-driven bulk edit, optionally only previewed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityBulkEditRequest {
+    pub query: ActivityQuery,
+    pub edit: ActivityBulkEdit,
+    /// Report what would change without writing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// One activity the edit changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityBulkEditChange {
+    pub activity_id: String,
+    /// Fields whose value or meaning changes; a currency change also lists the
+    /// unit price, amount and fee it re-denominates
+    pub changed_fields: Vec<String>,
+    pub before: Activity,
+    pub after: Activity,
+}
+
+/// Change of one holding caused by the edit, from replaying the account's
+/// history through the holdings calculator with and without it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityBulkEditHoldingChange {
+    pub account_id: String,
+    /// Position asset; `None` for a cash balance
+    pub asset_id: Option<String>,
+    /// Cash currency; `None` for a position
+    pub currency: Option<String>,
+    /// Quantity (or cash balance) before the edit
+    pub before: Decimal,
+    /// Quantity (or cash balance) after the edit
+    pub after: Decimal,
+    pub change: Decimal,
+}
+
+/// Outcome of a bulk edit, or its preview for a dry run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityBulkEditResult {
+    /// Number of activities selected by the query
+    pub matched: usize,
+    /// Selected activities the edit changes; those already matching it are left out
+    pub changes: Vec<ActivityBulkEditChange>,
+    pub holding_changes: Vec<ActivityBulkEditHoldingChange>,
+    /// False for a dry run
+    pub applied: bool,
+}
+
+/// Names of the fields an edit changed on an activity.
+pub fn edited_fields(before: &Activity, after: &Activity) -> Vec<String> {
+    let mut fields = Vec::new();
+    if before.account_id != after.account_id {
+        fields.push("accountId");
+    }
+    if before.activity_type != after.activity_type
+        || before.activity_type_override != after.activity_type_override
+    {
+        fields.push("activityType");
+    }
+    if before.subtype != after.subtype {
+        fields.push("subtype");
+    }
+    if before.currency != after.currency {
+        fields.push("currency");
+        let amounts = [
+            ("unitPrice", after.unit_price),
+            ("amount", after.amount),
+            ("fee", after.fee),
+        ];
+        fields.extend(
+            amounts
+                .into_iter()
+                .filter(|(_, value)| value.is_some_and(|v| !v.is_zero()))
+                .map(|(name, _)| name),
+        );
+    }
+    if before.metadata != after.metadata {
+        fields.push("metadata");
+    }
+    fields.into_iter().map(str::to_string).collect()
+}
+
+/// A position (asset) or cash balance (currency) within an account.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum HoldingKey {
+    Position {
+        account_id: String,
+        asset_id: String,
+    },
+    Cash {
+        account_id: String,
+        currency: String,
+    },
+}
+
+/// Position quantities and cash balances of an account's holdings.
+fn holding_balances(snapshot: &AccountStateSnapshot) -> Vec<(HoldingKey, Decimal)> {
+    let positions = snapshot.positions.values().map(|position| {
+        (
+            HoldingKey::Position {
+                account_id: snapshot.account_id.clone(),
+                asset_id: position.asset_id.clone(),
+            },
+            position.quantity,
+        )
+    });
+    let cash = snapshot.cash_balances.iter().map(|(currency, amount)| {
+        (
+            HoldingKey::Cash {
+                account_id: snapshot.account_id.clone(),
+                currency: currency.clone(),
+            },
+            *amount,
+        )
+    });
+    positions.chain(cash).collect()
+}
+
+/// Holdings moved by an edit, given the replayed holdings of the affected
+/// accounts before and after it, ordered by account then holding. Holdings the
+/// edit leaves unchanged are left out.
+pub fn holding_changes(
+    before: &[AccountStateSnapshot],
+    after: &[AccountStateSnapshot],
+) -> Vec<ActivityBulkEditHoldingChange> {
+    let mut totals: BTreeMap<HoldingKey, (Decimal, Decimal)> = BTreeMap::new();
+    for (key, amount) in before.iter().flat_map(holding_balances) {
+        totals.entry(key).or_default().0 += amount;
+    }
+    for (key, amount) in after.iter().flat_map(holding_balances) {
+        totals.entry(key).or_default().1 += amount;
+    }
+
+    totals
+        .into_iter()
+        .filter(|(_, (before, after))| before != after)
+        .map(|(key, (before, after))| {
+            let (account_id, asset_id, currency) = match key {
+                HoldingKey::Position {
+                    account_id,
+                    asset_id,
+                } => (account_id, Some(asset_id), None),
+                HoldingKey::Cash {
+                    account_id,
+                    currency,
+                } => (account_id, None, Some(currency)),
+            };
+            ActivityBulkEditHoldingChange {
+                account_id,
+                asset_id,
+                currency,
+                before,
+                after,
+                change: after - before,
+            }
+        })
+        .collect()
+}
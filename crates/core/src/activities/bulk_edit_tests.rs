@@ -0,0 +1,203 @@
+//! Tests for query-driven bulk editing of activities.
+
+#[cfg(test)]
+mod tests {
+    use crate::activities::{
+        edited_fields, holding_changes, Activity, ActivityBulkEdit, ActivityBulkEditRequest,
+        ActivityQuery, ActivityStatus, ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_TRANSFER_IN,
+    };
+    use crate::portfolio::snapshot::{AccountStateSnapshot, Position};
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn activity(id: &str, account_id: &str, activity_type: &str, day: u32) -> Activity {
+        let date = NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+        Activity {
+            id: id.to_string(),
+            account_id: account_id.to_string(),
+            asset_id: None,
+            activity_type: activity_type.to_string(),
+            activity_type_override: None,
+            source_type: None,
+            subtype: None,
+            status: ActivityStatus::Posted,
+            activity_date: Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()),
+            settlement_date: None,
+            quantity: None,
+            unit_price: None,
+            amount: None,
+            fee: None,
+            currency: "USD".to_string(),
+            fx_rate: None,
+            notes: None,
+            metadata: None,
+            source_system: Some("CSV".to_string()),
+            source_record_id: None,
+            source_group_id: None,
+            idempotency_key: None,
+            import_run_id: None,
+            is_user_modified: false,
+            needs_review: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_query_combines_criteria() {
+        let mut deposit = activity("a1", "acc_1", ACTIVITY_TYPE_DEPOSIT, 10);
+        deposit.notes = Some("Imported from Broker X".to_string());
+        let other_account = activity("a2", "acc_2", ACTIVITY_TYPE_DEPOSIT, 10);
+        let too_late = activity("a3", "acc_1", ACTIVITY_TYPE_DEPOSIT, 25);
+
+        let query = ActivityQuery {
+            account_ids: Some(vec!["acc_1".to_string()]),
+            activity_types: Some(vec![ACTIVITY_TYPE_DEPOSIT.to_string()]),
+            date_to: NaiveDate::from_ymd_opt(2024, 3, 20),
+            notes_contains: Some("broker x".to_string()),
+            ..Default::default()
+        };
+
+        assert!(!query.is_empty());
+        assert!(query.matches(&deposit));
+        assert!(!query.matches(&other_account));
+        assert!(!query.matches(&too_late));
+        assert!(ActivityQuery::default().is_empty());
+    }
+
+    #[test]
+    fn test_apply_replaces_type_override_and_keeps_metadata() {
+        let mut transfer = activity("a1", "acc_1", ACTIVITY_TYPE_DEPOSIT, 10);
+        transfer.activity_type_override = Some(ACTIVITY_TYPE_DEPOSIT.to_string());
+        transfer.subtype = Some("BONUS".to_string());
+        transfer.metadata = Some(json!({ "source": "csv" }));
+
+        let edit = ActivityBulkEdit {
+            activity_type: Some(ACTIVITY_TYPE_TRANSFER_IN.to_string()),
+            subtype: Some(None),
+            is_external_flow: Some(true),
+            ..Default::default()
+        };
+        let edited = edit.apply(&transfer);
+
+        assert_eq!(edited.effective_type(), ACTIVITY_TYPE_TRANSFER_IN);
+        assert_eq!(edited.subtype, None);
+        assert_eq!(
+            edited.metadata,
+            Some(json!({ "source": "csv", "flow": { "is_external": true } }))
+        );
+        assert_eq!(
+            edited_fields(&transfer, &edited),
+            vec!["activityType", "subtype", "metadata"]
+        );
+        assert!(edited_fields(&edited, &edit.apply(&edited)).is_empty());
+    }
+
+    #[test]
+    fn test_currency_change_lists_redenominated_amounts() {
+        let mut buy = activity("a1", "acc_1", "BUY", 10);
+        buy.quantity = Some(dec!(10));
+        buy.unit_price = Some(dec!(100));
+        buy.amount = Some(dec!(1000));
+        buy.fee = Some(Decimal::ZERO);
+
+        let edit = ActivityBulkEdit {
+            activity_currency: Some("EUR".to_string()),
+            ..Default::default()
+        };
+        let edited = edit.apply(&buy);
+
+        assert_eq!(edited.currency, "EUR");
+        assert_eq!(edited.unit_price, Some(dec!(100)));
+        assert_eq!(
+            edited_fields(&buy, &edited),
+            vec!["currency", "unitPrice", "amount"]
+        );
+    }
+
+    fn holdings(
+        account_id: &str,
+        positions: &[(&str, Decimal)],
+        cash: Decimal,
+    ) -> AccountStateSnapshot {
+        AccountStateSnapshot {
+            account_id: account_id.to_string(),
+            currency: "USD".to_string(),
+            positions: positions
+                .iter()
+                .map(|(asset_id, quantity)| {
+                    let position = Position {
+                        asset_id: asset_id.to_string(),
+                        quantity: *quantity,
+                        ..Default::default()
+                    };
+                    (asset_id.to_string(), position)
+                })
+                .collect(),
+            cash_balances: HashMap::from([("USD".to_string(), cash)]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_holding_changes_when_reassigning_account() {
+        let before = [
+            holdings("acc_1", &[("SEC:AAPL:XNAS", dec!(10))], dec!(-1001)),
+            holdings("acc_2", &[], dec!(500)),
+        ];
+        let after = [
+            holdings("acc_1", &[], Decimal::ZERO),
+            holdings("acc_2", &[("SEC:AAPL:XNAS", dec!(10))], dec!(-501)),
+        ];
+
+        let changes = holding_changes(&before, &after);
+
+        let find = |account_id: &str, asset_id: Option<&str>| {
+            changes
+                .iter()
+                .find(|c| c.account_id == account_id && c.asset_id.as_deref() == asset_id)
+                .unwrap()
+        };
+        assert_eq!(changes.len(), 4);
+        assert_eq!(find("acc_1", Some("SEC:AAPL:XNAS")).change, dec!(-10));
+        assert_eq!(find("acc_2", Some("SEC:AAPL:XNAS")).change, dec!(10));
+        assert_eq!(find("acc_1", None).change, dec!(1001));
+        assert_eq!(find("acc_2", None).before, dec!(500));
+        assert_eq!(find("acc_2", None).after, dec!(-501));
+    }
+
+    #[test]
+    fn test_holding_changes_skip_unaffected_holdings() {
+        let before = [holdings("acc_1", &[("SEC:AAPL:XNAS", dec!(10))], dec!(500))];
+        let after = [holdings("acc_1", &[("SEC:AAPL:XNAS", dec!(10))], dec!(400))];
+
+        let changes = holding_changes(&before, &after);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].currency.as_deref(), Some("USD"));
+        assert_eq!(changes[0].change, dec!(-100));
+    }
+
+    #[test]
+    fn test_request_deserializes_subtype_patch() {
+        let clear: ActivityBulkEditRequest = serde_json::from_value(json!({
+            "query": { "accountIds": ["acc_1"] },
+            "edit": { "subtype": null },
+            "dryRun": true
+        }))
+        .unwrap();
+        assert_eq!(clear.edit.subtype, Some(None));
+        assert!(clear.dry_run);
+
+        let untouched: ActivityBulkEditRequest = serde_json::from_value(json!({
+            "query": { "accountIds": ["acc_1"] },
+            "edit": { "activityCurrency": "EUR" }
+        }))
+        .unwrap();
+        assert_eq!(untouched.edit.subtype, None);
+        assert!(!untouched.dry_run);
+    }
+}
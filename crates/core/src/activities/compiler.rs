@@ -7,9 +7,12 @@
 //! - Stable calculator that only understands primitives
 
 use crate::activities::activities_constants::*;
+use crate::activities::activities_model::decimal_from_json;
 use crate::activities::Activity;
 use crate::Result;
+use log::warn;
 use rust_decimal::Decimal;
+use serde_json::Value;
 
 /// Compiles a stored activity (event) into canonical postings for the calculator.
 ///
@@ -51,16 +54,35 @@ impl ActivityCompiler for DefaultActivityCompiler {
                 Ok(self.compile_drip(activity))
             }
 
-            // Staking Reward: Interest + Buy
-            (ACTIVITY_TYPE_INTEREST, Some(ACTIVITY_SUBTYPE_STAKING_REWARD)) => {
-                Ok(self.compile_staking_reward(activity))
-            }
+            // Staking Reward / Airdrop: Interest + Buy
+            (
+                ACTIVITY_TYPE_INTEREST,
+                Some(ACTIVITY_SUBTYPE_STAKING_REWARD | ACTIVITY_SUBTYPE_AIRDROP),
+            ) => Ok(self.compile_staking_reward(activity)),
 
             // Dividend in Kind: Dividend + Add Holding (different asset)
             (ACTIVITY_TYPE_DIVIDEND, Some(ACTIVITY_SUBTYPE_DIVIDEND_IN_KIND)) => {
                 Ok(self.compile_dividend_in_kind(activity))
             }
 
+            // Hard Fork: cost basis split from the parent coin
+            (ACTIVITY_TYPE_ADJUSTMENT, Some(ACTIVITY_SUBTYPE_HARD_FORK)) => {
+                Ok(self.compile_hard_fork(activity))
+            }
+
+            // Swap: Sell + Buy
+            (ACTIVITY_TYPE_SELL, Some(ACTIVITY_SUBTYPE_SWAP)) => Ok(self.compile_swap(activity)),
+
+            // Network Fee in kind: Sell + Fee
+            (ACTIVITY_TYPE_FEE, Some(ACTIVITY_SUBTYPE_NETWORK_FEE)) => {
+                Ok(self.compile_network_fee(activity))
+            }
+
+            // FX Conversion: cash exchanged between currencies
+            (ACTIVITY_TYPE_TRANSFER_OUT, Some(ACTIVITY_SUBTYPE_FX_CONVERSION)) => {
+                Ok(self.compile_fx_conversion(activity))
+            }
+
             // Default: Pass through unchanged
             _ => Ok(vec![activity.clone()]),
         }
@@ -108,10 +130,10 @@ impl DefaultActivityCompiler {
         vec![dividend_leg, buy_leg]
     }
 
-    /// Staking Reward: One stored row → INTEREST + BUY
+    /// Staking Reward / Airdrop: One stored row → INTEREST + BUY
     ///
     /// Stored:
-    ///   activity_type = INTEREST, subtype = STAKING_REWARD
+    ///   activity_type = INTEREST, subtype = STAKING_REWARD | AIRDROP
     ///   asset_id = rewarded token
     ///   quantity = reward quantity
     ///   unit_price = FMV at receipt
@@ -184,6 +206,250 @@ impl DefaultActivityCompiler {
 
         vec![dividend_leg, transfer_in_leg]
     }
+
+    /// Hard Fork: Coins of a new chain received from a chain split
+    ///
+    /// Stored:
+    ///   activity_type = ADJUSTMENT, subtype = HARD_FORK
+    ///   asset_id = the parent coin
+    ///   metadata.received_asset_id = the forked coin
+    ///   metadata.cost_basis_ratio = share of the parent's cost basis moved (0..1, default 0)
+    ///   quantity = forked coins received
+    ///
+    /// Compiled:
+    ///   1. ADJUSTMENT (HARD_FORK): the calculator moves the share of the parent's
+    ///      lot cost basis to a new lot of the forked coin. Only the calculator knows
+    ///      the parent's lots, so the split itself cannot be expressed as BUY/SELL legs.
+    ///
+    /// Net cash effect: 0, no contribution
+    fn compile_hard_fork(&self, activity: &Activity) -> Vec<Activity> {
+        let Some(received_asset_id) = activity.get_meta::<String>("received_asset_id") else {
+            warn!(
+                "Hard fork {} has no received_asset_id in metadata. Skipping.",
+                activity.id
+            );
+            return vec![];
+        };
+        let ratio = activity
+            .get_meta_decimal("cost_basis_ratio")
+            .unwrap_or(Decimal::ZERO)
+            .clamp(Decimal::ZERO, Decimal::ONE);
+
+        let mut fork_leg = activity.clone();
+        fork_leg.id = format!("{}:fork", activity.id);
+        fork_leg.activity_type = ACTIVITY_TYPE_ADJUSTMENT.to_string();
+        fork_leg.activity_type_override = None;
+        fork_leg.unit_price = None;
+        fork_leg.amount = None;
+        fork_leg.fee = Some(Decimal::ZERO);
+        fork_leg.metadata = Some(serde_json::json!({
+            "received_asset_id": received_asset_id,
+            "cost_basis_ratio": ratio,
+        }));
+
+        vec![fork_leg]
+    }
+
+    /// Swap: Crypto-to-crypto trade → SELL + BUY
+    ///
+    /// Stored:
+    ///   activity_type = SELL, subtype = SWAP
+    ///   asset_id = coin given up
+    ///   quantity = quantity given up
+    ///   unit_price = FMV of the coin given up
+    ///   fee = trading/network fee in the activity currency
+    ///   metadata.received_asset_id = coin received
+    ///   metadata.received_quantity = quantity received
+    ///
+    /// Compiled:
+    ///   1. SELL: disposal of the given coin at FMV (realizes gain/loss, carries the fee)
+    ///   2. BUY: acquisition of the received coin, cost = FMV of the coin given up
+    ///
+    /// Net cash effect: -fee
+    fn compile_swap(&self, activity: &Activity) -> Vec<Activity> {
+        let received_asset_id = activity.get_meta::<String>("received_asset_id");
+        let received_quantity = activity
+            .get_meta_decimal("received_quantity")
+            .filter(|q| q.is_sign_positive() && !q.is_zero());
+        let (Some(received_asset_id), Some(received_quantity)) =
+            (received_asset_id, received_quantity)
+        else {
+            warn!(
+                "Swap {} is missing received_asset_id or received_quantity. Compiling as SELL only.",
+                activity.id
+            );
+            let mut sell_leg = activity.clone();
+            sell_leg.subtype = None;
+            return vec![sell_leg];
+        };
+
+        let proceeds = activity.qty() * activity.price();
+
+        // Leg 1: SELL (disposal at FMV)
+        let mut sell_leg = activity.clone();
+        sell_leg.id = format!("{}:sell", activity.id);
+        sell_leg.activity_type = ACTIVITY_TYPE_SELL.to_string();
+        sell_leg.activity_type_override = None;
+        sell_leg.subtype = None;
+        sell_leg.amount = None;
+
+        // Leg 2: BUY (acquisition of the received coin)
+        let mut buy_leg = activity.clone();
+        buy_leg.id = format!("{}:buy", activity.id);
+        buy_leg.activity_type = ACTIVITY_TYPE_BUY.to_string();
+        buy_leg.activity_type_override = None;
+        buy_leg.subtype = None;
+        buy_leg.asset_id = Some(received_asset_id);
+        buy_leg.quantity = Some(received_quantity);
+        buy_leg.unit_price = Some(proceeds / received_quantity);
+        buy_leg.amount = None;
+        buy_leg.fee = Some(Decimal::ZERO); // Fee already in sell leg
+        buy_leg.metadata = None;
+
+        vec![sell_leg, buy_leg]
+    }
+
+    /// Network Fee: Fee paid in kind → SELL + FEE
+    ///
+    /// Stored:
+    ///   activity_type = FEE, subtype = NETWORK_FEE
+    ///   asset_id = coin spent on the fee
+    ///   quantity = coins spent
+    ///   unit_price = FMV at payment
+    ///
+    /// Compiled:
+    ///   1. SELL: disposal of the coins at FMV (reduces the quantity held)
+    ///   2. FEE: charge of the same value
+    ///
+    /// Net cash effect: 0
+    fn compile_network_fee(&self, activity: &Activity) -> Vec<Activity> {
+        if activity.asset_id.as_deref().unwrap_or("").is_empty() || activity.qty().is_zero() {
+            // No coin to reduce: a regular cash fee
+            let mut fee_leg = activity.clone();
+            fee_leg.subtype = None;
+            return vec![fee_leg];
+        }
+
+        let value = activity.qty() * activity.price();
+
+        // Leg 1: SELL (disposal of the coins)
+        let mut sell_leg = activity.clone();
+        sell_leg.id = format!("{}:sell", activity.id);
+        sell_leg.activity_type = ACTIVITY_TYPE_SELL.to_string();
+        sell_leg.activity_type_override = None;
+        sell_leg.subtype = None;
+        sell_leg.amount = None;
+        sell_leg.fee = Some(Decimal::ZERO);
+
+        if value.is_zero() {
+            return vec![sell_leg];
+        }
+
+        // Leg 2: FEE (expense recognition)
+        let mut fee_leg = activity.clone();
+        fee_leg.id = format!("{}:fee", activity.id);
+        fee_leg.activity_type = ACTIVITY_TYPE_FEE.to_string();
+        fee_leg.activity_type_override = None;
+        fee_leg.subtype = None;
+        fee_leg.quantity = None;
+        fee_leg.unit_price = None;
+        fee_leg.amount = Some(value);
+        fee_leg.fee = None;
+
+        vec![sell_leg, fee_leg]
+    }
+
+    /// FX Conversion: Cash exchanged between two currencies inside the account
+    ///
+    /// Stored:
+    ///   activity_type = TRANSFER_OUT, subtype = FX_CONVERSION
+    ///   currency / amount = currency and amount sold
+    ///   fee = conversion fee in the sold currency
+    ///   metadata.to_currency = currency bought
+    ///   metadata.to_amount = amount bought (or metadata.rate, bought per sold)
+    ///
+    /// Compiled:
+    ///   1. TRANSFER_OUT (FX_CONVERSION): the calculator books both cash legs.
+    ///      Metadata is normalized to to_currency, to_amount and rate.
+    ///      Without to_currency or to_amount/rate only the amount sold is booked
+    ///      and the posting is flagged for review.
+    ///
+    /// Net cash effect: -(amount + fee) sold currency, +to_amount bought currency;
+    /// no contribution
+    fn compile_fx_conversion(&self, activity: &Activity) -> Vec<Activity> {
+        let sold = activity.amt();
+        if sold <= Decimal::ZERO {
+            warn!(
+                "FX conversion {} has no amount sold. Skipping.",
+                activity.id
+            );
+            return vec![];
+        }
+        if activity
+            .get_meta::<String>("to_currency")
+            .is_some_and(|c| c.trim().eq_ignore_ascii_case(&activity.currency))
+        {
+            warn!(
+                "FX conversion {} converts into its own currency. Skipping.",
+                activity.id
+            );
+            return vec![];
+        }
+
+        let mut conversion_leg = activity.clone();
+        conversion_leg.id = format!("{}:fx", activity.id);
+        conversion_leg.activity_type = ACTIVITY_TYPE_TRANSFER_OUT.to_string();
+        conversion_leg.activity_type_override = None;
+        conversion_leg.asset_id = None;
+        conversion_leg.quantity = None;
+        conversion_leg.unit_price = None;
+        match fx_conversion_target(&activity.currency, sold, activity.metadata.as_ref()) {
+            Some((to_currency, to_amount)) => {
+                conversion_leg.metadata = Some(serde_json::json!({
+                    "to_currency": to_currency,
+                    "to_amount": to_amount,
+                    "rate": to_amount / sold,
+                }));
+            }
+            None => {
+                warn!(
+                    "FX conversion {} is missing to_currency or to_amount/rate. \
+                     Booking only the amount sold.",
+                    activity.id
+                );
+                conversion_leg.metadata = None;
+                conversion_leg.needs_review = true;
+            }
+        }
+
+        vec![conversion_leg]
+    }
+}
+
+/// Currency and amount bought by an `FX_CONVERSION` selling `sold` of
+/// `currency`: `to_currency` with `to_amount`, or `rate` per unit sold.
+pub(crate) fn fx_conversion_target(
+    currency: &str,
+    sold: Decimal,
+    metadata: Option<&Value>,
+) -> Option<(String, Decimal)> {
+    let metadata = metadata?;
+    let to_currency = metadata
+        .get("to_currency")?
+        .as_str()
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty() && c != currency)?;
+    let to_amount = metadata
+        .get("to_amount")
+        .and_then(decimal_from_json)
+        .or_else(|| {
+            metadata
+                .get("rate")
+                .and_then(decimal_from_json)
+                .map(|rate| sold * rate)
+        })
+        .filter(|a| a.is_sign_positive() && !a.is_zero())?;
+    Some((to_currency, to_amount))
 }
 
 impl Default for DefaultActivityCompiler {
@@ -415,6 +681,250 @@ mod tests {
         assert_eq!(result[1].asset_id, Some("AAPL".to_string()));
     }
 
+    #[test]
+    fn test_compile_airdrop_produces_interest_and_buy() {
+        let compiler = DefaultActivityCompiler::new();
+        let mut activity = create_test_activity();
+        activity.activity_type = ACTIVITY_TYPE_INTEREST.to_string();
+        activity.subtype = Some(ACTIVITY_SUBTYPE_AIRDROP.to_string());
+        activity.asset_id = Some("UNI".to_string());
+        activity.quantity = Some(dec!(400));
+        activity.unit_price = Some(dec!(3));
+        activity.amount = Some(dec!(1200));
+
+        let result = compiler.compile(&activity).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].activity_type, ACTIVITY_TYPE_INTEREST);
+        assert_eq!(result[0].amount, Some(dec!(1200)));
+        assert_eq!(result[1].activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(result[1].asset_id, Some("UNI".to_string()));
+        assert_eq!(result[1].quantity, Some(dec!(400)));
+        assert_eq!(result[1].unit_price, Some(dec!(3)));
+    }
+
+    #[test]
+    fn test_compile_hard_fork_normalizes_metadata() {
+        let compiler = DefaultActivityCompiler::new();
+        let mut activity = create_test_activity();
+        activity.activity_type = ACTIVITY_TYPE_ADJUSTMENT.to_string();
+        activity.subtype = Some(ACTIVITY_SUBTYPE_HARD_FORK.to_string());
+        activity.asset_id = Some("BTC".to_string());
+        activity.quantity = Some(dec!(2));
+        activity.metadata = Some(serde_json::json!({
+            "received_asset_id": "BCH",
+            "cost_basis_ratio": 1.5,
+            "note": "ignored"
+        }));
+
+        let result = compiler.compile(&activity).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "test-1:fork");
+        assert_eq!(result[0].activity_type, ACTIVITY_TYPE_ADJUSTMENT);
+        assert_eq!(
+            result[0].subtype.as_deref(),
+            Some(ACTIVITY_SUBTYPE_HARD_FORK)
+        );
+        assert_eq!(result[0].asset_id, Some("BTC".to_string()));
+        assert_eq!(result[0].quantity, Some(dec!(2)));
+        assert_eq!(
+            result[0].get_meta::<String>("received_asset_id"),
+            Some("BCH".to_string())
+        );
+        // Ratio is clamped to the parent's full cost basis
+        assert_eq!(
+            result[0].get_meta_decimal("cost_basis_ratio"),
+            Some(dec!(1))
+        );
+        assert!(result[0].get_meta::<String>("note").is_none());
+    }
+
+    #[test]
+    fn test_compile_hard_fork_without_received_asset_is_skipped() {
+        let compiler = DefaultActivityCompiler::new();
+        let mut activity = create_test_activity();
+        activity.activity_type = ACTIVITY_TYPE_ADJUSTMENT.to_string();
+        activity.subtype = Some(ACTIVITY_SUBTYPE_HARD_FORK.to_string());
+
+        let result = compiler.compile(&activity).unwrap();
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_compile_swap_produces_sell_and_buy_at_fmv() {
+        let compiler = DefaultActivityCompiler::new();
+        let mut activity = create_test_activity();
+        activity.activity_type = ACTIVITY_TYPE_SELL.to_string();
+        activity.subtype = Some(ACTIVITY_SUBTYPE_SWAP.to_string());
+        activity.asset_id = Some("ETH".to_string());
+        activity.quantity = Some(dec!(1)); // ETH given up
+        activity.unit_price = Some(dec!(3000)); // ETH FMV
+        activity.amount = None;
+        activity.fee = Some(dec!(5));
+        activity.metadata = Some(serde_json::json!({
+            "received_asset_id": "SOL",
+            "received_quantity": "20"
+        }));
+
+        let result = compiler.compile(&activity).unwrap();
+
+        assert_eq!(result.len(), 2);
+
+        // First leg: SELL of the given coin, carrying the fee
+        assert_eq!(result[0].id, "test-1:sell");
+        assert_eq!(result[0].activity_type, ACTIVITY_TYPE_SELL);
+        assert!(result[0].subtype.is_none());
+        assert_eq!(result[0].asset_id, Some("ETH".to_string()));
+        assert_eq!(result[0].quantity, Some(dec!(1)));
+        assert_eq!(result[0].unit_price, Some(dec!(3000)));
+        assert_eq!(result[0].fee, Some(dec!(5)));
+
+        // Second leg: BUY of the received coin at the FMV given up
+        assert_eq!(result[1].id, "test-1:buy");
+        assert_eq!(result[1].activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(result[1].asset_id, Some("SOL".to_string()));
+        assert_eq!(result[1].quantity, Some(dec!(20)));
+        assert_eq!(result[1].unit_price, Some(dec!(150)));
+        assert_eq!(result[1].fee, Some(dec!(0)));
+        assert!(result[1].metadata.is_none());
+
+        // Sell proceeds pay for the buy: only the fee leaves cash
+        let sell_cash = result[0].qty() * result[0].price() - result[0].fee_amt();
+        let buy_cash = result[1].qty() * result[1].price() + result[1].fee_amt();
+        assert_eq!(sell_cash - buy_cash, dec!(-5));
+    }
+
+    #[test]
+    fn test_compile_swap_without_received_quantity_falls_back_to_sell() {
+        let compiler = DefaultActivityCompiler::new();
+        let mut activity = create_test_activity();
+        activity.activity_type = ACTIVITY_TYPE_SELL.to_string();
+        activity.subtype = Some(ACTIVITY_SUBTYPE_SWAP.to_string());
+        activity.metadata = Some(serde_json::json!({ "received_asset_id": "SOL" }));
+
+        let result = compiler.compile(&activity).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "test-1");
+        assert_eq!(result[0].activity_type, ACTIVITY_TYPE_SELL);
+        assert!(result[0].subtype.is_none());
+    }
+
+    #[test]
+    fn test_compile_network_fee_reduces_quantity_without_cash_effect() {
+        let compiler = DefaultActivityCompiler::new();
+        let mut activity = create_test_activity();
+        activity.activity_type = ACTIVITY_TYPE_FEE.to_string();
+        activity.subtype = Some(ACTIVITY_SUBTYPE_NETWORK_FEE.to_string());
+        activity.asset_id = Some("ETH".to_string());
+        activity.quantity = Some(dec!(0.002)); // gas paid
+        activity.unit_price = Some(dec!(2500));
+        activity.amount = None;
+        activity.fee = None;
+
+        let result = compiler.compile(&activity).unwrap();
+
+        assert_eq!(result.len(), 2);
+
+        // First leg: SELL of the coins spent
+        assert_eq!(result[0].id, "test-1:sell");
+        assert_eq!(result[0].activity_type, ACTIVITY_TYPE_SELL);
+        assert_eq!(result[0].quantity, Some(dec!(0.002)));
+        assert_eq!(result[0].unit_price, Some(dec!(2500)));
+        assert_eq!(result[0].fee, Some(dec!(0)));
+
+        // Second leg: FEE of the same value
+        assert_eq!(result[1].id, "test-1:fee");
+        assert_eq!(result[1].activity_type, ACTIVITY_TYPE_FEE);
+        assert!(result[1].subtype.is_none());
+        assert!(result[1].quantity.is_none());
+        assert_eq!(result[1].amount, Some(dec!(5)));
+    }
+
+    #[test]
+    fn test_compile_network_fee_without_asset_is_cash_fee() {
+        let compiler = DefaultActivityCompiler::new();
+        let mut activity = create_test_activity();
+        activity.activity_type = ACTIVITY_TYPE_FEE.to_string();
+        activity.subtype = Some(ACTIVITY_SUBTYPE_NETWORK_FEE.to_string());
+        activity.asset_id = None;
+        activity.amount = Some(dec!(3));
+
+        let result = compiler.compile(&activity).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "test-1");
+        assert_eq!(result[0].activity_type, ACTIVITY_TYPE_FEE);
+        assert!(result[0].subtype.is_none());
+    }
+
+    #[test]
+    fn test_compile_fx_conversion_derives_amount_from_rate() {
+        let compiler = DefaultActivityCompiler::new();
+        let mut activity = create_test_activity();
+        activity.activity_type = ACTIVITY_TYPE_TRANSFER_OUT.to_string();
+        activity.subtype = Some(ACTIVITY_SUBTYPE_FX_CONVERSION.to_string());
+        activity.asset_id = None;
+        activity.amount = Some(dec!(1000));
+        activity.metadata = Some(serde_json::json!({
+            "to_currency": "eur",
+            "rate": "0.92"
+        }));
+
+        let result = compiler.compile(&activity).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "test-1:fx");
+        assert_eq!(result[0].activity_type, ACTIVITY_TYPE_TRANSFER_OUT);
+        assert_eq!(
+            result[0].subtype.as_deref(),
+            Some(ACTIVITY_SUBTYPE_FX_CONVERSION)
+        );
+        assert_eq!(
+            result[0].get_meta::<String>("to_currency"),
+            Some("EUR".to_string())
+        );
+        assert_eq!(result[0].get_meta_decimal("to_amount"), Some(dec!(920)));
+        assert_eq!(result[0].get_meta_decimal("rate"), Some(dec!(0.92)));
+    }
+
+    #[test]
+    fn test_compile_fx_conversion_into_same_currency_is_skipped() {
+        let compiler = DefaultActivityCompiler::new();
+        let mut activity = create_test_activity();
+        activity.activity_type = ACTIVITY_TYPE_TRANSFER_OUT.to_string();
+        activity.subtype = Some(ACTIVITY_SUBTYPE_FX_CONVERSION.to_string());
+        activity.metadata = Some(serde_json::json!({
+            "to_currency": "USD",
+            "to_amount": 100
+        }));
+
+        let result = compiler.compile(&activity).unwrap();
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_compile_fx_conversion_without_target_keeps_cash_out() {
+        let compiler = DefaultActivityCompiler::new();
+        let mut activity = create_test_activity();
+        activity.activity_type = ACTIVITY_TYPE_TRANSFER_OUT.to_string();
+        activity.subtype = Some(ACTIVITY_SUBTYPE_FX_CONVERSION.to_string());
+        activity.asset_id = None;
+        activity.amount = Some(dec!(1000));
+        activity.metadata = Some(serde_json::json!({ "to_currency": "EUR" }));
+
+        let result = compiler.compile(&activity).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "test-1:fx");
+        assert_eq!(result[0].amount, Some(dec!(1000)));
+        assert!(result[0].metadata.is_none());
+        assert!(result[0].needs_review);
+    }
+
     #[test]
     fn test_compile_respects_override() {
         let compiler = DefaultActivityCompiler::new();
@@ -0,0 +1,384 @@
+//! Fuzzy duplicate detection across import sources.
+//!
+//! Idempotency keys only catch exact duplicates. The same trade imported from a
+//! CSV and later pulled by broker sync often differs slightly (price rounding,
+//! trade vs settlement date), slips past the key and is counted twice. This
+//! module pairs activities that are probably the same event.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::activities_model::{Activity, ActivityStatus, ActivityUpsert};
+
+/// Metadata key set on an activity flagged as a probable duplicate, holding the
+/// id of the activity it duplicates.
+pub const PROBABLE_DUPLICATE_META_KEY: &str = "probable_duplicate_of";
+
+/// Metadata key listing the provider identities (`id`, `idempotency_key`) of
+/// synced duplicates merged into an activity that kept its own identity.
+pub const MERGED_DUPLICATES_META_KEY: &str = "merged_duplicates";
+
+/// Tolerances used to decide whether two activities are the same event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuzzyMatchConfig {
+    /// Maximum number of days between the two activity dates
+    pub date_window_days: i64,
+    /// Relative difference allowed between quantities
+    pub quantity_tolerance: Decimal,
+    /// Relative difference allowed between unit prices (or amounts when there is no price)
+    pub price_tolerance: Decimal,
+}
+
+impl Default for FuzzyMatchConfig {
+    fn default() -> Self {
+        Self {
+            date_window_days: 3,
+            quantity_tolerance: Decimal::new(1, 3),
+            price_tolerance: Decimal::new(1, 2),
+        }
+    }
+}
+
+/// An activity that probably duplicates another one from a different source.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbableDuplicate {
+    /// The later of the two, suspected to be the duplicate
+    pub activity_id: String,
+    /// The activity it duplicates
+    pub duplicate_of_id: String,
+    pub account_id: String,
+    pub asset_id: Option<String>,
+    pub activity_type: String,
+    pub activity_source: String,
+    pub duplicate_of_source: String,
+    /// Days between the two activity dates
+    pub day_difference: i64,
+    /// Absolute quantity difference
+    pub quantity_difference: Decimal,
+    /// Absolute unit price (or amount) difference
+    pub price_difference: Decimal,
+}
+
+/// The fields of an activity the matcher compares.
+struct Candidate<'a> {
+    id: &'a str,
+    account_id: &'a str,
+    asset_id: Option<&'a str>,
+    activity_type: &'a str,
+    date: NaiveDate,
+    quantity: Option<Decimal>,
+    unit_price: Option<Decimal>,
+    amount: Option<Decimal>,
+    source: String,
+    created_at: DateTime<Utc>,
+}
+
+impl<'a> Candidate<'a> {
+    fn from_activity(activity: &'a Activity) -> Option<Self> {
+        if activity.status == ActivityStatus::Void {
+            return None;
+        }
+        Some(Self {
+            id: &activity.id,
+            account_id: &activity.account_id,
+            asset_id: activity.asset_id.as_deref(),
+            activity_type: activity.effective_type(),
+            date: activity.effective_date(),
+            quantity: activity.quantity,
+            unit_price: activity.unit_price,
+            amount: activity.amount,
+            source: normalize_source(activity.source_system.as_deref()),
+            created_at: activity.created_at,
+        })
+    }
+
+    fn from_upsert(upsert: &'a ActivityUpsert) -> Option<Self> {
+        let date = DateTime::parse_from_rfc3339(&upsert.activity_date)
+            .map(|dt| dt.with_timezone(&Utc).date_naive())
+            .or_else(|_| NaiveDate::parse_from_str(&upsert.activity_date, "%Y-%m-%d"))
+            .ok()?;
+        Some(Self {
+            id: &upsert.id,
+            account_id: &upsert.account_id,
+            asset_id: upsert.asset_id.as_deref(),
+            activity_type: &upsert.activity_type,
+            date,
+            quantity: upsert.quantity,
+            unit_price: upsert.unit_price,
+            amount: upsert.amount,
+            source: normalize_source(upsert.source_system.as_deref()),
+            created_at: Utc::now(),
+        })
+    }
+}
+
+fn normalize_source(source_system: Option<&str>) -> String {
+    source_system
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or("MANUAL")
+        .to_ascii_uppercase()
+}
+
+/// Relative difference of two values, against the larger magnitude.
+fn relative_difference(a: Decimal, b: Decimal) -> Decimal {
+    let scale = a.abs().max(b.abs());
+    if scale.is_zero() {
+        Decimal::ZERO
+    } else {
+        (a - b).abs() / scale
+    }
+}
+
+/// Compares two optional values. Both missing is a match; one missing is not.
+fn within_tolerance(a: Option<Decimal>, b: Option<Decimal>, tolerance: Decimal) -> Option<Decimal> {
+    match (a, b) {
+        (Some(a), Some(b)) if relative_difference(a, b) <= tolerance => Some((a - b).abs()),
+        (None, None) => Some(Decimal::ZERO),
+        _ => None,
+    }
+}
+
+/// Returns the match if `later` probably duplicates `earlier`.
+fn match_pair(
+    later: &Candidate,
+    earlier: &Candidate,
+    config: &FuzzyMatchConfig,
+) -> Option<ProbableDuplicate> {
+    // Exact duplicates from one source are handled by idempotency keys; two
+    // matching rows from the same source are usually two real trades.
+    if later.id == earlier.id
+        || later.source == earlier.source
+        || later.account_id != earlier.account_id
+        || later.asset_id != earlier.asset_id
+        || !later
+            .activity_type
+            .eq_ignore_ascii_case(earlier.activity_type)
+    {
+        return None;
+    }
+
+    let day_difference = (later.date - earlier.date).num_days().abs();
+    if day_difference > config.date_window_days {
+        return None;
+    }
+
+    let quantity_difference =
+        within_tolerance(later.quantity, earlier.quantity, config.quantity_tolerance)?;
+    let price_difference = match (later.unit_price, earlier.unit_price) {
+        (Some(_), Some(_)) => {
+            within_tolerance(later.unit_price, earlier.unit_price, config.price_tolerance)?
+        }
+        // Cash activities carry no price, so the amount has to agree instead
+        _ => match (later.amount, earlier.amount) {
+            (Some(_), Some(_)) => {
+                within_tolerance(later.amount, earlier.amount, config.price_tolerance)?
+            }
+            _ if later.asset_id.is_none() => return None,
+            _ => Decimal::ZERO,
+        },
+    };
+
+    Some(ProbableDuplicate {
+        activity_id: later.id.to_string(),
+        duplicate_of_id: earlier.id.to_string(),
+        account_id: later.account_id.to_string(),
+        asset_id: later.asset_id.map(str::to_string),
+        activity_type: later.activity_type.to_string(),
+        activity_source: later.source.clone(),
+        duplicate_of_source: earlier.source.clone(),
+        day_difference,
+        quantity_difference,
+        price_difference,
+    })
+}
+
+/// Picks the closest match, preferring the smallest date gap.
+fn closest(matches: impl Iterator<Item = ProbableDuplicate>) -> Option<ProbableDuplicate> {
+    matches.min_by(|a, b| {
+        a.day_difference
+            .cmp(&b.day_difference)
+            .then(a.price_difference.cmp(&b.price_difference))
+            .then(a.quantity_difference.cmp(&b.quantity_difference))
+    })
+}
+
+/// Scans existing history for probable duplicates.
+///
+/// Each pair is reported once, with the more recently created activity as the
+/// duplicate. An activity is matched against at most one other.
+pub fn find_probable_duplicates(
+    activities: &[Activity],
+    config: &FuzzyMatchConfig,
+) -> Vec<ProbableDuplicate> {
+    let mut candidates: Vec<Candidate> = activities
+        .iter()
+        .filter_map(Candidate::from_activity)
+        .collect();
+    candidates.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(b.id)));
+
+    let mut groups: HashMap<(&str, Option<&str>), Vec<&Candidate>> = HashMap::new();
+    for candidate in &candidates {
+        groups
+            .entry((candidate.account_id, candidate.asset_id))
+            .or_default()
+            .push(candidate);
+    }
+
+    let mut paired: HashSet<String> = HashSet::new();
+    let mut duplicates = Vec::new();
+    // Walk in creation order so the newer activity is the one flagged
+    for later in &candidates {
+        if paired.contains(later.id) {
+            continue;
+        }
+        let group = &groups[&(later.account_id, later.asset_id)];
+        let found = closest(
+            group
+                .iter()
+                .take_while(|earlier| earlier.created_at <= later.created_at)
+                .filter(|earlier| !paired.contains(earlier.id))
+                .filter_map(|earlier| match_pair(later, earlier, config)),
+        );
+        if let Some(duplicate) = found {
+            paired.insert(later.id.to_string());
+            paired.insert(duplicate.duplicate_of_id.clone());
+            duplicates.push(duplicate);
+        }
+    }
+    duplicates
+}
+
+/// Matches incoming upserts against existing activities.
+///
+/// Upserts that already exist (same id or idempotency key) are updates, not
+/// duplicates, and are ignored. Returns the match keyed by upsert id.
+pub fn match_incoming_duplicates(
+    incoming: &[ActivityUpsert],
+    existing: &[Activity],
+    config: &FuzzyMatchConfig,
+) -> HashMap<String, ProbableDuplicate> {
+    let known_ids: HashSet<&str> = existing.iter().map(|a| a.id.as_str()).collect();
+    let known_keys: HashSet<&str> = existing
+        .iter()
+        .filter_map(|a| a.idempotency_key.as_deref())
+        .collect();
+    let existing: Vec<Candidate> = existing
+        .iter()
+        .filter_map(Candidate::from_activity)
+        .collect();
+
+    incoming
+        .iter()
+        .filter(|upsert| {
+            !known_ids.contains(upsert.id.as_str())
+                && !upsert
+                    .idempotency_key
+                    .as_deref()
+                    .is_some_and(|key| known_keys.contains(key))
+        })
+        .filter_map(|upsert| {
+            let candidate = Candidate::from_upsert(upsert)?;
+            closest(
+                existing
+                    .iter()
+                    .filter_map(|earlier| match_pair(&candidate, earlier, config)),
+            )
+            .map(|duplicate| (upsert.id.clone(), duplicate))
+        })
+        .collect()
+}
+
+/// Flags upserts that probably duplicate existing activities for review.
+///
+/// The activity is still written; it is marked `needs_review` with the id of the
+/// matching activity in its metadata so the user can merge the two. Updates of
+/// activities flagged by an earlier sync keep the flag until the user merges
+/// them. Returns the number of newly flagged upserts.
+pub fn flag_probable_duplicates(
+    incoming: &mut [ActivityUpsert],
+    existing: &[Activity],
+    config: &FuzzyMatchConfig,
+) -> usize {
+    let matches = match_incoming_duplicates(incoming, existing, config);
+    let mut flagged: HashMap<&str, &str> = HashMap::new();
+    for activity in existing {
+        let Some(duplicate_of) = activity
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(PROBABLE_DUPLICATE_META_KEY))
+            .and_then(|v| v.as_str())
+        else {
+            continue;
+        };
+        flagged.insert(activity.id.as_str(), duplicate_of);
+        if let Some(key) = activity.idempotency_key.as_deref() {
+            flagged.insert(key, duplicate_of);
+        }
+    }
+
+    for upsert in incoming.iter_mut() {
+        let duplicate_of = match matches.get(&upsert.id) {
+            Some(duplicate) => duplicate.duplicate_of_id.clone(),
+            None => {
+                let earlier = flagged.get(upsert.id.as_str()).or_else(|| {
+                    upsert
+                        .idempotency_key
+                        .as_deref()
+                        .and_then(|key| flagged.get(key))
+                });
+                match earlier {
+                    Some(duplicate_of) => duplicate_of.to_string(),
+                    None => continue,
+                }
+            }
+        };
+        let mut metadata = upsert
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+            .filter(|m| m.is_object())
+            .unwrap_or_else(|| serde_json::json!({}));
+        metadata[PROBABLE_DUPLICATE_META_KEY] = serde_json::Value::String(duplicate_of);
+        upsert.metadata = Some(metadata.to_string());
+        upsert.needs_review = Some(true);
+    }
+    matches.len()
+}
+
+/// Removes upserts of synced duplicates that were merged into another activity,
+/// so the next sync does not re-create them. Returns the number removed.
+pub fn drop_merged_duplicates(incoming: &mut Vec<ActivityUpsert>, existing: &[Activity]) -> usize {
+    let merged: HashSet<&str> = existing
+        .iter()
+        .filter_map(|a| {
+            a.metadata
+                .as_ref()?
+                .get(MERGED_DUPLICATES_META_KEY)?
+                .as_array()
+        })
+        .flatten()
+        .flat_map(|identity| {
+            ["id", "idempotency_key"]
+                .into_iter()
+                .filter_map(|field| identity.get(field)?.as_str())
+        })
+        .collect();
+    if merged.is_empty() {
+        return 0;
+    }
+
+    let before = incoming.len();
+    incoming.retain(|upsert| {
+        !merged.contains(upsert.id.as_str())
+            && !upsert
+                .idempotency_key
+                .as_deref()
+                .is_some_and(|key| merged.contains(key))
+    });
+    before - incoming.len()
+}
@@ -0,0 +1,203 @@
+//! Tests for fuzzy duplicate detection across import sources.
+
+#[cfg(test)]
+mod tests {
+    use crate::activities::{
+        drop_merged_duplicates, find_probable_duplicates, flag_probable_duplicates, Activity,
+        ActivityStatus, ActivityUpsert, FuzzyMatchConfig, ACTIVITY_TYPE_BUY, ACTIVITY_TYPE_DEPOSIT,
+        MERGED_DUPLICATES_META_KEY, PROBABLE_DUPLICATE_META_KEY,
+    };
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn activity(id: &str, source: Option<&str>, day: u32, price: Decimal) -> Activity {
+        let date = NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+        Activity {
+            id: id.to_string(),
+            account_id: "acc_1".to_string(),
+            asset_id: Some("SEC:AAPL:XNAS".to_string()),
+            activity_type: ACTIVITY_TYPE_BUY.to_string(),
+            activity_type_override: None,
+            source_type: None,
+            subtype: None,
+            status: ActivityStatus::Posted,
+            activity_date: Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()),
+            settlement_date: None,
+            quantity: Some(dec!(10)),
+            unit_price: Some(price),
+            amount: None,
+            fee: None,
+            currency: "USD".to_string(),
+            fx_rate: None,
+            notes: None,
+            metadata: None,
+            source_system: source.map(str::to_string),
+            source_record_id: None,
+            source_group_id: None,
+            idempotency_key: None,
+            import_run_id: None,
+            is_user_modified: false,
+            needs_review: false,
+            created_at: Utc::now() + Duration::seconds(day as i64),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn upsert(id: &str, day: u32, price: Decimal) -> ActivityUpsert {
+        ActivityUpsert {
+            id: id.to_string(),
+            account_id: "acc_1".to_string(),
+            asset_id: Some("SEC:AAPL:XNAS".to_string()),
+            activity_type: ACTIVITY_TYPE_BUY.to_string(),
+            subtype: None,
+            activity_date: format!("2024-05-{:02}T14:30:00Z", day),
+            quantity: Some(dec!(10)),
+            unit_price: Some(price),
+            currency: "USD".to_string(),
+            fee: None,
+            amount: None,
+            status: None,
+            notes: None,
+            fx_rate: None,
+            metadata: Some(r#"{"provider_type":"trade"}"#.to_string()),
+            needs_review: None,
+            source_system: Some("SNAPTRADE".to_string()),
+            source_record_id: Some(id.to_string()),
+            source_group_id: None,
+            idempotency_key: None,
+            import_run_id: None,
+        }
+    }
+
+    #[test]
+    fn test_rounding_and_date_drift_across_sources_is_a_duplicate() {
+        let csv = activity("csv_1", Some("CSV"), 10, dec!(187.4321));
+        let synced = activity("sync_1", Some("SNAPTRADE"), 12, dec!(187.43));
+
+        let duplicates = find_probable_duplicates(&[synced, csv], &FuzzyMatchConfig::default());
+        assert_eq!(duplicates.len(), 1);
+        let duplicate = &duplicates[0];
+        // The activity created later is the one flagged
+        assert_eq!(duplicate.activity_id, "sync_1");
+        assert_eq!(duplicate.duplicate_of_id, "csv_1");
+        assert_eq!(duplicate.day_difference, 2);
+        assert_eq!(duplicate.price_difference, dec!(0.0021));
+    }
+
+    #[test]
+    fn test_same_source_or_distant_activities_are_not_duplicates() {
+        let config = FuzzyMatchConfig::default();
+
+        // Two identical buys from one source are two real trades
+        let same_source = [
+            activity("a", Some("CSV"), 10, dec!(100)),
+            activity("b", Some("csv"), 10, dec!(100)),
+        ];
+        assert!(find_probable_duplicates(&same_source, &config).is_empty());
+
+        let outside_window = [
+            activity("a", None, 10, dec!(100)),
+            activity("b", Some("CSV"), 14, dec!(100)),
+        ];
+        assert!(find_probable_duplicates(&outside_window, &config).is_empty());
+
+        let different_price = [
+            activity("a", None, 10, dec!(100)),
+            activity("b", Some("CSV"), 10, dec!(102)),
+        ];
+        assert!(find_probable_duplicates(&different_price, &config).is_empty());
+
+        let mut voided = activity("b", Some("CSV"), 10, dec!(100));
+        voided.status = ActivityStatus::Void;
+        assert!(
+            find_probable_duplicates(&[activity("a", None, 10, dec!(100)), voided], &config)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_cash_activities_match_on_amount() {
+        let cash = |id: &str, source: &str, amount: Decimal| {
+            let mut a = activity(id, Some(source), 3, Decimal::ZERO);
+            a.asset_id = None;
+            a.activity_type = ACTIVITY_TYPE_DEPOSIT.to_string();
+            a.quantity = None;
+            a.unit_price = None;
+            a.amount = Some(amount);
+            a
+        };
+        let config = FuzzyMatchConfig::default();
+        assert_eq!(
+            find_probable_duplicates(
+                &[cash("a", "CSV", dec!(500)), cash("b", "PLAID", dec!(500))],
+                &config
+            )
+            .len(),
+            1
+        );
+        assert!(find_probable_duplicates(
+            &[cash("a", "CSV", dec!(500)), cash("b", "PLAID", dec!(750))],
+            &config
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_incoming_upserts_are_flagged_for_review() {
+        let existing = vec![
+            activity("csv_1", Some("CSV"), 10, dec!(187.4321)),
+            activity("sync_2", Some("SNAPTRADE"), 20, dec!(190)),
+        ];
+        let mut incoming = vec![
+            upsert("sync_1", 11, dec!(187.43)),
+            // Already stored: an update, not a duplicate
+            upsert("sync_2", 20, dec!(190)),
+        ];
+
+        let flagged =
+            flag_probable_duplicates(&mut incoming, &existing, &FuzzyMatchConfig::default());
+        assert_eq!(flagged, 1);
+        assert_eq!(incoming[0].needs_review, Some(true));
+        let metadata: serde_json::Value =
+            serde_json::from_str(incoming[0].metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata[PROBABLE_DUPLICATE_META_KEY], "csv_1");
+        assert_eq!(metadata["provider_type"], "trade");
+        assert_eq!(incoming[1].needs_review, None);
+    }
+
+    #[test]
+    fn test_flag_survives_the_next_sync() {
+        let mut flagged = activity("sync_1", Some("SNAPTRADE"), 11, dec!(187.43));
+        flagged.metadata = Some(serde_json::json!({ PROBABLE_DUPLICATE_META_KEY: "csv_1" }));
+        flagged.needs_review = true;
+        let existing = vec![activity("csv_1", Some("CSV"), 10, dec!(187.4321)), flagged];
+        let mut incoming = vec![upsert("sync_1", 11, dec!(187.43))];
+
+        let count =
+            flag_probable_duplicates(&mut incoming, &existing, &FuzzyMatchConfig::default());
+        assert_eq!(count, 0);
+        assert_eq!(incoming[0].needs_review, Some(true));
+        let metadata: serde_json::Value =
+            serde_json::from_str(incoming[0].metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata[PROBABLE_DUPLICATE_META_KEY], "csv_1");
+        assert_eq!(metadata["provider_type"], "trade");
+    }
+
+    #[test]
+    fn test_merged_duplicates_are_not_recreated() {
+        let mut kept = activity("csv_1", Some("CSV"), 10, dec!(187.4321));
+        kept.source_record_id = Some("row-7".to_string());
+        kept.metadata = Some(serde_json::json!({
+            MERGED_DUPLICATES_META_KEY: [{ "id": "sync_1", "idempotency_key": null }],
+        }));
+        let mut incoming = vec![
+            upsert("sync_1", 11, dec!(187.43)),
+            upsert("sync_3", 12, dec!(200)),
+        ];
+
+        assert_eq!(drop_merged_duplicates(&mut incoming, &[kept]), 1);
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(incoming[0].id, "sync_3");
+    }
+}
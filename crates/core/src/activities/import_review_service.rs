@@ -0,0 +1,234 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use log::debug;
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+
+use crate::activities::{
+    ImportReviewServiceTrait, ImportRun, ImportRunChange, ImportRunChangeRepositoryTrait,
+    ImportRunChangeStatus, ImportRunRepositoryTrait, ImportRunRevertResult, ImportRunStatus,
+};
+use crate::errors::{DatabaseError, Error, ValidationError};
+use crate::events::{DomainEvent, DomainEventSink, NoOpDomainEventSink};
+use crate::Result;
+
+/// Service for reviewing, partially applying and rolling back import runs.
+pub struct ImportReviewService {
+    change_repository: Arc<dyn ImportRunChangeRepositoryTrait>,
+    import_run_repository: Arc<dyn ImportRunRepositoryTrait>,
+    event_sink: Arc<dyn DomainEventSink>,
+}
+
+impl ImportReviewService {
+    pub fn new(
+        change_repository: Arc<dyn ImportRunChangeRepositoryTrait>,
+        import_run_repository: Arc<dyn ImportRunRepositoryTrait>,
+    ) -> Self {
+        Self {
+            change_repository,
+            import_run_repository,
+            event_sink: Arc::new(NoOpDomainEventSink),
+        }
+    }
+
+    /// Sets the domain event sink for this service.
+    ///
+    /// Reverting changes emits ActivitiesChanged so snapshots are recalculated.
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn DomainEventSink>) -> Self {
+        self.event_sink = event_sink;
+        self
+    }
+
+    fn get_run(&self, import_run_id: &str) -> Result<ImportRun> {
+        self.import_run_repository
+            .get_by_id(import_run_id)?
+            .ok_or_else(|| {
+                Error::Database(DatabaseError::NotFound(format!(
+                    "Import run {} not found",
+                    import_run_id
+                )))
+            })
+    }
+
+    /// Marks a run waiting for review as applied once no change is pending.
+    async fn finish_review(&self, mut run: ImportRun) -> Result<()> {
+        if run.status != ImportRunStatus::NeedsReview {
+            return Ok(());
+        }
+        let pending = self
+            .change_repository
+            .get_changes(&run.id)?
+            .iter()
+            .any(|c| c.status == ImportRunChangeStatus::Pending);
+        if !pending {
+            run.complete();
+            self.import_run_repository.update(run).await?;
+        }
+        Ok(())
+    }
+
+    fn emit_changes(&self, changes: &[ImportRunChange]) {
+        let (account_ids, asset_ids, currencies) = affected_entities(changes);
+        if !account_ids.is_empty() {
+            self.event_sink.emit(DomainEvent::activities_changed(
+                account_ids,
+                asset_ids,
+                currencies,
+            ));
+        }
+    }
+}
+
+/// Picks the requested changes of a run, all of which must still be pending.
+fn select_pending_changes(
+    changes: Vec<ImportRunChange>,
+    change_ids: &[String],
+) -> Result<Vec<ImportRunChange>> {
+    let requested: HashSet<&str> = change_ids.iter().map(String::as_str).collect();
+    let selected: Vec<ImportRunChange> = changes
+        .into_iter()
+        .filter(|c| requested.contains(c.id.as_str()))
+        .collect();
+
+    if selected.len() != requested.len() {
+        return Err(Error::Validation(ValidationError::InvalidInput(
+            "Some changes do not belong to this import run".to_string(),
+        )));
+    }
+    if let Some(reviewed) = selected
+        .iter()
+        .find(|c| c.status != ImportRunChangeStatus::Pending)
+    {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Change {} has already been reviewed",
+            reviewed.id
+        ))));
+    }
+    Ok(selected)
+}
+
+/// Accounts, assets and currencies touched by the changes, before or after the run.
+fn affected_entities(
+    changes: &[ImportRunChange],
+) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let mut account_ids = BTreeSet::new();
+    let mut asset_ids = BTreeSet::new();
+    let mut currencies = BTreeSet::new();
+    for activity in changes
+        .iter()
+        .flat_map(|c| c.before.iter().chain(c.after.iter()))
+    {
+        account_ids.insert(activity.account_id.clone());
+        if let Some(asset_id) = &activity.asset_id {
+            asset_ids.insert(asset_id.clone());
+        }
+        currencies.insert(activity.currency.clone());
+    }
+    (
+        account_ids.into_iter().collect(),
+        asset_ids.into_iter().collect(),
+        currencies.into_iter().collect(),
+    )
+}
+
+#[async_trait]
+impl ImportReviewServiceTrait for ImportReviewService {
+    fn get_import_run_changes(&self, import_run_id: &str) -> Result<Vec<ImportRunChange>> {
+        self.get_run(import_run_id)?;
+        self.change_repository.get_changes(import_run_id)
+    }
+
+    async fn approve_import_run_changes(
+        &self,
+        import_run_id: &str,
+        change_ids: Vec<String>,
+    ) -> Result<usize> {
+        let run = self.get_run(import_run_id)?;
+        let selected = select_pending_changes(
+            self.change_repository.get_changes(import_run_id)?,
+            &change_ids,
+        )?;
+        if selected.is_empty() {
+            return Ok(0);
+        }
+
+        let approved = self
+            .change_repository
+            .approve_changes(import_run_id, selected.into_iter().map(|c| c.id).collect())
+            .await?;
+        self.finish_review(run).await?;
+        Ok(approved)
+    }
+
+    async fn reject_import_run_changes(
+        &self,
+        import_run_id: &str,
+        change_ids: Vec<String>,
+    ) -> Result<ImportRunRevertResult> {
+        let run = self.get_run(import_run_id)?;
+        let selected = select_pending_changes(
+            self.change_repository.get_changes(import_run_id)?,
+            &change_ids,
+        )?;
+        if selected.is_empty() {
+            return Ok(ImportRunRevertResult::default());
+        }
+
+        let result = self
+            .change_repository
+            .revert_changes(
+                import_run_id,
+                selected.iter().map(|c| c.id.clone()).collect(),
+                ImportRunChangeStatus::Rejected,
+            )
+            .await?;
+        self.emit_changes(&selected);
+        self.finish_review(run).await?;
+        Ok(result)
+    }
+
+    async fn rollback_import_run(&self, import_run_id: &str) -> Result<ImportRunRevertResult> {
+        let mut run = self.get_run(import_run_id)?;
+        if !matches!(
+            run.status,
+            ImportRunStatus::Applied | ImportRunStatus::NeedsReview
+        ) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Import run {} cannot be rolled back while {:?}",
+                import_run_id, run.status
+            ))));
+        }
+
+        let applied: Vec<ImportRunChange> = self
+            .change_repository
+            .get_changes(import_run_id)?
+            .into_iter()
+            .filter(|c| c.status.is_applied())
+            .collect();
+        let result = self
+            .change_repository
+            .revert_changes(
+                import_run_id,
+                applied.iter().map(|c| c.id.clone()).collect(),
+                ImportRunChangeStatus::RolledBack,
+            )
+            .await?;
+        self.emit_changes(&applied);
+
+        run.status = ImportRunStatus::Cancelled;
+        run.updated_at = Utc::now();
+        if result.skipped > 0 {
+            run.warnings.get_or_insert_with(Vec::new).push(format!(
+                "{} activities edited after the import were kept on rollback",
+                result.skipped
+            ));
+        }
+        self.import_run_repository.update(run).await?;
+
+        debug!(
+            "Rolled back import run {}: {} removed, {} restored, {} skipped",
+            import_run_id, result.removed, result.restored, result.skipped
+        );
+        Ok(result)
+    }
+}
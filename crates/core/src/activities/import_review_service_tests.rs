@@ -0,0 +1,280 @@
+#[cfg(test)]
+mod tests {
+    use crate::activities::{
+        Activity, ActivityStatus, ImportReviewService, ImportReviewServiceTrait, ImportRun,
+        ImportRunChange, ImportRunChangeRepositoryTrait, ImportRunChangeStatus,
+        ImportRunChangeType, ImportRunMode, ImportRunRepositoryTrait, ImportRunRevertResult,
+        ImportRunStatus, ImportRunType, ReviewMode,
+    };
+    use crate::errors::Result;
+    use crate::events::{DomainEvent, MockDomainEventSink};
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct MockImportRunRepository {
+        runs: Mutex<HashMap<String, ImportRun>>,
+    }
+
+    #[async_trait]
+    impl ImportRunRepositoryTrait for MockImportRunRepository {
+        async fn create(&self, import_run: ImportRun) -> Result<ImportRun> {
+            self.runs
+                .lock()
+                .unwrap()
+                .insert(import_run.id.clone(), import_run.clone());
+            Ok(import_run)
+        }
+
+        async fn update(&self, import_run: ImportRun) -> Result<ImportRun> {
+            self.create(import_run).await
+        }
+
+        fn get_by_id(&self, id: &str) -> Result<Option<ImportRun>> {
+            Ok(self.runs.lock().unwrap().get(id).cloned())
+        }
+
+        fn get_recent_for_account(&self, _account_id: &str, _limit: i64) -> Result<Vec<ImportRun>> {
+            unimplemented!()
+        }
+    }
+
+    #[derive(Default)]
+    struct MockChangeRepository {
+        changes: Mutex<Vec<ImportRunChange>>,
+    }
+
+    #[async_trait]
+    impl ImportRunChangeRepositoryTrait for MockChangeRepository {
+        fn get_changes(&self, import_run_id: &str) -> Result<Vec<ImportRunChange>> {
+            Ok(self
+                .changes
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|c| c.import_run_id == import_run_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn approve_changes(
+            &self,
+            _import_run_id: &str,
+            change_ids: Vec<String>,
+        ) -> Result<usize> {
+            let mut changes = self.changes.lock().unwrap();
+            let mut approved = 0;
+            for change in changes.iter_mut().filter(|c| change_ids.contains(&c.id)) {
+                change.status = ImportRunChangeStatus::Approved;
+                approved += 1;
+            }
+            Ok(approved)
+        }
+
+        async fn revert_changes(
+            &self,
+            _import_run_id: &str,
+            change_ids: Vec<String>,
+            status: ImportRunChangeStatus,
+        ) -> Result<ImportRunRevertResult> {
+            let mut changes = self.changes.lock().unwrap();
+            let mut result = ImportRunRevertResult::default();
+            for change in changes.iter_mut().filter(|c| change_ids.contains(&c.id)) {
+                match change.change_type {
+                    ImportRunChangeType::Insert => result.removed += 1,
+                    ImportRunChangeType::Update => result.restored += 1,
+                }
+                change.status = status;
+            }
+            Ok(result)
+        }
+    }
+
+    fn activity(id: &str, account_id: &str) -> Activity {
+        Activity {
+            id: id.to_string(),
+            account_id: account_id.to_string(),
+            asset_id: Some("SEC:AAPL:XNAS".to_string()),
+            activity_type: "BUY".to_string(),
+            activity_type_override: None,
+            source_type: None,
+            subtype: None,
+            status: ActivityStatus::Posted,
+            activity_date: Utc::now(),
+            settlement_date: None,
+            quantity: Some(dec!(1)),
+            unit_price: Some(dec!(100)),
+            amount: None,
+            fee: None,
+            currency: "USD".to_string(),
+            fx_rate: None,
+            notes: None,
+            metadata: None,
+            source_system: Some("CSV".to_string()),
+            source_record_id: None,
+            source_group_id: None,
+            idempotency_key: None,
+            import_run_id: Some("run_1".to_string()),
+            is_user_modified: false,
+            needs_review: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn change(id: &str, change_type: ImportRunChangeType) -> ImportRunChange {
+        let activity_id = format!("act_{}", id);
+        ImportRunChange {
+            id: id.to_string(),
+            import_run_id: "run_1".to_string(),
+            activity_id: activity_id.clone(),
+            change_type,
+            before: (change_type == ImportRunChangeType::Update)
+                .then(|| activity(&activity_id, "acc_old")),
+            after: Some(activity(&activity_id, "acc_1")),
+            status: ImportRunChangeStatus::Pending,
+            reviewed_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    struct Fixture {
+        service: ImportReviewService,
+        runs: Arc<MockImportRunRepository>,
+        changes: Arc<MockChangeRepository>,
+        events: MockDomainEventSink,
+    }
+
+    fn fixture(status: ImportRunStatus) -> Fixture {
+        let mut run = ImportRun::new(
+            "acc_1".to_string(),
+            "CSV".to_string(),
+            ImportRunType::Import,
+            ImportRunMode::Initial,
+            ReviewMode::Always,
+        );
+        run.id = "run_1".to_string();
+        run.status = status;
+
+        let runs = Arc::new(MockImportRunRepository::default());
+        runs.runs.lock().unwrap().insert(run.id.clone(), run);
+        let changes = Arc::new(MockChangeRepository {
+            changes: Mutex::new(vec![
+                change("c1", ImportRunChangeType::Insert),
+                change("c2", ImportRunChangeType::Insert),
+                change("c3", ImportRunChangeType::Update),
+            ]),
+        });
+        let events = MockDomainEventSink::new();
+        let service = ImportReviewService::new(changes.clone(), runs.clone())
+            .with_event_sink(Arc::new(events.clone()));
+        Fixture {
+            service,
+            runs,
+            changes,
+            events,
+        }
+    }
+
+    fn run_status(fixture: &Fixture) -> ImportRunStatus {
+        fixture.runs.get_by_id("run_1").unwrap().unwrap().status
+    }
+
+    #[tokio::test]
+    async fn test_partial_review_completes_run_once_nothing_is_pending() {
+        let f = fixture(ImportRunStatus::NeedsReview);
+
+        let result = f
+            .service
+            .reject_import_run_changes("run_1", vec!["c1".to_string(), "c3".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(result.removed, 1);
+        assert_eq!(result.restored, 1);
+        assert_eq!(run_status(&f), ImportRunStatus::NeedsReview);
+
+        // Accounts before and after the overwritten activity are recalculated
+        match &f.events.events()[0] {
+            DomainEvent::ActivitiesChanged { account_ids, .. } => {
+                assert_eq!(
+                    account_ids,
+                    &vec!["acc_1".to_string(), "acc_old".to_string()]
+                );
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        let approved = f
+            .service
+            .approve_import_run_changes("run_1", vec!["c2".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(approved, 1);
+        assert_eq!(run_status(&f), ImportRunStatus::Applied);
+        assert_eq!(f.events.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_review_rejects_unknown_or_reviewed_changes() {
+        let f = fixture(ImportRunStatus::NeedsReview);
+        assert!(f
+            .service
+            .approve_import_run_changes("run_1", vec!["missing".to_string()])
+            .await
+            .is_err());
+
+        f.service
+            .approve_import_run_changes("run_1", vec!["c1".to_string()])
+            .await
+            .unwrap();
+        assert!(f
+            .service
+            .reject_import_run_changes("run_1", vec!["c1".to_string()])
+            .await
+            .is_err());
+        assert!(f.service.get_import_run_changes("run_2").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rollback_reverts_changes_still_in_effect() {
+        let f = fixture(ImportRunStatus::Applied);
+        f.service
+            .reject_import_run_changes("run_1", vec!["c2".to_string()])
+            .await
+            .unwrap();
+
+        let result = f.service.rollback_import_run("run_1").await.unwrap();
+        assert_eq!(result.removed, 1);
+        assert_eq!(result.restored, 1);
+        assert_eq!(run_status(&f), ImportRunStatus::Cancelled);
+
+        let statuses: Vec<ImportRunChangeStatus> = f
+            .changes
+            .get_changes("run_1")
+            .unwrap()
+            .iter()
+            .map(|c| c.status)
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ImportRunChangeStatus::RolledBack,
+                ImportRunChangeStatus::Rejected,
+                ImportRunChangeStatus::RolledBack,
+            ]
+        );
+
+        // A cancelled run cannot be rolled back twice
+        assert!(f.service.rollback_import_run("run_1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_running_import_cannot_be_rolled_back() {
+        let f = fixture(ImportRunStatus::Running);
+        assert!(f.service.rollback_import_run("run_1").await.is_err());
+        assert!(f.events.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 //! Import run domain models used by broker ingest and manual activity imports.
 
+use crate::activities::Activity;
 use crate::Result;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -177,6 +178,68 @@ impl ImportRun {
     }
 }
 
+/// How an import run changed an activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ImportRunChangeType {
+    /// The run created the activity
+    Insert,
+    /// The run overwrote an existing activity
+    Update,
+}
+
+/// Review state of a change made by an import run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ImportRunChangeStatus {
+    /// Applied, not reviewed yet
+    #[default]
+    Pending,
+    /// Kept by the user
+    Approved,
+    /// Reverted by the user during review
+    Rejected,
+    /// Reverted together with the whole run
+    RolledBack,
+}
+
+impl ImportRunChangeStatus {
+    /// Whether the change is still in effect
+    pub fn is_applied(&self) -> bool {
+        matches!(self, Self::Pending | Self::Approved)
+    }
+}
+
+/// An activity inserted or updated by an import run.
+///
+/// `before` is the activity as it was before the run overwrote it (updates only);
+/// `after` is the activity as the run wrote it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRunChange {
+    pub id: String,
+    pub import_run_id: String,
+    pub activity_id: String,
+    pub change_type: ImportRunChangeType,
+    pub before: Option<Activity>,
+    pub after: Option<Activity>,
+    pub status: ImportRunChangeStatus,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Outcome of reverting changes made by an import run
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRunRevertResult {
+    /// Inserted activities that were deleted
+    pub removed: usize,
+    /// Updated activities that were restored to their previous state
+    pub restored: usize,
+    /// Activities left untouched because they were edited after the run
+    pub skipped: usize,
+}
+
 /// Trait for ImportRun persistence operations
 #[async_trait]
 pub trait ImportRunRepositoryTrait: Send + Sync {
@@ -192,3 +255,22 @@ pub trait ImportRunRepositoryTrait: Send + Sync {
     /// Get recent import runs for an account
     fn get_recent_for_account(&self, account_id: &str, limit: i64) -> Result<Vec<ImportRun>>;
 }
+
+/// Trait for persisting the per-activity changes of import runs
+#[async_trait]
+pub trait ImportRunChangeRepositoryTrait: Send + Sync {
+    /// Get the changes recorded for an import run, in the order they were applied
+    fn get_changes(&self, import_run_id: &str) -> Result<Vec<ImportRunChange>>;
+
+    /// Mark changes as approved and clear the review flag on their activities
+    async fn approve_changes(&self, import_run_id: &str, change_ids: Vec<String>) -> Result<usize>;
+
+    /// Undo changes: delete inserted activities and restore overwritten ones.
+    /// Activities edited since the run are left as they are.
+    async fn revert_changes(
+        &self,
+        import_run_id: &str,
+        change_ids: Vec<String>,
+        status: ImportRunChangeStatus,
+    ) -> Result<ImportRunRevertResult>;
+}
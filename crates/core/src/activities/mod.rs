@@ -5,9 +5,13 @@ mod activities_errors;
 mod activities_model;
 mod activities_service;
 mod activities_traits;
+mod broker_parsers;
+mod bulk_edit;
 mod compiler;
 mod csv_parser;
+mod fuzzy_duplicates;
 mod idempotency;
+mod import_review_service;
 mod import_run_model;
 
 #[cfg(test)]
@@ -16,6 +20,15 @@ mod activities_service_tests;
 #[cfg(test)]
 mod activities_model_tests;
 
+#[cfg(test)]
+mod bulk_edit_tests;
+
+#[cfg(test)]
+mod fuzzy_duplicates_tests;
+
+#[cfg(test)]
+mod import_review_service_tests;
+
 pub use activities_constants::*;
 pub use activities_errors::ActivityError;
 pub use activities_model::{
@@ -27,13 +40,30 @@ pub use activities_model::{
     NewActivity, PrepareActivitiesResult, Sort, SymbolInput,
 };
 pub use activities_service::ActivityService;
-pub use activities_traits::{ActivityRepositoryTrait, ActivityServiceTrait};
+pub use activities_traits::{
+    ActivityRepositoryTrait, ActivityServiceTrait, ImportReviewServiceTrait,
+};
+pub use broker_parsers::{
+    is_ibkr_flex, parse_ibkr_flex, BrokerParserInfo, BrokerParserRegistry, BrokerStatement,
+    BrokerStatementParser, ChargePattern, ContractNoteTemplate, IBKR_FLEX_SOURCE_SYSTEM,
+};
+pub use bulk_edit::{
+    edited_fields, holding_changes, ActivityBulkEdit, ActivityBulkEditChange,
+    ActivityBulkEditHoldingChange, ActivityBulkEditRequest, ActivityBulkEditResult, ActivityQuery,
+};
 pub use compiler::{ActivityCompiler, DefaultActivityCompiler};
 pub use csv_parser::{parse_csv, ParseConfig, ParseError, ParsedCsvResult};
+pub use fuzzy_duplicates::{
+    drop_merged_duplicates, find_probable_duplicates, flag_probable_duplicates,
+    match_incoming_duplicates, FuzzyMatchConfig, ProbableDuplicate, MERGED_DUPLICATES_META_KEY,
+    PROBABLE_DUPLICATE_META_KEY,
+};
 pub use idempotency::{
     compute_activity_idempotency_key, compute_idempotency_key, generate_manual_idempotency_key,
 };
+pub use import_review_service::ImportReviewService;
 pub use import_run_model::{
-    ImportRun, ImportRunMode, ImportRunRepositoryTrait, ImportRunStatus, ImportRunSummary,
-    ImportRunType, ReviewMode,
+    ImportRun, ImportRunChange, ImportRunChangeRepositoryTrait, ImportRunChangeStatus,
+    ImportRunChangeType, ImportRunMode, ImportRunRepositoryTrait, ImportRunRevertResult,
+    ImportRunStatus, ImportRunSummary, ImportRunType, ReviewMode,
 };
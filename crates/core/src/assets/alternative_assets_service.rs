@@ -8,10 +8,11 @@
 //! - No activities (avoids activity clutter)
 //! - Just asset record + valuation quotes
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::{TimeZone, Utc};
+use chrono::{Months, NaiveDate, TimeZone, Utc};
 use log::debug;
 use rust_decimal::Decimal;
 use serde_json::{json, Value};
@@ -25,7 +26,12 @@ use super::alternative_assets_model::{
 use super::alternative_assets_traits::{
     AlternativeAssetRepositoryTrait, AlternativeAssetServiceTrait,
 };
-use super::{AssetKind, AssetRepositoryTrait, NewAsset, QuoteMode};
+use super::loan_model::{AmortizationSchedule, LoanTerms, LOAN_METADATA_KEY};
+use super::rental_model::{
+    NewPropertyCashFlow, PropertyCashFlow, RentalPerformance, RentalPerformanceInput,
+};
+use super::valuation_model::{ValuationModel, ValuationSeries, VALUATION_MODEL_METADATA_KEY};
+use super::{Asset, AssetKind, AssetRepositoryTrait, NewAsset, QuoteMode};
 use crate::errors::{Error, Result, ValidationError};
 use crate::events::{DomainEvent, DomainEventSink, NoOpDomainEventSink};
 use crate::quotes::{DataSource, Quote, QuoteServiceTrait};
@@ -152,6 +158,114 @@ impl AlternativeAssetService {
         Some(meta)
     }
 
+    /// Loads an asset and checks that it is a liability.
+    fn get_liability(&self, liability_id: &str) -> Result<Asset> {
+        let liability = self.asset_repository.get_by_id(liability_id)?;
+        if liability.kind != AssetKind::Liability {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Asset {} is not a liability (kind: {:?})",
+                liability_id, liability.kind
+            ))));
+        }
+        Ok(liability)
+    }
+
+    /// Projects an asset's value on `date` from its valuation model, if it has one.
+    fn projected_value(
+        &self,
+        asset: &Asset,
+        in_service_date: Option<NaiveDate>,
+        date: NaiveDate,
+    ) -> Option<Decimal> {
+        let model = ValuationModel::from_metadata(asset.metadata.as_ref())?;
+        let load = |asset_id: &str, manual_only: bool| -> Vec<(NaiveDate, Decimal)> {
+            let ids = HashSet::from([asset_id.to_string()]);
+            self.quote_service
+                .get_quotes_in_range(&ids, NaiveDate::MIN, date)
+                .map(|quotes| {
+                    quotes
+                        .iter()
+                        .filter(|q| !manual_only || q.data_source == DataSource::Manual)
+                        .map(|q| (q.timestamp.date_naive(), q.close))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let appraisals = load(&asset.id, true);
+        let index = model
+            .index_asset_id()
+            .map(|id| load(id, false))
+            .unwrap_or_default();
+        ValuationSeries::new(model, appraisals, index, in_service_date).value_on(date)
+    }
+
+    /// Value of an alternative asset on `date`: the loan balance for liabilities
+    /// with loan terms, the projected value for modeled assets, otherwise the
+    /// latest valuation on or before the date.
+    fn value_as_of(&self, asset: &Asset, date: NaiveDate) -> Option<Decimal> {
+        if asset.kind == AssetKind::Liability {
+            if let Some(schedule) = LoanTerms::from_metadata(asset.metadata.as_ref())
+                .and_then(|terms| terms.schedule().ok())
+            {
+                return Some(schedule.balance_on(date));
+            }
+        }
+        if let Some(value) = self.projected_value(asset, Self::purchase_date(asset), date) {
+            return Some(value);
+        }
+        self.quote_service
+            .get_historical_quotes(&asset.id)
+            .ok()?
+            .into_iter()
+            .filter(|q| q.timestamp.date_naive() <= date)
+            .max_by_key(|q| q.timestamp)
+            .map(|q| q.close)
+    }
+
+    /// Original amount owed on a liability: the loan principal when loan terms
+    /// are attached, otherwise its first recorded valuation.
+    fn original_balance(&self, liability: &Asset) -> Option<Decimal> {
+        if let Some(terms) = LoanTerms::from_metadata(liability.metadata.as_ref()) {
+            return Some(terms.principal);
+        }
+        self.quote_service
+            .get_historical_quotes(&liability.id)
+            .ok()?
+            .into_iter()
+            .min_by_key(|q| q.timestamp)
+            .map(|q| q.close)
+    }
+
+    fn purchase_date(asset: &Asset) -> Option<NaiveDate> {
+        asset
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("purchase_date"))
+            .and_then(|v| v.as_str())
+            .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+    }
+
+    fn purchase_price(asset: &Asset) -> Option<Decimal> {
+        asset
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("purchase_price"))
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<Decimal>().ok())
+    }
+
+    /// Loads an asset and checks that it is a property.
+    fn get_property(&self, asset_id: &str) -> Result<Asset> {
+        let property = self.asset_repository.get_by_id(asset_id)?;
+        if property.kind != AssetKind::Property {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Asset {} is not a property (kind: {:?})",
+                asset_id, property.kind
+            ))));
+        }
+        Ok(property)
+    }
+
     /// Derives the display code for an alternative asset from its metadata.
     ///
     /// Uses the unified `sub_type` field (e.g., "gold" → "Gold", "mortgage" → "Mortgage").
@@ -521,8 +635,8 @@ impl AlternativeAssetServiceTrait for AlternativeAssetService {
                         "Invalid purchase price format".to_string(),
                     ))
                 })?;
-                let purchase_date = chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-                    .map_err(|_| {
+                let purchase_date =
+                    NaiveDate::parse_from_str(date_str, "%Y-%m-%d").map_err(|_| {
                         Error::Validation(ValidationError::InvalidInput(
                             "Invalid purchase date format".to_string(),
                         ))
@@ -564,6 +678,217 @@ impl AlternativeAssetServiceTrait for AlternativeAssetService {
         })
     }
 
+    async fn set_loan_terms(
+        &self,
+        liability_id: &str,
+        terms: Option<LoanTerms>,
+    ) -> Result<Option<AmortizationSchedule>> {
+        debug!("Setting loan terms for liability {}", liability_id);
+
+        let liability = self.get_liability(liability_id)?;
+        let schedule = terms.as_ref().map(LoanTerms::schedule).transpose()?;
+
+        let mut metadata_obj = liability
+            .metadata
+            .as_ref()
+            .and_then(|v| v.as_object().cloned())
+            .unwrap_or_default();
+        match &terms {
+            Some(terms) => {
+                metadata_obj.insert(LOAN_METADATA_KEY.to_string(), serde_json::to_value(terms)?);
+            }
+            None => {
+                metadata_obj.remove(LOAN_METADATA_KEY);
+            }
+        }
+
+        self.alternative_asset_repository
+            .update_asset_metadata(liability_id, Some(Value::Object(metadata_obj)))
+            .await?;
+
+        debug!(
+            "Loan terms {} for liability {}",
+            if terms.is_some() { "set" } else { "cleared" },
+            liability_id
+        );
+
+        Ok(schedule)
+    }
+
+    fn get_loan_schedule(&self, liability_id: &str) -> Result<Option<AmortizationSchedule>> {
+        let liability = self.get_liability(liability_id)?;
+        LoanTerms::from_metadata(liability.metadata.as_ref())
+            .map(|terms| terms.schedule())
+            .transpose()
+    }
+
+    async fn set_valuation_model(
+        &self,
+        asset_id: &str,
+        model: Option<ValuationModel>,
+    ) -> Result<()> {
+        debug!("Setting valuation model for asset {}", asset_id);
+
+        let asset = self.asset_repository.get_by_id(asset_id)?;
+        if !asset.kind.is_alternative() || asset.kind == AssetKind::Liability {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Asset {} does not support valuation models (kind: {:?})",
+                asset_id, asset.kind
+            ))));
+        }
+
+        if let Some(model) = &model {
+            model.validate()?;
+            if let Some(index_asset_id) = model.index_asset_id() {
+                // Fails with NotFound when the index series does not exist
+                self.asset_repository.get_by_id(index_asset_id)?;
+            }
+        }
+
+        let mut metadata_obj = asset
+            .metadata
+            .as_ref()
+            .and_then(|v| v.as_object().cloned())
+            .unwrap_or_default();
+        match &model {
+            Some(model) => {
+                metadata_obj.insert(
+                    VALUATION_MODEL_METADATA_KEY.to_string(),
+                    serde_json::to_value(model)?,
+                );
+            }
+            None => {
+                metadata_obj.remove(VALUATION_MODEL_METADATA_KEY);
+            }
+        }
+
+        self.alternative_asset_repository
+            .update_asset_metadata(asset_id, Some(Value::Object(metadata_obj)))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn add_property_cash_flow(
+        &self,
+        new_flow: NewPropertyCashFlow,
+    ) -> Result<PropertyCashFlow> {
+        let property = self.get_property(&new_flow.asset_id)?;
+        if new_flow.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Cash flow amount must be greater than 0".to_string(),
+            )));
+        }
+        let currency = new_flow
+            .currency
+            .unwrap_or_else(|| property.quote_ccy.clone());
+        if currency != property.quote_ccy {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Cash flow currency {} does not match property currency {}",
+                currency, property.quote_ccy
+            ))));
+        }
+
+        let flow = PropertyCashFlow {
+            id: Uuid::new_v4().to_string(),
+            asset_id: new_flow.asset_id,
+            kind: new_flow.kind,
+            category: new_flow.category.filter(|c| !c.trim().is_empty()),
+            amount: new_flow.amount,
+            currency,
+            date: new_flow.date,
+            notes: new_flow.notes,
+            created_at: Utc::now().naive_utc(),
+        };
+
+        debug!(
+            "Recording {} of {} for property {}",
+            flow.kind.as_str(),
+            flow.amount,
+            flow.asset_id
+        );
+
+        self.alternative_asset_repository
+            .insert_property_cash_flow(flow)
+            .await
+    }
+
+    async fn delete_property_cash_flow(&self, cash_flow_id: &str) -> Result<()> {
+        self.alternative_asset_repository
+            .delete_property_cash_flow(cash_flow_id)
+            .await
+    }
+
+    fn get_property_cash_flows(&self, asset_id: &str) -> Result<Vec<PropertyCashFlow>> {
+        self.get_property(asset_id)?;
+        self.alternative_asset_repository
+            .list_property_cash_flows(asset_id)
+    }
+
+    fn get_rental_performance(
+        &self,
+        asset_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<RentalPerformance> {
+        let property = self.get_property(asset_id)?;
+        let cash_flows = self
+            .alternative_asset_repository
+            .list_property_cash_flows(asset_id)?;
+
+        // Trailing 12 months ending on `as_of`
+        let period_start = as_of
+            .checked_sub_months(Months::new(12))
+            .and_then(|d| d.succ_opt())
+            .unwrap_or(as_of);
+
+        let property_value = self.value_as_of(&property, as_of).unwrap_or(Decimal::ZERO);
+
+        let liabilities: Vec<Asset> = self
+            .alternative_asset_repository
+            .find_liabilities_linked_to(asset_id)?
+            .iter()
+            .filter_map(|id| self.asset_repository.get_by_id(id).ok())
+            .filter(|a| a.kind == AssetKind::Liability)
+            .collect();
+
+        let linked_liabilities: Decimal = liabilities
+            .iter()
+            .filter_map(|l| self.value_as_of(l, as_of))
+            .sum();
+
+        let borrowed: Decimal = liabilities
+            .iter()
+            .filter_map(|l| self.original_balance(l))
+            .sum();
+        let cash_invested = Self::purchase_price(&property).map(|price| price - borrowed);
+
+        let scheduled: Vec<AmortizationSchedule> = liabilities
+            .iter()
+            .filter_map(|l| LoanTerms::from_metadata(l.metadata.as_ref()))
+            .filter_map(|terms| terms.schedule().ok())
+            .collect();
+        let scheduled_debt_service = (!scheduled.is_empty()).then(|| {
+            scheduled
+                .iter()
+                .flat_map(|s| s.entries.iter())
+                .filter(|e| e.date >= period_start && e.date <= as_of)
+                .map(|e| e.payment)
+                .sum()
+        });
+
+        Ok(RentalPerformance::compute(RentalPerformanceInput {
+            asset_id,
+            currency: &property.quote_ccy,
+            period_start,
+            period_end: as_of,
+            cash_flows: &cash_flows,
+            property_value,
+            linked_liabilities,
+            cash_invested,
+            scheduled_debt_service,
+        }))
+    }
+
     fn get_alternative_holdings(&self) -> Result<Vec<AlternativeHolding>> {
         debug!("Fetching alternative holdings");
 
@@ -606,7 +931,7 @@ impl AlternativeAssetServiceTrait for AlternativeAssetService {
                     .as_ref()
                     .and_then(|m| m.get("purchase_date"))
                     .and_then(|v| v.as_str())
-                    .and_then(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
+                    .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
 
                 // Extract linked_asset_id from metadata (for liabilities)
                 let linked_asset_id = asset
@@ -616,9 +941,24 @@ impl AlternativeAssetServiceTrait for AlternativeAssetService {
                     .and_then(|v| v.as_str())
                     .map(|s| s.to_string());
 
+                // Loan-backed liabilities report their scheduled balance and
+                // modeled assets their projected value as of today
+                let today = Utc::now().date_naive();
+                let derived_value = if asset.kind == AssetKind::Liability {
+                    LoanTerms::from_metadata(asset.metadata.as_ref())
+                        .and_then(|terms| terms.schedule().ok())
+                        .map(|schedule| schedule.balance_on(today))
+                } else {
+                    self.projected_value(&asset, purchase_date, today)
+                };
+                let (market_value, valuation_date) = match derived_value {
+                    Some(value) => (value, Utc::now()),
+                    None => (quote.close, quote.timestamp),
+                };
+
                 // Calculate unrealized gain if we have purchase price
                 let (unrealized_gain, unrealized_gain_pct) = if let Some(pp) = purchase_price {
-                    let gain = quote.close - pp;
+                    let gain = market_value - pp;
                     let pct = if pp != Decimal::ZERO {
                         Some(gain / pp)
                     } else {
@@ -638,12 +978,12 @@ impl AlternativeAssetServiceTrait for AlternativeAssetService {
                         .unwrap_or_else(|| asset.display_code.clone().unwrap_or_default()),
                     symbol: asset.display_code.unwrap_or_default(),
                     currency: asset.quote_ccy,
-                    market_value: quote.close,
+                    market_value,
                     purchase_price,
                     purchase_date,
                     unrealized_gain,
                     unrealized_gain_pct,
-                    valuation_date: quote.timestamp,
+                    valuation_date,
                     metadata: asset.metadata,
                     linked_asset_id,
                     notes: asset.notes,
@@ -699,9 +1039,9 @@ mod tests {
             name: "Beach House".to_string(),
             currency: "USD".to_string(),
             current_value: Decimal::new(450000, 0),
-            value_date: chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            value_date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
             purchase_price: Some(Decimal::new(400000, 0)),
-            purchase_date: Some(chrono::NaiveDate::from_ymd_opt(2020, 3, 1).unwrap()),
+            purchase_date: Some(NaiveDate::from_ymd_opt(2020, 3, 1).unwrap()),
             metadata: Some(json!({"sub_type": "residence"})),
             linked_asset_id: None,
         };
@@ -15,6 +15,9 @@ use super::alternative_assets_model::{
     LinkLiabilityRequest, LinkLiabilityResponse, UpdateAssetDetailsRequest,
     UpdateAssetDetailsResponse, UpdateValuationRequest, UpdateValuationResponse,
 };
+use super::loan_model::{AmortizationSchedule, LoanTerms};
+use super::rental_model::{NewPropertyCashFlow, PropertyCashFlow, RentalPerformance};
+use super::valuation_model::ValuationModel;
 use crate::errors::Result;
 
 /// Trait defining the contract for Alternative Asset service operations.
@@ -114,6 +117,80 @@ pub trait AlternativeAssetServiceTrait: Send + Sync {
         request: UpdateAssetDetailsRequest,
     ) -> Result<UpdateAssetDetailsResponse>;
 
+    /// Attaches loan terms to a liability, or removes them when `terms` is `None`.
+    ///
+    /// With terms attached, the liability's balance is derived from its
+    /// amortization schedule instead of manual valuations. Other metadata
+    /// keys (sub_type, linked_asset_id, ...) are preserved.
+    ///
+    /// # Arguments
+    /// * `liability_id` - The ID of the liability
+    /// * `terms` - The loan terms, or `None` to clear them
+    ///
+    /// # Returns
+    /// The generated schedule when terms were set
+    async fn set_loan_terms(
+        &self,
+        liability_id: &str,
+        terms: Option<LoanTerms>,
+    ) -> Result<Option<AmortizationSchedule>>;
+
+    /// Gets the amortization schedule of a liability.
+    ///
+    /// # Arguments
+    /// * `liability_id` - The ID of the liability
+    ///
+    /// # Returns
+    /// The schedule, or `None` if the liability has no loan terms
+    fn get_loan_schedule(&self, liability_id: &str) -> Result<Option<AmortizationSchedule>>;
+
+    /// Sets the valuation model of an alternative asset, or removes it when `model` is `None`.
+    ///
+    /// With a model set, the asset's value is projected daily from its most
+    /// recent appraisal (index-linked, fixed appreciation or depreciation)
+    /// instead of staying flat until the next manual valuation.
+    ///
+    /// # Arguments
+    /// * `asset_id` - The ID of the asset (any alternative kind except liabilities)
+    /// * `model` - The valuation model, or `None` to clear it
+    async fn set_valuation_model(
+        &self,
+        asset_id: &str,
+        model: Option<ValuationModel>,
+    ) -> Result<()>;
+
+    /// Records a rental income, operating expense or mortgage payment entry for a property.
+    ///
+    /// # Arguments
+    /// * `new_flow` - The cash-flow entry (currency defaults to the property's)
+    ///
+    /// # Returns
+    /// The stored entry
+    async fn add_property_cash_flow(
+        &self,
+        new_flow: NewPropertyCashFlow,
+    ) -> Result<PropertyCashFlow>;
+
+    /// Deletes a property cash-flow entry.
+    async fn delete_property_cash_flow(&self, cash_flow_id: &str) -> Result<()>;
+
+    /// Lists the cash-flow entries of a property, oldest first.
+    fn get_property_cash_flows(&self, asset_id: &str) -> Result<Vec<PropertyCashFlow>>;
+
+    /// Computes rental metrics of a property for the 12 months ending `as_of`.
+    ///
+    /// Includes net operating income, cap rate, cash-on-cash return and equity
+    /// (property value minus the balance of linked liabilities).
+    ///
+    /// # Arguments
+    /// * `asset_id` - The ID of the property
+    /// * `as_of` - End of the trailing period (inclusive)
+    fn get_rental_performance(
+        &self,
+        asset_id: &str,
+        as_of: chrono::NaiveDate,
+    ) -> Result<RentalPerformance>;
+
     /// Gets all alternative holdings (assets with their latest valuations).
     ///
     /// This retrieves all alternative assets (Property, Vehicle, Collectible,
@@ -164,6 +241,15 @@ pub trait AlternativeAssetRepositoryTrait: Send + Sync {
     /// List of liability asset IDs
     fn find_liabilities_linked_to(&self, linked_asset_id: &str) -> Result<Vec<String>>;
 
+    /// Lists the cash-flow entries of a property, ordered by date.
+    fn list_property_cash_flows(&self, asset_id: &str) -> Result<Vec<PropertyCashFlow>>;
+
+    /// Inserts a property cash-flow entry.
+    async fn insert_property_cash_flow(&self, flow: PropertyCashFlow) -> Result<PropertyCashFlow>;
+
+    /// Deletes a property cash-flow entry.
+    async fn delete_property_cash_flow(&self, cash_flow_id: &str) -> Result<()>;
+
     /// Updates an asset's details (name and/or metadata).
     ///
     /// # Arguments
@@ -0,0 +1,403 @@
+//! Loan and mortgage models for liabilities.
+//!
+//! A liability normally carries whatever balance the user last entered as a
+//! valuation quote. When loan terms are attached (stored under the `loan` key
+//! of the liability's metadata), the balance is instead derived from an
+//! amortization schedule, so net worth history follows the real payoff curve.
+//!
+//! The schedule uses the standard annuity formula per payment period:
+//!
+//! ```text
+//! payment = P * r / (1 - (1 + r)^-n)
+//! ```
+//!
+//! where `r` is the periodic rate and `n` the number of remaining payments.
+//! For variable-rate loans the payment is recomputed from the outstanding
+//! balance whenever a rate change takes effect. Extra payments go straight to
+//! principal and shorten the term; the regular payment is left unchanged.
+
+use chrono::{Duration, Months, NaiveDate};
+use rust_decimal::prelude::*;
+use rust_decimal::MathematicalOps;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Metadata key holding the JSON-encoded `LoanTerms` of a liability.
+pub const LOAN_METADATA_KEY: &str = "loan";
+
+/// Monetary amounts in the schedule are rounded to cents.
+const MONEY_DP: u32 = 2;
+
+/// Longest supported loan term (50 years).
+const MAX_LOAN_TERM_MONTHS: u32 = 600;
+
+/// Interest rate behaviour of a loan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RateType {
+    /// One rate for the whole term.
+    #[default]
+    Fixed,
+    /// Rate follows `rate_changes`; the payment is recomputed at each change.
+    Variable,
+}
+
+/// How often regular payments are made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PaymentFrequency {
+    Weekly,
+    Biweekly,
+    #[default]
+    Monthly,
+    Quarterly,
+    Annually,
+}
+
+impl PaymentFrequency {
+    /// Number of payments per year.
+    pub fn periods_per_year(&self) -> u32 {
+        match self {
+            PaymentFrequency::Weekly => 52,
+            PaymentFrequency::Biweekly => 26,
+            PaymentFrequency::Monthly => 12,
+            PaymentFrequency::Quarterly => 4,
+            PaymentFrequency::Annually => 1,
+        }
+    }
+
+    /// Date of the payment `periods` steps after `anchor`.
+    fn advance(&self, anchor: NaiveDate, periods: u32) -> Option<NaiveDate> {
+        match self {
+            PaymentFrequency::Weekly => anchor.checked_add_signed(Duration::weeks(periods as i64)),
+            PaymentFrequency::Biweekly => {
+                anchor.checked_add_signed(Duration::weeks(2 * periods as i64))
+            }
+            PaymentFrequency::Monthly => anchor.checked_add_months(Months::new(periods)),
+            PaymentFrequency::Quarterly => anchor.checked_add_months(Months::new(3 * periods)),
+            PaymentFrequency::Annually => anchor.checked_add_months(Months::new(12 * periods)),
+        }
+    }
+}
+
+/// A change of the annual rate of a variable-rate loan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateChange {
+    /// First day the new rate applies.
+    pub effective_date: NaiveDate,
+    /// Annual nominal rate as a fraction (0.045 = 4.5%).
+    pub annual_rate: Decimal,
+}
+
+/// A one-off payment towards principal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraPayment {
+    /// Date of the payment. Applied with the first regular payment on or after it.
+    pub date: NaiveDate,
+    pub amount: Decimal,
+}
+
+/// Terms of an amortizing loan or mortgage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanTerms {
+    /// Amount borrowed, in the liability's currency.
+    pub principal: Decimal,
+    /// Disbursement date; the balance is zero before it.
+    pub start_date: NaiveDate,
+    /// Annual nominal rate as a fraction (0.045 = 4.5%).
+    pub annual_rate: Decimal,
+    #[serde(default)]
+    pub rate_type: RateType,
+    /// Rate changes for variable-rate loans, in any order.
+    #[serde(default)]
+    pub rate_changes: Vec<RateChange>,
+    /// Original term in months.
+    pub term_months: u32,
+    #[serde(default)]
+    pub payment_frequency: PaymentFrequency,
+    /// Date of the first regular payment. Defaults to one period after `start_date`.
+    #[serde(default)]
+    pub first_payment_date: Option<NaiveDate>,
+    /// Additional principal paid with every regular payment.
+    #[serde(default)]
+    pub recurring_extra_payment: Option<Decimal>,
+    /// One-off principal prepayments.
+    #[serde(default)]
+    pub extra_payments: Vec<ExtraPayment>,
+}
+
+/// One payment in an amortization schedule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmortizationEntry {
+    /// 1-based payment number.
+    pub period: u32,
+    pub date: NaiveDate,
+    /// Annual rate in effect for this period.
+    pub annual_rate: Decimal,
+    /// Total paid in this period (interest + principal + extra principal).
+    pub payment: Decimal,
+    pub interest: Decimal,
+    /// Scheduled principal portion of the regular payment.
+    pub principal: Decimal,
+    /// Additional principal from recurring and one-off extra payments.
+    pub extra_principal: Decimal,
+    /// Outstanding balance after this payment.
+    pub balance: Decimal,
+}
+
+/// Full amortization schedule of a loan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmortizationSchedule {
+    pub terms: LoanTerms,
+    pub entries: Vec<AmortizationEntry>,
+    pub total_interest: Decimal,
+    pub total_paid: Decimal,
+    /// Date of the final payment (earlier than the term if extra payments were made).
+    pub payoff_date: Option<NaiveDate>,
+}
+
+impl LoanTerms {
+    /// Read loan terms from liability metadata.
+    ///
+    /// Accepts the terms either as a JSON object or as a JSON-encoded string,
+    /// since generic metadata edits store values as strings.
+    pub fn from_metadata(metadata: Option<&Value>) -> Option<Self> {
+        let raw = metadata?.get(LOAN_METADATA_KEY)?;
+        match raw {
+            Value::String(s) => serde_json::from_str(s).ok(),
+            other => serde_json::from_value(other.clone()).ok(),
+        }
+    }
+
+    /// Validate the terms before building a schedule.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: &str| -> Result<()> {
+            Err(Error::Validation(ValidationError::InvalidInput(
+                msg.to_string(),
+            )))
+        };
+
+        if self.principal <= Decimal::ZERO {
+            return invalid("Loan principal must be greater than 0");
+        }
+        if self.term_months == 0 {
+            return invalid("Loan term must be at least one month");
+        }
+        if self.term_months > MAX_LOAN_TERM_MONTHS {
+            return invalid("Loan term cannot be longer than 50 years");
+        }
+        if self.annual_rate < Decimal::ZERO
+            || self
+                .rate_changes
+                .iter()
+                .any(|c| c.annual_rate < Decimal::ZERO)
+        {
+            return invalid("Loan interest rate cannot be negative");
+        }
+        if self.annual_rate >= Decimal::ONE
+            || self
+                .rate_changes
+                .iter()
+                .any(|c| c.annual_rate >= Decimal::ONE)
+        {
+            return invalid("Loan interest rate must be a fraction (e.g. 0.045 for 4.5%)");
+        }
+        if self.rate_type == RateType::Fixed && !self.rate_changes.is_empty() {
+            return invalid("Fixed-rate loans cannot have rate changes");
+        }
+        if self
+            .first_payment_date
+            .is_some_and(|d| d <= self.start_date)
+        {
+            return invalid("First payment date must be after the loan start date");
+        }
+        if self
+            .recurring_extra_payment
+            .is_some_and(|a| a < Decimal::ZERO)
+            || self
+                .extra_payments
+                .iter()
+                .any(|p| p.amount <= Decimal::ZERO)
+        {
+            return invalid("Extra payments must be positive");
+        }
+        Ok(())
+    }
+
+    /// Number of regular payments over the full term.
+    pub fn number_of_payments(&self) -> u32 {
+        let per_year = self.payment_frequency.periods_per_year();
+        ((self.term_months * per_year) as f64 / 12.0)
+            .round()
+            .max(1.0) as u32
+    }
+
+    /// Annual rate in effect on `date`.
+    pub fn rate_on(&self, date: NaiveDate) -> Decimal {
+        if self.rate_type == RateType::Fixed {
+            return self.annual_rate;
+        }
+        self.rate_changes
+            .iter()
+            .filter(|c| c.effective_date <= date)
+            .max_by_key(|c| c.effective_date)
+            .map(|c| c.annual_rate)
+            .unwrap_or(self.annual_rate)
+    }
+
+    fn first_payment(&self) -> Result<NaiveDate> {
+        match self.first_payment_date {
+            Some(date) => Ok(date),
+            None => self
+                .payment_frequency
+                .advance(self.start_date, 1)
+                .ok_or_else(|| {
+                    Error::Validation(ValidationError::InvalidInput(
+                        "Loan start date is out of range".to_string(),
+                    ))
+                }),
+        }
+    }
+
+    /// Build the amortization schedule for these terms.
+    pub fn schedule(&self) -> Result<AmortizationSchedule> {
+        self.validate()?;
+
+        let per_year = Decimal::from(self.payment_frequency.periods_per_year());
+        let total_periods = self.number_of_payments();
+        let first_payment = self.first_payment()?;
+        let recurring_extra = self.recurring_extra_payment.unwrap_or(Decimal::ZERO);
+
+        let mut extras = self.extra_payments.clone();
+        extras.sort_by_key(|p| p.date);
+        let mut extras = extras.into_iter().peekable();
+
+        let mut balance = self.principal;
+        let mut current_rate: Option<Decimal> = None;
+        let mut payment = Decimal::ZERO;
+        let mut entries = Vec::new();
+
+        for period in 1..=total_periods {
+            if balance <= Decimal::ZERO {
+                break;
+            }
+            let Some(date) = self.payment_frequency.advance(first_payment, period - 1) else {
+                break;
+            };
+
+            // Interest for this period accrues at the rate in effect at its start.
+            let period_start = if period == 1 {
+                self.start_date
+            } else {
+                entries
+                    .last()
+                    .map(|e: &AmortizationEntry| e.date)
+                    .unwrap_or(self.start_date)
+            };
+            let annual_rate = self.rate_on(period_start);
+            let periodic_rate = annual_rate / per_year;
+
+            if current_rate != Some(annual_rate) {
+                let remaining = total_periods - period + 1;
+                payment = level_payment(balance, periodic_rate, remaining)?;
+                current_rate = Some(annual_rate);
+            }
+
+            let interest = (balance * periodic_rate).round_dp(MONEY_DP);
+            let is_last = period == total_periods;
+            let principal = if is_last {
+                balance
+            } else {
+                (payment - interest).max(Decimal::ZERO).min(balance)
+            };
+
+            let mut extra = recurring_extra;
+            while let Some(p) = extras.next_if(|p| p.date <= date) {
+                extra += p.amount;
+            }
+            let extra_principal = extra.min(balance - principal);
+
+            balance -= principal + extra_principal;
+            entries.push(AmortizationEntry {
+                period,
+                date,
+                annual_rate,
+                payment: interest + principal + extra_principal,
+                interest,
+                principal,
+                extra_principal,
+                balance,
+            });
+        }
+
+        let total_interest = entries.iter().map(|e| e.interest).sum();
+        let total_paid = entries.iter().map(|e| e.payment).sum();
+        let payoff_date = entries.last().map(|e| e.date);
+
+        Ok(AmortizationSchedule {
+            terms: self.clone(),
+            entries,
+            total_interest,
+            total_paid,
+            payoff_date,
+        })
+    }
+}
+
+impl AmortizationSchedule {
+    /// Outstanding balance at the end of `date`.
+    ///
+    /// Zero before the loan starts and after it is paid off.
+    pub fn balance_on(&self, date: NaiveDate) -> Decimal {
+        if date < self.terms.start_date {
+            return Decimal::ZERO;
+        }
+        self.entries
+            .iter()
+            .take_while(|e| e.date <= date)
+            .last()
+            .map(|e| e.balance)
+            .unwrap_or(self.terms.principal)
+    }
+
+    /// Date of the most recent payment on or before `date`, or the start date.
+    pub fn last_event_on(&self, date: NaiveDate) -> NaiveDate {
+        self.entries
+            .iter()
+            .take_while(|e| e.date <= date)
+            .last()
+            .map(|e| e.date)
+            .unwrap_or(self.terms.start_date)
+    }
+}
+
+/// Level payment that amortizes `balance` over `periods` at `periodic_rate`.
+fn level_payment(balance: Decimal, periodic_rate: Decimal, periods: u32) -> Result<Decimal> {
+    if periods == 0 {
+        return Ok(balance);
+    }
+    if periodic_rate.is_zero() {
+        return Ok((balance / Decimal::from(periods)).round_dp(MONEY_DP));
+    }
+    let payment = (Decimal::ONE + periodic_rate)
+        .checked_powi(periods as i64)
+        .and_then(|growth| {
+            balance
+                .checked_mul(periodic_rate)?
+                .checked_mul(growth)?
+                .checked_div(growth - Decimal::ONE)
+        })
+        .ok_or_else(|| {
+            Error::Validation(ValidationError::InvalidInput(
+                "Loan amount and rate are too large to compute a payment".to_string(),
+            ))
+        })?;
+    Ok(payment.round_dp_with_strategy(MONEY_DP, RoundingStrategy::AwayFromZero))
+}
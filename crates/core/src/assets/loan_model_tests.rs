@@ -0,0 +1,221 @@
+//! Tests for loan terms and amortization schedules.
+
+#[cfg(test)]
+mod tests {
+    use crate::assets::{ExtraPayment, LoanTerms, PaymentFrequency, RateChange, RateType};
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn mortgage() -> LoanTerms {
+        LoanTerms {
+            principal: dec!(100000),
+            start_date: date(2024, 1, 1),
+            annual_rate: dec!(0.06),
+            rate_type: RateType::Fixed,
+            rate_changes: vec![],
+            term_months: 360,
+            payment_frequency: PaymentFrequency::Monthly,
+            first_payment_date: None,
+            recurring_extra_payment: None,
+            extra_payments: vec![],
+        }
+    }
+
+    #[test]
+    fn test_fixed_rate_schedule_matches_annuity_payment() {
+        let schedule = mortgage().schedule().unwrap();
+
+        assert_eq!(schedule.entries.len(), 360);
+        let first = &schedule.entries[0];
+        assert_eq!(first.date, date(2024, 2, 1));
+        assert_eq!(first.payment, dec!(599.56));
+        assert_eq!(first.interest, dec!(500.00));
+        assert_eq!(first.principal, dec!(99.56));
+        assert_eq!(first.balance, dec!(99900.44));
+
+        let last = schedule.entries.last().unwrap();
+        assert_eq!(last.balance, Decimal::ZERO);
+        assert_eq!(schedule.payoff_date, Some(date(2054, 1, 1)));
+
+        let principal_paid: Decimal = schedule
+            .entries
+            .iter()
+            .map(|e| e.principal + e.extra_principal)
+            .sum();
+        assert_eq!(principal_paid, dec!(100000));
+        assert_eq!(schedule.total_paid, schedule.total_interest + dec!(100000));
+    }
+
+    #[test]
+    fn test_zero_rate_loan_splits_principal_evenly() {
+        let terms = LoanTerms {
+            principal: dec!(1200),
+            annual_rate: Decimal::ZERO,
+            term_months: 12,
+            ..mortgage()
+        };
+        let schedule = terms.schedule().unwrap();
+
+        assert_eq!(schedule.entries.len(), 12);
+        assert!(schedule
+            .entries
+            .iter()
+            .all(|e| e.interest.is_zero() && e.payment == dec!(100)));
+        assert_eq!(schedule.total_interest, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_extra_payments_shorten_term_and_reduce_interest() {
+        let base = mortgage().schedule().unwrap();
+        let terms = LoanTerms {
+            recurring_extra_payment: Some(dec!(200)),
+            extra_payments: vec![ExtraPayment {
+                date: date(2025, 6, 15),
+                amount: dec!(10000),
+            }],
+            ..mortgage()
+        };
+        let schedule = terms.schedule().unwrap();
+
+        assert!(schedule.entries.len() < base.entries.len());
+        assert!(schedule.total_interest < base.total_interest);
+        assert_eq!(schedule.entries.last().unwrap().balance, Decimal::ZERO);
+
+        // The one-off prepayment lands on the first payment on or after its date.
+        let lump = schedule
+            .entries
+            .iter()
+            .find(|e| e.date == date(2025, 7, 1))
+            .unwrap();
+        assert_eq!(lump.extra_principal, dec!(10200));
+    }
+
+    #[test]
+    fn test_variable_rate_recomputes_payment_at_rate_change() {
+        let terms = LoanTerms {
+            rate_type: RateType::Variable,
+            rate_changes: vec![RateChange {
+                effective_date: date(2025, 1, 1),
+                annual_rate: dec!(0.08),
+            }],
+            ..mortgage()
+        };
+        let schedule = terms.schedule().unwrap();
+
+        let before = &schedule.entries[10];
+        let after = &schedule.entries[13];
+        assert_eq!(before.annual_rate, dec!(0.06));
+        assert_eq!(after.annual_rate, dec!(0.08));
+        assert!(after.payment > before.payment);
+        assert_eq!(schedule.entries.len(), 360);
+        assert_eq!(schedule.entries.last().unwrap().balance, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_balance_on_follows_schedule() {
+        let schedule = mortgage().schedule().unwrap();
+
+        assert_eq!(schedule.balance_on(date(2023, 12, 31)), Decimal::ZERO);
+        assert_eq!(schedule.balance_on(date(2024, 1, 15)), dec!(100000));
+        assert_eq!(schedule.balance_on(date(2024, 2, 1)), dec!(99900.44));
+        assert_eq!(schedule.balance_on(date(2024, 2, 20)), dec!(99900.44));
+        assert_eq!(schedule.balance_on(date(2060, 1, 1)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_biweekly_schedule_uses_26_periods_per_year() {
+        let terms = LoanTerms {
+            term_months: 12,
+            principal: dec!(2600),
+            annual_rate: Decimal::ZERO,
+            payment_frequency: PaymentFrequency::Biweekly,
+            ..mortgage()
+        };
+        let schedule = terms.schedule().unwrap();
+
+        assert_eq!(schedule.entries.len(), 26);
+        assert_eq!(schedule.entries[0].date, date(2024, 1, 15));
+        assert_eq!(schedule.entries[1].date, date(2024, 1, 29));
+    }
+
+    #[test]
+    fn test_validation_rejects_bad_terms() {
+        let zero_principal = LoanTerms {
+            principal: Decimal::ZERO,
+            ..mortgage()
+        };
+        assert!(zero_principal.schedule().is_err());
+
+        let percent_rate = LoanTerms {
+            annual_rate: dec!(6),
+            ..mortgage()
+        };
+        assert!(percent_rate.validate().is_err());
+
+        let percent_rate_change = LoanTerms {
+            rate_type: RateType::Variable,
+            rate_changes: vec![RateChange {
+                effective_date: date(2025, 1, 1),
+                annual_rate: dec!(4.5),
+            }],
+            ..mortgage()
+        };
+        assert!(percent_rate_change.validate().is_err());
+
+        let fixed_with_changes = LoanTerms {
+            rate_changes: vec![RateChange {
+                effective_date: date(2025, 1, 1),
+                annual_rate: dec!(0.05),
+            }],
+            ..mortgage()
+        };
+        assert!(fixed_with_changes.validate().is_err());
+
+        let too_long = LoanTerms {
+            term_months: 100_000,
+            ..mortgage()
+        };
+        assert!(too_long.validate().is_err());
+    }
+
+    #[test]
+    fn test_payment_overflow_is_an_error() {
+        let huge = LoanTerms {
+            principal: Decimal::MAX / dec!(2),
+            annual_rate: dec!(0.99),
+            payment_frequency: PaymentFrequency::Weekly,
+            ..mortgage()
+        };
+        assert!(huge.schedule().is_err());
+    }
+
+    #[test]
+    fn test_from_metadata_accepts_object_and_string() {
+        let terms = mortgage();
+        let as_object = json!({ "loan": serde_json::to_value(&terms).unwrap() });
+        assert_eq!(
+            LoanTerms::from_metadata(Some(&as_object)),
+            Some(terms.clone())
+        );
+
+        let as_string = json!({ "loan": serde_json::to_string(&terms).unwrap() });
+        assert_eq!(LoanTerms::from_metadata(Some(&as_string)), Some(terms));
+
+        assert_eq!(LoanTerms::from_metadata(Some(&json!({}))), None);
+        assert_eq!(LoanTerms::from_metadata(None), None);
+    }
+
+    #[test]
+    fn test_terms_serialize_camel_case() {
+        let value = serde_json::to_value(mortgage()).unwrap();
+        assert_eq!(value["termMonths"], json!(360));
+        assert_eq!(value["rateType"], json!("FIXED"));
+        assert_eq!(value["paymentFrequency"], json!("MONTHLY"));
+    }
+}
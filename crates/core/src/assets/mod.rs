@@ -10,9 +10,18 @@ mod assets_service;
 mod assets_traits;
 mod auto_classification;
 mod classification_service;
+mod loan_model;
+mod rental_model;
+mod valuation_model;
 
 #[cfg(test)]
 mod assets_model_tests;
+#[cfg(test)]
+mod loan_model_tests;
+#[cfg(test)]
+mod rental_model_tests;
+#[cfg(test)]
+mod valuation_model_tests;
 
 // Re-export the public interface
 pub use alternative_assets_model::{
@@ -39,3 +48,12 @@ pub use auto_classification::{
 pub use classification_service::{
     AssetClassificationService, AssetClassifications, CategoryWithWeight,
 };
+pub use loan_model::{
+    AmortizationEntry, AmortizationSchedule, ExtraPayment, LoanTerms, PaymentFrequency, RateChange,
+    RateType, LOAN_METADATA_KEY,
+};
+pub use rental_model::{
+    NewPropertyCashFlow, PropertyCashFlow, PropertyCashFlowKind, RentalPerformance,
+    RentalPerformanceInput,
+};
+pub use valuation_model::{ValuationModel, ValuationSeries, VALUATION_MODEL_METADATA_KEY};
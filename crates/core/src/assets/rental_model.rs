@@ -0,0 +1,190 @@
+//! Rental property cash-flow models.
+//!
+//! Rental income, operating expenses and mortgage payments are recorded
+//! against a property asset. From those entries and the property's current
+//! value, `RentalPerformance` derives the usual investor metrics:
+//!
+//! - Net operating income (NOI) = income - operating expenses
+//! - Cap rate = annualized NOI / property value
+//! - Cash flow = NOI - debt service (mortgage payments)
+//! - Cash-on-cash return = annualized cash flow / cash invested
+//! - Equity = property value - balance of linked liabilities
+
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Kind of a property cash-flow entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PropertyCashFlowKind {
+    /// Rent and other property income.
+    Income,
+    /// Operating expenses (taxes, insurance, maintenance, management, ...).
+    Expense,
+    /// Mortgage payment (debt service, excluded from NOI).
+    MortgagePayment,
+}
+
+impl PropertyCashFlowKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PropertyCashFlowKind::Income => "INCOME",
+            PropertyCashFlowKind::Expense => "EXPENSE",
+            PropertyCashFlowKind::MortgagePayment => "MORTGAGE_PAYMENT",
+        }
+    }
+}
+
+impl std::str::FromStr for PropertyCashFlowKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "INCOME" => Ok(PropertyCashFlowKind::Income),
+            "EXPENSE" => Ok(PropertyCashFlowKind::Expense),
+            "MORTGAGE_PAYMENT" => Ok(PropertyCashFlowKind::MortgagePayment),
+            other => Err(format!("Unknown property cash flow kind: {}", other)),
+        }
+    }
+}
+
+/// A recorded cash-flow entry of a property.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyCashFlow {
+    pub id: String,
+    pub asset_id: String,
+    pub kind: PropertyCashFlowKind,
+    /// Free-form category (e.g. "rent", "property_tax", "repairs").
+    pub category: Option<String>,
+    /// Positive amount in the property's currency.
+    pub amount: Decimal,
+    pub currency: String,
+    pub date: NaiveDate,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Input for recording a property cash-flow entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPropertyCashFlow {
+    pub asset_id: String,
+    pub kind: PropertyCashFlowKind,
+    pub category: Option<String>,
+    pub amount: Decimal,
+    /// Defaults to the property's currency.
+    pub currency: Option<String>,
+    pub date: NaiveDate,
+    pub notes: Option<String>,
+}
+
+/// Rental metrics of a property over a period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RentalPerformance {
+    pub asset_id: String,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    /// Currency of all amounts (the property's currency).
+    pub currency: String,
+    pub gross_income: Decimal,
+    pub operating_expenses: Decimal,
+    pub net_operating_income: Decimal,
+    /// Mortgage payments in the period (recorded, or scheduled from linked loans).
+    pub debt_service: Decimal,
+    /// NOI minus debt service.
+    pub cash_flow: Decimal,
+    /// Property value at the end of the period.
+    pub property_value: Decimal,
+    /// Balance of liabilities linked to the property at the end of the period.
+    pub linked_liabilities: Decimal,
+    /// Property value minus linked liabilities.
+    pub equity: Decimal,
+    /// Cash put into the property (purchase price minus borrowed principal).
+    pub cash_invested: Option<Decimal>,
+    /// Annualized NOI / property value.
+    pub cap_rate: Option<Decimal>,
+    /// Annualized cash flow / cash invested.
+    pub cash_on_cash_return: Option<Decimal>,
+}
+
+/// Inputs for computing `RentalPerformance`, all in the property's currency.
+#[derive(Debug, Clone)]
+pub struct RentalPerformanceInput<'a> {
+    pub asset_id: &'a str,
+    pub currency: &'a str,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub cash_flows: &'a [PropertyCashFlow],
+    pub property_value: Decimal,
+    pub linked_liabilities: Decimal,
+    pub cash_invested: Option<Decimal>,
+    /// Debt service derived from linked loan schedules, used when no mortgage
+    /// payments were recorded in the period.
+    pub scheduled_debt_service: Option<Decimal>,
+}
+
+impl RentalPerformance {
+    /// Computes rental metrics from cash-flow entries within the period (inclusive).
+    pub fn compute(input: RentalPerformanceInput<'_>) -> Self {
+        let in_period = input
+            .cash_flows
+            .iter()
+            .filter(|f| f.date >= input.period_start && f.date <= input.period_end);
+
+        let mut gross_income = Decimal::ZERO;
+        let mut operating_expenses = Decimal::ZERO;
+        let mut recorded_debt_service = Decimal::ZERO;
+        let mut has_mortgage_entries = false;
+        for flow in in_period {
+            match flow.kind {
+                PropertyCashFlowKind::Income => gross_income += flow.amount,
+                PropertyCashFlowKind::Expense => operating_expenses += flow.amount,
+                PropertyCashFlowKind::MortgagePayment => {
+                    recorded_debt_service += flow.amount;
+                    has_mortgage_entries = true;
+                }
+            }
+        }
+
+        let debt_service = if has_mortgage_entries {
+            recorded_debt_service
+        } else {
+            input.scheduled_debt_service.unwrap_or(Decimal::ZERO)
+        };
+
+        let net_operating_income = gross_income - operating_expenses;
+        let cash_flow = net_operating_income - debt_service;
+
+        // Annualize so cap rate and cash-on-cash are comparable for any period length
+        let days = (input.period_end - input.period_start).num_days() + 1;
+        let annualize = |amount: Decimal| amount * Decimal::from(365) / Decimal::from(days.max(1));
+
+        let cap_rate = (input.property_value > Decimal::ZERO)
+            .then(|| (annualize(net_operating_income) / input.property_value).round_dp(4));
+        let cash_on_cash_return = input
+            .cash_invested
+            .filter(|c| *c > Decimal::ZERO)
+            .map(|c| (annualize(cash_flow) / c).round_dp(4));
+
+        Self {
+            asset_id: input.asset_id.to_string(),
+            period_start: input.period_start,
+            period_end: input.period_end,
+            currency: input.currency.to_string(),
+            gross_income,
+            operating_expenses,
+            net_operating_income,
+            debt_service,
+            cash_flow,
+            property_value: input.property_value,
+            linked_liabilities: input.linked_liabilities,
+            equity: input.property_value - input.linked_liabilities,
+            cash_invested: input.cash_invested,
+            cap_rate,
+            cash_on_cash_return,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Tests for rental property cash-flow metrics.
+
+#[cfg(test)]
+mod tests {
+    use crate::assets::{
+        PropertyCashFlow, PropertyCashFlowKind, RentalPerformance, RentalPerformanceInput,
+    };
+    use chrono::{NaiveDate, Utc};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn flow(kind: PropertyCashFlowKind, amount: Decimal, date: NaiveDate) -> PropertyCashFlow {
+        PropertyCashFlow {
+            id: format!("{:?}-{}", kind, date),
+            asset_id: "PROP-rental".to_string(),
+            kind,
+            category: None,
+            amount,
+            currency: "USD".to_string(),
+            date,
+            notes: None,
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    /// One year of 2,000/month rent, 500/month expenses and 1,000/month mortgage.
+    fn year_of_flows() -> Vec<PropertyCashFlow> {
+        (1..=12)
+            .flat_map(|m| {
+                let d = date(2024, m, 1);
+                vec![
+                    flow(PropertyCashFlowKind::Income, dec!(2000), d),
+                    flow(PropertyCashFlowKind::Expense, dec!(500), d),
+                    flow(PropertyCashFlowKind::MortgagePayment, dec!(1000), d),
+                ]
+            })
+            .collect()
+    }
+
+    fn input(flows: &[PropertyCashFlow]) -> RentalPerformanceInput<'_> {
+        RentalPerformanceInput {
+            asset_id: "PROP-rental",
+            currency: "USD",
+            period_start: date(2024, 1, 1),
+            period_end: date(2024, 12, 31),
+            cash_flows: flows,
+            property_value: dec!(300000),
+            linked_liabilities: dec!(180000),
+            cash_invested: Some(dec!(60000)),
+            scheduled_debt_service: None,
+        }
+    }
+
+    #[test]
+    fn test_noi_cap_rate_and_cash_on_cash() {
+        let flows = year_of_flows();
+        let perf = RentalPerformance::compute(RentalPerformanceInput {
+            period_end: date(2024, 12, 30),
+            ..input(&flows)
+        });
+
+        assert_eq!(perf.gross_income, dec!(24000));
+        assert_eq!(perf.operating_expenses, dec!(6000));
+        assert_eq!(perf.net_operating_income, dec!(18000));
+        assert_eq!(perf.debt_service, dec!(12000));
+        assert_eq!(perf.cash_flow, dec!(6000));
+        assert_eq!(perf.equity, dec!(120000));
+        assert_eq!(perf.cap_rate, Some(dec!(0.06)));
+        assert_eq!(perf.cash_on_cash_return, Some(dec!(0.1)));
+    }
+
+    #[test]
+    fn test_flows_outside_period_are_ignored() {
+        let flows = year_of_flows();
+        let perf = RentalPerformance::compute(RentalPerformanceInput {
+            period_start: date(2024, 7, 1),
+            ..input(&flows)
+        });
+
+        assert_eq!(perf.gross_income, dec!(12000));
+        assert_eq!(perf.net_operating_income, dec!(9000));
+    }
+
+    #[test]
+    fn test_scheduled_debt_service_used_without_recorded_payments() {
+        let flows: Vec<PropertyCashFlow> = year_of_flows()
+            .into_iter()
+            .filter(|f| f.kind != PropertyCashFlowKind::MortgagePayment)
+            .collect();
+        let perf = RentalPerformance::compute(RentalPerformanceInput {
+            scheduled_debt_service: Some(dec!(9000)),
+            ..input(&flows)
+        });
+
+        assert_eq!(perf.debt_service, dec!(9000));
+        assert_eq!(perf.cash_flow, dec!(9000));
+    }
+
+    #[test]
+    fn test_ratios_missing_without_value_or_investment() {
+        let flows = year_of_flows();
+        let perf = RentalPerformance::compute(RentalPerformanceInput {
+            property_value: Decimal::ZERO,
+            cash_invested: None,
+            ..input(&flows)
+        });
+
+        assert_eq!(perf.cap_rate, None);
+        assert_eq!(perf.cash_on_cash_return, None);
+    }
+}
@@ -0,0 +1,252 @@
+//! Valuation models for alternative assets.
+//!
+//! Properties, vehicles and other alternative assets are normally valued only
+//! by manual appraisals (MANUAL quotes), which turns net worth into a
+//! staircase. A valuation model, stored under the `valuation_model` key of the
+//! asset's metadata, projects a synthetic daily value forward from the most
+//! recent appraisal on or before each date:
+//!
+//! - `INDEX_LINKED`: follows an index series stored as quotes on another asset
+//!   (house-price index, CPI, ...), scaled by the index change since the appraisal.
+//! - `APPRECIATION`: compounds a fixed annual rate.
+//! - `STRAIGHT_LINE`: depreciates linearly to a salvage value at the end of
+//!   the asset's useful life.
+//! - `DECLINING_BALANCE`: depreciates by a fixed annual percentage of the
+//!   remaining value, floored at the salvage value.
+//!
+//! A new appraisal always resets the projection to the appraised value.
+
+use chrono::{Months, NaiveDate};
+use rust_decimal::prelude::*;
+use rust_decimal::MathematicalOps;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Metadata key holding the JSON-encoded `ValuationModel` of an asset.
+pub const VALUATION_MODEL_METADATA_KEY: &str = "valuation_model";
+
+const DAYS_PER_YEAR: i64 = 365;
+
+/// Longest supported useful life for straight-line depreciation.
+const MAX_USEFUL_LIFE_YEARS: u32 = 100;
+
+/// How an alternative asset's value evolves between appraisals.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ValuationModel {
+    /// Scale the last appraisal by the change of an index series.
+    #[serde(rename_all = "camelCase")]
+    IndexLinked {
+        /// Asset whose quotes hold the index values.
+        index_asset_id: String,
+    },
+    /// Compound a fixed annual rate (0.03 = 3% per year).
+    #[serde(rename_all = "camelCase")]
+    Appreciation { annual_rate: Decimal },
+    /// Depreciate linearly to `salvage_value` at the end of the useful life.
+    #[serde(rename_all = "camelCase")]
+    StraightLine {
+        useful_life_years: u32,
+        #[serde(default)]
+        salvage_value: Decimal,
+        /// Start of the useful life. Defaults to the asset's purchase date,
+        /// or its first appraisal.
+        #[serde(default)]
+        in_service_date: Option<NaiveDate>,
+    },
+    /// Depreciate a fixed fraction of the remaining value per year (0.15 = 15%).
+    #[serde(rename_all = "camelCase")]
+    DecliningBalance {
+        annual_rate: Decimal,
+        #[serde(default)]
+        salvage_value: Decimal,
+    },
+}
+
+impl ValuationModel {
+    /// Read the valuation model from asset metadata.
+    ///
+    /// Accepts the model either as a JSON object or as a JSON-encoded string.
+    pub fn from_metadata(metadata: Option<&Value>) -> Option<Self> {
+        let raw = metadata?.get(VALUATION_MODEL_METADATA_KEY)?;
+        match raw {
+            Value::String(s) => serde_json::from_str(s).ok(),
+            other => serde_json::from_value(other.clone()).ok(),
+        }
+    }
+
+    /// Validate the model parameters.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: &str| -> Result<()> {
+            Err(Error::Validation(ValidationError::InvalidInput(
+                msg.to_string(),
+            )))
+        };
+
+        match self {
+            ValuationModel::IndexLinked { index_asset_id } => {
+                if index_asset_id.trim().is_empty() {
+                    return invalid("Index-linked valuation requires an index asset");
+                }
+            }
+            ValuationModel::Appreciation { annual_rate } => {
+                if *annual_rate <= -Decimal::ONE {
+                    return invalid("Appreciation rate must be greater than -100%");
+                }
+            }
+            ValuationModel::StraightLine {
+                useful_life_years,
+                salvage_value,
+                ..
+            } => {
+                if *useful_life_years == 0 {
+                    return invalid("Useful life must be at least one year");
+                }
+                if *useful_life_years > MAX_USEFUL_LIFE_YEARS {
+                    return invalid("Useful life cannot be longer than 100 years");
+                }
+                if *salvage_value < Decimal::ZERO {
+                    return invalid("Salvage value cannot be negative");
+                }
+            }
+            ValuationModel::DecliningBalance {
+                annual_rate,
+                salvage_value,
+            } => {
+                if *annual_rate <= Decimal::ZERO || *annual_rate >= Decimal::ONE {
+                    return invalid("Depreciation rate must be between 0 and 1");
+                }
+                if *salvage_value < Decimal::ZERO {
+                    return invalid("Salvage value cannot be negative");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Index asset referenced by the model, if any.
+    pub fn index_asset_id(&self) -> Option<&str> {
+        match self {
+            ValuationModel::IndexLinked { index_asset_id } => Some(index_asset_id),
+            _ => None,
+        }
+    }
+}
+
+/// Appraisals and index data needed to project an asset's value.
+#[derive(Debug, Clone)]
+pub struct ValuationSeries {
+    pub model: ValuationModel,
+    /// Manual appraisals as (date, value), sorted by date.
+    pub appraisals: Vec<(NaiveDate, Decimal)>,
+    /// Index values as (date, value), sorted by date. Only used by `IndexLinked`.
+    pub index: Vec<(NaiveDate, Decimal)>,
+    /// Fallback start of useful life for `StraightLine` (e.g. the purchase date).
+    pub in_service_date: Option<NaiveDate>,
+}
+
+impl ValuationSeries {
+    pub fn new(
+        model: ValuationModel,
+        mut appraisals: Vec<(NaiveDate, Decimal)>,
+        mut index: Vec<(NaiveDate, Decimal)>,
+        in_service_date: Option<NaiveDate>,
+    ) -> Self {
+        appraisals.sort_by_key(|(d, _)| *d);
+        index.sort_by_key(|(d, _)| *d);
+        Self {
+            model,
+            appraisals,
+            index,
+            in_service_date,
+        }
+    }
+
+    /// Date of the first appraisal; the asset has no value before it.
+    pub fn first_date(&self) -> Option<NaiveDate> {
+        self.appraisals.first().map(|(d, _)| *d)
+    }
+
+    /// Projected value on `date`, or `None` before the first appraisal or when
+    /// the projection overflows.
+    pub fn value_on(&self, date: NaiveDate) -> Option<Decimal> {
+        let (anchor_date, anchor_value) = last_on_or_before(&self.appraisals, date)?;
+        if date == anchor_date {
+            return Some(anchor_value);
+        }
+        let years = Decimal::from((date - anchor_date).num_days()) / Decimal::from(DAYS_PER_YEAR);
+
+        let value = match &self.model {
+            ValuationModel::IndexLinked { .. } => {
+                let base = last_on_or_before(&self.index, anchor_date)
+                    .or_else(|| self.index.first().copied());
+                let current = last_on_or_before(&self.index, date);
+                match (base, current) {
+                    (Some((_, base)), Some((_, current))) if !base.is_zero() => {
+                        anchor_value * current / base
+                    }
+                    _ => anchor_value,
+                }
+            }
+            ValuationModel::Appreciation { annual_rate } => {
+                anchor_value.checked_mul(compound(Decimal::ONE + annual_rate, years)?)?
+            }
+            ValuationModel::StraightLine {
+                useful_life_years,
+                salvage_value,
+                in_service_date,
+            } => {
+                let start = in_service_date
+                    .or(self.in_service_date)
+                    .or_else(|| self.first_date())
+                    .unwrap_or(anchor_date);
+                let end_of_life = useful_life_years
+                    .checked_mul(12)
+                    .and_then(|months| start.checked_add_months(Months::new(months)))
+                    .unwrap_or(NaiveDate::MAX);
+                if anchor_date >= end_of_life || anchor_value <= *salvage_value {
+                    anchor_value
+                } else if date >= end_of_life {
+                    *salvage_value
+                } else {
+                    let remaining = Decimal::from((end_of_life - anchor_date).num_days());
+                    let elapsed = Decimal::from((date - anchor_date).num_days());
+                    anchor_value - (anchor_value - salvage_value) * elapsed / remaining
+                }
+            }
+            ValuationModel::DecliningBalance {
+                annual_rate,
+                salvage_value,
+            } => {
+                let projected =
+                    anchor_value.checked_mul(compound(Decimal::ONE - annual_rate, years)?)?;
+                if anchor_value <= *salvage_value {
+                    anchor_value
+                } else {
+                    projected.max(*salvage_value)
+                }
+            }
+        };
+
+        Some(value.round_dp(2))
+    }
+}
+
+/// Latest (date, value) on or before `date` in a date-sorted series.
+fn last_on_or_before(
+    series: &[(NaiveDate, Decimal)],
+    date: NaiveDate,
+) -> Option<(NaiveDate, Decimal)> {
+    let idx = series.partition_point(|(d, _)| *d <= date);
+    idx.checked_sub(1).map(|i| series[i])
+}
+
+/// `factor ^ years` for fractional years, or `None` on overflow.
+fn compound(factor: Decimal, years: Decimal) -> Option<Decimal> {
+    if years.is_zero() {
+        return Some(Decimal::ONE);
+    }
+    factor.checked_powd(years)
+}
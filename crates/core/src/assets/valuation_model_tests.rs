@@ -0,0 +1,195 @@
+//! Tests for alternative asset valuation models.
+
+#[cfg(test)]
+mod tests {
+    use crate::assets::{ValuationModel, ValuationSeries};
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn series(model: ValuationModel, appraisals: Vec<(NaiveDate, Decimal)>) -> ValuationSeries {
+        ValuationSeries::new(model, appraisals, vec![], None)
+    }
+
+    #[test]
+    fn test_no_value_before_first_appraisal() {
+        let s = series(
+            ValuationModel::Appreciation {
+                annual_rate: dec!(0.03),
+            },
+            vec![(date(2024, 1, 1), dec!(100000))],
+        );
+        assert_eq!(s.value_on(date(2023, 12, 31)), None);
+        assert_eq!(s.value_on(date(2024, 1, 1)), Some(dec!(100000)));
+    }
+
+    #[test]
+    fn test_appreciation_overflow_has_no_value() {
+        let s = series(
+            ValuationModel::Appreciation {
+                annual_rate: dec!(1000),
+            },
+            vec![(date(2000, 1, 1), dec!(1000000))],
+        );
+        assert_eq!(s.value_on(date(2100, 1, 1)), None);
+    }
+
+    #[test]
+    fn test_fixed_appreciation_compounds_annually() {
+        let s = series(
+            ValuationModel::Appreciation {
+                annual_rate: dec!(0.03),
+            },
+            vec![(date(2023, 1, 1), dec!(100000))],
+        );
+        assert_eq!(s.value_on(date(2024, 1, 1)), Some(dec!(103000)));
+
+        let mid_year = s.value_on(date(2023, 7, 2)).unwrap();
+        assert!(mid_year > dec!(101000) && mid_year < dec!(102000));
+    }
+
+    #[test]
+    fn test_new_appraisal_resets_projection() {
+        let s = series(
+            ValuationModel::Appreciation {
+                annual_rate: dec!(0.03),
+            },
+            vec![
+                (date(2023, 1, 1), dec!(100000)),
+                (date(2024, 1, 1), dec!(90000)),
+            ],
+        );
+        assert_eq!(s.value_on(date(2024, 1, 1)), Some(dec!(90000)));
+        assert_eq!(s.value_on(date(2024, 12, 31)), Some(dec!(92700)));
+    }
+
+    #[test]
+    fn test_straight_line_depreciates_monthly_to_salvage() {
+        let s = series(
+            ValuationModel::StraightLine {
+                useful_life_years: 5,
+                salvage_value: dec!(5000),
+                in_service_date: None,
+            },
+            vec![(date(2024, 1, 1), dec!(20000))],
+        );
+
+        let mut previous = dec!(20000);
+        for month in 2..=12 {
+            let value = s.value_on(date(2024, month, 1)).unwrap();
+            assert!(value < previous, "value should drop every month");
+            previous = value;
+        }
+        assert_eq!(s.value_on(date(2029, 1, 1)), Some(dec!(5000)));
+        assert_eq!(s.value_on(date(2031, 6, 1)), Some(dec!(5000)));
+    }
+
+    #[test]
+    fn test_straight_line_uses_in_service_date_after_reappraisal() {
+        let s = ValuationSeries::new(
+            ValuationModel::StraightLine {
+                useful_life_years: 4,
+                salvage_value: Decimal::ZERO,
+                in_service_date: None,
+            },
+            vec![(date(2026, 1, 1), dec!(10000))],
+            vec![],
+            Some(date(2024, 1, 1)),
+        );
+        // Two years of life remain after the appraisal: halfway there, half the value.
+        assert_eq!(s.value_on(date(2027, 1, 1)), Some(dec!(5000)));
+        assert_eq!(s.value_on(date(2028, 1, 1)), Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_declining_balance_floors_at_salvage() {
+        let s = series(
+            ValuationModel::DecliningBalance {
+                annual_rate: dec!(0.15),
+                salvage_value: dec!(3000),
+            },
+            vec![(date(2024, 1, 1), dec!(30000))],
+        );
+        assert_eq!(s.value_on(date(2024, 12, 31)), Some(dec!(25500)));
+        assert_eq!(s.value_on(date(2060, 1, 1)), Some(dec!(3000)));
+    }
+
+    #[test]
+    fn test_index_linked_scales_by_index_change() {
+        let s = ValuationSeries::new(
+            ValuationModel::IndexLinked {
+                index_asset_id: "HPI".to_string(),
+            },
+            vec![(date(2024, 1, 1), dec!(300000))],
+            vec![
+                (date(2023, 12, 1), dec!(100)),
+                (date(2024, 6, 1), dec!(110)),
+            ],
+            None,
+        );
+        assert_eq!(s.value_on(date(2024, 3, 1)), Some(dec!(300000)));
+        assert_eq!(s.value_on(date(2024, 7, 1)), Some(dec!(330000)));
+    }
+
+    #[test]
+    fn test_model_serialization_round_trip() {
+        let model = ValuationModel::StraightLine {
+            useful_life_years: 8,
+            salvage_value: dec!(2000),
+            in_service_date: Some(date(2022, 5, 1)),
+        };
+        let value = serde_json::to_value(&model).unwrap();
+        assert_eq!(value["type"], json!("STRAIGHT_LINE"));
+        assert_eq!(value["usefulLifeYears"], json!(8));
+
+        let metadata = json!({ "valuation_model": value.to_string() });
+        assert_eq!(ValuationModel::from_metadata(Some(&metadata)), Some(model));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(ValuationModel::DecliningBalance {
+            annual_rate: dec!(1.5),
+            salvage_value: Decimal::ZERO,
+        }
+        .validate()
+        .is_err());
+        assert!(ValuationModel::StraightLine {
+            useful_life_years: 0,
+            salvage_value: Decimal::ZERO,
+            in_service_date: None,
+        }
+        .validate()
+        .is_err());
+        assert!(ValuationModel::IndexLinked {
+            index_asset_id: " ".to_string(),
+        }
+        .validate()
+        .is_err());
+        assert!(ValuationModel::Appreciation {
+            annual_rate: dec!(0.02),
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_useful_life_is_bounded() {
+        let model = |useful_life_years| ValuationModel::StraightLine {
+            useful_life_years,
+            salvage_value: Decimal::ZERO,
+            in_service_date: None,
+        };
+        assert!(model(100).validate().is_ok());
+        assert!(model(101).validate().is_err());
+
+        // Unvalidated metadata must not overflow the month arithmetic
+        let s = series(model(u32::MAX), vec![(date(2024, 1, 1), dec!(1000))]);
+        assert_eq!(s.value_on(date(2025, 1, 1)), Some(dec!(1000)));
+    }
+}
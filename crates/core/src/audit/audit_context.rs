@@ -0,0 +1,27 @@
+//! Attribution of writes to the user, an import run, broker or device sync.
+//!
+//! Repositories do not know on whose behalf they write, so the caller scopes
+//! the work with a context and the storage layer reads it when it records
+//! the change.
+
+use std::future::Future;
+
+use super::audit_model::AuditContext;
+
+tokio::task_local! {
+    static AUDIT_CONTEXT: AuditContext;
+}
+
+/// Runs `future` with every write it makes attributed to `context`.
+///
+/// The context is not inherited by tasks spawned from `future`.
+pub async fn with_audit_context<F: Future>(context: AuditContext, future: F) -> F::Output {
+    AUDIT_CONTEXT.scope(context, future).await
+}
+
+/// Context of the current task; writes outside any scope are made by the user.
+pub fn current_audit_context() -> AuditContext {
+    AUDIT_CONTEXT
+        .try_with(AuditContext::clone)
+        .unwrap_or_default()
+}
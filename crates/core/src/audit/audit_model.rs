@@ -0,0 +1,177 @@
+//! Audit log domain models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::sync::SyncEntity;
+
+/// Fields that change on every write and say nothing about what changed.
+const IGNORED_FIELDS: [&str; 1] = ["updated_at"];
+
+/// Kind of entity tracked by the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditEntityType {
+    Activity,
+    Account,
+    Asset,
+}
+
+impl AuditEntityType {
+    /// Audited entity type of a synced entity, if it is audited at all.
+    pub fn from_sync_entity(entity: SyncEntity) -> Option<Self> {
+        match entity {
+            SyncEntity::Activity => Some(Self::Activity),
+            SyncEntity::Account => Some(Self::Account),
+            SyncEntity::Asset => Some(Self::Asset),
+            _ => None,
+        }
+    }
+
+    pub fn sync_entity(&self) -> SyncEntity {
+        match self {
+            Self::Activity => SyncEntity::Activity,
+            Self::Account => SyncEntity::Account,
+            Self::Asset => SyncEntity::Asset,
+        }
+    }
+}
+
+/// What happened to the entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditOperation {
+    Create,
+    Update,
+    Delete,
+    /// Written back from an earlier version
+    Restore,
+}
+
+/// Who made the change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditSource {
+    /// Edited through the app
+    #[default]
+    User,
+    /// Written by a CSV or statement import
+    Import,
+    /// Written by broker sync
+    BrokerSync,
+    /// Received from another device
+    DeviceSync,
+    /// Background jobs such as asset enrichment
+    System,
+}
+
+/// Attribution of the writes made while it is in scope.
+///
+/// See [`super::with_audit_context`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditContext {
+    pub source: AuditSource,
+    pub import_run_id: Option<String>,
+    /// Audit entry being restored, if the writes restore an earlier version
+    pub restored_from: Option<String>,
+}
+
+impl AuditContext {
+    pub fn new(source: AuditSource) -> Self {
+        Self {
+            source,
+            ..Default::default()
+        }
+    }
+
+    pub fn import(import_run_id: impl Into<String>) -> Self {
+        Self {
+            source: AuditSource::Import,
+            import_run_id: Some(import_run_id.into()),
+            restored_from: None,
+        }
+    }
+
+    pub fn broker_sync(import_run_id: Option<String>) -> Self {
+        Self {
+            source: AuditSource::BrokerSync,
+            import_run_id,
+            restored_from: None,
+        }
+    }
+
+    pub fn restore(entry_id: impl Into<String>) -> Self {
+        Self {
+            source: AuditSource::User,
+            import_run_id: None,
+            restored_from: Some(entry_id.into()),
+        }
+    }
+}
+
+/// One change to an activity, account or asset.
+///
+/// `after` is the stored row once the change was made (`None` for deletes);
+/// `before` is the version recorded by the previous entry, `None` when the
+/// entity was created or had no history yet.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    pub id: String,
+    pub entity_type: AuditEntityType,
+    pub entity_id: String,
+    pub operation: AuditOperation,
+    pub source: AuditSource,
+    pub device_id: Option<String>,
+    pub import_run_id: Option<String>,
+    pub restored_from: Option<String>,
+    /// Fields that differ from `before`; empty when there is nothing to compare to
+    pub changed_fields: Vec<String>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLogEntry {
+    /// The version of the entity this entry leaves in place, `None` once deleted.
+    pub fn version(&self) -> Option<&Value> {
+        match self.operation {
+            AuditOperation::Delete => None,
+            _ => self.after.as_ref(),
+        }
+    }
+}
+
+/// Top-level fields whose values differ between two stored versions.
+pub fn changed_fields(before: &Value, after: &Value) -> Vec<String> {
+    let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = after
+        .iter()
+        .filter(|(key, value)| before.get(key.as_str()) != Some(value))
+        .map(|(key, _)| key.clone())
+        .chain(
+            before
+                .keys()
+                .filter(|key| !after.contains_key(key.as_str()))
+                .cloned(),
+        )
+        .filter(|key| !IGNORED_FIELDS.contains(&key.as_str()))
+        .collect();
+    fields.sort();
+    fields
+}
+
+/// Version of an entity at a point in time, from its history in any order.
+///
+/// `None` when the entity did not exist yet, was deleted, or has no history
+/// before `at`.
+pub fn version_at(history: &[AuditLogEntry], at: DateTime<Utc>) -> Option<&Value> {
+    history
+        .iter()
+        .filter(|entry| entry.created_at <= at)
+        .max_by_key(|entry| entry.created_at)
+        .and_then(AuditLogEntry::version)
+}
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::debug;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use super::audit_context::with_audit_context;
+use super::audit_model::{
+    version_at, AuditContext, AuditEntityType, AuditLogEntry, AuditOperation,
+};
+use super::audit_traits::{AuditLogRepositoryTrait, AuditServiceTrait};
+use crate::errors::{DatabaseError, Error, ValidationError};
+use crate::events::{CurrencyChange, DomainEvent, DomainEventSink, NoOpDomainEventSink};
+use crate::Result;
+
+const DEFAULT_RECENT_LIMIT: i64 = 100;
+
+pub struct AuditService {
+    repository: Arc<dyn AuditLogRepositoryTrait>,
+    event_sink: Arc<dyn DomainEventSink>,
+}
+
+impl AuditService {
+    pub fn new(repository: Arc<dyn AuditLogRepositoryTrait>) -> Self {
+        Self {
+            repository,
+            event_sink: Arc::new(NoOpDomainEventSink),
+        }
+    }
+
+    /// Sets the domain event sink for this service.
+    ///
+    /// Restoring a version emits the event the entity's own service would,
+    /// so portfolios are recalculated.
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn DomainEventSink>) -> Self {
+        self.event_sink = event_sink;
+        self
+    }
+
+    fn emit_restored(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: &str,
+        current: Option<&Value>,
+        restored: &Value,
+    ) {
+        let field = |version: Option<&Value>, key: &str| {
+            version
+                .and_then(|v| v.get(key))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        let versions = [current, Some(restored)];
+
+        let event = match entity_type {
+            AuditEntityType::Activity => {
+                let collect = |key: &str| -> Vec<String> {
+                    versions
+                        .iter()
+                        .filter_map(|v| field(*v, key))
+                        .collect::<BTreeSet<_>>()
+                        .into_iter()
+                        .collect()
+                };
+                DomainEvent::activities_changed(
+                    collect("account_id"),
+                    collect("asset_id"),
+                    collect("currency"),
+                )
+            }
+            AuditEntityType::Account => {
+                let old_currency = field(current, "currency");
+                let currency_changes = match field(Some(restored), "currency") {
+                    Some(new_currency) if old_currency.as_ref() != Some(&new_currency) => {
+                        vec![CurrencyChange {
+                            account_id: entity_id.to_string(),
+                            old_currency,
+                            new_currency,
+                        }]
+                    }
+                    _ => Vec::new(),
+                };
+                DomainEvent::accounts_changed(vec![entity_id.to_string()], currency_changes)
+            }
+            AuditEntityType::Asset => DomainEvent::assets_updated(vec![entity_id.to_string()]),
+        };
+        self.event_sink.emit(event);
+    }
+}
+
+#[async_trait]
+impl AuditServiceTrait for AuditService {
+    fn get_entity_history(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: &str,
+    ) -> Result<Vec<AuditLogEntry>> {
+        self.repository.get_entity_history(entity_type, entity_id)
+    }
+
+    fn get_recent_changes(
+        &self,
+        entity_type: Option<AuditEntityType>,
+        limit: Option<i64>,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let limit = limit.unwrap_or(DEFAULT_RECENT_LIMIT).clamp(1, 1000);
+        self.repository.get_recent_entries(entity_type, limit)
+    }
+
+    fn get_entity_version_at(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Option<Value>> {
+        let history = self.repository.get_entity_history(entity_type, entity_id)?;
+        Ok(version_at(&history, at).cloned())
+    }
+
+    async fn restore_version(&self, entry_id: &str) -> Result<AuditLogEntry> {
+        let entry = self.repository.get_entry(entry_id)?.ok_or_else(|| {
+            Error::Database(DatabaseError::NotFound(format!(
+                "Audit entry {} not found",
+                entry_id
+            )))
+        })?;
+        // A delete leaves nothing behind, so restoring it brings back what it removed
+        let version = match entry.operation {
+            AuditOperation::Delete => entry.before.clone(),
+            _ => entry.after.clone(),
+        }
+        .ok_or_else(|| {
+            Error::Validation(ValidationError::InvalidInput(format!(
+                "Audit entry {} has no version to restore",
+                entry_id
+            )))
+        })?;
+
+        let history = self
+            .repository
+            .get_entity_history(entry.entity_type, &entry.entity_id)?;
+        let current = history.first().and_then(AuditLogEntry::version).cloned();
+
+        with_audit_context(
+            AuditContext::restore(entry_id),
+            self.repository
+                .restore_version(entry.entity_type, &entry.entity_id, version.clone()),
+        )
+        .await?;
+        self.emit_restored(
+            entry.entity_type,
+            &entry.entity_id,
+            current.as_ref(),
+            &version,
+        );
+
+        debug!(
+            "Restored {:?} {} to the version of audit entry {}",
+            entry.entity_type, entry.entity_id, entry_id
+        );
+        self.repository
+            .get_entity_history(entry.entity_type, &entry.entity_id)?
+            .into_iter()
+            .find(|e| e.restored_from.as_deref() == Some(entry_id))
+            .ok_or_else(|| {
+                Error::Unexpected(format!(
+                    "Restore of audit entry {} was not recorded",
+                    entry_id
+                ))
+            })
+    }
+}
@@ -0,0 +1,249 @@
+//! Tests for the audit log service.
+
+#[cfg(test)]
+mod tests {
+    use crate::audit::{
+        changed_fields, current_audit_context, AuditEntityType, AuditLogEntry,
+        AuditLogRepositoryTrait, AuditOperation, AuditService, AuditServiceTrait, AuditSource,
+    };
+    use crate::errors::Result;
+    use crate::events::{DomainEvent, MockDomainEventSink};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    /// Records restores the way the storage layer does, attributed to the
+    /// audit context of the calling task.
+    #[derive(Default)]
+    struct MockAuditLogRepository {
+        entries: Mutex<Vec<AuditLogEntry>>,
+    }
+
+    #[async_trait]
+    impl AuditLogRepositoryTrait for MockAuditLogRepository {
+        fn get_entity_history(
+            &self,
+            entity_type: AuditEntityType,
+            entity_id: &str,
+        ) -> Result<Vec<AuditLogEntry>> {
+            let mut history: Vec<AuditLogEntry> = self
+                .entries
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| e.entity_type == entity_type && e.entity_id == entity_id)
+                .cloned()
+                .collect();
+            history.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+            Ok(history)
+        }
+
+        fn get_recent_entries(
+            &self,
+            _entity_type: Option<AuditEntityType>,
+            _limit: i64,
+        ) -> Result<Vec<AuditLogEntry>> {
+            unimplemented!()
+        }
+
+        fn get_entry(&self, entry_id: &str) -> Result<Option<AuditLogEntry>> {
+            Ok(self
+                .entries
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|e| e.id == entry_id)
+                .cloned())
+        }
+
+        async fn restore_version(
+            &self,
+            entity_type: AuditEntityType,
+            entity_id: &str,
+            version: Value,
+        ) -> Result<()> {
+            let context = current_audit_context();
+            let before = self
+                .get_entity_history(entity_type, entity_id)?
+                .first()
+                .and_then(|e| e.version().cloned());
+            let mut entries = self.entries.lock().unwrap();
+            let created_at = at(entries.len() as i64);
+            let id = format!("e{}", entries.len() + 1);
+            entries.push(AuditLogEntry {
+                id,
+                entity_type,
+                entity_id: entity_id.to_string(),
+                operation: AuditOperation::Restore,
+                source: context.source,
+                device_id: None,
+                import_run_id: context.import_run_id,
+                restored_from: context.restored_from,
+                changed_fields: before
+                    .as_ref()
+                    .map(|b| changed_fields(b, &version))
+                    .unwrap_or_default(),
+                before,
+                after: Some(version),
+                created_at,
+            });
+            Ok(())
+        }
+    }
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    fn activity(quantity: &str, account_id: &str) -> Value {
+        json!({
+            "id": "act_1",
+            "account_id": account_id,
+            "asset_id": "SEC:AAPL:XNAS",
+            "quantity": quantity,
+            "currency": "USD",
+            "updated_at": quantity,
+        })
+    }
+
+    fn entry(
+        id: &str,
+        operation: AuditOperation,
+        before: Option<Value>,
+        after: Option<Value>,
+        minutes: i64,
+    ) -> AuditLogEntry {
+        AuditLogEntry {
+            id: id.to_string(),
+            entity_type: AuditEntityType::Activity,
+            entity_id: "act_1".to_string(),
+            operation,
+            source: AuditSource::Import,
+            device_id: Some("device_1".to_string()),
+            import_run_id: Some("run_1".to_string()),
+            restored_from: None,
+            changed_fields: Vec::new(),
+            before,
+            after,
+            created_at: at(minutes),
+        }
+    }
+
+    fn fixture() -> (
+        AuditService,
+        Arc<MockAuditLogRepository>,
+        MockDomainEventSink,
+    ) {
+        let repository = Arc::new(MockAuditLogRepository {
+            entries: Mutex::new(vec![
+                entry(
+                    "e1",
+                    AuditOperation::Create,
+                    None,
+                    Some(activity("10", "acc_1")),
+                    0,
+                ),
+                entry(
+                    "e2",
+                    AuditOperation::Update,
+                    Some(activity("10", "acc_1")),
+                    Some(activity("12", "acc_2")),
+                    1,
+                ),
+                entry(
+                    "e3",
+                    AuditOperation::Delete,
+                    Some(activity("12", "acc_2")),
+                    None,
+                    2,
+                ),
+            ]),
+        });
+        let events = MockDomainEventSink::new();
+        let service =
+            AuditService::new(repository.clone()).with_event_sink(Arc::new(events.clone()));
+        (service, repository, events)
+    }
+
+    #[test]
+    fn test_changed_fields_ignore_updated_at() {
+        assert_eq!(
+            changed_fields(&activity("10", "acc_1"), &activity("12", "acc_2")),
+            vec!["account_id".to_string(), "quantity".to_string()]
+        );
+        let mut removed = activity("10", "acc_1");
+        removed.as_object_mut().unwrap().remove("asset_id");
+        assert_eq!(
+            changed_fields(&activity("10", "acc_1"), &removed),
+            vec!["asset_id".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_version_at_point_in_time() {
+        let (service, _, _) = fixture();
+        let version = |minutes: i64| {
+            service
+                .get_entity_version_at(AuditEntityType::Activity, "act_1", at(minutes))
+                .unwrap()
+        };
+        assert_eq!(
+            version(0).unwrap()["quantity"],
+            "10",
+            "created version is in place from its entry on"
+        );
+        assert_eq!(version(1).unwrap()["quantity"], "12");
+        assert_eq!(version(-1), None);
+        // Deleted
+        assert_eq!(version(5), None);
+    }
+
+    #[tokio::test]
+    async fn test_restoring_a_delete_brings_back_the_removed_version() {
+        let (service, repository, events) = fixture();
+
+        let restored = service.restore_version("e3").await.unwrap();
+        assert_eq!(restored.operation, AuditOperation::Restore);
+        assert_eq!(restored.source, AuditSource::User);
+        assert_eq!(restored.restored_from.as_deref(), Some("e3"));
+        assert_eq!(restored.import_run_id, None);
+        assert_eq!(restored.after, Some(activity("12", "acc_2")));
+        assert_eq!(
+            repository
+                .get_entity_history(AuditEntityType::Activity, "act_1")
+                .unwrap()
+                .len(),
+            4
+        );
+
+        match &events.events()[0] {
+            DomainEvent::ActivitiesChanged { account_ids, .. } => {
+                assert_eq!(account_ids, &vec!["acc_2".to_string()]);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restore_recalculates_both_versions() {
+        let (service, _, events) = fixture();
+        service.restore_version("e3").await.unwrap();
+
+        // Back to the original account
+        let restored = service.restore_version("e1").await.unwrap();
+        assert_eq!(restored.before, Some(activity("12", "acc_2")));
+        assert_eq!(
+            restored.changed_fields,
+            vec!["account_id".to_string(), "quantity".to_string()]
+        );
+        match &events.events()[1] {
+            DomainEvent::ActivitiesChanged { account_ids, .. } => {
+                assert_eq!(account_ids, &vec!["acc_1".to_string(), "acc_2".to_string()]);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        assert!(service.restore_version("missing").await.is_err());
+    }
+}
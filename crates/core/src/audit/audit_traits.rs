@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use super::audit_model::{AuditEntityType, AuditLogEntry};
+use crate::errors::Result;
+
+/// Storage of the append-only audit log.
+///
+/// Entries are written by the storage layer as part of each write; this
+/// trait only reads them and restores earlier versions.
+#[async_trait]
+pub trait AuditLogRepositoryTrait: Send + Sync {
+    /// Entries of one entity, newest first.
+    fn get_entity_history(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: &str,
+    ) -> Result<Vec<AuditLogEntry>>;
+
+    /// Latest entries across all entities, optionally of one type, newest first.
+    fn get_recent_entries(
+        &self,
+        entity_type: Option<AuditEntityType>,
+        limit: i64,
+    ) -> Result<Vec<AuditLogEntry>>;
+
+    fn get_entry(&self, entry_id: &str) -> Result<Option<AuditLogEntry>>;
+
+    /// Writes a stored version back, re-creating the entity if it was deleted.
+    /// The write is itself recorded in the log.
+    async fn restore_version(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: &str,
+        version: Value,
+    ) -> Result<()>;
+}
+
+/// Audit log service: per-entity history and point-in-time restore.
+#[async_trait]
+pub trait AuditServiceTrait: Send + Sync {
+    /// Changes to one entity, newest first.
+    fn get_entity_history(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: &str,
+    ) -> Result<Vec<AuditLogEntry>>;
+
+    /// Latest changes across all entities, newest first.
+    fn get_recent_changes(
+        &self,
+        entity_type: Option<AuditEntityType>,
+        limit: Option<i64>,
+    ) -> Result<Vec<AuditLogEntry>>;
+
+    /// Stored version of an entity at a point in time, `None` if it did not
+    /// exist then or its history starts later.
+    fn get_entity_version_at(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Option<Value>>;
+
+    /// Restores the version left by an audit entry (the version before it for
+    /// deletes). Returns the entry recording the restore.
+    async fn restore_version(&self, entry_id: &str) -> Result<AuditLogEntry>;
+}
@@ -0,0 +1,17 @@
+//! Audit log module - append-only history of activities, accounts and assets.
+
+mod audit_context;
+mod audit_model;
+mod audit_service;
+mod audit_traits;
+
+#[cfg(test)]
+mod audit_service_tests;
+
+pub use audit_context::{current_audit_context, with_audit_context};
+pub use audit_model::{
+    changed_fields, version_at, AuditContext, AuditEntityType, AuditLogEntry, AuditOperation,
+    AuditSource,
+};
+pub use audit_service::AuditService;
+pub use audit_traits::{AuditLogRepositoryTrait, AuditServiceTrait};
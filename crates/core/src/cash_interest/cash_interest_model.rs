@@ -0,0 +1,483 @@
+//! Cash interest settings and daily accrual.
+//!
+//! Savings accounts and broker cash sweeps pay interest on the cash balance.
+//! Settings live under `cashInterest` in the account `meta` JSON:
+//!
+//! - APY tiers: either the whole balance earns the rate of the highest tier it
+//!   reaches (`WHOLE`), or each tier's rate applies to the part of the balance
+//!   inside it (`MARGINAL`).
+//! - Compounding: `DAILY` adds each day's interest to the balance earning
+//!   interest; `MONTHLY` accrues simple interest until the payment date.
+//! - Payment day: day of month the accrued interest is paid (clamped to the
+//!   last day of shorter months). Each payment becomes an `INTEREST` draft.
+
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+use crate::activities::{ActivityStatus, NewActivity, ACTIVITY_TYPE_INTEREST};
+use crate::errors::{Error, Result, ValidationError};
+
+/// `source_system` of activities generated from accrued cash interest.
+pub const CASH_INTEREST_SOURCE_SYSTEM: &str = "CASH_INTEREST";
+
+/// Key of the settings in the account `meta` JSON.
+pub const CASH_INTEREST_META_KEY: &str = "cashInterest";
+
+const DAYS_PER_YEAR: u32 = 365;
+
+/// How interest is compounded between payments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Compounding {
+    /// Each day's interest earns interest from the next day on.
+    Daily,
+    /// Simple interest accrues until it is paid on the payment day.
+    #[default]
+    Monthly,
+}
+
+/// How APY tiers apply to the balance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TierMode {
+    /// The whole balance earns the rate of the highest tier it reaches.
+    #[default]
+    Whole,
+    /// Each tier's rate applies only to the part of the balance within it.
+    Marginal,
+}
+
+/// APY paid from `min_balance` upwards. `apy` is a fraction (0.045 = 4.5%).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApyTier {
+    pub min_balance: Decimal,
+    pub apy: Decimal,
+}
+
+/// Per-account cash interest settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashInterestSettings {
+    /// Cash currency earning interest. Defaults to the account currency.
+    #[serde(default)]
+    pub currency: Option<String>,
+    pub tiers: Vec<ApyTier>,
+    #[serde(default)]
+    pub tier_mode: TierMode,
+    #[serde(default)]
+    pub compounding: Compounding,
+    /// Day of month interest is paid (1-31).
+    pub payment_day: u32,
+    /// First day of accrual. Set to the day the settings are saved when omitted,
+    /// so past interest the user already recorded is not generated again.
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+    /// Last payment date interest drafts were generated for. Drafts the user
+    /// deletes are not generated again.
+    #[serde(default)]
+    pub drafted_through: Option<NaiveDate>,
+}
+
+/// Interest accrued on one day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyAccrual {
+    pub date: NaiveDate,
+    /// Balance earning interest (cash plus unpaid interest when compounding daily).
+    pub balance: Decimal,
+    pub interest: Decimal,
+}
+
+/// Interest accrued in the current payment period of an account.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashInterestAccrual {
+    pub account_id: String,
+    pub currency: String,
+    pub as_of: NaiveDate,
+    pub balance: Decimal,
+    /// Effective APY of the current balance.
+    pub apy: Decimal,
+    pub period_start: NaiveDate,
+    pub next_payment_date: NaiveDate,
+    pub accrued_interest: Decimal,
+    pub days: Vec<DailyAccrual>,
+}
+
+/// Cash yield of one account, for comparing savings and money-market accounts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashYieldSummary {
+    pub account_id: String,
+    pub account_name: String,
+    pub currency: String,
+    pub balance: Decimal,
+    /// APY from the settings for the current balance, if configured.
+    pub configured_apy: Option<Decimal>,
+    pub projected_annual_interest: Decimal,
+    /// Posted cash interest over the trailing year.
+    pub trailing_interest: Decimal,
+    /// Average daily cash balance over the trailing year.
+    pub average_balance: Decimal,
+    /// Trailing interest over average balance.
+    pub realized_yield: Option<Decimal>,
+}
+
+/// Last day of the month of `date`.
+fn last_day_of_month(date: NaiveDate) -> NaiveDate {
+    let first = date.with_day(1).unwrap_or(date);
+    first
+        .checked_add_months(Months::new(1))
+        .and_then(|d| d.pred_opt())
+        .unwrap_or(date)
+}
+
+impl CashInterestSettings {
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: &str| -> Result<()> {
+            Err(Error::Validation(ValidationError::InvalidInput(
+                msg.to_string(),
+            )))
+        };
+
+        if self.tiers.is_empty() {
+            return invalid("At least one APY tier is required");
+        }
+        if self.tiers.iter().any(|t| t.min_balance < Decimal::ZERO) {
+            return invalid("Tier minimum balances cannot be negative");
+        }
+        if self
+            .tiers
+            .iter()
+            .any(|t| t.apy < Decimal::ZERO || t.apy >= Decimal::ONE)
+        {
+            return invalid("APY must be a fraction between 0 and 1 (e.g. 0.045 for 4.5%)");
+        }
+        if !(1..=31).contains(&self.payment_day) {
+            return invalid("Payment day must be between 1 and 31");
+        }
+        Ok(())
+    }
+
+    /// Tiers sorted by minimum balance.
+    fn sorted_tiers(&self) -> Vec<&ApyTier> {
+        let mut tiers: Vec<&ApyTier> = self.tiers.iter().collect();
+        tiers.sort_by_key(|t| t.min_balance);
+        tiers
+    }
+
+    /// Interest rate for one day at `apy`.
+    fn daily_rate(&self, apy: Decimal) -> Decimal {
+        if apy.is_zero() {
+            return Decimal::ZERO;
+        }
+        let days = Decimal::from(DAYS_PER_YEAR);
+        match self.compounding {
+            Compounding::Daily => (Decimal::ONE + apy).powd(Decimal::ONE / days) - Decimal::ONE,
+            Compounding::Monthly => {
+                let monthly =
+                    (Decimal::ONE + apy).powd(Decimal::ONE / Decimal::from(12)) - Decimal::ONE;
+                monthly * Decimal::from(12) / days
+            }
+        }
+    }
+
+    /// Interest earned in one day on `balance`. Overdrawn balances earn nothing.
+    pub fn daily_interest(&self, balance: Decimal) -> Decimal {
+        if balance <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        let tiers = self.sorted_tiers();
+        match self.tier_mode {
+            TierMode::Whole => tiers
+                .iter()
+                .rev()
+                .find(|t| balance >= t.min_balance)
+                .map(|t| balance * self.daily_rate(t.apy))
+                .unwrap_or(Decimal::ZERO),
+            TierMode::Marginal => tiers
+                .iter()
+                .enumerate()
+                .filter(|(_, t)| balance > t.min_balance)
+                .map(|(i, t)| {
+                    let upper = tiers
+                        .get(i + 1)
+                        .map(|next| next.min_balance.min(balance))
+                        .unwrap_or(balance);
+                    (upper - t.min_balance) * self.daily_rate(t.apy)
+                })
+                .sum(),
+        }
+    }
+
+    /// Effective APY of `balance` (blended across tiers in marginal mode).
+    pub fn apy_for(&self, balance: Decimal) -> Decimal {
+        let tiers = self.sorted_tiers();
+        match self.tier_mode {
+            TierMode::Whole => tiers
+                .iter()
+                .rev()
+                .find(|t| balance >= t.min_balance)
+                .map(|t| t.apy)
+                .unwrap_or(Decimal::ZERO),
+            TierMode::Marginal => {
+                if balance <= Decimal::ZERO {
+                    return tiers.first().map(|t| t.apy).unwrap_or(Decimal::ZERO);
+                }
+                let weighted: Decimal = tiers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, t)| balance > t.min_balance)
+                    .map(|(i, t)| {
+                        let upper = tiers
+                            .get(i + 1)
+                            .map(|next| next.min_balance.min(balance))
+                            .unwrap_or(balance);
+                        (upper - t.min_balance) * t.apy
+                    })
+                    .sum();
+                weighted / balance
+            }
+        }
+    }
+
+    /// Payment date in the month of `date`.
+    fn payment_date_in_month(&self, date: NaiveDate) -> NaiveDate {
+        let last = last_day_of_month(date);
+        date.with_day(self.payment_day.min(last.day()))
+            .unwrap_or(last)
+    }
+
+    /// First payment date on or after `date`.
+    pub fn next_payment_date(&self, date: NaiveDate) -> NaiveDate {
+        let this_month = self.payment_date_in_month(date);
+        if this_month >= date {
+            return this_month;
+        }
+        let next_month = date
+            .with_day(1)
+            .and_then(|d| d.checked_add_months(Months::new(1)))
+            .unwrap_or(date);
+        self.payment_date_in_month(next_month)
+    }
+
+    /// Last payment date strictly before `date`, or `None` before the start date.
+    fn previous_payment_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let this_month = self.payment_date_in_month(date);
+        let previous = if this_month < date {
+            this_month
+        } else {
+            let prev_month = date
+                .with_day(1)
+                .and_then(|d| d.checked_sub_months(Months::new(1)))?;
+            self.payment_date_in_month(prev_month)
+        };
+        match self.start_date {
+            Some(start) if previous < start => None,
+            _ => Some(previous),
+        }
+    }
+
+    /// First day accruing towards the payment on `payment_date`.
+    pub fn period_start(&self, payment_date: NaiveDate) -> NaiveDate {
+        let after_previous = self
+            .previous_payment_date(payment_date)
+            .and_then(|d| d.succ_opt());
+        match (after_previous, self.start_date) {
+            (Some(d), Some(start)) => d.max(start),
+            (Some(d), None) => d,
+            (None, Some(start)) => start,
+            (None, None) => payment_date,
+        }
+    }
+
+    /// Payment dates in `[from, to]`.
+    pub fn payment_dates_between(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let mut dates = Vec::new();
+        let mut date = self.next_payment_date(from);
+        while date <= to {
+            dates.push(date);
+            match date.succ_opt() {
+                Some(next) => date = self.next_payment_date(next),
+                None => break,
+            }
+        }
+        dates
+    }
+
+    /// Accrues interest for every day in `[from, to]`.
+    ///
+    /// `balances` holds end-of-day cash balances; days without an entry carry the
+    /// previous balance forward. With daily compounding the interest accrued so
+    /// far in the period is added to the balance earning interest.
+    pub fn accrue(
+        &self,
+        balances: &BTreeMap<NaiveDate, Decimal>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Vec<DailyAccrual> {
+        let mut days = Vec::new();
+        let mut accrued = Decimal::ZERO;
+        let mut cash = balances
+            .range(..from)
+            .next_back()
+            .map(|(_, b)| *b)
+            .unwrap_or(Decimal::ZERO);
+
+        let mut date = from;
+        while date <= to {
+            if let Some(balance) = balances.get(&date) {
+                cash = *balance;
+            }
+            let balance = match self.compounding {
+                Compounding::Daily => cash + accrued,
+                Compounding::Monthly => cash,
+            };
+            let interest = self.daily_interest(balance);
+            accrued += interest;
+            days.push(DailyAccrual {
+                date,
+                balance,
+                interest,
+            });
+            match date.succ_opt() {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+        days
+    }
+
+    /// Interest accrued in the payment period up to and including `date`, given
+    /// the end-of-day `cash` balance and the interest accrued up to the day before.
+    /// Accrual restarts from zero on the first day of each period.
+    pub fn accrue_day(&self, date: NaiveDate, cash: Decimal, accrued_before: Decimal) -> Decimal {
+        if self.start_date.is_some_and(|start| date < start) {
+            return Decimal::ZERO;
+        }
+        let carried = if date == self.period_start(self.next_payment_date(date)) {
+            Decimal::ZERO
+        } else {
+            accrued_before
+        };
+        let balance = match self.compounding {
+            Compounding::Daily => cash + carried,
+            Compounding::Monthly => cash,
+        };
+        carried + self.daily_interest(balance)
+    }
+
+    /// Reads the settings from an account `meta` JSON string.
+    pub fn from_account_meta(meta: Option<&str>) -> Option<Self> {
+        let meta: serde_json::Value = serde_json::from_str(meta?).ok()?;
+        serde_json::from_value(meta.get(CASH_INTEREST_META_KEY)?.clone()).ok()
+    }
+
+    /// Writes the settings into an account `meta` JSON string, keeping other keys.
+    /// `None` removes them.
+    pub fn merge_into_account_meta(settings: Option<&Self>, meta: Option<&str>) -> Result<String> {
+        let mut value = meta
+            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+            .filter(|v| v.is_object())
+            .unwrap_or_else(|| json!({}));
+        match settings {
+            Some(settings) => {
+                value[CASH_INTEREST_META_KEY] = serde_json::to_value(settings).map_err(|e| {
+                    Error::Unexpected(format!("Failed to serialize cash interest settings: {}", e))
+                })?;
+            }
+            None => {
+                if let Some(map) = value.as_object_mut() {
+                    map.remove(CASH_INTEREST_META_KEY);
+                }
+            }
+        }
+        Ok(value.to_string())
+    }
+}
+
+/// Average of the daily cash balances in `[from, to]`, carrying balances forward
+/// over days without an entry.
+pub fn average_balance(
+    balances: &BTreeMap<NaiveDate, Decimal>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Decimal {
+    let mut cash = balances
+        .range(..from)
+        .next_back()
+        .map(|(_, b)| *b)
+        .unwrap_or(Decimal::ZERO);
+    let mut total = Decimal::ZERO;
+    let mut days = 0u32;
+    let mut date = from;
+    while date <= to {
+        if let Some(balance) = balances.get(&date) {
+            cash = *balance;
+        }
+        total += cash;
+        days += 1;
+        match date.succ_opt() {
+            Some(next) => date = next,
+            None => break,
+        }
+    }
+    if days == 0 {
+        Decimal::ZERO
+    } else {
+        total / Decimal::from(days)
+    }
+}
+
+/// Stable record ID of the interest payment of an account on a date.
+pub fn interest_record_id(account_id: &str, payment_date: NaiveDate) -> String {
+    format!("{}:{}", account_id, payment_date)
+}
+
+/// `INTEREST` draft for interest accrued up to `payment_date`, for the user to confirm.
+pub fn interest_draft(
+    account_id: &str,
+    currency: &str,
+    payment_date: NaiveDate,
+    amount: Decimal,
+    period_start: NaiveDate,
+) -> NewActivity {
+    let record_id = interest_record_id(account_id, payment_date);
+    NewActivity {
+        id: None,
+        account_id: account_id.to_string(),
+        symbol: None,
+        activity_type: ACTIVITY_TYPE_INTEREST.to_string(),
+        subtype: None,
+        activity_date: payment_date.format("%Y-%m-%d").to_string(),
+        quantity: None,
+        unit_price: None,
+        currency: currency.to_string(),
+        fee: None,
+        amount: Some(amount),
+        status: Some(ActivityStatus::Draft),
+        notes: Some(format!(
+            "Accrued cash interest {} to {}",
+            period_start, payment_date
+        )),
+        fx_rate: None,
+        metadata: Some(
+            json!({
+                "cash_interest": {
+                    "period_start": period_start,
+                    "period_end": payment_date,
+                },
+            })
+            .to_string(),
+        ),
+        needs_review: Some(true),
+        source_system: Some(CASH_INTEREST_SOURCE_SYSTEM.to_string()),
+        source_record_id: Some(record_id.clone()),
+        source_group_id: None,
+        idempotency_key: Some(format!("{}:{}", CASH_INTEREST_SOURCE_SYSTEM, record_id)),
+    }
+}
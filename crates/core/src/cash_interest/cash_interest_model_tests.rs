@@ -0,0 +1,257 @@
+//! Tests for cash interest tiers, accrual and payment dates.
+
+#[cfg(test)]
+mod tests {
+    use crate::activities::{ActivityStatus, ACTIVITY_TYPE_INTEREST};
+    use crate::cash_interest::{
+        average_balance, interest_draft, ApyTier, CashInterestSettings, Compounding, TierMode,
+        CASH_INTEREST_SOURCE_SYSTEM,
+    };
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::collections::BTreeMap;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn settings(compounding: Compounding) -> CashInterestSettings {
+        CashInterestSettings {
+            currency: None,
+            tiers: vec![ApyTier {
+                min_balance: Decimal::ZERO,
+                apy: dec!(0.05),
+            }],
+            tier_mode: TierMode::Whole,
+            compounding,
+            payment_day: 31,
+            start_date: Some(date(2024, 1, 1)),
+            drafted_through: None,
+        }
+    }
+
+    fn tiered(tier_mode: TierMode) -> CashInterestSettings {
+        CashInterestSettings {
+            tiers: vec![
+                ApyTier {
+                    min_balance: dec!(10000),
+                    apy: dec!(0.04),
+                },
+                ApyTier {
+                    min_balance: Decimal::ZERO,
+                    apy: dec!(0.01),
+                },
+            ],
+            tier_mode,
+            ..settings(Compounding::Monthly)
+        }
+    }
+
+    fn constant_balance(balance: Decimal) -> BTreeMap<NaiveDate, Decimal> {
+        BTreeMap::from([(date(2023, 12, 31), balance)])
+    }
+
+    #[test]
+    fn test_daily_compounding_over_a_year_matches_apy() {
+        let s = settings(Compounding::Daily);
+        let days = s.accrue(
+            &constant_balance(dec!(10000)),
+            date(2025, 1, 1),
+            date(2025, 12, 31),
+        );
+        let interest: Decimal = days.iter().map(|d| d.interest).sum();
+
+        assert_eq!(days.len(), 365);
+        assert!((interest - dec!(500)).abs() < dec!(0.01));
+    }
+
+    #[test]
+    fn test_monthly_compounding_accrues_simple_interest() {
+        let s = settings(Compounding::Monthly);
+        let days = s.accrue(
+            &constant_balance(dec!(10000)),
+            date(2025, 1, 1),
+            date(2025, 1, 31),
+        );
+
+        // Every day earns the same amount: the balance does not grow until paid
+        assert!(days.iter().all(|d| d.balance == dec!(10000)));
+        assert!(days.iter().all(|d| d.interest == days[0].interest));
+        // (1.05^(1/12) - 1) * 10000 * 12/365 * 31
+        let interest: Decimal = days.iter().map(|d| d.interest).sum();
+        assert!((interest - dec!(41.52)).abs() < dec!(0.01));
+    }
+
+    #[test]
+    fn test_whole_balance_tiers_use_highest_tier_reached() {
+        let s = tiered(TierMode::Whole);
+
+        assert_eq!(s.apy_for(dec!(5000)), dec!(0.01));
+        assert_eq!(s.apy_for(dec!(10000)), dec!(0.04));
+        assert_eq!(s.apy_for(dec!(20000)), dec!(0.04));
+    }
+
+    #[test]
+    fn test_marginal_tiers_blend_rates() {
+        let s = tiered(TierMode::Marginal);
+
+        // 10k at 1% + 10k at 4% = 2.5% blended
+        assert_eq!(s.apy_for(dec!(20000)), dec!(0.025));
+        let marginal = s.daily_interest(dec!(20000));
+        let whole = tiered(TierMode::Whole).daily_interest(dec!(20000));
+        assert!(marginal < whole);
+    }
+
+    #[test]
+    fn test_negative_balance_earns_nothing() {
+        let s = settings(Compounding::Daily);
+        assert_eq!(s.daily_interest(dec!(-100)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_accrual_follows_balance_changes() {
+        let s = settings(Compounding::Monthly);
+        let mut balances = constant_balance(dec!(1000));
+        balances.insert(date(2025, 1, 16), dec!(3000));
+
+        let days = s.accrue(&balances, date(2025, 1, 1), date(2025, 1, 31));
+
+        assert_eq!(days[14].balance, dec!(1000));
+        assert_eq!(days[15].balance, dec!(3000));
+        assert_eq!(days[30].balance, dec!(3000));
+    }
+
+    #[test]
+    fn test_payment_day_is_clamped_to_month_end() {
+        let s = settings(Compounding::Monthly);
+        let dates = s.payment_dates_between(date(2024, 1, 15), date(2024, 4, 30));
+
+        assert_eq!(
+            dates,
+            vec![
+                date(2024, 1, 31),
+                date(2024, 2, 29),
+                date(2024, 3, 31),
+                date(2024, 4, 30)
+            ]
+        );
+        assert_eq!(s.period_start(date(2024, 3, 31)), date(2024, 3, 1));
+    }
+
+    #[test]
+    fn test_first_period_starts_at_start_date() {
+        let s = CashInterestSettings {
+            payment_day: 15,
+            start_date: Some(date(2024, 3, 20)),
+            ..settings(Compounding::Monthly)
+        };
+
+        assert_eq!(s.next_payment_date(date(2024, 3, 20)), date(2024, 4, 15));
+        assert_eq!(s.period_start(date(2024, 4, 15)), date(2024, 3, 20));
+        assert_eq!(s.period_start(date(2024, 5, 15)), date(2024, 4, 16));
+    }
+
+    #[test]
+    fn test_daily_accrual_matches_period_total_and_resets() {
+        let s = settings(Compounding::Daily);
+        let days = s.accrue(
+            &constant_balance(dec!(10000)),
+            date(2025, 3, 1),
+            date(2025, 3, 31),
+        );
+        let expected: Decimal = days.iter().map(|d| d.interest).sum();
+
+        let mut accrued = Decimal::ZERO;
+        let mut day = date(2025, 3, 1);
+        while day <= date(2025, 3, 31) {
+            accrued = s.accrue_day(day, dec!(10000), accrued);
+            day = day.succ_opt().unwrap();
+        }
+        assert_eq!(accrued, expected);
+
+        let next = s.accrue_day(date(2025, 4, 1), dec!(10000), accrued);
+        assert_eq!(next, days[0].interest);
+        assert_eq!(
+            s.accrue_day(date(2023, 12, 31), dec!(10000), Decimal::ZERO),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_percentages_and_bad_payment_day() {
+        let mut s = settings(Compounding::Daily);
+        s.tiers[0].apy = dec!(4.5);
+        assert!(s.validate().is_err());
+
+        let mut s = settings(Compounding::Daily);
+        s.payment_day = 0;
+        assert!(s.validate().is_err());
+
+        let mut s = settings(Compounding::Daily);
+        s.tiers.clear();
+        assert!(s.validate().is_err());
+
+        assert!(settings(Compounding::Daily).validate().is_ok());
+    }
+
+    #[test]
+    fn test_settings_round_trip_through_account_meta() {
+        let s = settings(Compounding::Daily);
+        let meta = CashInterestSettings::merge_into_account_meta(Some(&s), Some(r#"{"other":1}"#))
+            .unwrap();
+
+        assert_eq!(
+            CashInterestSettings::from_account_meta(Some(&meta)),
+            Some(s)
+        );
+        let value: serde_json::Value = serde_json::from_str(&meta).unwrap();
+        assert_eq!(value["other"], 1);
+
+        let cleared = CashInterestSettings::merge_into_account_meta(None, Some(&meta)).unwrap();
+        assert_eq!(
+            CashInterestSettings::from_account_meta(Some(&cleared)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_average_balance_carries_forward() {
+        let mut balances = constant_balance(dec!(1000));
+        balances.insert(date(2025, 1, 3), dec!(4000));
+
+        assert_eq!(
+            average_balance(&balances, date(2025, 1, 1), date(2025, 1, 4)),
+            dec!(2500)
+        );
+    }
+
+    #[test]
+    fn test_interest_draft() {
+        let activity = interest_draft(
+            "acc-1",
+            "USD",
+            date(2024, 1, 31),
+            dec!(41.52),
+            date(2024, 1, 1),
+        );
+
+        assert_eq!(activity.activity_type, ACTIVITY_TYPE_INTEREST);
+        assert_eq!(activity.status, Some(ActivityStatus::Draft));
+        assert_eq!(activity.amount, Some(dec!(41.52)));
+        assert_eq!(activity.activity_date, "2024-01-31");
+        assert!(activity.symbol.is_none());
+        assert_eq!(
+            activity.source_system.as_deref(),
+            Some(CASH_INTEREST_SOURCE_SYSTEM)
+        );
+        assert_eq!(
+            activity.source_record_id.as_deref(),
+            Some("acc-1:2024-01-31")
+        );
+        assert_eq!(
+            activity.idempotency_key.as_deref(),
+            Some("CASH_INTEREST:acc-1:2024-01-31")
+        );
+    }
+}
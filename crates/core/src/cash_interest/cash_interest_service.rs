@@ -0,0 +1,389 @@
+use crate::accounts::{Account, AccountServiceTrait, AccountUpdate};
+use crate::activities::{ActivityServiceTrait, ACTIVITY_TYPE_INTEREST};
+use crate::cash_interest::cash_interest_model::{
+    average_balance, interest_draft, interest_record_id, CashInterestAccrual, CashInterestSettings,
+    CashYieldSummary, Compounding, DailyAccrual, CASH_INTEREST_SOURCE_SYSTEM,
+};
+use crate::cash_interest::cash_interest_traits::CashInterestServiceTrait;
+use crate::errors::{Error, Result, ValidationError};
+use crate::portfolio::snapshot::SnapshotServiceTrait;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate, Utc};
+use log::{debug, warn};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+/// Days in the trailing window of the yield comparison.
+const TRAILING_DAYS: i64 = 365;
+
+pub struct CashInterestService {
+    account_service: Arc<dyn AccountServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    snapshot_service: Arc<dyn SnapshotServiceTrait>,
+}
+
+impl CashInterestService {
+    pub fn new(
+        account_service: Arc<dyn AccountServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        snapshot_service: Arc<dyn SnapshotServiceTrait>,
+    ) -> Self {
+        Self {
+            account_service,
+            activity_service,
+            snapshot_service,
+        }
+    }
+
+    fn settings_of(account: &Account) -> Result<CashInterestSettings> {
+        CashInterestSettings::from_account_meta(account.meta.as_deref()).ok_or_else(|| {
+            Error::Validation(ValidationError::InvalidInput(format!(
+                "Account {} has no cash interest settings",
+                account.id
+            )))
+        })
+    }
+
+    fn currency_of(account: &Account, settings: Option<&CashInterestSettings>) -> String {
+        settings
+            .and_then(|s| s.currency.clone())
+            .unwrap_or_else(|| account.currency.clone())
+    }
+
+    /// End-of-day cash balances of `currency` in `[from, to]`.
+    fn cash_balances(
+        &self,
+        account_id: &str,
+        currency: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<BTreeMap<NaiveDate, Decimal>> {
+        Ok(self
+            .snapshot_service
+            .get_daily_holdings_snapshots(account_id, Some(from), Some(to))?
+            .into_iter()
+            .map(|s| {
+                let cash = s
+                    .cash_balances
+                    .get(currency)
+                    .copied()
+                    .unwrap_or(Decimal::ZERO);
+                (s.snapshot_date, cash)
+            })
+            .collect())
+    }
+
+    /// Interest accrued up to each day in `[from, to]`, as stored in the snapshots.
+    fn accrued_interest(
+        &self,
+        account_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<BTreeMap<NaiveDate, Decimal>> {
+        Ok(self
+            .snapshot_service
+            .get_daily_holdings_snapshots(account_id, Some(from), Some(to))?
+            .into_iter()
+            .map(|s| (s.snapshot_date, s.cash_interest_accrued))
+            .collect())
+    }
+
+    /// Stores the settings in the account metadata.
+    async fn save_settings(
+        &self,
+        account: Account,
+        settings: Option<&CashInterestSettings>,
+    ) -> Result<Account> {
+        let meta =
+            CashInterestSettings::merge_into_account_meta(settings, account.meta.as_deref())?;
+        self.account_service
+            .update_account(AccountUpdate {
+                id: Some(account.id),
+                name: account.name,
+                account_type: account.account_type,
+                group: account.group,
+                is_default: account.is_default,
+                is_active: account.is_active,
+                platform_id: account.platform_id,
+                account_number: account.account_number,
+                meta: Some(meta),
+                provider: account.provider,
+                provider_account_id: account.provider_account_id,
+                is_archived: Some(account.is_archived),
+                tracking_mode: Some(account.tracking_mode),
+            })
+            .await
+    }
+
+    /// Creates interest drafts for past payment dates of a single account.
+    async fn record_payments(&self, account: &Account, as_of: NaiveDate) -> Result<usize> {
+        let Some(settings) = CashInterestSettings::from_account_meta(account.meta.as_deref())
+        else {
+            return Ok(0);
+        };
+        // Without a start date nothing before today is generated
+        let start = settings.start_date.unwrap_or(as_of);
+        let from = match settings.drafted_through.and_then(|d| d.succ_opt()) {
+            Some(next) => next.max(start),
+            None => start,
+        };
+        let payments = settings.payment_dates_between(from, as_of);
+        let Some(last_payment) = payments.last().copied() else {
+            return Ok(0);
+        };
+
+        let recorded: HashSet<String> = self
+            .activity_service
+            .get_activities_by_account_id(&account.id)?
+            .into_iter()
+            .filter(|a| a.source_system.as_deref() == Some(CASH_INTEREST_SOURCE_SYSTEM))
+            .filter_map(|a| a.source_record_id)
+            .collect();
+
+        let currency = Self::currency_of(account, Some(&settings));
+        let accrued = self.accrued_interest(&account.id, payments[0], last_payment)?;
+        let mut created = 0;
+        for &payment_date in &payments {
+            if recorded.contains(&interest_record_id(&account.id, payment_date)) {
+                continue;
+            }
+            let period_start = settings.period_start(payment_date);
+            let amount = accrued
+                .get(&payment_date)
+                .copied()
+                .unwrap_or(Decimal::ZERO)
+                .round_dp(2);
+            if amount <= Decimal::ZERO {
+                continue;
+            }
+            self.activity_service
+                .create_activity(interest_draft(
+                    &account.id,
+                    &currency,
+                    payment_date,
+                    amount,
+                    period_start,
+                ))
+                .await?;
+            created += 1;
+        }
+
+        if created > 0 {
+            debug!(
+                "Created {} cash interest drafts for account {}",
+                created, account.id
+            );
+        }
+
+        // Remember the payments handled so deleted drafts stay deleted. Payments
+        // without a snapshot yet are retried on the next run.
+        let drafted_through = payments
+            .iter()
+            .rev()
+            .find(|d| accrued.contains_key(d))
+            .copied();
+        if drafted_through.is_some() {
+            let settings = CashInterestSettings {
+                drafted_through,
+                ..settings
+            };
+            self.save_settings(account.clone(), Some(&settings)).await?;
+        }
+        Ok(created)
+    }
+
+    /// Posted cash interest (no asset) of `currency` in `(from, to]`.
+    fn posted_interest(
+        &self,
+        account_id: &str,
+        currency: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Decimal> {
+        Ok(self
+            .activity_service
+            .get_activities_by_account_id(account_id)?
+            .iter()
+            .filter(|a| {
+                a.activity_type == ACTIVITY_TYPE_INTEREST
+                    && a.is_posted()
+                    && a.asset_id.is_none()
+                    && a.currency == currency
+                    && a.effective_date() > from
+                    && a.effective_date() <= to
+            })
+            .map(|a| a.amt())
+            .sum())
+    }
+}
+
+#[async_trait]
+impl CashInterestServiceTrait for CashInterestService {
+    fn get_settings(&self, account_id: &str) -> Result<Option<CashInterestSettings>> {
+        let account = self.account_service.get_account(account_id)?;
+        Ok(CashInterestSettings::from_account_meta(
+            account.meta.as_deref(),
+        ))
+    }
+
+    async fn set_settings(
+        &self,
+        account_id: &str,
+        settings: Option<CashInterestSettings>,
+    ) -> Result<Account> {
+        let account = self.account_service.get_account(account_id)?;
+        let current = CashInterestSettings::from_account_meta(account.meta.as_deref());
+        let settings = match settings {
+            Some(settings) => {
+                settings.validate()?;
+                Some(CashInterestSettings {
+                    start_date: settings
+                        .start_date
+                        .or_else(|| Some(Utc::now().date_naive())),
+                    drafted_through: settings
+                        .drafted_through
+                        .or_else(|| current.and_then(|c| c.drafted_through)),
+                    ..settings
+                })
+            }
+            None => None,
+        };
+
+        let account = self.save_settings(account, settings.as_ref()).await?;
+        // The accrual stored in past snapshots depends on the settings
+        self.snapshot_service
+            .force_recalculate_holdings_snapshots(Some(std::slice::from_ref(&account.id)))
+            .await?;
+        Ok(account)
+    }
+
+    fn get_accrual(&self, account_id: &str, as_of: NaiveDate) -> Result<CashInterestAccrual> {
+        let account = self.account_service.get_account(account_id)?;
+        let settings = Self::settings_of(&account)?;
+        let currency = Self::currency_of(&account, Some(&settings));
+
+        let next_payment_date = settings.next_payment_date(as_of);
+        let period_start = settings.period_start(next_payment_date);
+        let from = period_start.min(as_of);
+        let balances = self.cash_balances(account_id, &currency, from, as_of)?;
+        let accrued = self.accrued_interest(account_id, from, as_of)?;
+
+        // Each day's interest is the change in the accrual stored in the snapshots
+        let mut days = Vec::new();
+        let mut accrued_before = Decimal::ZERO;
+        for (date, accrued_through) in accrued.range(period_start..=as_of) {
+            let cash = balances.get(date).copied().unwrap_or(Decimal::ZERO);
+            days.push(DailyAccrual {
+                date: *date,
+                balance: match settings.compounding {
+                    Compounding::Daily => cash + accrued_before,
+                    Compounding::Monthly => cash,
+                },
+                interest: *accrued_through - accrued_before,
+            });
+            accrued_before = *accrued_through;
+        }
+        let balance = balances
+            .range(..=as_of)
+            .next_back()
+            .map(|(_, b)| *b)
+            .unwrap_or(Decimal::ZERO);
+
+        Ok(CashInterestAccrual {
+            account_id: account.id,
+            currency,
+            as_of,
+            balance,
+            apy: settings.apy_for(balance),
+            period_start,
+            next_payment_date,
+            accrued_interest: accrued_before,
+            days,
+        })
+    }
+
+    fn get_yield_comparison(&self, as_of: NaiveDate) -> Result<Vec<CashYieldSummary>> {
+        let from = as_of - Duration::days(TRAILING_DAYS - 1);
+        let mut summaries = Vec::new();
+        for account in self.account_service.get_active_non_archived_accounts()? {
+            let settings = CashInterestSettings::from_account_meta(account.meta.as_deref());
+            let currency = Self::currency_of(&account, settings.as_ref());
+            let balances = self.cash_balances(&account.id, &currency, from, as_of)?;
+            let balance = balances
+                .range(..=as_of)
+                .next_back()
+                .map(|(_, b)| *b)
+                .unwrap_or(Decimal::ZERO);
+            if settings.is_none() && balance <= Decimal::ZERO {
+                continue;
+            }
+
+            let average_balance = average_balance(&balances, from, as_of);
+            let trailing_interest = self.posted_interest(
+                &account.id,
+                &currency,
+                as_of - Duration::days(TRAILING_DAYS),
+                as_of,
+            )?;
+            let configured_apy = settings.as_ref().map(|s| s.apy_for(balance));
+            summaries.push(CashYieldSummary {
+                account_id: account.id,
+                account_name: account.name,
+                currency,
+                balance,
+                configured_apy,
+                projected_annual_interest: (balance.max(Decimal::ZERO)
+                    * configured_apy.unwrap_or(Decimal::ZERO))
+                .round_dp(2),
+                trailing_interest,
+                realized_yield: (average_balance > Decimal::ZERO)
+                    .then(|| trailing_interest / average_balance),
+                average_balance,
+            });
+        }
+        summaries.sort_by(|a, b| {
+            b.configured_apy
+                .or(b.realized_yield)
+                .cmp(&a.configured_apy.or(a.realized_yield))
+        });
+        Ok(summaries)
+    }
+
+    async fn generate_interest_drafts(&self, as_of: NaiveDate) -> Result<usize> {
+        let accounts: Vec<Account> = self
+            .account_service
+            .get_active_non_archived_accounts()?
+            .into_iter()
+            .filter(|a| CashInterestSettings::from_account_meta(a.meta.as_deref()).is_some())
+            .collect();
+        if accounts.is_empty() {
+            return Ok(0);
+        }
+
+        // Bring the accrual in the snapshots up to date
+        let account_ids: Vec<String> = accounts.iter().map(|a| a.id.clone()).collect();
+        if let Err(e) = self
+            .snapshot_service
+            .calculate_holdings_snapshots(Some(&account_ids))
+            .await
+        {
+            warn!(
+                "Failed to update snapshots before cash interest drafts: {}",
+                e
+            );
+        }
+
+        let mut created = 0;
+        for account in accounts {
+            match self.record_payments(&account, as_of).await {
+                Ok(n) => created += n,
+                Err(e) => warn!(
+                    "Failed to create cash interest drafts for account {}: {}",
+                    account.id, e
+                ),
+            }
+        }
+        Ok(created)
+    }
+}
@@ -0,0 +1,32 @@
+use crate::accounts::Account;
+use crate::cash_interest::cash_interest_model::{
+    CashInterestAccrual, CashInterestSettings, CashYieldSummary,
+};
+use crate::errors::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+/// Trait for cash interest service operations
+#[async_trait]
+pub trait CashInterestServiceTrait: Send + Sync {
+    /// Cash interest settings of an account, if any.
+    fn get_settings(&self, account_id: &str) -> Result<Option<CashInterestSettings>>;
+
+    /// Validates and stores the settings in the account metadata. `None` removes them.
+    async fn set_settings(
+        &self,
+        account_id: &str,
+        settings: Option<CashInterestSettings>,
+    ) -> Result<Account>;
+
+    /// Interest accrued in the payment period containing `as_of`, as stored in
+    /// the holdings snapshots.
+    fn get_accrual(&self, account_id: &str, as_of: NaiveDate) -> Result<CashInterestAccrual>;
+
+    /// Configured and realized cash yields of active accounts holding cash.
+    fn get_yield_comparison(&self, as_of: NaiveDate) -> Result<Vec<CashYieldSummary>>;
+
+    /// Creates an `INTEREST` draft for every payment date on or before `as_of`
+    /// that was not drafted before. Returns the number created.
+    async fn generate_interest_drafts(&self, as_of: NaiveDate) -> Result<usize>;
+}
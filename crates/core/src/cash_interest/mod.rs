@@ -0,0 +1,16 @@
+//! Cash interest module - APY tiers, daily accrual and interest drafts for cash balances.
+
+mod cash_interest_model;
+mod cash_interest_service;
+mod cash_interest_traits;
+
+#[cfg(test)]
+mod cash_interest_model_tests;
+
+pub use cash_interest_model::{
+    average_balance, interest_draft, interest_record_id, ApyTier, CashInterestAccrual,
+    CashInterestSettings, CashYieldSummary, Compounding, DailyAccrual, TierMode,
+    CASH_INTEREST_META_KEY, CASH_INTEREST_SOURCE_SYSTEM,
+};
+pub use cash_interest_service::CashInterestService;
+pub use cash_interest_traits::CashInterestServiceTrait;
@@ -0,0 +1,342 @@
+//! Employee equity grants and vesting schedules.
+//!
+//! A grant awards shares of an employer asset (listed stock or a
+//! `PRIVATE_EQUITY` asset priced by manual quotes) into an account:
+//!
+//! - `RSU`: shares are delivered at each vest. Every vest becomes a
+//!   `TRANSFER_IN` activity with cost basis at the vest-date fair market value.
+//! - `ESPP`: shares are purchased at the end of an offering period, usually
+//!   modelled as a single vest on the purchase date. Treated like an RSU vest.
+//! - `ISO` / `NSO`: stock options. Vesting only grants the right to exercise,
+//!   so no activity is generated; value is the intrinsic value
+//!   (price - strike) of the vested and unvested options.
+
+use chrono::{Months, NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeSet;
+
+use crate::activities::{NewActivity, SymbolInput, ACTIVITY_TYPE_TRANSFER_IN};
+use crate::errors::{Error, Result, ValidationError};
+
+/// `source_system` of activities generated from vest events.
+pub const EQUITY_GRANT_SOURCE_SYSTEM: &str = "EQUITY_GRANT";
+
+/// Longest supported vesting period (50 years).
+const MAX_VESTING_MONTHS: u32 = 600;
+
+/// Kind of equity compensation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GrantType {
+    /// Restricted stock units.
+    Rsu,
+    /// Incentive stock options.
+    Iso,
+    /// Non-qualified stock options.
+    Nso,
+    /// Employee stock purchase plan.
+    Espp,
+}
+
+impl GrantType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GrantType::Rsu => "RSU",
+            GrantType::Iso => "ISO",
+            GrantType::Nso => "NSO",
+            GrantType::Espp => "ESPP",
+        }
+    }
+
+    /// Options carry a strike price and are valued at their intrinsic value.
+    pub fn is_option(&self) -> bool {
+        matches!(self, GrantType::Iso | GrantType::Nso)
+    }
+
+    /// Whether vesting delivers shares (and therefore generates activities).
+    pub fn delivers_shares(&self) -> bool {
+        !self.is_option()
+    }
+}
+
+impl std::str::FromStr for GrantType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "RSU" => Ok(GrantType::Rsu),
+            "ISO" => Ok(GrantType::Iso),
+            "NSO" => Ok(GrantType::Nso),
+            "ESPP" => Ok(GrantType::Espp),
+            other => Err(format!("Unknown grant type: {}", other)),
+        }
+    }
+}
+
+/// An equity grant with its vesting schedule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquityGrant {
+    pub id: String,
+    /// Account receiving the vested shares.
+    pub account_id: String,
+    /// Employer stock.
+    pub asset_id: String,
+    pub grant_type: GrantType,
+    pub grant_date: NaiveDate,
+    pub total_shares: Decimal,
+    /// Exercise price for options, purchase price for ESPP.
+    pub strike_price: Option<Decimal>,
+    /// Fair market value per share at grant, used when no quote exists at a vest date.
+    pub grant_price: Option<Decimal>,
+    pub currency: String,
+    pub vesting_start_date: NaiveDate,
+    /// Total vesting period. 0 vests everything on `vesting_start_date`.
+    pub vesting_months: u32,
+    /// Nothing vests before the cliff; the first vest releases everything accrued.
+    pub cliff_months: u32,
+    /// Months between vests after the cliff (1 = monthly, 3 = quarterly).
+    pub vesting_interval_months: u32,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Input for creating an equity grant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewEquityGrant {
+    pub account_id: String,
+    pub asset_id: String,
+    pub grant_type: GrantType,
+    pub grant_date: NaiveDate,
+    pub total_shares: Decimal,
+    pub strike_price: Option<Decimal>,
+    pub grant_price: Option<Decimal>,
+    /// Defaults to the asset's currency.
+    pub currency: Option<String>,
+    /// Defaults to the grant date.
+    pub vesting_start_date: Option<NaiveDate>,
+    pub vesting_months: u32,
+    #[serde(default)]
+    pub cliff_months: u32,
+    #[serde(default = "default_vesting_interval")]
+    pub vesting_interval_months: u32,
+    pub notes: Option<String>,
+}
+
+fn default_vesting_interval() -> u32 {
+    1
+}
+
+/// A single vest of a grant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestEvent {
+    pub date: NaiveDate,
+    pub shares: Decimal,
+    /// Shares vested up to and including this event.
+    pub cumulative_shares: Decimal,
+}
+
+/// Vested and unvested position of a grant at a price.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrantPosition {
+    pub vested_shares: Decimal,
+    pub unvested_shares: Decimal,
+    /// Market value of vested shares (intrinsic value for options).
+    pub vested_value: Decimal,
+    /// Market value of unvested shares (intrinsic value for options).
+    pub unvested_value: Decimal,
+}
+
+/// A grant with its position as of a date, for display.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquityGrantSummary {
+    pub grant: EquityGrant,
+    pub as_of: NaiveDate,
+    /// Latest price on or before `as_of`, in the grant currency.
+    pub price: Option<Decimal>,
+    #[serde(flatten)]
+    pub position: GrantPosition,
+    pub next_vest: Option<VestEvent>,
+}
+
+impl EquityGrant {
+    /// Validate the grant terms.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: &str| -> Result<()> {
+            Err(Error::Validation(ValidationError::InvalidInput(
+                msg.to_string(),
+            )))
+        };
+
+        if self.account_id.trim().is_empty() {
+            return invalid("Equity grant requires an account");
+        }
+        if self.asset_id.trim().is_empty() {
+            return invalid("Equity grant requires an asset");
+        }
+        if self.total_shares <= Decimal::ZERO {
+            return invalid("Total shares must be greater than 0");
+        }
+        if self.grant_type.is_option() && self.strike_price.is_none() {
+            return invalid("Stock options require a strike price");
+        }
+        if self.strike_price.is_some_and(|p| p < Decimal::ZERO)
+            || self.grant_price.is_some_and(|p| p < Decimal::ZERO)
+        {
+            return invalid("Prices cannot be negative");
+        }
+        if self.vesting_months > MAX_VESTING_MONTHS {
+            return invalid("Vesting period cannot be longer than 50 years");
+        }
+        if self.vesting_months > 0 && self.vesting_interval_months == 0 {
+            return invalid("Vesting interval must be at least one month");
+        }
+        if self.cliff_months > self.vesting_months {
+            return invalid("Cliff cannot be longer than the vesting period");
+        }
+        Ok(())
+    }
+
+    /// Vest events in date order. Shares vest in whole units; the final vest
+    /// carries any remainder so the events always add up to `total_shares`.
+    pub fn vest_events(&self) -> Vec<VestEvent> {
+        if self.vesting_months == 0 {
+            return vec![VestEvent {
+                date: self.vesting_start_date,
+                shares: self.total_shares,
+                cumulative_shares: self.total_shares,
+            }];
+        }
+
+        let interval = self.vesting_interval_months.max(1);
+        let mut offsets: BTreeSet<u32> = (1u32..)
+            .map_while(|k| k.checked_mul(interval))
+            .take_while(|m| *m < self.vesting_months)
+            .filter(|m| *m >= self.cliff_months)
+            .collect();
+        if self.cliff_months > 0 {
+            offsets.insert(self.cliff_months);
+        }
+        offsets.insert(self.vesting_months);
+
+        let months = Decimal::from(self.vesting_months);
+        let mut events = Vec::with_capacity(offsets.len());
+        let mut vested = Decimal::ZERO;
+        for offset in offsets {
+            let Some(date) = self
+                .vesting_start_date
+                .checked_add_months(Months::new(offset))
+            else {
+                break;
+            };
+            let cumulative = if offset >= self.vesting_months {
+                self.total_shares
+            } else {
+                (self.total_shares * Decimal::from(offset) / months).floor()
+            };
+            let shares = cumulative - vested;
+            if shares > Decimal::ZERO {
+                events.push(VestEvent {
+                    date,
+                    shares,
+                    cumulative_shares: cumulative,
+                });
+                vested = cumulative;
+            }
+        }
+        events
+    }
+
+    /// Shares vested on or before `date`.
+    pub fn vested_shares_on(&self, date: NaiveDate) -> Decimal {
+        self.vest_events()
+            .iter()
+            .take_while(|e| e.date <= date)
+            .last()
+            .map(|e| e.cumulative_shares)
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// First vest strictly after `date`.
+    pub fn next_vest_after(&self, date: NaiveDate) -> Option<VestEvent> {
+        self.vest_events().into_iter().find(|e| e.date > date)
+    }
+
+    /// Value of one share (or option) at `price`.
+    pub fn value_per_share(&self, price: Decimal) -> Decimal {
+        if self.grant_type.is_option() {
+            (price - self.strike_price.unwrap_or(Decimal::ZERO)).max(Decimal::ZERO)
+        } else {
+            price
+        }
+    }
+
+    /// Vested and unvested shares and values on `date` at `price`.
+    pub fn position_on(&self, date: NaiveDate, price: Option<Decimal>) -> GrantPosition {
+        let vested_shares = self.vested_shares_on(date);
+        let unvested_shares = self.total_shares - vested_shares;
+        let per_share = price
+            .map(|p| self.value_per_share(p))
+            .unwrap_or(Decimal::ZERO);
+        GrantPosition {
+            vested_shares,
+            unvested_shares,
+            vested_value: vested_shares * per_share,
+            unvested_value: unvested_shares * per_share,
+        }
+    }
+
+    /// Stable record ID of the activity generated for a vest.
+    pub fn vest_record_id(&self, event: &VestEvent) -> String {
+        format!("{}:{}", self.id, event.date)
+    }
+
+    /// Activity delivering the shares of a vest, with cost basis at `fmv`.
+    ///
+    /// Vested shares are compensation, so the transfer is flagged as an
+    /// external flow (new capital entering the portfolio).
+    pub fn vest_activity(&self, event: &VestEvent, fmv: Decimal) -> NewActivity {
+        let record_id = self.vest_record_id(event);
+        NewActivity {
+            id: None,
+            account_id: self.account_id.clone(),
+            symbol: Some(SymbolInput {
+                id: Some(self.asset_id.clone()),
+                ..Default::default()
+            }),
+            activity_type: ACTIVITY_TYPE_TRANSFER_IN.to_string(),
+            subtype: None,
+            activity_date: event.date.format("%Y-%m-%d").to_string(),
+            quantity: Some(event.shares),
+            unit_price: Some(fmv),
+            currency: self.currency.clone(),
+            fee: None,
+            amount: Some(event.shares * fmv),
+            status: None,
+            notes: Some(format!("{} vest", self.grant_type.as_str())),
+            fx_rate: None,
+            metadata: Some(
+                json!({
+                    "flow": { "is_external": true },
+                    "equity_grant": {
+                        "grant_id": self.id,
+                        "grant_type": self.grant_type.as_str(),
+                    },
+                })
+                .to_string(),
+            ),
+            needs_review: None,
+            source_system: Some(EQUITY_GRANT_SOURCE_SYSTEM.to_string()),
+            source_record_id: Some(record_id.clone()),
+            source_group_id: Some(self.id.clone()),
+            idempotency_key: Some(format!("{}:{}", EQUITY_GRANT_SOURCE_SYSTEM, record_id)),
+        }
+    }
+}
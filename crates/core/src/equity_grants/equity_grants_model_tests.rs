@@ -0,0 +1,186 @@
+//! Tests for equity grant vesting schedules and valuation.
+
+#[cfg(test)]
+mod tests {
+    use crate::activities::ACTIVITY_TYPE_TRANSFER_IN;
+    use crate::equity_grants::{EquityGrant, GrantType, EQUITY_GRANT_SOURCE_SYSTEM};
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    /// 4-year RSU grant with a 1-year cliff, vesting monthly.
+    fn rsu() -> EquityGrant {
+        EquityGrant {
+            id: "grant-1".to_string(),
+            account_id: "acc-1".to_string(),
+            asset_id: "ACME".to_string(),
+            grant_type: GrantType::Rsu,
+            grant_date: date(2024, 1, 15),
+            total_shares: dec!(4800),
+            strike_price: None,
+            grant_price: Some(dec!(50)),
+            currency: "USD".to_string(),
+            vesting_start_date: date(2024, 1, 15),
+            vesting_months: 48,
+            cliff_months: 12,
+            vesting_interval_months: 1,
+            notes: None,
+            created_at: date(2024, 1, 15).and_hms_opt(0, 0, 0).unwrap(),
+            updated_at: date(2024, 1, 15).and_hms_opt(0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_cliff_releases_accrued_shares_then_vests_monthly() {
+        let events = rsu().vest_events();
+
+        assert_eq!(events.len(), 37);
+        assert_eq!(events[0].date, date(2025, 1, 15));
+        assert_eq!(events[0].shares, dec!(1200));
+        assert_eq!(events[1].date, date(2025, 2, 15));
+        assert_eq!(events[1].shares, dec!(100));
+        assert_eq!(events.last().unwrap().date, date(2028, 1, 15));
+        assert_eq!(events.last().unwrap().cumulative_shares, dec!(4800));
+        let total: Decimal = events.iter().map(|e| e.shares).sum();
+        assert_eq!(total, dec!(4800));
+    }
+
+    #[test]
+    fn test_uneven_grant_vests_whole_shares_with_remainder_at_end() {
+        let grant = EquityGrant {
+            total_shares: dec!(1000),
+            cliff_months: 0,
+            vesting_interval_months: 3,
+            ..rsu()
+        };
+
+        let events = grant.vest_events();
+
+        assert_eq!(events.len(), 16);
+        assert_eq!(events[0].shares, dec!(62));
+        assert_eq!(events[1].cumulative_shares, dec!(125));
+        assert_eq!(events[2].cumulative_shares, dec!(187));
+        assert!(events.iter().all(|e| e.shares.fract().is_zero()));
+        assert_eq!(events.last().unwrap().cumulative_shares, dec!(1000));
+    }
+
+    #[test]
+    fn test_espp_without_vesting_period_vests_at_once() {
+        let grant = EquityGrant {
+            grant_type: GrantType::Espp,
+            total_shares: dec!(85),
+            strike_price: Some(dec!(42.50)),
+            vesting_start_date: date(2024, 6, 30),
+            vesting_months: 0,
+            cliff_months: 0,
+            ..rsu()
+        };
+
+        let events = grant.vest_events();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].date, date(2024, 6, 30));
+        assert_eq!(events[0].shares, dec!(85));
+    }
+
+    #[test]
+    fn test_position_splits_vested_and_unvested_value() {
+        let grant = rsu();
+
+        let before_cliff = grant.position_on(date(2024, 12, 31), Some(dec!(60)));
+        assert_eq!(before_cliff.vested_shares, Decimal::ZERO);
+        assert_eq!(before_cliff.unvested_value, dec!(288000));
+
+        let position = grant.position_on(date(2025, 3, 20), Some(dec!(60)));
+        assert_eq!(position.vested_shares, dec!(1400));
+        assert_eq!(position.unvested_shares, dec!(3400));
+        assert_eq!(position.vested_value, dec!(84000));
+        assert_eq!(position.unvested_value, dec!(204000));
+        assert_eq!(
+            grant.next_vest_after(date(2025, 3, 20)).unwrap().date,
+            date(2025, 4, 15)
+        );
+    }
+
+    #[test]
+    fn test_options_are_valued_at_intrinsic_value() {
+        let grant = EquityGrant {
+            grant_type: GrantType::Nso,
+            strike_price: Some(dec!(20)),
+            ..rsu()
+        };
+
+        let position = grant.position_on(date(2026, 1, 15), Some(dec!(35)));
+        assert_eq!(position.vested_shares, dec!(2400));
+        assert_eq!(position.vested_value, dec!(36000));
+        assert_eq!(position.unvested_value, dec!(36000));
+
+        // Underwater options are worth nothing, not a negative amount
+        let underwater = grant.position_on(date(2026, 1, 15), Some(dec!(15)));
+        assert_eq!(underwater.vested_value, Decimal::ZERO);
+        assert!(!grant.grant_type.delivers_shares());
+    }
+
+    #[test]
+    fn test_vest_activity_books_shares_at_vest_date_fmv() {
+        let grant = rsu();
+        let event = grant.vest_events().remove(0);
+
+        let activity = grant.vest_activity(&event, dec!(72.15));
+
+        assert_eq!(activity.activity_type, ACTIVITY_TYPE_TRANSFER_IN);
+        assert_eq!(activity.activity_date, "2025-01-15");
+        assert_eq!(activity.quantity, Some(dec!(1200)));
+        assert_eq!(activity.unit_price, Some(dec!(72.15)));
+        assert_eq!(activity.amount, Some(dec!(86580.00)));
+        assert_eq!(
+            activity.symbol.as_ref().and_then(|s| s.id.as_deref()),
+            Some("ACME")
+        );
+        assert_eq!(
+            activity.source_system.as_deref(),
+            Some(EQUITY_GRANT_SOURCE_SYSTEM)
+        );
+        assert_eq!(
+            activity.source_record_id.as_deref(),
+            Some("grant-1:2025-01-15")
+        );
+        let metadata: serde_json::Value =
+            serde_json::from_str(activity.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata["flow"]["is_external"], true);
+        assert_eq!(metadata["equity_grant"]["grant_type"], "RSU");
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_terms() {
+        assert!(rsu().validate().is_ok());
+
+        let option_without_strike = EquityGrant {
+            grant_type: GrantType::Iso,
+            ..rsu()
+        };
+        assert!(option_without_strike.validate().is_err());
+
+        let cliff_after_vesting = EquityGrant {
+            cliff_months: 60,
+            ..rsu()
+        };
+        assert!(cliff_after_vesting.validate().is_err());
+
+        let no_shares = EquityGrant {
+            total_shares: Decimal::ZERO,
+            ..rsu()
+        };
+        assert!(no_shares.validate().is_err());
+
+        let endless_vesting = EquityGrant {
+            vesting_months: u32::MAX,
+            ..rsu()
+        };
+        assert!(endless_vesting.validate().is_err());
+    }
+}
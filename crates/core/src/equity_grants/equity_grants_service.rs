@@ -0,0 +1,273 @@
+use crate::activities::Activity;
+use crate::activities::ActivityServiceTrait;
+use crate::assets::AssetRepositoryTrait;
+use crate::equity_grants::equity_grants_model::{
+    EquityGrant, EquityGrantSummary, NewEquityGrant, VestEvent, EQUITY_GRANT_SOURCE_SYSTEM,
+};
+use crate::equity_grants::equity_grants_traits::{
+    EquityGrantRepositoryTrait, EquityGrantServiceTrait,
+};
+use crate::errors::{Error, Result, ValidationError};
+use crate::fx::currency::{denormalization_multiplier, normalize_amount, normalize_currency_code};
+use crate::quotes::QuoteServiceTrait;
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use log::{debug, warn};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct EquityGrantService {
+    repository: Arc<dyn EquityGrantRepositoryTrait>,
+    asset_repository: Arc<dyn AssetRepositoryTrait>,
+    quote_service: Arc<dyn QuoteServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+}
+
+impl EquityGrantService {
+    pub fn new(
+        repository: Arc<dyn EquityGrantRepositoryTrait>,
+        asset_repository: Arc<dyn AssetRepositoryTrait>,
+        quote_service: Arc<dyn QuoteServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            asset_repository,
+            quote_service,
+            activity_service,
+        }
+    }
+
+    /// Price history of a grant's asset as (date, price in the grant currency),
+    /// sorted by date. Quotes in the minor or major unit of the grant currency
+    /// (e.g. GBp and GBP) are converted to the grant's unit.
+    fn price_history(&self, grant: &EquityGrant) -> Vec<(NaiveDate, Decimal)> {
+        let grant_major = normalize_currency_code(&grant.currency);
+        let to_grant_unit = denormalization_multiplier(&grant.currency);
+        let mut prices: Vec<(NaiveDate, Decimal)> = self
+            .quote_service
+            .get_historical_quotes(&grant.asset_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|q| {
+                let (price, currency) = normalize_amount(q.close, &q.currency);
+                if currency != grant_major {
+                    return None;
+                }
+                Some((q.timestamp.date_naive(), price * to_grant_unit))
+            })
+            .collect();
+        prices.sort_by_key(|(d, _)| *d);
+        prices
+    }
+
+    /// Latest price on or before `date`.
+    fn price_on(prices: &[(NaiveDate, Decimal)], date: NaiveDate) -> Option<Decimal> {
+        let idx = prices.partition_point(|(d, _)| *d <= date);
+        idx.checked_sub(1).map(|i| prices[i].1)
+    }
+
+    /// Checks the grant's asset is an investment (listed stock or private equity).
+    fn check_asset(&self, asset_id: &str) -> Result<crate::assets::Asset> {
+        let asset = self.asset_repository.get_by_id(asset_id)?;
+        if !asset.kind.is_investment() {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Asset {} cannot be granted as equity (kind: {:?})",
+                asset_id, asset.kind
+            ))));
+        }
+        Ok(asset)
+    }
+
+    /// Records activities for past vests of a single grant.
+    async fn record_vests(&self, grant: &EquityGrant, as_of: NaiveDate) -> Result<usize> {
+        if !grant.grant_type.delivers_shares() {
+            return Ok(0);
+        }
+
+        let due: Vec<VestEvent> = grant
+            .vest_events()
+            .into_iter()
+            .take_while(|e| e.date <= as_of)
+            .collect();
+        if due.is_empty() {
+            return Ok(0);
+        }
+
+        let recorded: HashSet<String> = self
+            .activity_service
+            .get_activities_by_account_id(&grant.account_id)?
+            .into_iter()
+            .filter(|a| a.source_system.as_deref() == Some(EQUITY_GRANT_SOURCE_SYSTEM))
+            .filter_map(|a| a.source_record_id)
+            .collect();
+
+        let prices = self.price_history(grant);
+        let mut created = 0;
+        for event in due {
+            if recorded.contains(&grant.vest_record_id(&event)) {
+                continue;
+            }
+            let Some(fmv) = Self::price_on(&prices, event.date).or(grant.grant_price) else {
+                warn!(
+                    "No price for {} on {}; skipping vest of grant {}",
+                    grant.asset_id, event.date, grant.id
+                );
+                continue;
+            };
+            self.activity_service
+                .create_activity(grant.vest_activity(&event, fmv))
+                .await?;
+            created += 1;
+        }
+
+        if created > 0 {
+            debug!(
+                "Recorded {} vest activities for grant {}",
+                created, grant.id
+            );
+        }
+        Ok(created)
+    }
+
+    /// Activities generated from the vests of a grant in the given accounts.
+    fn vest_activities_of(&self, grant_id: &str, account_ids: &[String]) -> Result<Vec<Activity>> {
+        Ok(self
+            .activity_service
+            .get_activities_by_account_ids(account_ids)?
+            .into_iter()
+            .filter(|a| {
+                a.source_system.as_deref() == Some(EQUITY_GRANT_SOURCE_SYSTEM)
+                    && a.source_group_id.as_deref() == Some(grant_id)
+            })
+            .collect())
+    }
+
+    /// Deletes vest activities that no longer match the grant's schedule, so
+    /// `record_vests` books them again from the current terms.
+    async fn remove_stale_vests(&self, grant: &EquityGrant, previous: &EquityGrant) -> Result<()> {
+        let schedule: HashMap<String, Decimal> = if grant.grant_type.delivers_shares() {
+            grant
+                .vest_events()
+                .iter()
+                .map(|e| (grant.vest_record_id(e), e.shares))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        let mut account_ids = vec![previous.account_id.clone()];
+        if grant.account_id != previous.account_id {
+            account_ids.push(grant.account_id.clone());
+        }
+        for activity in self.vest_activities_of(&grant.id, &account_ids)? {
+            let current = activity.account_id == grant.account_id
+                && activity.asset_id.as_deref() == Some(grant.asset_id.as_str())
+                && activity.currency == grant.currency
+                && activity
+                    .source_record_id
+                    .as_ref()
+                    .and_then(|id| schedule.get(id))
+                    .is_some_and(|shares| activity.quantity == Some(*shares));
+            if !current {
+                self.activity_service.delete_activity(activity.id).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EquityGrantServiceTrait for EquityGrantService {
+    fn get_equity_grants(&self) -> Result<Vec<EquityGrant>> {
+        self.repository.list_equity_grants()
+    }
+
+    fn get_equity_grant_summaries(&self, as_of: NaiveDate) -> Result<Vec<EquityGrantSummary>> {
+        let grants = self.repository.list_equity_grants()?;
+        Ok(grants
+            .into_iter()
+            .map(|grant| {
+                let prices = self.price_history(&grant);
+                let price = Self::price_on(&prices, as_of).or(grant.grant_price);
+                EquityGrantSummary {
+                    position: grant.position_on(as_of, price),
+                    next_vest: grant.next_vest_after(as_of),
+                    price,
+                    as_of,
+                    grant,
+                }
+            })
+            .collect())
+    }
+
+    fn get_vesting_schedule(&self, grant_id: &str) -> Result<Vec<VestEvent>> {
+        Ok(self.repository.get_equity_grant(grant_id)?.vest_events())
+    }
+
+    async fn create_equity_grant(&self, new_grant: NewEquityGrant) -> Result<EquityGrant> {
+        let asset = self.check_asset(&new_grant.asset_id)?;
+        let now = Utc::now().naive_utc();
+        let grant = EquityGrant {
+            id: Uuid::new_v4().to_string(),
+            account_id: new_grant.account_id,
+            asset_id: new_grant.asset_id,
+            grant_type: new_grant.grant_type,
+            grant_date: new_grant.grant_date,
+            total_shares: new_grant.total_shares,
+            strike_price: new_grant.strike_price,
+            grant_price: new_grant.grant_price,
+            currency: new_grant.currency.unwrap_or(asset.quote_ccy),
+            vesting_start_date: new_grant.vesting_start_date.unwrap_or(new_grant.grant_date),
+            vesting_months: new_grant.vesting_months,
+            cliff_months: new_grant.cliff_months,
+            vesting_interval_months: new_grant.vesting_interval_months,
+            notes: new_grant.notes,
+            created_at: now,
+            updated_at: now,
+        };
+        grant.validate()?;
+
+        let grant = self.repository.insert_equity_grant(grant).await?;
+        self.record_vests(&grant, Utc::now().date_naive()).await?;
+        Ok(grant)
+    }
+
+    async fn update_equity_grant(&self, grant: EquityGrant) -> Result<EquityGrant> {
+        let existing = self.repository.get_equity_grant(&grant.id)?;
+        self.check_asset(&grant.asset_id)?;
+        let grant = EquityGrant {
+            created_at: existing.created_at,
+            updated_at: Utc::now().naive_utc(),
+            ..grant
+        };
+        grant.validate()?;
+
+        let grant = self.repository.update_equity_grant(grant).await?;
+        self.remove_stale_vests(&grant, &existing).await?;
+        self.record_vests(&grant, Utc::now().date_naive()).await?;
+        Ok(grant)
+    }
+
+    async fn delete_equity_grant(&self, grant_id: &str) -> Result<()> {
+        let grant = self.repository.get_equity_grant(grant_id)?;
+        for activity in self.vest_activities_of(grant_id, &[grant.account_id])? {
+            self.activity_service.delete_activity(activity.id).await?;
+        }
+        self.repository.delete_equity_grant(grant_id).await?;
+        Ok(())
+    }
+
+    async fn sync_vest_activities(&self, as_of: NaiveDate) -> Result<usize> {
+        let mut created = 0;
+        for grant in self.repository.list_equity_grants()? {
+            match self.record_vests(&grant, as_of).await {
+                Ok(n) => created += n,
+                Err(e) => warn!("Failed to record vests of grant {}: {}", grant.id, e),
+            }
+        }
+        Ok(created)
+    }
+}
@@ -0,0 +1,44 @@
+use crate::equity_grants::equity_grants_model::{
+    EquityGrant, EquityGrantSummary, NewEquityGrant, VestEvent,
+};
+use crate::errors::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+/// Trait for equity grant repository operations
+#[async_trait]
+pub trait EquityGrantRepositoryTrait: Send + Sync {
+    fn list_equity_grants(&self) -> Result<Vec<EquityGrant>>;
+    fn get_equity_grant(&self, grant_id: &str) -> Result<EquityGrant>;
+    async fn insert_equity_grant(&self, grant: EquityGrant) -> Result<EquityGrant>;
+    async fn update_equity_grant(&self, grant: EquityGrant) -> Result<EquityGrant>;
+    async fn delete_equity_grant(&self, grant_id: &str) -> Result<usize>;
+}
+
+/// Trait for equity grant service operations
+#[async_trait]
+pub trait EquityGrantServiceTrait: Send + Sync {
+    fn get_equity_grants(&self) -> Result<Vec<EquityGrant>>;
+
+    /// Grants with vested/unvested shares and values as of `as_of`.
+    fn get_equity_grant_summaries(&self, as_of: NaiveDate) -> Result<Vec<EquityGrantSummary>>;
+
+    /// Vest events of a grant.
+    fn get_vesting_schedule(&self, grant_id: &str) -> Result<Vec<VestEvent>>;
+
+    /// Creates a grant and records activities for vests that already happened.
+    async fn create_equity_grant(&self, new_grant: NewEquityGrant) -> Result<EquityGrant>;
+
+    /// Updates a grant and records activities for vests that already happened.
+    ///
+    /// Vest activities that no longer match the schedule (a date that left it,
+    /// a different share count, account or asset) are deleted and booked again.
+    async fn update_equity_grant(&self, grant: EquityGrant) -> Result<EquityGrant>;
+
+    /// Deletes a grant and the activities generated from its vests.
+    async fn delete_equity_grant(&self, grant_id: &str) -> Result<()>;
+
+    /// Records a `TRANSFER_IN` activity for every share-delivering vest on or
+    /// before `as_of` that has no activity yet. Returns the number created.
+    async fn sync_vest_activities(&self, as_of: NaiveDate) -> Result<usize>;
+}
@@ -0,0 +1,15 @@
+//! Equity grants module - employee equity (RSU/ESPP/options) and vesting schedules.
+
+mod equity_grants_model;
+mod equity_grants_service;
+mod equity_grants_traits;
+
+#[cfg(test)]
+mod equity_grants_model_tests;
+
+pub use equity_grants_model::{
+    EquityGrant, EquityGrantSummary, GrantPosition, GrantType, NewEquityGrant, VestEvent,
+    EQUITY_GRANT_SOURCE_SYSTEM,
+};
+pub use equity_grants_service::EquityGrantService;
+pub use equity_grants_traits::{EquityGrantRepositoryTrait, EquityGrantServiceTrait};
@@ -4,6 +4,38 @@ use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
+/// Currencies tried, in order, as the intermediate leg when a pair has no direct rate.
+pub const FX_PIVOT_CURRENCIES: [&str; 2] = ["USD", "EUR"];
+
+/// A rate together with how it was derived.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedRate {
+    pub rate: Decimal,
+    /// Intermediate currencies of the path; empty for a direct (or inverted) pair.
+    pub via: Vec<String>,
+    /// Largest distance in days between the requested date and the date of a rate
+    /// used on the path. Zero when every leg had a rate for that exact day.
+    pub carried_days: i64,
+}
+
+impl ResolvedRate {
+    fn direct(rate: Decimal, carried_days: i64) -> Self {
+        Self {
+            rate,
+            via: Vec::new(),
+            carried_days,
+        }
+    }
+
+    pub fn is_triangulated(&self) -> bool {
+        !self.via.is_empty()
+    }
+
+    pub fn is_carried(&self) -> bool {
+        self.carried_days > 0
+    }
+}
+
 /// A calculator for currency conversions using a Graph-based approach.
 /// It stores rates as independent time-series per pair and calculates paths on demand.
 /// It supports exact matching and "nearest neighbor" lookups (past or future).
@@ -75,77 +107,109 @@ impl CurrencyConverter {
     /// Strategy: Bidirectional Nearest Neighbor.
     /// 1. Finds the closest rate ON or BEFORE the date.
     /// 2. Finds the closest rate AFTER the date.
-    /// 3. Returns the one with the smallest day difference.
-    fn get_direct_rate(&self, from: &str, to: &str, date: NaiveDate) -> Option<Decimal> {
-        let key = (from.to_string(), to.to_string());
-
-        if let Some(history) = self.rates.get(&key) {
-            // Find the closest rate in the past/present (<= date)
-            let prev = history.range(..=date).next_back();
-
-            // Find the closest rate in the future/present (>= date)
-            let next = history.range(date..).next();
-
-            match (prev, next) {
-                (Some((d1, r1)), Some((d2, r2))) => {
-                    // If exact match found, d1 == d2 == date.
-                    if d1 == d2 {
-                        return Some(*r1);
-                    }
+    /// 3. Returns the one with the smallest day difference, along with that difference.
+    fn get_direct_rate(&self, from: &str, to: &str, date: NaiveDate) -> Option<(Decimal, i64)> {
+        let history = self.rates.get(&(from.to_string(), to.to_string()))?;
+
+        // Closest rate in the past/present (<= date)
+        let prev = history
+            .range(..=date)
+            .next_back()
+            .map(|(d, r)| (*r, (date - *d).num_days()));
+
+        // Closest rate in the future/present (>= date)
+        let next = history
+            .range(date..)
+            .next()
+            .map(|(d, r)| (*r, (*d - date).num_days()));
+
+        match (prev, next) {
+            // On a tie (including an exact match) the past rate wins
+            (Some(p), Some(n)) => Some(if p.1 <= n.1 { p } else { n }),
+            // Only one side exists (e.g., static single rate)
+            (p, n) => p.or(n),
+        }
+    }
 
-                    // Compare distances.
-                    // date is NaiveDate (value), d1/d2 are &NaiveDate (refs)
-                    // We must dereference d1/d2, but NOT date.
-                    let dist_prev = (date - *d1).num_days().abs();
-                    let dist_next = (*d2 - date).num_days().abs();
+    /// Resolves the rate for `from -> to` on `date`, recording how it was derived.
+    ///
+    /// Tries, in order: the direct pair (or its inverse), a triangulation through
+    /// each of [`FX_PIVOT_CURRENCIES`], then the shortest path through any pairs.
+    pub fn resolve_rate(
+        &self,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+    ) -> Result<ResolvedRate, FxError> {
+        if from_currency == to_currency {
+            return Ok(ResolvedRate::direct(Decimal::ONE, 0));
+        }
 
-                    if dist_prev <= dist_next {
-                        Some(*r1)
-                    } else {
-                        Some(*r2)
-                    }
-                }
-                (Some((_, r)), None) => Some(*r), // Only past exists
-                (None, Some((_, r))) => Some(*r), // Only future exists (e.g., Static single rate)
-                (None, None) => None,
+        if let Some((rate, carried_days)) = self.get_direct_rate(from_currency, to_currency, date) {
+            return Ok(ResolvedRate::direct(rate, carried_days));
+        }
+
+        for pivot in FX_PIVOT_CURRENCIES {
+            if pivot == from_currency || pivot == to_currency {
+                continue;
+            }
+            if let (Some((first, first_days)), Some((second, second_days))) = (
+                self.get_direct_rate(from_currency, pivot, date),
+                self.get_direct_rate(pivot, to_currency, date),
+            ) {
+                return Ok(ResolvedRate {
+                    rate: first * second,
+                    via: vec![pivot.to_string()],
+                    carried_days: first_days.max(second_days),
+                });
             }
-        } else {
-            None
         }
+
+        self.find_path(from_currency, to_currency, date)
     }
 
-    /// Converts an amount using Breadth-First Search (BFS) to find the shortest path.
+    /// Breadth-First Search (BFS) for the shortest path between two currencies.
     /// Uses `get_direct_rate` to validate edges, ensuring the best available rate is used per hop.
-    pub fn convert_amount(
+    fn find_path(
         &self,
-        amount: Decimal,
         from_currency: &str,
         to_currency: &str,
         date: NaiveDate,
-    ) -> Result<Decimal, FxError> {
-        if from_currency == to_currency {
-            return Ok(amount);
-        }
-
-        // BFS State: (Current Currency, Accumulated Rate)
-        let mut queue: VecDeque<(String, Decimal)> = VecDeque::new();
+    ) -> Result<ResolvedRate, FxError> {
+        // BFS State: (Current Currency, Accumulated Rate, Path so far, Largest carry)
+        let mut queue: VecDeque<(String, Decimal, Vec<String>, i64)> = VecDeque::new();
         let mut visited: HashSet<String> = HashSet::new();
 
-        queue.push_back((from_currency.to_string(), Decimal::ONE));
+        queue.push_back((from_currency.to_string(), Decimal::ONE, Vec::new(), 0));
         visited.insert(from_currency.to_string());
 
-        while let Some((current_curr, current_rate)) = queue.pop_front() {
+        while let Some((current_curr, current_rate, path, carried_days)) = queue.pop_front() {
             if current_curr == to_currency {
-                return Ok(amount * current_rate);
+                // The path ends with the target itself, which is not an intermediate
+                let via = path[..path.len().saturating_sub(1)].to_vec();
+                return Ok(ResolvedRate {
+                    rate: current_rate,
+                    via,
+                    carried_days,
+                });
             }
 
             if let Some(neighbors) = self.adj.get(&current_curr) {
                 for neighbor in neighbors {
                     if !visited.contains(neighbor) {
                         // Check if a rate exists nearby for this specific edge
-                        if let Some(rate) = self.get_direct_rate(&current_curr, neighbor, date) {
+                        if let Some((rate, days)) =
+                            self.get_direct_rate(&current_curr, neighbor, date)
+                        {
                             visited.insert(neighbor.clone());
-                            queue.push_back((neighbor.clone(), current_rate * rate));
+                            let mut next_path = path.clone();
+                            next_path.push(neighbor.clone());
+                            queue.push_back((
+                                neighbor.clone(),
+                                current_rate * rate,
+                                next_path,
+                                carried_days.max(days),
+                            ));
                         }
                     }
                 }
@@ -158,6 +222,20 @@ impl CurrencyConverter {
         )))
     }
 
+    /// Converts an amount using the rate from `resolve_rate`.
+    pub fn convert_amount(
+        &self,
+        amount: Decimal,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+    ) -> Result<Decimal, FxError> {
+        if from_currency == to_currency {
+            return Ok(amount);
+        }
+        Ok(amount * self.resolve_rate(from_currency, to_currency, date)?.rate)
+    }
+
     /// Alias for backward compatibility. Functionally identical to `convert_amount`
     /// as strict vs nearest logic is handled inside `get_direct_rate`.
     pub fn convert_amount_nearest(
@@ -274,4 +352,74 @@ mod tests {
             .unwrap();
         assert_eq!(r2, Decimal::from(100));
     }
+
+    #[test]
+    fn test_triangulates_through_usd_before_eur() {
+        let rates = vec![
+            make_rate("CHF", "USD", 1.10, 2024, 3, 1),
+            make_rate("USD", "SEK", 10.0, 2024, 3, 1),
+            make_rate("CHF", "EUR", 1.05, 2024, 3, 1),
+            make_rate("EUR", "SEK", 11.0, 2024, 3, 1),
+        ];
+        let converter = CurrencyConverter::new(rates).unwrap();
+
+        let resolved = converter
+            .resolve_rate("CHF", "SEK", NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+            .unwrap();
+        assert_eq!(resolved.via, vec!["USD".to_string()]);
+        assert_eq!(resolved.rate.round_dp(6), Decimal::from(11));
+        assert!(!resolved.is_carried());
+
+        // The inverse uses the same legs
+        let inverse = converter
+            .resolve_rate("SEK", "CHF", NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+            .unwrap();
+        assert_eq!(inverse.via, vec!["USD".to_string()]);
+    }
+
+    #[test]
+    fn test_direct_rate_wins_over_triangulation() {
+        let rates = vec![
+            make_rate("CHF", "SEK", 12.0, 2024, 3, 1),
+            make_rate("CHF", "USD", 1.10, 2024, 3, 1),
+            make_rate("USD", "SEK", 10.0, 2024, 3, 1),
+        ];
+        let converter = CurrencyConverter::new(rates).unwrap();
+
+        let resolved = converter
+            .resolve_rate("CHF", "SEK", NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+            .unwrap();
+        assert!(!resolved.is_triangulated());
+        assert_eq!(resolved.rate, Decimal::from(12));
+    }
+
+    #[test]
+    fn test_carried_days_is_largest_leg_gap() {
+        let rates = vec![
+            make_rate("CHF", "USD", 1.10, 2024, 3, 1),
+            make_rate("USD", "SEK", 10.0, 2024, 3, 4),
+        ];
+        let converter = CurrencyConverter::new(rates).unwrap();
+
+        let resolved = converter
+            .resolve_rate("CHF", "SEK", NaiveDate::from_ymd_opt(2024, 3, 6).unwrap())
+            .unwrap();
+        assert_eq!(resolved.carried_days, 5);
+        assert!(resolved.is_carried());
+    }
+
+    #[test]
+    fn test_path_without_pivot_lists_intermediates() {
+        let rates = vec![
+            make_rate("NOK", "DKK", 0.65, 2024, 3, 1),
+            make_rate("DKK", "ISK", 20.0, 2024, 3, 1),
+        ];
+        let converter = CurrencyConverter::new(rates).unwrap();
+
+        let resolved = converter
+            .resolve_rate("NOK", "ISK", NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+            .unwrap();
+        assert_eq!(resolved.via, vec!["DKK".to_string()]);
+        assert_eq!(resolved.rate.round_dp(6), Decimal::from(13));
+    }
 }
@@ -1,5 +1,5 @@
 use crate::quotes::{DataSource, Quote};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -92,3 +92,28 @@ pub struct NewExchangeRate {
     pub rate: Decimal,
     pub source: DataSource,
 }
+
+/// Record of rates for a pair that were not read directly from that pair's own
+/// quotes on the requested day. Kept in memory since the last converter refresh.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FxRateQuality {
+    pub from_currency: String,
+    pub to_currency: String,
+    /// Lookups answered through intermediate currencies
+    pub triangulated_count: u64,
+    /// Intermediate currencies used by those lookups
+    pub via: Vec<String>,
+    /// Lookups answered with a rate from another day
+    pub carried_count: u64,
+    /// Largest distance in days between a requested date and the rate used
+    pub max_carried_days: i64,
+    /// Most recent requested date that needed a triangulated or carried rate
+    pub last_date: Option<NaiveDate>,
+}
+
+impl FxRateQuality {
+    pub fn pair_id(&self) -> String {
+        format!("{}:{}", self.from_currency, self.to_currency)
+    }
+}
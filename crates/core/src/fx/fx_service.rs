@@ -1,6 +1,6 @@
-use super::currency_converter::CurrencyConverter;
+use super::currency_converter::{CurrencyConverter, FX_PIVOT_CURRENCIES};
 use super::fx_errors::FxError;
-use super::fx_model::{ExchangeRate, NewExchangeRate};
+use super::fx_model::{ExchangeRate, FxRateQuality, NewExchangeRate};
 use super::fx_traits::{FxRepositoryTrait, FxServiceTrait};
 use crate::errors::Result;
 use crate::events::{DomainEvent, DomainEventSink, NoOpDomainEventSink};
@@ -9,14 +9,19 @@ use crate::quotes::DataSource;
 use async_trait::async_trait;
 use chrono::{NaiveDate, Utc};
 use rust_decimal::Decimal;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 
+/// Rates up to this many days away from the requested date (weekends, holidays)
+/// are not recorded as carried.
+const CARRY_TOLERANCE_DAYS: i64 = 3;
+
 #[derive(Clone)]
 pub struct FxService {
     repository: Arc<dyn FxRepositoryTrait>,
     converter: Arc<RwLock<Option<CurrencyConverter>>>,
     event_sink: Arc<dyn DomainEventSink>,
+    rate_quality: Arc<RwLock<HashMap<(String, String), FxRateQuality>>>,
 }
 
 impl FxService {
@@ -27,6 +32,7 @@ impl FxService {
             repository,
             converter: Arc::new(RwLock::new(None)),
             event_sink: Arc::new(NoOpDomainEventSink),
+            rate_quality: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -38,6 +44,11 @@ impl FxService {
 
     /// Initialize the currency converter with all exchange rates, filling missing days
     fn initialize_converter(&self) -> Result<()> {
+        // Rates are about to change, so earlier records no longer describe them
+        if let Ok(mut records) = self.rate_quality.write() {
+            records.clear();
+        }
+
         let all_historical_rates = self.repository.get_historical_exchange_rates()?;
 
         if all_historical_rates.is_empty() {
@@ -144,6 +155,58 @@ impl FxService {
         Ok(rate.rate)
     }
 
+    /// Records a rate that was triangulated or taken from another day.
+    fn record_rate_quality(
+        &self,
+        from: &str,
+        to: &str,
+        date: NaiveDate,
+        via: &[String],
+        carried_days: i64,
+    ) {
+        let carried = carried_days > CARRY_TOLERANCE_DAYS;
+        if via.is_empty() && !carried {
+            return;
+        }
+        let Ok(mut records) = self.rate_quality.write() else {
+            return;
+        };
+        let record = records
+            .entry((from.to_string(), to.to_string()))
+            .or_insert_with(|| FxRateQuality {
+                from_currency: from.to_string(),
+                to_currency: to.to_string(),
+                ..Default::default()
+            });
+        if !via.is_empty() {
+            record.triangulated_count += 1;
+            for currency in via {
+                if !record.via.contains(currency) {
+                    record.via.push(currency.clone());
+                }
+            }
+        }
+        if carried {
+            record.carried_count += 1;
+            record.max_carried_days = record.max_carried_days.max(carried_days);
+        }
+        record.last_date = record.last_date.max(Some(date));
+    }
+
+    /// Creates the market FX asset for a pair that has no rates yet.
+    async fn create_market_pair_if_missing(&self, from: &str, to: &str) -> Result<bool> {
+        if self.load_latest_exchange_rate(from, to).is_ok() {
+            return Ok(false);
+        }
+        let asset_id = self
+            .repository
+            .create_fx_asset(from, to, DataSource::Yahoo.as_str())
+            .await?;
+        self.event_sink
+            .emit(DomainEvent::assets_created(vec![asset_id]));
+        Ok(true)
+    }
+
     fn get_rate_for_date_between_normalized(
         &self,
         from: &str,
@@ -156,14 +219,16 @@ impl FxService {
 
         if let Ok(converter_lock) = self.converter.read() {
             if let Some(converter) = &*converter_lock {
-                if let Ok(rate) = converter.get_rate_nearest(from, to, date) {
-                    return Ok(rate);
+                if let Ok(resolved) = converter.resolve_rate(from, to, date) {
+                    self.record_rate_quality(from, to, date, &resolved.via, resolved.carried_days);
+                    return Ok(resolved.rate);
                 }
             }
         }
 
         let latest_rate = self.load_latest_exchange_rate(from, to)?;
         let fallback_date = latest_rate.timestamp.date_naive();
+        self.record_rate_quality(from, to, date, &[], (date - fallback_date).num_days().abs());
 
         log::warn!(
             "No exchange rate found for {}/{} on {}. Using fallback rate from {}",
@@ -371,18 +436,21 @@ impl FxServiceTrait for FxService {
             return Ok(());
         }
 
-        let existing_rate = self
-            .load_latest_exchange_rate(normalized_from, normalized_to)
-            .ok();
+        let created = self
+            .create_market_pair_if_missing(normalized_from, normalized_to)
+            .await?;
 
-        if existing_rate.is_none() {
-            let asset_id = self
-                .repository
-                .create_fx_asset(normalized_from, normalized_to, DataSource::Yahoo.as_str())
+        // Cross pairs between non-pivot currencies (e.g. CHF/SEK) are often not
+        // quoted by providers. Make sure both legs to the first pivot exist so the
+        // converter can triangulate while the direct pair has no rates.
+        let is_cross = !FX_PIVOT_CURRENCIES.contains(&normalized_from)
+            && !FX_PIVOT_CURRENCIES.contains(&normalized_to);
+        if created && is_cross {
+            let pivot = FX_PIVOT_CURRENCIES[0];
+            self.create_market_pair_if_missing(normalized_from, pivot)
+                .await?;
+            self.create_market_pair_if_missing(normalized_to, pivot)
                 .await?;
-
-            self.event_sink
-                .emit(DomainEvent::assets_created(vec![asset_id]));
         }
 
         Ok(())
@@ -423,6 +491,16 @@ impl FxServiceTrait for FxService {
         Ok(())
     }
 
+    fn get_rate_quality(&self) -> Vec<FxRateQuality> {
+        let mut records: Vec<FxRateQuality> = self
+            .rate_quality
+            .read()
+            .map(|records| records.values().cloned().collect())
+            .unwrap_or_default();
+        records.sort_by_key(|r| r.pair_id());
+        records
+    }
+
     async fn ensure_fx_pairs(&self, pairs: Vec<(String, String)>) -> Result<()> {
         let unique_pairs: HashSet<(String, String)> = pairs.into_iter().collect();
 
@@ -446,6 +524,7 @@ mod tests {
     #[derive(Default)]
     struct MockFxRepository {
         created_pairs: Mutex<Vec<(String, String, String)>>,
+        historical_rates: Vec<ExchangeRate>,
     }
 
     #[async_trait]
@@ -455,7 +534,7 @@ mod tests {
         }
 
         fn get_historical_exchange_rates(&self) -> Result<Vec<ExchangeRate>> {
-            Ok(vec![])
+            Ok(self.historical_rates.clone())
         }
 
         fn get_latest_exchange_rate(&self, _from: &str, _to: &str) -> Result<Option<ExchangeRate>> {
@@ -556,4 +635,77 @@ mod tests {
         assert_eq!(created[0].0, "USD");
         assert_eq!(created[0].1, "CAD");
     }
+
+    #[tokio::test]
+    async fn register_cross_pair_also_registers_pivot_legs() {
+        let repo = Arc::new(MockFxRepository::default());
+        let service = FxService::new(repo.clone());
+
+        service.register_currency_pair("CHF", "SEK").await.unwrap();
+
+        let created: Vec<(String, String)> = repo
+            .created_pairs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(from, to, _)| (from.clone(), to.clone()))
+            .collect();
+        assert_eq!(
+            created,
+            vec![
+                ("CHF".to_string(), "SEK".to_string()),
+                ("CHF".to_string(), "USD".to_string()),
+                ("SEK".to_string(), "USD".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn triangulated_and_carried_rates_are_recorded() {
+        let rate = |from: &str, to: &str, rate: Decimal, day: u32| ExchangeRate {
+            id: format!("{}-{}", from, to),
+            from_currency: from.to_string(),
+            to_currency: to.to_string(),
+            rate,
+            source: DataSource::Manual,
+            timestamp: NaiveDate::from_ymd_opt(2024, 3, day)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_utc(),
+        };
+        let repo = Arc::new(MockFxRepository {
+            historical_rates: vec![
+                rate("CHF", "USD", Decimal::new(11, 1), 1),
+                rate("USD", "SEK", Decimal::from(10), 1),
+            ],
+            ..Default::default()
+        });
+        let service = FxService::new(repo);
+        service.initialize().unwrap();
+
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        assert_eq!(
+            service
+                .get_exchange_rate_for_date("CHF", "SEK", day(1))
+                .unwrap(),
+            Decimal::from(11)
+        );
+        service
+            .get_exchange_rate_for_date("CHF", "SEK", day(10))
+            .unwrap();
+        // Within the weekend tolerance, a direct pair is not recorded
+        service
+            .get_exchange_rate_for_date("CHF", "USD", day(3))
+            .unwrap();
+
+        let quality = service.get_rate_quality();
+        assert_eq!(quality.len(), 1);
+        assert_eq!(quality[0].pair_id(), "CHF:SEK");
+        assert_eq!(quality[0].via, vec!["USD".to_string()]);
+        assert_eq!(quality[0].triangulated_count, 2);
+        assert_eq!(quality[0].carried_count, 1);
+        assert_eq!(quality[0].max_carried_days, 9);
+        assert_eq!(quality[0].last_date, Some(day(10)));
+    }
 }
@@ -1,4 +1,4 @@
-use super::fx_model::{ExchangeRate, NewExchangeRate};
+use super::fx_model::{ExchangeRate, FxRateQuality, NewExchangeRate};
 use crate::errors::Result;
 use crate::quotes::Quote;
 use async_trait::async_trait;
@@ -83,6 +83,12 @@ pub trait FxServiceTrait: Send + Sync {
         to_currency: &str,
     ) -> Result<()>;
 
+    /// Pairs whose rates were triangulated or carried from another day since
+    /// rates were last loaded.
+    fn get_rate_quality(&self) -> Vec<FxRateQuality> {
+        Vec::new()
+    }
+
     /// Registers multiple FX pairs in batch.
     /// Pairs are (from_currency, to_currency).
     async fn ensure_fx_pairs(&self, pairs: Vec<(String, String)>) -> Result<()>;
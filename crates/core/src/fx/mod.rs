@@ -10,8 +10,8 @@ mod fx_traits;
 pub use currency::{
     denormalization_multiplier, get_normalization_rule, normalize_amount, normalize_currency_code,
 };
-pub use currency_converter::CurrencyConverter;
+pub use currency_converter::{CurrencyConverter, ResolvedRate, FX_PIVOT_CURRENCIES};
 pub use fx_errors::FxError;
-pub use fx_model::{ExchangeRate, NewExchangeRate};
+pub use fx_model::{ExchangeRate, FxRateQuality, NewExchangeRate};
 pub use fx_service::FxService;
 pub use fx_traits::{FxRepositoryTrait, FxServiceTrait};
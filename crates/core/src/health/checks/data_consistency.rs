@@ -1,12 +1,14 @@
 //! Data consistency health check.
 //!
-//! Detects orphan references, negative positions, and legacy data needing migration.
+//! Detects orphan references, negative positions, legacy data needing migration,
+//! and positions that differ from what the broker reports.
 
 use async_trait::async_trait;
 
 use crate::errors::Result;
 use crate::health::model::{FixAction, HealthCategory, HealthIssue, NavigateAction, Severity};
 use crate::health::traits::{HealthCheck, HealthContext};
+use crate::reconciliation::ReconciliationReport;
 
 /// Types of data consistency issues.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -19,6 +21,8 @@ pub enum ConsistencyIssueType {
     NegativePosition,
     /// Asset has legacy sector/country data not migrated to taxonomy
     LegacyClassification,
+    /// Broker-reported position or cash differs from the computed one
+    HoldingsMismatch,
 }
 
 /// Data about a consistency issue.
@@ -188,10 +192,77 @@ impl DataConsistencyCheck {
             );
         }
 
+        // Emit health issue for positions that differ from the broker
+        if let Some(mismatch_issues) = by_type.get(&ConsistencyIssueType::HoldingsMismatch) {
+            let count = mismatch_issues.len();
+            let record_ids: Vec<String> = mismatch_issues
+                .iter()
+                .map(|i| i.record_id.clone())
+                .collect();
+            let mut account_ids: Vec<String> = mismatch_issues
+                .iter()
+                .filter_map(|i| i.account_id.clone())
+                .collect();
+            account_ids.sort();
+            account_ids.dedup();
+            let details = mismatch_issues
+                .iter()
+                .map(|i| i.description.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let data_hash = compute_data_hash(&record_ids);
+
+            health_issues.push(
+                HealthIssue::builder()
+                    .id(format!("holdings_mismatch:{}", data_hash))
+                    .severity(Severity::Warning)
+                    .category(HealthCategory::DataConsistency)
+                    .title(if count == 1 {
+                        "Holding differs from broker".to_string()
+                    } else {
+                        format!("{} holdings differ from broker", count)
+                    })
+                    .message(
+                        "Positions computed from your transactions do not match what your broker reports. Draft transactions can be created to review and post.",
+                    )
+                    .affected_count(count as u32)
+                    .fix_action(FixAction::create_reconciliation_drafts(account_ids))
+                    .details(details)
+                    .data_hash(data_hash)
+                    .build(),
+            );
+        }
+
         health_issues
     }
 }
 
+/// Converts reconciliation discrepancies into consistency issues.
+pub fn gather_holdings_mismatches(reports: &[ReconciliationReport]) -> Vec<ConsistencyIssueInfo> {
+    reports
+        .iter()
+        .flat_map(|report| {
+            report
+                .discrepancies
+                .iter()
+                .map(move |d| ConsistencyIssueInfo {
+                    issue_type: ConsistencyIssueType::HoldingsMismatch,
+                    // The difference is part of the identity so a dismissal
+                    // resurfaces when the gap changes
+                    record_id: format!(
+                        "{}:{}:{}",
+                        report.account_id,
+                        d.symbol,
+                        d.difference.normalize()
+                    ),
+                    description: d.explanation.clone(),
+                    account_id: Some(report.account_id.clone()),
+                    asset_id: d.asset_id.clone(),
+                })
+        })
+        .collect()
+}
+
 impl Default for DataConsistencyCheck {
     fn default() -> Self {
         Self::new()
@@ -324,6 +395,37 @@ mod tests {
         assert_eq!(issues.len(), 2);
     }
 
+    #[test]
+    fn test_holdings_mismatch_offers_drafts() {
+        let check = DataConsistencyCheck::new();
+        let ctx = HealthContext::new(HealthConfig::default(), "USD", 100_000.0);
+
+        let issues_data = vec![
+            ConsistencyIssueInfo {
+                issue_type: ConsistencyIssueType::HoldingsMismatch,
+                record_id: "acc_1:AAPL:30".to_string(),
+                description: "AAPL holds 40 shares at the broker".to_string(),
+                account_id: Some("acc_1".to_string()),
+                asset_id: Some("SEC:AAPL:XNAS".to_string()),
+            },
+            ConsistencyIssueInfo {
+                issue_type: ConsistencyIssueType::HoldingsMismatch,
+                record_id: "acc_1:USD:-60".to_string(),
+                description: "The broker reports 60 USD less cash".to_string(),
+                account_id: Some("acc_1".to_string()),
+                asset_id: None,
+            },
+        ];
+
+        let issues = check.analyze(&issues_data, &ctx);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].affected_count, 2);
+        let fix = issues[0].fix_action.as_ref().unwrap();
+        assert_eq!(fix.id, "create_reconciliation_drafts");
+        assert_eq!(fix.payload, serde_json::json!(["acc_1"]));
+    }
+
     #[test]
     fn test_no_issues() {
         let check = DataConsistencyCheck::new();
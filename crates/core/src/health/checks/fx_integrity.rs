@@ -1,12 +1,14 @@
 //! FX integrity health check.
 //!
-//! Detects missing or stale foreign exchange rates.
+//! Detects missing or stale foreign exchange rates, and rates that had to be
+//! triangulated through another currency or carried over a gap.
 
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 
 use crate::errors::Result;
-use crate::health::model::{FixAction, HealthCategory, HealthIssue, Severity};
+use crate::fx::FxRateQuality;
+use crate::health::model::{FixAction, HealthCategory, HealthIssue, NavigateAction, Severity};
 use crate::health::traits::{HealthCheck, HealthContext};
 
 /// Data about a currency pair needed for FX checks.
@@ -24,6 +26,9 @@ pub struct FxPairInfo {
     pub latest_quote_time: Option<DateTime<Utc>>,
 }
 
+/// Gap (in days) above which a carried rate is reported as an Error.
+const FX_CARRIED_ERROR_DAYS: i64 = 30;
+
 /// Health check that detects missing or stale FX rates.
 pub struct FxIntegrityCheck;
 
@@ -198,6 +203,98 @@ impl FxIntegrityCheck {
 
         issues
     }
+
+    /// Analyzes how rates were resolved since they were last loaded.
+    ///
+    /// Triangulated rates are reported as Info: they are usually accurate, but
+    /// depend on two pairs. Rates carried over a gap are a Warning, escalated to
+    /// Error when a gap exceeds `FX_CARRIED_ERROR_DAYS`.
+    pub fn analyze_rate_quality(
+        &self,
+        quality: &[FxRateQuality],
+        _ctx: &HealthContext,
+    ) -> Vec<HealthIssue> {
+        let mut issues = Vec::new();
+
+        let triangulated: Vec<&FxRateQuality> = quality
+            .iter()
+            .filter(|q| q.triangulated_count > 0)
+            .collect();
+        if !triangulated.is_empty() {
+            let pair_ids: Vec<String> = triangulated.iter().map(|q| q.pair_id()).collect();
+            let count = pair_ids.len();
+            let title = if count == 1 {
+                format!(
+                    "Exchange rate for {} derived through {}",
+                    pair_ids[0],
+                    triangulated[0].via.join(", ")
+                )
+            } else {
+                format!(
+                    "Exchange rates for {} pairs derived through another currency",
+                    count
+                )
+            };
+            let data_hash = compute_data_hash(&pair_ids, Severity::Info, 0.0);
+
+            issues.push(
+                HealthIssue::builder()
+                    .id(format!("fx_triangulated:{}", data_hash))
+                    .severity(Severity::Info)
+                    .category(HealthCategory::FxIntegrity)
+                    .title(title)
+                    .message(
+                        "No direct rate is available for some currency pairs, so they are converted through a common currency like USD.",
+                    )
+                    .details(pair_ids.join(", "))
+                    .affected_count(count as u32)
+                    .navigate_action(NavigateAction::to_market_data())
+                    .data_hash(data_hash)
+                    .build(),
+            );
+        }
+
+        let carried: Vec<&FxRateQuality> = quality.iter().filter(|q| q.carried_count > 0).collect();
+        if !carried.is_empty() {
+            let pair_ids: Vec<String> = carried.iter().map(|q| q.pair_id()).collect();
+            let max_days = carried
+                .iter()
+                .map(|q| q.max_carried_days)
+                .max()
+                .unwrap_or(0);
+            let severity = if max_days > FX_CARRIED_ERROR_DAYS {
+                Severity::Error
+            } else {
+                Severity::Warning
+            };
+            let count = pair_ids.len();
+            let title = if count == 1 {
+                format!("Gaps in exchange rate history for {}", pair_ids[0])
+            } else {
+                format!("Gaps in exchange rate history for {} pairs", count)
+            };
+            let data_hash = compute_data_hash(&pair_ids, severity, 0.0);
+
+            issues.push(
+                HealthIssue::builder()
+                    .id(format!("fx_carried:{}", data_hash))
+                    .severity(severity)
+                    .category(HealthCategory::FxIntegrity)
+                    .title(title)
+                    .message(format!(
+                        "Some historical conversions used the nearest available rate, up to {} days away. Fetching rate history fills these gaps.",
+                        max_days
+                    ))
+                    .details(pair_ids.join(", "))
+                    .affected_count(count as u32)
+                    .fix_action(FixAction::fetch_fx(pair_ids))
+                    .data_hash(data_hash)
+                    .build(),
+            );
+        }
+
+        issues
+    }
 }
 
 impl Default for FxIntegrityCheck {
@@ -299,4 +396,49 @@ mod tests {
         let issues = check.analyze(&pairs, &ctx);
         assert!(issues.is_empty());
     }
+
+    fn quality(pair: &str, triangulated: u64, carried_days: i64) -> FxRateQuality {
+        let (from, to) = pair.split_once(':').unwrap();
+        FxRateQuality {
+            from_currency: from.to_string(),
+            to_currency: to.to_string(),
+            triangulated_count: triangulated,
+            via: if triangulated > 0 {
+                vec!["USD".to_string()]
+            } else {
+                Vec::new()
+            },
+            carried_count: u64::from(carried_days > 0),
+            max_carried_days: carried_days,
+            last_date: None,
+        }
+    }
+
+    #[test]
+    fn test_triangulated_pair_is_info() {
+        let check = FxIntegrityCheck::new();
+        let ctx = HealthContext::new(HealthConfig::default(), "CHF", 100_000.0);
+
+        let issues = check.analyze_rate_quality(&[quality("SEK:CHF", 10, 0)], &ctx);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Info);
+        assert!(issues[0].title.contains("SEK:CHF"));
+        assert!(issues[0].title.contains("USD"));
+    }
+
+    #[test]
+    fn test_long_carried_gap_is_error() {
+        let check = FxIntegrityCheck::new();
+        let ctx = HealthContext::new(HealthConfig::default(), "CHF", 100_000.0);
+
+        let issues = check
+            .analyze_rate_quality(&[quality("SEK:CHF", 0, 5), quality("NOK:CHF", 0, 45)], &ctx);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[0].affected_count, 2);
+        assert!(issues[0].fix_action.is_some());
+
+        let issues = check.analyze_rate_quality(&[quality("SEK:CHF", 0, 5)], &ctx);
+        assert_eq!(issues[0].severity, Severity::Warning);
+    }
 }
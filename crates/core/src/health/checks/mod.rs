@@ -3,6 +3,7 @@
 //! This module contains the individual health check implementations:
 //! - Price staleness check
 //! - Quote sync error check
+//! - Quote quality check (gaps and spikes)
 //! - FX integrity check
 //! - Classification completeness check
 //! - Data consistency check
@@ -13,6 +14,7 @@ pub mod classification;
 pub mod data_consistency;
 pub mod fx_integrity;
 pub mod price_staleness;
+pub mod quote_quality;
 pub mod quote_sync;
 
 // Re-export check implementations
@@ -21,6 +23,7 @@ pub use classification::ClassificationCheck;
 pub use data_consistency::DataConsistencyCheck;
 pub use fx_integrity::FxIntegrityCheck;
 pub use price_staleness::PriceStalenessCheck;
+pub use quote_quality::QuoteQualityCheck;
 pub use quote_sync::QuoteSyncCheck;
 
 // Re-export data types used by checks
@@ -29,8 +32,11 @@ pub use classification::{LegacyMigrationInfo, UnclassifiedAssetInfo};
 pub use data_consistency::{ConsistencyIssueInfo, ConsistencyIssueType};
 pub use fx_integrity::FxPairInfo;
 pub use price_staleness::AssetHoldingInfo;
+pub use quote_quality::QuoteQualityInfo;
 pub use quote_sync::QuoteSyncErrorInfo;
 
 // Re-export data gathering functions
 pub use classification::gather_legacy_migration_status;
+pub use data_consistency::gather_holdings_mismatches;
+pub use quote_quality::gather_quote_quality_issues;
 pub use quote_sync::gather_quote_sync_errors;
@@ -1,17 +1,18 @@
 //! Price staleness health check.
 //!
 //! Detects assets with stale or missing market prices.
-//! Uses trading days (weekdays) for staleness calculation to avoid
-//! false positives on weekends when markets are closed.
+//! Uses trading days from each asset's exchange calendar for staleness
+//! calculation to avoid false positives on weekends and exchange holidays.
 
 use async_trait::async_trait;
-use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use chrono::{DateTime, NaiveDate, Utc};
 use std::collections::HashMap;
 
 use crate::errors::Result;
 use crate::health::model::{AffectedItem, FixAction, HealthCategory, HealthIssue, Severity};
 use crate::health::traits::{HealthCheck, HealthContext};
 use crate::utils::time_utils;
+use wealthfolio_market_data::resolver::TradingCalendar;
 
 /// Data about an asset holding for staleness checks.
 #[derive(Debug, Clone)]
@@ -45,7 +46,7 @@ impl PriceStalenessCheck {
     /// Analyzes holdings for price staleness issues.
     ///
     /// This is the core logic, exposed for testing.
-    /// Uses trading days (weekdays) for staleness calculation to avoid
+    /// Uses trading days (per exchange calendar) for staleness calculation to avoid
     /// false positives on weekends when markets are closed.
     pub fn analyze(
         &self,
@@ -82,11 +83,13 @@ impl PriceStalenessCheck {
                     // Only check staleness for assets with positive market value
                     // (assets with 0 quantity are not actively held)
                     if holding.market_value > 0.0 {
-                        let effective_today = time_utils::market_effective_date(
-                            ctx.now,
-                            holding.exchange_mic.as_deref(),
+                        let mic = holding.exchange_mic.as_deref();
+                        let effective_today = time_utils::market_effective_date(ctx.now, mic);
+                        let days_stale = trading_days_since(
+                            *quote_time,
+                            effective_today,
+                            time_utils::exchange_calendar(mic),
                         );
-                        let days_stale = trading_days_since(*quote_time, effective_today);
 
                         if days_stale >= critical_trading_days {
                             error_assets.push(holding);
@@ -240,46 +243,36 @@ impl Default for PriceStalenessCheck {
     }
 }
 
-/// Counts the number of trading days (weekdays) between two dates.
+/// Counts the number of trading days between two dates.
 ///
-/// This function counts weekdays from the day after `from_date` up to and including `to_date`.
-/// Weekends (Saturday and Sunday) are excluded from the count.
+/// This function counts trading days from the day after `from_date` up to and including
+/// `to_date`. Weekends and holidays of the given calendar are excluded from the count.
 ///
 /// # Arguments
 /// * `from_date` - The starting date (exclusive)
 /// * `to_date` - The ending date (inclusive)
+/// * `calendar` - The exchange trading calendar
 ///
 /// # Returns
 /// The number of trading days elapsed. Returns 0 if `to_date` is on or before `from_date`.
-fn trading_days_between(from_date: NaiveDate, to_date: NaiveDate) -> i64 {
-    if to_date <= from_date {
-        return 0;
-    }
-
-    let mut trading_days = 0;
-    let mut current = from_date;
-
-    // Iterate from the day after from_date to to_date (inclusive)
-    while let Some(next) = current.succ_opt() {
-        current = next;
-        if current > to_date {
-            break;
-        }
-        let weekday = current.weekday();
-        if weekday != Weekday::Sat && weekday != Weekday::Sun {
-            trading_days += 1;
-        }
-    }
-
-    trading_days
+fn trading_days_between(
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    calendar: &TradingCalendar,
+) -> i64 {
+    calendar.trading_days_between(from_date, to_date)
 }
 
 /// Counts trading days elapsed since a quote timestamp.
 ///
-/// Extracts dates from the timestamps and counts weekdays between them.
-fn trading_days_since(last_quote: DateTime<Utc>, today: NaiveDate) -> i64 {
+/// Extracts dates from the timestamps and counts trading days between them.
+fn trading_days_since(
+    last_quote: DateTime<Utc>,
+    today: NaiveDate,
+    calendar: &TradingCalendar,
+) -> i64 {
     let last_date = last_quote.date_naive();
-    trading_days_between(last_date, today)
+    trading_days_between(last_date, today, calendar)
 }
 
 #[async_trait]
@@ -372,7 +365,10 @@ mod tests {
     #[test]
     fn test_trading_days_between_same_day() {
         let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(); // Monday
-        assert_eq!(trading_days_between(date, date), 0);
+        assert_eq!(
+            trading_days_between(date, date, TradingCalendar::weekdays()),
+            0
+        );
     }
 
     #[test]
@@ -380,7 +376,10 @@ mod tests {
         // Monday to Wednesday = 2 trading days (Tue, Wed)
         let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
         let wednesday = NaiveDate::from_ymd_opt(2024, 1, 17).unwrap();
-        assert_eq!(trading_days_between(monday, wednesday), 2);
+        assert_eq!(
+            trading_days_between(monday, wednesday, TradingCalendar::weekdays()),
+            2
+        );
     }
 
     #[test]
@@ -388,7 +387,10 @@ mod tests {
         // Friday to Monday = 1 trading day (Monday only, Sat/Sun excluded)
         let friday = NaiveDate::from_ymd_opt(2024, 1, 19).unwrap();
         let monday = NaiveDate::from_ymd_opt(2024, 1, 22).unwrap();
-        assert_eq!(trading_days_between(friday, monday), 1);
+        assert_eq!(
+            trading_days_between(friday, monday, TradingCalendar::weekdays()),
+            1
+        );
     }
 
     #[test]
@@ -396,7 +398,10 @@ mod tests {
         // Friday to Saturday = 0 trading days (Saturday is weekend)
         let friday = NaiveDate::from_ymd_opt(2024, 1, 19).unwrap();
         let saturday = NaiveDate::from_ymd_opt(2024, 1, 20).unwrap();
-        assert_eq!(trading_days_between(friday, saturday), 0);
+        assert_eq!(
+            trading_days_between(friday, saturday, TradingCalendar::weekdays()),
+            0
+        );
     }
 
     #[test]
@@ -404,7 +409,10 @@ mod tests {
         // Friday to Sunday = 0 trading days (both Sat/Sun are weekend)
         let friday = NaiveDate::from_ymd_opt(2024, 1, 19).unwrap();
         let sunday = NaiveDate::from_ymd_opt(2024, 1, 21).unwrap();
-        assert_eq!(trading_days_between(friday, sunday), 0);
+        assert_eq!(
+            trading_days_between(friday, sunday, TradingCalendar::weekdays()),
+            0
+        );
     }
 
     #[test]
@@ -412,7 +420,21 @@ mod tests {
         // Monday to next Monday = 5 trading days
         let monday1 = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
         let monday2 = NaiveDate::from_ymd_opt(2024, 1, 22).unwrap();
-        assert_eq!(trading_days_between(monday1, monday2), 5);
+        assert_eq!(
+            trading_days_between(monday1, monday2, TradingCalendar::weekdays()),
+            5
+        );
+    }
+
+    #[test]
+    fn test_trading_days_skip_exchange_holidays() {
+        // Christmas Eve to Boxing Day 2024: NYSE is closed on the 25th, LSE on 25th and 26th
+        let christmas_eve = NaiveDate::from_ymd_opt(2024, 12, 24).unwrap();
+        let boxing_day = NaiveDate::from_ymd_opt(2024, 12, 26).unwrap();
+        let nyse = time_utils::exchange_calendar(Some("XNYS"));
+        let lse = time_utils::exchange_calendar(Some("XLON"));
+        assert_eq!(trading_days_between(christmas_eve, boxing_day, nyse), 1);
+        assert_eq!(trading_days_between(christmas_eve, boxing_day, lse), 0);
     }
 
     #[test]
@@ -503,6 +525,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_exchange_holiday_no_false_positive() {
+        let check = PriceStalenessCheck::new();
+
+        // Christmas Day 2024 evening in New York: NYSE was closed all day
+        let christmas = Utc.with_ymd_and_hms(2024, 12, 25, 23, 0, 0).unwrap();
+        let ctx =
+            HealthContext::with_timestamp(HealthConfig::default(), "USD", 100_000.0, christmas);
+
+        let holdings = vec![AssetHoldingInfo {
+            asset_id: "SEC:AAPL:XNAS".to_string(),
+            symbol: "AAPL".to_string(),
+            name: Some("Apple Inc.".to_string()),
+            exchange_mic: Some("XNAS".to_string()),
+            market_value: 10_000.0,
+            uses_market_pricing: true,
+        }];
+
+        // Quote from Christmas Eve close
+        let mut quote_times = HashMap::new();
+        let christmas_eve = Utc.with_ymd_and_hms(2024, 12, 24, 18, 0, 0).unwrap();
+        quote_times.insert("SEC:AAPL:XNAS".to_string(), christmas_eve);
+
+        let issues = check.analyze(&holdings, &quote_times, &ctx);
+        assert!(
+            issues.is_empty(),
+            "Christmas Eve quote should not be stale on Christmas Day"
+        );
+    }
+
     #[test]
     fn test_monday_friday_quote_not_stale() {
         let check = PriceStalenessCheck::new();
@@ -0,0 +1,343 @@
+//! Quote quality health check.
+//!
+//! Detects gaps and single-day price spikes in recent quote history for
+//! held assets. A bad print from one provider (e.g. a stray tick or a
+//! GBp/GBP unit mix-up) otherwise shows up as a phantom drop in valuations.
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate};
+use std::collections::{HashMap, HashSet};
+
+use super::price_staleness::AssetHoldingInfo;
+use crate::errors::Result;
+use crate::health::model::{AffectedItem, FixAction, HealthCategory, HealthIssue, Severity};
+use crate::health::traits::{HealthCheck, HealthContext};
+use crate::quotes::quality::{analyze_quotes, QuoteQualityConfig};
+use crate::quotes::{Quote, QuoteServiceTrait};
+use crate::utils::time_utils;
+
+/// Data about an asset with quote quality findings.
+#[derive(Debug, Clone)]
+pub struct QuoteQualityInfo {
+    /// Asset ID
+    pub asset_id: String,
+    /// Symbol for display
+    pub symbol: String,
+    /// Market value in base currency
+    pub market_value: f64,
+    /// Trading days without a quote
+    pub missing_days: i64,
+    /// Days with a suspicious single-day spike
+    pub spike_days: Vec<NaiveDate>,
+}
+
+/// Gathers quote quality findings for held, market-priced assets.
+///
+/// Loads the last `config.lookback_days` of quotes for the given holdings and
+/// runs gap and spike detection on each asset. Assets with clean data are omitted.
+///
+/// # Arguments
+/// * `quote_service` - The quote service for loading stored quotes
+/// * `holdings` - Current holdings (only market-priced ones are inspected)
+/// * `config` - Detection thresholds
+/// * `today` - End of the inspected window
+pub fn gather_quote_quality_issues(
+    quote_service: &dyn QuoteServiceTrait,
+    holdings: &[AssetHoldingInfo],
+    config: &QuoteQualityConfig,
+    today: NaiveDate,
+) -> Vec<QuoteQualityInfo> {
+    let held: Vec<&AssetHoldingInfo> = holdings
+        .iter()
+        .filter(|h| h.uses_market_pricing && h.market_value > 0.0)
+        .collect();
+    if held.is_empty() {
+        return Vec::new();
+    }
+
+    let start = today - Duration::days(config.lookback_days);
+    let asset_ids: HashSet<String> = held.iter().map(|h| h.asset_id.clone()).collect();
+    let quotes = match quote_service.get_quotes_in_range(&asset_ids, start, today) {
+        Ok(quotes) => quotes,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut by_asset: HashMap<String, Vec<Quote>> = HashMap::new();
+    for quote in quotes {
+        by_asset
+            .entry(quote.asset_id.clone())
+            .or_default()
+            .push(quote);
+    }
+
+    held.into_iter()
+        .filter_map(|holding| {
+            let quotes = by_asset.get(&holding.asset_id)?;
+            let calendar = time_utils::exchange_calendar(holding.exchange_mic.as_deref());
+            let report = analyze_quotes(&holding.asset_id, quotes, start, today, config, calendar);
+            if report.is_clean() {
+                return None;
+            }
+            Some(QuoteQualityInfo {
+                asset_id: holding.asset_id.clone(),
+                symbol: holding.symbol.clone(),
+                market_value: holding.market_value,
+                missing_days: report.unfilled_gap_days(),
+                spike_days: report.unresolved_spikes().iter().map(|s| s.day).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Health check that detects gaps and outliers in stored quotes.
+pub struct QuoteQualityCheck;
+
+impl QuoteQualityCheck {
+    /// Creates a new quote quality check.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Analyzes quote quality findings.
+    ///
+    /// This is the core logic, exposed for testing and direct use.
+    /// Spikes are reported as a warning (they distort valuations directly);
+    /// gaps are informational unless they affect a large share of the portfolio.
+    pub fn analyze(&self, findings: &[QuoteQualityInfo], ctx: &HealthContext) -> Vec<HealthIssue> {
+        let mut issues = Vec::new();
+
+        let spiked: Vec<&QuoteQualityInfo> = findings
+            .iter()
+            .filter(|f| !f.spike_days.is_empty())
+            .collect();
+        let gapped: Vec<&QuoteQualityInfo> =
+            findings.iter().filter(|f| f.missing_days > 0).collect();
+
+        if !spiked.is_empty() {
+            let mv_pct = mv_pct(&spiked, ctx);
+            let severity = if mv_pct > ctx.config.mv_escalation_threshold {
+                Severity::Error
+            } else {
+                Severity::Warning
+            };
+
+            let count = spiked.len();
+            let title = if count == 1 {
+                format!("Suspicious price for {}", spiked[0].symbol)
+            } else {
+                format!("Suspicious prices for {} holdings", count)
+            };
+
+            let asset_ids: Vec<String> = spiked.iter().map(|f| f.asset_id.clone()).collect();
+            let data_hash = compute_data_hash(&spiked, severity);
+            let affected_items: Vec<AffectedItem> = spiked
+                .iter()
+                .map(|f| AffectedItem::asset_market_data(&f.asset_id, &f.symbol))
+                .collect();
+            let details = build_details(&spiked, |f| {
+                let days: Vec<String> = f.spike_days.iter().map(|d| d.to_string()).collect();
+                format!("spike on {}", days.join(", "))
+            });
+
+            issues.push(
+                HealthIssue::builder()
+                    .id(format!("quote_quality:spike:{}", data_hash))
+                    .severity(severity)
+                    .category(HealthCategory::PriceStaleness)
+                    .title(title)
+                    .message(
+                        "Some prices jump and revert within a day, which usually means a bad print from the data provider. Review the quotes for these holdings.",
+                    )
+                    .affected_count(count as u32)
+                    .affected_mv_pct(mv_pct)
+                    .affected_items(affected_items)
+                    .fix_action(FixAction::sync_prices(asset_ids))
+                    .details(details)
+                    .data_hash(data_hash)
+                    .build(),
+            );
+        }
+
+        if !gapped.is_empty() {
+            let mv_pct = mv_pct(&gapped, ctx);
+            let severity = if mv_pct > ctx.config.mv_escalation_threshold {
+                Severity::Warning
+            } else {
+                Severity::Info
+            };
+
+            let count = gapped.len();
+            let title = if count == 1 {
+                format!("Missing prices for {}", gapped[0].symbol)
+            } else {
+                format!("Missing prices for {} holdings", count)
+            };
+
+            let asset_ids: Vec<String> = gapped.iter().map(|f| f.asset_id.clone()).collect();
+            let data_hash = compute_data_hash(&gapped, severity);
+            let affected_items: Vec<AffectedItem> = gapped
+                .iter()
+                .map(|f| AffectedItem::asset_market_data(&f.asset_id, &f.symbol))
+                .collect();
+            let details = build_details(&gapped, |f| {
+                format!("{} trading days without a price", f.missing_days)
+            });
+
+            issues.push(
+                HealthIssue::builder()
+                    .id(format!("quote_quality:gap:{}", data_hash))
+                    .severity(severity)
+                    .category(HealthCategory::PriceStaleness)
+                    .title(title)
+                    .message(
+                        "The data provider skipped some trading days. Values on those days carry forward the previous price.",
+                    )
+                    .affected_count(count as u32)
+                    .affected_mv_pct(mv_pct)
+                    .affected_items(affected_items)
+                    .fix_action(FixAction::sync_prices(asset_ids))
+                    .details(details)
+                    .data_hash(data_hash)
+                    .build(),
+            );
+        }
+
+        issues
+    }
+}
+
+impl Default for QuoteQualityCheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl HealthCheck for QuoteQualityCheck {
+    fn id(&self) -> &'static str {
+        "quote_quality"
+    }
+
+    fn category(&self) -> HealthCategory {
+        HealthCategory::PriceStaleness
+    }
+
+    async fn run(&self, _ctx: &HealthContext) -> Result<Vec<HealthIssue>> {
+        // The service will call analyze() directly with the data it gathers
+        Ok(Vec::new())
+    }
+}
+
+/// Share of portfolio value held in the given assets.
+fn mv_pct(findings: &[&QuoteQualityInfo], ctx: &HealthContext) -> f64 {
+    if ctx.total_portfolio_value > 0.0 {
+        findings.iter().map(|f| f.market_value).sum::<f64>() / ctx.total_portfolio_value
+    } else {
+        0.0
+    }
+}
+
+/// Builds a details string listing affected assets.
+fn build_details(
+    findings: &[&QuoteQualityInfo],
+    describe: impl Fn(&QuoteQualityInfo) -> String,
+) -> String {
+    let mut lines = Vec::new();
+    for (i, finding) in findings.iter().take(5).enumerate() {
+        lines.push(format!(
+            "{}. {} - {}",
+            i + 1,
+            finding.symbol,
+            describe(finding)
+        ));
+    }
+    if findings.len() > 5 {
+        lines.push(format!("... and {} more", findings.len() - 5));
+    }
+    lines.join("\n")
+}
+
+/// Computes a data hash for issue identity and change detection.
+fn compute_data_hash(findings: &[&QuoteQualityInfo], severity: Severity) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    let mut sorted: Vec<&&QuoteQualityInfo> = findings.iter().collect();
+    sorted.sort_by(|a, b| a.asset_id.cmp(&b.asset_id));
+    for finding in sorted {
+        finding.asset_id.hash(&mut hasher);
+        finding.missing_days.hash(&mut hasher);
+        finding.spike_days.hash(&mut hasher);
+    }
+    severity.as_str().hash(&mut hasher);
+
+    format!("{:x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::model::HealthConfig;
+
+    fn finding(asset_id: &str, missing_days: i64, spikes: usize, mv: f64) -> QuoteQualityInfo {
+        QuoteQualityInfo {
+            asset_id: asset_id.to_string(),
+            symbol: asset_id.to_string(),
+            market_value: mv,
+            missing_days,
+            spike_days: (0..spikes)
+                .map(|i| NaiveDate::from_ymd_opt(2024, 1, 15 + i as u32).unwrap())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_no_findings_no_issues() {
+        let check = QuoteQualityCheck::new();
+        let ctx = HealthContext::new(HealthConfig::default(), "USD", 100_000.0);
+
+        assert!(check.analyze(&[], &ctx).is_empty());
+    }
+
+    #[test]
+    fn test_spike_reported_as_warning() {
+        let check = QuoteQualityCheck::new();
+        let ctx = HealthContext::new(HealthConfig::default(), "USD", 100_000.0);
+
+        let issues = check.analyze(&[finding("VOD", 0, 1, 5_000.0)], &ctx);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert!(issues[0].id.starts_with("quote_quality:spike:"));
+    }
+
+    #[test]
+    fn test_spike_escalates_with_market_value() {
+        let check = QuoteQualityCheck::new();
+        let ctx = HealthContext::new(HealthConfig::default(), "USD", 100_000.0);
+
+        let issues = check.analyze(&[finding("VOD", 0, 1, 50_000.0)], &ctx);
+        assert_eq!(issues[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_gap_reported_as_info() {
+        let check = QuoteQualityCheck::new();
+        let ctx = HealthContext::new(HealthConfig::default(), "USD", 100_000.0);
+
+        let issues = check.analyze(&[finding("VOD", 3, 0, 5_000.0)], &ctx);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Info);
+        assert!(issues[0].id.starts_with("quote_quality:gap:"));
+    }
+
+    #[test]
+    fn test_data_hash_changes_with_findings() {
+        let a = finding("VOD", 3, 0, 5_000.0);
+        let b = finding("VOD", 4, 0, 5_000.0);
+        assert_ne!(
+            compute_data_hash(&[&a], Severity::Info),
+            compute_data_hash(&[&b], Severity::Info)
+        );
+    }
+}
@@ -72,4 +72,7 @@ pub use fixes::{
 };
 
 // Re-export data gathering functions from checks
-pub use checks::{gather_legacy_migration_status, gather_quote_sync_errors};
+pub use checks::{
+    gather_holdings_mismatches, gather_legacy_migration_status, gather_quote_quality_issues,
+    gather_quote_sync_errors,
+};
@@ -158,6 +158,16 @@ impl FixAction {
         }
     }
 
+    /// Creates a new fix action for creating the draft activities proposed by
+    /// holdings reconciliation.
+    pub fn create_reconciliation_drafts(account_ids: Vec<String>) -> Self {
+        Self {
+            id: "create_reconciliation_drafts".to_string(),
+            label: "Create Draft Transactions".to_string(),
+            payload: serde_json::json!(account_ids),
+        }
+    }
+
     /// Creates a new fix action for retrying sync on failed assets.
     pub fn retry_sync(asset_ids: Vec<String>) -> Self {
         Self {
@@ -13,14 +13,17 @@ use tokio::sync::RwLock;
 use crate::accounts::AccountServiceTrait;
 use crate::assets::AssetServiceTrait;
 use crate::errors::Result;
+use crate::fx::{FxRateQuality, FxServiceTrait};
 use crate::portfolio::holdings::HoldingsServiceTrait;
 use crate::quotes::QuoteServiceTrait;
+use crate::reconciliation::HoldingsReconciliationServiceTrait;
 use crate::taxonomies::TaxonomyServiceTrait;
 
 use super::checks::{
     AccountConfigurationCheck, AssetHoldingInfo, ClassificationCheck, ConsistencyIssueInfo,
     DataConsistencyCheck, FxIntegrityCheck, FxPairInfo, LegacyMigrationInfo, PriceStalenessCheck,
-    QuoteSyncCheck, QuoteSyncErrorInfo, UnclassifiedAssetInfo, UnconfiguredAccountInfo,
+    QuoteQualityCheck, QuoteQualityInfo, QuoteSyncCheck, QuoteSyncErrorInfo, UnclassifiedAssetInfo,
+    UnconfiguredAccountInfo,
 };
 use super::errors::HealthError;
 use super::model::{FixAction, HealthConfig, HealthIssue, HealthStatus, IssueDismissal};
@@ -46,6 +49,7 @@ pub struct HealthService {
     /// Individual check implementations
     price_check: PriceStalenessCheck,
     quote_sync_check: QuoteSyncCheck,
+    quote_quality_check: QuoteQualityCheck,
     fx_check: FxIntegrityCheck,
     classification_check: ClassificationCheck,
     consistency_check: DataConsistencyCheck,
@@ -61,6 +65,7 @@ impl HealthService {
             cached_status: RwLock::new(None),
             price_check: PriceStalenessCheck::new(),
             quote_sync_check: QuoteSyncCheck::new(),
+            quote_quality_check: QuoteQualityCheck::new(),
             fx_check: FxIntegrityCheck::new(),
             classification_check: ClassificationCheck::new(),
             consistency_check: DataConsistencyCheck::new(),
@@ -79,6 +84,7 @@ impl HealthService {
             cached_status: RwLock::new(None),
             price_check: PriceStalenessCheck::new(),
             quote_sync_check: QuoteSyncCheck::new(),
+            quote_quality_check: QuoteQualityCheck::new(),
             fx_check: FxIntegrityCheck::new(),
             classification_check: ClassificationCheck::new(),
             consistency_check: DataConsistencyCheck::new(),
@@ -98,7 +104,9 @@ impl HealthService {
         holdings: &[AssetHoldingInfo],
         latest_quote_times: &std::collections::HashMap<String, chrono::DateTime<chrono::Utc>>,
         quote_sync_errors: &[QuoteSyncErrorInfo],
+        quote_quality: &[QuoteQualityInfo],
         fx_pairs: &[FxPairInfo],
+        fx_rate_quality: &[FxRateQuality],
         unclassified_assets: &[UnclassifiedAssetInfo],
         consistency_issues: &[ConsistencyIssueInfo],
         legacy_migration_info: &Option<LegacyMigrationInfo>,
@@ -132,9 +140,19 @@ impl HealthService {
         debug!("Quote sync check found {} issues", sync_issues.len());
         all_issues.extend(sync_issues);
 
+        // Run quote quality check
+        debug!(
+            "Running quote quality check on {} assets with findings",
+            quote_quality.len()
+        );
+        let quality_issues = self.quote_quality_check.analyze(quote_quality, &ctx);
+        debug!("Quote quality check found {} issues", quality_issues.len());
+        all_issues.extend(quality_issues);
+
         // Run FX integrity check
         debug!("Running FX integrity check on {} pairs", fx_pairs.len());
-        let fx_issues = self.fx_check.analyze(fx_pairs, &ctx);
+        let mut fx_issues = self.fx_check.analyze(fx_pairs, &ctx);
+        fx_issues.extend(self.fx_check.analyze_rate_quality(fx_rate_quality, &ctx));
         debug!("FX integrity check found {} issues", fx_issues.len());
         all_issues.extend(fx_issues);
 
@@ -209,6 +227,7 @@ impl HealthService {
     /// Runs all health checks by gathering data from the provided services.
     ///
     /// This is the main entry point for health checks that handles all data gathering.
+    #[allow(clippy::too_many_arguments)]
     pub async fn run_full_checks(
         &self,
         base_currency: &str,
@@ -217,6 +236,8 @@ impl HealthService {
         quote_service: Arc<dyn QuoteServiceTrait>,
         asset_service: Arc<dyn AssetServiceTrait>,
         taxonomy_service: Arc<dyn TaxonomyServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        reconciliation_service: Arc<dyn HoldingsReconciliationServiceTrait>,
     ) -> Result<HealthStatus> {
         // Gather holdings data from all accounts
         let accounts = account_service.get_active_accounts()?;
@@ -293,11 +314,29 @@ impl HealthService {
             &latest_quote_times,
         );
 
-        // For now, we'll use empty data for FX, unclassified, and consistency checks
+        // Gather quote gaps and spikes for held assets
+        let quote_quality = super::gather_quote_quality_issues(
+            quote_service.as_ref(),
+            &all_holdings,
+            &quote_service.get_quality_config(),
+            chrono::Utc::now().date_naive(),
+        );
+
+        // Compare broker-reported holdings with positions computed from activities
+        let consistency_issues: Vec<ConsistencyIssueInfo> =
+            match reconciliation_service.reconcile_all().await {
+                Ok(reports) => super::gather_holdings_mismatches(&reports),
+                Err(e) => {
+                    warn!("Holdings reconciliation failed: {}", e);
+                    Vec::new()
+                }
+            };
+
+        // For now, we'll use empty data for FX and unclassified checks
         // These can be enhanced later with proper data gathering
         let fx_pairs: Vec<FxPairInfo> = Vec::new();
+        let fx_rate_quality = fx_service.get_rate_quality();
         let unclassified_assets: Vec<UnclassifiedAssetInfo> = Vec::new();
-        let consistency_issues: Vec<ConsistencyIssueInfo> = Vec::new();
 
         // Gather accounts without tracking mode set
         let unconfigured_accounts: Vec<UnconfiguredAccountInfo> = accounts
@@ -316,7 +355,9 @@ impl HealthService {
             &all_holdings,
             &latest_quote_times,
             &quote_sync_errors,
+            &quote_quality,
             &fx_pairs,
+            &fx_rate_quality,
             &unclassified_assets,
             &consistency_issues,
             &legacy_migration_info,
@@ -374,7 +415,9 @@ impl HealthServiceTrait for HealthService {
         holdings: &[AssetHoldingInfo],
         latest_quote_times: &std::collections::HashMap<String, chrono::DateTime<chrono::Utc>>,
         quote_sync_errors: &[QuoteSyncErrorInfo],
+        quote_quality: &[QuoteQualityInfo],
         fx_pairs: &[FxPairInfo],
+        fx_rate_quality: &[FxRateQuality],
         unclassified_assets: &[UnclassifiedAssetInfo],
         consistency_issues: &[ConsistencyIssueInfo],
         legacy_migration_info: &Option<LegacyMigrationInfo>,
@@ -388,7 +431,9 @@ impl HealthServiceTrait for HealthService {
             holdings,
             latest_quote_times,
             quote_sync_errors,
+            quote_quality,
             fx_pairs,
+            fx_rate_quality,
             unclassified_assets,
             consistency_issues,
             legacy_migration_info,
@@ -510,6 +555,7 @@ impl HealthServiceTrait for HealthService {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn run_full_checks(
         &self,
         base_currency: &str,
@@ -518,6 +564,8 @@ impl HealthServiceTrait for HealthService {
         quote_service: Arc<dyn QuoteServiceTrait>,
         asset_service: Arc<dyn AssetServiceTrait>,
         taxonomy_service: Arc<dyn TaxonomyServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        reconciliation_service: Arc<dyn HoldingsReconciliationServiceTrait>,
     ) -> Result<HealthStatus> {
         HealthService::run_full_checks(
             self,
@@ -527,6 +575,8 @@ impl HealthServiceTrait for HealthService {
             quote_service,
             asset_service,
             taxonomy_service,
+            fx_service,
+            reconciliation_service,
         )
         .await
     }
@@ -595,6 +645,8 @@ mod tests {
                 &[],
                 &[],
                 &[],
+                &[],
+                &[],
                 &None,
                 &[],
             )
@@ -670,6 +722,8 @@ mod tests {
                 &[],
                 &[],
                 &[],
+                &[],
+                &[],
                 &None,
                 &[],
             )
@@ -706,6 +760,8 @@ mod tests {
                 &[],
                 &[],
                 &[],
+                &[],
+                &[],
                 &None,
                 &[],
             )
@@ -732,6 +788,8 @@ mod tests {
                 &[],
                 &[],
                 &[],
+                &[],
+                &[],
                 &None,
                 &[],
             )
@@ -181,14 +181,16 @@ pub trait HealthDismissalStore: Send + Sync {
 // =============================================================================
 
 use super::checks::{
-    AssetHoldingInfo, ConsistencyIssueInfo, FxPairInfo, LegacyMigrationInfo, QuoteSyncErrorInfo,
-    UnclassifiedAssetInfo, UnconfiguredAccountInfo,
+    AssetHoldingInfo, ConsistencyIssueInfo, FxPairInfo, LegacyMigrationInfo, QuoteQualityInfo,
+    QuoteSyncErrorInfo, UnclassifiedAssetInfo, UnconfiguredAccountInfo,
 };
 use super::model::{FixAction, HealthStatus};
 use crate::accounts::AccountServiceTrait;
 use crate::assets::AssetServiceTrait;
+use crate::fx::{FxRateQuality, FxServiceTrait};
 use crate::portfolio::holdings::HoldingsServiceTrait;
 use crate::quotes::QuoteServiceTrait;
+use crate::reconciliation::HoldingsReconciliationServiceTrait;
 use crate::taxonomies::TaxonomyServiceTrait;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -222,7 +224,9 @@ pub trait HealthServiceTrait: Send + Sync {
     /// * `holdings` - Information about held assets
     /// * `latest_quote_times` - Latest quote timestamps by asset ID
     /// * `quote_sync_errors` - Assets with quote sync failures
+    /// * `quote_quality` - Assets with quote gaps or spikes
     /// * `fx_pairs` - FX pair information for currency checks
+    /// * `fx_rate_quality` - Triangulated or carried FX rates used in conversions
     /// * `unclassified_assets` - Assets missing classification
     /// * `consistency_issues` - Pre-detected data consistency issues
     /// * `legacy_migration_info` - Info about legacy classification data needing migration
@@ -239,7 +243,9 @@ pub trait HealthServiceTrait: Send + Sync {
         holdings: &[AssetHoldingInfo],
         latest_quote_times: &HashMap<String, DateTime<Utc>>,
         quote_sync_errors: &[QuoteSyncErrorInfo],
+        quote_quality: &[QuoteQualityInfo],
         fx_pairs: &[FxPairInfo],
+        fx_rate_quality: &[FxRateQuality],
         unclassified_assets: &[UnclassifiedAssetInfo],
         consistency_issues: &[ConsistencyIssueInfo],
         legacy_migration_info: &Option<LegacyMigrationInfo>,
@@ -307,6 +313,9 @@ pub trait HealthServiceTrait: Send + Sync {
     /// * `quote_service` - Service for accessing quotes
     /// * `asset_service` - Service for accessing assets
     /// * `taxonomy_service` - Service for accessing taxonomy data
+    /// * `fx_service` - Service for accessing FX rate quality
+    /// * `reconciliation_service` - Service comparing broker-reported holdings
+    #[allow(clippy::too_many_arguments)]
     async fn run_full_checks(
         &self,
         base_currency: &str,
@@ -315,6 +324,8 @@ pub trait HealthServiceTrait: Send + Sync {
         quote_service: Arc<dyn QuoteServiceTrait>,
         asset_service: Arc<dyn AssetServiceTrait>,
         taxonomy_service: Arc<dyn TaxonomyServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        reconciliation_service: Arc<dyn HoldingsReconciliationServiceTrait>,
     ) -> Result<HealthStatus>;
 }
 
@@ -8,7 +8,10 @@ pub mod accounts;
 pub mod activities;
 pub mod addons;
 pub mod assets;
+pub mod audit;
+pub mod cash_interest;
 pub mod constants;
+pub mod equity_grants;
 pub mod errors;
 pub mod events;
 pub mod fx;
@@ -17,6 +20,7 @@ pub mod health;
 pub mod limits;
 pub mod portfolio;
 pub mod quotes;
+pub mod reconciliation;
 pub mod secrets;
 pub mod settings;
 pub mod sync;
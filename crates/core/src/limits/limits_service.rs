@@ -327,6 +327,13 @@ mod tests {
         async fn create_activities(&self, _: Vec<NewActivity>) -> Result<usize> {
             unimplemented!()
         }
+        async fn create_import_run_activities(
+            &self,
+            _: &str,
+            _: Vec<NewActivity>,
+        ) -> Result<usize> {
+            unimplemented!()
+        }
         fn get_first_activity_date(&self, _: Option<&[String]>) -> Result<Option<DateTime<Utc>>> {
             unimplemented!()
         }
@@ -368,6 +375,14 @@ mod tests {
         ) -> Result<(Vec<String>, Vec<String>)> {
             Ok((Vec::new(), Vec::new()))
         }
+
+        async fn merge_activities(&self, _keep_id: &str, _duplicate_id: &str) -> Result<Activity> {
+            unimplemented!()
+        }
+
+        async fn bulk_edit_activities(&self, _edited: Vec<Activity>) -> Result<Vec<Activity>> {
+            unimplemented!()
+        }
     }
 
     struct MockFxService;
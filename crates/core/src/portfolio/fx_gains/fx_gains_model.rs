@@ -0,0 +1,413 @@
+//! Realized FX gains of currency conversions.
+//!
+//! Each foreign cash balance of an account is an average-cost pool valued in the
+//! base currency. Cash arriving in a foreign currency (deposits, sales, income)
+//! is added at that day's exchange rate; cash leaving it for anything other than
+//! a conversion (purchases, fees, withdrawals) is removed at the average cost.
+//! An `FX_CONVERSION` out of a foreign currency realizes the difference between
+//! the base-currency value of what was bought and the average cost of what was sold.
+//!
+//! Cash for which no exchange rate is known is left out of its pool rather than
+//! valued 1:1; the missing rate is reported on the next gain realized from it.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::activities::{Activity, ACTIVITY_SUBTYPE_FX_CONVERSION, ACTIVITY_TYPE_TRANSFER_OUT};
+
+/// Gain or loss realized by one FX conversion, in the base currency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RealizedFxGain {
+    pub activity_id: String,
+    pub account_id: String,
+    pub date: NaiveDate,
+    pub sold_currency: String,
+    pub sold_amount: Decimal,
+    pub bought_currency: String,
+    pub bought_amount: Decimal,
+    /// Base currency of `proceeds`, `cost_basis` and `gain`.
+    pub currency: String,
+    pub proceeds: Decimal,
+    pub cost_basis: Decimal,
+    pub gain: Decimal,
+    /// Rates that were unavailable. Cash without a rate is left out of
+    /// `proceeds` and `cost_basis`, so the gain is incomplete.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_rates: Vec<MissingFxRate>,
+}
+
+/// A currency with no known rate to the base currency on a date.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingFxRate {
+    pub currency: String,
+    pub date: NaiveDate,
+}
+
+/// Both legs of a compiled `FX_CONVERSION` posting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FxConversion {
+    pub activity_id: String,
+    pub date: NaiveDate,
+    pub sold_currency: String,
+    pub sold_amount: Decimal,
+    pub bought_currency: String,
+    pub bought_amount: Decimal,
+}
+
+impl FxConversion {
+    /// Reads a compiled `TRANSFER_OUT`/`FX_CONVERSION` posting.
+    /// `activity_id` is the stored activity the posting was compiled from.
+    pub fn from_posting(posting: &Activity, activity_id: &str) -> Option<Self> {
+        if posting.activity_type != ACTIVITY_TYPE_TRANSFER_OUT
+            || posting.subtype.as_deref() != Some(ACTIVITY_SUBTYPE_FX_CONVERSION)
+        {
+            return None;
+        }
+        Some(Self {
+            activity_id: activity_id.to_string(),
+            date: posting.effective_date(),
+            sold_currency: posting.currency.clone(),
+            sold_amount: posting.amt(),
+            bought_currency: posting.get_meta::<String>("to_currency")?,
+            bought_amount: posting.get_meta_decimal("to_amount")?,
+        })
+    }
+}
+
+/// Average-cost pool of one foreign currency.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CurrencyPool {
+    quantity: Decimal,
+    cost: Decimal,
+    /// Rates missing for cash left out of the pool, not yet reported.
+    missing_rates: BTreeSet<MissingFxRate>,
+}
+
+impl CurrencyPool {
+    fn acquire(&mut self, quantity: Decimal, cost: Decimal) {
+        self.quantity += quantity;
+        self.cost += cost;
+    }
+
+    /// Records cash that could not be valued.
+    fn skip(&mut self, currency: &str, date: NaiveDate) {
+        self.missing_rates.insert(MissingFxRate {
+            currency: currency.to_string(),
+            date,
+        });
+    }
+
+    /// Removes `quantity` and returns the quantity and cost it was valued at.
+    /// The part not covered by the pool (e.g. history before the first
+    /// snapshot) is valued at `market_rate`, or left out without one.
+    fn dispose(&mut self, quantity: Decimal, market_rate: Option<Decimal>) -> (Decimal, Decimal) {
+        let covered = quantity.min(self.quantity.max(Decimal::ZERO));
+        let covered_cost = if self.quantity.is_zero() {
+            Decimal::ZERO
+        } else {
+            self.cost * covered / self.quantity
+        };
+        self.quantity -= covered;
+        self.cost -= covered_cost;
+        match market_rate {
+            Some(rate) => (quantity, covered_cost + (quantity - covered) * rate),
+            None => (covered, covered_cost),
+        }
+    }
+}
+
+/// Replays daily cash balances and conversions of one account.
+///
+/// `cash_by_date` holds end-of-day cash balances by currency. `rate` returns the
+/// rate from a currency to `base_currency` on a date; missing rates fall back to
+/// the last known rate of the currency, and cash of a currency with no known
+/// rate yet is skipped and reported in `missing_rates`.
+pub fn realize_fx_gains(
+    account_id: &str,
+    base_currency: &str,
+    cash_by_date: &BTreeMap<NaiveDate, HashMap<String, Decimal>>,
+    conversions: &[FxConversion],
+    mut rate: impl FnMut(&str, NaiveDate) -> Option<Decimal>,
+) -> Vec<RealizedFxGain> {
+    let mut conversions_by_date: BTreeMap<NaiveDate, Vec<&FxConversion>> = BTreeMap::new();
+    for conversion in conversions {
+        conversions_by_date
+            .entry(conversion.date)
+            .or_default()
+            .push(conversion);
+    }
+    let dates: BTreeSet<NaiveDate> = cash_by_date
+        .keys()
+        .chain(conversions_by_date.keys())
+        .copied()
+        .collect();
+
+    let mut pools: HashMap<String, CurrencyPool> = HashMap::new();
+    let mut last_rates: HashMap<String, Decimal> = HashMap::new();
+    let mut previous_cash: HashMap<String, Decimal> = HashMap::new();
+    let mut gains = Vec::new();
+
+    let mut rate_on = |currency: &str, date: NaiveDate| -> Option<Decimal> {
+        match rate(currency, date) {
+            Some(r) => {
+                last_rates.insert(currency.to_string(), r);
+                Some(r)
+            }
+            None => last_rates.get(currency).copied(),
+        }
+    };
+
+    for date in dates {
+        let today = conversions_by_date.get(&date).cloned().unwrap_or_default();
+
+        // Cash flows other than today's conversions, by currency
+        let mut conversion_flows: HashMap<&str, Decimal> = HashMap::new();
+        for c in &today {
+            *conversion_flows.entry(&c.sold_currency).or_default() -= c.sold_amount;
+            *conversion_flows.entry(&c.bought_currency).or_default() += c.bought_amount;
+        }
+        let other_flows: HashMap<String, Decimal> = match cash_by_date.get(&date) {
+            Some(cash) => cash
+                .keys()
+                .chain(previous_cash.keys())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .map(|currency| {
+                    let delta = cash.get(currency).copied().unwrap_or_default()
+                        - previous_cash.get(currency).copied().unwrap_or_default();
+                    let converted = conversion_flows
+                        .get(currency.as_str())
+                        .copied()
+                        .unwrap_or_default();
+                    (currency.clone(), delta - converted)
+                })
+                .collect(),
+            None => HashMap::new(),
+        };
+
+        // Inflows first, so cash arriving the day it is converted has a cost
+        for (currency, flow) in &other_flows {
+            if currency != base_currency && *flow > Decimal::ZERO {
+                let pool = pools.entry(currency.clone()).or_default();
+                match rate_on(currency, date) {
+                    Some(rate) => pool.acquire(*flow, *flow * rate),
+                    None => pool.skip(currency, date),
+                }
+            }
+        }
+
+        for c in &today {
+            let proceeds = if c.bought_currency == base_currency {
+                Some(c.bought_amount)
+            } else if c.sold_currency == base_currency {
+                Some(c.sold_amount)
+            } else {
+                rate_on(&c.bought_currency, date).map(|rate| c.bought_amount * rate)
+            };
+
+            if c.sold_currency != base_currency {
+                let market_rate = rate_on(&c.sold_currency, date);
+                let pool = pools.entry(c.sold_currency.clone()).or_default();
+                let (valued, cost_basis) = pool.dispose(c.sold_amount, market_rate);
+                if valued < c.sold_amount {
+                    pool.skip(&c.sold_currency, date);
+                }
+                let mut missing_rates: Vec<MissingFxRate> = std::mem::take(&mut pool.missing_rates)
+                    .into_iter()
+                    .collect();
+                // Only the part of the sale that has a cost realizes a gain
+                let proceeds = match proceeds {
+                    Some(p) if valued == c.sold_amount => p,
+                    Some(p) => p * valued / c.sold_amount,
+                    None => {
+                        missing_rates.push(MissingFxRate {
+                            currency: c.bought_currency.clone(),
+                            date,
+                        });
+                        cost_basis
+                    }
+                };
+                gains.push(RealizedFxGain {
+                    activity_id: c.activity_id.clone(),
+                    account_id: account_id.to_string(),
+                    date,
+                    sold_currency: c.sold_currency.clone(),
+                    sold_amount: c.sold_amount,
+                    bought_currency: c.bought_currency.clone(),
+                    bought_amount: c.bought_amount,
+                    currency: base_currency.to_string(),
+                    proceeds,
+                    cost_basis,
+                    gain: proceeds - cost_basis,
+                    missing_rates,
+                });
+            }
+            if c.bought_currency != base_currency {
+                let pool = pools.entry(c.bought_currency.clone()).or_default();
+                match proceeds {
+                    Some(cost) => pool.acquire(c.bought_amount, cost),
+                    None => pool.skip(&c.bought_currency, date),
+                }
+            }
+        }
+
+        for (currency, flow) in &other_flows {
+            if currency != base_currency && *flow < Decimal::ZERO {
+                pools
+                    .entry(currency.clone())
+                    .or_default()
+                    .dispose(-*flow, None);
+            }
+        }
+
+        match cash_by_date.get(&date) {
+            Some(cash) => previous_cash = cash.clone(),
+            None => {
+                for (currency, flow) in conversion_flows {
+                    *previous_cash.entry(currency.to_string()).or_default() += flow;
+                }
+            }
+        }
+    }
+
+    gains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    fn cash(balances: &[(&str, Decimal)]) -> HashMap<String, Decimal> {
+        balances.iter().map(|(c, b)| (c.to_string(), *b)).collect()
+    }
+
+    fn conversion(day: u32, sold: (&str, Decimal), bought: (&str, Decimal)) -> FxConversion {
+        FxConversion {
+            activity_id: format!("fx-{}", day),
+            date: date(day),
+            sold_currency: sold.0.to_string(),
+            sold_amount: sold.1,
+            bought_currency: bought.0.to_string(),
+            bought_amount: bought.1,
+        }
+    }
+
+    /// EUR base; USD is worth 0.90 EUR on the 1st and 0.95 EUR afterwards.
+    fn usd_rate(currency: &str, d: NaiveDate) -> Option<Decimal> {
+        match currency {
+            "USD" if d == date(1) => Some(dec!(0.90)),
+            "USD" => Some(dec!(0.95)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_converting_back_to_base_realizes_rate_change() {
+        // Deposit 1,000 USD on the 1st, convert it to 950 EUR on the 5th
+        let cash_by_date = BTreeMap::from([
+            (date(1), cash(&[("USD", dec!(1000))])),
+            (date(5), cash(&[("USD", dec!(0)), ("EUR", dec!(950))])),
+        ]);
+        let conversions = [conversion(5, ("USD", dec!(1000)), ("EUR", dec!(950)))];
+
+        let gains = realize_fx_gains("acc-1", "EUR", &cash_by_date, &conversions, usd_rate);
+
+        assert_eq!(gains.len(), 1);
+        assert_eq!(gains[0].proceeds, dec!(950));
+        assert_eq!(gains[0].cost_basis, dec!(900));
+        assert_eq!(gains[0].gain, dec!(50));
+        assert_eq!(gains[0].currency, "EUR");
+    }
+
+    #[test]
+    fn test_buying_foreign_currency_sets_its_cost() {
+        // Convert 900 EUR to 1,000 USD on the 1st, then back at 950 EUR on the 5th
+        let cash_by_date = BTreeMap::from([
+            (date(1), cash(&[("EUR", dec!(100)), ("USD", dec!(1000))])),
+            (date(5), cash(&[("EUR", dec!(1050)), ("USD", dec!(0))])),
+        ]);
+        let conversions = [
+            conversion(1, ("EUR", dec!(900)), ("USD", dec!(1000))),
+            conversion(5, ("USD", dec!(1000)), ("EUR", dec!(950))),
+        ];
+        let mut opening = cash_by_date.clone();
+        opening.insert(
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            cash(&[("EUR", dec!(1000))]),
+        );
+
+        let gains = realize_fx_gains("acc-1", "EUR", &opening, &conversions, usd_rate);
+
+        // Only the conversion out of USD realizes a gain
+        assert_eq!(gains.len(), 1);
+        assert_eq!(gains[0].activity_id, "fx-5");
+        assert_eq!(gains[0].cost_basis, dec!(900));
+        assert_eq!(gains[0].gain, dec!(50));
+    }
+
+    #[test]
+    fn test_spending_foreign_cash_uses_average_cost() {
+        // 1,000 USD at 0.90, 1,000 USD at 0.95, spend 1,000, convert the rest
+        let cash_by_date = BTreeMap::from([
+            (date(1), cash(&[("USD", dec!(1000))])),
+            (date(2), cash(&[("USD", dec!(2000))])),
+            (date(3), cash(&[("USD", dec!(1000))])),
+            (date(4), cash(&[("USD", dec!(0)), ("EUR", dec!(960))])),
+        ]);
+        let conversions = [conversion(4, ("USD", dec!(1000)), ("EUR", dec!(960)))];
+
+        let gains = realize_fx_gains("acc-1", "EUR", &cash_by_date, &conversions, usd_rate);
+
+        assert_eq!(gains[0].cost_basis, dec!(925));
+        assert_eq!(gains[0].gain, dec!(35));
+    }
+
+    #[test]
+    fn test_conversion_without_snapshot_still_moves_pools() {
+        let cash_by_date = BTreeMap::from([(date(1), cash(&[("USD", dec!(1000))]))]);
+        let conversions = [conversion(3, ("USD", dec!(500)), ("EUR", dec!(480)))];
+
+        let gains = realize_fx_gains("acc-1", "EUR", &cash_by_date, &conversions, usd_rate);
+
+        assert_eq!(gains[0].cost_basis, dec!(450));
+        assert_eq!(gains[0].gain, dec!(30));
+    }
+
+    #[test]
+    fn test_cash_without_a_rate_is_skipped_and_reported() {
+        let cash_by_date = BTreeMap::from([
+            (date(1), cash(&[("USD", dec!(1000))])),
+            (date(5), cash(&[("USD", dec!(0)), ("EUR", dec!(950))])),
+        ]);
+        let conversions = [conversion(5, ("USD", dec!(1000)), ("EUR", dec!(950)))];
+
+        let gains = realize_fx_gains("acc-1", "EUR", &cash_by_date, &conversions, |_, _| None);
+
+        // Not valued 1:1: nothing has a cost, so nothing is realized
+        assert_eq!(gains[0].proceeds, dec!(0));
+        assert_eq!(gains[0].cost_basis, dec!(0));
+        assert_eq!(gains[0].gain, dec!(0));
+        assert_eq!(
+            gains[0].missing_rates,
+            vec![
+                MissingFxRate {
+                    currency: "USD".to_string(),
+                    date: date(1),
+                },
+                MissingFxRate {
+                    currency: "USD".to_string(),
+                    date: date(5),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use crate::accounts::AccountServiceTrait;
+use crate::activities::{
+    ActivityCompiler, ActivityServiceTrait, DefaultActivityCompiler, ACTIVITY_SUBTYPE_FX_CONVERSION,
+};
+use crate::fx::FxServiceTrait;
+use crate::portfolio::snapshot::SnapshotServiceTrait;
+use crate::Result;
+use chrono::NaiveDate;
+use log::{debug, warn};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+use super::{realize_fx_gains, FxConversion, RealizedFxGain};
+
+pub trait FxGainsServiceTrait: Send + Sync {
+    /// Gains realized by FX conversions between `start_date` and `end_date`
+    /// (inclusive), for one account or all active accounts.
+    fn get_realized_fx_gains(
+        &self,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<RealizedFxGain>>;
+}
+
+pub struct FxGainsService {
+    account_service: Arc<dyn AccountServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    snapshot_service: Arc<dyn SnapshotServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl FxGainsService {
+    pub fn new(
+        account_service: Arc<dyn AccountServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        snapshot_service: Arc<dyn SnapshotServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            account_service,
+            activity_service,
+            snapshot_service,
+            fx_service,
+            base_currency,
+        }
+    }
+
+    fn account_gains(
+        &self,
+        account_id: &str,
+        base_currency: &str,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<RealizedFxGain>> {
+        let compiler = DefaultActivityCompiler::new();
+        let mut conversions = Vec::new();
+        for activity in self
+            .activity_service
+            .get_activities_by_account_id(account_id)?
+        {
+            if activity.subtype.as_deref() != Some(ACTIVITY_SUBTYPE_FX_CONVERSION) {
+                continue;
+            }
+            for posting in compiler.compile(&activity)? {
+                conversions.extend(FxConversion::from_posting(&posting, &activity.id));
+            }
+        }
+        if conversions.is_empty() {
+            return Ok(Vec::new());
+        }
+        conversions.sort_by_key(|c| c.date);
+
+        // Pools need the whole cash history, whatever the reporting window
+        let cash_by_date: BTreeMap<NaiveDate, HashMap<String, Decimal>> = self
+            .snapshot_service
+            .get_daily_holdings_snapshots(account_id, None, end_date)?
+            .into_iter()
+            .map(|s| (s.snapshot_date, s.cash_balances))
+            .collect();
+
+        debug!(
+            "Replaying {} FX conversions over {} days for account {}",
+            conversions.len(),
+            cash_by_date.len(),
+            account_id
+        );
+        Ok(realize_fx_gains(
+            account_id,
+            base_currency,
+            &cash_by_date,
+            &conversions,
+            |currency, date| {
+                self.fx_service
+                    .get_exchange_rate_for_date(currency, base_currency, date)
+                    .map_err(|e| {
+                        warn!(
+                            "No {}/{} rate on {} for FX gains: {}",
+                            currency, base_currency, date, e
+                        )
+                    })
+                    .ok()
+            },
+        ))
+    }
+}
+
+impl FxGainsServiceTrait for FxGainsService {
+    fn get_realized_fx_gains(
+        &self,
+        account_id: Option<&str>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<RealizedFxGain>> {
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let account_ids: Vec<String> = match account_id {
+            Some(id) => vec![id.to_string()],
+            None => self
+                .account_service
+                .get_active_non_archived_accounts()?
+                .into_iter()
+                .map(|a| a.id)
+                .collect(),
+        };
+
+        let mut gains = Vec::new();
+        for id in account_ids {
+            gains.extend(self.account_gains(&id, &base_currency, end_date)?);
+        }
+        gains.retain(|g| {
+            start_date.is_none_or(|start| g.date >= start)
+                && end_date.is_none_or(|end| g.date <= end)
+        });
+        gains.sort_by(|a, b| a.date.cmp(&b.date).then(a.account_id.cmp(&b.account_id)));
+        Ok(gains)
+    }
+}
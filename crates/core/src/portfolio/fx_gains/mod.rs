@@ -0,0 +1,5 @@
+pub mod fx_gains_model;
+pub mod fx_gains_service;
+
+pub use fx_gains_model::*;
+pub use fx_gains_service::{FxGainsService, FxGainsServiceTrait};
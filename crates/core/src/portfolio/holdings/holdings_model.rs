@@ -50,6 +50,18 @@ impl MonetaryValue {
     }
 }
 
+/// Vested and unvested employee equity (RSU/ESPP/options) granted in a holding's asset.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EquityCompensation {
+    pub vested_shares: Decimal,
+    pub unvested_shares: Decimal,
+    /// Intrinsic value for options, market value for shares.
+    pub vested_value: MonetaryValue,
+    /// Not part of `market_value` until the shares vest.
+    pub unvested_value: MonetaryValue,
+}
+
 /// Lightweight holding summary for allocation drill-down views.
 /// Contains only the fields needed to display a list of holdings for a category.
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -122,4 +134,8 @@ pub struct Holding {
     /// Asset metadata (JSON) for alternative assets.
     /// Contains purchase_price, purchase_date, sub_type, linked_asset_id, etc.
     pub metadata: Option<Value>,
+
+    /// Equity grants in this holding's asset, when any exist.
+    #[serde(default)]
+    pub equity_compensation: Option<EquityCompensation>,
 }
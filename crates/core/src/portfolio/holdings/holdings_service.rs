@@ -181,6 +181,7 @@ impl HoldingsService {
                 weight: Decimal::ZERO,
                 as_of_date: today,
                 metadata: asset_info.metadata.clone(),
+                equity_compensation: None,
             };
             holdings.push(holding_view);
         }
@@ -238,6 +239,7 @@ impl HoldingsService {
                 weight: Decimal::ZERO,
                 as_of_date: today,
                 metadata: None,
+                equity_compensation: None,
             };
             holdings.push(holding_view);
         }
@@ -589,6 +591,7 @@ impl HoldingsServiceTrait for HoldingsService {
                 weight: Decimal::ZERO,
                 as_of_date: snapshot.snapshot_date,
                 metadata: asset.metadata.clone(),
+                equity_compensation: None,
             };
             holdings.push(holding);
         }
@@ -633,6 +636,7 @@ impl HoldingsServiceTrait for HoldingsService {
                 weight: Decimal::ZERO,
                 as_of_date: snapshot.snapshot_date,
                 metadata: None,
+                equity_compensation: None,
             };
             holdings.push(holding);
         }
@@ -718,6 +722,7 @@ mod tests {
             weight: dec!(0.1),
             as_of_date: as_of,
             metadata: None,
+            equity_compensation: None,
         };
 
         normalize_holding_currency(&mut holding);
@@ -786,6 +791,7 @@ mod tests {
             weight: dec!(1),
             as_of_date: valuation_date_today(),
             metadata: None,
+            equity_compensation: None,
         };
 
         normalize_holding_currency(&mut holding);
@@ -1,11 +1,14 @@
 use crate::assets::AssetKind;
+use crate::constants::PORTFOLIO_TOTAL_ACCOUNT_ID;
+use crate::equity_grants::{EquityGrant, EquityGrantRepositoryTrait};
 use crate::errors::Result;
 use crate::fx::currency::{normalize_amount, normalize_currency_code};
 use crate::fx::FxServiceTrait;
-use crate::portfolio::holdings::{Holding, HoldingType, MonetaryValue};
+use crate::portfolio::holdings::{EquityCompensation, Holding, HoldingType, MonetaryValue};
 use crate::quotes::{LatestQuotePair, QuoteServiceTrait};
 use crate::utils::time_utils::valuation_date_today;
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use log::{debug, warn};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -21,6 +24,7 @@ pub trait HoldingsValuationServiceTrait: Send + Sync {
 pub struct HoldingsValuationService {
     fx_service: Arc<dyn FxServiceTrait>,
     quote_service: Arc<dyn QuoteServiceTrait>,
+    equity_grant_repository: Option<Arc<dyn EquityGrantRepositoryTrait>>,
 }
 
 impl HoldingsValuationService {
@@ -31,9 +35,16 @@ impl HoldingsValuationService {
         Self {
             fx_service,
             quote_service,
+            equity_grant_repository: None,
         }
     }
 
+    /// Reports vested and unvested equity compensation on security holdings.
+    pub fn with_equity_grants(mut self, repository: Arc<dyn EquityGrantRepositoryTrait>) -> Self {
+        self.equity_grant_repository = Some(repository);
+        self
+    }
+
     // Private helper to get FX rate with logging and fallback
     fn get_fx_rate_or_fallback(
         &self,
@@ -101,6 +112,14 @@ impl HoldingsValuationServiceTrait for HoldingsValuationService {
 
         let today = valuation_date_today();
 
+        let equity_grants: Vec<EquityGrant> = match &self.equity_grant_repository {
+            Some(repository) => repository.list_equity_grants().unwrap_or_else(|e| {
+                warn!("Failed to load equity grants: {}", e);
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+
         for holding in holdings.iter_mut() {
             match holding.holding_type {
                 HoldingType::Security => {
@@ -116,6 +135,9 @@ impl HoldingsValuationServiceTrait for HoldingsValuationService {
                     let base_currency = holding.base_currency.clone();
                     self.calculate_security_valuation(holding, &base_currency, &latest_quote_pairs)
                         .await?;
+                    if !equity_grants.is_empty() {
+                        self.apply_equity_compensation(holding, &equity_grants, today);
+                    }
                 }
                 HoldingType::AlternativeAsset => {
                     // Use asset ID for quote lookups
@@ -540,6 +562,62 @@ impl HoldingsValuationService {
         Ok(())
     }
 
+    /// Attach vested/unvested grant values to a valued security holding.
+    ///
+    /// Grants match on asset and account; the total-portfolio view includes
+    /// grants of every account.
+    fn apply_equity_compensation(
+        &self,
+        holding: &mut Holding,
+        grants: &[EquityGrant],
+        today: NaiveDate,
+    ) {
+        let Some(asset_id) = holding.instrument.as_ref().map(|i| i.id.as_str()) else {
+            return;
+        };
+        let all_accounts = holding.account_id == PORTFOLIO_TOTAL_ACCOUNT_ID;
+        let matching: Vec<&EquityGrant> = grants
+            .iter()
+            .filter(|g| g.asset_id == asset_id)
+            .filter(|g| all_accounts || g.account_id == holding.account_id)
+            .collect();
+        if matching.is_empty() {
+            holding.equity_compensation = None;
+            return;
+        }
+
+        let local_currency = holding.local_currency.clone();
+        let base_currency = holding.base_currency.clone();
+        let mut compensation = EquityCompensation {
+            vested_shares: Decimal::ZERO,
+            unvested_shares: Decimal::ZERO,
+            vested_value: MonetaryValue::zero(),
+            unvested_value: MonetaryValue::zero(),
+        };
+
+        for grant in matching {
+            let context_msg = format!("HoldingValuation [EquityGrant {}]", grant.id);
+            let to_grant =
+                self.get_fx_rate_or_fallback(&local_currency, &grant.currency, &context_msg);
+            let to_local =
+                self.get_fx_rate_or_fallback(&grant.currency, &local_currency, &context_msg);
+            let to_base =
+                self.get_fx_rate_or_fallback(&grant.currency, &base_currency, &context_msg);
+
+            let price = holding.price.map(|p| p * to_grant);
+            let position = grant.position_on(today, price);
+
+            compensation.vested_shares += position.vested_shares;
+            compensation.unvested_shares += position.unvested_shares;
+            compensation.vested_value.local += position.vested_value * to_local;
+            compensation.vested_value.base += position.vested_value * to_base;
+            compensation.unvested_value.local += position.unvested_value * to_local;
+            compensation.unvested_value.base += position.unvested_value * to_base;
+        }
+
+        holding.equity_compensation = Some(compensation);
+    }
+
     fn calculate_cash_valuation(&self, holding: &mut Holding, base_currency: &str) -> Result<()> {
         let cash_currency = &holding.local_currency;
         let cash_amount = holding.quantity;
@@ -2,6 +2,7 @@
 #[cfg(test)]
 mod tests {
     use crate::assets::{Asset, ProviderProfile};
+    use crate::equity_grants::{EquityGrant, EquityGrantRepositoryTrait, GrantType};
     use crate::errors::{Error, Result};
     use crate::fx::{ExchangeRate, FxServiceTrait, NewExchangeRate};
     use crate::portfolio::holdings::holdings_model::{
@@ -10,14 +11,16 @@ mod tests {
     use crate::portfolio::holdings::holdings_valuation_service::{
         HoldingsValuationService, HoldingsValuationServiceTrait,
     };
-    use crate::quotes::{DataSource, MarketDataError};
     use crate::quotes::{
-        LatestQuotePair, LatestQuoteSnapshot, ProviderInfo, Quote, QuoteImport, QuoteServiceTrait,
-        QuoteSyncState, SymbolSearchResult, SymbolSyncPlan, SyncResult,
+        BulkImportOptions, BulkImportSummary, ConsensusConfig, LatestQuotePair,
+        LatestQuoteSnapshot, ProviderInfo, Quote, QuoteExportRequest, QuoteImport, QuoteProvenance,
+        QuoteQualityConfig, QuoteServiceTrait, QuoteSyncState, SymbolSearchResult, SymbolSyncPlan,
+        SyncResult,
     };
+    use crate::quotes::{DataSource, MarketDataError};
     use crate::utils::time_utils::valuation_date_today;
     use async_trait::async_trait;
-    use chrono::{NaiveDate, Utc};
+    use chrono::{Duration, NaiveDate, Utc};
     use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
     use std::collections::HashMap;
@@ -253,6 +256,15 @@ mod tests {
             unimplemented!()
         }
 
+        fn get_quote_provenance(
+            &self,
+            _asset_id: &str,
+            _start: NaiveDate,
+            _end: NaiveDate,
+        ) -> Result<Vec<QuoteProvenance>> {
+            unimplemented!()
+        }
+
         async fn add_quote(&self, _quote: &Quote) -> Result<Quote> {
             unimplemented!()
         }
@@ -392,6 +404,22 @@ mod tests {
             unimplemented!()
         }
 
+        fn get_consensus_config(&self) -> ConsensusConfig {
+            unimplemented!()
+        }
+
+        async fn update_consensus_config(&self, _config: ConsensusConfig) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn get_quality_config(&self) -> QuoteQualityConfig {
+            unimplemented!()
+        }
+
+        async fn update_quality_config(&self, _config: QuoteQualityConfig) -> Result<()> {
+            unimplemented!()
+        }
+
         // =========================================================================
         // Quote Import
         // =========================================================================
@@ -411,6 +439,18 @@ mod tests {
         ) -> Result<Vec<QuoteImport>> {
             unimplemented!()
         }
+
+        async fn bulk_import_quotes(
+            &self,
+            _path: &std::path::Path,
+            _options: BulkImportOptions,
+        ) -> Result<BulkImportSummary> {
+            unimplemented!()
+        }
+
+        fn export_market_data(&self, _request: &QuoteExportRequest) -> Result<Vec<u8>> {
+            unimplemented!()
+        }
     }
 
     // --- Helper Functions ---
@@ -496,6 +536,7 @@ mod tests {
             total_gain: None,          // To be calculated
             total_gain_pct: None,      // To be calculated
             metadata: None,
+            equity_compensation: None,
         }
     }
 
@@ -547,6 +588,59 @@ mod tests {
         }
     }
 
+    // --- Mock EquityGrantRepository ---
+    struct MockEquityGrantRepository {
+        grants: Vec<EquityGrant>,
+    }
+
+    #[async_trait]
+    impl EquityGrantRepositoryTrait for MockEquityGrantRepository {
+        fn list_equity_grants(&self) -> Result<Vec<EquityGrant>> {
+            Ok(self.grants.clone())
+        }
+        fn get_equity_grant(&self, _grant_id: &str) -> Result<EquityGrant> {
+            unimplemented!()
+        }
+        async fn insert_equity_grant(&self, _grant: EquityGrant) -> Result<EquityGrant> {
+            unimplemented!()
+        }
+        async fn update_equity_grant(&self, _grant: EquityGrant) -> Result<EquityGrant> {
+            unimplemented!()
+        }
+        async fn delete_equity_grant(&self, _grant_id: &str) -> Result<usize> {
+            unimplemented!()
+        }
+    }
+
+    fn create_grant(
+        id: &str,
+        account_id: &str,
+        grant_type: GrantType,
+        shares: Decimal,
+        strike_price: Option<Decimal>,
+        vests_on: NaiveDate,
+    ) -> EquityGrant {
+        let now = Utc::now().naive_utc();
+        EquityGrant {
+            id: id.to_string(),
+            account_id: account_id.to_string(),
+            asset_id: "ACME".to_string(),
+            grant_type,
+            grant_date: vests_on,
+            total_shares: shares,
+            strike_price,
+            grant_price: None,
+            currency: "USD".to_string(),
+            vesting_start_date: vests_on,
+            vesting_months: 0,
+            cliff_months: 0,
+            vesting_interval_months: 1,
+            notes: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
     // --- Test Setup ---
     fn setup_test_env() -> (
         Arc<MockFxService>,
@@ -1799,4 +1893,80 @@ mod tests {
         assert!(result.is_ok());
         assert!(holdings.is_empty()); // Should remain empty
     }
+
+    #[tokio::test]
+    async fn test_equity_compensation_reports_vested_and_unvested_value() {
+        let fx_service = Arc::new(MockFxService::default());
+        fx_service.add_rate("USD", "CAD", dec!(1.3));
+        let market_data_service = Arc::new(MockMarketDataService::default());
+        let latest_quote = create_quote("2024-01-10", dec!(100.0), "USD");
+        market_data_service.add_quote_pair("ACME", latest_quote, None);
+
+        let today = valuation_date_today();
+        let grants = vec![
+            create_grant(
+                "rsu",
+                "acc_1",
+                GrantType::Rsu,
+                dec!(100),
+                None,
+                today - Duration::days(10),
+            ),
+            create_grant(
+                "nso",
+                "acc_1",
+                GrantType::Nso,
+                dec!(50),
+                Some(dec!(80)),
+                today + Duration::days(365),
+            ),
+            // Other account: not part of this holding
+            create_grant(
+                "other",
+                "acc_2",
+                GrantType::Rsu,
+                dec!(999),
+                None,
+                today - Duration::days(10),
+            ),
+        ];
+        let valuation_service =
+            HoldingsValuationService::new(fx_service.clone(), market_data_service.clone())
+                .with_equity_grants(Arc::new(MockEquityGrantRepository { grants }));
+
+        let mut holdings = vec![create_holding(
+            "h1",
+            HoldingType::Security,
+            "ACME",
+            dec!(100),
+            "USD",
+            "CAD",
+            Some(dec!(9000.0)),
+            Some("Acme Corp"),
+        )];
+
+        valuation_service
+            .calculate_holdings_live_valuation(&mut holdings)
+            .await
+            .unwrap();
+
+        let compensation = holdings[0].equity_compensation.as_ref().unwrap();
+        assert_eq!(compensation.vested_shares, dec!(100));
+        assert_eq!(compensation.unvested_shares, dec!(50));
+        assert_monetary_value_approx(
+            Some(&compensation.vested_value),
+            dec!(10000),
+            dec!(13000),
+            TOLERANCE,
+            "Vested Value",
+        );
+        // Unvested options at intrinsic value: 50 * (100 - 80)
+        assert_monetary_value_approx(
+            Some(&compensation.unvested_value),
+            dec!(1000),
+            dec!(1300),
+            TOLERANCE,
+            "Unvested Value",
+        );
+    }
 }
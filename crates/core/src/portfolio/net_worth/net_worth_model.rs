@@ -75,6 +75,22 @@ pub struct LiabilitiesSection {
     pub breakdown: Vec<BreakdownItem>,
 }
 
+/// Equity held in a property: its value minus the liabilities linked to it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyEquity {
+    /// Property asset ID
+    pub asset_id: String,
+    /// Property name (if available)
+    pub name: Option<String>,
+    /// Property value in base currency
+    pub value: Decimal,
+    /// Balance of linked liabilities in base currency
+    pub linked_liabilities: Decimal,
+    /// value - linked_liabilities
+    pub equity: Decimal,
+}
+
 /// Information about a stale asset valuation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -107,6 +123,9 @@ pub struct NetWorthResponse {
     pub oldest_valuation_date: Option<NaiveDate>,
     /// Assets with valuations older than 90 days
     pub stale_assets: Vec<StaleAssetInfo>,
+    /// Equity per property (value minus linked liabilities)
+    #[serde(default)]
+    pub property_equity: Vec<PropertyEquity>,
 }
 
 impl NetWorthResponse {
@@ -120,6 +139,7 @@ impl NetWorthResponse {
             currency,
             oldest_valuation_date: None,
             stale_assets: Vec::new(),
+            property_equity: Vec::new(),
         }
     }
 }
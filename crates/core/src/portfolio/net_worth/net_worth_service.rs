@@ -1,26 +1,29 @@
 //! Net worth calculation service implementation.
 
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use chrono::{Datelike, Months, NaiveDate};
 use log::{debug, warn};
 use rust_decimal::Decimal;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 
 use super::net_worth_model::{
     AssetCategory, AssetsSection, BreakdownItem, LiabilitiesSection, NetWorthHistoryPoint,
-    NetWorthResponse, StaleAssetInfo, ValuationInfo,
+    NetWorthResponse, PropertyEquity, StaleAssetInfo, ValuationInfo,
 };
 use super::net_worth_traits::NetWorthServiceTrait;
 use crate::accounts::{account_types, AccountRepositoryTrait};
-use crate::assets::{AssetKind, AssetRepositoryTrait};
+use crate::assets::{
+    AmortizationSchedule, Asset, AssetKind, AssetRepositoryTrait, LoanTerms, ValuationModel,
+    ValuationSeries,
+};
 use crate::constants::DECIMAL_PRECISION;
 use crate::errors::Result;
 use crate::fx::currency::normalize_amount;
 use crate::fx::FxServiceTrait;
 use crate::portfolio::snapshot::SnapshotRepositoryTrait;
 use crate::portfolio::valuation::ValuationRepositoryTrait;
-use crate::quotes::QuoteServiceTrait;
+use crate::quotes::{DataSource, QuoteServiceTrait};
 
 /// Number of days after which a valuation is considered stale.
 const STALENESS_THRESHOLD_DAYS: i64 = 90;
@@ -125,6 +128,145 @@ impl NetWorthService {
         Ok(converted.round_dp(DECIMAL_PRECISION))
     }
 
+    /// Builds the amortization schedule of a liability with loan terms attached.
+    ///
+    /// Returns `None` for other assets, or when the stored terms are invalid
+    /// (the liability then falls back to its manual valuations).
+    fn loan_schedule(asset: &Asset) -> Option<AmortizationSchedule> {
+        if asset.kind != AssetKind::Liability {
+            return None;
+        }
+        let terms = LoanTerms::from_metadata(asset.metadata.as_ref())?;
+        match terms.schedule() {
+            Ok(schedule) => Some(schedule),
+            Err(e) => {
+                warn!(
+                    "Invalid loan terms for liability {}: {}. Using manual valuations.",
+                    asset.id, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Loads the appraisals (and index series) of an asset with a valuation model,
+    /// up to `as_of`.
+    ///
+    /// Appraisals are the asset's manual valuations. Returns `None` for
+    /// liabilities, assets without a model, or assets with no appraisal yet
+    /// (they then fall back to plain quote forward-fill).
+    fn valuation_series(&self, asset: &Asset, as_of: NaiveDate) -> Option<ValuationSeries> {
+        if asset.kind == AssetKind::Liability {
+            return None;
+        }
+        let model = ValuationModel::from_metadata(asset.metadata.as_ref())?;
+        if let Err(e) = model.validate() {
+            warn!(
+                "Invalid valuation model for asset {}: {}. Using manual valuations.",
+                asset.id, e
+            );
+            return None;
+        }
+
+        let load = |asset_id: &str, manual_only: bool| -> Vec<(NaiveDate, Decimal)> {
+            let ids = HashSet::from([asset_id.to_string()]);
+            self.quote_service
+                .get_quotes_in_range(&ids, NaiveDate::MIN, as_of)
+                .map(|quotes| {
+                    quotes
+                        .iter()
+                        .filter(|q| !manual_only || q.data_source == DataSource::Manual)
+                        .map(|q| (q.timestamp.date_naive(), q.close))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let appraisals = load(&asset.id, true);
+        if appraisals.is_empty() {
+            return None;
+        }
+        let index = model
+            .index_asset_id()
+            .map(|id| load(id, false))
+            .unwrap_or_default();
+        let in_service_date = asset
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("purchase_date"))
+            .and_then(|v| v.as_str())
+            .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
+
+        Some(ValuationSeries::new(
+            model,
+            appraisals,
+            index,
+            in_service_date,
+        ))
+    }
+
+    /// Converts an amount to base currency, falling back to the unconverted amount.
+    fn convert_to_base(
+        &self,
+        amount: Decimal,
+        currency: &str,
+        base_currency: &str,
+        date: NaiveDate,
+    ) -> Decimal {
+        if currency == base_currency {
+            return amount;
+        }
+        self.fx_service
+            .convert_currency_for_date(amount, currency, base_currency, date)
+            .unwrap_or(amount)
+    }
+
+    /// Build per-property equity from valuations, subtracting liabilities linked
+    /// to each property via `linked_asset_id` metadata.
+    fn build_property_equity(
+        valuations: &[ValuationInfo],
+        assets: &[Asset],
+    ) -> Vec<PropertyEquity> {
+        let linked_to: HashMap<&str, &str> = assets
+            .iter()
+            .filter(|a| a.kind == AssetKind::Liability)
+            .filter_map(|a| {
+                let target = a.metadata.as_ref()?.get("linked_asset_id")?.as_str()?;
+                Some((a.id.as_str(), target))
+            })
+            .collect();
+
+        let mut linked_balances: HashMap<&str, Decimal> = HashMap::new();
+        for val in valuations {
+            if val.category != AssetCategory::Liability {
+                continue;
+            }
+            if let Some(target) = linked_to.get(val.asset_id.as_str()) {
+                *linked_balances.entry(*target).or_insert(Decimal::ZERO) += val.market_value_base;
+            }
+        }
+
+        let mut equity: Vec<PropertyEquity> = valuations
+            .iter()
+            .filter(|v| v.category == AssetCategory::Property)
+            .map(|v| {
+                let linked = linked_balances
+                    .get(v.asset_id.as_str())
+                    .copied()
+                    .unwrap_or(Decimal::ZERO);
+                PropertyEquity {
+                    asset_id: v.asset_id.clone(),
+                    name: v.name.clone(),
+                    value: v.market_value_base.round_dp(DECIMAL_PRECISION),
+                    linked_liabilities: linked.round_dp(DECIMAL_PRECISION),
+                    equity: (v.market_value_base - linked).round_dp(DECIMAL_PRECISION),
+                }
+            })
+            .collect();
+        equity.sort_by_key(|e| std::cmp::Reverse(e.equity));
+        equity
+    }
+
     /// Get display name for asset category.
     fn category_display_name(category: AssetCategory) -> &'static str {
         match category {
@@ -413,18 +555,28 @@ impl NetWorthServiceTrait for NetWorthService {
                 continue;
             }
 
-            // Get the latest quote for this alternative asset
-            let (price, quote_currency, valuation_date) =
-                match self.get_latest_quote_as_of(&asset.id, date) {
-                    Some((p, c, d)) => (p, c, d),
-                    None => {
-                        debug!(
-                            "No quote found for alternative asset {}, skipping",
-                            asset.id
-                        );
-                        continue;
-                    }
-                };
+            // Loan-backed liabilities take their balance from the amortization
+            // schedule and modeled assets are projected from their last appraisal;
+            // everything else uses the latest valuation quote.
+            let latest = if let Some(schedule) = Self::loan_schedule(asset) {
+                Some((schedule.balance_on(date), asset.quote_ccy.clone(), date))
+            } else if let Some(series) = self.valuation_series(asset, date) {
+                series
+                    .value_on(date)
+                    .map(|value| (value, asset.quote_ccy.clone(), date))
+            } else {
+                self.get_latest_quote_as_of(&asset.id, date)
+            };
+            let (price, quote_currency, valuation_date) = match latest {
+                Some((p, c, d)) => (p, c, d),
+                None => {
+                    debug!(
+                        "No quote found for alternative asset {}, skipping",
+                        asset.id
+                    );
+                    continue;
+                }
+            };
 
             // For alternative assets, quantity is always 1 (value-based model)
             let quantity = Decimal::ONE;
@@ -467,6 +619,7 @@ impl NetWorthServiceTrait for NetWorthService {
 
         // Calculate net worth
         let net_worth = assets.total - liabilities.total;
+        let property_equity = Self::build_property_equity(&valuations, &all_assets);
 
         // Calculate staleness
         let (oldest_valuation_date, stale_assets) = Self::calculate_staleness(&valuations, date);
@@ -484,6 +637,7 @@ impl NetWorthServiceTrait for NetWorthService {
             currency: base_currency,
             oldest_valuation_date,
             stale_assets,
+            property_equity,
         })
     }
 
@@ -551,6 +705,21 @@ impl NetWorthServiceTrait for NetWorthService {
             .map(|a| a.id.clone())
             .collect();
 
+        // Liabilities with loan terms are valued from their amortization schedule
+        let loan_schedules: HashMap<String, AmortizationSchedule> = alternative_assets
+            .iter()
+            .filter_map(|a| Self::loan_schedule(a).map(|s| (a.id.clone(), s)))
+            .collect();
+
+        // Assets with a valuation model are projected daily from their appraisals
+        let valuation_series: HashMap<String, ValuationSeries> = alternative_assets
+            .iter()
+            .filter_map(|a| {
+                self.valuation_series(a, end_date)
+                    .map(|s| (a.id.clone(), s))
+            })
+            .collect();
+
         // Build currency lookup for FX conversion
         let asset_currency_map: HashMap<String, String> = alternative_assets
             .iter()
@@ -560,8 +729,13 @@ impl NetWorthServiceTrait for NetWorthService {
         // =====================================================================
         // 3. Load quotes for alternative assets
         // =====================================================================
-        let all_alt_symbols: HashSet<String> =
-            alternative_assets.iter().map(|a| a.id.clone()).collect();
+        let all_alt_symbols: HashSet<String> = alternative_assets
+            .iter()
+            .filter(|a| {
+                !loan_schedules.contains_key(&a.id) && !valuation_series.contains_key(&a.id)
+            })
+            .map(|a| a.id.clone())
+            .collect();
 
         // Get quotes in the date range
         let quotes_vec = self.quote_service.get_quotes_in_range_filled(
@@ -600,6 +774,9 @@ impl NetWorthServiceTrait for NetWorthService {
         let mut initial_asset_values: HashMap<String, Decimal> = HashMap::new();
 
         for asset in &alternative_assets {
+            if loan_schedules.contains_key(&asset.id) || valuation_series.contains_key(&asset.id) {
+                continue;
+            }
             if let Some((price, quote_currency, _)) =
                 self.get_latest_quote_as_of(&asset.id, start_date)
             {
@@ -626,6 +803,34 @@ impl NetWorthServiceTrait for NetWorthService {
         // =====================================================================
         let first_portfolio_date = portfolio_by_date.keys().next().copied();
 
+        // Dates on which derived values change: loan payments (balances step down
+        // on each payment), appraisals, and month starts for modeled assets so
+        // appreciation/depreciation shows even without portfolio data.
+        let mut synthetic_dates: BTreeSet<NaiveDate> = loan_schedules
+            .values()
+            .flat_map(|s| {
+                std::iter::once(s.terms.start_date).chain(s.entries.iter().map(|e| e.date))
+            })
+            .collect();
+        for series in valuation_series.values() {
+            synthetic_dates.extend(series.appraisals.iter().map(|(d, _)| *d));
+            let Some(first) = series.first_date() else {
+                continue;
+            };
+            let mut month = first.max(start_date).with_day(1).unwrap_or(start_date);
+            while month <= end_date {
+                synthetic_dates.insert(month);
+                month = match month.checked_add_months(Months::new(1)) {
+                    Some(next) => next,
+                    None => break,
+                };
+            }
+            if first <= end_date {
+                synthetic_dates.insert(end_date);
+            }
+        }
+        synthetic_dates.retain(|d| *d >= start_date && *d <= end_date);
+
         // Collect all dates with data
         let mut all_dates: Vec<NaiveDate> = Vec::new();
 
@@ -634,7 +839,7 @@ impl NetWorthServiceTrait for NetWorthService {
             all_dates.extend(portfolio_by_date.keys().cloned());
 
             // Add quote dates that are >= first portfolio date
-            for date in quotes_by_date.keys() {
+            for date in quotes_by_date.keys().chain(synthetic_dates.iter()) {
                 if *date >= first_pf_date && !all_dates.contains(date) {
                     all_dates.push(*date);
                 }
@@ -643,9 +848,14 @@ impl NetWorthServiceTrait for NetWorthService {
             // Edge case: no portfolio data, only alternative assets
             // Use all quote dates
             all_dates.extend(quotes_by_date.keys().cloned());
+            all_dates.extend(synthetic_dates.iter().cloned());
 
             // Also add start_date if we have initial values but no quotes in range
-            if all_dates.is_empty() && !initial_asset_values.is_empty() {
+            if all_dates.is_empty()
+                && (!initial_asset_values.is_empty()
+                    || !loan_schedules.is_empty()
+                    || !valuation_series.is_empty())
+            {
                 all_dates.push(start_date);
             }
         }
@@ -699,6 +909,29 @@ impl NetWorthServiceTrait for NetWorthService {
                 }
             }
 
+            for (liability_id, schedule) in &loan_schedules {
+                let balance = schedule.balance_on(date);
+                if balance.is_zero() {
+                    continue;
+                }
+                let currency = asset_currency_map
+                    .get(liability_id)
+                    .map(String::as_str)
+                    .unwrap_or(base_currency.as_str());
+                liabilities_value += self.convert_to_base(balance, currency, &base_currency, date);
+            }
+
+            for (asset_id, series) in &valuation_series {
+                let Some(value) = series.value_on(date) else {
+                    continue;
+                };
+                let currency = asset_currency_map
+                    .get(asset_id)
+                    .map(String::as_str)
+                    .unwrap_or(base_currency.as_str());
+                alt_assets_value += self.convert_to_base(value, currency, &base_currency, date);
+            }
+
             let total_assets = current_portfolio.value + alt_assets_value;
             let net_worth = total_assets - liabilities_value;
 
@@ -3,8 +3,8 @@
 use super::*;
 use crate::accounts::{Account, AccountRepositoryTrait, AccountUpdate, NewAccount};
 use crate::assets::{
-    Asset, AssetKind, AssetRepositoryTrait, NewAsset, ProviderProfile, QuoteMode,
-    UpdateAssetProfile,
+    Asset, AssetKind, AssetRepositoryTrait, LoanTerms, NewAsset, PaymentFrequency, ProviderProfile,
+    QuoteMode, RateType, UpdateAssetProfile,
 };
 use crate::errors::Result;
 use crate::fx::{ExchangeRate, FxServiceTrait, NewExchangeRate};
@@ -14,8 +14,9 @@ use crate::portfolio::snapshot::{
 use crate::portfolio::valuation::{DailyAccountValuation, ValuationRepositoryTrait};
 use crate::quotes::DataSource;
 use crate::quotes::{
-    LatestQuotePair, LatestQuoteSnapshot, ProviderInfo, Quote, QuoteImport, QuoteServiceTrait,
-    QuoteSyncState, SymbolSearchResult, SymbolSyncPlan, SyncResult,
+    BulkImportOptions, BulkImportSummary, ConsensusConfig, LatestQuotePair, LatestQuoteSnapshot,
+    ProviderInfo, Quote, QuoteExportRequest, QuoteImport, QuoteProvenance, QuoteQualityConfig,
+    QuoteServiceTrait, QuoteSyncState, SymbolSearchResult, SymbolSyncPlan, SyncResult,
 };
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDate, Utc};
@@ -439,6 +440,15 @@ impl QuoteServiceTrait for MockMarketDataRepository {
         unimplemented!()
     }
 
+    fn get_quote_provenance(
+        &self,
+        _asset_id: &str,
+        _start: NaiveDate,
+        _end: NaiveDate,
+    ) -> Result<Vec<QuoteProvenance>> {
+        unimplemented!()
+    }
+
     async fn add_quote(&self, _quote: &Quote) -> Result<Quote> {
         unimplemented!()
     }
@@ -578,6 +588,22 @@ impl QuoteServiceTrait for MockMarketDataRepository {
         unimplemented!()
     }
 
+    fn get_consensus_config(&self) -> ConsensusConfig {
+        unimplemented!()
+    }
+
+    async fn update_consensus_config(&self, _config: ConsensusConfig) -> Result<()> {
+        unimplemented!()
+    }
+
+    fn get_quality_config(&self) -> QuoteQualityConfig {
+        unimplemented!()
+    }
+
+    async fn update_quality_config(&self, _config: QuoteQualityConfig) -> Result<()> {
+        unimplemented!()
+    }
+
     // =========================================================================
     // Quote Import
     // =========================================================================
@@ -597,6 +623,18 @@ impl QuoteServiceTrait for MockMarketDataRepository {
     ) -> Result<Vec<QuoteImport>> {
         unimplemented!()
     }
+
+    async fn bulk_import_quotes(
+        &self,
+        _path: &std::path::Path,
+        _options: BulkImportOptions,
+    ) -> Result<BulkImportSummary> {
+        unimplemented!()
+    }
+
+    fn export_market_data(&self, _request: &QuoteExportRequest) -> Result<Vec<u8>> {
+        unimplemented!()
+    }
 }
 
 struct MockFxService {
@@ -884,6 +922,7 @@ fn create_test_snapshot(
         net_contribution_base: Decimal::ZERO,
         cash_total_account_currency: Decimal::ZERO,
         cash_total_base_currency: Decimal::ZERO,
+        cash_interest_accrued: Decimal::ZERO,
         calculated_at: Utc::now().naive_utc(),
         source: SnapshotSource::Calculated,
     }
@@ -1588,6 +1627,186 @@ fn test_history_liability_reduction_is_positive_gain() {
     assert_eq!(liability_reduction, dec!(5000));
 }
 
+fn create_loan_liability(id: &str) -> Asset {
+    let terms = LoanTerms {
+        principal: dec!(1200),
+        start_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        annual_rate: Decimal::ZERO,
+        rate_type: RateType::Fixed,
+        rate_changes: vec![],
+        term_months: 12,
+        payment_frequency: PaymentFrequency::Monthly,
+        first_payment_date: None,
+        recurring_extra_payment: None,
+        extra_payments: vec![],
+    };
+    let mut asset = create_test_asset(id, AssetKind::Liability, "USD");
+    asset.metadata = Some(serde_json::json!({
+        "sub_type": "mortgage",
+        "loan": serde_json::to_value(&terms).unwrap(),
+    }));
+    asset
+}
+
+#[test]
+fn test_history_loan_balance_follows_amortization_schedule() {
+    let d1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let d2 = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+
+    let liability = create_loan_liability("LIAB-loan");
+    // Manual valuation is ignored once loan terms are attached
+    let quotes = vec![create_test_quote("LIAB-loan", dec!(5000), d1, "USD")];
+
+    let service =
+        create_net_worth_service_with_valuations(vec![], vec![liability], vec![], quotes, vec![]);
+
+    let history = service.get_net_worth_history(d1, d2).unwrap();
+
+    let liabilities: Vec<(NaiveDate, Decimal)> = history
+        .iter()
+        .map(|p| (p.date, p.total_liabilities))
+        .collect();
+    assert_eq!(
+        liabilities,
+        vec![
+            (d1, dec!(1200)),
+            (NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(), dec!(1100)),
+            (NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), dec!(1000)),
+        ]
+    );
+    assert_eq!(history[2].net_worth, dec!(-1000));
+}
+
+#[tokio::test]
+async fn test_net_worth_uses_loan_schedule_balance() {
+    let liability = create_loan_liability("LIAB-loan");
+    let quotes = vec![create_test_quote(
+        "LIAB-loan",
+        dec!(5000),
+        NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        "USD",
+    )];
+
+    let account = create_test_account("inv1", "SECURITIES", "USD");
+    let service = create_net_worth_service(vec![account], vec![liability], vec![], quotes);
+
+    let date = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
+    let result = service.get_net_worth(date).await.unwrap();
+
+    assert_eq!(result.liabilities.total, dec!(700));
+    assert_eq!(result.net_worth, dec!(-700));
+    assert!(result.stale_assets.is_empty());
+}
+
+#[tokio::test]
+async fn test_property_equity_subtracts_linked_liabilities() {
+    let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+    let house = create_test_asset("PROP-house", AssetKind::Property, "USD");
+    let cabin = create_test_asset("PROP-cabin", AssetKind::Property, "USD");
+    let mut mortgage = create_test_asset("LIAB-mortgage", AssetKind::Liability, "USD");
+    mortgage.metadata = Some(serde_json::json!({ "linked_asset_id": "PROP-house" }));
+    let card = create_test_asset("LIAB-card", AssetKind::Liability, "USD");
+    let quotes = vec![
+        create_test_quote("PROP-house", dec!(500000), date, "USD"),
+        create_test_quote("PROP-cabin", dec!(150000), date, "USD"),
+        create_test_quote("LIAB-mortgage", dec!(320000), date, "USD"),
+        create_test_quote("LIAB-card", dec!(2000), date, "USD"),
+    ];
+
+    let account = create_test_account("inv1", "SECURITIES", "USD");
+    let service = create_net_worth_service(
+        vec![account],
+        vec![house, cabin, mortgage, card],
+        vec![],
+        quotes,
+    );
+
+    let result = service.get_net_worth(date).await.unwrap();
+
+    // Sorted by equity, largest first
+    assert_eq!(result.property_equity.len(), 2);
+    let house_equity = &result.property_equity[0];
+    assert_eq!(house_equity.asset_id, "PROP-house");
+    assert_eq!(house_equity.value, dec!(500000));
+    assert_eq!(house_equity.linked_liabilities, dec!(320000));
+    assert_eq!(house_equity.equity, dec!(180000));
+    // The unlinked card balance does not reduce any property's equity
+    let cabin_equity = &result.property_equity[1];
+    assert_eq!(cabin_equity.asset_id, "PROP-cabin");
+    assert_eq!(cabin_equity.linked_liabilities, Decimal::ZERO);
+    assert_eq!(cabin_equity.equity, dec!(150000));
+}
+
+#[test]
+fn test_history_depreciating_vehicle_loses_value_every_month() {
+    let d1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let d2 = NaiveDate::from_ymd_opt(2024, 4, 15).unwrap();
+
+    let mut car = create_test_asset("VEH-car", AssetKind::Vehicle, "USD");
+    car.metadata = Some(serde_json::json!({
+        "valuation_model": {
+            "type": "STRAIGHT_LINE",
+            "usefulLifeYears": 5,
+            "salvageValue": 0,
+        }
+    }));
+    let quotes = vec![create_test_quote("VEH-car", dec!(60000), d1, "USD")];
+
+    let service =
+        create_net_worth_service_with_valuations(vec![], vec![car], vec![], quotes, vec![]);
+
+    let history = service.get_net_worth_history(d1, d2).unwrap();
+
+    let dates: Vec<NaiveDate> = history.iter().map(|p| p.date).collect();
+    assert_eq!(
+        dates,
+        vec![
+            d1,
+            NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(),
+            d2,
+        ]
+    );
+    assert_eq!(history[0].alternative_assets_value, dec!(60000));
+    assert!(history
+        .windows(2)
+        .all(|w| w[1].alternative_assets_value < w[0].alternative_assets_value));
+    // 31 of 1827 days of useful life elapsed by Feb 1st
+    assert_eq!(history[1].alternative_assets_value, dec!(58981.94));
+}
+
+#[test]
+fn test_history_valuation_model_ignores_provider_quotes() {
+    let d1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let d2 = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+
+    let mut car = create_test_asset("VEH-car", AssetKind::Vehicle, "USD");
+    car.metadata = Some(serde_json::json!({
+        "valuation_model": {
+            "type": "STRAIGHT_LINE",
+            "usefulLifeYears": 5,
+            "salvageValue": 0,
+        }
+    }));
+    let mut synced = create_test_quote("VEH-car", dec!(10000), d1, "USD");
+    synced.id = "VEH-car-synced".to_string();
+    synced.timestamp = DateTime::from_naive_utc_and_offset(d2.and_hms_opt(16, 0, 0).unwrap(), Utc);
+    synced.data_source = DataSource::Yahoo;
+    let quotes = vec![create_test_quote("VEH-car", dec!(60000), d1, "USD"), synced];
+
+    let service =
+        create_net_worth_service_with_valuations(vec![], vec![car], vec![], quotes, vec![]);
+
+    let history = service.get_net_worth_history(d1, d2).unwrap();
+
+    // Only the manual appraisal anchors the projection
+    assert_eq!(
+        history.last().unwrap().alternative_assets_value,
+        dec!(58981.94)
+    );
+}
+
 #[test]
 fn test_history_contribution_adjusted_gain() {
     // Verify that deposits don't count as gain
@@ -4,8 +4,9 @@
 //! Only external flows (money crossing the portfolio boundary) affect TWR.
 
 use crate::activities::{
-    Activity, ACTIVITY_SUBTYPE_BONUS, ACTIVITY_TYPE_CREDIT, ACTIVITY_TYPE_DEPOSIT,
-    ACTIVITY_TYPE_TRANSFER_IN, ACTIVITY_TYPE_TRANSFER_OUT, ACTIVITY_TYPE_WITHDRAWAL,
+    Activity, ACTIVITY_SUBTYPE_BONUS, ACTIVITY_SUBTYPE_FX_CONVERSION, ACTIVITY_TYPE_CREDIT,
+    ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_TRANSFER_IN, ACTIVITY_TYPE_TRANSFER_OUT,
+    ACTIVITY_TYPE_WITHDRAWAL,
 };
 
 fn is_external_transfer(activity: &Activity) -> bool {
@@ -48,6 +49,7 @@ pub enum PerformanceScope {
 /// Internal flows:
 /// - BUY, SELL, DIVIDEND, INTEREST, SPLIT (asset reallocation)
 /// - TRANSFER_IN, TRANSFER_OUT (money moving between accounts)
+/// - TRANSFER_OUT with subtype FX_CONVERSION (cash changing currency, at any scope)
 /// - FEE, TAX (deductions from existing money)
 /// - CREDIT with other subtypes (REBATE, REFUND = not new money)
 pub fn classify_flow_for_scope(activity: &Activity, scope: PerformanceScope) -> FlowType {
@@ -69,6 +71,13 @@ pub fn classify_flow_for_scope(activity: &Activity, scope: PerformanceScope) ->
         };
     }
 
+    // FX conversions keep the cash inside the account
+    if effective_type == ACTIVITY_TYPE_TRANSFER_OUT
+        && activity.subtype.as_deref() == Some(ACTIVITY_SUBTYPE_FX_CONVERSION)
+    {
+        return FlowType::Internal;
+    }
+
     // TRANSFER_*: can be external when explicitly marked as portfolio-boundary flow.
     if effective_type == ACTIVITY_TYPE_TRANSFER_IN || effective_type == ACTIVITY_TYPE_TRANSFER_OUT {
         return match scope {
@@ -208,6 +217,16 @@ mod tests {
         assert_eq!(classify_flow(&activity), FlowType::Internal);
     }
 
+    #[test]
+    fn test_fx_conversion_is_internal_for_account_scope() {
+        let mut activity = create_test_activity("TRANSFER_OUT");
+        activity.subtype = Some("FX_CONVERSION".to_string());
+        assert_eq!(
+            classify_flow_for_scope(&activity, PerformanceScope::Account),
+            FlowType::Internal
+        );
+    }
+
     #[test]
     fn test_transfer_in_is_external_for_account_scope() {
         let activity = create_test_activity("TRANSFER_IN");
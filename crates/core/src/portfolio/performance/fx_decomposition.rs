@@ -0,0 +1,410 @@
+//! FX return decomposition.
+//!
+//! Splits the return of an account, measured in its own currency, into the part
+//! caused by price moves in each asset's quote currency and the part caused by
+//! those currencies moving against the account currency.
+
+use crate::constants::DECIMAL_PRECISION;
+use crate::fx::currency::{normalize_amount, normalize_currency_code};
+use crate::portfolio::snapshot::{AccountStateSnapshot, Lot};
+use crate::quotes::Quote;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+
+use super::{FxReturnDecomposition, HoldingFxDecomposition};
+
+/// Quotes keyed by date, then by asset id.
+pub type QuotesByDate = HashMap<NaiveDate, HashMap<String, Quote>>;
+
+struct AccountFx<F> {
+    currency: String,
+    rate: F,
+}
+
+impl<F: FnMut(&str, NaiveDate) -> Option<Decimal>> AccountFx<F> {
+    fn rate_to_account(&mut self, currency: &str, date: NaiveDate) -> Option<Decimal> {
+        if currency == self.currency {
+            Some(Decimal::ONE)
+        } else {
+            (self.rate)(currency, date)
+        }
+    }
+
+    /// Rate a lot was bought at. Lots converted at purchase carry the rate paid
+    /// (activity to position currency); it is assumed the purchase was settled in
+    /// the account currency, so its inverse is the position-to-account rate.
+    /// Other lots use the market rate of the acquisition date.
+    fn acquisition_rate(&mut self, lot: &Lot, currency: &str) -> Option<Decimal> {
+        if currency == self.currency {
+            return Some(Decimal::ONE);
+        }
+        match lot.fx_rate_to_position {
+            Some(rate) if rate > Decimal::ZERO => Some(Decimal::ONE / rate),
+            _ => self.rate_to_account(currency, lot.acquisition_date.date_naive()),
+        }
+    }
+}
+
+fn local_price<'a>(
+    quotes_by_date: &'a QuotesByDate,
+    date: NaiveDate,
+    asset_id: &str,
+) -> Option<(Decimal, &'a str)> {
+    quotes_by_date
+        .get(&date)?
+        .get(asset_id)
+        .map(|q| normalize_amount(q.close, &q.currency))
+}
+
+/// Decomposes the return over consecutive daily `snapshots` into local and currency effects.
+///
+/// `rate(currency, date)` returns the rate converting `currency` into the account
+/// currency of the snapshots. Holdings without quotes or rates on both ends of a
+/// day are left out of that day. Alternative assets are excluded, as in TWR.
+pub fn decompose_fx_returns(
+    snapshots: &[AccountStateSnapshot],
+    quotes_by_date: &QuotesByDate,
+    rate: impl FnMut(&str, NaiveDate) -> Option<Decimal>,
+) -> FxReturnDecomposition {
+    let Some(last) = snapshots.last() else {
+        return FxReturnDecomposition::default();
+    };
+    let mut fx = AccountFx {
+        currency: normalize_currency_code(&last.currency).to_string(),
+        rate,
+    };
+
+    let mut holdings: BTreeMap<String, HoldingFxDecomposition> = BTreeMap::new();
+    let mut local_amount = Decimal::ZERO;
+    let mut currency_amount = Decimal::ZERO;
+    let mut realized_fx_gain = Decimal::ZERO;
+    let mut cumulative_local = Decimal::ONE;
+    let mut cumulative_total = Decimal::ONE;
+
+    for window in snapshots.windows(2) {
+        let (prev, curr) = (&window[0], &window[1]);
+        let (d0, d1) = (prev.snapshot_date, curr.snapshot_date);
+        let mut start_value = Decimal::ZERO;
+        let mut local_day = Decimal::ZERO;
+        let mut currency_day = Decimal::ZERO;
+
+        for (asset_id, position) in &prev.positions {
+            if position.quantity.is_zero() || position.is_alternative {
+                continue;
+            }
+            let (Some((p0, currency)), Some((p1, _))) = (
+                local_price(quotes_by_date, d0, asset_id),
+                local_price(quotes_by_date, d1, asset_id),
+            ) else {
+                continue;
+            };
+            let (Some(x0), Some(x1)) = (
+                fx.rate_to_account(currency, d0),
+                fx.rate_to_account(currency, d1),
+            ) else {
+                continue;
+            };
+
+            let q = position.quantity;
+            let local = q * (p1 - p0) * x0;
+            let currency_effect = q * p1 * (x1 - x0);
+            start_value += q * p0 * x0;
+            local_day += local;
+            currency_day += currency_effect;
+
+            let holding =
+                holdings
+                    .entry(asset_id.clone())
+                    .or_insert_with(|| HoldingFxDecomposition {
+                        asset_id: asset_id.clone(),
+                        currency: currency.to_string(),
+                        ..Default::default()
+                    });
+            holding.local_return_amount += local;
+            holding.currency_return_amount += currency_effect;
+        }
+
+        for (cash_currency, amount) in &prev.cash_balances {
+            let (amount, currency) = normalize_amount(*amount, cash_currency);
+            let (Some(x0), Some(x1)) = (
+                fx.rate_to_account(currency, d0),
+                fx.rate_to_account(currency, d1),
+            ) else {
+                continue;
+            };
+            start_value += amount * x0;
+            currency_day += amount * (x1 - x0);
+        }
+
+        // Lots that shrank or disappeared were disposed of on d1
+        for (asset_id, position) in &prev.positions {
+            let remaining: HashMap<&str, Decimal> = curr
+                .positions
+                .get(asset_id)
+                .map(|p| p.lots.iter().map(|l| (l.id.as_str(), l.quantity)).collect())
+                .unwrap_or_default();
+            for lot in &position.lots {
+                let disposed =
+                    lot.quantity - remaining.get(lot.id.as_str()).copied().unwrap_or_default();
+                if disposed <= Decimal::ZERO || lot.quantity.is_zero() {
+                    continue;
+                }
+                let (cost, currency) =
+                    normalize_amount(lot.cost_basis * disposed / lot.quantity, &position.currency);
+                let (Some(acquired_at), Some(x1)) = (
+                    fx.acquisition_rate(lot, currency),
+                    fx.rate_to_account(currency, d1),
+                ) else {
+                    continue;
+                };
+                let gain = cost * (x1 - acquired_at);
+                realized_fx_gain += gain;
+                holdings
+                    .entry(asset_id.clone())
+                    .or_insert_with(|| HoldingFxDecomposition {
+                        asset_id: asset_id.clone(),
+                        currency: currency.to_string(),
+                        ..Default::default()
+                    })
+                    .realized_fx_gain += gain;
+            }
+        }
+
+        local_amount += local_day;
+        currency_amount += currency_day;
+        if start_value > Decimal::ZERO {
+            cumulative_local *= Decimal::ONE + local_day / start_value;
+            cumulative_total *= Decimal::ONE + (local_day + currency_day) / start_value;
+        }
+    }
+
+    // Unrealized gains of the positions still open at the end, split at acquisition rates
+    let end_date = last.snapshot_date;
+    let mut unrealized_fx_gain = Decimal::ZERO;
+    for (asset_id, position) in &last.positions {
+        if position.quantity.is_zero() || position.is_alternative {
+            continue;
+        }
+        let Some((price, quote_currency)) = local_price(quotes_by_date, end_date, asset_id) else {
+            continue;
+        };
+        let Some(quote_rate) = fx.rate_to_account(quote_currency, end_date) else {
+            continue;
+        };
+        let position_currency = normalize_currency_code(&position.currency);
+        let Some(end_rate) = fx.rate_to_account(position_currency, end_date) else {
+            continue;
+        };
+
+        let mut cost_at_acquisition = Decimal::ZERO;
+        let mut cost_at_end = Decimal::ZERO;
+        for lot in &position.lots {
+            let (cost, currency) = normalize_amount(lot.cost_basis, &position.currency);
+            let acquired_at = fx.acquisition_rate(lot, currency).unwrap_or(end_rate);
+            cost_at_acquisition += cost * acquired_at;
+            cost_at_end += cost * end_rate;
+        }
+        let market_value = position.quantity * price * quote_rate;
+
+        let holding = holdings
+            .entry(asset_id.clone())
+            .or_insert_with(|| HoldingFxDecomposition {
+                asset_id: asset_id.clone(),
+                currency: quote_currency.to_string(),
+                ..Default::default()
+            });
+        holding.unrealized_local_gain = market_value - cost_at_end;
+        holding.unrealized_fx_gain = cost_at_end - cost_at_acquisition;
+        unrealized_fx_gain += holding.unrealized_fx_gain;
+    }
+
+    let currency_return = if cumulative_local.is_zero() {
+        Decimal::ZERO
+    } else {
+        cumulative_total / cumulative_local - Decimal::ONE
+    };
+
+    FxReturnDecomposition {
+        local_return: (cumulative_local - Decimal::ONE).round_dp(DECIMAL_PRECISION),
+        currency_return: currency_return.round_dp(DECIMAL_PRECISION),
+        local_return_amount: local_amount.round_dp(DECIMAL_PRECISION),
+        currency_return_amount: currency_amount.round_dp(DECIMAL_PRECISION),
+        realized_fx_gain: realized_fx_gain.round_dp(DECIMAL_PRECISION),
+        unrealized_fx_gain: unrealized_fx_gain.round_dp(DECIMAL_PRECISION),
+        holdings: holdings
+            .into_values()
+            .map(|h| HoldingFxDecomposition {
+                local_return_amount: h.local_return_amount.round_dp(DECIMAL_PRECISION),
+                currency_return_amount: h.currency_return_amount.round_dp(DECIMAL_PRECISION),
+                unrealized_local_gain: h.unrealized_local_gain.round_dp(DECIMAL_PRECISION),
+                unrealized_fx_gain: h.unrealized_fx_gain.round_dp(DECIMAL_PRECISION),
+                realized_fx_gain: h.realized_fx_gain.round_dp(DECIMAL_PRECISION),
+                ..h
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::snapshot::Position;
+    use chrono::{TimeZone, Utc};
+    use rust_decimal_macros::dec;
+    use std::collections::VecDeque;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, d).unwrap()
+    }
+
+    fn lot(id: &str, quantity: Decimal, cost_basis: Decimal, fx: Option<Decimal>) -> Lot {
+        Lot {
+            id: id.to_string(),
+            position_id: "POS".to_string(),
+            acquisition_date: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            quantity,
+            cost_basis,
+            acquisition_price: cost_basis / quantity,
+            acquisition_fees: Decimal::ZERO,
+            fx_rate_to_position: fx,
+        }
+    }
+
+    fn snapshot(day: u32, lots: Vec<Lot>, cash: &[(&str, Decimal)]) -> AccountStateSnapshot {
+        let mut positions = HashMap::new();
+        if !lots.is_empty() {
+            positions.insert(
+                "AAPL".to_string(),
+                Position {
+                    asset_id: "AAPL".to_string(),
+                    currency: "USD".to_string(),
+                    quantity: lots.iter().map(|l| l.quantity).sum(),
+                    total_cost_basis: lots.iter().map(|l| l.cost_basis).sum(),
+                    lots: VecDeque::from(lots),
+                    ..Default::default()
+                },
+            );
+        }
+        AccountStateSnapshot {
+            account_id: "acc".to_string(),
+            snapshot_date: date(day),
+            currency: "EUR".to_string(),
+            positions,
+            cash_balances: cash.iter().map(|(c, a)| (c.to_string(), *a)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn quotes(prices: &[(u32, Decimal)]) -> QuotesByDate {
+        prices
+            .iter()
+            .map(|(day, close)| {
+                let quote = Quote {
+                    asset_id: "AAPL".to_string(),
+                    close: *close,
+                    currency: "USD".to_string(),
+                    ..Default::default()
+                };
+                (date(*day), HashMap::from([("AAPL".to_string(), quote)]))
+            })
+            .collect()
+    }
+
+    /// USD -> EUR: 0.90 on day 1, 1.00 on day 2.
+    fn usd_eur(_: &str, d: NaiveDate) -> Option<Decimal> {
+        Some(if d == date(1) { dec!(0.90) } else { dec!(1.00) })
+    }
+
+    #[test]
+    fn test_daily_effects_add_up_to_market_move() {
+        let snapshots = vec![
+            snapshot(1, vec![lot("l1", dec!(10), dec!(1000), None)], &[]),
+            snapshot(2, vec![lot("l1", dec!(10), dec!(1000), None)], &[]),
+        ];
+        let result = decompose_fx_returns(
+            &snapshots,
+            &quotes(&[(1, dec!(100)), (2, dec!(110))]),
+            usd_eur,
+        );
+
+        // 10 * 10 USD * 0.90 = 90 EUR from price, 10 * 110 * 0.10 = 110 EUR from FX
+        assert_eq!(result.local_return_amount, dec!(90));
+        assert_eq!(result.currency_return_amount, dec!(110));
+        // Start value 900 EUR, end value 1100 EUR
+        assert_eq!(result.local_return, dec!(0.1));
+        let total = (Decimal::ONE + result.local_return) * (Decimal::ONE + result.currency_return);
+        assert!((total - dec!(1100) / dec!(900)).abs() < dec!(0.000001));
+        assert_eq!(result.holdings.len(), 1);
+        assert_eq!(result.holdings[0].currency, "USD");
+    }
+
+    #[test]
+    fn test_foreign_cash_only_has_currency_effect() {
+        let snapshots = vec![
+            snapshot(1, vec![], &[("USD", dec!(1000)), ("EUR", dec!(500))]),
+            snapshot(2, vec![], &[("USD", dec!(1000)), ("EUR", dec!(500))]),
+        ];
+        let result = decompose_fx_returns(&snapshots, &HashMap::new(), usd_eur);
+
+        assert_eq!(result.local_return_amount, Decimal::ZERO);
+        assert_eq!(result.currency_return_amount, dec!(100));
+        assert!(result.holdings.is_empty());
+    }
+
+    #[test]
+    fn test_unrealized_fx_gain_uses_lot_rate() {
+        // Bought with EUR at 1.25 USD per EUR, i.e. 0.80 EUR per USD
+        let snapshots = vec![
+            snapshot(
+                1,
+                vec![lot("l1", dec!(10), dec!(1000), Some(dec!(1.25)))],
+                &[],
+            ),
+            snapshot(
+                2,
+                vec![lot("l1", dec!(10), dec!(1000), Some(dec!(1.25)))],
+                &[],
+            ),
+        ];
+        let result = decompose_fx_returns(
+            &snapshots,
+            &quotes(&[(1, dec!(100)), (2, dec!(120))]),
+            usd_eur,
+        );
+
+        let holding = &result.holdings[0];
+        // 1000 USD cost: 800 EUR at purchase, 1000 EUR today
+        assert_eq!(holding.unrealized_fx_gain, dec!(200));
+        // 1200 USD value vs 1000 USD cost at today's rate
+        assert_eq!(holding.unrealized_local_gain, dec!(200));
+        assert_eq!(result.unrealized_fx_gain, dec!(200));
+    }
+
+    #[test]
+    fn test_disposed_lot_realizes_fx_gain() {
+        let snapshots = vec![
+            snapshot(
+                1,
+                vec![
+                    lot("l1", dec!(10), dec!(1000), None),
+                    lot("l2", dec!(5), dec!(500), None),
+                ],
+                &[],
+            ),
+            snapshot(2, vec![lot("l2", dec!(5), dec!(500), None)], &[]),
+        ];
+        let result = decompose_fx_returns(
+            &snapshots,
+            &quotes(&[(1, dec!(100)), (2, dec!(100))]),
+            usd_eur,
+        );
+
+        // l1 cost 1000 USD acquired at 0.90 (day 1 market rate), disposed at 1.00
+        assert_eq!(result.realized_fx_gain, dec!(100));
+        assert_eq!(result.holdings[0].realized_fx_gain, dec!(100));
+        // Remaining lot only carries unrealized FX
+        assert_eq!(result.unrealized_fx_gain, dec!(50));
+    }
+}
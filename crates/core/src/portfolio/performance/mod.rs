@@ -1,4 +1,5 @@
 mod flow_classifier;
+mod fx_decomposition;
 pub mod performance_model;
 pub mod performance_service;
 
@@ -7,5 +8,6 @@ pub use flow_classifier::{
     classify_flow_for_scope, is_external_flow, is_external_flow_for_scope, FlowType,
     PerformanceScope,
 };
+pub use fx_decomposition::{decompose_fx_returns, QuotesByDate};
 pub use performance_model::*;
 pub use performance_service::*;
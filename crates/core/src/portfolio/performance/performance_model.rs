@@ -62,6 +62,48 @@ pub struct PerformanceMetrics {
     /// Indicates if this is a HOLDINGS mode account (no cash flow tracking)
     #[serde(default)]
     pub is_holdings_mode: bool,
+    /// Split of the return into local price and currency effects (account performance only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fx_decomposition: Option<FxReturnDecomposition>,
+}
+
+/// Local price vs currency effect of a single holding, in the metrics currency.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldingFxDecomposition {
+    pub asset_id: String,
+    /// Currency the asset is quoted in
+    pub currency: String,
+    /// Gain from price moves in the quote currency over the period
+    pub local_return_amount: Decimal,
+    /// Gain from the quote currency moving against the metrics currency over the period
+    pub currency_return_amount: Decimal,
+    /// Unrealized gain at period end from price moves since acquisition
+    pub unrealized_local_gain: Decimal,
+    /// Unrealized gain at period end from FX moves since acquisition
+    pub unrealized_fx_gain: Decimal,
+    /// FX part of gains realized by disposals within the period
+    pub realized_fx_gain: Decimal,
+}
+
+/// Decomposition of a period return into local price return and currency return.
+///
+/// Daily effects are attributed on beginning-of-day holdings:
+/// `local = q * (P1 - P0) * X0` and `currency = q * P1 * (X1 - X0)`, so both
+/// amounts add up to the market move with no residual. Rates are linked
+/// geometrically: `(1 + local_return) * (1 + currency_return) - 1` is the
+/// cumulative market return, excluding cash flows.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FxReturnDecomposition {
+    pub local_return: Decimal,
+    pub currency_return: Decimal,
+    pub local_return_amount: Decimal,
+    /// Includes the revaluation of cash held in foreign currencies
+    pub currency_return_amount: Decimal,
+    pub realized_fx_gain: Decimal,
+    pub unrealized_fx_gain: Decimal,
+    pub holdings: Vec<HoldingFxDecomposition>,
 }
 
 // This struct now only holds the calculated performance metrics.
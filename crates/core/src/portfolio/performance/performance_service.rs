@@ -1,14 +1,17 @@
 use crate::accounts::TrackingMode;
 use crate::constants::{DECIMAL_PRECISION, PORTFOLIO_TOTAL_ACCOUNT_ID};
 use crate::errors::{self, Result, ValidationError};
+use crate::fx::currency::normalize_currency_code;
+use crate::fx::FxServiceTrait;
 use crate::performance::ReturnData;
+use crate::portfolio::snapshot::SnapshotServiceTrait;
 use crate::quotes::QuoteServiceTrait;
 use crate::utils::time_utils::valuation_date_today;
 use crate::valuation::ValuationServiceTrait;
 
 use async_trait::async_trait;
 use chrono::{Duration, NaiveDate};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use log::{debug, warn};
@@ -16,7 +19,10 @@ use rust_decimal::Decimal;
 use rust_decimal::MathematicalOps;
 use rust_decimal_macros::dec;
 
-use super::{PerformanceMetrics, SimplePerformanceMetrics};
+use super::{
+    decompose_fx_returns, FxReturnDecomposition, PerformanceMetrics, QuotesByDate,
+    SimplePerformanceMetrics,
+};
 use crate::portfolio::valuation::DailyAccountValuation;
 
 #[async_trait]
@@ -51,6 +57,8 @@ pub trait PerformanceServiceTrait: Send + Sync {
 pub struct PerformanceService {
     valuation_service: Arc<dyn ValuationServiceTrait + Send + Sync>,
     quote_service: Arc<dyn QuoteServiceTrait + Send + Sync>,
+    snapshot_service: Arc<dyn SnapshotServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
 }
 
 const TRADING_DAYS_PER_YEAR: u32 = 252;
@@ -61,13 +69,92 @@ impl PerformanceService {
     pub fn new(
         valuation_service: Arc<dyn ValuationServiceTrait + Send + Sync>,
         quote_service: Arc<dyn QuoteServiceTrait + Send + Sync>,
+        snapshot_service: Arc<dyn SnapshotServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
     ) -> Self {
         Self {
             valuation_service,
             quote_service,
+            snapshot_service,
+            fx_service,
         }
     }
 
+    /// Splits the account return into local price and currency effects.
+    /// Failures are logged and leave the decomposition out of the metrics.
+    fn calculate_fx_decomposition(
+        &self,
+        account_id: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Option<FxReturnDecomposition> {
+        match self.try_calculate_fx_decomposition(account_id, start_date, end_date) {
+            Ok(decomposition) => Some(decomposition),
+            Err(e) => {
+                warn!(
+                    "FX decomposition for account '{}' failed: {}. Skipping it.",
+                    account_id, e
+                );
+                None
+            }
+        }
+    }
+
+    fn try_calculate_fx_decomposition(
+        &self,
+        account_id: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<FxReturnDecomposition> {
+        let snapshots = self.snapshot_service.get_daily_holdings_snapshots(
+            account_id,
+            Some(start_date),
+            Some(end_date),
+        )?;
+        let Some(account_currency) = snapshots
+            .last()
+            .map(|s| normalize_currency_code(&s.currency).to_string())
+        else {
+            return Ok(FxReturnDecomposition::default());
+        };
+
+        let asset_ids: HashSet<String> = snapshots
+            .iter()
+            .flat_map(|s| s.positions.keys().cloned())
+            .collect();
+        let mut quotes_by_date: QuotesByDate = HashMap::new();
+        for quote in self
+            .quote_service
+            .get_quotes_in_range_filled(&asset_ids, start_date, end_date)?
+        {
+            quotes_by_date
+                .entry(quote.timestamp.date_naive())
+                .or_default()
+                .insert(quote.asset_id.clone(), quote);
+        }
+
+        let mut rates: HashMap<(String, NaiveDate), Option<Decimal>> = HashMap::new();
+        Ok(decompose_fx_returns(
+            &snapshots,
+            &quotes_by_date,
+            |currency, date| {
+                *rates
+                    .entry((currency.to_string(), date))
+                    .or_insert_with(|| {
+                        self.fx_service
+                            .get_exchange_rate_for_date(currency, &account_currency, date)
+                            .map_err(|e| {
+                                debug!(
+                                    "No FX rate {}->{} on {}: {}",
+                                    currency, account_currency, date, e
+                                )
+                            })
+                            .ok()
+                    })
+            },
+        ))
+    }
+
     fn get_account_boundary_data(
         &self,
         account_id: &str,
@@ -347,6 +434,11 @@ impl PerformanceService {
             volatility: volatility.round_dp(DECIMAL_PRECISION),
             max_drawdown: max_drawdown.round_dp(DECIMAL_PRECISION),
             is_holdings_mode,
+            fx_decomposition: self.calculate_fx_decomposition(
+                account_id,
+                actual_start_date,
+                actual_end_date,
+            ),
         };
 
         Ok(result)
@@ -452,6 +544,7 @@ impl PerformanceService {
             volatility: Decimal::ZERO,
             max_drawdown: Decimal::ZERO,
             is_holdings_mode,
+            fx_decomposition: None,
         };
 
         Ok(result)
@@ -595,6 +688,7 @@ impl PerformanceService {
             volatility: volatility.round_dp(DECIMAL_PRECISION),
             max_drawdown: max_drawdown.round_dp(DECIMAL_PRECISION),
             is_holdings_mode: false,
+            fx_decomposition: None,
         };
 
         Ok(result)
@@ -619,6 +713,7 @@ impl PerformanceService {
             volatility: Decimal::ZERO,
             max_drawdown: Decimal::ZERO,
             is_holdings_mode: false,
+            fx_decomposition: None,
         }
     }
 
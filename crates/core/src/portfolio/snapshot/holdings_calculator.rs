@@ -1,4 +1,7 @@
-use crate::activities::{Activity, ActivityType};
+use crate::activities::{
+    Activity, ActivityCompiler, ActivityType, DefaultActivityCompiler,
+    ACTIVITY_SUBTYPE_FX_CONVERSION, ACTIVITY_SUBTYPE_HARD_FORK, ACTIVITY_TYPE_TRANSFER_OUT,
+};
 use crate::assets::AssetRepositoryTrait;
 use crate::errors::{CalculatorError, Error, Result};
 use crate::fx::FxServiceTrait;
@@ -6,14 +9,30 @@ use crate::portfolio::snapshot::AccountStateSnapshot;
 use crate::portfolio::snapshot::HoldingsCalculationResult;
 use crate::portfolio::snapshot::HoldingsCalculationWarning;
 use crate::portfolio::snapshot::Position;
+use crate::portfolio::snapshot::SnapshotService;
 
 use chrono::{DateTime, NaiveDate, Utc};
 use log::{debug, error, warn};
 use rust_decimal::Decimal;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 
+/// Currency and amount bought by a compiled FX_CONVERSION posting.
+fn fx_conversion_bought(activity: &Activity) -> Option<(String, Decimal)> {
+    let to_currency = activity
+        .get_meta::<String>("to_currency")
+        .filter(|c| !c.is_empty())?;
+    Some((to_currency, activity.get_meta_decimal("to_amount")?))
+}
+
+/// An FX_CONVERSION posting of which only the amount sold can be booked.
+fn is_partial_fx_conversion(activity: &Activity) -> bool {
+    activity.effective_type() == ACTIVITY_TYPE_TRANSFER_OUT
+        && activity.subtype.as_deref() == Some(ACTIVITY_SUBTYPE_FX_CONVERSION)
+        && fx_conversion_bought(activity).is_none()
+}
+
 /// Helper function for cash mutations.
 /// Books cash in the specified currency (should be activity.currency per design spec).
 #[inline]
@@ -99,6 +118,18 @@ impl HoldingsCalculator {
                 &account_currency,
                 &mut asset_currency_cache,
             ) {
+                Ok(_) if is_partial_fx_conversion(activity) => {
+                    let warning = HoldingsCalculationWarning {
+                        activity_id: activity.id.clone(),
+                        account_id: next_state.account_id.clone(),
+                        date: target_date,
+                        message: "FX conversion has no to_currency or to_amount. \
+                                  Only the amount sold was booked."
+                            .to_string(),
+                    };
+                    warn!("{}", warning);
+                    warnings.push(warning);
+                }
                 Ok(_) => {} // Activity processed successfully
                 Err(e) => {
                     let warning = HoldingsCalculationWarning {
@@ -167,6 +198,44 @@ impl HoldingsCalculator {
         ))
     }
 
+    /// Books an account's whole activity history from an empty state and returns
+    /// the final holdings, preprocessing activities as the snapshot service does
+    /// (compiled into postings, adjusted for splits).
+    ///
+    /// Used to preview the effect of activity changes without storing snapshots.
+    pub fn replay(
+        &self,
+        account_id: &str,
+        currency: &str,
+        activities: &[Activity],
+    ) -> Result<HoldingsCalculationResult> {
+        let compiled = DefaultActivityCompiler::new().compile_all(activities)?;
+        let split_factors =
+            SnapshotService::calculate_split_factors(&compiled, NaiveDate::MIN, NaiveDate::MAX);
+        let adjusted = SnapshotService::adjust_activities_for_splits(&compiled, &split_factors);
+
+        let mut by_date: BTreeMap<NaiveDate, Vec<Activity>> = BTreeMap::new();
+        for activity in adjusted {
+            by_date
+                .entry(activity.activity_date.naive_utc().date())
+                .or_default()
+                .push(activity);
+        }
+
+        let mut state = AccountStateSnapshot {
+            account_id: account_id.to_string(),
+            currency: currency.to_string(),
+            ..Default::default()
+        };
+        let mut warnings = Vec::new();
+        for (date, activities_today) in &by_date {
+            let result = self.calculate_next_holdings(&state, activities_today, *date)?;
+            state = result.snapshot;
+            warnings.extend(result.warnings);
+        }
+        Ok(HoldingsCalculationResult::with_warnings(state, warnings))
+    }
+
     /// Processes a single activity, updating positions, cash, and net_deposit.
     /// Books cash in ACTIVITY currency (not account currency) per design spec.
     /// Uses asset_currency_cache to avoid repeated DB lookups for asset currencies and kind info.
@@ -203,6 +272,10 @@ impl HoldingsCalculator {
                 self.handle_transfer_in(activity, state, account_currency, asset_currency_cache)
             }
             ActivityType::TransferOut => {
+                if activity.subtype.as_deref() == Some(ACTIVITY_SUBTYPE_FX_CONVERSION) {
+                    // Cash stays in the account: no net_contribution change.
+                    return self.handle_fx_conversion(activity, state);
+                }
                 // Transfers always affect account-level net_contribution.
                 self.handle_transfer_out(activity, state, account_currency, asset_currency_cache)
             }
@@ -210,8 +283,12 @@ impl HoldingsCalculator {
             ActivityType::Adjustment => {
                 // ADJUSTMENT: Non-trade correction / transformation (usually no cash movement)
                 // Examples: option expire worthless, RoC basis adjustment, merger/spinoff
-                // Currently just skip - specific handling will be added as needed
-                Ok(())
+                // Only hard forks are handled so far - others are skipped
+                if activity.subtype.as_deref() == Some(ACTIVITY_SUBTYPE_HARD_FORK) {
+                    self.handle_hard_fork(activity, state, asset_currency_cache)
+                } else {
+                    Ok(())
+                }
             }
             ActivityType::Unknown => {
                 warn!(
@@ -727,6 +804,105 @@ impl HoldingsCalculator {
         Ok(())
     }
 
+    /// Handle TRANSFER_OUT/FX_CONVERSION posting.
+    /// Books the amount sold plus fee out of the ACTIVITY currency and the amount
+    /// bought into `to_currency`. No net_contribution change.
+    /// A conversion that does not say what was bought only books the amount sold.
+    fn handle_fx_conversion(
+        &self,
+        activity: &Activity,
+        state: &mut AccountStateSnapshot,
+    ) -> Result<()> {
+        add_cash(
+            state,
+            &activity.currency,
+            -(activity.amt() + activity.fee_amt()),
+        );
+
+        if let Some((to_currency, to_amount)) = fx_conversion_bought(activity) {
+            add_cash(state, &to_currency, to_amount);
+        }
+        Ok(())
+    }
+
+    /// Handle ADJUSTMENT/HARD_FORK posting.
+    /// Moves `cost_basis_ratio` of the parent coin's cost basis to a new lot of the
+    /// forked coin. No cash movement and no net_contribution change.
+    fn handle_hard_fork(
+        &self,
+        activity: &Activity,
+        state: &mut AccountStateSnapshot,
+        asset_currency_cache: &mut HashMap<String, (String, bool)>,
+    ) -> Result<()> {
+        let parent_asset_id = activity.asset_id.as_deref().unwrap_or("");
+        let Some(received_asset_id) = activity.get_meta::<String>("received_asset_id") else {
+            warn!("Hard fork {} has no received asset. Skipping.", activity.id);
+            return Ok(());
+        };
+        let ratio = activity
+            .get_meta_decimal("cost_basis_ratio")
+            .unwrap_or(Decimal::ZERO);
+
+        let (basis_moved, parent_currency) = match state.positions.get_mut(parent_asset_id) {
+            Some(parent) => (
+                parent.split_cost_basis(ratio, &activity.id)?,
+                parent.currency.clone(),
+            ),
+            None => {
+                warn!(
+                    "Hard fork {} of non-existent position {}. Forked coins get zero cost basis.",
+                    activity.id, parent_asset_id
+                );
+                (Decimal::ZERO, activity.currency.clone())
+            }
+        };
+
+        let position = self.get_or_create_position_mut_cached(
+            state,
+            &received_asset_id,
+            &activity.currency,
+            activity.activity_date,
+            asset_currency_cache,
+        )?;
+
+        let basis = if basis_moved.is_zero() || position.currency == parent_currency {
+            basis_moved
+        } else {
+            match self.fx_service.convert_currency_for_date(
+                basis_moved,
+                &parent_currency,
+                &position.currency,
+                activity.activity_date.naive_utc().date(),
+            ) {
+                Ok(converted) => converted,
+                Err(e) => {
+                    warn!(
+                        "Holdings Calc (HardFork {}): Failed conversion {}->{}: {}. Using original amount.",
+                        activity.id, parent_currency, position.currency, e
+                    );
+                    basis_moved
+                }
+            }
+        };
+
+        let quantity = activity.qty();
+        let unit_price = if quantity.is_zero() {
+            Decimal::ZERO
+        } else {
+            basis / quantity
+        };
+        position.add_lot_values(
+            activity.id.clone(),
+            quantity,
+            unit_price,
+            Decimal::ZERO,
+            activity.activity_date,
+            None,
+        )?;
+
+        Ok(())
+    }
+
     /// Converts an amount from activity currency to account currency.
     /// If the activity has a valid fx_rate (Some and not zero), uses it directly.
     /// Otherwise, falls back to the FxService for conversion.
@@ -1,7 +1,10 @@
 // Test cases for HoldingsCalculator will go here.
 #[cfg(test)]
 mod tests {
-    use crate::activities::{Activity, ActivityStatus, ActivityType};
+    use crate::activities::{
+        Activity, ActivityStatus, ActivityType, ACTIVITY_SUBTYPE_FX_CONVERSION,
+        ACTIVITY_SUBTYPE_HARD_FORK,
+    };
     use crate::assets::{
         Asset, AssetKind, AssetRepositoryTrait, NewAsset, QuoteMode, UpdateAssetProfile,
     };
@@ -464,6 +467,7 @@ mod tests {
             net_contribution_base: Decimal::ZERO,
             cash_total_account_currency: Decimal::ZERO,
             cash_total_base_currency: Decimal::ZERO,
+            cash_interest_accrued: Decimal::ZERO,
             source: SnapshotSource::Calculated,
         }
     }
@@ -3616,4 +3620,210 @@ mod tests {
             "Cash should be booked in activity currency (USD)"
         );
     }
+
+    #[test]
+    fn test_hard_fork_moves_cost_basis_to_forked_coin() {
+        let mock_fx_service = Arc::new(MockFxService::new());
+        let account_currency = "USD";
+        let base_currency = Arc::new(RwLock::new(account_currency.to_string()));
+        let calculator = create_calculator(mock_fx_service, base_currency);
+
+        let previous_snapshot = create_initial_snapshot("acc_1", account_currency, "2017-07-31");
+
+        // Day 1: buy 2 BTC @ 2,500 USD
+        let buy_activity = create_default_activity(
+            "act_buy_btc",
+            ActivityType::Buy,
+            "BTC",
+            dec!(2),
+            dec!(2500),
+            dec!(0),
+            account_currency,
+            "2017-07-31",
+        );
+        let after_buy = calculator
+            .calculate_next_holdings(
+                &previous_snapshot,
+                &[buy_activity],
+                NaiveDate::from_str("2017-07-31").unwrap(),
+            )
+            .unwrap()
+            .snapshot;
+
+        // Day 2: fork yields 2 BCH, 10% of the BTC cost basis moves to BCH
+        let mut fork_activity = create_default_activity(
+            "act_fork:fork",
+            ActivityType::Adjustment,
+            "BTC",
+            dec!(2),
+            dec!(0),
+            dec!(0),
+            account_currency,
+            "2017-08-01",
+        );
+        fork_activity.subtype = Some(ACTIVITY_SUBTYPE_HARD_FORK.to_string());
+        fork_activity.metadata = Some(serde_json::json!({
+            "received_asset_id": "BCH",
+            "cost_basis_ratio": 0.1
+        }));
+
+        let next_state = calculator
+            .calculate_next_holdings(
+                &after_buy,
+                &[fork_activity],
+                NaiveDate::from_str("2017-08-01").unwrap(),
+            )
+            .unwrap()
+            .snapshot;
+
+        let btc = next_state.positions.get("BTC").unwrap();
+        assert_eq!(btc.quantity, dec!(2));
+        assert_eq!(btc.total_cost_basis, dec!(4500));
+
+        let bch = next_state.positions.get("BCH").unwrap();
+        assert_eq!(bch.quantity, dec!(2));
+        assert_eq!(bch.total_cost_basis, dec!(500));
+        assert_eq!(bch.average_cost, dec!(250));
+
+        // Basis only moved: total cost basis, cash and contributions are unchanged
+        assert_eq!(next_state.cost_basis, dec!(5000));
+        assert_eq!(
+            next_state.cash_balances.get(account_currency),
+            after_buy.cash_balances.get(account_currency)
+        );
+        assert_eq!(next_state.net_contribution, after_buy.net_contribution);
+    }
+
+    #[test]
+    fn test_fx_conversion_moves_cash_without_changing_contribution() {
+        let mut mock_fx_service = MockFxService::new();
+        add_usd_cad_rates(&mut mock_fx_service, "2023-01-10");
+        let account_currency = "CAD";
+        let base_currency = Arc::new(RwLock::new(account_currency.to_string()));
+        let calculator = create_calculator(Arc::new(mock_fx_service), base_currency);
+
+        let mut previous_snapshot =
+            create_initial_snapshot("acc_1", account_currency, "2023-01-09");
+        previous_snapshot
+            .cash_balances
+            .insert("CAD".to_string(), dec!(2000));
+        previous_snapshot.net_contribution = dec!(2000);
+        previous_snapshot.net_contribution_base = dec!(2000);
+
+        // Sell 1,300 CAD for 1,000 USD, paying a 2 CAD conversion fee
+        let mut conversion = create_cash_activity(
+            "act_fx:fx",
+            ActivityType::TransferOut,
+            dec!(1300),
+            dec!(2),
+            "CAD",
+            "2023-01-10",
+        );
+        conversion.subtype = Some(ACTIVITY_SUBTYPE_FX_CONVERSION.to_string());
+        conversion.metadata = Some(serde_json::json!({
+            "to_currency": "USD",
+            "to_amount": 1000
+        }));
+
+        let next_state = calculator
+            .calculate_next_holdings(
+                &previous_snapshot,
+                &[conversion],
+                NaiveDate::from_str("2023-01-10").unwrap(),
+            )
+            .unwrap()
+            .snapshot;
+
+        assert_eq!(next_state.cash_balances.get("CAD"), Some(&dec!(698)));
+        assert_eq!(next_state.cash_balances.get("USD"), Some(&dec!(1000)));
+        assert_eq!(next_state.net_contribution, dec!(2000));
+        assert_eq!(next_state.net_contribution_base, dec!(2000));
+    }
+
+    #[test]
+    fn test_fx_conversion_without_to_amount_is_reported() {
+        let mock_fx_service = Arc::new(MockFxService::new());
+        let account_currency = "USD";
+        let base_currency = Arc::new(RwLock::new(account_currency.to_string()));
+        let calculator = create_calculator(mock_fx_service, base_currency);
+        let previous_snapshot = create_initial_snapshot("acc_1", account_currency, "2023-01-09");
+
+        let mut conversion = create_cash_activity(
+            "act_fx:fx",
+            ActivityType::TransferOut,
+            dec!(100),
+            dec!(0),
+            "USD",
+            "2023-01-10",
+        );
+        conversion.subtype = Some(ACTIVITY_SUBTYPE_FX_CONVERSION.to_string());
+
+        let result = calculator
+            .calculate_next_holdings(
+                &previous_snapshot,
+                &[conversion],
+                NaiveDate::from_str("2023-01-10").unwrap(),
+            )
+            .unwrap();
+
+        // The cash still leaves the account; the conversion is reported
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.snapshot.cash_balances.get("USD"), Some(&dec!(-100)));
+    }
+
+    #[test]
+    fn test_replay_books_history_with_splits() {
+        let base_currency = Arc::new(RwLock::new("USD".to_string()));
+        let calculator = create_calculator(Arc::new(MockFxService::new()), base_currency);
+
+        let deposit = create_cash_activity(
+            "dep",
+            ActivityType::Deposit,
+            dec!(2000),
+            dec!(0),
+            "USD",
+            "2024-01-02",
+        );
+        let buy = create_default_activity(
+            "buy",
+            ActivityType::Buy,
+            "AAPL",
+            dec!(10),
+            dec!(100),
+            dec!(1),
+            "USD",
+            "2024-01-03",
+        );
+        let mut split = create_default_activity(
+            "split",
+            ActivityType::Split,
+            "AAPL",
+            dec!(0),
+            dec!(0),
+            dec!(0),
+            "USD",
+            "2024-01-04",
+        );
+        split.amount = Some(dec!(2));
+        let sell = create_default_activity(
+            "sell",
+            ActivityType::Sell,
+            "AAPL",
+            dec!(5),
+            dec!(60),
+            dec!(0),
+            "USD",
+            "2024-01-05",
+        );
+
+        // Order does not matter; activities are booked by date
+        let result = calculator
+            .replay("acc_1", "USD", &[sell, split, buy, deposit])
+            .unwrap();
+
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.snapshot.account_id, "acc_1");
+        assert_eq!(result.snapshot.positions["AAPL"].quantity, dec!(15));
+        assert_eq!(result.snapshot.cash_balances.get("USD"), Some(&dec!(1299)));
+    }
 }
@@ -220,6 +220,7 @@ impl ManualSnapshotService {
             net_contribution_base: Decimal::ZERO,
             cash_total_account_currency: Decimal::ZERO,
             cash_total_base_currency: Decimal::ZERO,
+            cash_interest_accrued: Decimal::ZERO,
             calculated_at: Utc::now().naive_utc(),
             source: request.source,
         };
@@ -420,6 +420,30 @@ impl Position {
         ))
     }
 
+    /// Moves a share of every lot's cost basis out of the position, keeping quantities.
+    /// Used when a hard fork allocates part of the parent coin's basis to the new coin.
+    /// Returns the cost basis removed, in the position's currency.
+    pub fn split_cost_basis(&mut self, ratio: Decimal, activity_id: &str) -> Result<Decimal> {
+        if ratio.is_sign_negative() || ratio > Decimal::ONE {
+            return Err(CalculatorError::InvalidActivity(format!(
+                "Cost basis ratio must be between 0 and 1, got {} for activity {}",
+                ratio, activity_id
+            ))
+            .into());
+        }
+
+        let mut removed = Decimal::ZERO;
+        for lot in self.lots.iter_mut() {
+            let moved = lot.cost_basis * ratio;
+            lot.cost_basis -= moved;
+            lot.acquisition_price *= Decimal::ONE - ratio;
+            lot.acquisition_fees *= Decimal::ONE - ratio;
+            removed += moved;
+        }
+        self.recalculate_aggregates();
+        Ok(removed)
+    }
+
     /// Applies stock split.
     pub fn apply_split(&mut self, split_ratio: Decimal, activity_id: &str) -> Result<()> {
         if !split_ratio.is_sign_positive() {
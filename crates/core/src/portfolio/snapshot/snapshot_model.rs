@@ -124,6 +124,11 @@ pub struct AccountStateSnapshot {
     /// Source of this snapshot (how it was created)
     #[serde(default)]
     pub source: SnapshotSource,
+
+    /// Cash interest accrued since the last payment date, in the currency of the
+    /// account's cash interest settings. Zero without settings.
+    #[serde(default)]
+    pub cash_interest_accrued: Decimal,
 }
 
 impl Default for AccountStateSnapshot {
@@ -142,6 +147,7 @@ impl Default for AccountStateSnapshot {
             cash_total_base_currency: Decimal::ZERO,
             calculated_at: Utc::now().naive_utc(),
             source: SnapshotSource::default(),
+            cash_interest_accrued: Decimal::ZERO,
         }
     }
 }
@@ -5,6 +5,7 @@ use crate::activities::{
     Activity, ActivityCompiler, ActivityRepositoryTrait, DefaultActivityCompiler,
 };
 use crate::assets::AssetRepositoryTrait;
+use crate::cash_interest::CashInterestSettings;
 use crate::constants::{DECIMAL_PRECISION, PORTFOLIO_TOTAL_ACCOUNT_ID};
 use crate::errors::{CalculatorError, Error, Result};
 use crate::events::{DomainEvent, DomainEventSink, NoOpDomainEventSink};
@@ -401,13 +402,13 @@ impl SnapshotService {
         let compiled_activities = compiler.compile_all(all_activities)?;
 
         // Perform split adjustments on the compiled activity list
-        let split_factors = self.calculate_split_factors(
+        let split_factors = Self::calculate_split_factors(
             &compiled_activities,
             min_activity_date,
             calculation_end_date,
         );
         let adjusted_activities =
-            self.adjust_activities_for_splits(&compiled_activities, &split_factors);
+            Self::adjust_activities_for_splits(&compiled_activities, &split_factors);
 
         // Group adjusted activities by original account ID and date
         let mut activities_by_account_date: ActivitiesByAccount = HashMap::new();
@@ -590,6 +591,21 @@ impl SnapshotService {
         let mut all_warnings: Vec<HoldingsCalculationWarning> = Vec::new();
         let date_range = get_days_between(calculation_min_date, calculation_end_date);
 
+        // Cash interest accrues daily in the snapshots of accounts with settings
+        let interest_settings: HashMap<&str, (CashInterestSettings, String)> =
+            accounts_needing_calculation
+                .iter()
+                .filter_map(|(id, account)| {
+                    let settings =
+                        CashInterestSettings::from_account_meta(account.meta.as_deref())?;
+                    let currency = settings
+                        .currency
+                        .clone()
+                        .unwrap_or_else(|| account.currency.clone());
+                    Some((id.as_str(), (settings, currency)))
+                })
+                .collect();
+
         for current_date in date_range {
             // Process only accounts whose effective start date is today or earlier
             let accounts_to_process_today: Vec<_> = accounts_needing_calculation
@@ -635,7 +651,7 @@ impl SnapshotService {
                 let is_first_day = effective_start_dates.get(account_id) == Some(&current_date);
                 let has_activities = !activities_today.is_empty();
 
-                let current_holdings_snapshot: AccountStateSnapshot; // Final state for today
+                let mut current_holdings_snapshot: AccountStateSnapshot; // Final state for today
 
                 if !has_activities {
                     // No activities today, just carry forward the previous state
@@ -688,9 +704,23 @@ impl SnapshotService {
                     }
                 }
 
+                let mut accrual_changed = false;
+                if let Some((settings, currency)) = interest_settings.get(account_id.as_str()) {
+                    let cash = current_holdings_snapshot
+                        .cash_balances
+                        .get(currency)
+                        .copied()
+                        .unwrap_or(Decimal::ZERO);
+                    let previous_accrued = previous_holdings_snapshot.cash_interest_accrued;
+                    let accrued = settings.accrue_day(current_date, cash, previous_accrued);
+                    accrual_changed = accrued != previous_accrued;
+                    current_holdings_snapshot.cash_interest_accrued = accrued;
+                }
+
                 // Decide if it's a keyframe based on the determined snapshot
-                // A keyframe is needed on the first day of calculation or if activities happened.
-                let is_keyframe = is_first_day || has_activities;
+                // A keyframe is needed on the first day of calculation, if activities happened
+                // or if cash interest accrued.
+                let is_keyframe = is_first_day || has_activities || accrual_changed;
 
                 if is_keyframe {
                     // Create the keyframe based on the final state for today
@@ -891,6 +921,7 @@ impl SnapshotService {
             // For TOTAL snapshot, account_currency == base_currency
             cash_total_account_currency: cash_total_base.round_dp(DECIMAL_PRECISION),
             cash_total_base_currency: cash_total_base.round_dp(DECIMAL_PRECISION),
+            cash_interest_accrued: Decimal::ZERO,
             calculated_at: Utc::now().naive_utc(),
             source: SnapshotSource::Calculated,
         })
@@ -1013,6 +1044,7 @@ impl SnapshotService {
             net_contribution_base: Decimal::ZERO,
             cash_total_account_currency: Decimal::ZERO,
             cash_total_base_currency: Decimal::ZERO,
+            cash_interest_accrued: Decimal::ZERO,
             calculated_at: Utc::now().naive_utc(),
             source: SnapshotSource::Calculated,
         }
@@ -1020,8 +1052,7 @@ impl SnapshotService {
 
     // (Helper function group_activities_by_account_and_date moved inside preprocess_data)
 
-    fn calculate_split_factors(
-        &self,
+    pub(crate) fn calculate_split_factors(
         activities: &[Activity],
         start_date: NaiveDate,
         end_date: NaiveDate,
@@ -1076,8 +1107,7 @@ impl SnapshotService {
         split_factors
     }
 
-    fn adjust_activities_for_splits(
-        &self,
+    pub(crate) fn adjust_activities_for_splits(
         activities: &[Activity],
         split_factors: &HashMap<String, Vec<(NaiveDate, Decimal)>>,
     ) -> Vec<Activity> {
@@ -1694,6 +1724,7 @@ impl SnapshotServiceTrait for SnapshotService {
             net_contribution_base: earliest.net_contribution_base,
             cash_total_account_currency: earliest.cash_total_account_currency,
             cash_total_base_currency: earliest.cash_total_base_currency,
+            cash_interest_accrued: Decimal::ZERO,
         };
 
         self.snapshot_repository
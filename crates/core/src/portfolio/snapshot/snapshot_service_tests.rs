@@ -398,6 +398,13 @@ mod tests {
         async fn create_activities(&self, _activities: Vec<NewActivity>) -> AppResult<usize> {
             unimplemented!()
         }
+        async fn create_import_run_activities(
+            &self,
+            _import_run_id: &str,
+            _activities: Vec<NewActivity>,
+        ) -> AppResult<usize> {
+            unimplemented!()
+        }
         fn get_first_activity_date(
             &self,
             _account_ids: Option<&[String]>,
@@ -459,6 +466,18 @@ mod tests {
         ) -> AppResult<(Vec<String>, Vec<String>)> {
             Ok((Vec::new(), Vec::new()))
         }
+
+        async fn merge_activities(
+            &self,
+            _keep_id: &str,
+            _duplicate_id: &str,
+        ) -> AppResult<Activity> {
+            unimplemented!()
+        }
+
+        async fn bulk_edit_activities(&self, _edited: Vec<Activity>) -> AppResult<Vec<Activity>> {
+            unimplemented!()
+        }
     }
 
     #[derive(Clone, Debug)]
@@ -549,6 +568,13 @@ mod tests {
         async fn create_activities(&self, _a: Vec<NewActivity>) -> AppResult<usize> {
             unimplemented!()
         }
+        async fn create_import_run_activities(
+            &self,
+            _id: &str,
+            _a: Vec<NewActivity>,
+        ) -> AppResult<usize> {
+            unimplemented!()
+        }
         fn get_first_activity_date(
             &self,
             _ids: Option<&[String]>,
@@ -607,6 +633,18 @@ mod tests {
         ) -> AppResult<(Vec<String>, Vec<String>)> {
             Ok((Vec::new(), Vec::new()))
         }
+
+        async fn merge_activities(
+            &self,
+            _keep_id: &str,
+            _duplicate_id: &str,
+        ) -> AppResult<Activity> {
+            unimplemented!()
+        }
+
+        async fn bulk_edit_activities(&self, _edited: Vec<Activity>) -> AppResult<Vec<Activity>> {
+            unimplemented!()
+        }
     }
 
     // Mock SnapshotRepository that implements the trait
@@ -2763,6 +2801,77 @@ mod tests {
         assert!(!dates.contains(&d_between));
     }
 
+    #[tokio::test]
+    async fn test_cash_interest_accrual_is_stored_in_snapshots() {
+        use crate::cash_interest::{ApyTier, CashInterestSettings, Compounding, TierMode};
+
+        let base = Arc::new(RwLock::new("USD".to_string()));
+        let d1 = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2025, 1, 11).unwrap();
+
+        let settings = CashInterestSettings {
+            currency: None,
+            tiers: vec![ApyTier {
+                min_balance: Decimal::ZERO,
+                apy: dec!(0.05),
+            }],
+            tier_mode: TierMode::Whole,
+            compounding: Compounding::Daily,
+            payment_day: 31,
+            start_date: Some(d1),
+            drafted_through: None,
+        };
+        let mut acc = create_test_account("acc1", "USD", "Savings");
+        acc.meta =
+            Some(CashInterestSettings::merge_into_account_meta(Some(&settings), None).unwrap());
+        let mut account_repo = MockAccountRepository::new();
+        account_repo.add_account(acc.clone());
+
+        let dep = create_test_activity(
+            "dep1",
+            &acc.id,
+            Some("CASH:USD"),
+            "DEPOSIT",
+            d1,
+            None,
+            None,
+            Some(dec!(10000)),
+            "USD",
+        );
+
+        let snapshot_repo = Arc::new(MockSnapshotRepository::new());
+        let svc = SnapshotService::new(
+            base,
+            Arc::new(account_repo),
+            Arc::new(MockActivityRepositoryWithData::new(vec![dep])),
+            snapshot_repo.clone(),
+            Arc::new(MockAssetRepository::new()),
+            Arc::new(MockFxService::new()),
+        );
+
+        svc.calculate_holdings_snapshots(None).await.unwrap();
+
+        let frames = snapshot_repo.get_saved_snapshots();
+        let accrued_on = |date: NaiveDate| {
+            frames
+                .iter()
+                .find(|f| f.snapshot_date == date)
+                .map(|f| f.cash_interest_accrued)
+                .unwrap()
+        };
+
+        // Accruing days are keyframes even without activities
+        assert_eq!(
+            accrued_on(d1),
+            settings.accrue_day(d1, dec!(10000), Decimal::ZERO)
+        );
+        assert_eq!(
+            accrued_on(d2),
+            settings.accrue_day(d2, dec!(10000), accrued_on(d1))
+        );
+        assert!(accrued_on(d2) > accrued_on(d1));
+    }
+
     // ==================== GET DAILY HOLDINGS SNAPSHOTS TESTS ====================
 
     #[tokio::test]
@@ -0,0 +1,1020 @@
+//! Bulk quote import for large history files.
+//!
+//! `QuoteImportService` works on in-memory `QuoteImport` rows, which is fine
+//! for a handful of manual quotes but not for seeding decades of history.
+//! This module streams provider dump files instead:
+//!
+//! - Stooq / EOD-style CSV or TXT files (one ticker per file, or combined)
+//! - Directories of such files, walked recursively
+//! - Zip archives of such directories (entries are streamed, never extracted)
+//! - Parquet files (requires the `parquet` feature)
+//!
+//! # Pipeline
+//!
+//! ```text
+//! blocking reader ──batches──▶ AssetMatcher ──▶ QuoteValidator ──▶ QuoteStore::bulk_insert_quotes
+//! ```
+//!
+//! Files are parsed on a blocking thread and handed over through a bounded
+//! channel, so memory stays flat regardless of file size. Each batch is
+//! matched to existing assets, validated, and written in one transaction.
+//!
+//! # Key Invariants
+//!
+//! - Imported quotes get `QuoteSource::Manual`, like the per-row import
+//! - Quotes get fresh UUIDs so they sync to other devices; the store matches
+//!   existing manual rows by asset and day, so re-importing a dump is idempotent
+//! - Rows for unknown tickers are counted and reported, never create assets
+
+use chrono::{DateTime, NaiveDate, Utc};
+use log::{debug, info};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::constants::{BULK_IMPORT_BATCH_SIZE, BULK_IMPORT_MAX_REPORTED_ISSUES};
+use super::import::{ImportValidationStatus, QuoteImport, QuoteValidator};
+use super::model::{DataSource, Quote};
+use super::store::QuoteStore;
+use super::types::AssetId;
+use crate::assets::Asset;
+use crate::errors::{Error, Result, ValidationError};
+
+use wealthfolio_market_data::dump_exchange_to_mics;
+
+/// Number of batches buffered between the file reader and the writer.
+const CHANNEL_CAPACITY: usize = 4;
+
+// =============================================================================
+// Options & Summary
+// =============================================================================
+
+/// Options for a bulk quote import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkImportOptions {
+    /// Rows written per transaction.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// Replace existing quotes for the same asset and day instead of skipping them.
+    #[serde(default)]
+    pub overwrite: bool,
+    /// Exchange assumed for tickers that carry no market code (e.g. `XNAS`).
+    #[serde(default)]
+    pub default_exchange_mic: Option<String>,
+}
+
+fn default_batch_size() -> usize {
+    BULK_IMPORT_BATCH_SIZE
+}
+
+impl Default for BulkImportOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: BULK_IMPORT_BATCH_SIZE,
+            overwrite: false,
+            default_exchange_mic: None,
+        }
+    }
+}
+
+/// Result of a bulk quote import.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkImportSummary {
+    /// Number of data files processed.
+    pub files_read: usize,
+    /// Number of data rows read across all files.
+    pub rows_read: usize,
+    /// Number of quotes written.
+    pub imported: usize,
+    /// Number of rows skipped because a quote already existed.
+    pub skipped_duplicates: usize,
+    /// Number of rows rejected by validation.
+    pub invalid: usize,
+    /// Number of rows whose ticker did not match an existing asset.
+    pub unmatched: usize,
+    /// Sample of tickers that did not match an existing asset.
+    pub unmatched_symbols: Vec<String>,
+    /// Sample of file and row errors.
+    pub errors: Vec<String>,
+}
+
+impl BulkImportSummary {
+    fn record_error(&mut self, message: String) {
+        if self.errors.len() < BULK_IMPORT_MAX_REPORTED_ISSUES {
+            self.errors.push(message);
+        }
+    }
+
+    fn record_unmatched(&mut self, symbol: &str) {
+        self.unmatched += 1;
+        if self.unmatched_symbols.len() < BULK_IMPORT_MAX_REPORTED_ISSUES
+            && !self.unmatched_symbols.iter().any(|s| s == symbol)
+        {
+            self.unmatched_symbols.push(symbol.to_string());
+        }
+    }
+}
+
+// =============================================================================
+// Import Entry Point
+// =============================================================================
+
+/// Import quotes from a file, directory or zip archive at `path`.
+///
+/// Tickers are matched against `assets`; rows for unknown tickers are skipped.
+/// Returns an error only if the path is missing or the store fails; bad files
+/// and rows are reported in the summary.
+pub async fn import_from_path<Q>(
+    quote_store: &Q,
+    assets: &[Asset],
+    path: &Path,
+    options: &BulkImportOptions,
+) -> Result<BulkImportSummary>
+where
+    Q: QuoteStore + ?Sized,
+{
+    if !path.exists() {
+        return Err(ValidationError::InvalidInput(format!(
+            "Import path does not exist: {}",
+            path.display()
+        ))
+        .into());
+    }
+
+    let (tx, mut rx) = mpsc::channel(CHANNEL_CAPACITY);
+    let reader_path = path.to_path_buf();
+    let batch_size = options.batch_size.max(1);
+    let reader = tokio::task::spawn_blocking(move || read_path(&reader_path, batch_size, &tx));
+
+    let mut matcher = AssetMatcher::new(assets, options.default_exchange_mic.as_deref());
+    let mut summary = BulkImportSummary::default();
+
+    while let Some(event) = rx.recv().await {
+        match event {
+            ReadEvent::FileRead => summary.files_read += 1,
+            ReadEvent::Failed(message) => summary.record_error(message),
+            ReadEvent::Batch(rows) => {
+                let quotes = prepare_batch(rows, &mut matcher, &mut summary);
+                if quotes.is_empty() {
+                    continue;
+                }
+                let written = quote_store
+                    .bulk_insert_quotes(&quotes, options.overwrite)
+                    .await?;
+                summary.imported += written;
+                summary.skipped_duplicates += quotes.len().saturating_sub(written);
+                debug!(
+                    "Bulk import: wrote {} of {} quotes in batch",
+                    written,
+                    quotes.len()
+                );
+            }
+        }
+    }
+
+    reader
+        .await
+        .map_err(|e| Error::Unexpected(format!("Bulk import reader failed: {}", e)))?;
+
+    info!(
+        "Bulk import from {} finished: {} files, {} rows, {} imported, {} duplicates, {} invalid, {} unmatched",
+        path.display(),
+        summary.files_read,
+        summary.rows_read,
+        summary.imported,
+        summary.skipped_duplicates,
+        summary.invalid,
+        summary.unmatched
+    );
+
+    Ok(summary)
+}
+
+/// Match, validate and convert a batch of raw rows into quotes.
+fn prepare_batch(
+    rows: Vec<BulkQuoteRow>,
+    matcher: &mut AssetMatcher<'_>,
+    summary: &mut BulkImportSummary,
+) -> Vec<Quote> {
+    summary.rows_read += rows.len();
+    let mut quotes = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let Some(asset) = matcher.resolve(&row.symbol, row.exchange.as_deref()) else {
+            summary.record_unmatched(&row.symbol);
+            continue;
+        };
+
+        let import = QuoteImport {
+            symbol: asset.id.clone(),
+            date: row.date,
+            open: row.open,
+            high: row.high,
+            low: row.low,
+            close: row.close,
+            volume: row.volume,
+            currency: asset.quote_ccy.clone(),
+            validation_status: ImportValidationStatus::Valid,
+            error_message: None,
+        };
+
+        match QuoteValidator::validate(&import) {
+            ImportValidationStatus::Error(msg) => {
+                summary.invalid += 1;
+                summary.record_error(format!("{} on {}: {}", row.symbol, import.date, msg));
+            }
+            ImportValidationStatus::Valid | ImportValidationStatus::Warning(_) => {
+                if let Some(quote) = import_to_quote(&import, row.adjclose) {
+                    quotes.push(quote);
+                }
+            }
+        }
+    }
+
+    quotes
+}
+
+/// Convert a validated import row to a manual quote at noon UTC.
+fn import_to_quote(import: &QuoteImport, adjclose: Option<Decimal>) -> Option<Quote> {
+    let day = import.parse_day()?;
+    let asset_id = AssetId::new(&import.symbol);
+    let timestamp: DateTime<Utc> = day.0.and_hms_opt(12, 0, 0)?.and_utc();
+
+    Some(Quote {
+        id: Uuid::new_v4().to_string(),
+        asset_id: asset_id.0,
+        timestamp,
+        open: import.open_or_close(),
+        high: import.high_or_close(),
+        low: import.low_or_close(),
+        close: import.close,
+        adjclose: adjclose.unwrap_or(import.close),
+        volume: import.volume_or_zero(),
+        currency: import.currency.clone(),
+        data_source: DataSource::Manual,
+        created_at: Utc::now(),
+        notes: None,
+    })
+}
+
+// =============================================================================
+// Asset Matching
+// =============================================================================
+
+/// Resolves dump tickers (`AAPL.US`, `VOD` + `LSE`) to existing assets.
+///
+/// Lookup order: exact asset ID, then ticker restricted to the exchanges
+/// implied by the market code. Ambiguous tickers are left unmatched rather
+/// than guessed. Results are cached since dumps repeat a ticker per row.
+struct AssetMatcher<'a> {
+    by_id: HashMap<String, &'a Asset>,
+    by_ticker: HashMap<String, Vec<&'a Asset>>,
+    default_mic: Option<String>,
+    cache: HashMap<(String, Option<String>), Option<&'a Asset>>,
+}
+
+impl<'a> AssetMatcher<'a> {
+    fn new(assets: &'a [Asset], default_mic: Option<&str>) -> Self {
+        let mut by_id = HashMap::new();
+        let mut by_ticker: HashMap<String, Vec<&'a Asset>> = HashMap::new();
+
+        for asset in assets {
+            by_id.insert(asset.id.to_uppercase(), asset);
+
+            let mut tickers: Vec<String> = [&asset.instrument_symbol, &asset.display_code]
+                .into_iter()
+                .flatten()
+                .map(|t| t.trim().to_uppercase())
+                .filter(|t| !t.is_empty())
+                .collect();
+            tickers.dedup();
+            for ticker in tickers {
+                by_ticker.entry(ticker).or_default().push(asset);
+            }
+        }
+
+        Self {
+            by_id,
+            by_ticker,
+            default_mic: default_mic.map(|m| m.trim().to_uppercase()),
+            cache: HashMap::new(),
+        }
+    }
+
+    fn resolve(&mut self, symbol: &str, exchange: Option<&str>) -> Option<&'a Asset> {
+        let key = (symbol.to_uppercase(), exchange.map(|e| e.to_uppercase()));
+        if let Some(cached) = self.cache.get(&key) {
+            return *cached;
+        }
+        let resolved = self.lookup(&key.0, key.1.as_deref());
+        self.cache.insert(key, resolved);
+        resolved
+    }
+
+    fn lookup(&self, symbol: &str, exchange: Option<&str>) -> Option<&'a Asset> {
+        if let Some(asset) = self.by_id.get(symbol) {
+            return Some(*asset);
+        }
+
+        let suffixed = symbol
+            .rsplit_once('.')
+            .map(|(base, code)| (base, dump_exchange_to_mics(code)))
+            .filter(|(_, mics)| !mics.is_empty());
+        let (ticker, mics): (&str, Vec<&str>) = match (exchange, suffixed) {
+            (Some(code), _) => (symbol, dump_exchange_to_mics(code)),
+            (None, Some((base, mics))) => (base, mics),
+            (None, None) => (symbol, self.default_mic.as_deref().into_iter().collect()),
+        };
+
+        let candidates = self.by_ticker.get(ticker)?;
+        if mics.is_empty() {
+            return match candidates.as_slice() {
+                [only] => Some(*only),
+                _ => None,
+            };
+        }
+
+        let on_exchange: Vec<&'a Asset> = candidates
+            .iter()
+            .copied()
+            .filter(|a| {
+                a.instrument_exchange_mic
+                    .as_deref()
+                    .is_some_and(|mic| mics.contains(&mic))
+            })
+            .collect();
+        match on_exchange.as_slice() {
+            [only] => Some(*only),
+            [] => match candidates.as_slice() {
+                // A lone asset without an exchange is still an unambiguous match.
+                [only] if only.instrument_exchange_mic.is_none() => Some(*only),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+// =============================================================================
+// File Reading
+// =============================================================================
+
+/// A raw row read from a dump file, before asset matching.
+#[derive(Debug, Clone, PartialEq)]
+struct BulkQuoteRow {
+    symbol: String,
+    exchange: Option<String>,
+    /// Normalized to YYYY-MM-DD when parseable; left raw for the validator otherwise.
+    date: String,
+    open: Option<Decimal>,
+    high: Option<Decimal>,
+    low: Option<Decimal>,
+    close: Decimal,
+    adjclose: Option<Decimal>,
+    volume: Option<Decimal>,
+}
+
+/// Messages sent from the blocking reader to the async writer.
+#[derive(Debug)]
+enum ReadEvent {
+    Batch(Vec<BulkQuoteRow>),
+    FileRead,
+    Failed(String),
+}
+
+/// Supported input formats, detected from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputFormat {
+    Csv,
+    Zip,
+    Parquet,
+}
+
+impl InputFormat {
+    fn from_name(name: &str) -> Option<Self> {
+        let ext = Path::new(name).extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "csv" | "txt" => Some(Self::Csv),
+            "zip" => Some(Self::Zip),
+            "parquet" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+}
+
+/// Buffers rows into batches and forwards them to the writer.
+///
+/// Once the receiver is gone (the import failed), `closed` is set and the
+/// reader stops at the next row.
+struct RowSink<'a> {
+    tx: &'a mpsc::Sender<ReadEvent>,
+    batch: Vec<BulkQuoteRow>,
+    batch_size: usize,
+    closed: bool,
+}
+
+impl<'a> RowSink<'a> {
+    fn new(tx: &'a mpsc::Sender<ReadEvent>, batch_size: usize) -> Self {
+        Self {
+            tx,
+            batch: Vec::with_capacity(batch_size),
+            batch_size,
+            closed: false,
+        }
+    }
+
+    fn push(&mut self, row: BulkQuoteRow) -> bool {
+        self.batch.push(row);
+        if self.batch.len() >= self.batch_size {
+            self.flush();
+        }
+        !self.closed
+    }
+
+    fn flush(&mut self) {
+        if !self.batch.is_empty() {
+            let batch = std::mem::replace(&mut self.batch, Vec::with_capacity(self.batch_size));
+            self.send(ReadEvent::Batch(batch));
+        }
+    }
+
+    fn send(&mut self, event: ReadEvent) {
+        if !self.closed && self.tx.blocking_send(event).is_err() {
+            self.closed = true;
+        }
+    }
+}
+
+/// Read every supported file under `path`. Must run on a blocking thread.
+fn read_path(path: &Path, batch_size: usize, tx: &mpsc::Sender<ReadEvent>) {
+    let mut sink = RowSink::new(tx, batch_size);
+
+    if path.is_dir() {
+        for file in collect_files(path) {
+            if sink.closed {
+                break;
+            }
+            read_file(&file, &mut sink);
+        }
+    } else if InputFormat::from_name(&path.to_string_lossy()).is_none() {
+        sink.send(ReadEvent::Failed(format!(
+            "{}: unsupported file type (expected .csv, .txt, .zip or .parquet)",
+            path.display()
+        )));
+    } else {
+        read_file(path, &mut sink);
+    }
+
+    sink.flush();
+}
+
+/// Recursively list supported files under `dir`, in a stable order.
+fn collect_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if InputFormat::from_name(&path.to_string_lossy()).is_some() {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+fn read_file(path: &Path, sink: &mut RowSink<'_>) {
+    let name = path.to_string_lossy().to_string();
+    let result = match InputFormat::from_name(&name) {
+        Some(InputFormat::Csv) => File::open(path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| read_csv(file, &name, sink)),
+        Some(InputFormat::Zip) => {
+            // Members are counted as data files, the archive itself is not.
+            if let Err(e) = read_zip(path, sink) {
+                sink.send(ReadEvent::Failed(format!("{}: {}", name, e)));
+            }
+            return;
+        }
+        Some(InputFormat::Parquet) => read_parquet(path, &name, sink),
+        None => return,
+    };
+
+    match result {
+        Ok(()) => sink.send(ReadEvent::FileRead),
+        Err(e) => sink.send(ReadEvent::Failed(format!("{}: {}", name, e))),
+    }
+}
+
+fn read_zip(path: &Path, sink: &mut RowSink<'_>) -> std::result::Result<(), String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    for index in 0..archive.len() {
+        if sink.closed {
+            break;
+        }
+        let entry = archive.by_index(index).map_err(|e| e.to_string())?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry.name().to_string();
+        match InputFormat::from_name(&name) {
+            Some(InputFormat::Csv) => match read_csv(entry, &name, sink) {
+                Ok(()) => sink.send(ReadEvent::FileRead),
+                Err(e) => sink.send(ReadEvent::Failed(format!("{}: {}", name, e))),
+            },
+            Some(InputFormat::Parquet) | Some(InputFormat::Zip) => {
+                sink.send(ReadEvent::Failed(format!(
+                    "{}: nested archives and Parquet files inside zip archives are not supported",
+                    name
+                )));
+            }
+            None => {}
+        }
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// CSV
+// =============================================================================
+
+/// Column positions resolved from a dump header row.
+///
+/// Headers are matched case-insensitively with `<>`, spaces and underscores
+/// stripped, which covers Stooq (`<TICKER>,<DATE>,...`), EODHD
+/// (`Code,Ex,Date,...,Adjusted_close`) and Yahoo (`Date,...,Adj Close`).
+#[derive(Debug, Default)]
+struct CsvColumns {
+    symbol: Option<usize>,
+    exchange: Option<usize>,
+    period: Option<usize>,
+    date: usize,
+    open: Option<usize>,
+    high: Option<usize>,
+    low: Option<usize>,
+    close: usize,
+    adjclose: Option<usize>,
+    volume: Option<usize>,
+}
+
+impl CsvColumns {
+    fn from_headers(headers: &csv::StringRecord) -> std::result::Result<Self, String> {
+        let mut date = None;
+        let mut close = None;
+        let mut columns = CsvColumns::default();
+
+        for (idx, header) in headers.iter().enumerate() {
+            let slot = match normalize_header(header).as_str() {
+                "ticker" | "symbol" | "code" => &mut columns.symbol,
+                "ex" | "exchange" | "market" => &mut columns.exchange,
+                "per" | "period" => &mut columns.period,
+                "date" => &mut date,
+                "open" => &mut columns.open,
+                "high" => &mut columns.high,
+                "low" => &mut columns.low,
+                "close" => &mut close,
+                "adjclose" | "adjustedclose" => &mut columns.adjclose,
+                "vol" | "volume" => &mut columns.volume,
+                _ => continue,
+            };
+            slot.get_or_insert(idx);
+        }
+
+        columns.date = date.ok_or("missing date column")?;
+        columns.close = close.ok_or("missing close column")?;
+        Ok(columns)
+    }
+}
+
+fn normalize_header(header: &str) -> String {
+    header
+        .trim()
+        .trim_start_matches('\u{feff}')
+        .chars()
+        .filter(|c| !matches!(c, '<' | '>' | ' ' | '_'))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Symbol implied by a file name, e.g. `data/daily/us/aapl.us.txt` → `aapl.us`.
+fn symbol_from_file_name(name: &str) -> String {
+    Path::new(name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn read_csv<R: Read>(
+    input: R,
+    name: &str,
+    sink: &mut RowSink<'_>,
+) -> std::result::Result<(), String> {
+    let mut input = BufReader::new(input);
+    let delimiter = sniff_delimiter(input.fill_buf().map_err(|e| e.to_string())?);
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(input);
+
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let columns = CsvColumns::from_headers(&headers)?;
+    let file_symbol = symbol_from_file_name(name);
+
+    let mut record = csv::StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            // The reader cannot move past an I/O error, so give up on the file.
+            Err(e) if e.is_io_error() => return Err(e.to_string()),
+            Err(e) => {
+                sink.send(ReadEvent::Failed(format!("{}: {}", name, e)));
+                continue;
+            }
+        }
+
+        let field = |idx: Option<usize>| record.get(idx?).filter(|s| !s.is_empty());
+
+        // Stooq files may mix intraday bars into a daily dump.
+        if field(columns.period).is_some_and(|p| !p.eq_ignore_ascii_case("D")) {
+            continue;
+        }
+
+        let row = BulkQuoteRow {
+            symbol: field(columns.symbol)
+                .map(str::to_string)
+                .unwrap_or_else(|| file_symbol.clone()),
+            exchange: field(columns.exchange).map(str::to_string),
+            date: normalize_date(field(Some(columns.date)).unwrap_or_default()),
+            open: field(columns.open).and_then(parse_decimal),
+            high: field(columns.high).and_then(parse_decimal),
+            low: field(columns.low).and_then(parse_decimal),
+            close: field(Some(columns.close))
+                .and_then(parse_decimal)
+                .unwrap_or(Decimal::ZERO),
+            adjclose: field(columns.adjclose).and_then(parse_decimal),
+            volume: field(columns.volume).and_then(parse_decimal),
+        };
+
+        if !sink.push(row) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Pick `;` or tab for files that clearly use them, `,` otherwise.
+fn sniff_delimiter(head: &[u8]) -> u8 {
+    let first_line = head.split(|b| *b == b'\n').next().unwrap_or_default();
+    let count = |d: u8| first_line.iter().filter(|b| **b == d).count();
+    [b',', b';', b'\t']
+        .into_iter()
+        .max_by_key(|d| (count(*d), *d == b','))
+        .unwrap_or(b',')
+}
+
+/// Normalize `YYYYMMDD`, `YYYY/MM/DD` and timestamped dates to `YYYY-MM-DD`.
+///
+/// Unparseable values are returned unchanged so the validator reports them.
+fn normalize_date(raw: &str) -> String {
+    let date_part = raw.split(['T', ' ']).next().unwrap_or(raw);
+    ["%Y-%m-%d", "%Y%m%d", "%Y/%m/%d"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(date_part, fmt).ok())
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| raw.to_string())
+}
+
+fn parse_decimal(raw: &str) -> Option<Decimal> {
+    Decimal::from_str(raw)
+        .or_else(|_| Decimal::from_scientific(raw))
+        .ok()
+}
+
+// =============================================================================
+// Parquet
+// =============================================================================
+
+#[cfg(feature = "parquet")]
+fn read_parquet(
+    path: &Path,
+    name: &str,
+    sink: &mut RowSink<'_>,
+) -> std::result::Result<(), String> {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::Field;
+
+    fn field_to_string(field: &Field) -> Option<String> {
+        match field {
+            Field::Str(s) => Some(s.clone()),
+            Field::Date(days) => NaiveDate::from_num_days_from_ce_opt(*days + 719_163)
+                .map(|d| d.format("%Y-%m-%d").to_string()),
+            Field::TimestampMillis(ms) => {
+                DateTime::from_timestamp_millis(*ms).map(|d| d.format("%Y-%m-%d").to_string())
+            }
+            Field::TimestampMicros(us) => {
+                DateTime::from_timestamp_micros(*us).map(|d| d.format("%Y-%m-%d").to_string())
+            }
+            Field::Int(v) => Some(v.to_string()),
+            Field::Long(v) => Some(v.to_string()),
+            _ => None,
+        }
+    }
+
+    fn field_to_decimal(field: &Field) -> Option<Decimal> {
+        match field {
+            Field::Int(v) => Some(Decimal::from(*v)),
+            Field::Long(v) => Some(Decimal::from(*v)),
+            Field::Float(v) => Decimal::try_from(*v).ok(),
+            Field::Double(v) => Decimal::try_from(*v).ok(),
+            Field::Str(s) => parse_decimal(s),
+            _ => None,
+        }
+    }
+
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let reader = SerializedFileReader::new(file).map_err(|e| e.to_string())?;
+    let rows = reader.get_row_iter(None).map_err(|e| e.to_string())?;
+    let file_symbol = symbol_from_file_name(name);
+
+    for row in rows {
+        let row = row.map_err(|e| e.to_string())?;
+        let mut quote_row = BulkQuoteRow {
+            symbol: file_symbol.clone(),
+            exchange: None,
+            date: String::new(),
+            open: None,
+            high: None,
+            low: None,
+            close: Decimal::ZERO,
+            adjclose: None,
+            volume: None,
+        };
+
+        for (column, field) in row.get_column_iter() {
+            match normalize_header(column).as_str() {
+                "ticker" | "symbol" | "code" => {
+                    if let Some(s) = field_to_string(field) {
+                        quote_row.symbol = s;
+                    }
+                }
+                "ex" | "exchange" | "market" => quote_row.exchange = field_to_string(field),
+                "date" => {
+                    quote_row.date = normalize_date(&field_to_string(field).unwrap_or_default())
+                }
+                "open" => quote_row.open = field_to_decimal(field),
+                "high" => quote_row.high = field_to_decimal(field),
+                "low" => quote_row.low = field_to_decimal(field),
+                "close" => quote_row.close = field_to_decimal(field).unwrap_or(Decimal::ZERO),
+                "adjclose" | "adjustedclose" => quote_row.adjclose = field_to_decimal(field),
+                "vol" | "volume" => quote_row.volume = field_to_decimal(field),
+                _ => {}
+            }
+        }
+
+        if !sink.push(quote_row) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "parquet"))]
+fn read_parquet(
+    _path: &Path,
+    _name: &str,
+    _sink: &mut RowSink<'_>,
+) -> std::result::Result<(), String> {
+    Err("Parquet support is not enabled in this build".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::{AssetKind, QuoteMode};
+    use rust_decimal_macros::dec;
+    use std::io::Write;
+
+    fn asset(id: &str, symbol: &str, mic: Option<&str>, ccy: &str) -> Asset {
+        Asset {
+            id: id.to_string(),
+            kind: AssetKind::Investment,
+            display_code: Some(symbol.to_string()),
+            quote_mode: QuoteMode::Market,
+            quote_ccy: ccy.to_string(),
+            instrument_symbol: Some(symbol.to_string()),
+            instrument_exchange_mic: mic.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn read_all(path: &Path, batch_size: usize) -> (Vec<BulkQuoteRow>, usize, Vec<String>) {
+        let (tx, mut rx) = mpsc::channel(1_000);
+        read_path(path, batch_size, &tx);
+        drop(tx);
+
+        let (mut rows, mut files, mut errors) = (Vec::new(), 0, Vec::new());
+        while let Some(event) = rx.blocking_recv() {
+            match event {
+                ReadEvent::Batch(batch) => {
+                    assert!(batch.len() <= batch_size);
+                    rows.extend(batch);
+                }
+                ReadEvent::FileRead => files += 1,
+                ReadEvent::Failed(msg) => errors.push(msg),
+            }
+        }
+        (rows, files, errors)
+    }
+
+    #[test]
+    fn test_normalize_date() {
+        assert_eq!(normalize_date("20240105"), "2024-01-05");
+        assert_eq!(normalize_date("2024/01/05"), "2024-01-05");
+        assert_eq!(normalize_date("2024-01-05 00:00:00"), "2024-01-05");
+        assert_eq!(normalize_date("2024-01-05T00:00:00Z"), "2024-01-05");
+        assert_eq!(normalize_date("05.01.2024"), "05.01.2024");
+    }
+
+    #[test]
+    fn test_sniff_delimiter() {
+        assert_eq!(sniff_delimiter(b"Date,Open,Close\n"), b',');
+        assert_eq!(sniff_delimiter(b"Date;Open;Close\n"), b';');
+        assert_eq!(sniff_delimiter(b"Date\tOpen\tClose\n"), b'\t');
+        assert_eq!(sniff_delimiter(b"Date\n"), b',');
+    }
+
+    #[test]
+    fn test_reads_stooq_file_in_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aapl.us.txt");
+        std::fs::write(
+            &path,
+            "<TICKER>,<PER>,<DATE>,<TIME>,<OPEN>,<HIGH>,<LOW>,<CLOSE>,<VOL>,<OPENINT>\n\
+             AAPL.US,D,20240102,000000,187.15,188.44,183.885,185.64,8.2488674e+07,0\n\
+             AAPL.US,D,20240103,000000,184.22,185.88,183.43,184.25,58414460,0\n\
+             AAPL.US,5,20240103,153000,184.0,184.5,183.9,184.2,1000,0\n\
+             AAPL.US,D,20240104,000000,182.15,183.0872,180.88,181.91,71983570,0\n",
+        )
+        .unwrap();
+
+        let (rows, files, errors) = read_all(&path, 2);
+
+        assert_eq!(files, 1);
+        assert!(errors.is_empty());
+        assert_eq!(rows.len(), 3, "intraday bar should be skipped");
+        assert_eq!(rows[0].symbol, "AAPL.US");
+        assert_eq!(rows[0].date, "2024-01-02");
+        assert_eq!(rows[0].close, dec!(185.64));
+        assert_eq!(rows[0].volume, Some(dec!(82488674)));
+    }
+
+    #[test]
+    fn test_reads_zip_and_uses_file_stem_as_symbol() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dump.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("daily/uk/vod.uk.csv", options).unwrap();
+        zip.write_all(
+            b"Date,Open,High,Low,Close,Adj Close,Volume\n2024-01-02,70,71,69,70.5,68.1,1000\n",
+        )
+        .unwrap();
+        zip.start_file("daily/readme.md", options).unwrap();
+        zip.write_all(b"ignored").unwrap();
+        zip.finish().unwrap();
+
+        let (rows, files, errors) = read_all(&path, 100);
+
+        assert_eq!(files, 1);
+        assert!(errors.is_empty());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].symbol, "vod.uk");
+        assert_eq!(rows[0].adjclose, Some(dec!(68.1)));
+    }
+
+    #[test]
+    fn test_reports_file_without_close_column() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("bad.csv"), "Date,Open\n2024-01-02,1\n").unwrap();
+        std::fs::write(
+            dir.path().join("good.csv"),
+            "Code,Ex,Date,Close\nVOD,LSE,2024-01-02,70.5\n",
+        )
+        .unwrap();
+
+        let (rows, files, errors) = read_all(dir.path(), 100);
+
+        assert_eq!(files, 1);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("missing close column"));
+        assert_eq!(rows[0].exchange.as_deref(), Some("LSE"));
+    }
+
+    #[test]
+    fn test_asset_matcher_uses_market_codes() {
+        let assets = vec![
+            asset("SEC:VOD:XLON", "VOD", Some("XLON"), "GBp"),
+            asset("SEC:VOD:XNAS", "VOD", Some("XNAS"), "USD"),
+            asset("SEC:AAPL:XNAS", "AAPL", Some("XNAS"), "USD"),
+            asset("SEC:BRK.B:XNYS", "BRK.B", Some("XNYS"), "USD"),
+        ];
+        let mut matcher = AssetMatcher::new(&assets, None);
+
+        let id = |a: Option<&Asset>| a.map(|a| a.id.clone());
+        assert_eq!(
+            id(matcher.resolve("vod.uk", None)),
+            Some("SEC:VOD:XLON".into())
+        );
+        assert_eq!(
+            id(matcher.resolve("VOD", Some("LSE"))),
+            Some("SEC:VOD:XLON".into())
+        );
+        assert_eq!(
+            id(matcher.resolve("VOD.US", None)),
+            Some("SEC:VOD:XNAS".into())
+        );
+        assert_eq!(id(matcher.resolve("VOD", None)), None, "ambiguous");
+        assert_eq!(
+            id(matcher.resolve("AAPL", None)),
+            Some("SEC:AAPL:XNAS".into())
+        );
+        assert_eq!(
+            id(matcher.resolve("BRK.B", None)),
+            Some("SEC:BRK.B:XNYS".into())
+        );
+        assert_eq!(
+            id(matcher.resolve("sec:aapl:xnas", None)),
+            Some("SEC:AAPL:XNAS".into())
+        );
+        assert_eq!(id(matcher.resolve("MSFT.US", None)), None);
+
+        let mut with_default = AssetMatcher::new(&assets, Some("xnas"));
+        assert_eq!(
+            id(with_default.resolve("VOD", None)),
+            Some("SEC:VOD:XNAS".into())
+        );
+    }
+
+    #[test]
+    fn test_prepare_batch_validates_and_counts() {
+        let assets = vec![asset("SEC:AAPL:XNAS", "AAPL", Some("XNAS"), "USD")];
+        let mut matcher = AssetMatcher::new(&assets, None);
+        let mut summary = BulkImportSummary::default();
+        let row = |symbol: &str, date: &str, close: Decimal| BulkQuoteRow {
+            symbol: symbol.to_string(),
+            exchange: None,
+            date: date.to_string(),
+            open: None,
+            high: Some(dec!(190)),
+            low: Some(dec!(180)),
+            close,
+            adjclose: None,
+            volume: None,
+        };
+
+        let quotes = prepare_batch(
+            vec![
+                row("AAPL.US", "2024-01-02", dec!(185)),
+                row("AAPL.US", "2024-01-03", Decimal::ZERO),
+                row("AAPL.US", "01/04/2024", dec!(182)),
+                row("TSLA.US", "2024-01-02", dec!(250)),
+            ],
+            &mut matcher,
+            &mut summary,
+        );
+
+        assert_eq!(quotes.len(), 1);
+        assert!(Uuid::parse_str(&quotes[0].id).is_ok());
+        assert_eq!(quotes[0].currency, "USD");
+        assert_eq!(quotes[0].open, dec!(185));
+        assert_eq!(quotes[0].data_source, DataSource::Manual);
+        assert_eq!(summary.rows_read, 4);
+        assert_eq!(summary.invalid, 2);
+        assert_eq!(summary.unmatched, 1);
+        assert_eq!(summary.unmatched_symbols, vec!["TSLA.US".to_string()]);
+    }
+}
@@ -261,6 +261,73 @@ impl MarketDataClient {
         Ok(core_quotes)
     }
 
+    /// Fetch historical quotes for an asset from providers other than `excluded`.
+    ///
+    /// Used by the quote quality pass to refetch a suspicious range from the
+    /// next provider in priority order.
+    pub async fn fetch_historical_quotes_excluding(
+        &self,
+        asset: &Asset,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        excluded: &[&str],
+    ) -> Result<Vec<Quote>> {
+        let context = self.build_quote_context(asset)?;
+
+        debug!(
+            "Fetching fallback quotes for {:?} from {} to {} (excluding {:?})",
+            context.instrument,
+            start.format("%Y-%m-%d"),
+            end.format("%Y-%m-%d"),
+            excluded
+        );
+
+        let market_quotes = self
+            .registry
+            .fetch_quotes_excluding(&context, start, end, excluded)
+            .await
+            .map_err(MarketDataClientError::from)?;
+
+        Ok(market_quotes
+            .into_iter()
+            .map(|mq| Self::convert_quote(mq, &asset.id))
+            .collect())
+    }
+
+    /// Fetch historical quotes for an asset from two different providers.
+    ///
+    /// Returns `(primary, secondary)`. The secondary list is empty when only one
+    /// provider can serve the asset.
+    pub async fn fetch_historical_quotes_dual(
+        &self,
+        asset: &Asset,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<(Vec<Quote>, Vec<Quote>)> {
+        let context = self.build_quote_context(asset)?;
+
+        debug!(
+            "Fetching dual-source quotes for {:?} from {} to {}",
+            context.instrument,
+            start.format("%Y-%m-%d"),
+            end.format("%Y-%m-%d")
+        );
+
+        let dual = self
+            .registry
+            .fetch_quotes_dual(&context, start, end)
+            .await
+            .map_err(MarketDataClientError::from)?;
+
+        let convert = |quotes: Vec<wealthfolio_market_data::Quote>| -> Vec<Quote> {
+            quotes
+                .into_iter()
+                .map(|mq| Self::convert_quote(mq, &asset.id))
+                .collect()
+        };
+        Ok((convert(dual.primary), convert(dual.secondary)))
+    }
+
     /// Fetch the latest quote for an asset.
     pub async fn fetch_latest_quote(&self, asset: &Asset) -> Result<Quote> {
         let context = self.build_quote_context(asset)?;
@@ -0,0 +1,545 @@
+//! Dual-provider quote consensus.
+//!
+//! When consensus mode is enabled, each sync asks two providers for the same
+//! range and reconciles them day by day before anything is stored. The stored
+//! quote keeps the primary provider's id so re-syncs replace the same row, and
+//! its `data_source` names the provider whose prices were kept. A
+//! [`QuoteProvenance`] record keeps both candidates, which provider supplied
+//! each field, and how far apart they were.
+//!
+//! Reconciliation rules for a day present in both sets:
+//! - **Agreed** - closes within `tolerance`; the primary is kept and any
+//!   missing OHLC/volume fields are filled from the secondary
+//! - **UnitCorrected** - closes differ by ~100x, the signature of a provider
+//!   reporting pence as pounds (GBp vs GBP); the candidate continuing the
+//!   previous consensus close wins
+//! - **Disputed** - any other disagreement; the candidate closer to the
+//!   previous consensus close wins, or the primary when there is no history
+//!
+//! Days only the primary returned are kept as **SingleSource**. Days only the
+//! secondary returned are ignored so the stored series matches the primary
+//! provider's calendar.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use num_traits::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::model::Quote;
+use crate::fx::currency::{get_normalization_rule, normalize_currency_code};
+
+/// Lower bound of the close ratio treated as a major/minor unit mix-up.
+const UNIT_RATIO_MIN: Decimal = dec!(90);
+/// Upper bound of the close ratio treated as a major/minor unit mix-up.
+const UNIT_RATIO_MAX: Decimal = dec!(110);
+
+/// Quote fields tracked in [`QuoteProvenance::field_sources`].
+const FIELDS: [&str; 6] = ["open", "high", "low", "close", "adjclose", "volume"];
+
+// =============================================================================
+// Configuration
+// =============================================================================
+
+/// Settings for dual-provider consensus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsensusConfig {
+    /// Whether sync queries a second provider and reconciles results (default: false)
+    pub enabled: bool,
+
+    /// Relative close difference (0.02 = 2%) still considered agreement (default: 0.02)
+    pub tolerance: f64,
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            tolerance: 0.02,
+        }
+    }
+}
+
+// =============================================================================
+// Provenance
+// =============================================================================
+
+/// Outcome of reconciling one day's quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ConsensusStatus {
+    /// Both providers agree within tolerance.
+    Agreed,
+    /// Providers differ by a unit factor; the consistent candidate was used.
+    UnitCorrected,
+    /// Providers disagree beyond tolerance.
+    Disputed,
+    /// Only one provider returned this day.
+    SingleSource,
+}
+
+impl ConsensusStatus {
+    /// Returns the storage identifier for this status.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConsensusStatus::Agreed => "AGREED",
+            ConsensusStatus::UnitCorrected => "UNIT_CORRECTED",
+            ConsensusStatus::Disputed => "DISPUTED",
+            ConsensusStatus::SingleSource => "SINGLE_SOURCE",
+        }
+    }
+}
+
+impl From<&str> for ConsensusStatus {
+    fn from(s: &str) -> Self {
+        match s {
+            "AGREED" => ConsensusStatus::Agreed,
+            "UNIT_CORRECTED" => ConsensusStatus::UnitCorrected,
+            "DISPUTED" => ConsensusStatus::Disputed,
+            _ => ConsensusStatus::SingleSource,
+        }
+    }
+}
+
+/// A provider's value for a quote, as returned before reconciliation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteCandidate {
+    /// Provider identifier (e.g. "YAHOO")
+    pub source: String,
+    /// Open price as reported by the provider
+    #[serde(default)]
+    pub open: Decimal,
+    /// High price as reported by the provider
+    #[serde(default)]
+    pub high: Decimal,
+    /// Low price as reported by the provider
+    #[serde(default)]
+    pub low: Decimal,
+    /// Close price as reported by the provider
+    pub close: Decimal,
+    /// Adjusted close as reported by the provider
+    #[serde(default)]
+    pub adjclose: Decimal,
+    /// Volume as reported by the provider
+    #[serde(default)]
+    pub volume: Decimal,
+    /// Currency as reported by the provider
+    pub currency: String,
+}
+
+/// Where the values of a stored quote came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteProvenance {
+    /// ID of the stored quote
+    pub quote_id: String,
+    /// Canonical asset identifier
+    pub asset_id: String,
+    /// Quote day
+    pub day: NaiveDate,
+    /// Reconciliation outcome
+    pub status: ConsensusStatus,
+    /// Provider that supplied each field (open, high, low, close, adjclose, volume)
+    pub field_sources: BTreeMap<String, String>,
+    /// Every provider's quote for the day as reported, primary first
+    pub candidates: Vec<QuoteCandidate>,
+    /// Relative close difference between candidates, after unit normalization
+    pub deviation: Option<Decimal>,
+    /// When the record was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl QuoteProvenance {
+    /// Close difference between the candidates in percent, if there were two.
+    pub fn deviation_pct(&self) -> Option<f64> {
+        self.deviation
+            .and_then(|d| (d * Decimal::ONE_HUNDRED).to_f64())
+    }
+
+    /// Short human-readable summary, e.g. "YAHOO vs FINNHUB disagree by 3.0%".
+    pub fn summary(&self) -> Option<String> {
+        let [first, second] = self.candidates.as_slice() else {
+            return None;
+        };
+        match self.status {
+            ConsensusStatus::Agreed => {
+                Some(format!("{} and {} agree", first.source, second.source))
+            }
+            ConsensusStatus::UnitCorrected => Some(format!(
+                "{} ({}) vs {} ({}) differ by a unit factor",
+                first.source, first.currency, second.source, second.currency
+            )),
+            ConsensusStatus::Disputed => Some(format!(
+                "{} vs {} disagree by {:.1}%",
+                first.source,
+                second.source,
+                self.deviation_pct().unwrap_or_default()
+            )),
+            ConsensusStatus::SingleSource => None,
+        }
+    }
+}
+
+// =============================================================================
+// Reconciliation
+// =============================================================================
+
+/// Reconciles primary and secondary provider quotes into one series.
+///
+/// `previous_close` is the last stored close before the range, used to decide
+/// disputes on the first day. Returns the quotes to store (one per primary day,
+/// in date order) and a provenance record for each of them.
+pub fn build_consensus(
+    mut primary: Vec<Quote>,
+    secondary: &[Quote],
+    previous_close: Option<Decimal>,
+    config: &ConsensusConfig,
+) -> (Vec<Quote>, Vec<QuoteProvenance>) {
+    primary.sort_by_key(|q| q.timestamp);
+    let by_day: HashMap<NaiveDate, &Quote> = secondary
+        .iter()
+        .map(|q| (q.timestamp.date_naive(), q))
+        .collect();
+    let tolerance = Decimal::try_from(config.tolerance).unwrap_or(dec!(0.02));
+
+    let mut quotes = Vec::with_capacity(primary.len());
+    let mut provenance = Vec::with_capacity(primary.len());
+    let mut previous_close = previous_close.filter(|c| *c > Decimal::ZERO);
+
+    for mut quote in primary {
+        let day = quote.timestamp.date_naive();
+        let primary_source = quote.data_source.as_str().to_string();
+        let mut record = QuoteProvenance {
+            quote_id: quote.id.clone(),
+            asset_id: quote.asset_id.clone(),
+            day,
+            status: ConsensusStatus::SingleSource,
+            field_sources: FIELDS
+                .iter()
+                .map(|f| (f.to_string(), primary_source.clone()))
+                .collect(),
+            candidates: vec![candidate(&quote)],
+            deviation: None,
+            created_at: Utc::now(),
+        };
+
+        if let Some(other) = by_day.get(&day).copied() {
+            reconcile(&mut quote, other, tolerance, previous_close, &mut record);
+        }
+
+        if quote.close > Decimal::ZERO {
+            previous_close = Some(quote.close);
+        }
+        quotes.push(quote);
+        provenance.push(record);
+    }
+
+    (quotes, provenance)
+}
+
+/// Reconciles one primary quote against the secondary quote for the same day.
+fn reconcile(
+    quote: &mut Quote,
+    other: &Quote,
+    tolerance: Decimal,
+    previous_close: Option<Decimal>,
+    record: &mut QuoteProvenance,
+) {
+    record.candidates.push(candidate(other));
+    let other_source = other.data_source.as_str().to_string();
+    let scale = unit_scale(&other.currency, &quote.currency);
+    let other_close = other.close * scale;
+
+    if quote.close <= Decimal::ZERO || other_close <= Decimal::ZERO {
+        record.status = ConsensusStatus::Disputed;
+        if quote.close <= Decimal::ZERO && other_close > Decimal::ZERO {
+            adopt(quote, other, scale, &other_source, record);
+        }
+        return;
+    }
+
+    let ratio = other_close / quote.close;
+    record.deviation = Some((ratio - Decimal::ONE).abs());
+
+    if (ratio - Decimal::ONE).abs() <= tolerance {
+        record.status = ConsensusStatus::Agreed;
+        fill_missing(quote, other, scale, &other_source, record);
+        return;
+    }
+
+    let is_unit_factor = (UNIT_RATIO_MIN..=UNIT_RATIO_MAX).contains(&ratio)
+        || (UNIT_RATIO_MIN..=UNIT_RATIO_MAX).contains(&(Decimal::ONE / ratio));
+
+    let prefer_other = previous_close
+        .map(|prev| relative_distance(other_close, prev) < relative_distance(quote.close, prev))
+        .unwrap_or(false);
+
+    record.status = if is_unit_factor && previous_close.is_some() {
+        ConsensusStatus::UnitCorrected
+    } else {
+        ConsensusStatus::Disputed
+    };
+    if prefer_other {
+        adopt(quote, other, scale, &other_source, record);
+    }
+}
+
+/// Replaces the price fields of `quote` with the secondary's values and
+/// attributes the quote to the secondary provider.
+fn adopt(
+    quote: &mut Quote,
+    other: &Quote,
+    scale: Decimal,
+    source: &str,
+    record: &mut QuoteProvenance,
+) {
+    quote.open = other.open * scale;
+    quote.high = other.high * scale;
+    quote.low = other.low * scale;
+    quote.close = other.close * scale;
+    quote.adjclose = other.adjclose * scale;
+    quote.data_source = other.data_source.clone();
+    for field in ["open", "high", "low", "close", "adjclose"] {
+        record
+            .field_sources
+            .insert(field.to_string(), source.to_string());
+    }
+}
+
+/// Fills zero-valued fields of `quote` from an agreeing secondary quote.
+fn fill_missing(
+    quote: &mut Quote,
+    other: &Quote,
+    scale: Decimal,
+    source: &str,
+    record: &mut QuoteProvenance,
+) {
+    let mut fill = |field: &str, target: &mut Decimal, value: Decimal| {
+        if target.is_zero() && !value.is_zero() {
+            *target = value;
+            record
+                .field_sources
+                .insert(field.to_string(), source.to_string());
+        }
+    };
+    fill("open", &mut quote.open, other.open * scale);
+    fill("high", &mut quote.high, other.high * scale);
+    fill("low", &mut quote.low, other.low * scale);
+    fill("adjclose", &mut quote.adjclose, other.adjclose * scale);
+    fill("volume", &mut quote.volume, other.volume);
+}
+
+/// Factor converting prices in `from` currency into `to` currency units when
+/// both denote the same currency at different scales (e.g. GBP -> GBp = 100).
+fn unit_scale(from: &str, to: &str) -> Decimal {
+    if from == to || normalize_currency_code(from) != normalize_currency_code(to) {
+        return Decimal::ONE;
+    }
+    let from_factor = get_normalization_rule(from).map_or(Decimal::ONE, |r| r.factor);
+    let to_factor = get_normalization_rule(to).map_or(Decimal::ONE, |r| r.factor);
+    from_factor / to_factor
+}
+
+fn relative_distance(value: Decimal, reference: Decimal) -> Decimal {
+    ((value - reference) / reference).abs()
+}
+
+fn candidate(quote: &Quote) -> QuoteCandidate {
+    QuoteCandidate {
+        source: quote.data_source.as_str().to_string(),
+        open: quote.open,
+        high: quote.high,
+        low: quote.low,
+        close: quote.close,
+        adjclose: quote.adjclose,
+        volume: quote.volume,
+        currency: quote.currency.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quotes::model::DataSource;
+    use chrono::TimeZone;
+
+    fn quote(day: u32, close: Decimal, currency: &str, source: DataSource) -> Quote {
+        let date = NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+        Quote {
+            id: format!("SEC:VOD:XLON_{}_{}", date, source.as_str()),
+            asset_id: "SEC:VOD:XLON".to_string(),
+            timestamp: Utc.from_utc_datetime(&date.and_hms_opt(16, 0, 0).unwrap()),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            adjclose: close,
+            volume: dec!(1000),
+            currency: currency.to_string(),
+            data_source: source,
+            created_at: Utc::now(),
+            notes: None,
+        }
+    }
+
+    fn yahoo(day: u32, close: Decimal) -> Quote {
+        quote(day, close, "GBp", DataSource::Yahoo)
+    }
+
+    fn finnhub(day: u32, close: Decimal) -> Quote {
+        quote(day, close, "GBp", DataSource::Finnhub)
+    }
+
+    #[test]
+    fn test_agreeing_quotes_keep_primary() {
+        let (quotes, provenance) = build_consensus(
+            vec![yahoo(4, dec!(70.0))],
+            &[finnhub(4, dec!(70.5))],
+            None,
+            &ConsensusConfig::default(),
+        );
+
+        assert_eq!(quotes[0].close, dec!(70.0));
+        assert_eq!(quotes[0].data_source, DataSource::Yahoo);
+        assert_eq!(provenance[0].status, ConsensusStatus::Agreed);
+        assert_eq!(provenance[0].candidates.len(), 2);
+        assert_eq!(provenance[0].field_sources["close"], "YAHOO");
+    }
+
+    #[test]
+    fn test_both_providers_quotes_are_recorded() {
+        let mut secondary = finnhub(4, dec!(70.5));
+        secondary.high = dec!(72);
+        secondary.volume = dec!(2500);
+        let (_, provenance) = build_consensus(
+            vec![yahoo(4, dec!(70.0))],
+            &[secondary],
+            None,
+            &ConsensusConfig::default(),
+        );
+
+        let [primary, other] = provenance[0].candidates.as_slice() else {
+            panic!("expected two candidates");
+        };
+        assert_eq!(primary.source, "YAHOO");
+        assert_eq!(primary.volume, dec!(1000));
+        assert_eq!(other.source, "FINNHUB");
+        assert_eq!(other.close, dec!(70.5));
+        assert_eq!(other.high, dec!(72));
+        assert_eq!(other.volume, dec!(2500));
+    }
+
+    #[test]
+    fn test_agreeing_quotes_fill_missing_fields() {
+        let mut primary = yahoo(4, dec!(70.0));
+        primary.volume = Decimal::ZERO;
+        let (quotes, provenance) = build_consensus(
+            vec![primary],
+            &[finnhub(4, dec!(70.0))],
+            None,
+            &ConsensusConfig::default(),
+        );
+
+        assert_eq!(quotes[0].volume, dec!(1000));
+        assert_eq!(provenance[0].field_sources["volume"], "FINNHUB");
+        assert_eq!(provenance[0].field_sources["close"], "YAHOO");
+    }
+
+    #[test]
+    fn test_mislabelled_unit_is_corrected_from_history() {
+        // Day 5: the primary reports pounds while labelling them pence.
+        let (quotes, provenance) = build_consensus(
+            vec![yahoo(4, dec!(70.0)), yahoo(5, dec!(0.71))],
+            &[finnhub(4, dec!(70.1)), finnhub(5, dec!(71.0))],
+            None,
+            &ConsensusConfig::default(),
+        );
+
+        assert_eq!(quotes[1].close, dec!(71.0));
+        assert_eq!(quotes[1].id, "SEC:VOD:XLON_2024-03-05_YAHOO");
+        assert_eq!(quotes[1].data_source, DataSource::Finnhub);
+        assert_eq!(quotes[0].data_source, DataSource::Yahoo);
+        assert_eq!(provenance[1].status, ConsensusStatus::UnitCorrected);
+        assert_eq!(provenance[1].field_sources["close"], "FINNHUB");
+    }
+
+    #[test]
+    fn test_declared_minor_unit_is_normalized_before_comparing() {
+        let mut secondary = finnhub(4, dec!(0.70));
+        secondary.currency = "GBP".to_string();
+        let (quotes, provenance) = build_consensus(
+            vec![yahoo(4, dec!(70.0))],
+            &[secondary],
+            None,
+            &ConsensusConfig::default(),
+        );
+
+        assert_eq!(quotes[0].close, dec!(70.0));
+        assert_eq!(provenance[0].status, ConsensusStatus::Agreed);
+        assert_eq!(provenance[0].deviation, Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_disagreement_without_history_keeps_primary() {
+        let (quotes, provenance) = build_consensus(
+            vec![yahoo(4, dec!(100))],
+            &[finnhub(4, dec!(103))],
+            None,
+            &ConsensusConfig::default(),
+        );
+
+        assert_eq!(quotes[0].close, dec!(100));
+        assert_eq!(quotes[0].data_source, DataSource::Yahoo);
+        assert_eq!(provenance[0].status, ConsensusStatus::Disputed);
+        assert_eq!(
+            provenance[0].summary().as_deref(),
+            Some("YAHOO vs FINNHUB disagree by 3.0%")
+        );
+    }
+
+    #[test]
+    fn test_disagreement_prefers_candidate_closer_to_history() {
+        let (quotes, provenance) = build_consensus(
+            vec![yahoo(4, dec!(100)), yahoo(5, dec!(130))],
+            &[finnhub(4, dec!(100)), finnhub(5, dec!(101))],
+            None,
+            &ConsensusConfig::default(),
+        );
+
+        assert_eq!(quotes[1].close, dec!(101));
+        assert_eq!(quotes[1].data_source, DataSource::Finnhub);
+        assert_eq!(provenance[1].status, ConsensusStatus::Disputed);
+        assert_eq!(provenance[1].field_sources["close"], "FINNHUB");
+        assert_eq!(provenance[1].field_sources["volume"], "YAHOO");
+    }
+
+    #[test]
+    fn test_first_day_dispute_uses_stored_close() {
+        let (quotes, provenance) = build_consensus(
+            vec![yahoo(5, dec!(0.71))],
+            &[finnhub(5, dec!(71.0))],
+            Some(dec!(70.0)),
+            &ConsensusConfig::default(),
+        );
+
+        assert_eq!(quotes[0].close, dec!(71.0));
+        assert_eq!(provenance[0].status, ConsensusStatus::UnitCorrected);
+    }
+
+    #[test]
+    fn test_primary_only_days_are_single_source() {
+        let (quotes, provenance) = build_consensus(
+            vec![yahoo(4, dec!(70)), yahoo(5, dec!(71))],
+            &[finnhub(4, dec!(70)), finnhub(6, dec!(72))],
+            None,
+            &ConsensusConfig::default(),
+        );
+
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(provenance[1].status, ConsensusStatus::SingleSource);
+        assert!(provenance[1].summary().is_none());
+    }
+}
@@ -36,9 +36,22 @@ pub const BACKFILL_SAFETY_MARGIN_DAYS: i64 = 7;
 /// (e.g., weekends, holidays, market not yet open).
 pub const MIN_SYNC_LOOKBACK_DAYS: i64 = 5;
 
+/// Minimum number of exchange trading days a sync window must span.
+/// Widens windows that would otherwise fall entirely within a long holiday
+/// (e.g., Golden Week, Christmas to New Year).
+pub const MIN_SYNC_TRADING_DAYS: u32 = 3;
+
 /// Days to look back when filling missing quotes for gap-filling operations.
 pub const QUOTE_LOOKBACK_DAYS: i64 = 14;
 
+/// Days of quote history loaded before a sync window for quality analysis,
+/// so spikes at the start of the window still have a preceding neighbour.
+pub const QUALITY_CONTEXT_DAYS: i64 = 10;
+
+/// Days searched before a sync window for the stored close that seeds
+/// consensus dispute resolution on the window's first day.
+pub const CONSENSUS_SEED_LOOKBACK_DAYS: i64 = 10;
+
 /// Minimum number of days of historical data required before first activity.
 /// If we have fewer trading days than this before first activity, trigger backfill.
 pub const MIN_HISTORICAL_TRADING_DAYS: i64 = 20;
@@ -57,3 +70,16 @@ pub const MAX_SYNC_ERRORS: i32 = 10;
 /// The per-provider rate limiter already enforces its own concurrency/delay,
 /// so this just controls how many assets we dispatch at once.
 pub const SYNC_CONCURRENCY: usize = 10;
+
+/// App setting key holding the JSON-encoded dual-provider consensus settings.
+pub const QUOTE_CONSENSUS_SETTINGS_KEY: &str = "quote_consensus";
+
+/// Default number of rows written per transaction by the bulk quote import.
+pub const BULK_IMPORT_BATCH_SIZE: usize = 5_000;
+
+/// Maximum number of unmatched symbols and error lines kept in a bulk import
+/// summary. Counts are always exact; only the sample lists are capped.
+pub const BULK_IMPORT_MAX_REPORTED_ISSUES: usize = 100;
+
+/// App setting key holding the JSON-encoded quote quality thresholds.
+pub const QUOTE_QUALITY_SETTINGS_KEY: &str = "quote_quality";
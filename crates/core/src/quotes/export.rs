@@ -0,0 +1,405 @@
+//! Market data export.
+//!
+//! Serializes stored quotes and FX rates for a set of assets and a date range
+//! into CSV, JSON Lines or Parquet, so price history can be analysed outside
+//! the app without reading the SQLite file directly.
+//!
+//! FX rates are stored as quotes on `AssetKind::Fx` assets, so both share the
+//! same row shape; the `kind` column tells them apart.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::model::Quote;
+use crate::assets::Asset;
+use crate::errors::{Error, Result, ValidationError};
+
+/// Output format for a market data export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteExportFormat {
+    #[default]
+    Csv,
+    Jsonl,
+    Parquet,
+}
+
+impl QuoteExportFormat {
+    /// MIME type for HTTP responses.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            QuoteExportFormat::Csv => "text/csv",
+            QuoteExportFormat::Jsonl => "application/x-ndjson",
+            QuoteExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+
+    /// File extension without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            QuoteExportFormat::Csv => "csv",
+            QuoteExportFormat::Jsonl => "jsonl",
+            QuoteExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
+impl FromStr for QuoteExportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(QuoteExportFormat::Csv),
+            "jsonl" | "ndjson" => Ok(QuoteExportFormat::Jsonl),
+            "parquet" => Ok(QuoteExportFormat::Parquet),
+            other => Err(ValidationError::InvalidInput(format!(
+                "Unsupported export format '{}'. Expected csv, jsonl or parquet",
+                other
+            ))
+            .into()),
+        }
+    }
+}
+
+/// Selection of market data to export.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteExportRequest {
+    /// Assets whose quotes should be exported.
+    #[serde(default)]
+    pub asset_ids: Vec<String>,
+    /// First day to include (inclusive). Defaults to the earliest stored quote.
+    pub start_date: Option<NaiveDate>,
+    /// Last day to include (inclusive). Defaults to today.
+    pub end_date: Option<NaiveDate>,
+    /// Output format.
+    #[serde(default)]
+    pub format: QuoteExportFormat,
+    /// Also export the FX rate series that convert the selected assets' currencies.
+    #[serde(default)]
+    pub include_fx: bool,
+}
+
+/// One exported quote or FX rate.
+///
+/// Field names stay snake_case so JSONL keys match the CSV and Parquet columns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketDataExportRow {
+    pub asset_id: String,
+    pub symbol: String,
+    /// Asset kind (`INVESTMENT`, `FX`, ...).
+    pub kind: String,
+    /// Date in YYYY-MM-DD format.
+    pub date: String,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub adjclose: Decimal,
+    pub volume: Decimal,
+    pub currency: String,
+    pub source: String,
+}
+
+impl MarketDataExportRow {
+    /// Build an export row from a stored quote and its asset.
+    pub fn from_quote(asset: &Asset, quote: &Quote) -> Self {
+        let symbol = asset
+            .display_code
+            .clone()
+            .or_else(|| asset.instrument_symbol.clone())
+            .unwrap_or_else(|| asset.id.clone());
+
+        Self {
+            asset_id: asset.id.clone(),
+            symbol,
+            kind: asset.kind.as_db_str().to_string(),
+            date: quote.timestamp.format("%Y-%m-%d").to_string(),
+            open: quote.open,
+            high: quote.high,
+            low: quote.low,
+            close: quote.close,
+            adjclose: quote.adjclose,
+            volume: quote.volume,
+            currency: quote.currency.clone(),
+            source: quote.data_source.as_str().to_string(),
+        }
+    }
+}
+
+/// Encode export rows in the requested format.
+pub fn encode_rows(rows: &[MarketDataExportRow], format: QuoteExportFormat) -> Result<Vec<u8>> {
+    match format {
+        QuoteExportFormat::Csv => encode_csv(rows),
+        QuoteExportFormat::Jsonl => encode_jsonl(rows),
+        QuoteExportFormat::Parquet => encode_parquet(rows),
+    }
+}
+
+fn encode_csv(rows: &[MarketDataExportRow]) -> Result<Vec<u8>> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    writer
+        .write_record(CSV_HEADERS)
+        .map_err(|e| Error::Unexpected(format!("Failed to write CSV header: {}", e)))?;
+    for row in rows {
+        writer
+            .serialize(row)
+            .map_err(|e| Error::Unexpected(format!("Failed to write CSV row: {}", e)))?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| Error::Unexpected(format!("Failed to finish CSV export: {}", e)))
+}
+
+/// Snake-case headers, shared by every export format; notebooks read these without renaming.
+const CSV_HEADERS: [&str; 12] = [
+    "asset_id", "symbol", "kind", "date", "open", "high", "low", "close", "adjclose", "volume",
+    "currency", "source",
+];
+
+fn encode_jsonl(rows: &[MarketDataExportRow]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    for row in rows {
+        serde_json::to_writer(&mut out, row)?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+#[cfg(feature = "parquet")]
+fn encode_parquet(rows: &[MarketDataExportRow]) -> Result<Vec<u8>> {
+    use num_traits::ToPrimitive;
+    use parquet::basic::Compression;
+    use parquet::data_type::{ByteArray, ByteArrayType, DoubleType};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+    use std::sync::Arc;
+
+    const SCHEMA: &str = "
+        message market_data {
+            REQUIRED BYTE_ARRAY asset_id (UTF8);
+            REQUIRED BYTE_ARRAY symbol (UTF8);
+            REQUIRED BYTE_ARRAY kind (UTF8);
+            REQUIRED BYTE_ARRAY date (UTF8);
+            REQUIRED DOUBLE open;
+            REQUIRED DOUBLE high;
+            REQUIRED DOUBLE low;
+            REQUIRED DOUBLE close;
+            REQUIRED DOUBLE adjclose;
+            REQUIRED DOUBLE volume;
+            REQUIRED BYTE_ARRAY currency (UTF8);
+            REQUIRED BYTE_ARRAY source (UTF8);
+        }
+    ";
+
+    let parquet_err = |e: parquet::errors::ParquetError| {
+        Error::Unexpected(format!("Failed to write Parquet export: {}", e))
+    };
+    let text = |f: fn(&MarketDataExportRow) -> &str| -> Vec<ByteArray> {
+        rows.iter().map(|r| ByteArray::from(f(r))).collect()
+    };
+    let number = |f: fn(&MarketDataExportRow) -> Decimal| -> Vec<f64> {
+        rows.iter()
+            .map(|r| f(r).to_f64().unwrap_or(f64::NAN))
+            .collect()
+    };
+
+    let schema = Arc::new(parse_message_type(SCHEMA).map_err(parquet_err)?);
+    let props = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build(),
+    );
+    let mut writer = SerializedFileWriter::new(Vec::new(), schema, props).map_err(parquet_err)?;
+
+    if !rows.is_empty() {
+        let text_columns = [
+            text(|r| r.asset_id.as_str()),
+            text(|r| r.symbol.as_str()),
+            text(|r| r.kind.as_str()),
+            text(|r| r.date.as_str()),
+        ];
+        let number_columns = [
+            number(|r| r.open),
+            number(|r| r.high),
+            number(|r| r.low),
+            number(|r| r.close),
+            number(|r| r.adjclose),
+            number(|r| r.volume),
+        ];
+        let trailing_columns = [text(|r| r.currency.as_str()), text(|r| r.source.as_str())];
+
+        let mut row_group = writer.next_row_group().map_err(parquet_err)?;
+        for values in &text_columns {
+            let mut column = row_group
+                .next_column()
+                .map_err(parquet_err)?
+                .ok_or_else(|| Error::Unexpected("Parquet schema mismatch".to_string()))?;
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(values, None, None)
+                .map_err(parquet_err)?;
+            column.close().map_err(parquet_err)?;
+        }
+        for values in &number_columns {
+            let mut column = row_group
+                .next_column()
+                .map_err(parquet_err)?
+                .ok_or_else(|| Error::Unexpected("Parquet schema mismatch".to_string()))?;
+            column
+                .typed::<DoubleType>()
+                .write_batch(values, None, None)
+                .map_err(parquet_err)?;
+            column.close().map_err(parquet_err)?;
+        }
+        for values in &trailing_columns {
+            let mut column = row_group
+                .next_column()
+                .map_err(parquet_err)?
+                .ok_or_else(|| Error::Unexpected("Parquet schema mismatch".to_string()))?;
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(values, None, None)
+                .map_err(parquet_err)?;
+            column.close().map_err(parquet_err)?;
+        }
+        row_group.close().map_err(parquet_err)?;
+    }
+
+    writer.into_inner().map_err(parquet_err)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn encode_parquet(_rows: &[MarketDataExportRow]) -> Result<Vec<u8>> {
+    Err(
+        ValidationError::InvalidInput("Parquet export is not enabled in this build".to_string())
+            .into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn row(asset_id: &str, kind: &str, date: &str, close: Decimal) -> MarketDataExportRow {
+        MarketDataExportRow {
+            asset_id: asset_id.to_string(),
+            symbol: asset_id.to_string(),
+            kind: kind.to_string(),
+            date: date.to_string(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            adjclose: close,
+            volume: Decimal::ZERO,
+            currency: "USD".to_string(),
+            source: "YAHOO".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!(
+            "CSV".parse::<QuoteExportFormat>().unwrap(),
+            QuoteExportFormat::Csv
+        );
+        assert_eq!(
+            "ndjson".parse::<QuoteExportFormat>().unwrap(),
+            QuoteExportFormat::Jsonl
+        );
+        assert!("xlsx".parse::<QuoteExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_encode_csv_has_snake_case_header() {
+        let rows = vec![row("AAPL", "INVESTMENT", "2024-01-02", dec!(185.64))];
+        let csv = String::from_utf8(encode_rows(&rows, QuoteExportFormat::Csv).unwrap()).unwrap();
+        let mut lines = csv.lines();
+
+        assert_eq!(
+            lines.next().unwrap(),
+            "asset_id,symbol,kind,date,open,high,low,close,adjclose,volume,currency,source"
+        );
+        let data = lines.next().unwrap();
+        assert!(data.starts_with("AAPL,AAPL,INVESTMENT,2024-01-02,185.64"));
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn test_encode_jsonl_one_object_per_line() {
+        let rows = vec![
+            row("AAPL", "INVESTMENT", "2024-01-02", dec!(185.64)),
+            row("FX:EUR:USD", "FX", "2024-01-02", dec!(1.0945)),
+        ];
+        let out = String::from_utf8(encode_rows(&rows, QuoteExportFormat::Jsonl).unwrap()).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        let fx: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(fx["asset_id"], "FX:EUR:USD");
+        assert_eq!(fx["kind"], "FX");
+    }
+
+    #[test]
+    fn test_formats_share_field_names() {
+        let rows = vec![row("AAPL", "INVESTMENT", "2024-01-02", dec!(185.64))];
+
+        let csv = String::from_utf8(encode_rows(&rows, QuoteExportFormat::Csv).unwrap()).unwrap();
+        let csv_header: Vec<&str> = csv.lines().next().unwrap().split(',').collect();
+        assert_eq!(csv_header, CSV_HEADERS);
+
+        let jsonl = encode_rows(&rows, QuoteExportFormat::Jsonl).unwrap();
+        let object: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(jsonl.trim_ascii_end()).unwrap();
+        let mut jsonl_keys: Vec<&str> = object.keys().map(String::as_str).collect();
+        let mut expected = CSV_HEADERS.to_vec();
+        jsonl_keys.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(jsonl_keys, expected);
+
+        #[cfg(feature = "parquet")]
+        {
+            use parquet::file::reader::{FileReader, SerializedFileReader};
+
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("export.parquet");
+            std::fs::write(
+                &path,
+                encode_rows(&rows, QuoteExportFormat::Parquet).unwrap(),
+            )
+            .unwrap();
+            let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+            let schema = reader.metadata().file_metadata().schema_descr_ptr();
+            let columns: Vec<&str> = schema.columns().iter().map(|c| c.name()).collect();
+            assert_eq!(columns, CSV_HEADERS);
+        }
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_encode_parquet_roundtrip() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let rows = vec![
+            row("AAPL", "INVESTMENT", "2024-01-02", dec!(185.64)),
+            row("AAPL", "INVESTMENT", "2024-01-03", dec!(184.25)),
+        ];
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.parquet");
+        std::fs::write(
+            &path,
+            encode_rows(&rows, QuoteExportFormat::Parquet).unwrap(),
+        )
+        .unwrap();
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+    }
+}
@@ -8,6 +8,10 @@
 //! - [`sync`] - Quote synchronization service
 //! - [`service`] - Unified quote service combining all operations
 //! - [`import`] - Quote import and validation utilities
+//! - [`bulk_import`] - Streaming import of large provider dump files
+//! - [`export`] - CSV, JSON Lines and Parquet export of stored quotes and FX rates
+//! - [`quality`] - Gap and spike detection for provider data
+//! - [`consensus`] - Dual-provider reconciliation and per-quote provenance
 //! - [`client`] - Market data client facade for the market-data crate
 //! - [`provider_settings`] - Provider settings models
 //! - [`constants`] - Configuration constants
@@ -30,18 +34,26 @@
 //! 6. **Unified Service** (`service.rs`) - Combines CRUD, sync, and provider operations
 //! 7. **Import** (`import.rs`) - Import validation and conversion utilities
 //! 8. **Provider Settings** (`provider_settings.rs`) - Settings management for providers
+//! 9. **Quality** (`quality.rs`) - Post-sync gap and outlier detection
+//! 10. **Consensus** (`consensus.rs`) - Cross-checking two providers before storing
+//! 11. **Bulk Import** (`bulk_import.rs`) - Batched history seeding from dump files
+//! 12. **Export** (`export.rs`) - Market data export for external analysis
 //!
 //! This separation allows:
 //! - Easy testing with mock implementations
 //! - Swapping storage backends without changing business logic
 //! - Clear boundaries between domain and infrastructure concerns
 
+pub mod bulk_import;
 pub mod client;
+pub mod consensus;
 pub mod constants;
 pub mod errors;
+pub mod export;
 pub mod import;
 pub mod model;
 pub mod provider_settings;
+pub mod quality;
 pub mod service;
 pub mod store;
 pub mod sync;
@@ -79,6 +91,18 @@ pub use import::{
     QuoteImport, QuoteImportService, QuoteValidator, ValidationStatus,
 };
 
+// Re-export bulk import types
+pub use bulk_import::{BulkImportOptions, BulkImportSummary};
+
+// Re-export export types
+pub use export::{MarketDataExportRow, QuoteExportFormat, QuoteExportRequest};
+
+// Re-export quality types
+pub use quality::{QuoteGap, QuoteQualityConfig, QuoteQualityReport, QuoteSpike};
+
+// Re-export consensus types
+pub use consensus::{ConsensusConfig, ConsensusStatus, QuoteCandidate, QuoteProvenance};
+
 // Re-export constants
 pub use constants::*;
 
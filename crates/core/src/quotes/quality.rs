@@ -0,0 +1,645 @@
+//! Quote quality analysis.
+//!
+//! Providers occasionally return incomplete or wrong data: missing trading days,
+//! or a single bad print (e.g. a price quoted in GBp instead of GBP, or a stray
+//! tick) that reverts the next day. Left alone, both propagate into daily
+//! valuations and show up as phantom drops in performance charts.
+//!
+//! This module detects:
+//! - **Gaps** - runs of expected trading days without any quote
+//! - **Spikes** - single-day moves whose log return is beyond a configurable
+//!   robust z-score and that revert on the following day
+//!
+//! Analysis is pure and has no dependencies on storage; `QuoteSyncService` runs
+//! it after each sync and the health center runs it over recent history.
+
+use chrono::NaiveDate;
+use num_traits::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::model::{DataSource, Quote};
+use crate::errors::{Error, Result, ValidationError};
+use wealthfolio_market_data::resolver::TradingCalendar;
+
+/// Scale factor converting a median absolute deviation into a standard
+/// deviation estimate for normally distributed data.
+const MAD_TO_STD: f64 = 1.4826;
+
+/// Floor for the return scale so perfectly flat series don't divide by zero.
+const MIN_RETURN_SCALE: f64 = 1e-4;
+
+// =============================================================================
+// Configuration
+// =============================================================================
+
+/// Thresholds controlling quote quality detection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteQualityConfig {
+    /// Robust z-score a daily log return must exceed (in both directions)
+    /// for the day to be flagged as a spike (default: 6.0)
+    pub spike_z_score: f64,
+
+    /// Minimum absolute daily move (as a fraction, 0.15 = 15%) for a spike.
+    /// Prevents flagging noise on very low-volatility series (default: 0.15)
+    pub min_spike_move: f64,
+
+    /// Minimum number of consecutive missing trading days reported as a gap.
+    /// Expected trading days come from the exchange calendar; exchanges without
+    /// one are treated as open every weekday, so the default of 2 keeps their
+    /// single-day holidays from being reported.
+    pub min_gap_trading_days: i64,
+
+    /// Whether to refetch flagged ranges from the next provider in priority order
+    /// and replace bad data with the fallback provider's quotes (default: true)
+    pub refetch_from_fallback: bool,
+
+    /// Days of history inspected by the health check (default: 90)
+    pub lookback_days: i64,
+}
+
+impl Default for QuoteQualityConfig {
+    fn default() -> Self {
+        Self {
+            spike_z_score: 6.0,
+            min_spike_move: 0.15,
+            min_gap_trading_days: 2,
+            refetch_from_fallback: true,
+            lookback_days: 90,
+        }
+    }
+}
+
+impl QuoteQualityConfig {
+    /// Rejects thresholds that would flag every day or nothing at all.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: &str| -> Result<()> {
+            Err(Error::Validation(ValidationError::InvalidInput(
+                msg.to_string(),
+            )))
+        };
+        if !(self.spike_z_score.is_finite() && self.spike_z_score > 0.0) {
+            return invalid("Spike z-score must be greater than 0");
+        }
+        if !(self.min_spike_move.is_finite() && self.min_spike_move >= 0.0) {
+            return invalid("Minimum spike move cannot be negative");
+        }
+        if self.min_gap_trading_days < 1 {
+            return invalid("Minimum gap must be at least one trading day");
+        }
+        if self.lookback_days < 1 {
+            return invalid("Lookback must be at least one day");
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Findings
+// =============================================================================
+
+/// A run of consecutive trading days with no quote.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteGap {
+    /// First missing trading day
+    pub start: NaiveDate,
+    /// Last missing trading day
+    pub end: NaiveDate,
+    /// Number of missing trading days in the run
+    pub missing_days: i64,
+    /// Number of days filled from a fallback provider
+    pub filled_days: i64,
+}
+
+/// A single-day price spike that reverts the next trading day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteSpike {
+    /// Day of the suspicious quote
+    pub day: NaiveDate,
+    /// ID of the suspicious quote
+    pub quote_id: String,
+    /// Source of the suspicious quote
+    pub source: DataSource,
+    /// Close price of the suspicious quote
+    pub close: Decimal,
+    /// Price implied by the neighbouring days (geometric mean)
+    pub expected: Decimal,
+    /// Robust z-score of the smaller of the two moves (into and out of the day)
+    pub z_score: f64,
+    /// Whether the quote was replaced with a fallback provider's value
+    pub resolved: bool,
+}
+
+/// Quality findings for a single asset.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteQualityReport {
+    pub asset_id: String,
+    pub gaps: Vec<QuoteGap>,
+    pub spikes: Vec<QuoteSpike>,
+}
+
+impl QuoteQualityReport {
+    /// Returns true when no gaps or spikes were found.
+    pub fn is_clean(&self) -> bool {
+        self.gaps.is_empty() && self.spikes.is_empty()
+    }
+
+    /// Total trading days still missing after any fallback fill.
+    pub fn unfilled_gap_days(&self) -> i64 {
+        self.gaps
+            .iter()
+            .map(|g| (g.missing_days - g.filled_days).max(0))
+            .sum()
+    }
+
+    /// Spikes that were not replaced by a fallback value.
+    pub fn unresolved_spikes(&self) -> Vec<&QuoteSpike> {
+        self.spikes.iter().filter(|s| !s.resolved).collect()
+    }
+}
+
+// =============================================================================
+// Analysis
+// =============================================================================
+
+/// Analyzes quotes for an asset and reports gaps and spikes within `[start, end]`.
+///
+/// Quotes outside the window are still used as neighbours for spike detection,
+/// so callers should include a few days of context on either side. When several
+/// quotes exist for the same day, manual quotes take precedence.
+///
+/// Gaps are only reported between the first and last available quote: missing
+/// data at the end of the series is staleness, which the price staleness check
+/// already covers. Expected trading days come from `calendar`.
+pub fn analyze_quotes(
+    asset_id: &str,
+    quotes: &[Quote],
+    start: NaiveDate,
+    end: NaiveDate,
+    config: &QuoteQualityConfig,
+    calendar: &TradingCalendar,
+) -> QuoteQualityReport {
+    let series = daily_series(quotes);
+
+    QuoteQualityReport {
+        asset_id: asset_id.to_string(),
+        gaps: detect_gaps(&series, start, end, config, calendar),
+        spikes: detect_spikes(&series, start, end, config),
+    }
+}
+
+/// Collapses quotes into one quote per day, preferring manual entries.
+fn daily_series(quotes: &[Quote]) -> BTreeMap<NaiveDate, &Quote> {
+    let mut by_day: BTreeMap<NaiveDate, &Quote> = BTreeMap::new();
+    for quote in quotes {
+        let day = quote.timestamp.date_naive();
+        match by_day.get(&day) {
+            Some(existing) if existing.data_source == DataSource::Manual => {}
+            Some(_) if quote.data_source != DataSource::Manual => {}
+            _ => {
+                by_day.insert(day, quote);
+            }
+        }
+    }
+    by_day
+}
+
+fn detect_gaps(
+    series: &BTreeMap<NaiveDate, &Quote>,
+    start: NaiveDate,
+    end: NaiveDate,
+    config: &QuoteQualityConfig,
+    calendar: &TradingCalendar,
+) -> Vec<QuoteGap> {
+    let (Some(first), Some(last)) = (series.keys().next(), series.keys().next_back()) else {
+        return Vec::new();
+    };
+
+    let from = start.max(*first);
+    let to = end.min(*last);
+    let mut gaps = Vec::new();
+    let mut current: Option<QuoteGap> = None;
+
+    let mut day = from;
+    while day <= to {
+        if calendar.is_trading_day(day) {
+            if series.contains_key(&day) {
+                if let Some(gap) = current.take() {
+                    gaps.push(gap);
+                }
+            } else {
+                match current.as_mut() {
+                    Some(gap) => {
+                        gap.end = day;
+                        gap.missing_days += 1;
+                    }
+                    None => {
+                        current = Some(QuoteGap {
+                            start: day,
+                            end: day,
+                            missing_days: 1,
+                            filled_days: 0,
+                        });
+                    }
+                }
+            }
+        }
+        match day.succ_opt() {
+            Some(next) => day = next,
+            None => break,
+        }
+    }
+    if let Some(gap) = current {
+        gaps.push(gap);
+    }
+
+    gaps.retain(|g| g.missing_days >= config.min_gap_trading_days.max(1));
+    gaps
+}
+
+fn detect_spikes(
+    series: &BTreeMap<NaiveDate, &Quote>,
+    start: NaiveDate,
+    end: NaiveDate,
+    config: &QuoteQualityConfig,
+) -> Vec<QuoteSpike> {
+    let points: Vec<(NaiveDate, &Quote, f64)> = series
+        .iter()
+        .filter_map(|(day, q)| q.close.to_f64().filter(|c| *c > 0.0).map(|c| (*day, *q, c)))
+        .collect();
+
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    let returns: Vec<f64> = points.windows(2).map(|w| (w[1].2 / w[0].2).ln()).collect();
+    let scale = robust_scale(&returns);
+
+    let mut spikes = Vec::new();
+    for i in 1..points.len() - 1 {
+        let (day, quote, _) = points[i];
+        if day < start || day > end {
+            continue;
+        }
+
+        let move_in = returns[i - 1];
+        let move_out = returns[i];
+
+        // A spike jumps away and comes straight back: opposite signs, both large.
+        if move_in.signum() == move_out.signum() {
+            continue;
+        }
+        let smaller = move_in.abs().min(move_out.abs());
+        if smaller < config.min_spike_move {
+            continue;
+        }
+        let z_score = smaller / scale;
+        if z_score < config.spike_z_score {
+            continue;
+        }
+
+        let expected = (points[i - 1].2 * points[i + 1].2).sqrt();
+        spikes.push(QuoteSpike {
+            day,
+            quote_id: quote.id.clone(),
+            source: quote.data_source.clone(),
+            close: quote.close,
+            expected: Decimal::from_f64_retain(expected)
+                .map(|d| d.round_dp(6))
+                .unwrap_or(Decimal::ZERO),
+            z_score,
+            resolved: false,
+        });
+    }
+
+    spikes
+}
+
+/// Robust standard deviation estimate (MAD-based) of a return series.
+fn robust_scale(returns: &[f64]) -> f64 {
+    let median = median(returns);
+    let deviations: Vec<f64> = returns.iter().map(|r| (r - median).abs()).collect();
+    (median_of(deviations) * MAD_TO_STD).max(MIN_RETURN_SCALE)
+}
+
+fn median(values: &[f64]) -> f64 {
+    median_of(values.to_vec())
+}
+
+fn median_of(mut values: Vec<f64>) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Returns true when a fallback close is a better fit for the neighbours than
+/// the flagged spike, i.e. the fallback provider does not share the bad print.
+pub fn is_plausible_replacement(spike: &QuoteSpike, fallback_close: Decimal) -> bool {
+    let (Some(expected), Some(bad), Some(candidate)) = (
+        spike.expected.to_f64(),
+        spike.close.to_f64(),
+        fallback_close.to_f64(),
+    ) else {
+        return false;
+    };
+    if expected <= 0.0 || bad <= 0.0 || candidate <= 0.0 {
+        return false;
+    }
+    let bad_dev = (bad / expected).ln().abs();
+    let candidate_dev = (candidate / expected).ln().abs();
+    candidate_dev < bad_dev / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+    use rust_decimal_macros::dec;
+
+    fn quote(day: NaiveDate, close: Decimal, source: DataSource) -> Quote {
+        Quote {
+            id: format!("SEC:TEST:XNAS_{}_{}", day, source.as_str()),
+            asset_id: "SEC:TEST:XNAS".to_string(),
+            timestamp: Utc.from_utc_datetime(&day.and_hms_opt(16, 0, 0).unwrap()),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            adjclose: close,
+            volume: Decimal::ZERO,
+            currency: "USD".to_string(),
+            data_source: source,
+            created_at: Utc::now(),
+            notes: None,
+        }
+    }
+
+    /// Builds a weekday series starting Monday 2024-01-08 with small oscillations.
+    fn weekday_series(days: usize) -> Vec<Quote> {
+        let mut quotes = Vec::new();
+        let mut day = NaiveDate::from_ymd_opt(2024, 1, 8).unwrap();
+        let mut i = 0;
+        while quotes.len() < days {
+            if TradingCalendar::weekdays().is_trading_day(day) {
+                let close = if i % 2 == 0 { dec!(100) } else { dec!(101) };
+                quotes.push(quote(day, close, DataSource::Yahoo));
+                i += 1;
+            }
+            day += Duration::days(1);
+        }
+        quotes
+    }
+
+    fn full_range(quotes: &[Quote]) -> (NaiveDate, NaiveDate) {
+        (
+            quotes.first().unwrap().timestamp.date_naive(),
+            quotes.last().unwrap().timestamp.date_naive(),
+        )
+    }
+
+    #[test]
+    fn test_clean_series_has_no_findings() {
+        let quotes = weekday_series(20);
+        let (start, end) = full_range(&quotes);
+
+        let report = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &QuoteQualityConfig::default(),
+            TradingCalendar::weekdays(),
+        );
+
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn test_single_day_spike_detected() {
+        let mut quotes = weekday_series(20);
+        // One bad print 40% below the surrounding days
+        quotes[10].close = dec!(60);
+        let (start, end) = full_range(&quotes);
+
+        let report = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &QuoteQualityConfig::default(),
+            TradingCalendar::weekdays(),
+        );
+
+        assert_eq!(report.spikes.len(), 1);
+        assert_eq!(report.spikes[0].day, quotes[10].timestamp.date_naive());
+        assert_eq!(report.spikes[0].close, dec!(60));
+        assert!(report.gaps.is_empty());
+    }
+
+    #[test]
+    fn test_persistent_move_is_not_a_spike() {
+        let mut quotes = weekday_series(20);
+        // A real 40% drop that persists should not be flagged
+        for q in quotes.iter_mut().skip(10) {
+            q.close = dec!(60);
+        }
+        let (start, end) = full_range(&quotes);
+
+        let report = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &QuoteQualityConfig::default(),
+            TradingCalendar::weekdays(),
+        );
+
+        assert!(report.spikes.is_empty());
+    }
+
+    #[test]
+    fn test_small_reversal_below_min_move_ignored() {
+        let mut quotes = weekday_series(20);
+        quotes[10].close = dec!(95);
+        let (start, end) = full_range(&quotes);
+
+        let report = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &QuoteQualityConfig::default(),
+            TradingCalendar::weekdays(),
+        );
+
+        assert!(report.spikes.is_empty());
+    }
+
+    #[test]
+    fn test_gap_detected_and_weekends_ignored() {
+        let mut quotes = weekday_series(20);
+        // Remove three consecutive trading days
+        quotes.drain(5..8);
+        let (start, end) = full_range(&quotes);
+
+        let report = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &QuoteQualityConfig::default(),
+            TradingCalendar::weekdays(),
+        );
+
+        assert_eq!(report.gaps.len(), 1);
+        assert_eq!(report.gaps[0].missing_days, 3);
+        assert_eq!(report.unfilled_gap_days(), 3);
+    }
+
+    #[test]
+    fn test_exchange_holiday_is_not_a_gap() {
+        let mut quotes = weekday_series(20);
+        // Drop Monday 2024-01-15 (Martin Luther King Jr. Day, NYSE/NASDAQ closed)
+        let mlk_day = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        quotes.retain(|q| q.timestamp.date_naive() != mlk_day);
+        let (start, end) = full_range(&quotes);
+        let config = QuoteQualityConfig {
+            min_gap_trading_days: 1,
+            ..QuoteQualityConfig::default()
+        };
+
+        let weekdays = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &config,
+            TradingCalendar::weekdays(),
+        );
+        assert_eq!(weekdays.gaps.len(), 1);
+
+        let nasdaq = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &config,
+            crate::utils::time_utils::exchange_calendar(Some("XNAS")),
+        );
+        assert!(nasdaq.gaps.is_empty());
+    }
+
+    #[test]
+    fn test_single_missing_day_below_threshold() {
+        let mut quotes = weekday_series(20);
+        quotes.remove(5);
+        let (start, end) = full_range(&quotes);
+
+        let report = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &QuoteQualityConfig::default(),
+            TradingCalendar::weekdays(),
+        );
+
+        assert!(report.gaps.is_empty());
+    }
+
+    #[test]
+    fn test_manual_quote_takes_precedence() {
+        let mut quotes = weekday_series(20);
+        let day = quotes[10].timestamp.date_naive();
+        quotes[10].close = dec!(60);
+        quotes.push(quote(day, dec!(100), DataSource::Manual));
+        let (start, _) = full_range(&quotes);
+        let end = quotes[19].timestamp.date_naive();
+
+        let report = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &QuoteQualityConfig::default(),
+            TradingCalendar::weekdays(),
+        );
+
+        assert!(report.spikes.is_empty());
+    }
+
+    #[test]
+    fn test_findings_limited_to_window() {
+        let mut quotes = weekday_series(20);
+        quotes[3].close = dec!(60);
+        let start = quotes[8].timestamp.date_naive();
+        let end = quotes[19].timestamp.date_naive();
+
+        let report = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &QuoteQualityConfig::default(),
+            TradingCalendar::weekdays(),
+        );
+
+        assert!(report.spikes.is_empty());
+    }
+
+    #[test]
+    fn test_plausible_replacement() {
+        let mut quotes = weekday_series(20);
+        quotes[10].close = dec!(60);
+        let (start, end) = full_range(&quotes);
+        let report = analyze_quotes(
+            "SEC:TEST:XNAS",
+            &quotes,
+            start,
+            end,
+            &QuoteQualityConfig::default(),
+            TradingCalendar::weekdays(),
+        );
+        let spike = &report.spikes[0];
+
+        assert!(is_plausible_replacement(spike, dec!(100.5)));
+        assert!(!is_plausible_replacement(spike, dec!(61)));
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(QuoteQualityConfig::default().validate().is_ok());
+
+        let config = QuoteQualityConfig {
+            spike_z_score: 0.0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = QuoteQualityConfig {
+            min_gap_trading_days: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config: QuoteQualityConfig = serde_json::from_str(
+            r#"{"spikeZScore":4.5,"minSpikeMove":0.1,"minGapTradingDays":3,
+                "refetchFromFallback":false,"lookbackDays":30}"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.lookback_days, 30);
+    }
+}
@@ -11,14 +11,19 @@ use async_trait::async_trait;
 use chrono::{Duration, NaiveDate, TimeZone, Utc};
 use log::{debug, info};
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use crate::utils::time_utils;
 
+use super::bulk_import::{self, BulkImportOptions, BulkImportSummary};
 use super::client::{MarketDataClient, ProviderConfig};
+use super::consensus::{ConsensusConfig, QuoteProvenance};
+use super::export::{self, MarketDataExportRow, QuoteExportRequest};
 use super::import::{ImportValidationStatus, QuoteConverter, QuoteImport, QuoteValidator};
 use super::model::{DataSource, LatestQuotePair, Quote, ResolvedQuote, SymbolSearchResult};
+use super::quality::QuoteQualityConfig;
 use super::store::{ProviderSettingsStore, QuoteStore};
 use super::sync::{QuoteSyncService, QuoteSyncServiceTrait, SyncResult};
 use super::sync_state::{QuoteSyncState, SymbolSyncPlan, SyncMode, SyncStateStore};
@@ -31,6 +36,7 @@ use crate::errors::Result;
 use crate::fx::currency::{get_normalization_rule, normalize_currency_code};
 use crate::secrets::SecretStore;
 
+use wealthfolio_market_data::resolver::mic_to_trading_calendar;
 use wealthfolio_market_data::{exchanges_for_currency, mic_to_exchange_name};
 
 /// Provider information combining static info with settings.
@@ -167,6 +173,16 @@ pub trait QuoteServiceTrait: Send + Sync {
         end: NaiveDate,
     ) -> Result<HashMap<NaiveDate, HashMap<String, Quote>>>;
 
+    /// Get consensus provenance for an asset's quotes within a date range.
+    ///
+    /// Only quotes synced with dual-provider consensus enabled have provenance.
+    fn get_quote_provenance(
+        &self,
+        asset_id: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<QuoteProvenance>>;
+
     /// Add a new quote.
     async fn add_quote(&self, quote: &Quote) -> Result<Quote>;
 
@@ -315,6 +331,18 @@ pub trait QuoteServiceTrait: Send + Sync {
         enabled: bool,
     ) -> Result<()>;
 
+    /// Get the current dual-provider consensus settings.
+    fn get_consensus_config(&self) -> ConsensusConfig;
+
+    /// Replace the dual-provider consensus settings used by future syncs.
+    async fn update_consensus_config(&self, config: ConsensusConfig) -> Result<()>;
+
+    /// Get the current quote quality thresholds.
+    fn get_quality_config(&self) -> QuoteQualityConfig;
+
+    /// Replace the quote quality thresholds used by future syncs and health checks.
+    async fn update_quality_config(&self, config: QuoteQualityConfig) -> Result<()>;
+
     // =========================================================================
     // Quote Import
     // =========================================================================
@@ -345,6 +373,25 @@ pub trait QuoteServiceTrait: Send + Sync {
         quotes: Vec<QuoteImport>,
         overwrite: bool,
     ) -> Result<Vec<QuoteImport>>;
+
+    /// Stream quote history from a dump file, directory or zip archive.
+    ///
+    /// Intended for seeding long histories (Stooq/EOD dumps, Parquet) that
+    /// are too large for `import_quotes`. Tickers are matched to existing
+    /// assets; unknown tickers are reported in the summary.
+    async fn bulk_import_quotes(
+        &self,
+        path: &Path,
+        options: BulkImportOptions,
+    ) -> Result<BulkImportSummary>;
+
+    // =========================================================================
+    // Quote Export
+    // =========================================================================
+
+    /// Export stored quotes (and optionally FX rates) for the requested assets
+    /// and date range, encoded in the requested format.
+    fn export_market_data(&self, request: &QuoteExportRequest) -> Result<Vec<u8>>;
 }
 
 /// Unified quote service implementation.
@@ -373,6 +420,10 @@ where
     /// Sync service.
     #[allow(clippy::type_complexity)]
     sync_service: Arc<RwLock<Option<Arc<QuoteSyncService<Q, S, A, R>>>>>,
+    /// Dual-provider consensus settings applied to the sync service.
+    consensus_config: std::sync::RwLock<ConsensusConfig>,
+    /// Quote quality thresholds applied to the sync service.
+    quality_config: std::sync::RwLock<QuoteQualityConfig>,
 }
 
 impl<Q, S, PS, A, R> QuoteService<Q, S, PS, A, R>
@@ -425,6 +476,8 @@ where
             client: client_arc,
             secret_store,
             sync_service: Arc::new(RwLock::new(Some(Arc::new(sync_service)))),
+            consensus_config: std::sync::RwLock::new(ConsensusConfig::default()),
+            quality_config: std::sync::RwLock::new(QuoteQualityConfig::default()),
         })
     }
 
@@ -444,16 +497,28 @@ where
         *self.client.write().await = new_client;
 
         // Refresh sync service with updated client
+        self.refresh_sync_service().await;
+
+        Ok(())
+    }
+
+    /// Rebuild the sync service with the current client, consensus and quality settings.
+    async fn refresh_sync_service(&self) {
         let new_sync = QuoteSyncService::new(
             self.client.clone(),
             self.quote_store.clone(),
             self.sync_state_store.clone(),
             self.asset_repo.clone(),
             self.activity_repo.clone(),
-        );
+        )
+        .with_consensus_config(
+            self.consensus_config
+                .read()
+                .map(|c| c.clone())
+                .unwrap_or_default(),
+        )
+        .with_quality_config(self.get_quality_config());
         *self.sync_service.write().await = Some(Arc::new(new_sync));
-
-        Ok(())
     }
 
     /// Get the sync service.
@@ -743,6 +808,7 @@ where
                 for quote in quotes.iter_mut() {
                     reconcile_quote_currency(quote, asset);
                 }
+                drop_non_trading_day_quotes(&mut quotes, asset);
             }
             all_quotes.extend(quotes);
         }
@@ -771,6 +837,16 @@ where
         Ok(daily)
     }
 
+    fn get_quote_provenance(
+        &self,
+        asset_id: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<QuoteProvenance>> {
+        self.quote_store
+            .provenance_range(&AssetId::new(asset_id), Day::new(start), Day::new(end))
+    }
+
     async fn add_quote(&self, quote: &Quote) -> Result<Quote> {
         self.quote_store.save_quote(quote).await
     }
@@ -1258,6 +1334,37 @@ where
         Ok(())
     }
 
+    fn get_consensus_config(&self) -> ConsensusConfig {
+        self.consensus_config
+            .read()
+            .map(|c| c.clone())
+            .unwrap_or_default()
+    }
+
+    async fn update_consensus_config(&self, config: ConsensusConfig) -> Result<()> {
+        if let Ok(mut current) = self.consensus_config.write() {
+            *current = config;
+        }
+        self.refresh_sync_service().await;
+        Ok(())
+    }
+
+    fn get_quality_config(&self) -> QuoteQualityConfig {
+        self.quality_config
+            .read()
+            .map(|c| c.clone())
+            .unwrap_or_default()
+    }
+
+    async fn update_quality_config(&self, config: QuoteQualityConfig) -> Result<()> {
+        config.validate()?;
+        if let Ok(mut current) = self.quality_config.write() {
+            *current = config;
+        }
+        self.refresh_sync_service().await;
+        Ok(())
+    }
+
     // =========================================================================
     // Quote Import
     // =========================================================================
@@ -1502,6 +1609,80 @@ where
 
         Ok(quotes)
     }
+
+    async fn bulk_import_quotes(
+        &self,
+        path: &Path,
+        options: BulkImportOptions,
+    ) -> Result<BulkImportSummary> {
+        info!(
+            "Bulk importing quotes from {} (overwrite={})",
+            path.display(),
+            options.overwrite
+        );
+        let assets = self.asset_repo.list()?;
+        bulk_import::import_from_path(self.quote_store.as_ref(), &assets, path, &options).await
+    }
+
+    fn export_market_data(&self, request: &QuoteExportRequest) -> Result<Vec<u8>> {
+        let start = request
+            .start_date
+            .map(Day::new)
+            .unwrap_or_else(|| Day::from_ymd(1900, 1, 1).unwrap());
+        let end = request.end_date.map(Day::new).unwrap_or_else(Day::today);
+        if start > end {
+            return Err(crate::errors::ValidationError::InvalidInput(
+                "Export start date must not be after end date".to_string(),
+            )
+            .into());
+        }
+
+        let mut assets = self.asset_repo.list_by_asset_ids(&request.asset_ids)?;
+        if request.include_fx {
+            // Only the pairs that convert one of the selected assets' currencies
+            let selected: HashSet<String> = assets.iter().map(|a| a.id.clone()).collect();
+            let currencies: HashSet<String> = assets
+                .iter()
+                .filter(|a| a.kind != AssetKind::Fx)
+                .map(|a| normalize_currency_code(&a.quote_ccy).to_string())
+                .collect();
+            assets.extend(self.asset_repo.list()?.into_iter().filter(|a| {
+                a.kind == AssetKind::Fx
+                    && !selected.contains(&a.id)
+                    && (currencies.contains(&a.quote_ccy)
+                        || a.instrument_symbol
+                            .as_ref()
+                            .is_some_and(|base| currencies.contains(base)))
+            }));
+        }
+        if assets.is_empty() {
+            return Err(crate::errors::ValidationError::InvalidInput(
+                "No assets selected for export".to_string(),
+            )
+            .into());
+        }
+        assets.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut rows = Vec::new();
+        for asset in &assets {
+            let quotes = self
+                .quote_store
+                .range(&AssetId::new(&asset.id), start, end, None)?;
+            rows.extend(
+                quotes
+                    .iter()
+                    .map(|quote| MarketDataExportRow::from_quote(asset, quote)),
+            );
+        }
+
+        info!(
+            "Exporting {} market data rows for {} assets as {}",
+            rows.len(),
+            assets.len(),
+            request.format.extension()
+        );
+        export::encode_rows(&rows, request.format)
+    }
 }
 
 // =============================================================================
@@ -1599,6 +1780,26 @@ fn mic_to_yahoo_suffix(mic: &str) -> Option<&'static str> {
 // Gap Filling Helper
 // =============================================================================
 
+/// Removes provider quotes dated on days the asset's exchange was closed.
+///
+/// Some providers emit a bar for exchange holidays (often a stale repeat or a
+/// partial print). Dropping it lets the valuation grid carry the last real
+/// close forward instead. Manual quotes and assets whose exchange has no
+/// trading calendar (crypto, FX, unknown MICs) are left untouched.
+fn drop_non_trading_day_quotes(quotes: &mut Vec<Quote>, asset: &Asset) {
+    let Some(calendar) = asset
+        .instrument_exchange_mic
+        .as_deref()
+        .and_then(mic_to_trading_calendar)
+    else {
+        return;
+    };
+    quotes.retain(|quote| {
+        quote.data_source == DataSource::Manual
+            || calendar.is_trading_day(quote.timestamp.date_naive())
+    });
+}
+
 /// Fills missing quotes for weekends and holidays by carrying forward the last known quote.
 ///
 /// This is critical for portfolio valuation which needs a quote for every day in the range.
@@ -1740,4 +1941,49 @@ mod tests {
         reconcile_quote_currency(&mut quote, &asset);
         assert_eq!(quote.currency, "GBp");
     }
+
+    #[test]
+    fn test_drop_non_trading_day_quotes_uses_exchange_calendar() {
+        let quote_on = |day: u32, source: DataSource| Quote {
+            id: format!("q_{}", day),
+            created_at: Utc::now(),
+            data_source: source,
+            timestamp: Utc.with_ymd_and_hms(2024, 12, day, 21, 0, 0).unwrap(),
+            asset_id: "asset_1".to_string(),
+            open: dec!(100),
+            high: dec!(100),
+            low: dec!(100),
+            close: dec!(100),
+            adjclose: dec!(100),
+            volume: dec!(0),
+            currency: "USD".to_string(),
+            notes: None,
+        };
+        let quotes = vec![
+            quote_on(24, DataSource::Yahoo),
+            quote_on(25, DataSource::Yahoo),
+            quote_on(25, DataSource::Manual),
+            quote_on(26, DataSource::Yahoo),
+        ];
+
+        let listed = Asset {
+            id: "asset_1".to_string(),
+            instrument_exchange_mic: Some("XNYS".to_string()),
+            ..Default::default()
+        };
+        let mut kept = quotes.clone();
+        drop_non_trading_day_quotes(&mut kept, &listed);
+        assert_eq!(kept.len(), 3);
+        assert!(!kept
+            .iter()
+            .any(|q| q.data_source == DataSource::Yahoo && q.id == "q_25"));
+
+        let unlisted = Asset {
+            id: "asset_1".to_string(),
+            ..Default::default()
+        };
+        let mut kept = quotes.clone();
+        drop_non_trading_day_quotes(&mut kept, &unlisted);
+        assert_eq!(kept.len(), 4);
+    }
 }
@@ -15,6 +15,7 @@
 mod tests {
     use crate::errors::{DatabaseError, Result};
     use crate::quotes::{
+        consensus::QuoteProvenance,
         model::{DataSource, LatestQuotePair, Quote},
         store::QuoteStore,
         types::{AssetId, Day, QuoteSource},
@@ -34,6 +35,7 @@ mod tests {
     struct MockQuoteStore {
         quotes: Arc<Mutex<Vec<Quote>>>,
         fail_on_save: Arc<Mutex<bool>>,
+        provenance: Arc<Mutex<Vec<QuoteProvenance>>>,
     }
 
     impl MockQuoteStore {
@@ -45,7 +47,7 @@ mod tests {
         fn with_quotes(quotes: Vec<Quote>) -> Self {
             Self {
                 quotes: Arc::new(Mutex::new(quotes)),
-                fail_on_save: Arc::new(Mutex::new(false)),
+                ..Self::default()
             }
         }
 
@@ -97,6 +99,32 @@ mod tests {
             Ok(count)
         }
 
+        async fn bulk_insert_quotes(
+            &self,
+            quotes_to_insert: &[Quote],
+            overwrite: bool,
+        ) -> Result<usize> {
+            let mut quotes = self.quotes.lock().unwrap();
+            let mut count = 0;
+            for quote in quotes_to_insert {
+                let same = |q: &Quote| {
+                    q.id == quote.id
+                        || (q.asset_id == quote.asset_id
+                            && q.timestamp.date_naive() == quote.timestamp.date_naive()
+                            && q.data_source == quote.data_source)
+                };
+                if quotes.iter().any(same) {
+                    if !overwrite {
+                        continue;
+                    }
+                    quotes.retain(|q| !same(q));
+                }
+                quotes.push(quote.clone());
+                count += 1;
+            }
+            Ok(count)
+        }
+
         async fn delete_quotes_for_asset(&self, asset_id: &AssetId) -> Result<usize> {
             let mut quotes = self.quotes.lock().unwrap();
             let original_len = quotes.len();
@@ -291,6 +319,31 @@ mod tests {
             }
             Ok(result)
         }
+
+        async fn upsert_provenance(&self, records: &[QuoteProvenance]) -> Result<usize> {
+            let mut provenance = self.provenance.lock().unwrap();
+            for record in records {
+                provenance.retain(|p| p.quote_id != record.quote_id);
+                provenance.push(record.clone());
+            }
+            Ok(records.len())
+        }
+
+        fn provenance_range(
+            &self,
+            asset_id: &AssetId,
+            start: Day,
+            end: Day,
+        ) -> Result<Vec<QuoteProvenance>> {
+            let provenance = self.provenance.lock().unwrap();
+            Ok(provenance
+                .iter()
+                .filter(|p| {
+                    p.asset_id == asset_id.as_str() && p.day >= start.date() && p.day <= end.date()
+                })
+                .cloned()
+                .collect())
+        }
     }
 
     // =========================================================================
@@ -565,6 +618,72 @@ mod tests {
         assert!(store.get_latest_quote("MSFT").is_ok());
     }
 
+    // =========================================================================
+    // Bulk Import Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_bulk_import_writes_batches_and_skips_existing() {
+        use crate::assets::Asset;
+        use crate::quotes::bulk_import::{import_from_path, BulkImportOptions};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aapl.us.txt");
+        std::fs::write(
+            &path,
+            "Date,Open,High,Low,Close,Volume\n\
+             20240102,187.15,188.44,183.88,185.64,82488674\n\
+             20240103,184.22,185.88,183.43,184.25,58414460\n\
+             20240104,182.15,183.08,180.88,181.91,71983570\n",
+        )
+        .unwrap();
+
+        let assets = vec![Asset {
+            id: "SEC:AAPL:XNAS".to_string(),
+            instrument_symbol: Some("AAPL".to_string()),
+            instrument_exchange_mic: Some("XNAS".to_string()),
+            quote_ccy: "USD".to_string(),
+            ..Default::default()
+        }];
+        let store = MockQuoteStore::new();
+        let options = BulkImportOptions {
+            batch_size: 2,
+            ..Default::default()
+        };
+
+        let summary = import_from_path(&store, &assets, &path, &options)
+            .await
+            .unwrap();
+        assert_eq!(summary.files_read, 1);
+        assert_eq!(summary.rows_read, 3);
+        assert_eq!(summary.imported, 3);
+        assert_eq!(
+            store.get_latest_quote("SEC:AAPL:XNAS").unwrap().close,
+            dec!(181.91)
+        );
+
+        let again = import_from_path(&store, &assets, &path, &options)
+            .await
+            .unwrap();
+        assert_eq!(again.imported, 0);
+        assert_eq!(again.skipped_duplicates, 3);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_import_rejects_missing_path() {
+        use crate::quotes::bulk_import::{import_from_path, BulkImportOptions};
+
+        let store = MockQuoteStore::new();
+        let result = import_from_path(
+            &store,
+            &[],
+            std::path::Path::new("/nonexistent/quotes.zip"),
+            &BulkImportOptions::default(),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
     // =========================================================================
     // Method Mapping Verification Tests
     // =========================================================================
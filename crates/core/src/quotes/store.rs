@@ -17,6 +17,7 @@ use async_trait::async_trait;
 use chrono::NaiveDate;
 use std::collections::HashMap;
 
+use super::consensus::QuoteProvenance;
 use super::model::{LatestQuotePair, Quote};
 use super::types::{AssetId, Day, QuoteSource};
 use crate::errors::Result;
@@ -83,6 +84,22 @@ pub trait QuoteStore: Send + Sync {
     /// The number of quotes that were inserted or updated
     async fn upsert_quotes(&self, quotes: &[Quote]) -> Result<usize>;
 
+    /// Inserts a large batch of quotes in a single transaction.
+    ///
+    /// Used by the bulk history import. Provider quotes are written in plain
+    /// chunks; manual quotes are matched to existing manual rows by asset and
+    /// day and recorded in the sync outbox, like `upsert_quotes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `quotes` - The quotes to insert
+    /// * `overwrite` - Replace existing quotes instead of skipping them
+    ///
+    /// # Returns
+    ///
+    /// The number of quotes that were written
+    async fn bulk_insert_quotes(&self, quotes: &[Quote], overwrite: bool) -> Result<usize>;
+
     /// Deletes all quotes for a specific asset.
     ///
     /// This is useful when an asset is deleted and all its quote history
@@ -209,6 +226,31 @@ pub trait QuoteStore: Send + Sync {
         source: &str,
     ) -> Result<HashMap<String, (NaiveDate, NaiveDate)>>;
 
+    // =========================================================================
+    // Provenance
+    // =========================================================================
+
+    /// Upserts consensus provenance records, keyed by quote ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - Provenance for quotes stored by a consensus sync
+    ///
+    /// # Returns
+    ///
+    /// The number of records that were inserted or updated
+    async fn upsert_provenance(&self, records: &[QuoteProvenance]) -> Result<usize>;
+
+    /// Gets provenance records for an asset within a date range (inclusive).
+    ///
+    /// Quotes stored without consensus mode have no provenance and are omitted.
+    fn provenance_range(
+        &self,
+        asset_id: &AssetId,
+        start: Day,
+        end: Day,
+    ) -> Result<Vec<QuoteProvenance>>;
+
     // =========================================================================
     // Legacy Methods (String-based, for backward compatibility)
     // =========================================================================
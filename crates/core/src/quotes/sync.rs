@@ -11,6 +11,7 @@
 //!       ├─► MarketDataClient (fetch quotes via market-data crate)
 //!       ├─► QuoteStore (persist quotes)
 //!       ├─► SyncStateStore (track sync state)
+//!       ├─► quality (post-sync gap/spike detection, fallback refetch)
 //!       ├─► AssetRepository (asset lookups)
 //!       └─► ActivityRepository (activity bounds)
 //! ```
@@ -30,12 +31,17 @@ use std::sync::{Arc, LazyLock, Mutex};
 use tokio::sync::RwLock;
 
 use super::client::MarketDataClient;
+use super::consensus::{build_consensus, ConsensusConfig, QuoteProvenance};
 use super::constants::*;
 use super::errors::MarketDataError;
+use super::model::{DataSource, Quote};
+use super::quality::{
+    analyze_quotes, is_plausible_replacement, QuoteQualityConfig, QuoteQualityReport,
+};
 use super::store::QuoteStore;
 use super::sync_state::{
-    calculate_sync_window, determine_sync_category, QuoteSyncState, SymbolSyncPlan, SyncCategory,
-    SyncMode, SyncPlanningInputs, SyncStateStore,
+    calculate_sync_window, determine_sync_category, min_lookback_start, QuoteSyncState,
+    SymbolSyncPlan, SyncCategory, SyncMode, SyncPlanningInputs, SyncStateStore,
 };
 use super::types::{AssetId, Day, ProviderId};
 use crate::activities::{ActivityRepositoryTrait, ActivityUpsert};
@@ -211,6 +217,8 @@ pub struct SyncResult {
     pub failures: Vec<(String, String)>,
     /// Reasons why specific assets were skipped (asset_id, reason).
     pub skipped_reasons: Vec<(String, AssetSkipReason)>,
+    /// Quote quality findings for synced assets (only assets with findings).
+    pub quality_reports: Vec<QuoteQualityReport>,
 }
 
 impl SyncResult {
@@ -342,6 +350,10 @@ where
     asset_repo: Arc<A>,
     /// Activity repository for activity bounds.
     activity_repo: Arc<R>,
+    /// Thresholds for the post-sync quote quality pass.
+    quality_config: QuoteQualityConfig,
+    /// Dual-provider consensus settings.
+    consensus_config: ConsensusConfig,
 }
 
 impl<Q, S, A, R> QuoteSyncService<Q, S, A, R>
@@ -365,9 +377,23 @@ where
             sync_state_store,
             asset_repo,
             activity_repo,
+            quality_config: QuoteQualityConfig::default(),
+            consensus_config: ConsensusConfig::default(),
         }
     }
 
+    /// Override the quote quality thresholds used after each sync.
+    pub fn with_quality_config(mut self, config: QuoteQualityConfig) -> Self {
+        self.quality_config = config;
+        self
+    }
+
+    /// Enable or tune dual-provider consensus for provider syncs.
+    pub fn with_consensus_config(mut self, config: ConsensusConfig) -> Self {
+        self.consensus_config = config;
+        self
+    }
+
     /// Build sync plan for a list of assets.
     ///
     /// Determines the date ranges needed for each asset based on:
@@ -381,10 +407,11 @@ where
             .sync_state_store
             .get_by_asset_ids(&asset_ids)
             .unwrap_or_default();
-        let activity_bounds = self
+        let mut activity_bounds = self
             .activity_repo
             .get_activity_bounds_for_assets(&asset_ids)
             .unwrap_or_default();
+        self.fill_fx_activity_bounds(assets, &mut activity_bounds);
 
         // Compute quote bounds per provider
         // Group assets by preferred_provider and batch query
@@ -416,6 +443,41 @@ where
             .collect()
     }
 
+    /// Gives FX assets the portfolio's first activity date as `activity_min`.
+    ///
+    /// FX assets have no activities of their own, so without this a newly
+    /// registered pair only gets recent rates and older valuations fall back on
+    /// the earliest rate available. With it, planning treats the pair like any
+    /// other asset: `New` until it has quotes, `NeedsBackfill` while its history
+    /// starts after the first activity.
+    fn fill_fx_activity_bounds<'a>(
+        &self,
+        assets: impl IntoIterator<Item = &'a Asset>,
+        activity_bounds: &mut HashMap<String, (Option<NaiveDate>, Option<NaiveDate>)>,
+    ) {
+        let mut fx_assets = assets
+            .into_iter()
+            .filter(|a| a.kind == AssetKind::Fx)
+            .peekable();
+        if fx_assets.peek().is_none() {
+            return;
+        }
+        let Some(first_activity) = self
+            .activity_repo
+            .get_first_activity_date_overall()
+            .ok()
+            .map(|dt| dt.date_naive())
+        else {
+            return;
+        };
+        for asset in fx_assets {
+            let (activity_min, _) = activity_bounds
+                .entry(asset.id.clone())
+                .or_insert((None, None));
+            activity_min.get_or_insert(first_activity);
+        }
+    }
+
     /// Check if an asset should be synced.
     fn should_sync_asset(&self, asset: &Asset) -> bool {
         self.get_skip_reason(asset).is_none()
@@ -447,6 +509,7 @@ where
     ) -> Option<SymbolSyncPlan> {
         let effective_today = effective_market_today(now, asset.instrument_exchange_mic.as_deref());
         let fetch_end_date = market_fetch_end_date(now, asset.instrument_exchange_mic.as_deref());
+        let calendar = time_utils::exchange_calendar(asset.instrument_exchange_mic.as_deref());
         // Get existing sync state
         let state = self
             .sync_state_store
@@ -483,7 +546,8 @@ where
         }
 
         // Use the new calculate_sync_window function
-        let (start_date, end_date) = calculate_sync_window(&category, &inputs, effective_today)?;
+        let (start_date, end_date) =
+            calculate_sync_window(&category, &inputs, effective_today, calendar)?;
         let end_date = clamp_end_date_for_fetch(&category, end_date, fetch_end_date);
 
         // Validate date range
@@ -539,11 +603,16 @@ where
                     CLOSED_POSITION_GRACE_PERIOD_DAYS,
                     planning_today,
                 );
-                let (start, end) = calculate_sync_window(&category, inputs, planning_today)
-                    .unwrap_or((
-                        fetch_end_date - Duration::days(QUOTE_HISTORY_BUFFER_DAYS),
-                        fetch_end_date,
-                    ));
+                let (start, end) = calculate_sync_window(
+                    &category,
+                    inputs,
+                    planning_today,
+                    time_utils::exchange_calendar(asset.instrument_exchange_mic.as_deref()),
+                )
+                .unwrap_or((
+                    fetch_end_date - Duration::days(QUOTE_HISTORY_BUFFER_DAYS),
+                    fetch_end_date,
+                ));
 
                 (
                     start,
@@ -681,6 +750,57 @@ where
         }
     }
 
+    /// Fetch provider quotes for a sync window.
+    ///
+    /// With consensus enabled, two providers are queried and reconciled; the
+    /// returned provenance describes each stored quote. Otherwise the primary
+    /// provider's quotes are returned as-is with no provenance.
+    async fn fetch_provider_quotes(
+        &self,
+        asset: &Asset,
+        start_date: NaiveDate,
+        start_dt: DateTime<Utc>,
+        end_dt: DateTime<Utc>,
+    ) -> Result<(Vec<Quote>, Vec<QuoteProvenance>)> {
+        let client = self.client.read().await;
+        if !self.consensus_config.enabled {
+            let quotes = client
+                .fetch_historical_quotes(asset, start_dt, end_dt)
+                .await?;
+            return Ok((quotes, Vec::new()));
+        }
+
+        let (primary, secondary) = client
+            .fetch_historical_quotes_dual(asset, start_dt, end_dt)
+            .await?;
+        if secondary.is_empty() {
+            debug!(
+                "No second provider for {}, storing single-source quotes",
+                asset.id
+            );
+        }
+
+        // Seed dispute resolution with the last stored close before the window.
+        let asset_id = AssetId::new(&asset.id);
+        let previous_close = self
+            .quote_store
+            .range(
+                &asset_id,
+                Day::new(start_date - Duration::days(CONSENSUS_SEED_LOOKBACK_DAYS)),
+                Day::new(start_date - Duration::days(1)),
+                None,
+            )
+            .ok()
+            .and_then(|quotes| quotes.last().map(|q| q.close));
+
+        Ok(build_consensus(
+            primary,
+            &secondary,
+            previous_close,
+            &self.consensus_config,
+        ))
+    }
+
     /// Sync a single asset according to its sync plan.
     ///
     /// Uses per-asset locking (US-012) to prevent duplicate sync work when multiple
@@ -715,12 +835,11 @@ where
         let end_dt = Utc.from_utc_datetime(&plan.end_date.and_hms_opt(23, 59, 59).unwrap());
 
         // Fetch quotes via MarketDataClient
-        let client = self.client.read().await;
-        match client
-            .fetch_historical_quotes(asset, start_dt, end_dt)
+        match self
+            .fetch_provider_quotes(asset, plan.start_date, start_dt, end_dt)
             .await
         {
-            Ok(mut quotes) => {
+            Ok((mut quotes, provenance)) => {
                 // Sort quotes by timestamp to ensure correct ordering
                 // This is important because we use first()/last() to determine date ranges
                 quotes.sort_by_key(|q| q.timestamp);
@@ -744,6 +863,10 @@ where
                         Ok(_) => {
                             debug!("Saved {} quotes for {}", quotes_count, asset.id);
 
+                            if let Err(e) = self.quote_store.upsert_provenance(&provenance).await {
+                                warn!("Failed to save quote provenance for {}: {:?}", asset.id, e);
+                            }
+
                             // Sync splits for this asset over the same date range.
                             self.sync_splits(asset, plan.start_date, plan.end_date)
                                 .await;
@@ -755,9 +878,16 @@ where
                             }
 
                             // Persist the actual provider used so future planning reads correct quote bounds.
-                            if let Some(actual_source) =
-                                quotes.first().map(|q| q.data_source.as_str().to_string())
-                            {
+                            // Under consensus a quote may carry the secondary's source, so take the
+                            // primary from the provenance candidates.
+                            let actual_source = provenance
+                                .first()
+                                .and_then(|p| p.candidates.first())
+                                .map(|c| c.source.clone())
+                                .or_else(|| {
+                                    quotes.first().map(|q| q.data_source.as_str().to_string())
+                                });
+                            if let Some(actual_source) = actual_source {
                                 match self.sync_state_store.get_by_asset_id(&asset.id) {
                                     Ok(Some(mut state)) if state.data_source != actual_source => {
                                         state.data_source = actual_source;
@@ -865,6 +995,165 @@ where
         }
     }
 
+    /// Run the quote quality pass over a freshly synced range.
+    ///
+    /// Loads stored quotes for the plan window (plus some leading context so
+    /// spikes at the start of the window have neighbours), detects gaps and
+    /// single-day spikes, and optionally repairs them from the next provider.
+    /// Returns `None` when the range is clean. Failures are logged and never
+    /// affect the sync result.
+    async fn run_quality_pass(
+        &self,
+        asset: &Asset,
+        plan: &SymbolSyncPlan,
+    ) -> Option<QuoteQualityReport> {
+        let asset_id = AssetId::new(&asset.id);
+        let context_start = plan.start_date - Duration::days(QUALITY_CONTEXT_DAYS);
+
+        let quotes =
+            match self
+                .quote_store
+                .range(&asset_id, Day(context_start), Day(plan.end_date), None)
+            {
+                Ok(quotes) => quotes,
+                Err(e) => {
+                    warn!(
+                        "Quality pass: failed to load quotes for {}: {:?}",
+                        asset.id, e
+                    );
+                    return None;
+                }
+            };
+
+        let mut report = analyze_quotes(
+            &asset.id,
+            &quotes,
+            plan.start_date,
+            plan.end_date,
+            &self.quality_config,
+            time_utils::exchange_calendar(asset.instrument_exchange_mic.as_deref()),
+        );
+
+        if report.is_clean() {
+            return None;
+        }
+
+        info!(
+            "Quality pass for {}: {} gap(s), {} spike(s)",
+            asset.id,
+            report.gaps.len(),
+            report.spikes.len()
+        );
+
+        if self.quality_config.refetch_from_fallback {
+            self.repair_from_fallback(asset, &quotes, &mut report).await;
+        }
+
+        Some(report)
+    }
+
+    /// Refetch flagged days from the next provider and replace bad data.
+    ///
+    /// Gap days are filled with whatever the fallback returns. A spike is only
+    /// replaced when the fallback value sits closer to the neighbouring days than
+    /// the flagged print; manual quotes are never touched.
+    async fn repair_from_fallback(
+        &self,
+        asset: &Asset,
+        quotes: &[Quote],
+        report: &mut QuoteQualityReport,
+    ) {
+        let flagged_days: Vec<NaiveDate> = report
+            .gaps
+            .iter()
+            .flat_map(|g| [g.start, g.end])
+            .chain(report.spikes.iter().map(|s| s.day))
+            .collect();
+        let (Some(from), Some(to)) = (
+            flagged_days.iter().min().copied(),
+            flagged_days.iter().max().copied(),
+        ) else {
+            return;
+        };
+
+        let mut excluded: Vec<&str> = quotes
+            .iter()
+            .filter(|q| q.data_source != DataSource::Manual)
+            .map(|q| q.data_source.as_str())
+            .collect();
+        excluded.sort_unstable();
+        excluded.dedup();
+
+        let start_dt = Utc.from_utc_datetime(&from.and_hms_opt(0, 0, 0).unwrap());
+        let end_dt = Utc.from_utc_datetime(&to.and_hms_opt(23, 59, 59).unwrap());
+
+        let client = self.client.read().await;
+        let fallback = match client
+            .fetch_historical_quotes_excluding(asset, start_dt, end_dt, &excluded)
+            .await
+        {
+            Ok(fallback) => fallback,
+            Err(e) => {
+                debug!("Quality pass: no fallback quotes for {}: {}", asset.id, e);
+                return;
+            }
+        };
+        drop(client);
+
+        let fallback_by_day: HashMap<NaiveDate, Quote> = fallback
+            .into_iter()
+            .map(|q| (q.timestamp.date_naive(), q))
+            .collect();
+
+        let mut to_upsert: Vec<Quote> = Vec::new();
+        let mut to_delete: Vec<String> = Vec::new();
+
+        for gap in report.gaps.iter_mut() {
+            let filled: Vec<Quote> = fallback_by_day
+                .iter()
+                .filter(|(day, _)| **day >= gap.start && **day <= gap.end)
+                .map(|(_, q)| q.clone())
+                .collect();
+            gap.filled_days = filled.len() as i64;
+            to_upsert.extend(filled);
+        }
+
+        for spike in report.spikes.iter_mut() {
+            if spike.source == DataSource::Manual {
+                continue;
+            }
+            let Some(candidate) = fallback_by_day.get(&spike.day) else {
+                continue;
+            };
+            if is_plausible_replacement(spike, candidate.close) {
+                to_delete.push(spike.quote_id.clone());
+                to_upsert.push(candidate.clone());
+                spike.resolved = true;
+            }
+        }
+
+        if to_upsert.is_empty() {
+            return;
+        }
+
+        for quote_id in &to_delete {
+            if let Err(e) = self.quote_store.delete_quote(quote_id).await {
+                warn!("Quality pass: failed to delete quote {}: {:?}", quote_id, e);
+            }
+        }
+
+        match self.quote_store.upsert_quotes(&to_upsert).await {
+            Ok(count) => info!(
+                "Quality pass: stored {} fallback quotes for {}",
+                count, asset.id
+            ),
+            Err(e) => warn!(
+                "Quality pass: failed to store fallback quotes for {}: {:?}",
+                asset.id, e
+            ),
+        }
+    }
+
     /// Execute sync for a list of plans.
     ///
     /// Returns a boxed future to provide an explicit `Send` boundary for
@@ -899,30 +1188,45 @@ where
             let asset_map: HashMap<String, Asset> =
                 assets.into_iter().map(|a| (a.id.clone(), a)).collect();
 
-            let asset_results: Vec<AssetSyncResult> = stream::iter(plans)
-                .map(|plan| {
-                    let asset = asset_map.get(&plan.asset_id).cloned();
-                    async move {
-                        if let Some(asset) = asset {
-                            self.sync_asset(&asset, &plan).await
-                        } else {
-                            warn!("Asset not found for asset_id: {}", plan.asset_id);
-                            AssetSyncResult {
-                                asset_id: AssetId::new(&plan.asset_id),
-                                quotes_added: 0,
-                                status: SyncStatus::Failed,
-                                error: Some("Asset not found".to_string()),
+            let asset_results: Vec<(AssetSyncResult, Option<QuoteQualityReport>)> =
+                stream::iter(plans)
+                    .map(|plan| {
+                        let asset = asset_map.get(&plan.asset_id).cloned();
+                        async move {
+                            if let Some(asset) = asset {
+                                let result = self.sync_asset(&asset, &plan).await;
+                                let report = if result.status == SyncStatus::Success
+                                    && result.quotes_added > 0
+                                {
+                                    self.run_quality_pass(&asset, &plan).await
+                                } else {
+                                    None
+                                };
+                                (result, report)
+                            } else {
+                                warn!("Asset not found for asset_id: {}", plan.asset_id);
+                                (
+                                    AssetSyncResult {
+                                        asset_id: AssetId::new(&plan.asset_id),
+                                        quotes_added: 0,
+                                        status: SyncStatus::Failed,
+                                        error: Some("Asset not found".to_string()),
+                                    },
+                                    None,
+                                )
                             }
                         }
-                    }
-                })
-                .buffer_unordered(SYNC_CONCURRENCY)
-                .collect()
-                .await;
+                    })
+                    .buffer_unordered(SYNC_CONCURRENCY)
+                    .collect()
+                    .await;
 
             let mut result = SyncResult::default();
-            for asset_result in asset_results {
+            for (asset_result, report) in asset_results {
                 result.add_result(asset_result);
+                if let Some(report) = report {
+                    result.quality_reports.push(report);
+                }
             }
 
             // Replace asset IDs with display codes in failures for human-readable messages
@@ -990,9 +1294,10 @@ where
             .collect();
 
         // Compute activity bounds on-the-fly from activities table
-        let activity_bounds = self
+        let mut activity_bounds = self
             .activity_repo
             .get_activity_bounds_for_assets(&asset_ids)?;
+        self.fill_fx_activity_bounds(assets.iter(), &mut activity_bounds);
 
         // Compute quote bounds on-the-fly from quotes table, filtered by provider
         // Group states by data_source to batch quote bounds queries
@@ -1044,6 +1349,7 @@ where
                 effective_market_today(now, asset.instrument_exchange_mic.as_deref());
             let fetch_end_date =
                 market_fetch_end_date(now, asset.instrument_exchange_mic.as_deref());
+            let calendar = time_utils::exchange_calendar(asset.instrument_exchange_mic.as_deref());
             let category = determine_sync_category(
                 &inputs,
                 CLOSED_POSITION_GRACE_PERIOD_DAYS,
@@ -1056,7 +1362,7 @@ where
 
             if matches!(category, SyncCategory::NeedsBackfill) && inputs.is_active {
                 if let Some((start_date, end_date)) =
-                    calculate_sync_window(&category, &inputs, effective_today)
+                    calculate_sync_window(&category, &inputs, effective_today, calendar)
                 {
                     if start_date <= end_date {
                         plans.push(SymbolSyncPlan {
@@ -1075,10 +1381,10 @@ where
 
                 let recent_category = SyncCategory::Active;
                 if let Some((start_date, end_date)) =
-                    calculate_sync_window(&recent_category, &inputs, effective_today)
+                    calculate_sync_window(&recent_category, &inputs, effective_today, calendar)
                 {
                     let start_date = if start_date >= effective_today {
-                        effective_today - Duration::days(MIN_SYNC_LOOKBACK_DAYS)
+                        min_lookback_start(effective_today, calendar)
                     } else {
                         start_date
                     };
@@ -1105,14 +1411,14 @@ where
 
             // Use the new calculate_sync_window function
             let Some((start_date, end_date)) =
-                calculate_sync_window(&category, &inputs, effective_today)
+                calculate_sync_window(&category, &inputs, effective_today, calendar)
             else {
                 continue;
             };
 
             // Ensure minimum lookback
             let start_date = if start_date >= effective_today {
-                effective_today - Duration::days(MIN_SYNC_LOOKBACK_DAYS)
+                min_lookback_start(effective_today, calendar)
             } else {
                 start_date
             };
@@ -1220,9 +1526,10 @@ where
         let now = Utc::now();
         let syncable_ids: Vec<String> = syncable.iter().map(|asset| asset.id.clone()).collect();
         let existing_states = self.sync_state_store.get_by_asset_ids(&syncable_ids)?;
-        let activity_bounds = self
+        let mut activity_bounds = self
             .activity_repo
             .get_activity_bounds_for_assets(&syncable_ids)?;
+        self.fill_fx_activity_bounds(syncable.iter().copied(), &mut activity_bounds);
 
         // Compute quote bounds per provider
         let mut quote_bounds: HashMap<String, (NaiveDate, NaiveDate)> = HashMap::new();
@@ -1257,6 +1564,7 @@ where
                 effective_market_today(now, asset.instrument_exchange_mic.as_deref());
             let fetch_end_date =
                 market_fetch_end_date(now, asset.instrument_exchange_mic.as_deref());
+            let calendar = time_utils::exchange_calendar(asset.instrument_exchange_mic.as_deref());
 
             // Skip assets with too many consecutive errors (unless full resync)
             if !matches!(mode, SyncMode::BackfillHistory { .. }) {
@@ -1305,7 +1613,7 @@ where
             {
                 let recent_category = SyncCategory::Active;
                 if let Some((start_date, end_date)) =
-                    calculate_sync_window(&recent_category, &inputs, effective_today)
+                    calculate_sync_window(&recent_category, &inputs, effective_today, calendar)
                 {
                     if start_date <= end_date {
                         plans.push(SymbolSyncPlan {
@@ -1327,7 +1635,7 @@ where
                 }
 
                 if let Some((start_date, end_date)) =
-                    calculate_sync_window(&category, &inputs, effective_today)
+                    calculate_sync_window(&category, &inputs, effective_today, calendar)
                 {
                     if start_date <= end_date {
                         plans.push(SymbolSyncPlan {
@@ -1738,6 +2046,7 @@ mod tests {
                 ("CASH:USD".to_string(), AssetSkipReason::CashAsset),
                 ("MANUAL_ASSET".to_string(), AssetSkipReason::ManualPricing),
             ],
+            quality_reports: vec![],
         };
         assert!(result.is_success());
         assert!(result.summary().contains("100"));
@@ -1755,6 +2064,7 @@ mod tests {
             }],
             failures: vec![("AAPL".to_string(), "timeout".to_string())],
             skipped_reasons: vec![],
+            quality_reports: vec![],
         };
         assert!(!result_with_failures.is_success());
         assert!(result_with_failures.summary().contains("1 failures"));
@@ -2057,11 +2367,12 @@ mod tests {
 
         #[test]
         fn test_fx_incremental_does_not_backfill() {
-            // In Incremental mode, FX assets should continue from last_quote_date
-            // and should NOT trigger a backfill to global earliest activity
+            // In Incremental mode, FX assets continue from last_quote_date once
+            // their history reaches the global earliest activity.
             //
-            // This is important for performance - we don't want every incremental
-            // sync to fetch years of FX history
+            // fill_fx_activity_bounds() gives FX assets that date as activity_min,
+            // so a pair is backfilled once (New / NeedsBackfill) and then synced
+            // incrementally like any other asset.
             // FX incremental behavior documented
         }
     }
@@ -7,6 +7,7 @@ use async_trait::async_trait;
 use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use wealthfolio_market_data::resolver::TradingCalendar;
 
 use crate::errors::Result;
 
@@ -170,7 +171,8 @@ impl SyncCategory {
 // =============================================================================
 
 use super::constants::{
-    BACKFILL_SAFETY_MARGIN_DAYS, MIN_SYNC_LOOKBACK_DAYS, OVERLAP_DAYS, QUOTE_HISTORY_BUFFER_DAYS,
+    BACKFILL_SAFETY_MARGIN_DAYS, MIN_SYNC_LOOKBACK_DAYS, MIN_SYNC_TRADING_DAYS, OVERLAP_DAYS,
+    QUOTE_HISTORY_BUFFER_DAYS,
 };
 
 /// Inputs for sync planning, computed on-the-fly from operational tables.
@@ -244,10 +246,14 @@ pub fn determine_sync_category(
 ///
 /// Returns (start_date, end_date) for the sync operation.
 /// Returns None if the asset should not be synced.
+///
+/// Overlap and minimum window sizes are widened using the exchange `calendar`
+/// so every window spans at least `MIN_SYNC_TRADING_DAYS` trading days.
 pub fn calculate_sync_window(
     category: &SyncCategory,
     inputs: &SyncPlanningInputs,
     today: NaiveDate,
+    calendar: &TradingCalendar,
 ) -> Option<(NaiveDate, NaiveDate)> {
     match category {
         SyncCategory::Closed => None,
@@ -256,7 +262,10 @@ pub fn calculate_sync_window(
             // Continue from last quote with overlap, or start from activity if no quotes
             let start = inputs
                 .quote_max
-                .map(|d| d - Duration::days(OVERLAP_DAYS))
+                .map(|d| {
+                    (d - Duration::days(OVERLAP_DAYS))
+                        .min(calendar.sub_trading_days(d, MIN_SYNC_TRADING_DAYS))
+                })
                 .or_else(|| {
                     inputs
                         .activity_min
@@ -266,7 +275,7 @@ pub fn calculate_sync_window(
 
             // Ensure minimum lookback
             let start = if start >= today {
-                today - Duration::days(MIN_SYNC_LOOKBACK_DAYS)
+                min_lookback_start(today, calendar)
             } else {
                 start
             };
@@ -303,11 +312,13 @@ pub fn calculate_sync_window(
             // Ensure minimum window size to avoid single-day fetch failures
             // (e.g., weekends, holidays). Expand end forward if needed.
             let window_size = (initial_end - start).num_days().max(0);
-            let end = if window_size < MIN_SYNC_LOOKBACK_DAYS {
-                start + Duration::days(MIN_SYNC_LOOKBACK_DAYS)
-            } else {
-                initial_end
-            };
+            let trading_days = calendar.trading_days(start, initial_end).len() as u32;
+            let end =
+                if window_size < MIN_SYNC_LOOKBACK_DAYS || trading_days < MIN_SYNC_TRADING_DAYS {
+                    min_window_end(start, calendar).max(initial_end)
+                } else {
+                    initial_end
+                };
 
             if start > end {
                 None
@@ -318,6 +329,29 @@ pub fn calculate_sync_window(
     }
 }
 
+/// Earliest start date for a minimum lookback window ending at `today`.
+///
+/// Covers at least `MIN_SYNC_LOOKBACK_DAYS` calendar days and
+/// `MIN_SYNC_TRADING_DAYS` trading days, whichever reaches further back.
+pub fn min_lookback_start(today: NaiveDate, calendar: &TradingCalendar) -> NaiveDate {
+    (today - Duration::days(MIN_SYNC_LOOKBACK_DAYS))
+        .min(calendar.sub_trading_days(today, MIN_SYNC_TRADING_DAYS))
+}
+
+/// End date for a minimum window starting at `start`.
+///
+/// Covers at least `MIN_SYNC_LOOKBACK_DAYS` calendar days and
+/// `MIN_SYNC_TRADING_DAYS` trading days, whichever reaches further forward.
+fn min_window_end(start: NaiveDate, calendar: &TradingCalendar) -> NaiveDate {
+    let mut end = start;
+    let mut trading_days = u32::from(calendar.is_trading_day(start));
+    while trading_days < MIN_SYNC_TRADING_DAYS {
+        end = calendar.next_trading_day(end);
+        trading_days += 1;
+    }
+    end.max(start + Duration::days(MIN_SYNC_LOOKBACK_DAYS))
+}
+
 /// Plan for syncing a specific asset.
 #[derive(Debug, Clone)]
 pub struct SymbolSyncPlan {
@@ -755,7 +789,7 @@ mod tests {
         assert_eq!(category, SyncCategory::NeedsBackfill);
 
         // Calculate window
-        let window = calculate_sync_window(&category, &inputs, today);
+        let window = calculate_sync_window(&category, &inputs, today, TradingCalendar::weekdays());
         assert!(window.is_some(), "Should return a valid window");
 
         let (start, end) = window.unwrap();
@@ -790,7 +824,7 @@ mod tests {
         let category = determine_sync_category(&inputs, 30, today);
         assert_eq!(category, SyncCategory::NeedsBackfill);
 
-        let window = calculate_sync_window(&category, &inputs, today);
+        let window = calculate_sync_window(&category, &inputs, today, TradingCalendar::weekdays());
         assert!(window.is_some());
 
         let (start, end) = window.unwrap();
@@ -833,7 +867,8 @@ mod tests {
         let category = determine_sync_category(&inputs, 30, today);
         assert_eq!(category, SyncCategory::NeedsBackfill);
 
-        let (start, _end) = calculate_sync_window(&category, &inputs, today).unwrap();
+        let (start, _end) =
+            calculate_sync_window(&category, &inputs, today, TradingCalendar::weekdays()).unwrap();
 
         // Repro detail: planner can request start before quote_min/inception proxy.
         // Runtime sync layer now handles resulting provider boundary errors as non-fatal.
@@ -845,6 +880,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_backfill_window_expands_over_exchange_holidays() {
+        // Gap falls inside Japan's New Year closure (Dec 31 - Jan 3) and a weekend
+        let today = NaiveDate::from_ymd_opt(2025, 2, 1).unwrap();
+        let buffer = QUOTE_HISTORY_BUFFER_DAYS + BACKFILL_SAFETY_MARGIN_DAYS;
+        let start = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let activity_min = start + Duration::days(buffer);
+        let quote_min = start + Duration::days(3);
+
+        let inputs = create_inputs(
+            true,
+            None,
+            Some(activity_min),
+            Some(activity_min),
+            Some(quote_min),
+            Some(today),
+        );
+        let category = determine_sync_category(&inputs, 30, today);
+        assert_eq!(category, SyncCategory::NeedsBackfill);
+
+        let tse = crate::utils::time_utils::exchange_calendar(Some("XTKS"));
+        let (window_start, window_end) =
+            calculate_sync_window(&category, &inputs, today, tse).unwrap();
+        assert_eq!(window_start, start);
+        // Jan 6, 7 and 8 are the first three trading days of 2025 on the TSE
+        assert_eq!(window_end, NaiveDate::from_ymd_opt(2025, 1, 8).unwrap());
+
+        let (_, weekday_end) =
+            calculate_sync_window(&category, &inputs, today, TradingCalendar::weekdays()).unwrap();
+        assert_eq!(weekday_end, start + Duration::days(MIN_SYNC_LOOKBACK_DAYS));
+    }
+
     #[test]
     fn test_backfill_window_exactly_minimum_size() {
         // Window exactly at MIN_SYNC_LOOKBACK_DAYS should not expand
@@ -871,7 +938,8 @@ mod tests {
         let category = determine_sync_category(&inputs, 30, today);
         // This might be Active if quote coverage is sufficient, check either way
         if category == SyncCategory::NeedsBackfill {
-            let window = calculate_sync_window(&category, &inputs, today);
+            let window =
+                calculate_sync_window(&category, &inputs, today, TradingCalendar::weekdays());
             assert!(window.is_some());
 
             let (start, end) = window.unwrap();
@@ -910,7 +978,7 @@ mod tests {
             "Should be NeedsBackfill when required_start < quote_min"
         );
 
-        let window = calculate_sync_window(&category, &inputs, today);
+        let window = calculate_sync_window(&category, &inputs, today, TradingCalendar::weekdays());
         assert!(
             window.is_some(),
             "Should return valid window even for 0-day gap"
@@ -949,7 +1017,7 @@ mod tests {
         let category = determine_sync_category(&inputs, 30, today);
         assert_eq!(category, SyncCategory::NeedsBackfill);
 
-        let window = calculate_sync_window(&category, &inputs, today);
+        let window = calculate_sync_window(&category, &inputs, today, TradingCalendar::weekdays());
         let (start, end) = window.unwrap();
 
         // Expanded end may be >= quote_min (overlapping existing quotes)
@@ -978,7 +1046,7 @@ mod tests {
         let category = determine_sync_category(&inputs, 30, today);
         assert_eq!(category, SyncCategory::Active);
 
-        let window = calculate_sync_window(&category, &inputs, today);
+        let window = calculate_sync_window(&category, &inputs, today, TradingCalendar::weekdays());
         let (_, end) = window.unwrap();
 
         assert_eq!(end, today, "Active category should always end at today");
@@ -1001,7 +1069,7 @@ mod tests {
         let category = determine_sync_category(&inputs, 30, today);
         assert_eq!(category, SyncCategory::New);
 
-        let window = calculate_sync_window(&category, &inputs, today);
+        let window = calculate_sync_window(&category, &inputs, today, TradingCalendar::weekdays());
         let (_, end) = window.unwrap();
 
         assert_eq!(end, today, "New category should always end at today");
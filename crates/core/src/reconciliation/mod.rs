@@ -0,0 +1,15 @@
+//! Holdings reconciliation module - broker-reported positions against positions computed from activities.
+
+mod reconciliation_model;
+mod reconciliation_service;
+mod reconciliation_traits;
+
+#[cfg(test)]
+mod reconciliation_model_tests;
+
+pub use reconciliation_model::{
+    reconcile_holdings, DiscrepancyCause, HoldingDiscrepancy, ReconciliationReport,
+    ReportedHoldings, ReportedPosition, RECONCILIATION_SOURCE_SYSTEM,
+};
+pub use reconciliation_service::{HoldingsReconciliationService, REPORTED_HOLDINGS_SETTINGS_KEY};
+pub use reconciliation_traits::HoldingsReconciliationServiceTrait;
@@ -0,0 +1,511 @@
+//! Reconciliation of broker-reported holdings against computed positions.
+//!
+//! For accounts tracked through transactions, positions and cash are computed
+//! from activities. Brokers also report what they hold; any difference means
+//! activities are missing or wrong. Each discrepancy is explained with the most
+//! likely cause and comes with draft activities that would close it, for the
+//! user to review before posting.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::activities::{
+    Activity, ActivityStatus, NewActivity, SymbolInput, ACTIVITY_SUBTYPE_DRIP, ACTIVITY_TYPE_BUY,
+    ACTIVITY_TYPE_DIVIDEND, ACTIVITY_TYPE_SPLIT, ACTIVITY_TYPE_TRANSFER_IN,
+    ACTIVITY_TYPE_TRANSFER_OUT,
+};
+use crate::portfolio::snapshot::{AccountStateSnapshot, Position};
+
+/// Source system of the draft activities proposed by a reconciliation.
+pub const RECONCILIATION_SOURCE_SYSTEM: &str = "RECONCILIATION";
+
+/// Quantities closer than this are considered equal.
+const QUANTITY_TOLERANCE: Decimal = dec!(0.000001);
+
+/// Cash balances closer than this are considered equal.
+const CASH_TOLERANCE: Decimal = dec!(0.01);
+
+/// Largest split (or reverse split) ratio recognised, e.g. 100-for-1.
+const MAX_SPLIT_RATIO: i64 = 100;
+
+/// Relative error allowed between a quantity ratio and a whole split ratio.
+const SPLIT_RATIO_TOLERANCE: Decimal = dec!(0.001);
+
+/// Largest share of the reported quantity a dividend reinvestment explains.
+const MAX_REINVESTED_SHARE: Decimal = dec!(0.05);
+
+/// A position as reported by the broker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportedPosition {
+    pub symbol: String,
+    #[serde(default)]
+    pub exchange_mic: Option<String>,
+    pub quantity: Decimal,
+    #[serde(default)]
+    pub price: Option<Decimal>,
+    pub currency: String,
+}
+
+/// Positions and cash balances a broker reported for an account on a date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportedHoldings {
+    pub account_id: String,
+    pub as_of: NaiveDate,
+    pub positions: Vec<ReportedPosition>,
+    /// Currency -> balance. Empty when the broker does not report cash, in
+    /// which case cash is not reconciled.
+    #[serde(default)]
+    pub cash_balances: HashMap<String, Decimal>,
+}
+
+/// Most likely reason for a discrepancy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DiscrepancyCause {
+    /// Quantities differ by a whole split ratio.
+    MissingSplit,
+    /// The broker holds a few more shares and dividends were paid that have
+    /// no matching purchase.
+    MissingDividendReinvestment,
+    /// Shares or cash moved in or out without a recorded activity.
+    UnknownTransfer,
+}
+
+/// A position or cash balance that differs between broker and activities.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldingDiscrepancy {
+    pub account_id: String,
+    /// Asset of the computed position, when the reported one could be matched.
+    pub asset_id: Option<String>,
+    /// Reported symbol, or the currency for cash.
+    pub symbol: String,
+    pub currency: String,
+    pub is_cash: bool,
+    pub reported_quantity: Decimal,
+    pub computed_quantity: Decimal,
+    /// `reported_quantity - computed_quantity`.
+    pub difference: Decimal,
+    pub cause: DiscrepancyCause,
+    pub explanation: String,
+    /// Draft activities that would close the discrepancy.
+    pub proposed_activities: Vec<NewActivity>,
+}
+
+/// Result of reconciling one account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationReport {
+    pub account_id: String,
+    /// Date of the broker report.
+    pub as_of: NaiveDate,
+    /// Date of the computed snapshot compared against it.
+    pub computed_as_of: Option<NaiveDate>,
+    /// Positions and cash balances that agree.
+    pub matched_count: usize,
+    pub discrepancies: Vec<HoldingDiscrepancy>,
+}
+
+impl ReconciliationReport {
+    pub fn is_reconciled(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// Compares `reported` with the `computed` snapshot of the same account.
+///
+/// `symbols` maps the asset IDs of computed positions to their ticker, which is
+/// how reported positions are matched. `activities` are the account's
+/// activities, used to explain discrepancies.
+pub fn reconcile_holdings(
+    reported: &ReportedHoldings,
+    computed: Option<&AccountStateSnapshot>,
+    activities: &[Activity],
+    symbols: &HashMap<String, String>,
+) -> ReconciliationReport {
+    let positions: Vec<&Position> = computed
+        .map(|s| {
+            s.positions
+                .values()
+                .filter(|p| !p.is_alternative && !p.quantity.is_zero())
+                .collect()
+        })
+        .unwrap_or_default();
+    let by_symbol: HashMap<String, &Position> = positions
+        .iter()
+        .flat_map(|p| {
+            let mut keys = vec![(p.asset_id.to_uppercase(), *p)];
+            if let Some(symbol) = symbols.get(&p.asset_id) {
+                keys.push((symbol.to_uppercase(), *p));
+            }
+            keys
+        })
+        .collect();
+
+    let mut report = ReconciliationReport {
+        account_id: reported.account_id.clone(),
+        as_of: reported.as_of,
+        computed_as_of: computed.map(|s| s.snapshot_date),
+        matched_count: 0,
+        discrepancies: Vec::new(),
+    };
+    // Cash spent by proposed purchases, per currency
+    let mut cash_adjustments: HashMap<String, Decimal> = HashMap::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+
+    for rp in reported.positions.iter().filter(|p| !p.quantity.is_zero()) {
+        let position = by_symbol.get(&rp.symbol.to_uppercase()).copied();
+        if let Some(position) = position {
+            seen.insert(position.asset_id.as_str());
+        }
+        let computed_quantity = position.map(|p| p.quantity).unwrap_or(Decimal::ZERO);
+        if (rp.quantity - computed_quantity).abs() <= QUANTITY_TOLERANCE {
+            report.matched_count += 1;
+            continue;
+        }
+        report.discrepancies.push(explain_position(
+            reported,
+            PositionGap {
+                asset_id: position.map(|p| p.asset_id.clone()),
+                symbol: rp.symbol.clone(),
+                exchange_mic: rp.exchange_mic.clone(),
+                currency: rp.currency.clone(),
+                reported: rp.quantity,
+                computed: computed_quantity,
+                unit_price: rp.price.or(position.map(|p| p.average_cost)),
+            },
+            activities,
+            &mut cash_adjustments,
+        ));
+    }
+
+    // Positions the broker no longer reports
+    for position in positions
+        .iter()
+        .filter(|p| !seen.contains(p.asset_id.as_str()))
+    {
+        report.discrepancies.push(explain_position(
+            reported,
+            PositionGap {
+                asset_id: Some(position.asset_id.clone()),
+                symbol: symbols
+                    .get(&position.asset_id)
+                    .cloned()
+                    .unwrap_or_else(|| position.asset_id.clone()),
+                exchange_mic: None,
+                currency: position.currency.clone(),
+                reported: Decimal::ZERO,
+                computed: position.quantity,
+                unit_price: Some(position.average_cost),
+            },
+            activities,
+            &mut cash_adjustments,
+        ));
+    }
+
+    if !reported.cash_balances.is_empty() {
+        let computed_cash = computed.map(|s| &s.cash_balances);
+        let currencies: BTreeSet<&String> = reported
+            .cash_balances
+            .keys()
+            .chain(computed_cash.into_iter().flat_map(|c| c.keys()))
+            .collect();
+        for currency in currencies {
+            let reported_cash = reported
+                .cash_balances
+                .get(currency)
+                .copied()
+                .unwrap_or(Decimal::ZERO);
+            let computed_cash = computed_cash
+                .and_then(|c| c.get(currency))
+                .copied()
+                .unwrap_or(Decimal::ZERO)
+                + cash_adjustments
+                    .get(currency)
+                    .copied()
+                    .unwrap_or(Decimal::ZERO);
+            if (reported_cash - computed_cash).abs() <= CASH_TOLERANCE {
+                report.matched_count += 1;
+                continue;
+            }
+            report.discrepancies.push(explain_cash(
+                reported,
+                currency,
+                reported_cash,
+                computed_cash,
+            ));
+        }
+    }
+
+    report
+}
+
+/// A position whose reported and computed quantities differ.
+struct PositionGap {
+    asset_id: Option<String>,
+    symbol: String,
+    exchange_mic: Option<String>,
+    currency: String,
+    reported: Decimal,
+    computed: Decimal,
+    unit_price: Option<Decimal>,
+}
+
+fn explain_position(
+    reported: &ReportedHoldings,
+    gap: PositionGap,
+    activities: &[Activity],
+    cash_adjustments: &mut HashMap<String, Decimal>,
+) -> HoldingDiscrepancy {
+    let difference = gap.reported - gap.computed;
+    let asset_activities: Vec<&Activity> = activities
+        .iter()
+        .filter(|a| a.status != ActivityStatus::Void)
+        .filter(|a| gap.asset_id.is_some() && a.asset_id == gap.asset_id)
+        .collect();
+
+    let (cause, explanation, proposal) = if let Some(ratio) =
+        split_ratio(gap.reported, gap.computed)
+    {
+        let explanation = format!(
+            "{} holds {} shares at the broker and {} from activities, a {}-for-{} split. \
+             Set the split date before posting.",
+            gap.symbol,
+            gap.reported.normalize(),
+            gap.computed.normalize(),
+            ratio.0,
+            ratio.1
+        );
+        let mut split = draft(reported, &gap, ACTIVITY_TYPE_SPLIT, &explanation);
+        split.amount = Some((Decimal::from(ratio.0) / Decimal::from(ratio.1)).round_dp(8));
+        (DiscrepancyCause::MissingSplit, explanation, split)
+    } else if let Some(dividends) = unreinvested_dividends(&asset_activities, &gap, difference) {
+        let amount: Decimal = dividends.iter().map(|d| d.amt()).sum();
+        let last = dividends.iter().max_by_key(|d| d.activity_date).unwrap();
+        let explanation = format!(
+            "{} holds {} more shares at the broker, and {} of its dividends have no \
+             matching purchase. They were likely reinvested.",
+            gap.symbol,
+            difference.normalize(),
+            dividends.len()
+        );
+        let mut buy = draft(reported, &gap, ACTIVITY_TYPE_BUY, &explanation);
+        buy.activity_date = last.effective_date().format("%Y-%m-%d").to_string();
+        buy.currency = last.currency.clone();
+        buy.quantity = Some(difference);
+        buy.unit_price = Some((amount / difference).round_dp(8));
+        buy.amount = Some(amount);
+        *cash_adjustments.entry(last.currency.clone()).or_default() -= amount;
+        (
+            DiscrepancyCause::MissingDividendReinvestment,
+            explanation,
+            buy,
+        )
+    } else {
+        let (activity_type, direction) = if difference.is_sign_positive() {
+            (ACTIVITY_TYPE_TRANSFER_IN, "more")
+        } else {
+            (ACTIVITY_TYPE_TRANSFER_OUT, "fewer")
+        };
+        let explanation = format!(
+            "{} holds {} {} shares at the broker than recorded in activities. \
+             Shares may have been transferred without a recorded activity.",
+            gap.symbol,
+            difference.abs().normalize(),
+            direction
+        );
+        let mut transfer = draft(reported, &gap, activity_type, &explanation);
+        transfer.quantity = Some(difference.abs());
+        transfer.unit_price = gap.unit_price;
+        transfer.amount = gap.unit_price.map(|p| p * difference.abs());
+        (DiscrepancyCause::UnknownTransfer, explanation, transfer)
+    };
+
+    HoldingDiscrepancy {
+        account_id: reported.account_id.clone(),
+        asset_id: gap.asset_id,
+        symbol: gap.symbol,
+        currency: gap.currency,
+        is_cash: false,
+        reported_quantity: gap.reported,
+        computed_quantity: gap.computed,
+        difference,
+        cause,
+        explanation,
+        proposed_activities: vec![proposal],
+    }
+}
+
+fn explain_cash(
+    reported: &ReportedHoldings,
+    currency: &str,
+    reported_cash: Decimal,
+    computed_cash: Decimal,
+) -> HoldingDiscrepancy {
+    let difference = reported_cash - computed_cash;
+    let (activity_type, direction) = if difference.is_sign_positive() {
+        (ACTIVITY_TYPE_TRANSFER_IN, "more")
+    } else {
+        (ACTIVITY_TYPE_TRANSFER_OUT, "less")
+    };
+    let explanation = format!(
+        "The broker reports {} {} {} cash than recorded in activities. \
+         Cash may have been transferred without a recorded activity.",
+        difference.abs().round_dp(2),
+        currency,
+        direction
+    );
+    let record_id = draft_record_id(&reported.account_id, currency, activity_type, difference);
+    let proposal = NewActivity {
+        id: None,
+        account_id: reported.account_id.clone(),
+        symbol: None,
+        activity_type: activity_type.to_string(),
+        subtype: None,
+        activity_date: reported.as_of.format("%Y-%m-%d").to_string(),
+        quantity: None,
+        unit_price: None,
+        currency: currency.to_string(),
+        fee: None,
+        amount: Some(difference.abs()),
+        status: Some(ActivityStatus::Draft),
+        notes: Some(explanation.clone()),
+        fx_rate: None,
+        metadata: Some(json!({ "reconciliation": { "as_of": reported.as_of } }).to_string()),
+        needs_review: Some(true),
+        source_system: Some(RECONCILIATION_SOURCE_SYSTEM.to_string()),
+        source_record_id: Some(record_id.clone()),
+        source_group_id: None,
+        idempotency_key: Some(format!("{}:{}", RECONCILIATION_SOURCE_SYSTEM, record_id)),
+    };
+
+    HoldingDiscrepancy {
+        account_id: reported.account_id.clone(),
+        asset_id: None,
+        symbol: currency.to_string(),
+        currency: currency.to_string(),
+        is_cash: true,
+        reported_quantity: reported_cash,
+        computed_quantity: computed_cash,
+        difference,
+        cause: DiscrepancyCause::UnknownTransfer,
+        explanation,
+        proposed_activities: vec![proposal],
+    }
+}
+
+/// Identifies a proposed draft across syncs. The report date is left out so
+/// a discrepancy that persists is not proposed again every day; the amount is
+/// kept so a later, different gap gets its own draft.
+fn draft_record_id(
+    account_id: &str,
+    symbol: &str,
+    activity_type: &str,
+    difference: Decimal,
+) -> String {
+    format!(
+        "{}:{}:{}:{}",
+        account_id,
+        symbol,
+        activity_type,
+        difference.normalize()
+    )
+}
+
+/// Draft for a position, dated on the report date.
+fn draft(
+    reported: &ReportedHoldings,
+    gap: &PositionGap,
+    activity_type: &str,
+    explanation: &str,
+) -> NewActivity {
+    let record_id = draft_record_id(
+        &reported.account_id,
+        &gap.symbol,
+        activity_type,
+        gap.reported - gap.computed,
+    );
+    NewActivity {
+        id: None,
+        account_id: reported.account_id.clone(),
+        symbol: Some(SymbolInput {
+            id: gap.asset_id.clone(),
+            symbol: Some(gap.symbol.clone()),
+            exchange_mic: gap.exchange_mic.clone(),
+            ..Default::default()
+        }),
+        activity_type: activity_type.to_string(),
+        subtype: None,
+        activity_date: reported.as_of.format("%Y-%m-%d").to_string(),
+        quantity: None,
+        unit_price: None,
+        currency: gap.currency.clone(),
+        fee: None,
+        amount: None,
+        status: Some(ActivityStatus::Draft),
+        notes: Some(explanation.to_string()),
+        fx_rate: None,
+        metadata: Some(json!({ "reconciliation": { "as_of": reported.as_of } }).to_string()),
+        needs_review: Some(true),
+        source_system: Some(RECONCILIATION_SOURCE_SYSTEM.to_string()),
+        source_record_id: Some(record_id.clone()),
+        source_group_id: None,
+        idempotency_key: Some(format!("{}:{}", RECONCILIATION_SOURCE_SYSTEM, record_id)),
+    }
+}
+
+/// Returns `(new, old)` when `reported / computed` is a whole split or
+/// reverse split ratio, e.g. `(4, 1)` or `(1, 10)`.
+fn split_ratio(reported: Decimal, computed: Decimal) -> Option<(i64, i64)> {
+    if reported <= Decimal::ZERO || computed <= Decimal::ZERO {
+        return None;
+    }
+    let (ratio, forward) = if reported > computed {
+        (reported / computed, true)
+    } else {
+        (computed / reported, false)
+    };
+    let whole = ratio.round();
+    if whole < Decimal::TWO || whole > Decimal::from(MAX_SPLIT_RATIO) {
+        return None;
+    }
+    if (ratio - whole).abs() > whole * SPLIT_RATIO_TOLERANCE {
+        return None;
+    }
+    let n = i64::try_from(whole).ok()?;
+    Some(if forward { (n, 1) } else { (1, n) })
+}
+
+/// Dividends of the asset without a purchase on the same day, when they can
+/// account for `difference` extra shares.
+fn unreinvested_dividends<'a>(
+    activities: &[&'a Activity],
+    gap: &PositionGap,
+    difference: Decimal,
+) -> Option<Vec<&'a Activity>> {
+    if difference <= Decimal::ZERO || difference > gap.reported * MAX_REINVESTED_SHARE {
+        return None;
+    }
+    let purchase_dates: HashSet<NaiveDate> = activities
+        .iter()
+        .filter(|a| a.effective_type() == ACTIVITY_TYPE_BUY)
+        .map(|a| a.effective_date())
+        .collect();
+    let dividends: Vec<&Activity> = activities
+        .iter()
+        .copied()
+        .filter(|a| {
+            a.effective_type() == ACTIVITY_TYPE_DIVIDEND
+                && a.subtype.as_deref() != Some(ACTIVITY_SUBTYPE_DRIP)
+                && a.amt() > Decimal::ZERO
+                && !purchase_dates.contains(&a.effective_date())
+        })
+        .collect();
+    (!dividends.is_empty()).then_some(dividends)
+}
@@ -0,0 +1,288 @@
+//! Tests for matching broker-reported holdings and explaining discrepancies.
+
+#[cfg(test)]
+mod tests {
+    use crate::activities::{
+        Activity, ActivityStatus, ACTIVITY_TYPE_BUY, ACTIVITY_TYPE_DIVIDEND, ACTIVITY_TYPE_SPLIT,
+        ACTIVITY_TYPE_TRANSFER_IN, ACTIVITY_TYPE_TRANSFER_OUT,
+    };
+    use crate::portfolio::snapshot::{AccountStateSnapshot, Position};
+    use crate::reconciliation::{
+        reconcile_holdings, DiscrepancyCause, ReportedHoldings, ReportedPosition,
+        RECONCILIATION_SOURCE_SYSTEM,
+    };
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::collections::HashMap;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn computed(positions: &[(&str, Decimal)], cash: &[(&str, Decimal)]) -> AccountStateSnapshot {
+        AccountStateSnapshot {
+            account_id: "acc_1".to_string(),
+            snapshot_date: date(2024, 6, 28),
+            currency: "USD".to_string(),
+            positions: positions
+                .iter()
+                .map(|(asset_id, quantity)| {
+                    (
+                        asset_id.to_string(),
+                        Position {
+                            id: format!("acc_1_{}", asset_id),
+                            account_id: "acc_1".to_string(),
+                            asset_id: asset_id.to_string(),
+                            quantity: *quantity,
+                            average_cost: dec!(100),
+                            currency: "USD".to_string(),
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+            cash_balances: cash.iter().map(|(c, a)| (c.to_string(), *a)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn reported(positions: &[(&str, Decimal)], cash: &[(&str, Decimal)]) -> ReportedHoldings {
+        ReportedHoldings {
+            account_id: "acc_1".to_string(),
+            as_of: date(2024, 6, 30),
+            positions: positions
+                .iter()
+                .map(|(symbol, quantity)| ReportedPosition {
+                    symbol: symbol.to_string(),
+                    exchange_mic: None,
+                    quantity: *quantity,
+                    price: Some(dec!(50)),
+                    currency: "USD".to_string(),
+                })
+                .collect(),
+            cash_balances: cash.iter().map(|(c, a)| (c.to_string(), *a)).collect(),
+        }
+    }
+
+    fn symbols() -> HashMap<String, String> {
+        HashMap::from([
+            ("SEC:AAPL:XNAS".to_string(), "AAPL".to_string()),
+            ("SEC:MSFT:XNAS".to_string(), "MSFT".to_string()),
+        ])
+    }
+
+    fn activity(activity_type: &str, day: NaiveDate, amount: Decimal) -> Activity {
+        Activity {
+            id: format!("{}_{}", activity_type, day),
+            account_id: "acc_1".to_string(),
+            asset_id: Some("SEC:MSFT:XNAS".to_string()),
+            activity_type: activity_type.to_string(),
+            activity_type_override: None,
+            source_type: None,
+            subtype: None,
+            status: ActivityStatus::Posted,
+            activity_date: Utc.from_utc_datetime(&day.and_hms_opt(0, 0, 0).unwrap()),
+            settlement_date: None,
+            quantity: None,
+            unit_price: None,
+            amount: Some(amount),
+            fee: None,
+            currency: "USD".to_string(),
+            fx_rate: None,
+            notes: None,
+            metadata: None,
+            source_system: None,
+            source_record_id: None,
+            source_group_id: None,
+            idempotency_key: None,
+            import_run_id: None,
+            is_user_modified: false,
+            needs_review: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_matching_holdings_are_reconciled() {
+        let snapshot = computed(
+            &[("SEC:AAPL:XNAS", dec!(10)), ("SEC:MSFT:XNAS", dec!(5))],
+            &[("USD", dec!(250.00))],
+        );
+        let report = reconcile_holdings(
+            &reported(
+                &[("aapl", dec!(10)), ("MSFT", dec!(5))],
+                &[("USD", dec!(250.004))],
+            ),
+            Some(&snapshot),
+            &[],
+            &symbols(),
+        );
+        assert!(report.is_reconciled());
+        assert_eq!(report.matched_count, 3);
+        assert_eq!(report.computed_as_of, Some(date(2024, 6, 28)));
+    }
+
+    #[test]
+    fn test_whole_ratio_is_explained_as_missing_split() {
+        let snapshot = computed(&[("SEC:AAPL:XNAS", dec!(10))], &[]);
+        let report = reconcile_holdings(
+            &reported(&[("AAPL", dec!(40))], &[]),
+            Some(&snapshot),
+            &[],
+            &symbols(),
+        );
+        assert_eq!(report.discrepancies.len(), 1);
+        let discrepancy = &report.discrepancies[0];
+        assert_eq!(discrepancy.cause, DiscrepancyCause::MissingSplit);
+        assert_eq!(discrepancy.difference, dec!(30));
+
+        let split = &discrepancy.proposed_activities[0];
+        assert_eq!(split.activity_type, ACTIVITY_TYPE_SPLIT);
+        assert_eq!(split.amount, Some(dec!(4)));
+        assert_eq!(split.status, Some(ActivityStatus::Draft));
+        assert_eq!(split.needs_review, Some(true));
+        assert_eq!(
+            split.symbol.as_ref().and_then(|s| s.id.as_deref()),
+            Some("SEC:AAPL:XNAS")
+        );
+
+        // Reverse split
+        let report = reconcile_holdings(
+            &reported(&[("AAPL", dec!(1))], &[]),
+            Some(&snapshot),
+            &[],
+            &symbols(),
+        );
+        let split = &report.discrepancies[0].proposed_activities[0];
+        assert_eq!(split.amount, Some(dec!(0.1)));
+    }
+
+    #[test]
+    fn test_unmatched_dividends_explain_extra_shares() {
+        let snapshot = computed(&[("SEC:MSFT:XNAS", dec!(100))], &[("USD", dec!(60))]);
+        let activities = vec![
+            activity(ACTIVITY_TYPE_BUY, date(2024, 1, 10), dec!(10000)),
+            activity(ACTIVITY_TYPE_DIVIDEND, date(2024, 3, 14), dec!(30)),
+            activity(ACTIVITY_TYPE_DIVIDEND, date(2024, 6, 13), dec!(30)),
+        ];
+        let report = reconcile_holdings(
+            &reported(&[("MSFT", dec!(100.15))], &[("USD", dec!(0))]),
+            Some(&snapshot),
+            &activities,
+            &symbols(),
+        );
+
+        // The reinvestment also explains the cash the broker no longer holds
+        assert_eq!(report.discrepancies.len(), 1);
+        assert_eq!(report.matched_count, 1);
+        let discrepancy = &report.discrepancies[0];
+        assert_eq!(
+            discrepancy.cause,
+            DiscrepancyCause::MissingDividendReinvestment
+        );
+        let buy = &discrepancy.proposed_activities[0];
+        assert_eq!(buy.activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(buy.quantity, Some(dec!(0.15)));
+        assert_eq!(buy.amount, Some(dec!(60)));
+        assert_eq!(buy.unit_price, Some(dec!(400)));
+        assert_eq!(buy.activity_date, "2024-06-13");
+    }
+
+    #[test]
+    fn test_other_gaps_are_unknown_transfers() {
+        let snapshot = computed(
+            &[("SEC:AAPL:XNAS", dec!(10)), ("SEC:MSFT:XNAS", dec!(5))],
+            &[("USD", dec!(100))],
+        );
+        let report = reconcile_holdings(
+            &reported(
+                &[("AAPL", dec!(13)), ("VTI", dec!(2))],
+                &[("USD", dec!(40)), ("EUR", dec!(15))],
+            ),
+            Some(&snapshot),
+            &[],
+            &symbols(),
+        );
+        assert_eq!(report.matched_count, 0);
+        assert_eq!(report.discrepancies.len(), 5);
+        assert!(report
+            .discrepancies
+            .iter()
+            .all(|d| d.cause == DiscrepancyCause::UnknownTransfer));
+
+        let by_symbol = |symbol: &str| {
+            report
+                .discrepancies
+                .iter()
+                .find(|d| d.symbol == symbol)
+                .unwrap()
+        };
+        let aapl = &by_symbol("AAPL").proposed_activities[0];
+        assert_eq!(aapl.activity_type, ACTIVITY_TYPE_TRANSFER_IN);
+        assert_eq!(aapl.quantity, Some(dec!(3)));
+        assert_eq!(aapl.unit_price, Some(dec!(50)));
+
+        // Not held at the broker any more
+        let msft = by_symbol("MSFT");
+        assert_eq!(msft.reported_quantity, Decimal::ZERO);
+        assert_eq!(
+            msft.proposed_activities[0].activity_type,
+            ACTIVITY_TYPE_TRANSFER_OUT
+        );
+        assert_eq!(msft.proposed_activities[0].unit_price, Some(dec!(100)));
+
+        // Unknown to the activities
+        assert_eq!(by_symbol("VTI").asset_id, None);
+
+        let usd = by_symbol("USD");
+        assert!(usd.is_cash);
+        assert_eq!(usd.difference, dec!(-60));
+        assert_eq!(
+            usd.proposed_activities[0].activity_type,
+            ACTIVITY_TYPE_TRANSFER_OUT
+        );
+        assert_eq!(usd.proposed_activities[0].amount, Some(dec!(60)));
+        assert!(usd.proposed_activities[0].symbol.is_none());
+        assert_eq!(
+            by_symbol("EUR").proposed_activities[0]
+                .source_system
+                .as_deref(),
+            Some(RECONCILIATION_SOURCE_SYSTEM)
+        );
+    }
+
+    #[test]
+    fn test_draft_record_ids_do_not_depend_on_report_date() {
+        let snapshot = computed(&[("SEC:AAPL:XNAS", dec!(10))], &[("USD", dec!(100))]);
+        let record_ids = |as_of: NaiveDate| {
+            let mut holdings = reported(&[("AAPL", dec!(13))], &[("USD", dec!(40))]);
+            holdings.as_of = as_of;
+            reconcile_holdings(&holdings, Some(&snapshot), &[], &symbols())
+                .discrepancies
+                .into_iter()
+                .flat_map(|d| d.proposed_activities)
+                .map(|a| a.source_record_id.unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // Syncing again on a later day proposes the same drafts, so they are not duplicated
+        let first = record_ids(date(2024, 6, 30));
+        assert_eq!(first.len(), 2);
+        assert_eq!(first, record_ids(date(2024, 7, 1)));
+    }
+
+    #[test]
+    fn test_cash_is_skipped_when_not_reported() {
+        let snapshot = computed(&[("SEC:AAPL:XNAS", dec!(10))], &[("USD", dec!(100))]);
+        let report = reconcile_holdings(
+            &reported(&[("AAPL", dec!(10))], &[]),
+            Some(&snapshot),
+            &[],
+            &symbols(),
+        );
+        assert!(report.is_reconciled());
+        assert_eq!(report.matched_count, 1);
+    }
+}
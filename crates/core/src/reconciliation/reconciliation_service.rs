@@ -0,0 +1,197 @@
+use crate::accounts::{AccountServiceTrait, TrackingMode};
+use crate::activities::ActivityServiceTrait;
+use crate::assets::AssetServiceTrait;
+use crate::errors::{Error, Result};
+use crate::portfolio::snapshot::SnapshotServiceTrait;
+use crate::reconciliation::reconciliation_model::{
+    reconcile_holdings, ReconciliationReport, ReportedHoldings, RECONCILIATION_SOURCE_SYSTEM,
+};
+use crate::reconciliation::reconciliation_traits::HoldingsReconciliationServiceTrait;
+use crate::settings::SettingsServiceTrait;
+use async_trait::async_trait;
+use log::{debug, warn};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+/// Settings key of the latest broker report per account.
+pub const REPORTED_HOLDINGS_SETTINGS_KEY: &str = "broker_reported_holdings";
+
+pub struct HoldingsReconciliationService {
+    account_service: Arc<dyn AccountServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    snapshot_service: Arc<dyn SnapshotServiceTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    settings_service: Arc<dyn SettingsServiceTrait>,
+}
+
+impl HoldingsReconciliationService {
+    pub fn new(
+        account_service: Arc<dyn AccountServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        snapshot_service: Arc<dyn SnapshotServiceTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        settings_service: Arc<dyn SettingsServiceTrait>,
+    ) -> Self {
+        Self {
+            account_service,
+            activity_service,
+            snapshot_service,
+            asset_service,
+            settings_service,
+        }
+    }
+
+    fn load_reports(&self) -> Result<BTreeMap<String, ReportedHoldings>> {
+        match self
+            .settings_service
+            .get_setting_value(REPORTED_HOLDINGS_SETTINGS_KEY)?
+        {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| Error::Unexpected(format!("Invalid stored broker holdings: {}", e))),
+            None => Ok(BTreeMap::new()),
+        }
+    }
+
+    /// Ticker of each asset, used to match the broker's symbols.
+    async fn symbols_of(&self, asset_ids: &[String]) -> Result<HashMap<String, String>> {
+        if asset_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        Ok(self
+            .asset_service
+            .get_assets_by_asset_ids(asset_ids)
+            .await?
+            .into_iter()
+            .filter_map(|asset| {
+                let symbol = asset
+                    .display_code
+                    .clone()
+                    .or_else(|| asset.instrument_symbol.clone())?;
+                Some((asset.id, symbol))
+            })
+            .collect())
+    }
+
+    async fn reconcile(&self, reported: &ReportedHoldings) -> Result<ReconciliationReport> {
+        // Keyframes are saved on each day holdings change, so the latest one
+        // on or before the report date is the computed state on that date.
+        let computed = self
+            .snapshot_service
+            .get_holdings_keyframes(&reported.account_id, None, Some(reported.as_of))?
+            .into_iter()
+            .max_by_key(|s| s.snapshot_date);
+        let asset_ids: Vec<String> = computed
+            .iter()
+            .flat_map(|s| s.positions.keys().cloned())
+            .collect();
+        let symbols = self.symbols_of(&asset_ids).await?;
+        let activities = self
+            .activity_service
+            .get_activities_by_account_id(&reported.account_id)?;
+
+        let report = reconcile_holdings(reported, computed.as_ref(), &activities, &symbols);
+        debug!(
+            "Reconciled account {} as of {}: {} matched, {} discrepancies",
+            report.account_id,
+            report.as_of,
+            report.matched_count,
+            report.discrepancies.len()
+        );
+        Ok(report)
+    }
+
+    fn is_reconcilable(&self, account_id: &str) -> Result<bool> {
+        let account = self.account_service.get_account(account_id)?;
+        Ok(account.is_active && account.tracking_mode == TrackingMode::Transactions)
+    }
+}
+
+#[async_trait]
+impl HoldingsReconciliationServiceTrait for HoldingsReconciliationService {
+    async fn record_reported_holdings(&self, holdings: ReportedHoldings) -> Result<()> {
+        let mut reports = self.load_reports()?;
+        reports.insert(holdings.account_id.clone(), holdings);
+        let json = serde_json::to_string(&reports)
+            .map_err(|e| Error::Unexpected(format!("Failed to store broker holdings: {}", e)))?;
+        self.settings_service
+            .set_setting_value(REPORTED_HOLDINGS_SETTINGS_KEY, &json)
+            .await
+    }
+
+    fn get_reported_holdings(&self, account_id: &str) -> Result<Option<ReportedHoldings>> {
+        Ok(self.load_reports()?.remove(account_id))
+    }
+
+    async fn reconcile_account(&self, account_id: &str) -> Result<Option<ReconciliationReport>> {
+        let Some(reported) = self.get_reported_holdings(account_id)? else {
+            return Ok(None);
+        };
+        if !self.is_reconcilable(account_id)? {
+            return Ok(None);
+        }
+        self.reconcile(&reported).await.map(Some)
+    }
+
+    async fn reconcile_all(&self) -> Result<Vec<ReconciliationReport>> {
+        let active: HashSet<String> = self
+            .account_service
+            .get_active_accounts()?
+            .into_iter()
+            .filter(|a| a.tracking_mode == TrackingMode::Transactions)
+            .map(|a| a.id)
+            .collect();
+
+        let mut reports = Vec::new();
+        for reported in self.load_reports()?.into_values() {
+            if !active.contains(&reported.account_id) {
+                continue;
+            }
+            match self.reconcile(&reported).await {
+                Ok(report) => reports.push(report),
+                Err(e) => warn!(
+                    "Failed to reconcile holdings of account {}: {}",
+                    reported.account_id, e
+                ),
+            }
+        }
+        Ok(reports)
+    }
+
+    async fn create_proposed_drafts(&self, account_id: &str) -> Result<usize> {
+        let Some(report) = self.reconcile_account(account_id).await? else {
+            return Ok(0);
+        };
+        let recorded: HashSet<String> = self
+            .activity_service
+            .get_activities_by_account_id(account_id)?
+            .into_iter()
+            .filter(|a| a.source_system.as_deref() == Some(RECONCILIATION_SOURCE_SYSTEM))
+            .filter_map(|a| a.source_record_id)
+            .collect();
+
+        let mut created = 0;
+        for activity in report
+            .discrepancies
+            .into_iter()
+            .flat_map(|d| d.proposed_activities)
+        {
+            if activity
+                .source_record_id
+                .as_ref()
+                .is_some_and(|id| recorded.contains(id))
+            {
+                continue;
+            }
+            self.activity_service.create_activity(activity).await?;
+            created += 1;
+        }
+
+        if created > 0 {
+            debug!(
+                "Created {} reconciliation drafts for account {}",
+                created, account_id
+            );
+        }
+        Ok(created)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::errors::Result;
+use crate::reconciliation::reconciliation_model::{ReconciliationReport, ReportedHoldings};
+use async_trait::async_trait;
+
+/// Trait for holdings reconciliation service operations
+#[async_trait]
+pub trait HoldingsReconciliationServiceTrait: Send + Sync {
+    /// Stores the latest broker report of an account, replacing the previous one.
+    async fn record_reported_holdings(&self, holdings: ReportedHoldings) -> Result<()>;
+
+    /// Latest broker report of an account, if any.
+    fn get_reported_holdings(&self, account_id: &str) -> Result<Option<ReportedHoldings>>;
+
+    /// Reconciles the latest broker report of an account against the positions
+    /// computed from its activities. `None` when the account has no report or
+    /// is not tracked through transactions.
+    async fn reconcile_account(&self, account_id: &str) -> Result<Option<ReconciliationReport>>;
+
+    /// Reconciles every active account that has a broker report.
+    async fn reconcile_all(&self) -> Result<Vec<ReconciliationReport>>;
+
+    /// Creates the proposed draft activities of an account that were not
+    /// created before. Returns the number created.
+    async fn create_proposed_drafts(&self, account_id: &str) -> Result<usize>;
+}
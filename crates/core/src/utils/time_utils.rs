@@ -1,6 +1,6 @@
-use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
 use chrono_tz::Tz;
-use wealthfolio_market_data::resolver::exchange_metadata;
+use wealthfolio_market_data::resolver::{exchange_metadata, trading_calendar, TradingCalendar};
 
 /// Default timezone for valuation dates.
 /// This is the canonical timezone used to convert UTC instants to domain dates.
@@ -46,17 +46,16 @@ pub fn get_days_between(start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
     days
 }
 
-/// Returns the most recent trading day (weekday) before the given date.
-fn previous_trading_day(date: NaiveDate) -> NaiveDate {
-    let mut current = date;
-    while let Some(prev) = current.pred_opt() {
-        current = prev;
-        let weekday = current.weekday();
-        if weekday != Weekday::Sat && weekday != Weekday::Sun {
-            return current;
-        }
-    }
-    date
+/// Returns the trading calendar for an exchange.
+///
+/// Unknown or missing exchanges get a weekday-only calendar without holidays.
+pub fn exchange_calendar(mic: Option<&str>) -> &'static TradingCalendar {
+    trading_calendar(mic)
+}
+
+/// Returns whether the exchange is open on the given date.
+pub fn is_trading_day(date: NaiveDate, mic: Option<&str>) -> bool {
+    exchange_calendar(mic).is_trading_day(date)
 }
 
 /// Returns the effective trading date for a given exchange at the provided instant.
@@ -66,6 +65,7 @@ fn previous_trading_day(date: NaiveDate) -> NaiveDate {
 /// (plus grace), the effective date is the previous trading day. Unknown exchanges
 /// fall back to the UTC date.
 pub fn market_effective_date(now: DateTime<Utc>, mic: Option<&str>) -> NaiveDate {
+    let calendar = exchange_calendar(mic);
     let (tz, close_time) = match mic
         .and_then(exchange_metadata::mic_to_timezone)
         .and_then(|tz_name| tz_name.parse::<Tz>().ok())
@@ -77,10 +77,9 @@ pub fn market_effective_date(now: DateTime<Utc>, mic: Option<&str>) -> NaiveDate
     let local_now = now.with_timezone(&tz);
     let local_date = local_now.date_naive();
 
-    // If it's a weekend in the exchange/valuation timezone, use the previous trading day.
-    let weekday = local_date.weekday();
-    if weekday == Weekday::Sat || weekday == Weekday::Sun {
-        return previous_trading_day(local_date);
+    // If the exchange is closed today (weekend or holiday), use the previous trading day.
+    if !calendar.is_trading_day(local_date) {
+        return calendar.previous_trading_day(local_date);
     }
 
     let Some((close_hour, close_minute)) = close_time else {
@@ -104,7 +103,7 @@ pub fn market_effective_date(now: DateTime<Utc>, mic: Option<&str>) -> NaiveDate
 
     let cutoff = close_local + Duration::minutes(DEFAULT_MARKET_CLOSE_GRACE_MINUTES);
     if local_now < cutoff {
-        previous_trading_day(local_date)
+        calendar.previous_trading_day(local_date)
     } else {
         local_date
     }
@@ -113,7 +112,8 @@ pub fn market_effective_date(now: DateTime<Utc>, mic: Option<&str>) -> NaiveDate
 /// Returns the market-local trading date for fetch windows.
 ///
 /// Unlike `market_effective_date`, this does not wait for market close + grace.
-/// It uses the exchange-local calendar day (weekends roll back to prior trading day).
+/// It uses the exchange-local calendar day (weekends and holidays roll back to the prior
+/// trading day).
 pub fn market_calendar_date(now: DateTime<Utc>, mic: Option<&str>) -> NaiveDate {
     let tz = mic
         .and_then(exchange_metadata::mic_to_timezone)
@@ -121,10 +121,5 @@ pub fn market_calendar_date(now: DateTime<Utc>, mic: Option<&str>) -> NaiveDate
         .unwrap_or(DEFAULT_VALUATION_TZ);
 
     let local_date = now.with_timezone(&tz).date_naive();
-    let weekday = local_date.weekday();
-    if weekday == Weekday::Sat || weekday == Weekday::Sun {
-        previous_trading_day(local_date)
-    } else {
-        local_date
-    }
+    exchange_calendar(mic).trading_day_on_or_before(local_date)
 }
@@ -4,8 +4,10 @@
 //! - Root key generation and derivation
 //! - X25519 ECDH key exchange
 //! - XChaCha20-Poly1305 authenticated encryption
+//! - Passphrase-based key derivation (Argon2id)
 //! - Pairing code generation and verification
 
+use argon2::Argon2;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use chacha20poly1305::{
     aead::{Aead, KeyInit},
@@ -20,6 +22,7 @@ use x25519_dalek::{PublicKey, StaticSecret};
 /// Key sizes
 const ROOT_KEY_SIZE: usize = 32;
 const NONCE_SIZE: usize = 24; // XChaCha20 uses 24-byte nonce
+const SALT_SIZE: usize = 16;
 
 /// HKDF info strings
 const DEK_INFO: &[u8] = b"wealthfolio-dek";
@@ -121,6 +124,11 @@ pub fn derive_session_key(shared_secret_b64: &str, context: &str) -> Result<Stri
 
 /// Encrypt data using XChaCha20-Poly1305
 pub fn encrypt(key_b64: &str, plaintext: &str) -> Result<String, String> {
+    encrypt_bytes(key_b64, plaintext.as_bytes())
+}
+
+/// Encrypt binary data using XChaCha20-Poly1305, returning base64 of nonce + ciphertext
+pub fn encrypt_bytes(key_b64: &str, plaintext: &[u8]) -> Result<String, String> {
     let key_bytes: [u8; 32] = BASE64
         .decode(key_b64)
         .map_err(|e| format!("Invalid key: {}", e))?
@@ -137,7 +145,7 @@ pub fn encrypt(key_b64: &str, plaintext: &str) -> Result<String, String> {
 
     // Encrypt
     let ciphertext = cipher
-        .encrypt(nonce, plaintext.as_bytes())
+        .encrypt(nonce, plaintext)
         .map_err(|e| format!("Encryption failed: {}", e))?;
 
     // Prepend nonce to ciphertext
@@ -150,6 +158,12 @@ pub fn encrypt(key_b64: &str, plaintext: &str) -> Result<String, String> {
 
 /// Decrypt data using XChaCha20-Poly1305
 pub fn decrypt(key_b64: &str, ciphertext_b64: &str) -> Result<String, String> {
+    let plaintext = decrypt_bytes(key_b64, ciphertext_b64)?;
+    String::from_utf8(plaintext).map_err(|e| format!("Invalid UTF-8 in plaintext: {}", e))
+}
+
+/// Decrypt binary data produced by [`encrypt_bytes`]
+pub fn decrypt_bytes(key_b64: &str, ciphertext_b64: &str) -> Result<Vec<u8>, String> {
     let key_bytes: [u8; 32] = BASE64
         .decode(key_b64)
         .map_err(|e| format!("Invalid key: {}", e))?
@@ -171,11 +185,32 @@ pub fn decrypt(key_b64: &str, ciphertext_b64: &str) -> Result<String, String> {
     let cipher = XChaCha20Poly1305::new_from_slice(&key_bytes)
         .map_err(|e| format!("Failed to create cipher: {}", e))?;
 
-    let plaintext = cipher
+    cipher
         .decrypt(nonce, ciphertext)
-        .map_err(|_| "Decryption failed - invalid key or corrupted data")?;
+        .map_err(|_| "Decryption failed - invalid key or corrupted data".to_string())
+}
 
-    String::from_utf8(plaintext).map_err(|e| format!("Invalid UTF-8 in plaintext: {}", e))
+/// Generate a random salt for passphrase key derivation (base64)
+pub fn generate_salt() -> String {
+    let mut salt = [0u8; SALT_SIZE];
+    OsRng.fill_bytes(&mut salt);
+    BASE64.encode(salt)
+}
+
+/// Derive an encryption key from a user passphrase using Argon2id.
+/// Passphrases are low-entropy, so unlike the HKDF derivations above this uses
+/// a memory-hard function to slow down brute forcing.
+pub fn derive_passphrase_key(passphrase: &str, salt_b64: &str) -> Result<String, String> {
+    let salt = BASE64
+        .decode(salt_b64)
+        .map_err(|e| format!("Invalid salt: {}", e))?;
+
+    let mut key = [0u8; ROOT_KEY_SIZE];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| format!("Passphrase key derivation failed: {}", e))?;
+
+    Ok(BASE64.encode(key))
 }
 
 /// Generate a 6-character alphanumeric pairing code
@@ -297,6 +332,22 @@ mod tests {
         assert_eq!(plaintext, decrypted);
     }
 
+    #[test]
+    fn test_passphrase_key_encrypts_bytes() {
+        let salt = generate_salt();
+        let key = derive_passphrase_key("correct horse", &salt).unwrap();
+        let plaintext = [0u8, 159, 146, 150, 255];
+
+        // Same passphrase and salt should produce same key
+        assert_eq!(key, derive_passphrase_key("correct horse", &salt).unwrap());
+
+        let ciphertext = encrypt_bytes(&key, &plaintext).unwrap();
+        assert_eq!(decrypt_bytes(&key, &ciphertext).unwrap(), plaintext);
+
+        let wrong_key = derive_passphrase_key("wrong horse", &salt).unwrap();
+        assert!(decrypt_bytes(&wrong_key, &ciphertext).is_err());
+    }
+
     #[test]
     fn test_pairing_code() {
         let code = generate_pairing_code();
@@ -695,6 +695,7 @@ where
                     client_timestamp: remote_event.client_timestamp,
                     seq: remote_event.seq,
                     payload: payload_json,
+                    device_id: remote_event.device_id,
                 });
             }
 
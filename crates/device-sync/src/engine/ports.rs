@@ -63,6 +63,8 @@ pub struct ReplayEvent {
     pub client_timestamp: String,
     pub seq: i64,
     pub payload: serde_json::Value,
+    /// Device that produced the event.
+    pub device_id: String,
 }
 
 #[derive(Debug, Clone)]
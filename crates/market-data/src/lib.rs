@@ -70,10 +70,11 @@ pub use models::{
 
 // Re-export resolver types
 pub use resolver::{
-    exchanges_for_currency, get_exchange_list, mic_to_currency, mic_to_exchange_name,
-    strip_yahoo_suffix, yahoo_exchange_suffixes, yahoo_exchange_to_mic, yahoo_suffix_to_mic,
-    AssetResolver, ExchangeInfo, ExchangeMap, ExchangeSuffix, ResolutionSource, ResolvedInstrument,
-    Resolver, ResolverChain, RulesResolver, SymbolResolver,
+    dump_exchange_to_mics, exchanges_for_currency, get_exchange_list, mic_to_currency,
+    mic_to_exchange_name, strip_yahoo_suffix, yahoo_exchange_suffixes, yahoo_exchange_to_mic,
+    yahoo_suffix_to_mic, AssetResolver, ExchangeInfo, ExchangeMap, ExchangeSuffix,
+    ResolutionSource, ResolvedInstrument, Resolver, ResolverChain, RulesResolver, SymbolResolver,
+    TradingCalendar,
 };
 
 // Re-export provider types
@@ -86,6 +87,6 @@ pub use provider::{MarketDataProvider, ProviderCapabilities, RateLimit};
 
 // Re-export registry types
 pub use registry::{
-    CircuitBreaker, CircuitState, FetchDiagnostics, ProviderAttempt, ProviderRegistry,
+    CircuitBreaker, CircuitState, DualQuotes, FetchDiagnostics, ProviderAttempt, ProviderRegistry,
     QuoteValidator, RateLimiter, SkipReason, ValidationSeverity,
 };
@@ -24,6 +24,18 @@ use crate::models::{
 use crate::provider::MarketDataProvider;
 use crate::resolver::SymbolResolver;
 
+/// Quotes for the same instrument and range from two independent providers.
+///
+/// Returned by [`ProviderRegistry::fetch_quotes_dual`]. `secondary` is empty
+/// when no other provider could serve the request.
+#[derive(Debug, Clone, Default)]
+pub struct DualQuotes {
+    /// Quotes from the first provider that succeeded.
+    pub primary: Vec<Quote>,
+    /// Quotes from the next provider in priority order.
+    pub secondary: Vec<Quote>,
+}
+
 /// Provider registry for orchestrating market data fetching.
 pub struct ProviderRegistry {
     providers: Vec<Arc<dyn MarketDataProvider>>,
@@ -127,7 +139,72 @@ impl ProviderRegistry {
         end: DateTime<Utc>,
     ) -> Result<Vec<Quote>, MarketDataError> {
         let providers = self.ordered_providers(context, true); // true = historical
+        self.fetch_quotes_from(providers, context, start, end).await
+    }
+
+    /// Fetch quotes for an instrument, skipping the given providers.
+    ///
+    /// Used to obtain a second opinion from the next provider in priority order,
+    /// e.g. when a quote quality pass finds gaps or suspicious prints in data
+    /// returned by the primary provider. The preferred provider is ignored if it
+    /// is in `excluded`.
+    pub async fn fetch_quotes_excluding(
+        &self,
+        context: &QuoteContext,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        excluded: &[&str],
+    ) -> Result<Vec<Quote>, MarketDataError> {
+        let providers: Vec<_> = self
+            .ordered_providers(context, true)
+            .into_iter()
+            .filter(|p| !excluded.contains(&p.id()))
+            .collect();
+        self.fetch_quotes_from(providers, context, start, end).await
+    }
+
+    /// Fetch quotes for an instrument from two different providers.
+    ///
+    /// The primary set follows the normal fallback order of [`fetch_quotes`](Self::fetch_quotes).
+    /// The secondary set comes from the next provider that succeeds once the
+    /// primary's source is excluded. A failing secondary is not an error: the
+    /// caller gets an empty `secondary` and can fall back to single-source data.
+    pub async fn fetch_quotes_dual(
+        &self,
+        context: &QuoteContext,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<DualQuotes, MarketDataError> {
+        let primary = self.fetch_quotes(context, start, end).await?;
+        let Some(primary_source) = primary.first().map(|q| q.source.clone()) else {
+            return Ok(DualQuotes::default());
+        };
+
+        let secondary = match self
+            .fetch_quotes_excluding(context, start, end, &[primary_source.as_str()])
+            .await
+        {
+            Ok(quotes) => quotes,
+            Err(e) => {
+                debug!(
+                    "No secondary quotes for {:?} besides '{}': {:?}",
+                    context.instrument, primary_source, e
+                );
+                Vec::new()
+            }
+        };
+
+        Ok(DualQuotes { primary, secondary })
+    }
 
+    /// Try each provider in order until one returns valid quotes.
+    async fn fetch_quotes_from(
+        &self,
+        providers: Vec<&Arc<dyn MarketDataProvider>>,
+        context: &QuoteContext,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Quote>, MarketDataError> {
         if providers.is_empty() {
             warn!(
                 "No providers available for asset kind: {:?}",
@@ -1025,6 +1102,97 @@ mod tests {
         assert_eq!(ordered[2].id(), "PROVIDER_B");
     }
 
+    #[tokio::test]
+    async fn test_fetch_quotes_excluding_skips_provider() {
+        let providers: Vec<Arc<dyn MarketDataProvider>> = vec![
+            Arc::new(MockProvider::new("PROVIDER_A", 5, false)),
+            Arc::new(MockProvider::new("PROVIDER_B", 10, false)),
+        ];
+
+        let resolver = Arc::new(MockResolver);
+        let registry = ProviderRegistry::new(providers, resolver);
+
+        let context = QuoteContext {
+            instrument: InstrumentId::Equity {
+                ticker: Arc::from("TEST"),
+                mic: Some(Cow::Borrowed("XNAS")),
+            },
+            overrides: None,
+            currency_hint: None,
+            preferred_provider: Some(Cow::Borrowed("PROVIDER_A")),
+        };
+
+        let quotes = registry
+            .fetch_quotes_excluding(&context, Utc::now(), Utc::now(), &["PROVIDER_A"])
+            .await
+            .unwrap();
+
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes[0].source, "PROVIDER_B");
+
+        let result = registry
+            .fetch_quotes_excluding(
+                &context,
+                Utc::now(),
+                Utc::now(),
+                &["PROVIDER_A", "PROVIDER_B"],
+            )
+            .await;
+        assert!(matches!(result, Err(MarketDataError::NoProvidersAvailable)));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_quotes_dual_queries_two_providers() {
+        let providers: Vec<Arc<dyn MarketDataProvider>> = vec![
+            Arc::new(MockProvider::new("PROVIDER_A", 5, false)),
+            Arc::new(MockProvider::new("PROVIDER_B", 10, false)),
+        ];
+        let registry = ProviderRegistry::new(providers, Arc::new(MockResolver));
+
+        let context = QuoteContext {
+            instrument: InstrumentId::Equity {
+                ticker: Arc::from("TEST"),
+                mic: Some(Cow::Borrowed("XNAS")),
+            },
+            overrides: None,
+            currency_hint: None,
+            preferred_provider: None,
+        };
+
+        let dual = registry
+            .fetch_quotes_dual(&context, Utc::now(), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(dual.primary[0].source, "PROVIDER_A");
+        assert_eq!(dual.secondary[0].source, "PROVIDER_B");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_quotes_dual_tolerates_secondary_failure() {
+        let providers: Vec<Arc<dyn MarketDataProvider>> = vec![
+            Arc::new(MockProvider::new("PROVIDER_A", 5, false)),
+            Arc::new(MockProvider::new("PROVIDER_B", 10, true)),
+        ];
+        let registry = ProviderRegistry::new(providers, Arc::new(MockResolver));
+
+        let context = QuoteContext {
+            instrument: InstrumentId::Equity {
+                ticker: Arc::from("TEST"),
+                mic: Some(Cow::Borrowed("XNAS")),
+            },
+            overrides: None,
+            currency_hint: None,
+            preferred_provider: None,
+        };
+
+        let dual = registry
+            .fetch_quotes_dual(&context, Utc::now(), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(dual.primary.len(), 1);
+        assert!(dual.secondary.is_empty());
+    }
+
     #[test]
     fn test_filter_by_instrument_kind() {
         struct CryptoOnlyProvider;
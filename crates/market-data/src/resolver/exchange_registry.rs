@@ -1,19 +1,24 @@
 //! JSON-driven exchange metadata registry.
 //!
 //! Loads `exchanges.json` at compile time via `include_str!` and builds
-//! reverse-lookup indexes once via `lazy_static`.
+//! reverse-lookup indexes once via `lazy_static`. Trading calendars declared in
+//! the same file are built by `trading_calendar`.
 
 use std::collections::HashMap;
 
 use lazy_static::lazy_static;
 use serde::Deserialize;
 
+use super::trading_calendar::CalendarSpec;
+
 // ── JSON schema ──────────────────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct ExchangeCatalog {
     pub exchanges: Vec<ExchangeEntry>,
     pub currency_priority: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub calendars: HashMap<String, CalendarSpec>,
 }
 
 #[derive(Debug, Clone, Deserialize, serde::Serialize)]
@@ -29,6 +34,9 @@ pub struct ExchangeEntry {
     pub timezone: Option<String>,
     #[serde(default)]
     pub close: Option<[u8; 2]>,
+    /// Trading calendar id from the catalog's `calendars` section.
+    #[serde(default)]
+    pub calendar: Option<String>,
     #[serde(default)]
     pub yahoo: Option<YahooInfo>,
     #[serde(default)]
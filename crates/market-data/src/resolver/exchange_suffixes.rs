@@ -140,6 +140,34 @@ pub fn yahoo_suffix_to_mic(suffix: &str) -> Option<&'static str> {
         .copied()
 }
 
+/// Market codes used by end-of-day bulk dumps (Stooq, EODHD) that name a
+/// country or venue group rather than a single exchange.
+const DUMP_MARKET_CODES: &[(&str, &[&str])] = &[
+    ("US", &["XNYS", "XNAS", "ARCX", "BATS", "XASE"]),
+    ("UK", &["XLON"]),
+    ("LSE", &["XLON"]),
+    ("XETRA", &["XETR"]),
+    ("DE", &["XETR", "XFRA"]),
+    ("JP", &["XTKS"]),
+    ("HK", &["XHKG"]),
+    ("WAR", &["XWAR"]),
+    ("PL", &["XWAR"]),
+    ("HU", &["XBUD"]),
+];
+
+/// Map an exchange code from a bulk quote dump to candidate MICs.
+///
+/// Accepts the market codes used by Stooq (`AAPL.US`) and EODHD (`VOD.LSE`)
+/// as well as Yahoo suffixes (`SHOP.TO`). Returns an empty list for unknown
+/// codes, which callers should treat as part of the ticker (e.g. `BRK.B`).
+pub fn dump_exchange_to_mics(code: &str) -> Vec<&'static str> {
+    let code = code.trim().trim_start_matches('.').to_uppercase();
+    if let Some((_, mics)) = DUMP_MARKET_CODES.iter().find(|(c, _)| *c == code) {
+        return mics.to_vec();
+    }
+    yahoo_suffix_to_mic(&code).into_iter().collect()
+}
+
 /// Extract canonical ticker from Yahoo provider symbol.
 ///
 /// Uses a whitelist approach to safely strip exchange suffixes while preserving
@@ -242,6 +270,15 @@ mod tests {
         assert_eq!(yahoo_exchange_to_mic("UNKNOWN"), None);
     }
 
+    #[test]
+    fn test_dump_exchange_to_mics() {
+        assert!(dump_exchange_to_mics("US").contains(&"XNAS"));
+        assert_eq!(dump_exchange_to_mics("uk"), vec!["XLON"]);
+        assert_eq!(dump_exchange_to_mics("LSE"), vec!["XLON"]);
+        assert_eq!(dump_exchange_to_mics(".TO"), vec!["XTSE"]);
+        assert!(dump_exchange_to_mics("B").is_empty());
+    }
+
     #[test]
     fn test_strip_yahoo_suffix() {
         // Normal exchange suffixes
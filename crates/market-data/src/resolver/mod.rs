@@ -81,6 +81,7 @@ pub mod exchange_metadata;
 pub(crate) mod exchange_registry;
 mod exchange_suffixes;
 mod rules_resolver;
+mod trading_calendar;
 mod traits;
 
 // Re-export main types
@@ -92,8 +93,9 @@ pub use exchange_metadata::{
 };
 pub use exchange_registry::{get_exchange_list, ExchangeInfo};
 pub use exchange_suffixes::{
-    strip_yahoo_suffix, yahoo_exchange_suffixes, yahoo_exchange_to_mic, yahoo_suffix_to_mic,
-    ExchangeMap, ExchangeSuffix,
+    dump_exchange_to_mics, strip_yahoo_suffix, yahoo_exchange_suffixes, yahoo_exchange_to_mic,
+    yahoo_suffix_to_mic, ExchangeMap, ExchangeSuffix,
 };
 pub use rules_resolver::RulesResolver;
+pub use trading_calendar::{mic_to_trading_calendar, trading_calendar, TradingCalendar};
 pub use traits::{ResolutionSource, ResolvedInstrument, Resolver, SymbolResolver};
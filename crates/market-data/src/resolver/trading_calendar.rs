@@ -0,0 +1,534 @@
+//! Exchange trading calendars.
+//!
+//! Calendars are declared in `exchanges.json` under `calendars` and attached to
+//! exchanges via their `calendar` field. Each calendar lists its weekend days and
+//! a set of holiday rules (fixed dates with weekend observance, nth weekdays,
+//! Easter offsets, equinoxes) plus one-off closures. Holidays are expanded per
+//! year on first use and cached.
+//!
+//! Exchanges without a calendar fall back to a weekday-only calendar, which
+//! matches the behaviour before calendars existed.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::RwLock;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+
+use super::exchange_registry::REGISTRY;
+
+// ── JSON schema ──────────────────────────────────────────────────────────────
+
+/// Calendar definition as declared in `exchanges.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct CalendarSpec {
+    #[serde(default = "default_weekend")]
+    pub weekend: Vec<Weekday>,
+    #[serde(default)]
+    pub holidays: Vec<HolidayRule>,
+    /// One-off closures (national mourning days, coronations, ...).
+    #[serde(default)]
+    pub closures: Vec<NaiveDate>,
+    /// Treat a weekday sandwiched between two holidays as a holiday (Japan).
+    #[serde(default)]
+    pub bridge_days: bool,
+}
+
+fn default_weekend() -> Vec<Weekday> {
+    vec![Weekday::Sat, Weekday::Sun]
+}
+
+/// A recurring holiday rule.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct HolidayRule {
+    #[serde(flatten)]
+    pub kind: HolidayKind,
+    #[serde(default)]
+    pub observance: Observance,
+    /// First year the rule applies (inclusive).
+    #[serde(default)]
+    pub from: Option<i32>,
+    /// Last year the rule applies (inclusive).
+    #[serde(default)]
+    pub until: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub(crate) enum HolidayKind {
+    /// Same calendar date every year.
+    Fixed { month: u32, day: u32 },
+    /// The nth given weekday of a month; negative `n` counts from the end.
+    NthWeekday { month: u32, weekday: Weekday, n: i8 },
+    /// The last given weekday on or before a date (e.g. Victoria Day).
+    WeekdayOnOrBefore {
+        month: u32,
+        day: u32,
+        weekday: Weekday,
+    },
+    /// Offset in days from Western Easter Sunday.
+    Easter { offset: i64 },
+    /// Japanese spring or autumn equinox day.
+    Equinox { season: Season },
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Season {
+    Spring,
+    Autumn,
+}
+
+/// How a holiday falling on a weekend is observed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Observance {
+    /// Not moved; a weekend holiday is simply lost.
+    #[default]
+    None,
+    /// Saturday moves to Friday, Sunday to Monday.
+    NearestWeekday,
+    /// Only Sunday moves to Monday.
+    SundayToMonday,
+    /// Weekend moves to the next weekday that is not already a holiday.
+    Substitute,
+    /// Sunday moves to the next day that is not already a holiday.
+    SundaySubstitute,
+}
+
+// ── Calendar ─────────────────────────────────────────────────────────────────
+
+/// Trading calendar for an exchange.
+#[derive(Debug)]
+pub struct TradingCalendar {
+    id: String,
+    spec: CalendarSpec,
+    holidays_by_year: RwLock<HashMap<i32, HashSet<NaiveDate>>>,
+}
+
+impl TradingCalendar {
+    fn from_spec(id: impl Into<String>, spec: CalendarSpec) -> Self {
+        Self {
+            id: id.into(),
+            spec,
+            holidays_by_year: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Weekday-only calendar with no holidays.
+    pub fn weekdays() -> &'static TradingCalendar {
+        &WEEKDAYS
+    }
+
+    /// Calendar identifier (e.g. "US", "JP"), or "WEEKDAYS" for the fallback.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Whether the date falls on this calendar's weekend.
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.spec.weekend.contains(&date.weekday())
+    }
+
+    /// Whether the exchange is closed for a holiday on a regular weekday.
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        if let Some(days) = self.holidays_by_year.read().unwrap().get(&date.year()) {
+            return days.contains(&date);
+        }
+        let days = self.compute_holidays(date.year());
+        let is_holiday = days.contains(&date);
+        self.holidays_by_year
+            .write()
+            .unwrap()
+            .insert(date.year(), days);
+        is_holiday
+    }
+
+    /// Whether the exchange is open on the given date.
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// Holidays in the given year, sorted.
+    pub fn holidays(&self, year: i32) -> Vec<NaiveDate> {
+        let mut days: Vec<NaiveDate> = self.compute_holidays(year).into_iter().collect();
+        days.sort();
+        days
+    }
+
+    /// The last trading day strictly before `date`.
+    pub fn previous_trading_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut current = date;
+        while let Some(prev) = current.pred_opt() {
+            current = prev;
+            if self.is_trading_day(current) {
+                return current;
+            }
+        }
+        date
+    }
+
+    /// The first trading day strictly after `date`.
+    pub fn next_trading_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut current = date;
+        while let Some(next) = current.succ_opt() {
+            current = next;
+            if self.is_trading_day(current) {
+                return current;
+            }
+        }
+        date
+    }
+
+    /// `date` itself if it is a trading day, otherwise the previous trading day.
+    pub fn trading_day_on_or_before(&self, date: NaiveDate) -> NaiveDate {
+        if self.is_trading_day(date) {
+            date
+        } else {
+            self.previous_trading_day(date)
+        }
+    }
+
+    /// Steps back `count` trading days from `date`.
+    pub fn sub_trading_days(&self, date: NaiveDate, count: u32) -> NaiveDate {
+        (0..count).fold(date, |d, _| self.previous_trading_day(d))
+    }
+
+    /// Counts trading days after `from` up to and including `to`.
+    ///
+    /// Returns 0 if `to` is on or before `from`.
+    pub fn trading_days_between(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        if to <= from {
+            return 0;
+        }
+        from.iter_days()
+            .skip(1)
+            .take_while(|d| *d <= to)
+            .filter(|d| self.is_trading_day(*d))
+            .count() as i64
+    }
+
+    /// All trading days in `[start, end]`.
+    pub fn trading_days(&self, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        if start > end {
+            return Vec::new();
+        }
+        start
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .filter(|d| self.is_trading_day(*d))
+            .collect()
+    }
+
+    fn compute_holidays(&self, year: i32) -> HashSet<NaiveDate> {
+        let active: Vec<&HolidayRule> = self
+            .spec
+            .holidays
+            .iter()
+            .filter(|r| r.from.is_none_or(|from| year >= from))
+            .filter(|r| r.until.is_none_or(|until| year <= until))
+            .collect();
+
+        // Raw dates first so substitution can see every holiday of the year.
+        let raw: Vec<Option<NaiveDate>> = active.iter().map(|r| r.kind.date_in(year)).collect();
+        let raw_set: HashSet<NaiveDate> = raw.iter().flatten().copied().collect();
+
+        let mut observed: BTreeSet<NaiveDate> = BTreeSet::new();
+        for (rule, date) in active.iter().zip(raw) {
+            let Some(date) = date else { continue };
+            if let Some(day) = self.observe(date, rule.observance, &raw_set, &observed) {
+                observed.insert(day);
+            }
+        }
+
+        observed.extend(
+            self.spec
+                .closures
+                .iter()
+                .filter(|d| d.year() == year)
+                .copied(),
+        );
+
+        if self.spec.bridge_days {
+            let bridges: Vec<NaiveDate> = observed
+                .iter()
+                .filter_map(|d| d.succ_opt())
+                .filter(|d| {
+                    !self.is_weekend(*d)
+                        && !observed.contains(d)
+                        && d.succ_opt().is_some_and(|next| observed.contains(&next))
+                })
+                .collect();
+            observed.extend(bridges);
+        }
+
+        observed.into_iter().collect()
+    }
+
+    fn observe(
+        &self,
+        date: NaiveDate,
+        observance: Observance,
+        raw: &HashSet<NaiveDate>,
+        observed: &BTreeSet<NaiveDate>,
+    ) -> Option<NaiveDate> {
+        let taken = |d: &NaiveDate| raw.contains(d) || observed.contains(d);
+        match (observance, date.weekday()) {
+            (_, wd) if !self.spec.weekend.contains(&wd) => Some(date),
+            (Observance::None, _) => None,
+            (Observance::NearestWeekday, Weekday::Sat) => date.pred_opt(),
+            (Observance::NearestWeekday, Weekday::Sun) => date.succ_opt(),
+            (Observance::SundayToMonday, Weekday::Sun) => date.succ_opt(),
+            (Observance::Substitute, _) => date
+                .iter_days()
+                .skip(1)
+                .find(|d| !self.is_weekend(*d) && !taken(d)),
+            (Observance::SundaySubstitute, Weekday::Sun) => {
+                date.iter_days().skip(1).find(|d| !taken(d))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl HolidayKind {
+    fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            HolidayKind::Fixed { month, day } => NaiveDate::from_ymd_opt(year, month, day),
+            HolidayKind::NthWeekday { month, weekday, n } => {
+                if n >= 0 {
+                    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8)
+                } else {
+                    last_weekday_of_month(year, month, weekday)
+                        .map(|d| d - Duration::weeks(i64::from(-n) - 1))
+                        .filter(|d| d.month() == month)
+                }
+            }
+            HolidayKind::WeekdayOnOrBefore {
+                month,
+                day,
+                weekday,
+            } => {
+                let date = NaiveDate::from_ymd_opt(year, month, day)?;
+                let back = (7 + date.weekday().num_days_from_monday()
+                    - weekday.num_days_from_monday())
+                    % 7;
+                Some(date - Duration::days(i64::from(back)))
+            }
+            HolidayKind::Easter { offset } => {
+                easter_sunday(year).map(|d| d + Duration::days(offset))
+            }
+            HolidayKind::Equinox { season } => equinox_day(year, season),
+        }
+    }
+}
+
+fn last_weekday_of_month(year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+    let first_of_next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    let last = first_of_next.pred_opt()?;
+    let back = (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    Some(last - Duration::days(i64::from(back)))
+}
+
+/// Western Easter Sunday (anonymous Gregorian algorithm).
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Japanese equinox holiday, using the standard approximation valid for 1980–2099.
+fn equinox_day(year: i32, season: Season) -> Option<NaiveDate> {
+    let (month, base) = match season {
+        Season::Spring => (3, 20.8431),
+        Season::Autumn => (9, 23.2488),
+    };
+    let y = f64::from(year - 1980);
+    let day = (base + 0.242194 * y - (y / 4.0).floor()).floor();
+    NaiveDate::from_ymd_opt(year, month, day as u32)
+}
+
+// ── Lookup ───────────────────────────────────────────────────────────────────
+
+lazy_static! {
+    static ref WEEKDAYS: TradingCalendar = TradingCalendar::from_spec(
+        "WEEKDAYS",
+        CalendarSpec {
+            weekend: default_weekend(),
+            holidays: Vec::new(),
+            closures: Vec::new(),
+            bridge_days: false,
+        },
+    );
+    static ref CALENDARS: HashMap<String, TradingCalendar> = REGISTRY
+        .catalog
+        .calendars
+        .iter()
+        .map(|(id, spec)| (id.clone(), TradingCalendar::from_spec(id, spec.clone())))
+        .collect();
+    static ref CALENDAR_BY_MIC: HashMap<String, &'static TradingCalendar> = REGISTRY
+        .catalog
+        .exchanges
+        .iter()
+        .filter_map(|e| Some((e.mic.clone(), CALENDARS.get(e.calendar.as_ref()?)?)))
+        .collect();
+}
+
+/// Get the trading calendar declared for a MIC code, if any.
+pub fn mic_to_trading_calendar(mic: &str) -> Option<&'static TradingCalendar> {
+    CALENDAR_BY_MIC.get(mic).copied()
+}
+
+/// Get the trading calendar for an optional MIC, falling back to weekdays only.
+pub fn trading_calendar(mic: Option<&str>) -> &'static TradingCalendar {
+    mic.and_then(mic_to_trading_calendar)
+        .unwrap_or_else(TradingCalendar::weekdays)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_easter_sunday() {
+        assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter_sunday(2026), Some(date(2026, 4, 5)));
+    }
+
+    #[test]
+    fn test_all_exchange_calendars_resolve() {
+        for entry in &REGISTRY.catalog.exchanges {
+            if let Some(ref id) = entry.calendar {
+                assert!(CALENDARS.contains_key(id), "unknown calendar {id}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_nyse_holidays_2025() {
+        let nyse = trading_calendar(Some("XNYS"));
+        assert_eq!(
+            nyse.holidays(2025),
+            vec![
+                date(2025, 1, 1),
+                date(2025, 1, 9), // national day of mourning
+                date(2025, 1, 20),
+                date(2025, 2, 17),
+                date(2025, 4, 18),
+                date(2025, 5, 26),
+                date(2025, 6, 19),
+                date(2025, 7, 4),
+                date(2025, 9, 1),
+                date(2025, 11, 27),
+                date(2025, 12, 25),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nyse_weekend_observance() {
+        let nyse = trading_calendar(Some("XNYS"));
+        // July 4th 2026 is a Saturday: observed Friday July 3rd
+        assert!(!nyse.is_trading_day(date(2026, 7, 3)));
+        // New Year 2022 was a Saturday: NYSE stayed open on Dec 31, 2021
+        assert!(nyse.is_trading_day(date(2021, 12, 31)));
+        // Juneteenth only from 2022
+        assert!(nyse.is_trading_day(date(2021, 6, 18)));
+    }
+
+    #[test]
+    fn test_lse_christmas_substitution() {
+        let lse = trading_calendar(Some("XLON"));
+        // 2021: Christmas Sat, Boxing Day Sun -> Mon 27 and Tue 28
+        assert!(!lse.is_trading_day(date(2021, 12, 27)));
+        assert!(!lse.is_trading_day(date(2021, 12, 28)));
+        assert!(lse.is_trading_day(date(2021, 12, 29)));
+        // 2022: Christmas Sun, Boxing Day Mon -> Christmas observed Tue 27
+        assert!(!lse.is_trading_day(date(2022, 12, 26)));
+        assert!(!lse.is_trading_day(date(2022, 12, 27)));
+    }
+
+    #[test]
+    fn test_tse_golden_week() {
+        let tse = trading_calendar(Some("XTKS"));
+        // 2026: Constitution Day (May 3) is a Sunday, substituted on Wed May 6
+        assert!(!tse.is_trading_day(date(2026, 4, 29)));
+        assert!(!tse.is_trading_day(date(2026, 5, 4)));
+        assert!(!tse.is_trading_day(date(2026, 5, 5)));
+        assert!(!tse.is_trading_day(date(2026, 5, 6)));
+        assert!(tse.is_trading_day(date(2026, 5, 7)));
+        // 2026: Sep 22 sits between Respect for the Aged Day and the equinox
+        assert!(!tse.is_trading_day(date(2026, 9, 21)));
+        assert!(!tse.is_trading_day(date(2026, 9, 22)));
+        assert!(!tse.is_trading_day(date(2026, 9, 23)));
+    }
+
+    #[test]
+    fn test_friday_saturday_weekend() {
+        let tadawul = trading_calendar(Some("XSAU"));
+        assert!(!tadawul.is_trading_day(date(2024, 1, 19))); // Friday
+        assert!(tadawul.is_trading_day(date(2024, 1, 21))); // Sunday
+    }
+
+    #[test]
+    fn test_unknown_mic_falls_back_to_weekdays() {
+        let cal = trading_calendar(Some("UNKNOWN"));
+        assert_eq!(cal.id(), "WEEKDAYS");
+        assert!(cal.is_trading_day(date(2024, 12, 25)));
+        assert!(!cal.is_trading_day(date(2024, 12, 28)));
+        assert!(trading_calendar(None).is_trading_day(date(2024, 12, 25)));
+    }
+
+    #[test]
+    fn test_trading_day_navigation() {
+        let nyse = trading_calendar(Some("XNYS"));
+        // Thursday after Christmas 2024 -> previous trading day is Tue Dec 24
+        assert_eq!(
+            nyse.previous_trading_day(date(2024, 12, 26)),
+            date(2024, 12, 24)
+        );
+        assert_eq!(
+            nyse.next_trading_day(date(2024, 12, 24)),
+            date(2024, 12, 26)
+        );
+        assert_eq!(
+            nyse.trading_day_on_or_before(date(2024, 12, 25)),
+            date(2024, 12, 24)
+        );
+        assert_eq!(
+            nyse.sub_trading_days(date(2024, 12, 26), 2),
+            date(2024, 12, 23)
+        );
+        // Dec 24 -> Dec 31: 26, 27, 30, 31
+        assert_eq!(
+            nyse.trading_days_between(date(2024, 12, 24), date(2024, 12, 31)),
+            4
+        );
+        assert_eq!(
+            nyse.trading_days(date(2024, 12, 24), date(2024, 12, 27)),
+            vec![date(2024, 12, 24), date(2024, 12, 26), date(2024, 12, 27)]
+        );
+    }
+}
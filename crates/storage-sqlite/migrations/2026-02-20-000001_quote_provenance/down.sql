@@ -0,0 +1,3 @@
+-- Drop quote provenance table
+DROP INDEX IF EXISTS idx_quote_provenance_asset_day;
+DROP TABLE IF EXISTS quote_provenance;
@@ -0,0 +1,17 @@
+-- Quote provenance table
+-- Records which providers supplied each stored quote when dual-provider
+-- consensus is enabled, with every candidate close and their deviation.
+
+CREATE TABLE quote_provenance (
+    quote_id TEXT PRIMARY KEY NOT NULL,
+    asset_id TEXT NOT NULL,
+    day TEXT NOT NULL,
+    status TEXT NOT NULL,
+    field_sources TEXT NOT NULL,
+    candidates TEXT NOT NULL,
+    deviation TEXT,
+    created_at TEXT NOT NULL
+);
+
+-- Index for per-asset range lookups
+CREATE INDEX idx_quote_provenance_asset_day ON quote_provenance(asset_id, day);
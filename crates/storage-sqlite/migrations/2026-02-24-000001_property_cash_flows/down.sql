@@ -0,0 +1,3 @@
+-- Drop property cash flows table
+DROP INDEX IF EXISTS idx_property_cash_flows_asset_date;
+DROP TABLE IF EXISTS property_cash_flows;
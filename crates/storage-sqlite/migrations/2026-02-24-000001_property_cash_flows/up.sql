@@ -0,0 +1,18 @@
+-- Property cash flows
+-- Rental income, operating expenses and mortgage payments recorded against
+-- a property asset, used to derive NOI, cap rate and cash-on-cash return.
+
+CREATE TABLE property_cash_flows (
+    id TEXT PRIMARY KEY NOT NULL,
+    asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK(kind IN ('INCOME', 'EXPENSE', 'MORTGAGE_PAYMENT')),
+    category TEXT,
+    amount TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    date TEXT NOT NULL,
+    notes TEXT,
+    created_at TEXT NOT NULL
+);
+
+-- Index for per-property date range lookups
+CREATE INDEX idx_property_cash_flows_asset_date ON property_cash_flows(asset_id, date);
@@ -0,0 +1,3 @@
+-- Drop equity grants table
+DROP INDEX IF EXISTS idx_equity_grants_account_asset;
+DROP TABLE IF EXISTS equity_grants;
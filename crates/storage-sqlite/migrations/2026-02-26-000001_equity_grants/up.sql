@@ -0,0 +1,25 @@
+-- Equity grants
+-- Employee equity (RSU, ISO/NSO options, ESPP) with vesting schedules.
+-- Vests of share-delivering grants are recorded as TRANSFER_IN activities
+-- (source_system = 'EQUITY_GRANT'), so no vest rows are stored here.
+
+CREATE TABLE equity_grants (
+    id TEXT PRIMARY KEY NOT NULL,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    grant_type TEXT NOT NULL CHECK(grant_type IN ('RSU', 'ISO', 'NSO', 'ESPP')),
+    grant_date TEXT NOT NULL,
+    total_shares TEXT NOT NULL,
+    strike_price TEXT,
+    grant_price TEXT,
+    currency TEXT NOT NULL,
+    vesting_start_date TEXT NOT NULL,
+    vesting_months INTEGER NOT NULL DEFAULT 0,
+    cliff_months INTEGER NOT NULL DEFAULT 0,
+    vesting_interval_months INTEGER NOT NULL DEFAULT 1,
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_equity_grants_account_asset ON equity_grants(account_id, asset_id);
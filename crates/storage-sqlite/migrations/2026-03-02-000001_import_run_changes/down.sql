@@ -0,0 +1,3 @@
+-- Drop import run changes table
+DROP INDEX IF EXISTS idx_import_run_changes_run;
+DROP TABLE IF EXISTS import_run_changes;
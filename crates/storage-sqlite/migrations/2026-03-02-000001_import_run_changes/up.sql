@@ -0,0 +1,19 @@
+-- Import run changes
+-- One row per activity inserted or updated by an import run, so a run can be
+-- reviewed row by row and rolled back. before_json holds the overwritten
+-- activity row (updates only), after_json the row as the run wrote it.
+
+CREATE TABLE import_run_changes (
+    id TEXT PRIMARY KEY NOT NULL,
+    import_run_id TEXT NOT NULL REFERENCES import_runs(id) ON DELETE CASCADE,
+    activity_id TEXT NOT NULL,
+    change_type TEXT NOT NULL CHECK(change_type IN ('INSERT', 'UPDATE')),
+    before_json TEXT,
+    after_json TEXT,
+    status TEXT NOT NULL DEFAULT 'PENDING'
+        CHECK(status IN ('PENDING', 'APPROVED', 'REJECTED', 'ROLLED_BACK')),
+    reviewed_at TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_import_run_changes_run ON import_run_changes(import_run_id);
@@ -0,0 +1,23 @@
+-- Audit log
+-- Append-only history of activities, accounts and assets. after_json holds the
+-- stored row once the change was made (NULL for deletes), before_json the
+-- version recorded by the previous entry of the same entity.
+
+CREATE TABLE audit_log (
+    id TEXT PRIMARY KEY NOT NULL,
+    entity_type TEXT NOT NULL CHECK(entity_type IN ('ACTIVITY', 'ACCOUNT', 'ASSET')),
+    entity_id TEXT NOT NULL,
+    operation TEXT NOT NULL CHECK(operation IN ('CREATE', 'UPDATE', 'DELETE', 'RESTORE')),
+    source TEXT NOT NULL
+        CHECK(source IN ('USER', 'IMPORT', 'BROKER_SYNC', 'DEVICE_SYNC', 'SYSTEM')),
+    device_id TEXT,
+    import_run_id TEXT,
+    restored_from TEXT,
+    changed_fields TEXT,
+    before_json TEXT,
+    after_json TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_audit_log_entity ON audit_log(entity_type, entity_id, created_at);
+CREATE INDEX idx_audit_log_created_at ON audit_log(created_at);
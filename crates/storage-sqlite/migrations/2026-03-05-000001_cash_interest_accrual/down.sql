@@ -0,0 +1 @@
+ALTER TABLE holdings_snapshots DROP COLUMN cash_interest_accrued;
@@ -0,0 +1,3 @@
+-- Cash interest accrued since the last payment date, per holdings snapshot.
+-- Accounts with cash interest settings get it on their next recalculation.
+ALTER TABLE holdings_snapshots ADD COLUMN cash_interest_accrued TEXT NOT NULL DEFAULT '0';
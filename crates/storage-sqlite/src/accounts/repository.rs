@@ -41,13 +41,14 @@ impl AccountRepositoryTrait for AccountRepository {
                 let mut account_db: AccountDB = new_account.into();
                 account_db.id = uuid::Uuid::new_v4().to_string();
 
-                diesel::insert_into(accounts::table)
+                // Read back the stored row so timestamps match the database defaults
+                let result_db = diesel::insert_into(accounts::table)
                     .values(&account_db)
-                    .execute(tx.conn())
+                    .get_result::<AccountDB>(tx.conn())
                     .map_err(StorageError::from)?;
 
-                let payload_db = account_db.clone();
-                let account: Account = account_db.into();
+                let payload_db = result_db.clone();
+                let account: Account = result_db.into();
                 tx.insert(&payload_db)?;
 
                 Ok(account)
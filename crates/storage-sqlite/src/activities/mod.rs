@@ -3,5 +3,5 @@
 mod model;
 mod repository;
 
-pub use model::{ActivityDB, ActivityDetailsDB, ImportMappingDB, IncomeDataDB};
+pub use model::{ActivityDB, ActivityDetailsDB, ImportMappingDB, ImportRunChangeDB, IncomeDataDB};
 pub use repository::ActivityRepository;
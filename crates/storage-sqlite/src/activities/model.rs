@@ -7,7 +7,8 @@ use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 use wealthfolio_core::activities::{
-    Activity, ActivityStatus, ActivityUpdate, ActivityUpsert, NewActivity,
+    Activity, ActivityStatus, ActivityUpdate, ActivityUpsert, ImportRunChange,
+    ImportRunChangeStatus, ImportRunChangeType, NewActivity,
 };
 
 /// Helper function to parse a string into a Decimal,
@@ -210,6 +211,96 @@ pub struct ImportMappingDB {
     pub updated_at: NaiveDateTime,
 }
 
+/// Database model for an activity inserted or updated by an import run
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::import_run_changes)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ImportRunChangeDB {
+    pub id: String,
+    pub import_run_id: String,
+    pub activity_id: String,
+    pub change_type: String,
+    /// Activity row overwritten by the run (updates only)
+    pub before_json: Option<String>,
+    /// Activity row as the run wrote it
+    pub after_json: Option<String>,
+    pub status: String,
+    pub reviewed_at: Option<String>,
+    pub created_at: String,
+}
+
+impl ImportRunChangeDB {
+    pub fn new(
+        import_run_id: &str,
+        change_type: ImportRunChangeType,
+        before: Option<&ActivityDB>,
+        after: &ActivityDB,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            import_run_id: import_run_id.to_string(),
+            activity_id: after.id.clone(),
+            change_type: enum_to_db(&change_type),
+            before_json: before.and_then(|row| serde_json::to_string(row).ok()),
+            after_json: serde_json::to_string(after).ok(),
+            status: enum_to_db(&ImportRunChangeStatus::Pending),
+            reviewed_at: None,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Activity row overwritten by the run, to restore on revert
+    pub fn before_row(&self) -> Option<ActivityDB> {
+        self.before_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+    }
+
+    pub fn change_type(&self) -> ImportRunChangeType {
+        enum_from_db(&self.change_type).unwrap_or(ImportRunChangeType::Insert)
+    }
+}
+
+/// Stores a SCREAMING_SNAKE_CASE enum as its bare serde name.
+pub fn enum_to_db<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value)
+        .unwrap_or_default()
+        .trim_matches('"')
+        .to_string()
+}
+
+fn enum_from_db<T: serde::de::DeserializeOwned>(value: &str) -> Option<T> {
+    serde_json::from_str(&format!("\"{}\"", value)).ok()
+}
+
+impl From<ImportRunChangeDB> for ImportRunChange {
+    fn from(db: ImportRunChangeDB) -> Self {
+        use chrono::DateTime;
+
+        let parse_row = |json: Option<&str>| {
+            json.and_then(|j| serde_json::from_str::<ActivityDB>(j).ok())
+                .map(Activity::from)
+        };
+        Self {
+            change_type: db.change_type(),
+            before: parse_row(db.before_json.as_deref()),
+            after: parse_row(db.after_json.as_deref()),
+            status: enum_from_db(&db.status).unwrap_or_default(),
+            reviewed_at: db.reviewed_at.and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc))
+            }),
+            created_at: DateTime::parse_from_rfc3339(&db.created_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            id: db.id,
+            import_run_id: db.import_run_id,
+            activity_id: db.activity_id,
+        }
+    }
+}
+
 /// Database model for income data query results
 #[derive(Debug, Serialize, QueryableByName)]
 #[serde(rename_all = "camelCase")]
@@ -13,16 +13,18 @@ use wealthfolio_core::activities::ActivityError;
 use wealthfolio_core::activities::{
     Activity, ActivityBulkIdentifierMapping, ActivityBulkMutationResult, ActivityDetails,
     ActivityRepositoryTrait, ActivitySearchResponse, ActivitySearchResponseMeta, ActivityUpdate,
-    ActivityUpsert, BulkUpsertResult, ImportMapping, IncomeData, NewActivity, Sort,
-    INCOME_ACTIVITY_TYPES, TRADING_ACTIVITY_TYPES,
+    ActivityUpsert, BulkUpsertResult, ImportMapping, ImportRunChange,
+    ImportRunChangeRepositoryTrait, ImportRunChangeStatus, ImportRunChangeType,
+    ImportRunRevertResult, IncomeData, NewActivity, Sort, INCOME_ACTIVITY_TYPES,
+    MERGED_DUPLICATES_META_KEY, PROBABLE_DUPLICATE_META_KEY, TRADING_ACTIVITY_TYPES,
 };
 use wealthfolio_core::limits::ContributionActivity;
 use wealthfolio_core::{Error, Result};
 
-use super::model::{ActivityDB, ActivityDetailsDB, ImportMappingDB};
+use super::model::{enum_to_db, ActivityDB, ActivityDetailsDB, ImportMappingDB, ImportRunChangeDB};
 use crate::db::{get_connection, WriteHandle};
 use crate::errors::StorageError;
-use crate::schema::{accounts, activities, activity_import_profiles, assets};
+use crate::schema::{accounts, activities, activity_import_profiles, assets, import_run_changes};
 use crate::utils::chunk_for_sqlite;
 use async_trait::async_trait;
 use diesel::dsl::{max, min};
@@ -48,6 +50,56 @@ impl ActivityRepository {
     pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
         Self { pool, writer }
     }
+
+    /// Inserts new activities, recording each insert against the import run if given.
+    async fn insert_activities(
+        &self,
+        activities_vec: Vec<NewActivity>,
+        import_run_id: Option<String>,
+    ) -> Result<usize> {
+        if activities_vec.is_empty() {
+            return Ok(0);
+        }
+        // Validate all activities first
+        for new_act in &activities_vec {
+            new_act.validate()?;
+        }
+        // Convert to ActivityDB and assign IDs
+        let activities_db_owned: Vec<ActivityDB> = activities_vec
+            .into_iter() // Consumes activities_vec
+            .map(|new_act| {
+                let mut db: ActivityDB = new_act.into();
+                db.id = Uuid::new_v4().to_string();
+                db.import_run_id = import_run_id.clone();
+                db
+            })
+            .collect();
+
+        self.writer
+            .exec_tx(move |tx| -> Result<usize> {
+                let num_inserted = diesel::insert_into(activities::table)
+                    .values(&activities_db_owned)
+                    .execute(tx.conn())
+                    .map_err(StorageError::from)?;
+                for activity_db in &activities_db_owned {
+                    tx.insert(activity_db)?;
+                }
+                if let Some(run_id) = &import_run_id {
+                    let changes: Vec<ImportRunChangeDB> = activities_db_owned
+                        .iter()
+                        .map(|row| {
+                            ImportRunChangeDB::new(run_id, ImportRunChangeType::Insert, None, row)
+                        })
+                        .collect();
+                    diesel::insert_into(import_run_changes::table)
+                        .values(&changes)
+                        .execute(tx.conn())
+                        .map_err(StorageError::from)?;
+                }
+                Ok(num_inserted)
+            })
+            .await
+    }
 }
 
 // Implement the trait for the repository
@@ -394,7 +446,7 @@ impl ActivityRepositoryTrait for ActivityRepository {
                 diesel::delete(activities::table.filter(activities::id.eq(&activity_id)))
                     .execute(tx.conn())
                     .map_err(StorageError::from)?;
-                tx.delete::<ActivityDB>(activity_id.clone());
+                tx.delete_model(&activity);
                 Ok(activity.into())
             })
             .await
@@ -419,7 +471,7 @@ impl ActivityRepositoryTrait for ActivityRepository {
                     diesel::delete(activities::table.filter(activities::id.eq(&delete_id)))
                         .execute(tx.conn())
                         .map_err(StorageError::from)?;
-                    tx.delete::<ActivityDB>(delete_id.clone());
+                    tx.delete_model(&activity_db);
                     outcome.deleted.push(Activity::from(activity_db));
                 }
 
@@ -635,34 +687,15 @@ impl ActivityRepositoryTrait for ActivityRepository {
     }
 
     async fn create_activities(&self, activities_vec: Vec<NewActivity>) -> Result<usize> {
-        if activities_vec.is_empty() {
-            return Ok(0);
-        }
-        // Validate all activities first
-        for new_act in &activities_vec {
-            new_act.validate()?;
-        }
-        // Convert to ActivityDB and assign IDs
-        let activities_db_owned: Vec<ActivityDB> = activities_vec
-            .into_iter() // Consumes activities_vec
-            .map(|new_act| {
-                let mut db: ActivityDB = new_act.into();
-                db.id = Uuid::new_v4().to_string();
-                db
-            })
-            .collect();
+        self.insert_activities(activities_vec, None).await
+    }
 
-        self.writer
-            .exec_tx(move |tx| -> Result<usize> {
-                let num_inserted = diesel::insert_into(activities::table)
-                    .values(&activities_db_owned)
-                    .execute(tx.conn())
-                    .map_err(StorageError::from)?;
-                for activity_db in &activities_db_owned {
-                    tx.insert(activity_db)?;
-                }
-                Ok(num_inserted)
-            })
+    async fn create_import_run_activities(
+        &self,
+        import_run_id: &str,
+        activities_vec: Vec<NewActivity>,
+    ) -> Result<usize> {
+        self.insert_activities(activities_vec, Some(import_run_id.to_string()))
             .await
     }
 
@@ -749,7 +782,7 @@ impl ActivityRepositoryTrait for ActivityRepository {
 
         // For income reporting, we need to handle different subtypes:
         // - Regular DIVIDEND/INTEREST: use the `amount` field directly
-        // - STAKING_REWARD/DRIP/DIVIDEND_IN_KIND subtypes: if amount is 0, calculate from:
+        // - STAKING_REWARD/AIRDROP/DRIP/DIVIDEND_IN_KIND subtypes: if amount is 0, calculate from:
         //   1. quantity * unit_price (if unit_price is available)
         //   2. quantity * market_price from quotes table (fallback)
         let query = "SELECT strftime('%Y-%m', a.activity_date) as date,
@@ -760,7 +793,7 @@ impl ActivityRepositoryTrait for ActivityRepository {
              COALESCE(ast.name, 'Cash') as symbol_name,
              a.currency,
              CASE
-                 WHEN a.subtype IN ('STAKING_REWARD', 'DRIP', 'DIVIDEND_IN_KIND')
+                 WHEN a.subtype IN ('STAKING_REWARD', 'AIRDROP', 'DRIP', 'DIVIDEND_IN_KIND')
                       AND (a.amount IS NULL OR CAST(a.amount AS REAL) = 0)
                  THEN CASE
                      WHEN a.unit_price IS NOT NULL AND CAST(a.unit_price AS REAL) > 0
@@ -1063,6 +1096,18 @@ impl ActivityRepositoryTrait for ActivityRepository {
                         || (idempotency_key.is_some()
                             && existing_by_idemp.contains_key(idempotency_key.as_ref().unwrap()));
 
+                    // Keep the overwritten row so the import run can be reviewed and rolled back
+                    let before = if will_update && activity_db.import_run_id.is_some() {
+                        activities::table
+                            .select(ActivityDB::as_select())
+                            .find(&activity_db.id)
+                            .first::<ActivityDB>(tx.conn())
+                            .optional()
+                            .map_err(StorageError::from)?
+                    } else {
+                        None
+                    };
+
                     match diesel::insert_into(activities::table)
                         .values(&activity_db)
                         .on_conflict(activities::id)
@@ -1088,7 +1133,7 @@ impl ActivityRepositoryTrait for ActivityRepository {
                             activities::needs_review.eq(excluded(activities::needs_review)),
                             activities::idempotency_key.eq(excluded(activities::idempotency_key)),
                             activities::import_run_id.eq(excluded(activities::import_run_id)),
-                            activities::updated_at.eq(now_update),
+                            activities::updated_at.eq(now_update.clone()),
                         ))
                         .execute(tx.conn())
                     {
@@ -1099,6 +1144,39 @@ impl ActivityRepositoryTrait for ActivityRepository {
                                 } else {
                                     tx.insert(&activity_db)?;
                                 }
+                                if let Some(run_id) = activity_db.import_run_id.as_deref() {
+                                    let change = match &before {
+                                        Some(before) => {
+                                            let mut after = activity_db.clone();
+                                            after.created_at = before.created_at.clone();
+                                            after.updated_at = now_update.clone();
+                                            // Re-synced rows that did not change are not worth reviewing
+                                            let mut unchanged = before.clone();
+                                            unchanged.import_run_id = after.import_run_id.clone();
+                                            unchanged.updated_at = after.updated_at.clone();
+                                            (unchanged != after).then(|| {
+                                                ImportRunChangeDB::new(
+                                                    run_id,
+                                                    ImportRunChangeType::Update,
+                                                    Some(before),
+                                                    &after,
+                                                )
+                                            })
+                                        }
+                                        None => Some(ImportRunChangeDB::new(
+                                            run_id,
+                                            ImportRunChangeType::Insert,
+                                            None,
+                                            &activity_db,
+                                        )),
+                                    };
+                                    if let Some(change) = change {
+                                        diesel::insert_into(import_run_changes::table)
+                                            .values(&change)
+                                            .execute(tx.conn())
+                                            .map_err(StorageError::from)?;
+                                    }
+                                }
                                 result.upserted += count;
                                 if will_update {
                                     result.updated += count;
@@ -1211,4 +1289,261 @@ impl ActivityRepositoryTrait for ActivityRepository {
             )
             .await
     }
+
+    async fn merge_activities(&self, keep_id: &str, duplicate_id: &str) -> Result<Activity> {
+        let keep_id = keep_id.to_string();
+        let duplicate_id = duplicate_id.to_string();
+        self.writer
+            .exec_tx(move |tx| -> Result<Activity> {
+                let mut kept = activities::table
+                    .select(ActivityDB::as_select())
+                    .find(&keep_id)
+                    .first::<ActivityDB>(tx.conn())
+                    .map_err(StorageError::from)?;
+                let duplicate = activities::table
+                    .select(ActivityDB::as_select())
+                    .find(&duplicate_id)
+                    .first::<ActivityDB>(tx.conn())
+                    .map_err(StorageError::from)?;
+
+                // Delete first: the idempotency key is unique and may move to the kept row
+                diesel::delete(activities::table.filter(activities::id.eq(&duplicate_id)))
+                    .execute(tx.conn())
+                    .map_err(StorageError::from)?;
+                tx.delete_model(&duplicate);
+
+                let mut metadata = kept
+                    .metadata
+                    .as_deref()
+                    .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+                    .filter(|m| m.is_object())
+                    .unwrap_or_else(|| serde_json::json!({}));
+                let mut metadata_changed = false;
+
+                // Keep the provider identity so later syncs update the kept row
+                // instead of re-creating the duplicate. A kept row with an identity
+                // of its own remembers the duplicate's, and syncs skip it.
+                if duplicate.source_record_id.is_some() || duplicate.idempotency_key.is_some() {
+                    if kept.source_record_id.is_none() {
+                        kept.source_system = duplicate.source_system;
+                        kept.source_record_id = duplicate.source_record_id;
+                        kept.source_group_id = duplicate.source_group_id;
+                        kept.idempotency_key = duplicate.idempotency_key;
+                    } else {
+                        let merged = metadata
+                            .as_object_mut()
+                            .map(|m| {
+                                m.entry(MERGED_DUPLICATES_META_KEY)
+                                    .or_insert_with(|| serde_json::json!([]))
+                            })
+                            .and_then(|v| v.as_array_mut());
+                        if let Some(merged) = merged {
+                            merged.push(serde_json::json!({
+                                "id": duplicate.id,
+                                "idempotency_key": duplicate.idempotency_key,
+                            }));
+                            metadata_changed = true;
+                        }
+                    }
+                }
+
+                if metadata
+                    .as_object_mut()
+                    .and_then(|m| m.remove(PROBABLE_DUPLICATE_META_KEY))
+                    .is_some()
+                {
+                    kept.needs_review = 0;
+                    metadata_changed = true;
+                }
+                if metadata_changed {
+                    kept.metadata = Some(metadata)
+                        .filter(|m| m.as_object().is_some_and(|o| !o.is_empty()))
+                        .map(|m| m.to_string());
+                }
+                kept.updated_at = Utc::now().to_rfc3339();
+
+                diesel::update(activities::table.find(&kept.id))
+                    .set(&kept)
+                    .execute(tx.conn())
+                    .map_err(StorageError::from)?;
+                if kept.metadata.is_none() {
+                    // The changeset skips None fields
+                    diesel::update(activities::table.find(&kept.id))
+                        .set(activities::metadata.eq(None::<String>))
+                        .execute(tx.conn())
+                        .map_err(StorageError::from)?;
+                }
+                tx.update(&kept)?;
+                Ok(kept.into())
+            })
+            .await
+    }
+
+    async fn bulk_edit_activities(&self, edited: Vec<Activity>) -> Result<Vec<Activity>> {
+        self.writer
+            .exec_tx(move |tx| -> Result<Vec<Activity>> {
+                let now = Utc::now().to_rfc3339();
+                let mut updated = Vec::with_capacity(edited.len());
+                for activity in edited {
+                    let mut row = activities::table
+                        .select(ActivityDB::as_select())
+                        .find(&activity.id)
+                        .first::<ActivityDB>(tx.conn())
+                        .map_err(StorageError::from)?;
+                    row.account_id = activity.account_id;
+                    row.activity_type = activity.activity_type;
+                    row.activity_type_override = activity.activity_type_override;
+                    row.subtype = activity.subtype;
+                    row.currency = activity.currency;
+                    row.metadata = activity.metadata.map(|m| m.to_string());
+                    row.is_user_modified = 1;
+                    row.updated_at = now.clone();
+
+                    diesel::update(activities::table.find(&row.id))
+                        .set(&row)
+                        .execute(tx.conn())
+                        .map_err(StorageError::from)?;
+                    // The changeset skips None fields, so cleared ones are set explicitly
+                    diesel::update(activities::table.find(&row.id))
+                        .set((
+                            activities::activity_type_override.eq(&row.activity_type_override),
+                            activities::subtype.eq(&row.subtype),
+                            activities::metadata.eq(&row.metadata),
+                        ))
+                        .execute(tx.conn())
+                        .map_err(StorageError::from)?;
+                    tx.update(&row)?;
+                    updated.push(row.into());
+                }
+                Ok(updated)
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl ImportRunChangeRepositoryTrait for ActivityRepository {
+    fn get_changes(&self, import_run_id: &str) -> Result<Vec<ImportRunChange>> {
+        let mut conn = get_connection(&self.pool)?;
+        let changes = import_run_changes::table
+            .filter(import_run_changes::import_run_id.eq(import_run_id))
+            .order(import_run_changes::created_at.asc())
+            .select(ImportRunChangeDB::as_select())
+            .load::<ImportRunChangeDB>(&mut conn)
+            .map_err(StorageError::from)?;
+        Ok(changes.into_iter().map(ImportRunChange::from).collect())
+    }
+
+    async fn approve_changes(&self, import_run_id: &str, change_ids: Vec<String>) -> Result<usize> {
+        let import_run_id = import_run_id.to_string();
+        self.writer
+            .exec_tx(move |tx| -> Result<usize> {
+                let activity_ids: Vec<String> = import_run_changes::table
+                    .filter(import_run_changes::import_run_id.eq(&import_run_id))
+                    .filter(import_run_changes::id.eq_any(&change_ids))
+                    .select(import_run_changes::activity_id)
+                    .load::<String>(tx.conn())
+                    .map_err(StorageError::from)?;
+
+                let approved = diesel::update(
+                    import_run_changes::table
+                        .filter(import_run_changes::import_run_id.eq(&import_run_id))
+                        .filter(import_run_changes::id.eq_any(&change_ids)),
+                )
+                .set((
+                    import_run_changes::status.eq(enum_to_db(&ImportRunChangeStatus::Approved)),
+                    import_run_changes::reviewed_at.eq(Some(Utc::now().to_rfc3339())),
+                ))
+                .execute(tx.conn())
+                .map_err(StorageError::from)?;
+
+                // Reviewed rows no longer need attention
+                let flagged: Vec<ActivityDB> = activities::table
+                    .select(ActivityDB::as_select())
+                    .filter(activities::id.eq_any(&activity_ids))
+                    .filter(activities::import_run_id.eq(&import_run_id))
+                    .filter(activities::needs_review.ne(0))
+                    .load::<ActivityDB>(tx.conn())
+                    .map_err(StorageError::from)?;
+                for mut activity_db in flagged {
+                    activity_db.needs_review = 0;
+                    activity_db.updated_at = Utc::now().to_rfc3339();
+                    diesel::update(activities::table.find(&activity_db.id))
+                        .set(&activity_db)
+                        .execute(tx.conn())
+                        .map_err(StorageError::from)?;
+                    tx.update(&activity_db)?;
+                }
+
+                Ok(approved)
+            })
+            .await
+    }
+
+    async fn revert_changes(
+        &self,
+        import_run_id: &str,
+        change_ids: Vec<String>,
+        status: ImportRunChangeStatus,
+    ) -> Result<ImportRunRevertResult> {
+        let import_run_id = import_run_id.to_string();
+        self.writer
+            .exec_tx(move |tx| -> Result<ImportRunRevertResult> {
+                let reviewed_at = Utc::now().to_rfc3339();
+                let mut result = ImportRunRevertResult::default();
+
+                // Newest first, so an activity changed twice ends up in its original state
+                let changes: Vec<ImportRunChangeDB> = import_run_changes::table
+                    .filter(import_run_changes::import_run_id.eq(&import_run_id))
+                    .filter(import_run_changes::id.eq_any(&change_ids))
+                    .order(import_run_changes::created_at.desc())
+                    .select(ImportRunChangeDB::as_select())
+                    .load::<ImportRunChangeDB>(tx.conn())
+                    .map_err(StorageError::from)?;
+
+                for change in changes {
+                    let current = activities::table
+                        .select(ActivityDB::as_select())
+                        .find(&change.activity_id)
+                        .first::<ActivityDB>(tx.conn())
+                        .optional()
+                        .map_err(StorageError::from)?;
+                    // Leave activities the user edited or a later run overwrote
+                    let untouched = current.as_ref().is_some_and(|a| {
+                        a.is_user_modified == 0
+                            && a.import_run_id.as_deref() == Some(import_run_id.as_str())
+                    });
+
+                    match (change.change_type(), change.before_row()) {
+                        (ImportRunChangeType::Insert, _) if untouched => {
+                            diesel::delete(activities::table.find(&change.activity_id))
+                                .execute(tx.conn())
+                                .map_err(StorageError::from)?;
+                            tx.delete::<ActivityDB>(change.activity_id.clone());
+                            result.removed += 1;
+                        }
+                        (ImportRunChangeType::Update, Some(before)) if untouched => {
+                            diesel::replace_into(activities::table)
+                                .values(&before)
+                                .execute(tx.conn())
+                                .map_err(StorageError::from)?;
+                            tx.update(&before)?;
+                            result.restored += 1;
+                        }
+                        _ => result.skipped += 1,
+                    }
+
+                    diesel::update(import_run_changes::table.find(&change.id))
+                        .set((
+                            import_run_changes::status.eq(enum_to_db(&status)),
+                            import_run_changes::reviewed_at.eq(Some(reviewed_at.clone())),
+                        ))
+                        .execute(tx.conn())
+                        .map_err(StorageError::from)?;
+                }
+
+                Ok(result)
+            })
+            .await
+    }
 }
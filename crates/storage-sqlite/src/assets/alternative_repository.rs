@@ -12,13 +12,14 @@ use diesel::r2d2::{self, Pool};
 use diesel::sqlite::SqliteConnection;
 use std::sync::Arc;
 
-use wealthfolio_core::assets::AlternativeAssetRepositoryTrait;
+use wealthfolio_core::assets::{AlternativeAssetRepositoryTrait, PropertyCashFlow};
 use wealthfolio_core::errors::DatabaseError;
 use wealthfolio_core::{Error, Result};
 
+use super::model::PropertyCashFlowDB;
 use crate::db::{get_connection, WriteHandle};
 use crate::errors::StorageError;
-use crate::schema::{assets, quotes};
+use crate::schema::{assets, property_cash_flows, quotes};
 
 /// Repository for managing alternative asset data in the database.
 ///
@@ -46,7 +47,8 @@ impl AlternativeAssetRepositoryTrait for AlternativeAssetRepository {
     /// This operation performs the following steps in a transaction:
     /// 1. Unlinks any liabilities that reference this asset (removes linked_asset_id from metadata)
     /// 2. Deletes all quotes for this asset WHERE data_source = 'MANUAL'
-    /// 3. Deletes the asset record
+    /// 3. Deletes rental cash flows recorded against the asset
+    /// 4. Deletes the asset record
     ///
     /// Note: No account or activity deletion needed - alternative assets don't create them.
     async fn delete_alternative_asset(&self, asset_id: &str) -> Result<()> {
@@ -98,7 +100,15 @@ impl AlternativeAssetRepositoryTrait for AlternativeAssetRepository {
                 .execute(tx.conn())
                 .map_err(StorageError::from)?;
 
-                // Step 3: Delete the asset record
+                // Step 3: Delete rental cash flows recorded against this asset
+                diesel::delete(
+                    property_cash_flows::table
+                        .filter(property_cash_flows::asset_id.eq(&asset_id_owned)),
+                )
+                .execute(tx.conn())
+                .map_err(StorageError::from)?;
+
+                // Step 4: Delete the asset record
                 let assets_deleted =
                     diesel::delete(assets::table.filter(assets::id.eq(&asset_id_owned)))
                         .execute(tx.conn())
@@ -151,6 +161,63 @@ impl AlternativeAssetRepositoryTrait for AlternativeAssetRepository {
         Ok(liability_ids)
     }
 
+    /// Lists the cash-flow entries of a property, ordered by date.
+    fn list_property_cash_flows(&self, asset_id: &str) -> Result<Vec<PropertyCashFlow>> {
+        let mut conn = get_connection(&self.pool)?;
+
+        let rows = property_cash_flows::table
+            .filter(property_cash_flows::asset_id.eq(asset_id))
+            .order((
+                property_cash_flows::date.asc(),
+                property_cash_flows::created_at.asc(),
+            ))
+            .select(PropertyCashFlowDB::as_select())
+            .load::<PropertyCashFlowDB>(&mut conn)
+            .map_err(StorageError::from)?;
+
+        Ok(rows.into_iter().map(PropertyCashFlow::from).collect())
+    }
+
+    /// Inserts a property cash-flow entry.
+    async fn insert_property_cash_flow(&self, flow: PropertyCashFlow) -> Result<PropertyCashFlow> {
+        let row = PropertyCashFlowDB::from(flow.clone());
+
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::insert_into(property_cash_flows::table)
+                    .values(&row)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                Ok(())
+            })
+            .await?;
+
+        Ok(flow)
+    }
+
+    /// Deletes a property cash-flow entry.
+    async fn delete_property_cash_flow(&self, cash_flow_id: &str) -> Result<()> {
+        let id_owned = cash_flow_id.to_string();
+
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                let deleted = diesel::delete(
+                    property_cash_flows::table.filter(property_cash_flows::id.eq(&id_owned)),
+                )
+                .execute(conn)
+                .map_err(StorageError::from)?;
+
+                if deleted == 0 {
+                    return Err(Error::Database(DatabaseError::NotFound(format!(
+                        "Property cash flow not found: {}",
+                        id_owned
+                    ))));
+                }
+                Ok(())
+            })
+            .await
+    }
+
     /// Updates an asset's details (name, display_code, metadata, and/or notes).
     async fn update_asset_details(
         &self,
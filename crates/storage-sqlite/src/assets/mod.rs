@@ -5,5 +5,5 @@ mod model;
 mod repository;
 
 pub use alternative_repository::AlternativeAssetRepository;
-pub use model::{AssetDB, InsertableAssetDB};
+pub use model::{AssetDB, InsertableAssetDB, PropertyCashFlowDB};
 pub use repository::AssetRepository;
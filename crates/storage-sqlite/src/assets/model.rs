@@ -8,7 +8,9 @@ use diesel::prelude::*;
 use log::error;
 use serde::{Deserialize, Serialize};
 
-use wealthfolio_core::assets::{Asset, AssetKind, InstrumentType, NewAsset, QuoteMode};
+use wealthfolio_core::assets::{
+    Asset, AssetKind, InstrumentType, NewAsset, PropertyCashFlow, PropertyCashFlowKind, QuoteMode,
+};
 
 /// Helper to parse datetime string to NaiveDateTime.
 ///
@@ -178,3 +180,58 @@ impl From<NewAsset> for InsertableAssetDB {
         }
     }
 }
+
+/// Database model for property cash flows.
+///
+/// Amounts are stored as decimal text, dates as `YYYY-MM-DD`.
+#[derive(Queryable, Selectable, Insertable, Debug, Clone, PartialEq)]
+#[diesel(table_name = crate::schema::property_cash_flows)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PropertyCashFlowDB {
+    pub id: String,
+    pub asset_id: String,
+    pub kind: String,
+    pub category: Option<String>,
+    pub amount: String,
+    pub currency: String,
+    pub date: String,
+    pub notes: Option<String>,
+    pub created_at: String,
+}
+
+impl From<PropertyCashFlowDB> for PropertyCashFlow {
+    fn from(db: PropertyCashFlowDB) -> Self {
+        let kind = db.kind.parse().unwrap_or_else(|e| {
+            error!("{} (cash flow {})", e, db.id);
+            PropertyCashFlowKind::Expense
+        });
+        Self {
+            kind,
+            category: db.category,
+            amount: db.amount.parse().unwrap_or_default(),
+            currency: db.currency,
+            date: chrono::NaiveDate::parse_from_str(&db.date, "%Y-%m-%d")
+                .unwrap_or_else(|_| text_to_datetime(&db.date).date()),
+            notes: db.notes,
+            created_at: text_to_datetime(&db.created_at),
+            id: db.id,
+            asset_id: db.asset_id,
+        }
+    }
+}
+
+impl From<PropertyCashFlow> for PropertyCashFlowDB {
+    fn from(domain: PropertyCashFlow) -> Self {
+        Self {
+            id: domain.id,
+            asset_id: domain.asset_id,
+            kind: domain.kind.as_str().to_string(),
+            category: domain.category,
+            amount: domain.amount.to_string(),
+            currency: domain.currency,
+            date: domain.date.format("%Y-%m-%d").to_string(),
+            notes: domain.notes,
+            created_at: domain.created_at.and_utc().to_rfc3339(),
+        }
+    }
+}
@@ -11,7 +11,7 @@ use wealthfolio_core::{Error, Result};
 use super::model::{AssetDB, InsertableAssetDB};
 use crate::db::{get_connection, WriteHandle};
 use crate::errors::StorageError;
-use crate::schema::{activities, assets, quotes};
+use crate::schema::{activities, assets, quote_provenance, quotes};
 use crate::utils::chunk_for_sqlite;
 
 /// Repository for managing asset data in the database
@@ -335,6 +335,11 @@ impl AssetRepositoryTrait for AssetRepository {
                 diesel::delete(quotes::table.filter(quotes::asset_id.eq(&asset_id_owned)))
                     .execute(tx.conn())
                     .map_err(StorageError::from)?;
+                diesel::delete(
+                    quote_provenance::table.filter(quote_provenance::asset_id.eq(&asset_id_owned)),
+                )
+                .execute(tx.conn())
+                .map_err(StorageError::from)?;
 
                 // Delete the asset
                 diesel::delete(assets::table.filter(assets::id.eq(&asset_id_owned)))
@@ -0,0 +1,9 @@
+//! SQLite storage implementation for the audit log.
+
+mod model;
+mod recorder;
+mod repository;
+
+pub use model::AuditLogDB;
+pub(crate) use recorder::{record_audit_changes, AuditChange};
+pub use repository::AuditLogRepository;
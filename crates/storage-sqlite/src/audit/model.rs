@@ -0,0 +1,85 @@
+//! Database models for the audit log.
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use wealthfolio_core::audit::{AuditLogEntry, AuditOperation, AuditSource};
+
+pub(crate) fn enum_to_db<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value)
+        .unwrap_or_default()
+        .trim_matches('"')
+        .to_string()
+}
+
+fn enum_from_db<T: serde::de::DeserializeOwned>(value: &str) -> Option<T> {
+    serde_json::from_str(&format!("\"{}\"", value)).ok()
+}
+
+/// Database model for one audit log entry
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::audit_log)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct AuditLogDB {
+    pub id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub operation: String,
+    pub source: String,
+    pub device_id: Option<String>,
+    pub import_run_id: Option<String>,
+    pub restored_from: Option<String>,
+    /// JSON array of the changed column names
+    pub changed_fields: Option<String>,
+    /// Version recorded by the previous entry of the entity
+    pub before_json: Option<String>,
+    /// Stored row once the change was made, NULL for deletes
+    pub after_json: Option<String>,
+    pub created_at: String,
+}
+
+impl AuditLogDB {
+    /// Stored version this entry leaves in place, `None` once deleted.
+    pub fn version(&self) -> Option<Value> {
+        if self.operation == enum_to_db(&AuditOperation::Delete) {
+            return None;
+        }
+        self.after_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+    }
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(json: Option<&str>) -> Option<T> {
+    json.and_then(|j| serde_json::from_str(j).ok())
+}
+
+impl TryFrom<AuditLogDB> for AuditLogEntry {
+    type Error = wealthfolio_core::Error;
+
+    fn try_from(db: AuditLogDB) -> Result<Self, Self::Error> {
+        Ok(Self {
+            entity_type: enum_from_db(&db.entity_type).ok_or_else(|| {
+                wealthfolio_core::Error::Unexpected(format!(
+                    "Unknown audited entity type '{}'",
+                    db.entity_type
+                ))
+            })?,
+            operation: enum_from_db(&db.operation).unwrap_or(AuditOperation::Update),
+            source: enum_from_db(&db.source).unwrap_or(AuditSource::User),
+            changed_fields: parse_json(db.changed_fields.as_deref()).unwrap_or_default(),
+            before: parse_json(db.before_json.as_deref()),
+            after: parse_json(db.after_json.as_deref()),
+            created_at: DateTime::parse_from_rfc3339(&db.created_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            id: db.id,
+            entity_id: db.entity_id,
+            device_id: db.device_id,
+            import_run_id: db.import_run_id,
+            restored_from: db.restored_from,
+        })
+    }
+}
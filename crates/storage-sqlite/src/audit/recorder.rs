@@ -0,0 +1,129 @@
+//! Records captured writes in the audit log, inside the writer's transaction.
+
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use serde_json::Value;
+use uuid::Uuid;
+
+use wealthfolio_core::audit::{changed_fields, AuditContext, AuditEntityType, AuditOperation};
+use wealthfolio_core::sync::SyncOperation;
+use wealthfolio_core::Result;
+
+use super::model::{enum_to_db, AuditLogDB};
+use crate::errors::StorageError;
+use crate::schema::audit_log;
+use crate::sync::SyncOutboxModel;
+
+/// A write to an audited entity, captured before it is recorded.
+pub(crate) struct AuditChange {
+    entity_type: AuditEntityType,
+    entity_id: String,
+    op: SyncOperation,
+    /// Row as written, or the deleted row when the caller still had it
+    row: Option<Value>,
+}
+
+impl AuditChange {
+    pub(crate) fn new(
+        entity_type: AuditEntityType,
+        entity_id: impl Into<String>,
+        op: SyncOperation,
+        row: Option<Value>,
+    ) -> Self {
+        Self {
+            entity_type,
+            entity_id: entity_id.into(),
+            op,
+            row,
+        }
+    }
+
+    /// Captures a model write, if the model's entity is audited.
+    pub(crate) fn for_model<T: SyncOutboxModel>(
+        model: &T,
+        op: SyncOperation,
+    ) -> Result<Option<Self>> {
+        let Some(entity_type) = AuditEntityType::from_sync_entity(T::ENTITY) else {
+            return Ok(None);
+        };
+        Ok(Some(Self::new(
+            entity_type,
+            model.sync_entity_id(),
+            op,
+            Some(serde_json::to_value(model)?),
+        )))
+    }
+
+    /// Captures a delete of which only the id is known.
+    pub(crate) fn delete_for_model<T: SyncOutboxModel>(entity_id: &str) -> Option<Self> {
+        AuditEntityType::from_sync_entity(T::ENTITY)
+            .map(|entity_type| Self::new(entity_type, entity_id, SyncOperation::Delete, None))
+    }
+}
+
+fn latest_entry(
+    conn: &mut SqliteConnection,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<Option<AuditLogDB>> {
+    Ok(audit_log::table
+        .filter(audit_log::entity_type.eq(entity_type))
+        .filter(audit_log::entity_id.eq(entity_id))
+        .order((audit_log::created_at.desc(), audit_log::id.desc()))
+        .select(AuditLogDB::as_select())
+        .first::<AuditLogDB>(conn)
+        .optional()
+        .map_err(StorageError::from)?)
+}
+
+/// Appends an entry per change, comparing each with the entity's previous entry.
+///
+/// Writes that leave every field but `updated_at` as it was are not recorded.
+pub(crate) fn record_audit_changes(
+    conn: &mut SqliteConnection,
+    context: &AuditContext,
+    device_id: Option<String>,
+    changes: Vec<AuditChange>,
+) -> Result<()> {
+    for change in changes {
+        let entity_type = enum_to_db(&change.entity_type);
+        let previous =
+            latest_entry(conn, &entity_type, &change.entity_id)?.and_then(|e| e.version());
+
+        let (operation, before, after) = match change.op {
+            // Fall back to the deleted row for entities changed before the log existed
+            SyncOperation::Delete => (AuditOperation::Delete, previous.or(change.row), None),
+            _ if context.restored_from.is_some() => (AuditOperation::Restore, previous, change.row),
+            SyncOperation::Create => (AuditOperation::Create, previous, change.row),
+            SyncOperation::Update => (AuditOperation::Update, previous, change.row),
+        };
+        let changed = match (&before, &after) {
+            (Some(before), Some(after)) => changed_fields(before, after),
+            _ => Vec::new(),
+        };
+        if operation == AuditOperation::Update && before.is_some() && changed.is_empty() {
+            continue;
+        }
+
+        let row = AuditLogDB {
+            id: Uuid::now_v7().to_string(),
+            entity_type,
+            entity_id: change.entity_id,
+            operation: enum_to_db(&operation),
+            source: enum_to_db(&context.source),
+            device_id: device_id.clone(),
+            import_run_id: context.import_run_id.clone(),
+            restored_from: context.restored_from.clone(),
+            changed_fields: Some(serde_json::to_string(&changed)?),
+            before_json: before.map(|v| v.to_string()),
+            after_json: after.map(|v| v.to_string()),
+            created_at: Utc::now().to_rfc3339(),
+        };
+        diesel::insert_into(audit_log::table)
+            .values(&row)
+            .execute(conn)
+            .map_err(StorageError::from)?;
+    }
+    Ok(())
+}
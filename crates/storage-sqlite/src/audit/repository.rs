@@ -0,0 +1,265 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{self, Pool};
+use diesel::sqlite::SqliteConnection;
+use serde_json::Value;
+use std::sync::Arc;
+
+use wealthfolio_core::audit::{AuditEntityType, AuditLogEntry, AuditLogRepositoryTrait};
+use wealthfolio_core::errors::{DatabaseError, Error, Result};
+
+use super::model::{enum_to_db, AuditLogDB};
+use crate::accounts::AccountDB;
+use crate::activities::ActivityDB;
+use crate::assets::AssetDB;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::StorageError;
+use crate::schema::{accounts, activities, assets, audit_log};
+use crate::sync::upsert_entity_payload;
+
+/// Repository for reading the audit log and restoring recorded versions
+pub struct AuditLogRepository {
+    pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl AuditLogRepository {
+    pub fn new(
+        pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+        writer: WriteHandle,
+    ) -> Self {
+        Self { pool, writer }
+    }
+}
+
+fn to_entries(rows: Vec<AuditLogDB>) -> Result<Vec<AuditLogEntry>> {
+    rows.into_iter().map(AuditLogEntry::try_from).collect()
+}
+
+#[async_trait]
+impl AuditLogRepositoryTrait for AuditLogRepository {
+    fn get_entity_history(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: &str,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = audit_log::table
+            .filter(audit_log::entity_type.eq(enum_to_db(&entity_type)))
+            .filter(audit_log::entity_id.eq(entity_id))
+            .order((audit_log::created_at.desc(), audit_log::id.desc()))
+            .select(AuditLogDB::as_select())
+            .load::<AuditLogDB>(&mut conn)
+            .map_err(StorageError::from)?;
+        to_entries(rows)
+    }
+
+    fn get_recent_entries(
+        &self,
+        entity_type: Option<AuditEntityType>,
+        limit: i64,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = audit_log::table.into_boxed();
+        if let Some(entity_type) = entity_type {
+            query = query.filter(audit_log::entity_type.eq(enum_to_db(&entity_type)));
+        }
+        let rows = query
+            .order((audit_log::created_at.desc(), audit_log::id.desc()))
+            .limit(limit)
+            .select(AuditLogDB::as_select())
+            .load::<AuditLogDB>(&mut conn)
+            .map_err(StorageError::from)?;
+        to_entries(rows)
+    }
+
+    fn get_entry(&self, entry_id: &str) -> Result<Option<AuditLogEntry>> {
+        let mut conn = get_connection(&self.pool)?;
+        audit_log::table
+            .find(entry_id)
+            .select(AuditLogDB::as_select())
+            .first::<AuditLogDB>(&mut conn)
+            .optional()
+            .map_err(StorageError::from)?
+            .map(AuditLogEntry::try_from)
+            .transpose()
+    }
+
+    async fn restore_version(
+        &self,
+        entity_type: AuditEntityType,
+        entity_id: &str,
+        mut version: Value,
+    ) -> Result<()> {
+        let Some(fields) = version.as_object_mut() else {
+            return Err(Error::Database(DatabaseError::Internal(
+                "Audited version must be a JSON object".to_string(),
+            )));
+        };
+        if fields.contains_key("updated_at") {
+            fields.insert(
+                "updated_at".to_string(),
+                Value::String(Utc::now().to_rfc3339()),
+            );
+        }
+        let entity_id = entity_id.to_string();
+
+        self.writer
+            .exec_tx(move |tx| -> Result<()> {
+                upsert_entity_payload(tx.conn(), &entity_type.sync_entity(), &entity_id, &version)?;
+
+                // Re-read the typed row so the restore is synced and audited
+                // like any other write
+                match entity_type {
+                    AuditEntityType::Activity => {
+                        let row = activities::table
+                            .find(&entity_id)
+                            .select(ActivityDB::as_select())
+                            .first::<ActivityDB>(tx.conn())
+                            .map_err(StorageError::from)?;
+                        tx.update(&row)
+                    }
+                    AuditEntityType::Account => {
+                        let row = accounts::table
+                            .find(&entity_id)
+                            .select(AccountDB::as_select())
+                            .first::<AccountDB>(tx.conn())
+                            .map_err(StorageError::from)?;
+                        tx.update(&row)
+                    }
+                    AuditEntityType::Asset => {
+                        let row = assets::table
+                            .find(&entity_id)
+                            .select(AssetDB::as_select())
+                            .first::<AssetDB>(tx.conn())
+                            .map_err(StorageError::from)?;
+                        tx.update(&row)
+                    }
+                }
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use wealthfolio_core::accounts::{AccountRepositoryTrait, AccountUpdate, NewAccount};
+    use wealthfolio_core::audit::{with_audit_context, AuditContext, AuditOperation, AuditSource};
+
+    use crate::accounts::AccountRepository;
+    use crate::db::{create_pool, init, run_migrations, write_actor::spawn_writer};
+
+    fn setup_db() -> (
+        Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+        WriteHandle,
+    ) {
+        let app_data = tempdir()
+            .expect("tempdir")
+            .keep()
+            .to_string_lossy()
+            .to_string();
+        let db_path = init(&app_data).expect("init db");
+        run_migrations(&db_path).expect("migrate db");
+        let pool = create_pool(&db_path).expect("create pool");
+        let writer = spawn_writer(pool.as_ref().clone());
+        (pool, writer)
+    }
+
+    fn update(id: &str, name: &str) -> AccountUpdate {
+        AccountUpdate {
+            id: Some(id.to_string()),
+            name: name.to_string(),
+            account_type: "SECURITIES".to_string(),
+            group: None,
+            is_default: false,
+            is_active: true,
+            platform_id: None,
+            account_number: None,
+            meta: None,
+            provider: None,
+            provider_account_id: None,
+            is_archived: None,
+            tracking_mode: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_account_changes_are_recorded_and_restorable() {
+        let (pool, writer) = setup_db();
+        let accounts_repo = AccountRepository::new(pool.clone(), writer.clone());
+        let audit_repo = AuditLogRepository::new(pool, writer);
+
+        let account = with_audit_context(
+            AuditContext::import("run_1"),
+            accounts_repo.create(NewAccount {
+                id: None,
+                name: "Brokerage".to_string(),
+                account_type: "SECURITIES".to_string(),
+                group: None,
+                currency: "USD".to_string(),
+                is_default: false,
+                is_active: true,
+                platform_id: None,
+                account_number: None,
+                meta: None,
+                provider: None,
+                provider_account_id: None,
+                is_archived: false,
+                tracking_mode: Default::default(),
+            }),
+        )
+        .await
+        .unwrap();
+        accounts_repo
+            .update(update(&account.id, "Renamed"))
+            .await
+            .unwrap();
+        // Writing the same values again is not a change
+        accounts_repo
+            .update(update(&account.id, "Renamed"))
+            .await
+            .unwrap();
+        accounts_repo.delete(&account.id).await.unwrap();
+
+        let history = audit_repo
+            .get_entity_history(AuditEntityType::Account, &account.id)
+            .unwrap();
+        let operations: Vec<AuditOperation> = history.iter().map(|e| e.operation).collect();
+        assert_eq!(
+            operations,
+            vec![
+                AuditOperation::Delete,
+                AuditOperation::Update,
+                AuditOperation::Create
+            ]
+        );
+        let created = &history[2];
+        assert_eq!(created.source, AuditSource::Import);
+        assert_eq!(created.import_run_id.as_deref(), Some("run_1"));
+        let renamed = &history[1];
+        assert_eq!(renamed.source, AuditSource::User);
+        assert_eq!(renamed.changed_fields, vec!["name".to_string()]);
+        assert_eq!(history[0].before, renamed.after);
+
+        let version = history[0].before.clone().unwrap();
+        with_audit_context(
+            AuditContext::restore(history[0].id.clone()),
+            audit_repo.restore_version(AuditEntityType::Account, &account.id, version),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            accounts_repo.get_by_id(&account.id).unwrap().name,
+            "Renamed"
+        );
+        let restored = &audit_repo.get_recent_entries(None, 1).unwrap()[0];
+        assert_eq!(restored.operation, AuditOperation::Restore);
+        assert_eq!(
+            restored.restored_from.as_deref(),
+            Some(history[0].id.as_str())
+        );
+    }
+}
@@ -0,0 +1,932 @@
+//! Portable single-file backup archives.
+//!
+//! An archive is a JSON document holding a manifest and a SQLite image of the
+//! portfolio tables, optionally encrypted with a passphrase. Unlike the `.db`
+//! backups it leaves out install-specific and derived state (device sync,
+//! calculated snapshots, AI chats, audit history), so it can move a portfolio
+//! between a desktop and a server install, and it can be merged into an
+//! existing database instead of replacing it.
+//!
+//! Restored rows are queued to the sync outbox and recorded in the audit log
+//! like any other write.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use diesel::connection::SimpleConnection;
+use diesel::migration::MigrationSource;
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::Text;
+use diesel::sqlite::Sqlite;
+use log::{info, warn};
+use rusqlite::Connection as RusqliteConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use wealthfolio_core::audit::{AuditContext, AuditSource};
+use wealthfolio_core::errors::{DatabaseError, Error, Result, ValidationError};
+use wealthfolio_device_sync::crypto;
+
+use super::write_actor::WriteProjection;
+use super::{backup_database_to_file, get_db_path, run_migrations, MIGRATIONS};
+use crate::accounts::AccountDB;
+use crate::activities::{ActivityDB, ImportMappingDB};
+use crate::assets::AssetDB;
+use crate::errors::StorageError;
+use crate::goals::{GoalDB, GoalsAllocationDB};
+use crate::limits::ContributionLimitDB;
+use crate::market_data::QuoteDB;
+use crate::portfolio::snapshot::AccountStateSnapshotDB;
+use crate::schema::{
+    accounts, activities, activity_import_profiles, asset_taxonomy_assignments, assets,
+    contribution_limits, goals, goals_allocation, holdings_snapshots, platforms, quotes,
+};
+use crate::sync::platform::PlatformDB;
+use crate::sync::SyncOutboxModel;
+use crate::taxonomies::AssetTaxonomyAssignmentDB;
+use crate::utils::chunk_for_sqlite;
+
+pub const ARCHIVE_FORMAT: &str = "wealthfolio-archive";
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+const ENCRYPTION_ALGORITHM: &str = "xchacha20poly1305";
+const KEY_DERIVATION: &str = "argon2id";
+
+/// Tables carried by an archive, grouped by section. Parents come before
+/// children so a restore can insert them in order.
+const ARCHIVE_SECTIONS: &[(&str, &[&str])] = &[
+    ("settings", &["app_settings", "market_data_providers"]),
+    ("accounts", &["platforms", "accounts"]),
+    (
+        "assets",
+        &["assets", "equity_grants", "property_cash_flows"],
+    ),
+    ("quotes", &["quotes", "quote_provenance"]),
+    (
+        "taxonomies",
+        &[
+            "taxonomies",
+            "taxonomy_categories",
+            "asset_taxonomy_assignments",
+        ],
+    ),
+    (
+        "activities",
+        &[
+            "activity_import_profiles",
+            "import_runs",
+            "activities",
+            "import_run_changes",
+        ],
+    ),
+    ("snapshots", &["holdings_snapshots"]),
+    ("goals", &["goals", "goals_allocation"]),
+    ("limits", &["contribution_limits"]),
+    (
+        "budget",
+        &["budget_categories", "budget_transactions", "budget_limits"],
+    ),
+];
+
+/// Rows that identify the install rather than the portfolio. They are never
+/// archived and are kept by a restore.
+fn install_rows(table: &str) -> Option<&'static str> {
+    match table {
+        "app_settings" => Some("setting_key IN ('instance_id', 'sync_enabled')"),
+        _ => None,
+    }
+}
+
+/// Rows of an archived table the archive carries, when not all of them.
+/// Calculated snapshots are rebuilt from activities after a restore, while the
+/// ones entered, imported or synced for holdings-mode accounts are the only
+/// record of those holdings.
+fn archived_rows(table: &str) -> Option<&'static str> {
+    match table {
+        "holdings_snapshots" => Some(
+            "source IN ('MANUAL_ENTRY', 'BROKER_IMPORTED', 'CSV_IMPORT') \
+             AND account_id IN (SELECT id FROM accounts WHERE tracking_mode = 'HOLDINGS')",
+        ),
+        _ => None,
+    }
+}
+
+/// Key column of the tables whose restored rows go to the sync outbox and
+/// the audit log, with a filter for the rows that can be synced.
+fn synced_rows(table: &str) -> Option<(&'static str, Option<&'static str>)> {
+    match table {
+        "platforms"
+        | "accounts"
+        | "assets"
+        | "asset_taxonomy_assignments"
+        | "activities"
+        | "holdings_snapshots"
+        | "goals"
+        | "goals_allocation"
+        | "contribution_limits" => Some(("id", None)),
+        "activity_import_profiles" => Some(("account_id", None)),
+        // Provider quotes are neither synced nor audited
+        "quotes" => Some(("id", Some("UPPER(source) = 'MANUAL'"))),
+        _ => None,
+    }
+}
+
+fn archive_tables() -> impl DoubleEndedIterator<Item = &'static str> {
+    ARCHIVE_SECTIONS
+        .iter()
+        .flat_map(|(_, tables)| tables.iter().copied())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveManifest {
+    pub format: String,
+    pub format_version: u32,
+    pub app_version: String,
+    /// Latest migration applied to the archived database
+    pub schema_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Rows archived per section
+    pub sections: BTreeMap<String, i64>,
+    /// Checksum of the unencrypted database image
+    pub checksum: String,
+    pub encryption: Option<ArchiveEncryption>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveEncryption {
+    pub algorithm: String,
+    pub key_derivation: String,
+    /// Base64 salt the passphrase key was derived with
+    pub salt: String,
+}
+
+/// Contents of an archive file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioArchive {
+    pub manifest: ArchiveManifest,
+    /// Base64 SQLite image, encrypted when the manifest says so
+    pub payload: String,
+}
+
+impl PortfolioArchive {
+    pub fn filename(&self) -> String {
+        format!(
+            "wealthfolio_archive_{}.json",
+            self.manifest.created_at.format("%Y%m%d_%H%M%S")
+        )
+    }
+}
+
+/// An archive ready to be saved, with its suggested file name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveExport {
+    pub filename: String,
+    pub archive: PortfolioArchive,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveRestoreMode {
+    /// Restore into a database without accounts or activities, replacing its
+    /// defaults (settings, taxonomies, providers) with the archived ones
+    #[default]
+    Empty,
+    /// Add archived rows to an existing database. Rows whose id already
+    /// exists are kept as they are.
+    Merge,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveSectionRestore {
+    pub archived: i64,
+    pub restored: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveRestoreSummary {
+    pub mode: ArchiveRestoreMode,
+    pub manifest: ArchiveManifest,
+    pub sections: BTreeMap<String, ArchiveSectionRestore>,
+}
+
+fn archive_failed(e: impl ToString) -> Error {
+    Error::Database(DatabaseError::BackupFailed(e.to_string()))
+}
+
+fn invalid_archive(message: impl Into<String>) -> Error {
+    Error::Validation(ValidationError::InvalidInput(message.into()))
+}
+
+/// Copy of an archive image on disk, deleted when dropped.
+struct ScratchDatabase {
+    path: String,
+}
+
+impl ScratchDatabase {
+    fn new(app_data_dir: &str) -> Result<Self> {
+        let dir = Path::new(app_data_dir).join("backups");
+        fs::create_dir_all(&dir).map_err(archive_failed)?;
+        let path = dir.join(format!(".archive-{}.db", uuid::Uuid::new_v4()));
+        Ok(Self {
+            path: path.to_string_lossy().to_string(),
+        })
+    }
+
+    fn open(&self) -> Result<RusqliteConnection> {
+        open_connection(&self.path)
+    }
+}
+
+impl Drop for ScratchDatabase {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let path = format!("{}{}", self.path, suffix);
+            if Path::new(&path).exists() {
+                if let Err(e) = fs::remove_file(&path) {
+                    warn!("Failed to remove archive scratch file {}: {}", path, e);
+                }
+            }
+        }
+    }
+}
+
+fn open_connection(path: &str) -> Result<RusqliteConnection> {
+    let conn = RusqliteConnection::open(path).map_err(archive_failed)?;
+    conn.busy_timeout(Duration::from_secs(30))
+        .map_err(archive_failed)?;
+    Ok(conn)
+}
+
+fn list_tables(conn: &RusqliteConnection, schema: &str) -> Result<Vec<String>> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT name FROM {}.sqlite_master WHERE type = 'table'",
+            schema
+        ))
+        .map_err(archive_failed)?;
+    let tables = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(archive_failed)?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(archive_failed)?;
+    Ok(tables)
+}
+
+fn table_columns(conn: &RusqliteConnection, schema: &str, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA {}.table_info(\"{}\")", schema, table))
+        .map_err(archive_failed)?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(archive_failed)?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(archive_failed)?;
+    Ok(columns)
+}
+
+fn count_rows(conn: &RusqliteConnection, schema: &str, table: &str) -> Result<i64> {
+    conn.query_row(
+        &format!("SELECT COUNT(*) FROM {}.\"{}\"", schema, table),
+        [],
+        |row| row.get(0),
+    )
+    .map_err(archive_failed)
+}
+
+fn applied_migrations(conn: &RusqliteConnection) -> Result<Vec<String>> {
+    let mut stmt = conn
+        .prepare("SELECT version FROM __diesel_schema_migrations ORDER BY version")
+        .map_err(archive_failed)?;
+    let versions = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(archive_failed)?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(archive_failed)?;
+    Ok(versions)
+}
+
+/// Fails for images with migrations this build doesn't know, i.e. archives
+/// made by a newer version.
+fn ensure_known_schema(conn: &RusqliteConnection) -> Result<()> {
+    let known: HashSet<String> = MigrationSource::<Sqlite>::migrations(&MIGRATIONS)
+        .map_err(|e| Error::Database(DatabaseError::MigrationFailed(e.to_string())))?
+        .iter()
+        .map(|migration| migration.name().version().to_string())
+        .collect();
+
+    match applied_migrations(conn)?
+        .into_iter()
+        .find(|version| !known.contains(version))
+    {
+        Some(version) => Err(invalid_archive(format!(
+            "The archive was created by a newer version of Wealthfolio (schema {}). \
+             Update before restoring it.",
+            version
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Empties every table the archive doesn't carry, drops install rows and
+/// compacts the image.
+fn strip_image(conn: &RusqliteConnection) -> Result<()> {
+    let archived: HashSet<&str> = archive_tables().collect();
+    for table in list_tables(conn, "main")? {
+        if archived.contains(table.as_str())
+            || table.starts_with("sqlite_")
+            || table.starts_with("__diesel")
+        {
+            continue;
+        }
+        conn.execute(&format!("DELETE FROM \"{}\"", table), [])
+            .map_err(archive_failed)?;
+    }
+    for table in archive_tables() {
+        if let Some(filter) = install_rows(table) {
+            conn.execute(&format!("DELETE FROM \"{}\" WHERE {}", table, filter), [])
+                .map_err(archive_failed)?;
+        }
+        if let Some(filter) = archived_rows(table) {
+            conn.execute(
+                &format!("DELETE FROM \"{}\" WHERE NOT ({})", table, filter),
+                [],
+            )
+            .map_err(archive_failed)?;
+        }
+    }
+    conn.execute_batch("VACUUM;").map_err(archive_failed)
+}
+
+/// Creates an archive of the database, encrypted when a passphrase is given.
+pub fn create_archive(app_data_dir: &str, passphrase: Option<&str>) -> Result<PortfolioArchive> {
+    let scratch = ScratchDatabase::new(app_data_dir)?;
+    backup_database_to_file(app_data_dir, &scratch.path)?;
+
+    let (sections, schema_version) = {
+        let conn = scratch.open()?;
+        strip_image(&conn)?;
+        let mut sections = BTreeMap::new();
+        for (section, tables) in ARCHIVE_SECTIONS {
+            let mut rows = 0;
+            for table in *tables {
+                rows += count_rows(&conn, "main", table)?;
+            }
+            sections.insert(section.to_string(), rows);
+        }
+        let schema_version = applied_migrations(&conn)?.pop().unwrap_or_default();
+        (sections, schema_version)
+    };
+
+    let image = fs::read(&scratch.path).map_err(archive_failed)?;
+    let checksum = crypto::sha256_checksum(&image);
+    let (payload, encryption) = match passphrase.filter(|p| !p.is_empty()) {
+        Some(passphrase) => {
+            let salt = crypto::generate_salt();
+            let key = crypto::derive_passphrase_key(passphrase, &salt).map_err(archive_failed)?;
+            let payload = crypto::encrypt_bytes(&key, &image).map_err(archive_failed)?;
+            let encryption = ArchiveEncryption {
+                algorithm: ENCRYPTION_ALGORITHM.to_string(),
+                key_derivation: KEY_DERIVATION.to_string(),
+                salt,
+            };
+            (payload, Some(encryption))
+        }
+        None => (BASE64.encode(&image), None),
+    };
+
+    info!(
+        "Created portfolio archive ({} bytes, encrypted: {})",
+        image.len(),
+        encryption.is_some()
+    );
+
+    Ok(PortfolioArchive {
+        manifest: ArchiveManifest {
+            format: ARCHIVE_FORMAT.to_string(),
+            format_version: ARCHIVE_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version,
+            created_at: Utc::now(),
+            sections,
+            checksum,
+            encryption,
+        },
+        payload,
+    })
+}
+
+/// Decodes (and decrypts) the database image and checks it against the manifest.
+fn read_image(archive: &PortfolioArchive, passphrase: Option<&str>) -> Result<Vec<u8>> {
+    let manifest = &archive.manifest;
+    if manifest.format != ARCHIVE_FORMAT {
+        return Err(invalid_archive("Not a Wealthfolio archive"));
+    }
+    if manifest.format_version > ARCHIVE_FORMAT_VERSION {
+        return Err(invalid_archive(format!(
+            "Archive format version {} is not supported; update Wealthfolio to restore it",
+            manifest.format_version
+        )));
+    }
+
+    let image = match &manifest.encryption {
+        Some(encryption) => {
+            if encryption.algorithm != ENCRYPTION_ALGORITHM
+                || encryption.key_derivation != KEY_DERIVATION
+            {
+                return Err(invalid_archive(format!(
+                    "Unsupported archive encryption {}/{}",
+                    encryption.algorithm, encryption.key_derivation
+                )));
+            }
+            let passphrase = passphrase.filter(|p| !p.is_empty()).ok_or_else(|| {
+                invalid_archive("The archive is encrypted; a passphrase is required")
+            })?;
+            let key = crypto::derive_passphrase_key(passphrase, &encryption.salt)
+                .map_err(invalid_archive)?;
+            crypto::decrypt_bytes(&key, &archive.payload)
+                .map_err(|_| invalid_archive("Wrong passphrase or corrupted archive"))?
+        }
+        None => BASE64
+            .decode(&archive.payload)
+            .map_err(|e| invalid_archive(format!("Invalid archive payload: {}", e)))?,
+    };
+
+    if crypto::sha256_checksum(&image) != manifest.checksum {
+        return Err(invalid_archive(
+            "Archive checksum does not match; the file is corrupted",
+        ));
+    }
+    Ok(image)
+}
+
+/// Restores an archive into the database.
+///
+/// The image is first migrated to this build's schema, so archives made by
+/// older versions restore as well. Everything happens in one transaction.
+pub fn restore_archive(
+    app_data_dir: &str,
+    archive: &PortfolioArchive,
+    passphrase: Option<&str>,
+    mode: ArchiveRestoreMode,
+) -> Result<ArchiveRestoreSummary> {
+    let image = read_image(archive, passphrase)?;
+
+    let scratch = ScratchDatabase::new(app_data_dir)?;
+    fs::write(&scratch.path, &image).map_err(archive_failed)?;
+    ensure_known_schema(&scratch.open()?)?;
+    run_migrations(&scratch.path)?;
+
+    let db_path = get_db_path(app_data_dir);
+    info!(
+        "Restoring portfolio archive into {} ({:?} mode)",
+        db_path, mode
+    );
+    let plan = plan_restore(&open_connection(&db_path)?, &scratch.open()?)?;
+
+    let mut conn = SqliteConnection::establish(&db_path).map_err(StorageError::from)?;
+    conn.batch_execute("PRAGMA busy_timeout = 30000; PRAGMA foreign_keys = ON;")
+        .map_err(archive_failed)?;
+    sql_query("ATTACH DATABASE ? AS archive")
+        .bind::<Text, _>(&scratch.path)
+        .execute(&mut conn)
+        .map_err(archive_failed)?;
+    // Keep the restore's own error, e.g. a refused empty restore, across the rollback
+    let mut failure = None;
+    let result = conn
+        .immediate_transaction(|conn| {
+            restore_tables(conn, &plan, mode).map_err(|e| {
+                failure = Some(e);
+                diesel::result::Error::RollbackTransaction
+            })
+        })
+        .map_err(|e| failure.take().unwrap_or_else(|| archive_failed(e)));
+    if let Err(e) = sql_query("DETACH DATABASE archive").execute(&mut conn) {
+        warn!("Failed to detach archive database: {}", e);
+    }
+
+    Ok(ArchiveRestoreSummary {
+        mode,
+        manifest: archive.manifest.clone(),
+        sections: result?,
+    })
+}
+
+/// Archived table to copy, with the columns it shares with this database.
+struct TableRestore {
+    section: &'static str,
+    table: &'static str,
+    columns: String,
+    archived: i64,
+}
+
+fn plan_restore(
+    conn: &RusqliteConnection,
+    image: &RusqliteConnection,
+) -> Result<Vec<TableRestore>> {
+    let mut plan = Vec::new();
+    for (section, tables) in ARCHIVE_SECTIONS {
+        for table in *tables {
+            let archived_columns: HashSet<String> =
+                table_columns(image, "main", table)?.into_iter().collect();
+            let columns = table_columns(conn, "main", table)?
+                .into_iter()
+                .filter(|column| archived_columns.contains(column))
+                .map(|column| format!("\"{}\"", column))
+                .collect::<Vec<_>>()
+                .join(", ");
+            if columns.is_empty() {
+                continue;
+            }
+            plan.push(TableRestore {
+                section,
+                table,
+                columns,
+                archived: count_rows(image, "main", table)?,
+            });
+        }
+    }
+    Ok(plan)
+}
+
+fn restore_tables(
+    conn: &mut SqliteConnection,
+    plan: &[TableRestore],
+    mode: ArchiveRestoreMode,
+) -> Result<BTreeMap<String, ArchiveSectionRestore>> {
+    // Checked on commit, so self-referencing tables can be copied in any order
+    sql_query("PRAGMA defer_foreign_keys = ON")
+        .execute(conn)
+        .map_err(archive_failed)?;
+
+    if mode == ArchiveRestoreMode::Empty {
+        let accounts_count: i64 = accounts::table
+            .count()
+            .get_result(conn)
+            .map_err(StorageError::from)?;
+        let activities_count: i64 = activities::table
+            .count()
+            .get_result(conn)
+            .map_err(StorageError::from)?;
+        if accounts_count + activities_count > 0 {
+            return Err(invalid_archive(
+                "The database already has accounts or activities; \
+                 restore in merge mode instead",
+            ));
+        }
+        for table in archive_tables().rev() {
+            let sql = match install_rows(table) {
+                Some(filter) => format!("DELETE FROM main.\"{}\" WHERE NOT ({})", table, filter),
+                None => format!("DELETE FROM main.\"{}\"", table),
+            };
+            sql_query(sql).execute(conn).map_err(archive_failed)?;
+        }
+    }
+
+    let mut projection =
+        WriteProjection::with_audit_context(AuditContext::new(AuditSource::Import));
+    let mut sections: BTreeMap<String, ArchiveSectionRestore> = ARCHIVE_SECTIONS
+        .iter()
+        .map(|(section, _)| (section.to_string(), ArchiveSectionRestore::default()))
+        .collect();
+    for TableRestore {
+        section,
+        table,
+        columns,
+        archived,
+    } in plan
+    {
+        let new_keys = match synced_rows(table) {
+            Some((key, filter)) => new_row_keys(conn, table, key, filter)?,
+            None => Vec::new(),
+        };
+        let inserted = sql_query(format!(
+            "INSERT OR IGNORE INTO main.\"{table}\" ({columns}) \
+             SELECT {columns} FROM archive.\"{table}\"",
+        ))
+        .execute(conn)
+        .map_err(archive_failed)?;
+        capture_restored(conn, &mut projection, table, &new_keys)?;
+
+        let restored = sections.entry(section.to_string()).or_default();
+        restored.archived += archived;
+        restored.restored += inserted as i64;
+    }
+    projection.flush(conn)?;
+    Ok(sections)
+}
+
+#[derive(QueryableByName)]
+struct RowKey {
+    #[diesel(sql_type = Text)]
+    key: String,
+}
+
+/// Keys of the archived rows the database doesn't have yet.
+fn new_row_keys(
+    conn: &mut SqliteConnection,
+    table: &str,
+    key: &str,
+    filter: Option<&str>,
+) -> Result<Vec<String>> {
+    let filter = filter.map(|f| format!(" AND {}", f)).unwrap_or_default();
+    let keys = sql_query(format!(
+        "SELECT \"{key}\" AS key FROM archive.\"{table}\" \
+         WHERE \"{key}\" NOT IN (SELECT \"{key}\" FROM main.\"{table}\"){filter}",
+    ))
+    .load::<RowKey>(conn)
+    .map_err(archive_failed)?;
+    Ok(keys.into_iter().map(|row| row.key).collect())
+}
+
+/// Captures the restored rows as creates, for the sync outbox and audit log.
+fn capture_restored(
+    conn: &mut SqliteConnection,
+    projection: &mut WriteProjection,
+    table: &str,
+    keys: &[String],
+) -> Result<()> {
+    if keys.is_empty() {
+        return Ok(());
+    }
+    match table {
+        "platforms" => capture_rows(conn, projection, keys, |conn, chunk| {
+            platforms::table
+                .filter(platforms::id.eq_any(chunk))
+                .select(PlatformDB::as_select())
+                .load(conn)
+        }),
+        "accounts" => capture_rows(conn, projection, keys, |conn, chunk| {
+            accounts::table
+                .filter(accounts::id.eq_any(chunk))
+                .select(AccountDB::as_select())
+                .load(conn)
+        }),
+        "assets" => capture_rows(conn, projection, keys, |conn, chunk| {
+            assets::table
+                .filter(assets::id.eq_any(chunk))
+                .select(AssetDB::as_select())
+                .load(conn)
+        }),
+        "quotes" => capture_rows(conn, projection, keys, |conn, chunk| {
+            quotes::table
+                .filter(quotes::id.eq_any(chunk))
+                .load::<QuoteDB>(conn)
+        }),
+        "asset_taxonomy_assignments" => capture_rows(conn, projection, keys, |conn, chunk| {
+            asset_taxonomy_assignments::table
+                .filter(asset_taxonomy_assignments::id.eq_any(chunk))
+                .select(AssetTaxonomyAssignmentDB::as_select())
+                .load(conn)
+        }),
+        "activity_import_profiles" => capture_rows(conn, projection, keys, |conn, chunk| {
+            activity_import_profiles::table
+                .filter(activity_import_profiles::account_id.eq_any(chunk))
+                .load::<ImportMappingDB>(conn)
+        }),
+        "activities" => capture_rows(conn, projection, keys, |conn, chunk| {
+            activities::table
+                .filter(activities::id.eq_any(chunk))
+                .select(ActivityDB::as_select())
+                .load(conn)
+        }),
+        "holdings_snapshots" => capture_rows(conn, projection, keys, |conn, chunk| {
+            holdings_snapshots::table
+                .filter(holdings_snapshots::id.eq_any(chunk))
+                .load::<AccountStateSnapshotDB>(conn)
+        }),
+        "goals" => capture_rows(conn, projection, keys, |conn, chunk| {
+            goals::table
+                .filter(goals::id.eq_any(chunk))
+                .select(GoalDB::as_select())
+                .load(conn)
+        }),
+        "goals_allocation" => capture_rows(conn, projection, keys, |conn, chunk| {
+            goals_allocation::table
+                .filter(goals_allocation::id.eq_any(chunk))
+                .select(GoalsAllocationDB::as_select())
+                .load(conn)
+        }),
+        "contribution_limits" => capture_rows(conn, projection, keys, |conn, chunk| {
+            contribution_limits::table
+                .filter(contribution_limits::id.eq_any(chunk))
+                .load::<ContributionLimitDB>(conn)
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn capture_rows<T: SyncOutboxModel>(
+    conn: &mut SqliteConnection,
+    projection: &mut WriteProjection,
+    keys: &[String],
+    load: impl Fn(&mut SqliteConnection, &[String]) -> QueryResult<Vec<T>>,
+) -> Result<()> {
+    for chunk in chunk_for_sqlite(keys) {
+        for row in load(conn, chunk).map_err(StorageError::from)? {
+            projection.capture_create(&row)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{init, run_migrations};
+    use tempfile::tempdir;
+
+    fn setup_app_dir() -> String {
+        let app_data = tempdir()
+            .expect("tempdir")
+            .keep()
+            .to_string_lossy()
+            .to_string();
+        let db_path = init(&app_data).expect("init db");
+        run_migrations(&db_path).expect("migrate db");
+        app_data
+    }
+
+    fn insert_account(app_data: &str, id: &str, name: &str) {
+        let conn = open_connection(&get_db_path(app_data)).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (id, name, account_type, currency, is_default, is_active, \
+             created_at, updated_at, is_archived, tracking_mode) \
+             VALUES (?1, ?2, 'SECURITIES', 'USD', 0, 1, '2024-01-01 00:00:00', \
+             '2024-01-01 00:00:00', 0, 'TRANSACTIONS')",
+            [id, name],
+        )
+        .unwrap();
+    }
+
+    fn insert_snapshot(app_data: &str, id: &str, account_id: &str, source: &str) {
+        let conn = open_connection(&get_db_path(app_data)).unwrap();
+        conn.execute(
+            "INSERT INTO holdings_snapshots (id, account_id, snapshot_date, currency, positions, \
+             cash_balances, cost_basis, net_contribution, calculated_at, net_contribution_base, \
+             cash_total_account_currency, cash_total_base_currency, source) \
+             VALUES (?1, ?2, '2024-01-31', 'USD', '{}', '{}', '0', '0', \
+             '2024-01-31T00:00:00Z', '0', '0', '0', ?3)",
+            [id, account_id, source],
+        )
+        .unwrap();
+    }
+
+    fn setting(app_data: &str, key: &str) -> Option<String> {
+        let conn = open_connection(&get_db_path(app_data)).unwrap();
+        conn.query_row(
+            "SELECT setting_value FROM app_settings WHERE setting_key = ?1",
+            [key],
+            |row| row.get(0),
+        )
+        .ok()
+    }
+
+    fn set_setting(app_data: &str, key: &str, value: &str) {
+        let conn = open_connection(&get_db_path(app_data)).unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO app_settings (setting_key, setting_value) VALUES (?1, ?2)",
+            [key, value],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_encrypted_archive_restores_into_empty_database() {
+        let source = setup_app_dir();
+        insert_account(&source, "acc_1", "Brokerage");
+        set_setting(&source, "base_currency", "EUR");
+        set_setting(&source, "instance_id", "source-install");
+
+        let archive = create_archive(&source, Some("secret")).unwrap();
+        assert_eq!(archive.manifest.sections["accounts"], 1);
+        assert!(archive.manifest.encryption.is_some());
+
+        let target = setup_app_dir();
+        set_setting(&target, "instance_id", "target-install");
+
+        let wrong = restore_archive(&target, &archive, Some("guess"), ArchiveRestoreMode::Empty);
+        assert!(matches!(wrong, Err(Error::Validation(_))));
+
+        let summary =
+            restore_archive(&target, &archive, Some("secret"), ArchiveRestoreMode::Empty).unwrap();
+        assert_eq!(summary.sections["accounts"].restored, 1);
+        assert_eq!(setting(&target, "base_currency").as_deref(), Some("EUR"));
+        // The install keeps its own identity
+        assert_eq!(
+            setting(&target, "instance_id").as_deref(),
+            Some("target-install")
+        );
+
+        // Restoring again needs merge mode, which skips existing rows
+        let refused = restore_archive(&target, &archive, Some("secret"), ArchiveRestoreMode::Empty);
+        assert!(matches!(refused, Err(Error::Validation(_))));
+        let merged =
+            restore_archive(&target, &archive, Some("secret"), ArchiveRestoreMode::Merge).unwrap();
+        assert_eq!(merged.sections["accounts"].archived, 1);
+        assert_eq!(merged.sections["accounts"].restored, 0);
+    }
+
+    #[test]
+    fn test_merge_keeps_existing_rows_and_settings() {
+        let source = setup_app_dir();
+        insert_account(&source, "acc_1", "Archived name");
+        insert_account(&source, "acc_2", "Pension");
+        set_setting(&source, "base_currency", "EUR");
+        let archive = create_archive(&source, None).unwrap();
+        assert!(archive.manifest.encryption.is_none());
+
+        let target = setup_app_dir();
+        insert_account(&target, "acc_1", "Local name");
+        set_setting(&target, "base_currency", "CAD");
+
+        let summary = restore_archive(&target, &archive, None, ArchiveRestoreMode::Merge).unwrap();
+        assert_eq!(summary.sections["accounts"].restored, 1);
+
+        let conn = open_connection(&get_db_path(&target)).unwrap();
+        let name: String = conn
+            .query_row("SELECT name FROM accounts WHERE id = 'acc_1'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(name, "Local name");
+        assert_eq!(count_rows(&conn, "main", "accounts").unwrap(), 2);
+        assert_eq!(setting(&target, "base_currency").as_deref(), Some("CAD"));
+    }
+
+    #[test]
+    fn test_holdings_mode_snapshots_are_archived_and_restores_are_recorded() {
+        let source = setup_app_dir();
+        insert_account(&source, "acc_1", "Brokerage");
+        insert_account(&source, "acc_2", "Pension");
+        {
+            let conn = open_connection(&get_db_path(&source)).unwrap();
+            conn.execute(
+                "UPDATE accounts SET tracking_mode = 'HOLDINGS' WHERE id = 'acc_2'",
+                [],
+            )
+            .unwrap();
+        }
+        insert_snapshot(&source, "snap_calc", "acc_1", "CALCULATED");
+        insert_snapshot(&source, "snap_tx_manual", "acc_1", "MANUAL_ENTRY");
+        insert_snapshot(&source, "snap_holdings_calc", "acc_2", "CALCULATED");
+        insert_snapshot(&source, "snap_manual", "acc_2", "MANUAL_ENTRY");
+        insert_snapshot(&source, "snap_broker", "acc_2", "BROKER_IMPORTED");
+
+        let archive = create_archive(&source, None).unwrap();
+        assert_eq!(archive.manifest.sections["snapshots"], 2);
+
+        let target = setup_app_dir();
+        let summary = restore_archive(&target, &archive, None, ArchiveRestoreMode::Empty).unwrap();
+        assert_eq!(summary.sections["snapshots"].restored, 2);
+
+        let conn = open_connection(&get_db_path(&target)).unwrap();
+        let mut stmt = conn
+            .prepare("SELECT id FROM holdings_snapshots ORDER BY id")
+            .unwrap();
+        let snapshots: Vec<String> = stmt
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(snapshots, vec!["snap_broker", "snap_manual"]);
+
+        // Restored rows are synced and audited like other writes
+        let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+        assert_eq!(
+            count(
+                "SELECT COUNT(*) FROM audit_log \
+                 WHERE entity_type = 'ACCOUNT' AND operation = 'CREATE' AND source = 'IMPORT'"
+            ),
+            2
+        );
+        assert_eq!(
+            count("SELECT COUNT(*) FROM sync_outbox WHERE entity_id IN ('acc_1', 'acc_2')"),
+            2
+        );
+
+        // Rows kept by a merge are not recorded again
+        restore_archive(&target, &archive, None, ArchiveRestoreMode::Merge).unwrap();
+        assert_eq!(count("SELECT COUNT(*) FROM audit_log"), 2);
+    }
+
+    #[test]
+    fn test_tampered_archive_is_rejected() {
+        let source = setup_app_dir();
+        insert_account(&source, "acc_1", "Brokerage");
+        let mut archive = create_archive(&source, None).unwrap();
+        archive.manifest.checksum = crypto::sha256_checksum(b"something else");
+
+        let target = setup_app_dir();
+        let result = restore_archive(&target, &archive, None, ArchiveRestoreMode::Empty);
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+}
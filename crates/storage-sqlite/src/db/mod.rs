@@ -22,6 +22,7 @@ const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
 pub type DbPool = r2d2::Pool<ConnectionManager<SqliteConnection>>;
 pub type DbConnection = PooledConnection<ConnectionManager<SqliteConnection>>;
 
+pub mod archive;
 pub mod write_actor;
 pub use write_actor::WriteHandle;
 
@@ -1,10 +1,14 @@
 use super::DbPool;
+use crate::audit::{record_audit_changes, AuditChange};
 use crate::errors::StorageError;
 use crate::sync::app_sync::ProjectedChange;
-use crate::sync::{flush_projected_outbox, OutboxWriteRequest, SyncOutboxModel};
+use crate::sync::{
+    flush_projected_outbox, resolve_local_device_id, OutboxWriteRequest, SyncOutboxModel,
+};
 use diesel::SqliteConnection;
 use std::any::Any;
 use tokio::sync::{mpsc, oneshot};
+use wealthfolio_core::audit::{current_audit_context, AuditContext};
 use wealthfolio_core::errors::Result;
 use wealthfolio_core::sync::SyncOperation;
 
@@ -72,8 +76,10 @@ impl WriteHandle {
         F: FnOnce(&mut SqliteConnection, &mut WriteProjection) -> Result<T> + Send + 'static,
         T: Send + 'static + Any,
     {
+        // The writer runs on its own task, so take the caller's attribution along
+        let audit_context = current_audit_context();
         self.exec(move |conn| {
-            let mut projection = WriteProjection::default();
+            let mut projection = WriteProjection::with_audit_context(audit_context);
             let result = job(conn, &mut projection)?;
             projection.flush(conn)?;
             Ok(result)
@@ -87,8 +93,9 @@ impl WriteHandle {
         F: FnOnce(&mut DbWriteTx<'_>) -> Result<T> + Send + 'static,
         T: Send + 'static + Any,
     {
+        let audit_context = current_audit_context();
         self.exec(move |conn| {
-            let mut projection = WriteProjection::default();
+            let mut projection = WriteProjection::with_audit_context(audit_context);
             let result = {
                 let mut tx = DbWriteTx {
                     conn,
@@ -137,14 +144,24 @@ impl<'a> DbWriteTx<'a> {
     }
 }
 
-/// Collects projected outbox writes and flushes them before transaction commit.
+/// Collects projected outbox writes and audit log changes and flushes them
+/// before transaction commit.
 #[derive(Default)]
 pub struct WriteProjection {
     outbox_requests: Vec<OutboxWriteRequest>,
     projected_changes: Vec<ProjectedChange>,
+    audit_context: AuditContext,
+    audit_changes: Vec<AuditChange>,
 }
 
 impl WriteProjection {
+    pub fn with_audit_context(audit_context: AuditContext) -> Self {
+        Self {
+            audit_context,
+            ..Default::default()
+        }
+    }
+
     pub fn queue_outbox(&mut self, request: OutboxWriteRequest) {
         self.outbox_requests.push(request);
     }
@@ -155,6 +172,10 @@ impl WriteProjection {
         model: &T,
         op: SyncOperation,
     ) -> Result<()> {
+        // Audited regardless of whether the change is synced to other devices
+        if let Some(change) = AuditChange::for_model(model, op)? {
+            self.audit_changes.push(change);
+        }
         if !model.should_sync_outbox(op) {
             return Ok(());
         }
@@ -173,19 +194,33 @@ impl WriteProjection {
 
     pub fn capture_delete<T: SyncOutboxModel>(&mut self, entity_id: impl Into<String>) {
         let entity_id = entity_id.into();
-        if T::should_sync_outbox_delete(&entity_id) {
-            self.projected_changes
-                .push(ProjectedChange::delete_for_model::<T>(entity_id));
-        }
+        self.audit_changes
+            .extend(AuditChange::delete_for_model::<T>(&entity_id));
+        self.capture_outbox_delete::<T>(entity_id);
     }
 
     pub fn capture_model_delete<T: SyncOutboxModel>(&mut self, model: &T) {
+        // Keep the deleted row in the audit log so it can be restored
+        if let Ok(Some(change)) = AuditChange::for_model(model, SyncOperation::Delete) {
+            self.audit_changes.push(change);
+        }
         if model.should_sync_outbox(SyncOperation::Delete) {
-            self.capture_delete::<T>(model.sync_entity_id().to_string());
+            self.capture_outbox_delete::<T>(model.sync_entity_id().to_string());
+        }
+    }
+
+    fn capture_outbox_delete<T: SyncOutboxModel>(&mut self, entity_id: String) {
+        if T::should_sync_outbox_delete(&entity_id) {
+            self.projected_changes
+                .push(ProjectedChange::delete_for_model::<T>(entity_id));
         }
     }
 
-    fn flush(self, conn: &mut SqliteConnection) -> Result<()> {
+    pub(crate) fn flush(self, conn: &mut SqliteConnection) -> Result<()> {
+        if !self.audit_changes.is_empty() {
+            let device_id = resolve_local_device_id(conn);
+            record_audit_changes(conn, &self.audit_context, device_id, self.audit_changes)?;
+        }
         flush_projected_outbox(conn, self.outbox_requests, self.projected_changes)
     }
 }
@@ -0,0 +1,7 @@
+//! SQLite storage implementation for equity grants.
+
+mod model;
+mod repository;
+
+pub use model::EquityGrantDB;
+pub use repository::EquityGrantRepository;
@@ -0,0 +1,93 @@
+//! Database models for equity grants.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use log::error;
+use wealthfolio_core::equity_grants::{EquityGrant, GrantType};
+
+/// Database model for equity grants.
+///
+/// Share counts and prices are stored as decimal text, dates as `YYYY-MM-DD`.
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Debug, Clone, PartialEq)]
+#[diesel(table_name = crate::schema::equity_grants)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[diesel(treat_none_as_null = true)]
+pub struct EquityGrantDB {
+    pub id: String,
+    pub account_id: String,
+    pub asset_id: String,
+    pub grant_type: String,
+    pub grant_date: String,
+    pub total_shares: String,
+    pub strike_price: Option<String>,
+    pub grant_price: Option<String>,
+    pub currency: String,
+    pub vesting_start_date: String,
+    pub vesting_months: i32,
+    pub cliff_months: i32,
+    pub vesting_interval_months: i32,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+fn text_to_date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap_or_default()
+}
+
+fn text_to_datetime(s: &str) -> NaiveDateTime {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.naive_utc())
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S"))
+        .unwrap_or_default()
+}
+
+impl From<EquityGrantDB> for EquityGrant {
+    fn from(db: EquityGrantDB) -> Self {
+        let grant_type = db.grant_type.parse().unwrap_or_else(|e| {
+            error!("{} (equity grant {})", e, db.id);
+            GrantType::Rsu
+        });
+        Self {
+            grant_type,
+            grant_date: text_to_date(&db.grant_date),
+            total_shares: db.total_shares.parse().unwrap_or_default(),
+            strike_price: db.strike_price.and_then(|p| p.parse().ok()),
+            grant_price: db.grant_price.and_then(|p| p.parse().ok()),
+            currency: db.currency,
+            vesting_start_date: text_to_date(&db.vesting_start_date),
+            vesting_months: db.vesting_months.max(0) as u32,
+            cliff_months: db.cliff_months.max(0) as u32,
+            vesting_interval_months: db.vesting_interval_months.max(0) as u32,
+            notes: db.notes,
+            created_at: text_to_datetime(&db.created_at),
+            updated_at: text_to_datetime(&db.updated_at),
+            id: db.id,
+            account_id: db.account_id,
+            asset_id: db.asset_id,
+        }
+    }
+}
+
+impl From<EquityGrant> for EquityGrantDB {
+    fn from(domain: EquityGrant) -> Self {
+        Self {
+            id: domain.id,
+            account_id: domain.account_id,
+            asset_id: domain.asset_id,
+            grant_type: domain.grant_type.as_str().to_string(),
+            grant_date: domain.grant_date.format("%Y-%m-%d").to_string(),
+            total_shares: domain.total_shares.to_string(),
+            strike_price: domain.strike_price.map(|p| p.to_string()),
+            grant_price: domain.grant_price.map(|p| p.to_string()),
+            currency: domain.currency,
+            vesting_start_date: domain.vesting_start_date.format("%Y-%m-%d").to_string(),
+            vesting_months: domain.vesting_months as i32,
+            cliff_months: domain.cliff_months as i32,
+            vesting_interval_months: domain.vesting_interval_months as i32,
+            notes: domain.notes,
+            created_at: domain.created_at.and_utc().to_rfc3339(),
+            updated_at: domain.updated_at.and_utc().to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use wealthfolio_core::equity_grants::{EquityGrant, EquityGrantRepositoryTrait};
+use wealthfolio_core::errors::{DatabaseError, Error};
+use wealthfolio_core::Result;
+
+use super::model::EquityGrantDB;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::StorageError;
+use crate::schema::equity_grants;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{self, Pool};
+use diesel::SqliteConnection;
+
+use std::sync::Arc;
+
+pub struct EquityGrantRepository {
+    pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl EquityGrantRepository {
+    pub fn new(
+        pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+        writer: WriteHandle,
+    ) -> Self {
+        EquityGrantRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl EquityGrantRepositoryTrait for EquityGrantRepository {
+    fn list_equity_grants(&self) -> Result<Vec<EquityGrant>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = equity_grants::table
+            .order((
+                equity_grants::grant_date.asc(),
+                equity_grants::created_at.asc(),
+            ))
+            .select(EquityGrantDB::as_select())
+            .load::<EquityGrantDB>(&mut conn)
+            .map_err(StorageError::from)?;
+        Ok(rows.into_iter().map(EquityGrant::from).collect())
+    }
+
+    fn get_equity_grant(&self, grant_id: &str) -> Result<EquityGrant> {
+        let mut conn = get_connection(&self.pool)?;
+        let row = equity_grants::table
+            .find(grant_id)
+            .select(EquityGrantDB::as_select())
+            .first::<EquityGrantDB>(&mut conn)
+            .optional()
+            .map_err(StorageError::from)?;
+        row.map(EquityGrant::from).ok_or_else(|| {
+            Error::Database(DatabaseError::NotFound(format!(
+                "Equity grant not found: {}",
+                grant_id
+            )))
+        })
+    }
+
+    async fn insert_equity_grant(&self, grant: EquityGrant) -> Result<EquityGrant> {
+        let row = EquityGrantDB::from(grant.clone());
+
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::insert_into(equity_grants::table)
+                    .values(&row)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                Ok(())
+            })
+            .await?;
+
+        Ok(grant)
+    }
+
+    async fn update_equity_grant(&self, grant: EquityGrant) -> Result<EquityGrant> {
+        let row = EquityGrantDB::from(grant.clone());
+
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                let updated = diesel::update(equity_grants::table.find(row.id.clone()))
+                    .set(&row)
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                if updated == 0 {
+                    return Err(Error::Database(DatabaseError::NotFound(format!(
+                        "Equity grant not found: {}",
+                        row.id
+                    ))));
+                }
+                Ok(())
+            })
+            .await?;
+
+        Ok(grant)
+    }
+
+    async fn delete_equity_grant(&self, grant_id: &str) -> Result<usize> {
+        let id_owned = grant_id.to_string();
+
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                let deleted = diesel::delete(equity_grants::table.find(&id_owned))
+                    .execute(conn)
+                    .map_err(StorageError::from)?;
+                if deleted == 0 {
+                    return Err(Error::Database(DatabaseError::NotFound(format!(
+                        "Equity grant not found: {}",
+                        id_owned
+                    ))));
+                }
+                Ok(deleted)
+            })
+            .await
+    }
+}
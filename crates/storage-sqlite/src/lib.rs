@@ -34,6 +34,8 @@ pub mod accounts;
 pub mod activities;
 pub mod ai_chat;
 pub mod assets;
+pub mod audit;
+pub mod equity_grants;
 pub mod fx;
 pub mod goals;
 pub mod health;
@@ -7,7 +7,8 @@ use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 use wealthfolio_core::quotes::{
-    DataSource, MarketDataProviderSetting, ProviderCapabilities, Quote, QuoteSyncState,
+    ConsensusStatus, DataSource, MarketDataProviderSetting, ProviderCapabilities, Quote,
+    QuoteProvenance, QuoteSyncState,
 };
 
 /// Database model for quotes
@@ -63,6 +64,23 @@ pub struct QuoteDB {
     pub timestamp: String,
 }
 
+/// Database model for quote provenance
+///
+/// `field_sources` and `candidates` are stored as JSON text.
+#[derive(Queryable, Selectable, Insertable, Debug, Clone, PartialEq)]
+#[diesel(table_name = crate::schema::quote_provenance)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct QuoteProvenanceDB {
+    pub quote_id: String,
+    pub asset_id: String,
+    pub day: String,
+    pub status: String,
+    pub field_sources: String,
+    pub candidates: String,
+    pub deviation: Option<String>,
+    pub created_at: String,
+}
+
 /// Database model for market data provider settings
 #[derive(
     Debug,
@@ -212,6 +230,40 @@ impl From<&Quote> for QuoteDB {
     }
 }
 
+impl From<QuoteProvenanceDB> for QuoteProvenance {
+    fn from(db: QuoteProvenanceDB) -> Self {
+        QuoteProvenance {
+            quote_id: db.quote_id,
+            asset_id: db.asset_id,
+            day: NaiveDate::parse_from_str(&db.day, "%Y-%m-%d").unwrap_or_default(),
+            status: ConsensusStatus::from(db.status.as_str()),
+            field_sources: serde_json::from_str(&db.field_sources).unwrap_or_default(),
+            candidates: serde_json::from_str(&db.candidates).unwrap_or_default(),
+            deviation: db.deviation.and_then(|d| Decimal::from_str(&d).ok()),
+            created_at: DateTime::parse_from_rfc3339(&db.created_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        }
+    }
+}
+
+impl From<&QuoteProvenance> for QuoteProvenanceDB {
+    fn from(record: &QuoteProvenance) -> Self {
+        QuoteProvenanceDB {
+            quote_id: record.quote_id.clone(),
+            asset_id: record.asset_id.clone(),
+            day: record.day.format("%Y-%m-%d").to_string(),
+            status: record.status.as_str().to_string(),
+            field_sources: serde_json::to_string(&record.field_sources)
+                .unwrap_or_else(|_| "{}".to_string()),
+            candidates: serde_json::to_string(&record.candidates)
+                .unwrap_or_else(|_| "[]".to_string()),
+            deviation: record.deviation.map(|d| d.to_string()),
+            created_at: record.created_at.to_rfc3339(),
+        }
+    }
+}
+
 impl From<MarketDataProviderSettingDB> for MarketDataProviderSetting {
     fn from(db: MarketDataProviderSettingDB) -> Self {
         let capabilities = ProviderCapabilities::for_provider(&db.id);
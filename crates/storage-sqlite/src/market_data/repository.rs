@@ -9,16 +9,20 @@ use diesel::sqlite::SqliteConnection;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use super::model::{MarketDataProviderSettingDB, QuoteDB, UpdateMarketDataProviderSettingDB};
+use super::model::{
+    MarketDataProviderSettingDB, QuoteDB, QuoteProvenanceDB, UpdateMarketDataProviderSettingDB,
+};
 use crate::db::{get_connection, WriteHandle};
 use crate::errors::{IntoCore, StorageError};
 use crate::schema::market_data_providers::dsl as market_data_providers_dsl;
+use crate::schema::quote_provenance::dsl as provenance_dsl;
 use crate::schema::quotes::dsl as quotes_dsl;
 use crate::utils::chunk_for_sqlite;
 use wealthfolio_core::quotes::store::{ProviderSettingsStore, QuoteStore};
 use wealthfolio_core::quotes::types::{AssetId, Day, QuoteSource};
 use wealthfolio_core::quotes::{
-    LatestQuotePair, MarketDataProviderSetting, Quote, UpdateMarketDataProviderSetting,
+    LatestQuotePair, MarketDataProviderSetting, Quote, QuoteProvenance,
+    UpdateMarketDataProviderSetting,
 };
 use wealthfolio_core::Result;
 
@@ -98,6 +102,13 @@ impl QuoteStore for MarketDataRepository {
                     .execute(tx.conn())
                     .map_err(StorageError::QueryFailed)?;
 
+                diesel::delete(
+                    provenance_dsl::quote_provenance
+                        .filter(provenance_dsl::quote_id.eq(&id_to_delete)),
+                )
+                .execute(tx.conn())
+                .map_err(StorageError::QueryFailed)?;
+
                 if let Some(row) = existing {
                     tx.delete_model(&row);
                 }
@@ -199,6 +210,83 @@ impl QuoteStore for MarketDataRepository {
             .await
     }
 
+    async fn bulk_insert_quotes(&self, input_quotes: &[Quote], overwrite: bool) -> Result<usize> {
+        if input_quotes.is_empty() {
+            return Ok(0);
+        }
+
+        let db_rows: Vec<QuoteDB> = input_quotes.iter().map(QuoteDB::from).collect();
+
+        self.writer
+            .exec_tx(move |tx| -> Result<usize> {
+                let (manual_rows, provider_rows): (Vec<QuoteDB>, Vec<QuoteDB>) = db_rows
+                    .into_iter()
+                    .partition(|row| row.source.eq_ignore_ascii_case("MANUAL"));
+
+                let mut total_written: usize = 0;
+                for chunk in provider_rows.chunks(1_000) {
+                    total_written += if overwrite {
+                        diesel::replace_into(quotes_dsl::quotes)
+                            .values(chunk)
+                            .execute(tx.conn())
+                    } else {
+                        diesel::insert_or_ignore_into(quotes_dsl::quotes)
+                            .values(chunk)
+                            .execute(tx.conn())
+                    }
+                    .map_err(StorageError::QueryFailed)?;
+                }
+
+                // Manual quotes are user data: match existing rows by asset and day
+                // and record every write in the sync outbox, like `upsert_quotes`.
+                for chunk in manual_rows.chunks(1_000) {
+                    let pairs: HashSet<(&str, &str)> = chunk
+                        .iter()
+                        .map(|r| (r.asset_id.as_str(), r.day.as_str()))
+                        .collect();
+                    let asset_ids: Vec<&str> = pairs.iter().map(|(a, _)| *a).collect();
+                    let days: Vec<&str> = pairs.iter().map(|(_, d)| *d).collect();
+                    let mut existing_ids: HashMap<(String, String), String> = quotes_dsl::quotes
+                        .filter(quotes_dsl::source.eq("MANUAL"))
+                        .filter(quotes_dsl::asset_id.eq_any(&asset_ids))
+                        .filter(quotes_dsl::day.eq_any(&days))
+                        .select((quotes_dsl::asset_id, quotes_dsl::day, quotes_dsl::id))
+                        .load::<(String, String, String)>(tx.conn())
+                        .map_err(StorageError::QueryFailed)?
+                        .into_iter()
+                        .map(|(asset_id, day, id)| ((asset_id, day), id))
+                        .collect();
+
+                    for row in chunk {
+                        let key = (row.asset_id.clone(), row.day.clone());
+                        let mut payload = row.clone();
+                        let is_update = match existing_ids.get(&key) {
+                            Some(_) if !overwrite => continue,
+                            Some(id) => {
+                                payload.id = id.clone();
+                                true
+                            }
+                            None => false,
+                        };
+
+                        total_written += diesel::replace_into(quotes_dsl::quotes)
+                            .values(&payload)
+                            .execute(tx.conn())
+                            .map_err(StorageError::QueryFailed)?;
+
+                        if is_update {
+                            tx.update(&payload)?;
+                        } else {
+                            tx.insert(&payload)?;
+                            existing_ids.insert(key, payload.id);
+                        }
+                    }
+                }
+                Ok(total_written)
+            })
+            .await
+    }
+
     async fn delete_quotes_for_asset(&self, asset_id: &AssetId) -> Result<usize> {
         let asset_id_str = asset_id.as_str().to_string();
 
@@ -216,6 +304,13 @@ impl QuoteStore for MarketDataRepository {
                 .execute(tx.conn())
                 .map_err(StorageError::QueryFailed)?;
 
+                diesel::delete(
+                    provenance_dsl::quote_provenance
+                        .filter(provenance_dsl::asset_id.eq(&asset_id_str)),
+                )
+                .execute(tx.conn())
+                .map_err(StorageError::QueryFailed)?;
+
                 for row in &existing_rows {
                     tx.delete_model(row);
                 }
@@ -232,11 +327,18 @@ impl QuoteStore for MarketDataRepository {
             .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
                 let count = diesel::delete(
                     quotes_dsl::quotes
-                        .filter(quotes_dsl::asset_id.eq(asset_id_str))
+                        .filter(quotes_dsl::asset_id.eq(&asset_id_str))
                         .filter(quotes_dsl::source.ne("MANUAL")),
                 )
                 .execute(conn)
                 .map_err(StorageError::QueryFailed)?;
+
+                diesel::delete(
+                    provenance_dsl::quote_provenance
+                        .filter(provenance_dsl::asset_id.eq(&asset_id_str)),
+                )
+                .execute(conn)
+                .map_err(StorageError::QueryFailed)?;
                 Ok(count)
             })
             .await
@@ -722,6 +824,54 @@ impl QuoteStore for MarketDataRepository {
 
         Ok(result)
     }
+
+    // =========================================================================
+    // Provenance
+    // =========================================================================
+
+    async fn upsert_provenance(&self, records: &[QuoteProvenance]) -> Result<usize> {
+        if records.is_empty() {
+            return Ok(0);
+        }
+
+        let db_rows: Vec<QuoteProvenanceDB> = records.iter().map(QuoteProvenanceDB::from).collect();
+
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                let mut total_upserted: usize = 0;
+                for chunk in db_rows.chunks(1_000) {
+                    total_upserted += diesel::replace_into(provenance_dsl::quote_provenance)
+                        .values(chunk)
+                        .execute(conn)
+                        .map_err(StorageError::QueryFailed)?;
+                }
+                Ok(total_upserted)
+            })
+            .await
+    }
+
+    fn provenance_range(
+        &self,
+        asset_id: &AssetId,
+        start: Day,
+        end: Day,
+    ) -> Result<Vec<QuoteProvenance>> {
+        let mut conn = get_connection(&self.pool)?;
+
+        let start_str = start.date().format("%Y-%m-%d").to_string();
+        let end_str = end.date().format("%Y-%m-%d").to_string();
+
+        let results = provenance_dsl::quote_provenance
+            .filter(provenance_dsl::asset_id.eq(asset_id.as_str()))
+            .filter(provenance_dsl::day.ge(&start_str))
+            .filter(provenance_dsl::day.le(&end_str))
+            .order(provenance_dsl::day.asc())
+            .select(QuoteProvenanceDB::as_select())
+            .load::<QuoteProvenanceDB>(&mut conn)
+            .into_core()?;
+
+        Ok(results.into_iter().map(QuoteProvenance::from).collect())
+    }
 }
 
 // =============================================================================
@@ -780,3 +930,101 @@ impl ProviderSettingsStore for MarketDataRepository {
         Ok(MarketDataProviderSetting::from(db_result))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{create_pool, run_migrations, write_actor::spawn_writer};
+    use crate::schema::sync_outbox;
+    use chrono::{TimeZone, Utc};
+    use diesel::dsl::count_star;
+    use rust_decimal::Decimal;
+    use tempfile::tempdir;
+    use uuid::Uuid;
+    use wealthfolio_core::quotes::DataSource;
+
+    async fn create_test_repository() -> (
+        MarketDataRepository,
+        Arc<Pool<ConnectionManager<SqliteConnection>>>,
+        tempfile::TempDir,
+    ) {
+        std::env::set_var("CONNECT_API_URL", "http://test.local");
+
+        let temp_dir = tempdir().expect("Failed to create temp directory");
+        let db_path = temp_dir.path().join("test.db");
+        let db_path_str = db_path.to_string_lossy().to_string();
+
+        run_migrations(&db_path_str).expect("Failed to run migrations");
+        let pool = create_pool(&db_path_str).expect("Failed to create pool");
+        let writer = spawn_writer((*pool).clone());
+
+        let mut conn = get_connection(&pool).expect("Failed to get connection");
+        diesel::sql_query(
+            "INSERT INTO assets (id, kind, name, display_code, is_active, quote_mode, quote_ccy, created_at, updated_at) \
+             VALUES ('asset-1', 'INVESTMENT', 'Test Asset', 'TST', 1, 'MANUAL', 'USD', CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
+        )
+        .execute(&mut conn)
+        .expect("Failed to create test asset");
+
+        let repo = MarketDataRepository::new(Arc::clone(&pool), writer);
+        (repo, pool, temp_dir)
+    }
+
+    fn count_outbox(pool: &Arc<Pool<ConnectionManager<SqliteConnection>>>) -> i64 {
+        let mut conn = get_connection(pool).expect("Failed to get connection");
+        sync_outbox::table
+            .select(count_star())
+            .first::<i64>(&mut conn)
+            .expect("Failed to count outbox rows")
+    }
+
+    fn manual_quote(day: u32, close: Decimal) -> Quote {
+        Quote {
+            id: Uuid::new_v4().to_string(),
+            asset_id: "asset-1".to_string(),
+            timestamp: Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap(),
+            open: close,
+            high: close,
+            low: close,
+            close,
+            adjclose: close,
+            volume: Decimal::ZERO,
+            currency: "USD".to_string(),
+            data_source: DataSource::Manual,
+            created_at: Utc::now(),
+            notes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_insert_records_manual_quotes_in_outbox() {
+        let (repo, pool, _temp_dir) = create_test_repository().await;
+        let before = count_outbox(&pool);
+
+        let quotes = vec![
+            manual_quote(2, Decimal::from(100)),
+            manual_quote(3, Decimal::from(101)),
+        ];
+        let written = repo.bulk_insert_quotes(&quotes, false).await.unwrap();
+        assert_eq!(written, 2);
+        assert_eq!(count_outbox(&pool), before + 2);
+
+        // Re-importing the same days skips them unless overwriting
+        let again = vec![manual_quote(2, Decimal::from(150))];
+        assert_eq!(repo.bulk_insert_quotes(&again, false).await.unwrap(), 0);
+        assert_eq!(count_outbox(&pool), before + 2);
+
+        assert_eq!(repo.bulk_insert_quotes(&again, true).await.unwrap(), 1);
+        assert_eq!(count_outbox(&pool), before + 3);
+        let stored = repo
+            .get_quotes_in_range(
+                "asset-1",
+                NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].id, quotes[0].id);
+        assert_eq!(stored[0].close, Decimal::from(150));
+    }
+}
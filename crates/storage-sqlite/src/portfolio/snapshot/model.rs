@@ -42,6 +42,8 @@ pub struct AccountStateSnapshotDB {
     pub cash_total_base_currency: String,
     #[diesel(sql_type = Text)]
     pub source: String,
+    #[diesel(sql_type = Text)]
+    pub cash_interest_accrued: String,
 }
 
 // Conversion from DB model to Domain model
@@ -76,6 +78,7 @@ impl From<AccountStateSnapshotDB> for AccountStateSnapshot {
             }),
             source: serde_json::from_str(&format!("\"{}\"", db.source))
                 .unwrap_or(SnapshotSource::Calculated),
+            cash_interest_accrued: Decimal::from_str(&db.cash_interest_accrued).unwrap_or_default(),
         }
     }
 }
@@ -117,6 +120,10 @@ impl From<AccountStateSnapshot> for AccountStateSnapshotDB {
                 .unwrap_or_else(|_| "\"CALCULATED\"".to_string())
                 .trim_matches('"')
                 .to_string(),
+            cash_interest_accrued: domain
+                .cash_interest_accrued
+                .round_dp(DECIMAL_PRECISION)
+                .to_string(),
         }
     }
 }
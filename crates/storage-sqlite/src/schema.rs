@@ -134,6 +134,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    audit_log (id) {
+        id -> Text,
+        entity_type -> Text,
+        entity_id -> Text,
+        operation -> Text,
+        source -> Text,
+        device_id -> Nullable<Text>,
+        import_run_id -> Nullable<Text>,
+        restored_from -> Nullable<Text>,
+        changed_fields -> Nullable<Text>,
+        before_json -> Nullable<Text>,
+        after_json -> Nullable<Text>,
+        created_at -> Text,
+    }
+}
+
 diesel::table! {
     brokers_sync_state (account_id, provider) {
         account_id -> Text,
@@ -180,6 +197,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    equity_grants (id) {
+        id -> Text,
+        account_id -> Text,
+        asset_id -> Text,
+        grant_type -> Text,
+        grant_date -> Text,
+        total_shares -> Text,
+        strike_price -> Nullable<Text>,
+        grant_price -> Nullable<Text>,
+        currency -> Text,
+        vesting_start_date -> Text,
+        vesting_months -> Integer,
+        cliff_months -> Integer,
+        vesting_interval_months -> Integer,
+        notes -> Nullable<Text>,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
 diesel::table! {
     goals (id) {
         id -> Text,
@@ -222,6 +260,21 @@ diesel::table! {
         cash_total_account_currency -> Text,
         cash_total_base_currency -> Text,
         source -> Text,
+        cash_interest_accrued -> Text,
+    }
+}
+
+diesel::table! {
+    import_run_changes (id) {
+        id -> Text,
+        import_run_id -> Text,
+        activity_id -> Text,
+        change_type -> Text,
+        before_json -> Nullable<Text>,
+        after_json -> Nullable<Text>,
+        status -> Text,
+        reviewed_at -> Nullable<Text>,
+        created_at -> Text,
     }
 }
 
@@ -369,6 +422,33 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    property_cash_flows (id) {
+        id -> Text,
+        asset_id -> Text,
+        kind -> Text,
+        category -> Nullable<Text>,
+        amount -> Text,
+        currency -> Text,
+        date -> Text,
+        notes -> Nullable<Text>,
+        created_at -> Text,
+    }
+}
+
+diesel::table! {
+    quote_provenance (quote_id) {
+        quote_id -> Text,
+        asset_id -> Text,
+        day -> Text,
+        status -> Text,
+        field_sources -> Text,
+        candidates -> Text,
+        deviation -> Nullable<Text>,
+        created_at -> Text,
+    }
+}
+
 diesel::table! {
     quotes (id) {
         id -> Text,
@@ -426,9 +506,13 @@ diesel::joinable!(ai_thread_tags -> ai_threads (thread_id));
 diesel::joinable!(asset_taxonomy_assignments -> assets (asset_id));
 diesel::joinable!(brokers_sync_state -> accounts (account_id));
 diesel::joinable!(brokers_sync_state -> import_runs (last_run_id));
+diesel::joinable!(equity_grants -> accounts (account_id));
+diesel::joinable!(equity_grants -> assets (asset_id));
 diesel::joinable!(goals_allocation -> accounts (account_id));
 diesel::joinable!(goals_allocation -> goals (goal_id));
+diesel::joinable!(import_run_changes -> import_runs (import_run_id));
 diesel::joinable!(import_runs -> accounts (account_id));
+diesel::joinable!(property_cash_flows -> assets (asset_id));
 diesel::joinable!(quotes -> assets (asset_id));
 diesel::joinable!(taxonomy_categories -> taxonomies (taxonomy_id));
 
@@ -442,16 +526,21 @@ diesel::allow_tables_to_appear_in_same_query!(
     app_settings,
     asset_taxonomy_assignments,
     assets,
+    audit_log,
     brokers_sync_state,
     contribution_limits,
     daily_account_valuation,
+    equity_grants,
     goals,
     goals_allocation,
     health_issue_dismissals,
     holdings_snapshots,
+    import_run_changes,
     import_runs,
     market_data_providers,
     platforms,
+    property_cash_flows,
+    quote_provenance,
     quote_sync_state,
     quotes,
     sync_applied_events,
@@ -121,6 +121,7 @@ impl ReplayStore for SqliteSyncEngineDbPorts {
                             event.client_timestamp,
                             event.seq,
                             event.payload,
+                            Some(event.device_id),
                         )
                     })
                     .collect(),
@@ -139,6 +140,7 @@ impl ReplayStore for SqliteSyncEngineDbPorts {
                 event.client_timestamp,
                 event.seq,
                 event.payload,
+                Some(event.device_id),
             )
             .await
             .map_err(|e| e.to_string())
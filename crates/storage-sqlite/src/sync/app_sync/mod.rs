@@ -13,6 +13,7 @@ pub use model::{
     SyncOutboxEventDB, SyncTableStateDB,
 };
 pub(crate) use outbox_projector::{flush_projected_outbox, ProjectedChange};
+pub(crate) use repository::{resolve_local_device_id, upsert_entity_payload};
 pub use repository::{
     insert_outbox_event, AppSyncRepository, OutboxWriteRequest, SyncLocalDataSummary,
     SyncTableRowCount,
@@ -8,12 +8,14 @@ use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex, OnceLock};
 use uuid::Uuid;
 
+use wealthfolio_core::audit::{AuditContext, AuditEntityType, AuditSource};
 use wealthfolio_core::errors::{DatabaseError, Error, Result};
 use wealthfolio_core::sync::{
     should_apply_lww, SyncEngineStatus, SyncEntity, SyncEntityMetadata, SyncOperation,
     SyncOutboxEvent, SyncOutboxStatus, APP_SYNC_TABLES,
 };
 
+use crate::audit::{record_audit_changes, AuditChange};
 use crate::db::{get_connection, WriteHandle};
 use crate::errors::StorageError;
 use crate::schema::{
@@ -26,6 +28,19 @@ use super::model::{
     SyncOutboxEventDB, SyncTableStateDB,
 };
 
+/// A remote event replayed by `apply_remote_events_lww_batch`: entity, entity id,
+/// operation, event id, client timestamp, seq, payload and origin device.
+pub type RemoteEventRow = (
+    SyncEntity,
+    String,
+    SyncOperation,
+    String,
+    String,
+    i64,
+    serde_json::Value,
+    Option<String>,
+);
+
 fn enum_to_db<T: serde::Serialize>(value: &T) -> Result<String> {
     Ok(serde_json::to_string(value)?.trim_matches('"').to_string())
 }
@@ -418,7 +433,7 @@ fn resolve_payload_key_version(conn: &mut SqliteConnection, requested_version: i
         .max(1))
 }
 
-fn resolve_local_device_id(conn: &mut SqliteConnection) -> Option<String> {
+pub(crate) fn resolve_local_device_id(conn: &mut SqliteConnection) -> Option<String> {
     sync_device_config::table
         .filter(sync_device_config::trust_state.eq("trusted"))
         .select(sync_device_config::device_id)
@@ -502,6 +517,88 @@ fn to_entity_metadata(row: SyncEntityMetadataDB) -> Result<SyncEntityMetadata> {
     })
 }
 
+/// Inserts or replaces a row from a JSON payload of its columns.
+///
+/// Unknown columns are rejected and generated columns skipped.
+fn upsert_payload_row(
+    conn: &mut SqliteConnection,
+    table_name: &str,
+    pk_name: &str,
+    entity_id: &str,
+    payload_json: &serde_json::Value,
+) -> Result<()> {
+    let payload_obj = payload_json.as_object().ok_or_else(|| {
+        Error::Database(DatabaseError::Internal(
+            "Sync payload must be a JSON object".to_string(),
+        ))
+    })?;
+
+    let fields: Vec<(String, serde_json::Value)> = payload_obj
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    let mut fields = normalize_payload_fields(conn, table_name, fields)?;
+    if let Some((_, payload_pk)) = fields.iter().find(|(k, _)| k == pk_name) {
+        if !payload_value_matches_entity_id(payload_pk, entity_id) {
+            return Err(Error::Database(DatabaseError::Internal(format!(
+                "Sync payload PK '{}' does not match entity_id '{}'",
+                pk_name, entity_id
+            ))));
+        }
+    } else {
+        fields.push((
+            pk_name.to_string(),
+            serde_json::Value::String(entity_id.to_string()),
+        ));
+    }
+
+    let columns = fields
+        .iter()
+        .map(|(k, _)| quote_identifier(k))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let values = fields
+        .iter()
+        .map(|(_, v)| json_value_to_sql_literal(v))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let upserts = fields
+        .iter()
+        .map(|(k, _)| {
+            let quoted = quote_identifier(k);
+            format!("{quoted}=excluded.{quoted}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let sql = format!(
+        "INSERT INTO {} ({columns}) VALUES ({values}) \
+         ON CONFLICT({}) DO UPDATE SET {upserts}",
+        quote_identifier(table_name),
+        quote_identifier(pk_name)
+    );
+    diesel::sql_query(sql)
+        .execute(conn)
+        .map_err(StorageError::from)?;
+    Ok(())
+}
+
+/// Writes a synced entity back from its stored payload, inserting it if missing.
+pub(crate) fn upsert_entity_payload(
+    conn: &mut SqliteConnection,
+    entity: &SyncEntity,
+    entity_id: &str,
+    payload_json: &serde_json::Value,
+) -> Result<()> {
+    let (table_name, pk_name) = entity_storage_mapping(entity).ok_or_else(|| {
+        Error::Database(DatabaseError::Internal(format!(
+            "No storage table for sync entity {:?}",
+            entity
+        )))
+    })?;
+    upsert_payload_row(conn, table_name, pk_name, entity_id, payload_json)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn apply_remote_event_lww_tx(
     conn: &mut SqliteConnection,
@@ -512,6 +609,7 @@ fn apply_remote_event_lww_tx(
     client_timestamp_value: String,
     seq_value: i64,
     payload_json: serde_json::Value,
+    device_id: Option<String>,
 ) -> Result<bool> {
     let already_applied = sync_applied_events::table
         .find(&event_id_value)
@@ -556,62 +654,26 @@ fn apply_remote_event_lww_tx(
                         .map_err(StorageError::from)?;
                 }
                 SyncOperation::Create | SyncOperation::Update => {
-                    let payload_obj = payload_json.as_object().ok_or_else(|| {
-                        Error::Database(DatabaseError::Internal(
-                            "Sync payload must be a JSON object".to_string(),
-                        ))
-                    })?;
-
-                    let fields: Vec<(String, serde_json::Value)> = payload_obj
-                        .iter()
-                        .map(|(k, v)| (k.clone(), v.clone()))
-                        .collect();
-                    let mut fields = normalize_payload_fields(conn, table_name, fields)?;
-                    if let Some((_, payload_pk)) = fields.iter().find(|(k, _)| k == pk_name) {
-                        if !payload_value_matches_entity_id(payload_pk, &entity_id_value) {
-                            return Err(Error::Database(DatabaseError::Internal(format!(
-                                "Sync payload PK '{}' does not match entity_id '{}'",
-                                pk_name, entity_id_value
-                            ))));
-                        }
-                    } else {
-                        fields.push((
-                            pk_name.to_string(),
-                            serde_json::Value::String(entity_id_value.clone()),
-                        ));
-                    }
-
-                    let columns = fields
-                        .iter()
-                        .map(|(k, _)| quote_identifier(k))
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                    let values = fields
-                        .iter()
-                        .map(|(_, v)| json_value_to_sql_literal(v))
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                    let upserts = fields
-                        .iter()
-                        .map(|(k, _)| {
-                            let quoted = quote_identifier(k);
-                            format!("{quoted}=excluded.{quoted}")
-                        })
-                        .collect::<Vec<_>>()
-                        .join(", ");
-
-                    let sql = format!(
-                        "INSERT INTO {} ({columns}) VALUES ({values}) \
-                         ON CONFLICT({}) DO UPDATE SET {upserts}",
-                        quote_identifier(table_name),
-                        quote_identifier(pk_name)
-                    );
-                    diesel::sql_query(sql)
-                        .execute(conn)
-                        .map_err(StorageError::from)?;
+                    upsert_payload_row(conn, table_name, pk_name, &entity_id_value, &payload_json)?;
                 }
             }
 
+            // Changes from other devices belong in the local history too
+            if let Some(entity_type) = AuditEntityType::from_sync_entity(entity) {
+                let row = (op != SyncOperation::Delete).then(|| payload_json.clone());
+                record_audit_changes(
+                    conn,
+                    &AuditContext::new(AuditSource::DeviceSync),
+                    device_id,
+                    vec![AuditChange::new(
+                        entity_type,
+                        entity_id_value.clone(),
+                        op,
+                        row,
+                    )],
+                )?;
+            }
+
             let now = Utc::now().to_rfc3339();
             diesel::insert_into(sync_table_state::table)
                 .values(SyncTableStateDB {
@@ -995,6 +1057,7 @@ impl AppSyncRepository {
         client_timestamp_value: String,
         seq_value: i64,
         payload_json: serde_json::Value,
+        device_id: Option<String>,
     ) -> Result<bool> {
         self.writer
             .exec(move |conn| {
@@ -1007,6 +1070,7 @@ impl AppSyncRepository {
                     client_timestamp_value,
                     seq_value,
                     payload_json,
+                    device_id,
                 )
             })
             .await
@@ -1014,15 +1078,7 @@ impl AppSyncRepository {
 
     pub async fn apply_remote_events_lww_batch(
         &self,
-        events: Vec<(
-            SyncEntity,
-            String,
-            SyncOperation,
-            String,
-            String,
-            i64,
-            serde_json::Value,
-        )>,
+        events: Vec<RemoteEventRow>,
     ) -> Result<usize> {
         if events.is_empty() {
             return Ok(0);
@@ -1042,7 +1098,8 @@ impl AppSyncRepository {
 
                 let result = (|| -> Result<usize> {
                     let mut applied = 0usize;
-                    for (entity, entity_id, op, event_id, client_timestamp, seq, payload) in events
+                    for (entity, entity_id, op, event_id, client_timestamp, seq, payload, device_id) in
+                        events
                     {
                         if apply_remote_event_lww_tx(
                             conn,
@@ -1053,6 +1110,7 @@ impl AppSyncRepository {
                             client_timestamp.clone(),
                             seq,
                             payload,
+                            device_id,
                         )
                         .map_err(|err| {
                             Error::Database(DatabaseError::Internal(format!(
@@ -1620,8 +1678,8 @@ mod tests {
 
     use crate::db::{create_pool, get_connection, init, run_migrations, write_actor::spawn_writer};
     use crate::schema::{
-        accounts, activity_import_profiles, assets, goals, platforms, sync_applied_events,
-        sync_entity_metadata, sync_outbox,
+        accounts, activity_import_profiles, assets, audit_log, goals, platforms,
+        sync_applied_events, sync_entity_metadata, sync_outbox,
     };
 
     fn setup_db() -> (
@@ -2233,6 +2291,7 @@ mod tests {
                 serde_json::json!({
                     "id": "different-account-id"
                 }),
+                None,
             )
             .await;
 
@@ -2262,6 +2321,7 @@ mod tests {
                     "website_url": "https://broker.example",
                     "logo_url": "https://broker.example/logo.png"
                 }),
+                None,
             )
             .await
             .expect("apply platform create");
@@ -2284,6 +2344,7 @@ mod tests {
                     "website_url": "https://broker.example/updated",
                     "logo_url": "https://broker.example/logo-v2.png"
                 }),
+                None,
             )
             .await
             .expect("apply platform update");
@@ -2320,6 +2381,7 @@ mod tests {
                     "targetAmount": 50000.0,
                     "isAchieved": true
                 }),
+                None,
             )
             .await
             .expect("apply goal create");
@@ -2357,6 +2419,7 @@ mod tests {
                     "createdAt": "2026-02-19 00:00:00",
                     "updatedAt": "2026-02-19 00:00:00"
                 }),
+                None,
             )
             .await
             .expect("apply import profile create");
@@ -2370,6 +2433,53 @@ mod tests {
         assert_eq!(name_value, "Broker Mapping");
     }
 
+    #[tokio::test]
+    async fn replay_records_origin_device_in_audit_log() {
+        let (pool, writer) = setup_db();
+        let repo = AppSyncRepository::new(pool.clone(), writer);
+
+        let applied = repo
+            .apply_remote_event_lww(
+                SyncEntity::Account,
+                "acc-audit".to_string(),
+                SyncOperation::Create,
+                "evt-account-audit".to_string(),
+                "2026-02-16T00:00:00Z".to_string(),
+                1,
+                serde_json::json!({
+                    "id": "acc-audit",
+                    "name": "Audited Account",
+                    "account_type": "cash",
+                    "group": serde_json::Value::Null,
+                    "currency": "USD",
+                    "is_default": false,
+                    "is_active": true,
+                    "platform_id": serde_json::Value::Null,
+                    "account_number": serde_json::Value::Null,
+                    "meta": serde_json::Value::Null,
+                    "provider": serde_json::Value::Null,
+                    "provider_account_id": serde_json::Value::Null,
+                    "is_archived": false,
+                    "tracking_mode": "portfolio"
+                }),
+                Some("device-remote".to_string()),
+            )
+            .await
+            .expect("apply account create");
+        assert!(applied);
+
+        let mut conn = get_connection(&pool).expect("conn");
+        let entries: Vec<(String, Option<String>)> = audit_log::table
+            .filter(audit_log::entity_id.eq("acc-audit"))
+            .select((audit_log::source, audit_log::device_id))
+            .load(&mut conn)
+            .expect("load audit log");
+        assert_eq!(
+            entries,
+            vec![("DEVICE_SYNC".to_string(), Some("device-remote".to_string()))]
+        );
+    }
+
     #[tokio::test]
     async fn replay_batch_applies_out_of_order_account_and_platform_events() {
         let (pool, writer) = setup_db();
@@ -2400,6 +2510,7 @@ mod tests {
                         "is_archived": false,
                         "tracking_mode": "portfolio"
                     }),
+                    None,
                 ),
                 (
                     SyncEntity::Platform,
@@ -2417,6 +2528,7 @@ mod tests {
                         "website_url": serde_json::Value::Null,
                         "logo_url": serde_json::Value::Null
                     }),
+                    None,
                 ),
             ])
             .await
@@ -2581,6 +2693,7 @@ mod tests {
                     "id": "acc-unknown-col",
                     "nonexistent_column": "value"
                 }),
+                None,
             )
             .await;
 
@@ -2614,6 +2727,7 @@ mod tests {
                     "isAchieved": false,
                     "is_achieved": true
                 }),
+                None,
             )
             .await;
 
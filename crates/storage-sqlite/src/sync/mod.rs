@@ -22,7 +22,7 @@ pub mod broker_ingest {
 }
 
 // Re-export for convenience
-pub(crate) use app_sync::flush_projected_outbox;
+pub(crate) use app_sync::{flush_projected_outbox, resolve_local_device_id, upsert_entity_payload};
 pub use app_sync::{
     insert_outbox_event, AppSyncRepository, OutboxWriteRequest, SqliteSyncEngineDbPorts,
     SyncLocalDataSummary, SyncTableRowCount,
@@ -62,12 +62,17 @@ export const ACTIVITY_SUBTYPES = {
 
   // Interest subtypes
   STAKING_REWARD: 'STAKING_REWARD',
+  AIRDROP: 'AIRDROP',
   LENDING_INTEREST: 'LENDING_INTEREST',
   COUPON: 'COUPON',
 
   // Split subtypes
   REVERSE_SPLIT: 'REVERSE_SPLIT',
 
+  // Crypto subtypes
+  HARD_FORK: 'HARD_FORK',
+  SWAP: 'SWAP',
+
   // Option subtypes
   OPTION_OPEN: 'OPTION_OPEN',
   OPTION_CLOSE: 'OPTION_CLOSE',
@@ -79,6 +84,7 @@ export const ACTIVITY_SUBTYPES = {
   MANAGEMENT_FEE: 'MANAGEMENT_FEE',
   ADR_FEE: 'ADR_FEE',
   INTEREST_CHARGE: 'INTEREST_CHARGE',
+  NETWORK_FEE: 'NETWORK_FEE',
 
   // Tax subtypes
   WITHHOLDING: 'WITHHOLDING',
@@ -92,6 +98,9 @@ export const ACTIVITY_SUBTYPES = {
   REBATE: 'REBATE',
   REVERSAL: 'REVERSAL',
 
+  // Transfer subtypes
+  FX_CONVERSION: 'FX_CONVERSION',
+
   // Liability subtypes
   LIABILITY_INTEREST_ACCRUAL: 'LIABILITY_INTEREST_ACCRUAL',
   LIABILITY_PRINCIPAL_PAYMENT: 'LIABILITY_PRINCIPAL_PAYMENT',